@@ -25,7 +25,6 @@ use std::time::Duration;
 use log::*;
 use reqwest::Url;
 use tari_base_node_client::{
-    grpc::GrpcBaseNodeClient,
     types::{BaseLayerMetadata, BlockInfo},
     BaseNodeClient,
     BaseNodeClientError,
@@ -57,7 +56,7 @@ use tari_dan_common_types::{
 };
 use tari_dan_storage::{
     consensus_models::{BurntUtxo, SubstateRecord},
-    global::{GlobalDb, MetadataKey},
+    global::{DbBaseLayerBlockInfo, GlobalDb, MetadataKey},
     StateStore,
     StorageError,
 };
@@ -72,10 +71,12 @@ use tokio::{task, task::JoinHandle, time};
 use url::ParseError;
 
 const LOG_TARGET: &str = "tari::dan::base_layer_scanner";
+/// The number of scanned blocks that are loaded at a time when searching for the fork point after a reorg
+const FORK_POINT_SEARCH_BATCH_SIZE: usize = 100;
 
-pub fn spawn<TAddr: NodeAddressable + 'static>(
+pub fn spawn<TAddr, TBaseNodeClient>(
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
-    base_node_client: GrpcBaseNodeClient,
+    base_node_client: TBaseNodeClient,
     epoch_manager: EpochManagerHandle<TAddr>,
     shutdown: ShutdownSignal,
     consensus_constants: ConsensusConstants,
@@ -87,7 +88,11 @@ pub fn spawn<TAddr: NodeAddressable + 'static>(
     // TODO: remove when base layer template registration is removed too
     template_manager: TemplateManagerHandle,
     template_sidechain_id: Option<PublicKey>,
-) -> JoinHandle<anyhow::Result<()>> {
+) -> JoinHandle<anyhow::Result<()>>
+where
+    TAddr: NodeAddressable + 'static,
+    TBaseNodeClient: BaseNodeClient + 'static,
+{
     task::spawn(async move {
         let base_layer_scanner = BaseLayerScanner::new(
             global_db,
@@ -109,14 +114,14 @@ pub fn spawn<TAddr: NodeAddressable + 'static>(
     })
 }
 
-pub struct BaseLayerScanner<TAddr> {
+pub struct BaseLayerScanner<TAddr, TBaseNodeClient> {
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
     last_scanned_height: u64,
     last_scanned_tip: Option<FixedHash>,
     last_scanned_hash: Option<FixedHash>,
     last_scanned_validator_node_mr: Option<FixedHash>,
     next_block_hash: Option<FixedHash>,
-    base_node_client: GrpcBaseNodeClient,
+    base_node_client: TBaseNodeClient,
     epoch_manager: EpochManagerHandle<TAddr>,
     shutdown: ShutdownSignal,
     consensus_constants: ConsensusConstants,
@@ -131,10 +136,14 @@ pub struct BaseLayerScanner<TAddr> {
     template_sidechain_id: Option<PublicKey>,
 }
 
-impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
+impl<TAddr, TBaseNodeClient> BaseLayerScanner<TAddr, TBaseNodeClient>
+where
+    TAddr: NodeAddressable + 'static,
+    TBaseNodeClient: BaseNodeClient,
+{
     pub fn new(
         global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
-        base_node_client: GrpcBaseNodeClient,
+        base_node_client: TBaseNodeClient,
         epoch_manager: EpochManagerHandle<TAddr>,
         shutdown: ShutdownSignal,
         consensus_constants: ConsensusConstants,
@@ -228,14 +237,39 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
                 self.sync_blockchain(tip).await?;
             },
             BlockchainProgression::Reorged => {
-                error!(
+                warn!(
                     target: LOG_TARGET,
-                    "⚠️ Base layer reorg detected. Rescanning from genesis."
+                    "⚠️ Base layer reorg detected. Searching for the fork point."
                 );
-                // TODO: we need to figure out where the fork happened, and delete data after the fork.
-                self.last_scanned_hash = None;
-                self.last_scanned_validator_node_mr = None;
-                self.last_scanned_height = 0;
+                match self.find_fork_point().await? {
+                    Some((block, validator_node_mr)) => {
+                        if self.last_scanned_hash == Some(block.hash) {
+                            info!(
+                                target: LOG_TARGET,
+                                "⛓️ Reorg did not affect any scanned blocks. Last scanned block {} at height {} is \
+                                 still on the base layer chain.",
+                                block.hash,
+                                block.height
+                            );
+                            self.set_last_scanned_block(tip.tip_hash, &BlockInfo {
+                                hash: block.hash,
+                                height: block.height,
+                                next_block_hash: None,
+                            })?;
+                        } else {
+                            self.rollback_to_block(block, validator_node_mr).await?;
+                        }
+                    },
+                    None => {
+                        error!(
+                            target: LOG_TARGET,
+                            "⚠️ None of the scanned blocks are on the base layer chain. Rescanning from genesis."
+                        );
+                        self.last_scanned_hash = None;
+                        self.last_scanned_validator_node_mr = None;
+                        self.last_scanned_height = 0;
+                    },
+                }
                 self.sync_blockchain(tip).await?;
             },
             BlockchainProgression::NoProgress => {
@@ -274,6 +308,95 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
         }
     }
 
+    /// Walks back through the scanned blocks, highest first, and returns the first one that is still on the base layer
+    /// chain along with the validator node MR of its header. Returns None if none of the scanned blocks are on the
+    /// chain.
+    async fn find_fork_point(&mut self) -> Result<Option<(DbBaseLayerBlockInfo, FixedHash)>, BaseLayerScannerError> {
+        let mut height = self.last_scanned_height;
+        loop {
+            let blocks = {
+                let mut tx = self.global_db.create_transaction()?;
+                self.global_db
+                    .base_layer(&mut tx)
+                    .get_base_layer_block_infos_descending(height, FORK_POINT_SEARCH_BATCH_SIZE)?
+            };
+            let Some(lowest_height) = blocks.last().map(|b| b.height) else {
+                return Ok(None);
+            };
+
+            for block in blocks {
+                if let Some(header) = self.base_node_client.get_header_by_hash(block.hash).await.optional()? {
+                    return Ok(Some((block, header.validator_node_mr)));
+                }
+                debug!(
+                    target: LOG_TARGET,
+                    "⛓️ Scanned block {} at height {} is no longer on the base layer chain", block.hash, block.height
+                );
+            }
+
+            match lowest_height.checked_sub(1) {
+                Some(h) => height = h,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Reverts the effects of all scanned blocks above the given fork point so that scanning can continue from it
+    async fn rollback_to_block(
+        &mut self,
+        block: DbBaseLayerBlockInfo,
+        validator_node_mr: FixedHash,
+    ) -> Result<(), BaseLayerScannerError> {
+        warn!(
+            target: LOG_TARGET,
+            "⚠️ Base layer forked at block {} (height {}). Rolling back {} scanned block(s).",
+            block.hash,
+            block.height,
+            self.last_scanned_height.saturating_sub(block.height)
+        );
+
+        // Validator node registrations and exits, epochs and committees
+        self.epoch_manager.rollback_to_block(block.height, block.hash).await?;
+        // Validator node changes are fetched per epoch, so the rollback reverts all changes fetched in the fork epoch,
+        // including those from blocks at or below the fork point. Fetch the changes for the fork epoch again from the
+        // new chain.
+        let fork_epoch = self
+            .base_node_client
+            .get_consensus_constants(block.height)
+            .await?
+            .height_to_epoch(block.height);
+        self.update_validators(fork_epoch).await?;
+
+        let mut tx = self.global_db.create_transaction()?;
+        let num_templates = self
+            .global_db
+            .templates(&mut tx)
+            .delete_registered_above_base_layer_height(block.height)?;
+        self.global_db.commit(tx)?;
+
+        // Burnt UTXOs that have already been proposed may have been minted on the sidechain and cannot be reverted
+        let num_burnt_utxos = self
+            .state_store
+            .with_write_tx(|tx| BurntUtxo::delete_all_unproposed_above_base_layer_height(tx, block.height))?;
+
+        info!(
+            target: LOG_TARGET,
+            "⛓️ Rolled back {} template registration(s) and {} burnt UTXO(s). Rescanning from height {}.",
+            num_templates,
+            num_burnt_utxos,
+            block.height
+        );
+
+        self.last_scanned_validator_node_mr = Some(validator_node_mr);
+        self.set_last_scanned_block(block.hash, &BlockInfo {
+            hash: block.hash,
+            height: block.height,
+            next_block_hash: None,
+        })?;
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn sync_blockchain(&mut self, tip: BaseLayerMetadata) -> Result<(), BaseLayerScannerError> {
         let start_scan_height = self.last_scanned_height;
@@ -498,6 +621,7 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
                 ),
                 Some(template_name),
                 epoch,
+                Some(block_info.height),
             )
             .await?;
 
@@ -690,6 +814,8 @@ pub enum BaseLayerScannerError {
     TemplateManagerError(#[from] TemplateManagerError),
     #[error("URL parse error: {0}")]
     UrlParse(#[from] ParseError),
+    #[error("State store error: {0}")]
+    StateStoreError(#[from] StorageError),
 }
 
 enum BlockchainProgression {
@@ -700,3 +826,336 @@ enum BlockchainProgression {
     /// The blockchain has not progressed since the last scan
     NoProgress,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        num::NonZeroU32,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use rand::rngs::OsRng;
+    use serde::Serialize;
+    use tari_base_node_client::types::{BaseLayerConsensusConstants, BaseLayerValidatorNode, SideChainUtxos};
+    use tari_common_types::epoch::VnEpoch;
+    use tari_core::{blocks::BlockHeader, transactions::transaction_components::ValidatorNodeSignature};
+    use tari_crypto::keys::PublicKey as _;
+    use tari_dan_common_types::{
+        layer_one_transaction::LayerOneTransactionDef,
+        NumPreshards,
+        PeerAddress,
+        SubstateAddress,
+    };
+    use tari_dan_storage::global::DbFactory;
+    use tari_dan_storage_sqlite::SqliteDbFactory;
+    use tari_epoch_manager::{
+        base_layer::{spawn_service, EpochManagerConfig},
+        traits::LayerOneTransactionSubmitter,
+    };
+    use tari_shutdown::Shutdown;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    const EPOCH_LENGTH: u64 = 10;
+
+    #[derive(Clone)]
+    struct MockBlock {
+        hash: FixedHash,
+        validator_node_mr: FixedHash,
+        changes: Vec<ValidatorNodeChange>,
+    }
+
+    /// A base node client serving a chain that can be replaced to simulate a reorg
+    #[derive(Clone, Default)]
+    struct MockBaseNodeClient {
+        chain: Arc<Mutex<Vec<MockBlock>>>,
+    }
+
+    impl MockBaseNodeClient {
+        fn set_chain(&self, chain: Vec<MockBlock>) {
+            *self.chain.lock().unwrap() = chain;
+        }
+
+        fn find_block(&self, hash: FixedHash) -> Option<(u64, MockBlock)> {
+            let chain = self.chain.lock().unwrap();
+            chain
+                .iter()
+                .position(|b| b.hash == hash)
+                .map(|height| (height as u64, chain[height].clone()))
+        }
+    }
+
+    #[async_trait]
+    impl BaseNodeClient for MockBaseNodeClient {
+        async fn test_connection(&mut self) -> Result<(), BaseNodeClientError> {
+            Ok(())
+        }
+
+        async fn get_network(&mut self) -> Result<u8, BaseNodeClientError> {
+            Ok(0)
+        }
+
+        async fn get_tip_info(&mut self) -> Result<BaseLayerMetadata, BaseNodeClientError> {
+            let chain = self.chain.lock().unwrap();
+            let tip = chain.last().expect("mock chain is empty");
+            Ok(BaseLayerMetadata {
+                height_of_longest_chain: chain.len() as u64 - 1,
+                tip_hash: tip.hash,
+            })
+        }
+
+        async fn get_validator_node_changes(
+            &mut self,
+            epoch: Epoch,
+            _sidechain_id: Option<&PublicKey>,
+        ) -> Result<Vec<ValidatorNodeChange>, BaseNodeClientError> {
+            let chain = self.chain.lock().unwrap();
+            Ok(chain
+                .iter()
+                .enumerate()
+                .filter(|(height, _)| *height as u64 / EPOCH_LENGTH == epoch.as_u64())
+                .flat_map(|(_, block)| block.changes.clone())
+                .collect())
+        }
+
+        async fn get_validator_nodes(
+            &mut self,
+            _height: u64,
+        ) -> Result<Vec<BaseLayerValidatorNode>, BaseNodeClientError> {
+            unimplemented!("not used by the scanner")
+        }
+
+        async fn get_shard_key(
+            &mut self,
+            _epoch: Epoch,
+            public_key: &PublicKey,
+        ) -> Result<Option<SubstateAddress>, BaseNodeClientError> {
+            let hash = FixedHash::try_from(public_key.as_bytes())?;
+            Ok(Some(SubstateAddress::from_hash_and_version(hash, 0)))
+        }
+
+        async fn get_template_registrations(
+            &mut self,
+            _start_hash: Option<FixedHash>,
+            _count: u64,
+        ) -> Result<Vec<CodeTemplateRegistration>, BaseNodeClientError> {
+            Ok(vec![])
+        }
+
+        async fn get_header_by_hash(&mut self, block_hash: FixedHash) -> Result<BlockHeader, BaseNodeClientError> {
+            let (height, block) = self
+                .find_block(block_hash)
+                .ok_or_else(|| tonic::Status::not_found("header not found"))?;
+            let mut header = BlockHeader::new(0);
+            header.height = height;
+            header.validator_node_mr = block.validator_node_mr;
+            Ok(header)
+        }
+
+        async fn get_consensus_constants(
+            &mut self,
+            _tip: u64,
+        ) -> Result<BaseLayerConsensusConstants, BaseNodeClientError> {
+            Ok(BaseLayerConsensusConstants {
+                epoch_length: EPOCH_LENGTH,
+                validator_node_registration_min_deposit_amount: MicroMinotari::zero(),
+            })
+        }
+
+        async fn get_sidechain_utxos(
+            &mut self,
+            start_hash: Option<FixedHash>,
+            count: u64,
+        ) -> Result<Vec<SideChainUtxos>, BaseNodeClientError> {
+            let chain = self.chain.lock().unwrap();
+            let start = match start_hash {
+                Some(hash) => chain
+                    .iter()
+                    .position(|b| b.hash == hash)
+                    .ok_or_else(|| tonic::Status::not_found("block not found"))?,
+                None => 0,
+            };
+            Ok((start..chain.len())
+                .take(count as usize)
+                .map(|height| SideChainUtxos {
+                    block_info: BlockInfo {
+                        hash: chain[height].hash,
+                        height: height as u64,
+                        next_block_hash: chain.get(height + 1).map(|b| b.hash),
+                    },
+                    outputs: vec![],
+                })
+                .collect())
+        }
+    }
+
+    struct NoopL1Submitter;
+
+    impl LayerOneTransactionSubmitter for NoopL1Submitter {
+        type Error = Infallible;
+
+        async fn submit_transaction<T: Serialize + Send>(
+            &self,
+            _proof: LayerOneTransactionDef<T>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn random_hash() -> FixedHash {
+        FixedHash::from(rand::random::<[u8; 32]>())
+    }
+
+    fn registration(activation_epoch: u64) -> (PublicKey, ValidatorNodeChange) {
+        let (secret, public_key) = PublicKey::random_keypair(&mut OsRng);
+        let claim_public_key = PublicKey::random_keypair(&mut OsRng).1;
+        let signature = ValidatorNodeSignature::sign(&secret, None, &claim_public_key, VnEpoch::zero());
+        let change = ValidatorNodeChange::Add {
+            registration: ValidatorNodeRegistration::new(signature, claim_public_key, VnEpoch(u64::MAX)),
+            activation_epoch: VnEpoch(activation_epoch),
+            minimum_value_promise: MicroMinotari::zero(),
+        };
+        (public_key, change)
+    }
+
+    fn exit(public_key: &PublicKey) -> ValidatorNodeChange {
+        ValidatorNodeChange::Remove {
+            public_key: public_key.clone(),
+        }
+    }
+
+    /// Extends the chain to the given tip height. The validator node MR changes in every block that has changes.
+    fn extend_chain(chain: &mut Vec<MockBlock>, tip_height: u64, changes: Vec<(u64, ValidatorNodeChange)>) {
+        while (chain.len() as u64) <= tip_height {
+            let height = chain.len() as u64;
+            let block_changes = changes
+                .iter()
+                .filter(|(h, _)| *h == height)
+                .map(|(_, change)| change.clone())
+                .collect::<Vec<_>>();
+            let validator_node_mr = match chain.last() {
+                Some(prev) if block_changes.is_empty() => prev.validator_node_mr,
+                _ => random_hash(),
+            };
+            chain.push(MockBlock {
+                hash: random_hash(),
+                validator_node_mr,
+                changes: block_changes,
+            });
+        }
+    }
+
+    fn registered_public_keys(
+        global_db: &GlobalDb<SqliteGlobalDbAdapter<PeerAddress>>,
+        epoch: Epoch,
+    ) -> Vec<PublicKey> {
+        let mut tx = global_db.create_transaction().unwrap();
+        global_db
+            .validator_nodes(&mut tx)
+            .get_all_registered_within_start_epoch(epoch)
+            .unwrap()
+            .into_iter()
+            .map(|vn| vn.public_key)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_reverts_and_refetches_validator_changes_on_a_reorg_within_an_epoch() {
+        let shutdown = Shutdown::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_factory = SqliteDbFactory::<PeerAddress>::new(temp_dir.path().to_path_buf());
+        db_factory.migrate().unwrap();
+        let global_db = db_factory.get_or_create_global_db().unwrap();
+
+        // Registrations activate in the epoch after the one they were mined in
+        let (vn_0, register_vn_0) = registration(1);
+        let (vn_1, register_vn_1) = registration(1);
+        let (vn_below_fork, register_vn_below_fork) = registration(2);
+        let (vn_reorged, register_vn_reorged) = registration(2);
+        let (vn_new, register_vn_new) = registration(3);
+
+        // Chain A: the fork point at height 15 is in the middle of epoch 1, with validator node changes in epoch 1 on
+        // either side of it
+        const FORK_HEIGHT: u64 = 15;
+        let mut chain_a = vec![];
+        extend_chain(&mut chain_a, FORK_HEIGHT, vec![
+            (2, register_vn_0),
+            (2, register_vn_1),
+            (12, register_vn_below_fork),
+            (12, exit(&vn_0)),
+        ]);
+        let mut chain_b = chain_a.clone();
+        extend_chain(&mut chain_a, 24, vec![(17, register_vn_reorged), (17, exit(&vn_1))]);
+        // Chain B has no further validator node changes in epoch 1
+        extend_chain(&mut chain_b, 27, vec![(22, register_vn_new)]);
+
+        let base_node_client = MockBaseNodeClient::default();
+        base_node_client.set_chain(chain_a);
+
+        let consensus_constants = ConsensusConstants {
+            base_layer_confirmations: 0,
+            ..ConsensusConstants::devnet()
+        };
+        let (epoch_manager, _) = spawn_service(
+            EpochManagerConfig {
+                base_layer_confirmations: 0,
+                committee_size: NonZeroU32::new(7).unwrap(),
+                validator_node_sidechain_id: None,
+                num_preshards: NumPreshards::P256,
+            },
+            global_db.clone(),
+            base_node_client.clone(),
+            PublicKey::random_keypair(&mut OsRng).1,
+            NoopL1Submitter,
+            shutdown.to_signal(),
+        );
+        let (template_tx, _template_rx) = mpsc::channel(1);
+        let mut scanner = BaseLayerScanner::new(
+            global_db.clone(),
+            base_node_client.clone(),
+            epoch_manager.clone(),
+            shutdown.to_signal(),
+            consensus_constants,
+            SqliteStateStore::connect(":memory:").unwrap(),
+            true,
+            Duration::from_secs(1),
+            None,
+            None,
+            TemplateManagerHandle::new(template_tx),
+            None,
+        );
+        scanner.load_initial_state().unwrap();
+
+        scanner.scan_blockchain().await.unwrap();
+        assert_eq!(scanner.last_scanned_height, 24);
+        assert_eq!(epoch_manager.current_epoch().await.unwrap(), Epoch(2));
+        let vns = registered_public_keys(&global_db, Epoch(2));
+        assert_eq!(vns.len(), 2);
+        assert!(vns.contains(&vn_below_fork));
+        assert!(vns.contains(&vn_reorged));
+
+        base_node_client.set_chain(chain_b);
+        scanner.scan_blockchain().await.unwrap();
+        assert_eq!(scanner.last_scanned_height, 27);
+        assert_eq!(epoch_manager.current_epoch().await.unwrap(), Epoch(2));
+
+        // The registration and exit below the fork point are kept, the exit above it is reverted
+        let vns = registered_public_keys(&global_db, Epoch(2));
+        assert_eq!(vns.len(), 2);
+        assert!(vns.contains(&vn_1));
+        assert!(vns.contains(&vn_below_fork));
+
+        let vns = registered_public_keys(&global_db, Epoch(3));
+        assert_eq!(vns.len(), 3);
+        assert!(vns.contains(&vn_1));
+        assert!(vns.contains(&vn_below_fork));
+        assert!(vns.contains(&vn_new));
+        assert!(!vns.contains(&vn_0));
+        assert!(!vns.contains(&vn_reorged));
+
+        shutdown.trigger();
+    }
+}
@@ -91,13 +91,19 @@ fn encode_non_fungible_into_json(
     Ok(())
 }
 
+/// Decodes an arbitrary CBOR value (e.g. component state or a typed event payload) into plain JSON
+pub fn encode_cbor_value_into_json(cbor_value: &CborValue) -> Result<json::Value, JsonEncodingError> {
+    let cbor_value = fix_invalid_object_keys(cbor_value);
+    let json_value = serde_json::to_value(cbor_value)?;
+    Ok(json_value)
+}
+
 fn fix_cbor_value_for_json(
     cbor_value: &tari_bor::Value,
     parent_object: &mut JsonObject,
     field_name: &str,
 ) -> Result<(), JsonEncodingError> {
-    let cbor_value = fix_invalid_object_keys(cbor_value);
-    let json_value = serde_json::to_value(cbor_value)?;
+    let json_value = encode_cbor_value_into_json(cbor_value)?;
     parent_object.insert(field_name.to_owned(), json_value);
 
     Ok(())
@@ -13,6 +13,8 @@ pub struct P2pConfig {
     pub enable_mdns: bool,
     pub listener_port: u16,
     pub reachability_mode: ReachabilityMode,
+    /// The zstd level used to compress messages sent to peers that support compression. None disables compression.
+    pub messaging_compression_level: Option<i32>,
 }
 
 impl Default for P2pConfig {
@@ -21,6 +23,7 @@ impl Default for P2pConfig {
             enable_mdns: true,
             listener_port: 0,
             reachability_mode: ReachabilityMode::default(),
+            messaging_compression_level: Some(3),
         }
     }
 }
@@ -93,6 +96,14 @@ impl SubConfigPath for PeerSeedsConfig {
 pub struct RpcConfig {
     pub max_simultaneous_sessions: usize,
     pub max_sessions_per_client: usize,
+    /// The maximum number of requests a client may have in flight on a single multiplexed session
+    pub max_concurrent_requests_per_session: usize,
+    /// The zstd level used to compress payloads on sessions that negotiated compression. None disables compression.
+    pub compression_level: Option<i32>,
+    /// The number of requests per second allowed for each peer across all sessions. None disables the limit.
+    pub peer_rate_limit: Option<u32>,
+    /// The number of requests a peer may make at once before the rate limit applies
+    pub peer_burst: u32,
 }
 
 impl Default for RpcConfig {
@@ -101,6 +112,10 @@ impl Default for RpcConfig {
             // TODO: autofiller uses a lot of sessions, once session management is improved we can reduce these
             max_simultaneous_sessions: 1000,
             max_sessions_per_client: 100,
+            max_concurrent_requests_per_session: 32,
+            compression_level: Some(3),
+            peer_rate_limit: Some(200),
+            peer_burst: 400,
         }
     }
 }
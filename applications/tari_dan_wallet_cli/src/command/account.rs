@@ -88,6 +88,10 @@ pub struct CreateArgs {
     pub fee: Option<u32>,
     #[clap(long, short, alias = "key")]
     pub key_id: Option<u64>,
+    /// A JSON file containing an account policy. If provided, a policy account is created that enforces the policy
+    /// itself.
+    #[clap(long)]
+    pub policy_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -175,6 +179,15 @@ impl AccountsSubcommand {
 }
 
 async fn handle_create(args: CreateArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    let policy = match args.policy_file {
+        Some(policy_file) => {
+            let policy_json =
+                fs::read_to_string(policy_file).map_err(|e| anyhow!("Failed to read policy file: {}", e))?;
+            Some(json::from_str(&policy_json).map_err(|e| anyhow!("Failed to parse policy JSON: {}", e))?)
+        },
+        None => None,
+    };
+
     println!("Submitted new account creation transaction...");
     let resp = client
         .create_account(AccountsCreateRequest {
@@ -183,6 +196,7 @@ async fn handle_create(args: CreateArgs, client: &mut WalletDaemonClient) -> Res
             is_default: args.is_default,
             max_fee: args.fee.map(|u| Amount::new(u.into())),
             key_id: args.key_id,
+            policy,
         })
         .await?;
 
@@ -55,14 +55,19 @@ use tari_template_lib::{
 };
 use tari_transaction::{Transaction, TransactionId, UnsignedTransaction};
 use tari_transaction_manifest::{parse_manifest, ManifestValue};
-use tari_utilities::ByteArray;
+use tari_utilities::{hex::Hex, ByteArray};
 use tari_wallet_daemon_client::{
     types::{
         AccountGetResponse,
+        AccountsBatchTransferRequest,
         AccountsTransferRequest,
+        BatchTransferRecipient,
+        BatchTransferStatus,
         ConfidentialTransferRequest,
         SettingsGetResponse,
+        TransactionApproveRequest,
         TransactionGetResultRequest,
+        TransactionRejectRequest,
         TransactionSubmitDryRunRequest,
         TransactionSubmitRequest,
         TransactionWaitResultRequest,
@@ -81,6 +86,11 @@ pub enum TransactionSubcommand {
     SubmitManifest(SubmitManifestArgs),
     Send(SendArgs),
     ConfidentialTransfer(ConfidentialTransferArgs),
+    BatchTransfer(BatchTransferArgs),
+    /// List transactions submitted by other clients that are waiting for approval
+    ListPending,
+    Approve(ApproveArgs),
+    Reject(RejectArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -113,6 +123,9 @@ pub struct CommonSubmitArgs {
     pub dump_outputs_into: Option<ComponentAddressOrName>,
     #[clap(long)]
     pub dry_run: bool,
+    /// Declare each input as read-only or writable based on a dry run of the transaction
+    #[clap(long)]
+    pub infer_lock_intents: bool,
     #[clap(long)]
     pub max_fee: Option<u64>,
     #[clap(long, short = 'f', alias = "fee-account")]
@@ -134,12 +147,16 @@ pub struct SubmitManifestArgs {
 
 #[derive(Debug, Args, Clone)]
 pub struct SendArgs {
-    amount: u64,
+    /// The amount to send in the smallest unit of the resource, or a decimal amount (e.g. 1.5) if `--decimal` is set
+    amount: String,
     resource_address: ResourceAddress,
     destination_public_key: FromHex<Vec<u8>>,
     #[clap(flatten)]
     common: CommonSubmitArgs,
     source_account_name: Option<ComponentAddressOrName>,
+    /// Interpret the amount as a decimal amount using the divisibility of the resource
+    #[clap(long)]
+    decimal: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -155,6 +172,39 @@ pub struct ConfidentialTransferArgs {
     resource_address: Option<ResourceAddress>,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct BatchTransferArgs {
+    /// A JSON file containing a list of recipients, or a CSV file with the columns
+    /// `destination_public_key,resource_address,amount,non_fungible_ids`. Amounts are in the smallest unit of the
+    /// resource, as for `send` without `--decimal`. Non-fungible ids in a CSV file are separated by `;`.
+    recipients_file: PathBuf,
+    #[clap(long, short = 'a', alias = "account")]
+    source_account: Option<ComponentAddressOrName>,
+    /// The maximum number of recipients paid in a single transaction. Defaults to the wallet daemon's limit.
+    #[clap(long, short = 'c')]
+    max_recipients_per_transaction: Option<u32>,
+    /// The maximum fee paid for each transaction in the batch
+    #[clap(long)]
+    max_fee: Option<u64>,
+    #[clap(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ApproveArgs {
+    /// The ID of the pending transaction
+    id: u64,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct RejectArgs {
+    /// The ID of the pending transaction
+    id: u64,
+    /// The reason returned to the client that submitted the transaction
+    #[clap(long, short = 'r')]
+    reason: Option<String>,
+}
+
 #[derive(Debug, Subcommand, Clone)]
 pub enum CliInstruction {
     CallFunction {
@@ -187,6 +237,25 @@ impl TransactionSubcommand {
             TransactionSubcommand::ConfidentialTransfer(args) => {
                 handle_confidential_transfer(args, &mut client).await?;
             },
+            TransactionSubcommand::BatchTransfer(args) => {
+                handle_batch_transfer(args, &mut client).await?;
+            },
+            TransactionSubcommand::ListPending => handle_list_pending(&mut client).await?,
+            TransactionSubcommand::Approve(args) => {
+                client
+                    .approve_transaction(TransactionApproveRequest { id: args.id })
+                    .await?;
+                println!("✅ Transaction {} approved", args.id);
+            },
+            TransactionSubcommand::Reject(args) => {
+                client
+                    .reject_transaction(TransactionRejectRequest {
+                        id: args.id,
+                        reason: args.reason,
+                    })
+                    .await?;
+                println!("❌ Transaction {} rejected", args.id);
+            },
         }
         Ok(())
     }
@@ -210,6 +279,50 @@ async fn handle_get(args: GetArgs, client: &mut WalletDaemonClient) -> Result<()
     Ok(())
 }
 
+async fn handle_list_pending(client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    let resp = client.list_pending_transactions().await?;
+    if resp.pending.is_empty() {
+        println!("No transactions are waiting for approval");
+        return Ok(());
+    }
+
+    for pending in resp.pending {
+        println!(
+            "⏳ Transaction {} requested by '{}' at {}",
+            pending.id, pending.requested_by, pending.requested_at
+        );
+        println!("Instructions:");
+        for instruction in &pending.instructions {
+            println!("- {}", instruction);
+        }
+        println!("Balance changes:");
+        if pending.balance_changes.is_empty() {
+            println!("  None");
+        }
+        for change in &pending.balance_changes {
+            println!(
+                "- {} {}: {} -> {}",
+                change.vault_address,
+                change
+                    .token_symbol
+                    .clone()
+                    .unwrap_or_else(|| change.resource_address.to_string()),
+                change.balance_before,
+                change.balance_after
+            );
+        }
+        if let Some(fee) = pending.estimated_fee {
+            println!("Estimated fee: {}", fee);
+        }
+        if let Some(err) = pending.dry_run_error {
+            println!("⚠️ Dry run failed: {}", err);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
 pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
     let SubmitArgs { instruction, common } = args;
     let instruction = match instruction {
@@ -290,6 +403,7 @@ pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) ->
             detect_inputs: common.detect_inputs.unwrap_or(true),
             detect_inputs_use_unversioned: true,
             proof_ids: vec![],
+            infer_lock_intents: common.infer_lock_intents,
         };
         let resp = client.submit_transaction(&request).await?;
         wait_transaction_result(resp.transaction_id, client).await?;
@@ -358,6 +472,7 @@ async fn handle_submit_manifest(
             detect_inputs: common.detect_inputs.unwrap_or(true),
             detect_inputs_use_unversioned: true,
             proof_ids: vec![],
+            infer_lock_intents: common.infer_lock_intents,
         };
 
         let resp = client.submit_transaction(&request).await?;
@@ -374,16 +489,27 @@ pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Res
         resource_address,
         destination_public_key,
         common,
+        decimal,
     } = args;
 
     let destination_public_key =
         PublicKey::from_canonical_bytes(&destination_public_key.into_inner()).map_err(anyhow::Error::msg)?;
 
+    let (amount, decimal_amount) = if decimal {
+        (Amount::zero(), Some(amount))
+    } else {
+        let amount = amount
+            .parse::<u64>()
+            .map_err(|e| anyhow!("Invalid amount '{}': {}. Use --decimal for decimal amounts", amount, e))?;
+        (amount.try_into()?, None)
+    };
+
     let fee = common.max_fee.map(|f| f.try_into()).transpose()?;
     let resp = client
         .accounts_transfer(AccountsTransferRequest {
             account: source_account_name,
-            amount: Amount::try_from(amount)?,
+            amount,
+            decimal_amount,
             resource_address,
             destination_public_key,
             max_fee: fee,
@@ -437,6 +563,125 @@ pub async fn handle_confidential_transfer(
     Ok(())
 }
 
+pub async fn handle_batch_transfer(
+    args: BatchTransferArgs,
+    client: &mut WalletDaemonClient,
+) -> Result<(), anyhow::Error> {
+    let BatchTransferArgs {
+        recipients_file,
+        source_account,
+        max_recipients_per_transaction,
+        max_fee,
+        dry_run,
+    } = args;
+
+    let contents = fs::read_to_string(&recipients_file)?;
+    let recipients = if recipients_file.extension().map_or(false, |ext| ext == "json") {
+        serde_json::from_str(&contents)?
+    } else {
+        parse_batch_transfer_csv(&contents)?
+    };
+
+    let resp = client
+        .accounts_batch_transfer(AccountsBatchTransferRequest {
+            account: source_account,
+            recipients,
+            max_fee: max_fee.map(|f| f.try_into()).transpose()?,
+            max_recipients_per_transaction,
+            dry_run,
+        })
+        .await?;
+
+    for tx in &resp.transactions {
+        println!(
+            "Transaction: {} ({} recipient(s), fee: {}) {}",
+            tx.transaction_id,
+            tx.recipients.len(),
+            tx.fee,
+            display_batch_transfer_status(&tx.status)
+        );
+    }
+    println!();
+    for (i, recipient) in resp.recipients.iter().enumerate() {
+        println!(
+            "{}. {} {} {}",
+            i + 1,
+            recipient.destination_public_key,
+            recipient.resource_address,
+            display_batch_transfer_status(&recipient.status)
+        );
+    }
+    println!();
+    let num_succeeded = resp.recipients.iter().filter(|r| r.status.is_success()).count();
+    println!(
+        "{}/{} recipient(s) paid. Total fees: {}",
+        num_succeeded,
+        resp.recipients.len(),
+        resp.total_fees
+    );
+
+    Ok(())
+}
+
+fn display_batch_transfer_status(status: &BatchTransferStatus) -> String {
+    match status {
+        BatchTransferStatus::Succeeded => "✅".to_string(),
+        BatchTransferStatus::Failed { reason } => format!("❌ {}", reason),
+    }
+}
+
+/// Parses the rows `destination_public_key,resource_address,amount,non_fungible_ids` of a batch transfer CSV file. A
+/// header row and empty lines are skipped.
+fn parse_batch_transfer_csv(contents: &str) -> Result<Vec<BatchTransferRecipient>, anyhow::Error> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("destination_public_key"))
+        .map(|(line_no, line)| {
+            let fields = line.split(',').map(|f| f.trim()).collect::<Vec<_>>();
+            if fields.len() < 2 || fields.len() > 4 {
+                return Err(anyhow!(
+                    "Line {}: expected 2 to 4 columns but got {}",
+                    line_no,
+                    fields.len()
+                ));
+            }
+            let destination_public_key = PublicKey::from_hex(fields[0])
+                .map_err(|e| anyhow!("Line {}: invalid destination public key: {}", line_no, e))?;
+            let resource_address = ResourceAddress::from_str(fields[1])
+                .map_err(|e| anyhow!("Line {}: invalid resource address: {}", line_no, e))?;
+            let amount = match fields.get(2).filter(|f| !f.is_empty()) {
+                Some(amount) => amount
+                    .parse::<u64>()
+                    .map_err(|e| anyhow!("Line {}: invalid amount: {}", line_no, e))?
+                    .try_into()?,
+                None => Amount::zero(),
+            };
+            let non_fungible_ids = fields
+                .get(3)
+                .filter(|f| !f.is_empty())
+                .map(|ids| {
+                    ids.split(';')
+                        .map(|id| {
+                            NonFungibleId::try_from_canonical_string(id.trim())
+                                .map_err(|e| anyhow!("Line {}: invalid non-fungible id '{}': {:?}", line_no, id, e))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            Ok(BatchTransferRecipient {
+                destination_public_key,
+                resource_address,
+                amount,
+                non_fungible_ids,
+            })
+        })
+        .collect()
+}
+
 pub async fn wait_transaction_result(
     transaction_id: TransactionId,
     client: &mut WalletDaemonClient,
@@ -507,6 +752,9 @@ fn summarize(result: &FinalizeResult, time_taken: Duration) {
 
     println!();
     println!("Fee: {}", result.fee_receipt.total_fees_charged());
+    if !result.fee_receipt.storage_rebate().is_zero() {
+        println!("Storage rebate: {}", result.fee_receipt.storage_rebate());
+    }
     println!("Time taken: {:?}", time_taken);
     println!();
     println!("OVERALL DECISION: {}", result.result);
@@ -983,3 +1231,63 @@ fn parse_globals(globals: Vec<String>) -> Result<HashMap<String, ManifestValue>,
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use tari_template_lib::constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS;
+
+    use super::*;
+
+    fn public_key_hex() -> String {
+        PublicKey::default().to_hex()
+    }
+
+    #[test]
+    fn it_parses_batch_transfer_csv_rows() {
+        let csv = format!(
+            "destination_public_key,resource_address,amount,non_fungible_ids\n\n{key},{resource},100\n{key},\
+             {resource},,u64_1; str_abc\n{key},{resource}\n",
+            key = public_key_hex(),
+            resource = CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+        );
+        let recipients = parse_batch_transfer_csv(&csv).unwrap();
+        assert_eq!(recipients.len(), 3);
+
+        assert_eq!(recipients[0].destination_public_key, PublicKey::default());
+        assert_eq!(recipients[0].resource_address, CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
+        assert_eq!(recipients[0].amount, Amount(100));
+        assert!(recipients[0].non_fungible_ids.is_empty());
+
+        // A blank amount is zero
+        assert_eq!(recipients[1].amount, Amount::zero());
+        assert_eq!(recipients[1].non_fungible_ids, vec![
+            NonFungibleId::from_u64(1),
+            NonFungibleId::try_from_string("abc").unwrap()
+        ]);
+
+        assert_eq!(recipients[2].amount, Amount::zero());
+        assert!(recipients[2].non_fungible_ids.is_empty());
+    }
+
+    #[test]
+    fn it_rejects_invalid_batch_transfer_csv_rows() {
+        let resource = CONFIDENTIAL_TARI_RESOURCE_ADDRESS;
+        let key = public_key_hex();
+
+        let err = parse_batch_transfer_csv(&format!("{key},{resource},1\nnot_a_key,{resource},1")).unwrap_err();
+        assert!(
+            err.to_string().contains("Line 2: invalid destination public key"),
+            "{}",
+            err
+        );
+
+        let err = parse_batch_transfer_csv(&format!("{key},{resource},1.5")).unwrap_err();
+        assert!(err.to_string().contains("Line 1: invalid amount"), "{}", err);
+
+        let err = parse_batch_transfer_csv(&format!("{key},{resource},,u64_x")).unwrap_err();
+        assert!(err.to_string().contains("Line 1: invalid non-fungible id"), "{}", err);
+
+        let err = parse_batch_transfer_csv(&key).unwrap_err();
+        assert!(err.to_string().contains("expected 2 to 4 columns"), "{}", err);
+    }
+}
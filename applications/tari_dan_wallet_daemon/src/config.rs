@@ -77,6 +77,15 @@ pub struct WalletDaemonConfig {
     /// utility. If this is not set, the value lookup table will be generated on the fly which will have a large
     /// performance cost when brute forcing high-value outputs.
     pub value_lookup_table_file: Option<PathBuf>,
+    /// If true, transactions submitted with a token that does not have the Admin permission are held until they are
+    /// approved or rejected by an admin client.
+    pub require_transaction_approval: bool,
+    /// Transactions submitted with a token that was granted any of these permissions are approved without waiting
+    /// for an admin e.g. "TransactionSend_component_...". Only applies if require_transaction_approval is true.
+    pub auto_approve_permissions: Vec<String>,
+    /// How long a transaction waits for approval before it is rejected
+    #[serde(with = "humantime_serde")]
+    pub transaction_approval_timeout: Duration,
 }
 
 impl Default for WalletDaemonConfig {
@@ -93,6 +102,9 @@ impl Default for WalletDaemonConfig {
             jwt_secret_key: Some(create_secret()),
             http_ui_address: Some("127.0.0.1:5100".parse().unwrap()),
             value_lookup_table_file: None,
+            require_transaction_approval: true,
+            auto_approve_permissions: vec![],
+            transaction_approval_timeout: Duration::from_secs(5 * 60),
         }
     }
 }
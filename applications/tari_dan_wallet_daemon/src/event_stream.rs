@@ -0,0 +1,388 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Extension, Query},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{stream, Stream};
+use log::*;
+use serde::Deserialize;
+use tari_dan_wallet_sdk::apis::jwt::{JrpcPermission, JrpcPermissions};
+use tari_shutdown::ShutdownSignal;
+use tari_wallet_daemon_client::types::WalletEventNotification;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task,
+    time,
+    time::{Interval, MissedTickBehavior},
+};
+
+use crate::{handlers::HandlerContext, notify::Notify, services::WalletEvent};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::event_stream";
+
+/// The number of past events that are kept so that clients can resume after reconnecting
+const HISTORY_SIZE: usize = 1000;
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// How often the token of a connected client is checked for expiry or revocation
+const TOKEN_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Assigns a sequential ID to each [WalletEvent] and keeps the most recent events so that a client that reconnects
+/// can receive the events it missed.
+#[derive(Debug, Clone)]
+pub struct EventHistory {
+    state: Arc<Mutex<HistoryState>>,
+    publisher: broadcast::Sender<(u64, WalletEvent)>,
+}
+
+#[derive(Debug)]
+struct HistoryState {
+    next_id: u64,
+    events: VecDeque<(u64, WalletEvent)>,
+}
+
+pub struct EventSubscription {
+    /// Events after the requested event ID that are still in the history
+    pub missed: VecDeque<(u64, WalletEvent)>,
+    /// True if some events after the requested event ID are no longer in the history
+    pub is_lagged: bool,
+    pub receiver: broadcast::Receiver<(u64, WalletEvent)>,
+}
+
+impl EventHistory {
+    pub fn spawn(notify: &Notify<WalletEvent>, mut shutdown_signal: ShutdownSignal) -> Self {
+        let history = Self::new();
+        let mut events = notify.subscribe();
+        let this = history.clone();
+        task::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(event) => this.push(event),
+                        Err(RecvError::Lagged(n)) => {
+                            warn!(target: LOG_TARGET, "Event history missed {} wallet events", n);
+                        },
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = shutdown_signal.wait() => break,
+                }
+            }
+        });
+
+        history
+    }
+
+    fn new() -> Self {
+        let (publisher, _) = broadcast::channel(HISTORY_SIZE);
+        Self {
+            state: Arc::new(Mutex::new(HistoryState {
+                next_id: 1,
+                events: VecDeque::with_capacity(HISTORY_SIZE),
+            })),
+            publisher,
+        }
+    }
+
+    fn push(&self, event: WalletEvent) {
+        let mut state = self.lock_state();
+        let id = state.next_id;
+        state.next_id += 1;
+        if state.events.len() >= HISTORY_SIZE {
+            state.events.pop_front();
+        }
+        state.events.push_back((id, event.clone()));
+        // Published while holding the lock so that a subscriber never misses an event between the history snapshot and
+        // the live events
+        let _ignore = self.publisher.send((id, event));
+    }
+
+    /// Subscribes to events after `last_event_id`. If `last_event_id` is None, only new events are received.
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> EventSubscription {
+        let state = self.lock_state();
+        let receiver = self.publisher.subscribe();
+        let Some(last_event_id) = last_event_id else {
+            return EventSubscription {
+                missed: VecDeque::new(),
+                is_lagged: false,
+                receiver,
+            };
+        };
+
+        // An ID from the future means that the daemon has restarted since the client last connected
+        let is_unknown_id = last_event_id >= state.next_id;
+        let oldest_id = state.events.front().map_or(state.next_id, |(id, _)| *id);
+        let is_lagged = is_unknown_id || last_event_id.saturating_add(1) < oldest_id;
+        let missed = state
+            .events
+            .iter()
+            .filter(|(id, _)| is_unknown_id || *id > last_event_id)
+            .cloned()
+            .collect();
+
+        EventSubscription {
+            missed,
+            is_lagged,
+            receiver,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, HistoryState> {
+        self.state.lock().expect("event history lock poisoned")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventStreamQuery {
+    /// The ID of the last event the client received. The `Last-Event-ID` header takes precedence.
+    last_event_id: Option<u64>,
+    /// Browsers cannot set headers on an EventSource, so the token may be provided as a query parameter. The daemon
+    /// does not trace the query, but a proxy in front of the daemon may log it.
+    token: Option<String>,
+}
+
+/// Streams wallet events to the client as server-sent events. Clients only receive events that their token
+/// permissions allow them to see.
+pub async fn handler(
+    Extension(context): Extension<Arc<HandlerContext>>,
+    Extension(token): Extension<Option<String>>,
+    Query(query): Query<EventStreamQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, String)> {
+    let token = token.or(query.token);
+    let claims = context
+        .wallet_sdk()
+        .jwt_api()
+        .check_token(token.clone())
+        .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))?;
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .or(query.last_event_id);
+
+    info!(
+        target: LOG_TARGET,
+        "🌐 Event stream opened for '{}' (last event id: {:?})", claims.name, last_event_id
+    );
+
+    let subscription = context.event_history().subscribe(last_event_id);
+    let mut token_check = time::interval(TOKEN_CHECK_INTERVAL);
+    token_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let state = EventStreamState {
+        context,
+        token,
+        permissions: claims.permissions,
+        subscription,
+        token_check,
+    };
+
+    let stream = stream::unfold(state, |mut state| async move {
+        let event = state.next_event().await?;
+        Some((event, state))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
+
+struct EventStreamState {
+    context: Arc<HandlerContext>,
+    token: Option<String>,
+    permissions: JrpcPermissions,
+    subscription: EventSubscription,
+    token_check: Interval,
+}
+
+impl EventStreamState {
+    /// Returns the next event the client is permitted to see, or None if the stream should end
+    async fn next_event(&mut self) -> Option<Result<Event, axum::Error>> {
+        loop {
+            if self.subscription.is_lagged {
+                self.subscription.is_lagged = false;
+                return Some(to_sse_event(None, &WalletEventNotification::Lagged));
+            }
+
+            let (id, event) = match self.subscription.missed.pop_front() {
+                Some(event) => event,
+                None => tokio::select! {
+                    event = self.subscription.receiver.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => {
+                            self.subscription.is_lagged = true;
+                            continue;
+                        },
+                        Err(RecvError::Closed) => return None,
+                    },
+                    _ = self.token_check.tick() => {
+                        if let Err(err) = self.context.wallet_sdk().jwt_api().check_token(self.token.clone()) {
+                            info!(target: LOG_TARGET, "🌐 Closing event stream: {}", err);
+                            return None;
+                        }
+                        continue;
+                    },
+                },
+            };
+
+            if !is_permitted(&self.permissions, &event) {
+                continue;
+            }
+            let Some(notification) = to_notification(event) else {
+                continue;
+            };
+            return Some(to_sse_event(Some(id), &notification));
+        }
+    }
+}
+
+fn is_permitted(permissions: &JrpcPermissions, event: &WalletEvent) -> bool {
+    let has = |permission: JrpcPermission| permissions.check_permission(&permission).is_ok();
+    match event {
+        WalletEvent::TransactionSubmitted(_) |
+        WalletEvent::TransactionFinalized(_) |
+        WalletEvent::TransactionInvalid(_) => has(JrpcPermission::TransactionGet),
+        WalletEvent::AccountCreated(event) => {
+            has(JrpcPermission::AccountInfo) ||
+                has(JrpcPermission::AccountList(None)) ||
+                has(JrpcPermission::AccountBalance(event.account.address.clone()))
+        },
+        WalletEvent::AccountChanged(event) => {
+            has(JrpcPermission::AccountInfo) ||
+                has(JrpcPermission::AccountList(None)) ||
+                has(JrpcPermission::AccountBalance(event.account_address.clone()))
+        },
+        WalletEvent::TransactionApprovalRequested(_) => has(JrpcPermission::Admin),
+        WalletEvent::AuthLoginRequest(_) => false,
+    }
+}
+
+fn to_notification(event: WalletEvent) -> Option<WalletEventNotification> {
+    let notification = match event {
+        WalletEvent::TransactionSubmitted(event) => WalletEventNotification::TransactionSubmitted {
+            transaction_id: event.transaction_id,
+            new_account_name: event.new_account.and_then(|a| a.name),
+        },
+        WalletEvent::TransactionFinalized(event) => WalletEventNotification::TransactionFinalized {
+            transaction_id: event.transaction_id,
+            status: event.status,
+            final_fee: event.final_fee,
+            result: event.finalize,
+        },
+        WalletEvent::TransactionInvalid(event) => WalletEventNotification::TransactionInvalid {
+            transaction_id: event.transaction_id,
+            status: event.status,
+            final_fee: event.final_fee,
+            result: event.finalize,
+        },
+        WalletEvent::AccountCreated(event) => WalletEventNotification::AccountCreated {
+            account: event.account,
+            created_by_tx: event.created_by_tx,
+        },
+        WalletEvent::AccountChanged(event) => WalletEventNotification::AccountChanged {
+            account_address: event.account_address,
+        },
+        WalletEvent::TransactionApprovalRequested(event) => WalletEventNotification::TransactionApprovalRequested {
+            id: event.id,
+            requested_by: event.requested_by,
+        },
+        WalletEvent::AuthLoginRequest(_) => return None,
+    };
+    Some(notification)
+}
+
+fn to_sse_event(id: Option<u64>, notification: &WalletEventNotification) -> Result<Event, axum::Error> {
+    let event = Event::default().event(notification.event_name());
+    // Lagged notifications have no ID so that the client's last event ID is preserved
+    let event = match id {
+        Some(id) => event.id(id.to_string()),
+        None => event,
+    };
+    event.json_data(notification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TransactionApprovalRequestedEvent;
+
+    fn create_history(num_events: u64) -> EventHistory {
+        let history = EventHistory::new();
+        for id in 0..num_events {
+            history.push(
+                TransactionApprovalRequestedEvent {
+                    id,
+                    requested_by: "test".to_string(),
+                }
+                .into(),
+            );
+        }
+        history
+    }
+
+    fn missed_ids(subscription: &EventSubscription) -> Vec<u64> {
+        subscription.missed.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn it_resumes_after_the_last_event_id() {
+        let history = create_history(3);
+
+        let subscription = history.subscribe(Some(1));
+        assert_eq!(missed_ids(&subscription), vec![2, 3]);
+        assert!(!subscription.is_lagged);
+
+        let subscription = history.subscribe(Some(3));
+        assert!(subscription.missed.is_empty());
+        assert!(!subscription.is_lagged);
+
+        let subscription = history.subscribe(None);
+        assert!(subscription.missed.is_empty());
+        assert!(!subscription.is_lagged);
+    }
+
+    #[test]
+    fn it_sends_all_events_and_lagged_for_an_id_from_before_a_restart() {
+        let history = create_history(3);
+        let subscription = history.subscribe(Some(10));
+        assert_eq!(missed_ids(&subscription), vec![1, 2, 3]);
+        assert!(subscription.is_lagged);
+    }
+
+    #[test]
+    fn it_sends_lagged_if_missed_events_were_evicted() {
+        let history = create_history(HISTORY_SIZE as u64 + 5);
+
+        let subscription = history.subscribe(Some(1));
+        assert!(subscription.is_lagged);
+        let missed = missed_ids(&subscription);
+        assert_eq!(missed.len(), HISTORY_SIZE);
+        assert_eq!(missed[0], 6);
+
+        // Event 6 is the oldest event in the history, so nothing was missed
+        let subscription = history.subscribe(Some(5));
+        assert!(!subscription.is_lagged);
+        assert_eq!(subscription.missed.len(), HISTORY_SIZE);
+    }
+
+    #[test]
+    fn it_receives_events_published_after_subscribing() {
+        let history = create_history(1);
+        let mut subscription = history.subscribe(Some(1));
+        history.push(
+            TransactionApprovalRequestedEvent {
+                id: 1,
+                requested_by: "test".to_string(),
+            }
+            .into(),
+        );
+        let (id, _) = subscription.receiver.try_recv().unwrap();
+        assert_eq!(id, 2);
+    }
+}
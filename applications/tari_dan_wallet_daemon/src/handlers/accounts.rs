@@ -123,9 +123,13 @@ pub async fn handle_create(
     );
 
     let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
-    let transaction = transaction_builder(context)
-        .fee_transaction_pay_from_component(default_account.address.as_component_address().unwrap(), max_fee)
-        .create_account(owner_pk.clone())
+    let builder = transaction_builder(context)
+        .fee_transaction_pay_from_component(default_account.address.as_component_address().unwrap(), max_fee);
+    let builder = match req.policy {
+        Some(policy) => builder.create_account_with_policy(owner_pk.clone(), policy),
+        None => builder.create_account(owner_pk.clone()),
+    };
+    let transaction = builder
         .with_inputs(inputs.into_iter().map(|input| input.into_unversioned()))
         .build_and_seal(&signing_key.key);
 
@@ -263,6 +267,7 @@ pub async fn handle_get_balances(
 
     let mut balances = Vec::with_capacity(vaults.len());
     for vault in vaults {
+        let display_balance = vault.format_amount(vault.revealed_balance + vault.confidential_balance);
         balances.push(BalanceEntry {
             vault_address: vault.address,
             resource_address: vault.resource_address,
@@ -270,6 +275,8 @@ pub async fn handle_get_balances(
             resource_type: vault.resource_type,
             confidential_balance: vault.confidential_balance,
             token_symbol: vault.token_symbol,
+            divisibility: vault.divisibility,
+            display_balance,
         })
     }
 
@@ -681,6 +688,7 @@ async fn finish_claiming<T: WalletStore>(
             owner_rule: None,
             access_rules: None,
             workspace_bucket: Some("bucket".to_string()),
+            policy: None,
         });
     }
     instructions.push(Instruction::CallMethod {
@@ -869,6 +877,31 @@ pub async fn handle_transfer(
         resource_substate.address.substate_id().clone(),
         Some(resource_substate.address.version()),
     );
+    let amount = match req.decimal_amount {
+        Some(ref decimal_amount) => {
+            if !req.amount.is_zero() {
+                return Err(invalid_params(
+                    "decimal_amount",
+                    Some("amount and decimal_amount cannot both be provided"),
+                ));
+            }
+            let resource = resource_substate
+                .substate
+                .as_resource()
+                .ok_or_else(|| anyhow!("Substate {} is not a resource", resource_substate.address))?;
+            resource.parse_amount(decimal_amount).ok_or_else(|| {
+                invalid_params(
+                    "decimal_amount",
+                    Some(format!(
+                        "Invalid amount '{}' for resource with divisibility {}",
+                        decimal_amount,
+                        resource.divisibility()
+                    )),
+                )
+            })?
+        },
+        None => req.amount,
+    };
     inputs.insert(resource_substate.address.into());
 
     let mut instructions = vec![];
@@ -890,6 +923,7 @@ pub async fn handle_transfer(
             owner_rule: None,
             access_rules: None,
             workspace_bucket: None,
+            policy: None,
         });
     }
 
@@ -910,7 +944,7 @@ pub async fn handle_transfer(
         Instruction::CallMethod {
             component_address: source_account_address,
             method: "withdraw".to_string(),
-            args: args![req.resource_address, req.amount],
+            args: args![req.resource_address, amount],
         },
         Instruction::PutLastInstructionOutputOnWorkspace {
             key: b"bucket".to_vec(),
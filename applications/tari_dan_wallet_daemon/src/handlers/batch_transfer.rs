@@ -0,0 +1,583 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::anyhow;
+use log::*;
+use tari_dan_common_types::{optional::Optional, SubstateRequirement};
+use tari_dan_wallet_sdk::{
+    apis::{
+        confidential_transfer::{ConfidentialTransferInputSelection, WithdrawProofParams},
+        jwt::JrpcPermission,
+        key_manager,
+        substate::ValidatorScanResult,
+    },
+    models::{Account, ConfidentialProofId},
+    DanWalletSdk,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::{
+    component::new_component_address_from_public_key,
+    instruction::Instruction,
+    resource::Resource,
+    substate::SubstateId,
+};
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_template_lib::{
+    args,
+    args::Arg,
+    models::{Amount, ComponentAddress, NonFungibleAddress, ResourceAddress},
+    prelude::ResourceType,
+};
+use tari_transaction::{Transaction, TransactionId};
+use tari_wallet_daemon_client::types::{
+    AccountsBatchTransferRequest,
+    AccountsBatchTransferResponse,
+    BatchTransferRecipient,
+    BatchTransferRecipientResult,
+    BatchTransferStatus,
+    BatchTransferTransaction,
+};
+
+use super::context::HandlerContext;
+use crate::{
+    handlers::helpers::{get_account_or_default, invalid_params, transaction_builder, wait_for_result},
+    indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
+    DEFAULT_FEE,
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::batch_transfer";
+
+/// The default maximum number of recipients paid in a single transaction
+const DEFAULT_MAX_RECIPIENTS_PER_TRANSACTION: usize = 50;
+
+type WalletSdk = DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>;
+
+/// Pays many recipients from one account. Recipients are split into chunks of at most
+/// `max_recipients_per_transaction`, and each chunk is submitted as a single transaction that creates any missing
+/// destination accounts and deposits all of the chunk's transfers. Chunks are submitted one after the other so that
+/// later chunks see the accounts created by earlier ones. A failed chunk does not stop the remaining chunks from being
+/// submitted.
+pub async fn handle_batch_transfer(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsBatchTransferRequest,
+) -> Result<AccountsBatchTransferResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    if req.recipients.is_empty() {
+        return Err(invalid_params("recipients", Some("At least one recipient is required")));
+    }
+    let chunk_size = req
+        .max_recipients_per_transaction
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_MAX_RECIPIENTS_PER_TRANSACTION);
+    if chunk_size == 0 {
+        return Err(invalid_params(
+            "max_recipients_per_transaction",
+            Some("Must be greater than zero"),
+        ));
+    }
+    let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
+
+    let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    let resources = fetch_resources(&sdk, &req.recipients).await?;
+    for (index, recipient) in req.recipients.iter().enumerate() {
+        validate_recipient(index, recipient, &resources[&recipient.resource_address])?;
+    }
+
+    let mut transactions = Vec::new();
+    let mut statuses = vec![None; req.recipients.len()];
+    let mut total_fees = Amount::zero();
+
+    for recipients in chunk_recipients(&req.recipients, chunk_size) {
+        let chunk = recipients.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        let (transaction_id, fee, status) =
+            match submit_chunk(context, &sdk, &account, &recipients, &resources, max_fee, req.dry_run).await {
+                Ok((transaction_id, fee, status)) => (Some(transaction_id), fee, status),
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Batch transfer chunk of {} recipient(s) failed: {}",
+                        chunk.len(),
+                        err
+                    );
+                    (None, Amount::zero(), BatchTransferStatus::Failed {
+                        reason: err.to_string(),
+                    })
+                },
+            };
+
+        total_fees += fee;
+        for i in &chunk {
+            statuses[*i] = Some((transaction_id, status.clone()));
+        }
+        if let Some(transaction_id) = transaction_id {
+            transactions.push(BatchTransferTransaction {
+                transaction_id,
+                fee,
+                recipients: chunk.iter().map(|i| *i as u32).collect(),
+                status,
+            });
+        }
+    }
+
+    let recipients = req
+        .recipients
+        .into_iter()
+        .zip(statuses)
+        .map(|(recipient, status)| {
+            let (transaction_id, status) = status.expect("BUG: every recipient is in a chunk");
+            BatchTransferRecipientResult {
+                destination_public_key: recipient.destination_public_key,
+                resource_address: recipient.resource_address,
+                transaction_id,
+                status,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    info!(
+        target: LOG_TARGET,
+        "Batch transfer: {}/{} recipient(s) paid in {} transaction(s). Fees: {}",
+        recipients.iter().filter(|r| r.status.is_success()).count(),
+        recipients.len(),
+        transactions.len(),
+        total_fees
+    );
+
+    Ok(AccountsBatchTransferResponse {
+        transactions,
+        recipients,
+        total_fees,
+    })
+}
+
+/// Splits the recipients into chunks of at most `chunk_size`, keeping the index of each recipient in the request
+fn chunk_recipients(
+    recipients: &[BatchTransferRecipient],
+    chunk_size: usize,
+) -> Vec<Vec<(usize, &BatchTransferRecipient)>> {
+    let indexed = recipients.iter().enumerate().collect::<Vec<_>>();
+    indexed.chunks(chunk_size).map(|chunk| chunk.to_vec()).collect()
+}
+
+async fn fetch_resources(
+    sdk: &WalletSdk,
+    recipients: &[BatchTransferRecipient],
+) -> Result<HashMap<ResourceAddress, (SubstateRequirement, Resource)>, anyhow::Error> {
+    let mut resources = HashMap::new();
+    for recipient in recipients {
+        if resources.contains_key(&recipient.resource_address) {
+            continue;
+        }
+        let ValidatorScanResult { address, substate, .. } = sdk
+            .substate_api()
+            .scan_for_substate(&SubstateId::Resource(recipient.resource_address), None)
+            .await?;
+        let resource = substate
+            .into_resource()
+            .ok_or_else(|| anyhow!("Substate {} is not a resource", address))?;
+        resources.insert(recipient.resource_address, (address.into(), resource));
+    }
+    Ok(resources)
+}
+
+fn validate_recipient(
+    index: usize,
+    recipient: &BatchTransferRecipient,
+    (_, resource): &(SubstateRequirement, Resource),
+) -> Result<(), anyhow::Error> {
+    let err = |reason: String| invalid_params("recipients", Some(format!("Recipient {index}: {reason}")));
+    if recipient.amount.is_negative() {
+        return Err(err("amount must not be negative".to_string()));
+    }
+    match resource.resource_type() {
+        ResourceType::NonFungible => {
+            if recipient.non_fungible_ids.is_empty() || !recipient.amount.is_zero() {
+                return Err(err(format!(
+                    "resource {} is non-fungible, non_fungible_ids must be provided instead of an amount",
+                    recipient.resource_address
+                )));
+            }
+        },
+        ResourceType::Fungible | ResourceType::Confidential => {
+            if recipient.amount.is_zero() || !recipient.non_fungible_ids.is_empty() {
+                return Err(err(format!(
+                    "resource {} is {}, a non-zero amount must be provided instead of non_fungible_ids",
+                    recipient.resource_address,
+                    resource.resource_type()
+                )));
+            }
+        },
+    }
+    Ok(())
+}
+
+async fn submit_chunk(
+    context: &HandlerContext,
+    sdk: &WalletSdk,
+    account: &Account,
+    recipients: &[(usize, &BatchTransferRecipient)],
+    resources: &HashMap<ResourceAddress, (SubstateRequirement, Resource)>,
+    max_fee: Amount,
+    dry_run: bool,
+) -> Result<(TransactionId, Amount, BatchTransferStatus), anyhow::Error> {
+    let mut proof_ids = Vec::new();
+    let result = build_chunk_transaction(context, sdk, account, recipients, resources, max_fee, &mut proof_ids).await;
+    let transaction = release_proofs_on_error(sdk, &proof_ids, result)?;
+    let transaction_id = *transaction.id();
+
+    if dry_run {
+        let result = context
+            .transaction_service()
+            .submit_dry_run_transaction(transaction, vec![])
+            .await;
+        // Nothing is spent in a dry run, so any confidential outputs locked for the proofs can be used again
+        release_proofs(sdk, &proof_ids);
+        let finalize = result?.finalize;
+        let status = match finalize.full_reject() {
+            Some(reason) => BatchTransferStatus::Failed {
+                reason: reason.to_string(),
+            },
+            None => BatchTransferStatus::Succeeded,
+        };
+        return Ok((transaction_id, finalize.fee_receipt.total_fees_paid, status));
+    }
+
+    let mut events = context.notifier().subscribe();
+    let result = async {
+        let outputs_api = sdk.confidential_outputs_api();
+        for proof_id in &proof_ids {
+            outputs_api.proofs_set_transaction_hash(*proof_id, transaction_id)?;
+        }
+        context
+            .transaction_service()
+            .submit_transaction(transaction, vec![])
+            .await?;
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    release_proofs_on_error(sdk, &proof_ids, result)?;
+
+    let finalized = match wait_for_result(&mut events, transaction_id).await {
+        Ok(finalized) => finalized,
+        Err(err) => {
+            return Ok((transaction_id, Amount::zero(), BatchTransferStatus::Failed {
+                reason: err.to_string(),
+            }))
+        },
+    };
+    let status = match finalized.finalize.full_reject() {
+        Some(reason) => BatchTransferStatus::Failed {
+            reason: reason.to_string(),
+        },
+        None => BatchTransferStatus::Succeeded,
+    };
+    Ok((transaction_id, finalized.final_fee, status))
+}
+
+async fn build_chunk_transaction(
+    context: &HandlerContext,
+    sdk: &WalletSdk,
+    account: &Account,
+    recipients: &[(usize, &BatchTransferRecipient)],
+    resources: &HashMap<ResourceAddress, (SubstateRequirement, Resource)>,
+    max_fee: Amount,
+    proof_ids: &mut Vec<ConfidentialProofId>,
+) -> Result<Transaction, anyhow::Error> {
+    let source_account_address = account
+        .address
+        .as_component_address()
+        .ok_or_else(|| anyhow!("Invalid account address"))?;
+
+    // Add all versioned account child addresses as inputs
+    let mut inputs = sdk.substate_api().load_dependent_substates(&[&account.address])?;
+    let mut instructions = Vec::new();
+
+    // Create any destination accounts that do not exist yet
+    let mut destinations = HashSet::new();
+    for (_, recipient) in recipients {
+        let destination =
+            new_component_address_from_public_key(&ACCOUNT_TEMPLATE_ADDRESS, &recipient.destination_public_key);
+        if !destinations.insert(destination) {
+            continue;
+        }
+        let existing_account = sdk
+            .substate_api()
+            .scan_for_substate(&SubstateId::Component(destination), None)
+            .await
+            .optional()?;
+        match existing_account {
+            Some(ValidatorScanResult { address, .. }) => {
+                inputs.insert(address.into());
+            },
+            None => instructions.push(Instruction::CreateAccount {
+                public_key_address: recipient.destination_public_key.clone(),
+                owner_rule: None,
+                access_rules: None,
+                workspace_bucket: None,
+                policy: None,
+            }),
+        }
+    }
+
+    for (index, recipient) in recipients {
+        let (resource_requirement, resource) = &resources[&recipient.resource_address];
+        inputs.insert(resource_requirement.clone());
+
+        let withdraw = match resource.resource_type() {
+            ResourceType::Fungible => withdraw_instruction(source_account_address, "withdraw", args![
+                recipient.resource_address,
+                recipient.amount
+            ]),
+            ResourceType::NonFungible => {
+                inputs.extend(recipient.non_fungible_ids.iter().map(|id| {
+                    SubstateRequirement::unversioned(NonFungibleAddress::new(recipient.resource_address, id.clone()))
+                }));
+                withdraw_instruction(source_account_address, "withdraw_many_non_fungibles", args![
+                    recipient.resource_address,
+                    recipient.non_fungible_ids
+                ])
+            },
+            ResourceType::Confidential => {
+                let (proof, proof_id) = sdk
+                    .confidential_transfer_api()
+                    .create_withdraw_proof(WithdrawProofParams {
+                        from_account: source_account_address,
+                        resource_address: recipient.resource_address,
+                        resource_view_key: resource.view_key().cloned(),
+                        destination_public_key: &recipient.destination_public_key,
+                        amount: recipient.amount,
+                        output_to_revealed: false,
+                        input_selection: ConfidentialTransferInputSelection::PreferConfidential,
+                    })?;
+                proof_ids.push(proof_id);
+                withdraw_instruction(source_account_address, "withdraw_confidential", args![
+                    recipient.resource_address,
+                    proof
+                ])
+            },
+        };
+
+        let bucket_key = format!("bucket_{}", index);
+        instructions.extend([
+            withdraw,
+            Instruction::PutLastInstructionOutputOnWorkspace {
+                key: bucket_key.clone().into_bytes(),
+            },
+            Instruction::CallMethod {
+                component_address: new_component_address_from_public_key(
+                    &ACCOUNT_TEMPLATE_ADDRESS,
+                    &recipient.destination_public_key,
+                ),
+                method: "deposit".to_string(),
+                args: args![Workspace(bucket_key)],
+            },
+        ]);
+    }
+
+    let account_secret_key = sdk
+        .key_manager_api()
+        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+
+    let transaction = transaction_builder(context)
+        .with_fee_instructions(vec![Instruction::CallMethod {
+            component_address: source_account_address,
+            method: "pay_fee".to_string(),
+            args: args![max_fee],
+        }])
+        .with_instructions(instructions)
+        .with_inputs(inputs.into_iter().map(|req| req.into_unversioned()))
+        .build_and_seal(&account_secret_key.key);
+
+    Ok(transaction)
+}
+
+fn withdraw_instruction(account: ComponentAddress, method: &str, args: Vec<Arg>) -> Instruction {
+    Instruction::CallMethod {
+        component_address: account,
+        method: method.to_string(),
+        args,
+    }
+}
+
+/// Releases the confidential outputs and revealed funds locked for the proofs if the result is an error, so that they
+/// can be spent by later transactions
+fn release_proofs_on_error<T>(
+    sdk: &WalletSdk,
+    proof_ids: &[ConfidentialProofId],
+    result: Result<T, anyhow::Error>,
+) -> Result<T, anyhow::Error> {
+    if result.is_err() {
+        release_proofs(sdk, proof_ids);
+    }
+    result
+}
+
+fn release_proofs(sdk: &WalletSdk, proof_ids: &[ConfidentialProofId]) {
+    let outputs_api = sdk.confidential_outputs_api();
+    for proof_id in proof_ids {
+        if let Err(err) = outputs_api.release_revealed_funds(*proof_id) {
+            error!(target: LOG_TARGET, "Failed to release revealed funds for proof {}: {}", proof_id, err);
+        }
+        if let Err(err) = outputs_api.release_proof_outputs(*proof_id) {
+            error!(target: LOG_TARGET, "Failed to release outputs for proof {}: {}", proof_id, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tari_common::configuration::Network;
+    use tari_common_types::types::PublicKey;
+    use tari_crypto::commitment::HomomorphicCommitmentFactory;
+    use tari_dan_wallet_sdk::{
+        models::{ConfidentialOutputModel, OutputStatus},
+        storage::{WalletStore, WalletStoreReader},
+        WalletSdkConfig,
+    };
+    use tari_engine_types::confidential::get_commitment_factory;
+    use tari_template_lib::{constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS, models::EncryptedData};
+
+    use super::*;
+
+    fn recipient(amount: u64) -> BatchTransferRecipient {
+        BatchTransferRecipient {
+            destination_public_key: PublicKey::default(),
+            resource_address: CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+            amount: Amount::try_from(amount).unwrap(),
+            non_fungible_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn it_chunks_recipients_in_order() {
+        let recipients = (1..=5).map(recipient).collect::<Vec<_>>();
+        let chunks = chunk_recipients(&recipients, 2)
+            .into_iter()
+            .map(|chunk| {
+                chunk
+                    .into_iter()
+                    .map(|(i, r)| (i, r.amount.as_u64_checked().unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, vec![vec![(0, 1), (1, 2)], vec![(2, 3), (3, 4)], vec![(4, 5)]]);
+
+        assert_eq!(chunk_recipients(&recipients, 10).len(), 1);
+    }
+
+    struct TestWallet {
+        sdk: WalletSdk,
+        store: SqliteWalletStore,
+        _temp: tempfile::TempDir,
+    }
+
+    impl TestWallet {
+        fn new() -> Self {
+            let temp = tempfile::tempdir().unwrap();
+            let store = SqliteWalletStore::try_open(temp.path().join("data/wallet.sqlite")).unwrap();
+            store.run_migrations().unwrap();
+            let sdk = DanWalletSdk::initialize(
+                Network::LocalNet,
+                store.clone(),
+                // Never connected to
+                IndexerJsonRpcNetworkInterface::new("http://127.0.0.1:1"),
+                WalletSdkConfig {
+                    password: None,
+                    jwt_expiry: Duration::from_secs(60),
+                    jwt_secret_key: "secret_key".to_string(),
+                },
+            )
+            .unwrap();
+
+            let accounts_api = sdk.accounts_api();
+            accounts_api
+                .add_account(Some("test"), &Self::account_address(), 0, true)
+                .unwrap();
+            accounts_api
+                .add_vault(
+                    Self::account_address(),
+                    Self::vault_address(),
+                    CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                    ResourceType::Confidential,
+                    Some("TEST".to_string()),
+                    0,
+                )
+                .unwrap();
+            sdk.confidential_outputs_api()
+                .add_output(ConfidentialOutputModel {
+                    account_address: Self::account_address(),
+                    vault_address: Self::vault_address(),
+                    commitment: get_commitment_factory().commit_value(&Default::default(), 100),
+                    value: 100,
+                    sender_public_nonce: None,
+                    encryption_secret_key_index: 0,
+                    encrypted_data: EncryptedData::try_from(vec![0; EncryptedData::min_size()]).unwrap(),
+                    public_asset_tag: None,
+                    status: OutputStatus::Unspent,
+                    locked_by_proof: None,
+                })
+                .unwrap();
+
+            Self {
+                sdk,
+                store,
+                _temp: temp,
+            }
+        }
+
+        fn account_address() -> SubstateId {
+            "component_0dc41b5cc74b36d696c7b140323a40a2f98b71df5d60e5a6bf4c1a07ffffffff"
+                .parse()
+                .unwrap()
+        }
+
+        fn vault_address() -> SubstateId {
+            "vault_0dc41b5cc74b36d696c7b140323a40a2f98b71df5d60e5a6bf4c1a07ffffffff"
+                .parse()
+                .unwrap()
+        }
+
+        fn lock_outputs(&self) -> ConfidentialProofId {
+            let outputs_api = self.sdk.confidential_outputs_api();
+            let proof_id = outputs_api.add_proof(&Self::vault_address()).unwrap();
+            outputs_api
+                .lock_outputs_by_amount(&Self::vault_address(), Amount(50), proof_id)
+                .unwrap();
+            proof_id
+        }
+
+        fn num_locked_outputs(&self, proof_id: ConfidentialProofId) -> usize {
+            self.store
+                .with_read_tx(|tx| tx.outputs_get_locked_by_proof(proof_id))
+                .unwrap()
+                .len()
+        }
+    }
+
+    #[test]
+    fn it_releases_locked_outputs_when_the_chunk_fails() {
+        let wallet = TestWallet::new();
+        let proof_id = wallet.lock_outputs();
+        assert_eq!(wallet.num_locked_outputs(proof_id), 1);
+
+        let result = release_proofs_on_error::<()>(&wallet.sdk, &[proof_id], Err(anyhow!("submit failed")));
+        assert!(result.is_err());
+        assert_eq!(wallet.num_locked_outputs(proof_id), 0);
+    }
+
+    #[test]
+    fn it_keeps_locked_outputs_when_the_chunk_is_submitted() {
+        let wallet = TestWallet::new();
+        let proof_id = wallet.lock_outputs();
+
+        release_proofs_on_error(&wallet.sdk, &[proof_id], Ok(())).unwrap();
+        assert_eq!(wallet.num_locked_outputs(proof_id), 1);
+    }
+}
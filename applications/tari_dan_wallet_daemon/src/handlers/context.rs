@@ -6,15 +6,19 @@ use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 
 use crate::{
     config::WalletDaemonConfig,
+    event_stream::EventHistory,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     notify::Notify,
     services::{AccountMonitorHandle, TransactionServiceHandle, WalletEvent},
+    transaction_approvals::TransactionApprovals,
 };
 
 #[derive(Debug, Clone)]
 pub struct HandlerContext {
     wallet_sdk: DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
     notifier: Notify<WalletEvent>,
+    event_history: EventHistory,
+    transaction_approvals: TransactionApprovals,
     transaction_service: TransactionServiceHandle,
     account_monitor: AccountMonitorHandle,
     config: WalletDaemonConfig,
@@ -24,6 +28,8 @@ impl HandlerContext {
     pub fn new(
         wallet_sdk: DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
         notifier: Notify<WalletEvent>,
+        event_history: EventHistory,
+        transaction_approvals: TransactionApprovals,
         transaction_service: TransactionServiceHandle,
         account_monitor: AccountMonitorHandle,
         config: WalletDaemonConfig,
@@ -31,6 +37,8 @@ impl HandlerContext {
         Self {
             wallet_sdk,
             notifier,
+            event_history,
+            transaction_approvals,
             transaction_service,
             account_monitor,
             config,
@@ -41,6 +49,14 @@ impl HandlerContext {
         &self.notifier
     }
 
+    pub fn event_history(&self) -> &EventHistory {
+        &self.event_history
+    }
+
+    pub fn transaction_approvals(&self) -> &TransactionApprovals {
+        &self.transaction_approvals
+    }
+
     pub fn wallet_sdk(&self) -> &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface> {
         &self.wallet_sdk
     }
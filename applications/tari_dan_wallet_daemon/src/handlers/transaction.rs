@@ -1,27 +1,41 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use anyhow::anyhow;
 use axum_jrpc::error::{JsonRpcError, JsonRpcErrorReason};
+use chrono::Utc;
 use futures::{future, future::Either};
 use log::*;
 use tari_dan_app_utilities::json_encoding;
-use tari_dan_common_types::{optional::Optional, Epoch};
-use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_dan_common_types::{optional::Optional, Epoch, SubstateRequirement};
+use tari_dan_wallet_sdk::{
+    apis::{jwt::JrpcPermission, key_manager},
+    network::WalletNetworkInterface,
+};
+use tari_engine_types::{commit_result::FinalizeResult, indexed_value::IndexedWellKnownTypes, substate::SubstateId};
 use tari_template_lib::{args, models::Amount};
+use tari_transaction::{infer_input_lock_intents, Transaction, UnsignedTransaction};
 use tari_wallet_daemon_client::types::{
     AccountGetRequest,
     AccountGetResponse,
+    BalanceChange,
     CallInstructionRequest,
+    PendingTransactionApproval,
     PublishTemplateRequest,
     PublishTemplateResponse,
+    TransactionApproveRequest,
+    TransactionApproveResponse,
     TransactionGetAllRequest,
     TransactionGetAllResponse,
     TransactionGetRequest,
     TransactionGetResponse,
     TransactionGetResultRequest,
     TransactionGetResultResponse,
+    TransactionListPendingRequest,
+    TransactionListPendingResponse,
+    TransactionRejectRequest,
+    TransactionRejectResponse,
     TransactionSubmitDryRunRequest,
     TransactionSubmitDryRunResponse,
     TransactionSubmitRequest,
@@ -34,10 +48,12 @@ use tokio::time;
 use super::{accounts, context::HandlerContext};
 use crate::{
     handlers::{
-        helpers::{get_account_or_default, transaction_builder},
+        helpers::{application, get_account_or_default, not_found, transaction_builder},
         HandlerError,
     },
-    services::WalletEvent,
+    jrpc_server::ApplicationErrorCode,
+    services::{TransactionApprovalRequestedEvent, WalletEvent},
+    transaction_approvals::ApprovalDecision,
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::transaction";
@@ -86,6 +102,7 @@ pub async fn handle_submit_instruction(
         detect_inputs: req.override_inputs.unwrap_or_default(),
         detect_inputs_use_unversioned: true,
         proof_ids: vec![],
+        infer_lock_intents: false,
     };
     handle_submit(context, token, request).await
 }
@@ -97,8 +114,10 @@ pub async fn handle_submit(
 ) -> Result<TransactionSubmitResponse, anyhow::Error> {
     let sdk = context.wallet_sdk();
     // TODO: fine-grained checks of individual addresses involved (resources, components, etc)
-    sdk.jwt_api()
-        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let claims = sdk.jwt_api().check_token(token)?;
+    claims
+        .permissions
+        .check_permission(&JrpcPermission::TransactionSend(None))?;
     let key_api = sdk.key_manager_api();
     // Fetch the key to sign the transaction
     // TODO: Ideally the SDK should take care of signing the transaction internally
@@ -140,9 +159,38 @@ pub async fn handle_submit(
         req.detect_inputs_use_unversioned,
     );
 
+    let inferred_inputs = if req.infer_lock_intents {
+        let dry_run_transaction = transaction_builder(context)
+            .with_unsigned_transaction(req.transaction.clone())
+            .with_inputs(detected_inputs.clone())
+            .build_and_seal(&key.key);
+        let exec_result = context
+            .transaction_service()
+            .submit_dry_run_transaction(dry_run_transaction, autofill_inputs.clone())
+            .await?;
+        let inputs = req
+            .transaction
+            .inputs()
+            .iter()
+            .cloned()
+            .chain(detected_inputs.iter().cloned())
+            .chain(autofill_inputs.iter().cloned());
+        let inferred = infer_input_lock_intents(inputs, &exec_result.finalize);
+        info!(
+            target: LOG_TARGET,
+            "Inferred lock intents for {} input(s) ({} read-only)",
+            inferred.len(),
+            inferred.iter().filter(|i| i.is_read_only()).count(),
+        );
+        inferred.into_iter().collect()
+    } else {
+        vec![]
+    };
+
     let transaction = transaction_builder(context)
         .with_unsigned_transaction(req.transaction)
         .with_inputs(detected_inputs)
+        .with_replaced_inputs(inferred_inputs)
         .build_and_seal(&key.key);
 
     if log_enabled!(log::Level::Debug) {
@@ -151,6 +199,13 @@ pub async fn handle_submit(
         }
     }
 
+    if context
+        .transaction_approvals()
+        .is_approval_required(&claims.permissions)
+    {
+        wait_for_approval(context, claims.name, &transaction, &autofill_inputs).await?;
+    }
+
     for proof_id in req.proof_ids {
         // update the proofs table with the corresponding transaction hash
         sdk.confidential_outputs_api()
@@ -171,6 +226,205 @@ pub async fn handle_submit(
     Ok(TransactionSubmitResponse { transaction_id })
 }
 
+/// Parks the transaction until an admin approves or rejects it. The given transaction is exactly the transaction that
+/// is submitted once approved. It is dry run so that the admin can see how it affects the balances of this wallet.
+async fn wait_for_approval(
+    context: &HandlerContext,
+    requested_by: String,
+    transaction: &Transaction,
+    autofill_inputs: &[SubstateRequirement],
+) -> Result<(), anyhow::Error> {
+    let instructions = transaction
+        .fee_instructions()
+        .iter()
+        .chain(transaction.instructions())
+        .map(|instruction| instruction.to_string())
+        .collect();
+
+    let mut approval = PendingTransactionApproval {
+        // Assigned by the approval queue
+        id: 0,
+        requested_by: requested_by.clone(),
+        requested_at: Utc::now().naive_utc(),
+        transaction: UnsignedTransaction::from(transaction.unsealed_transaction().unsigned_transaction().clone()),
+        instructions,
+        balance_changes: vec![],
+        estimated_fee: None,
+        dry_run_error: None,
+    };
+    match context
+        .transaction_service()
+        .submit_dry_run_transaction(transaction.clone(), autofill_inputs.to_vec())
+        .await
+    {
+        Ok(exec_result) => {
+            let finalize = &exec_result.finalize;
+            if let Some(reason) = finalize.full_reject() {
+                approval.dry_run_error = Some(reason.to_string());
+            } else {
+                approval.estimated_fee = Some(finalize.fee_receipt.total_fees_charged());
+                approval.balance_changes = get_balance_changes(context, finalize).await?;
+                if let Some(reason) = finalize.reject() {
+                    approval.dry_run_error = Some(reason.to_string());
+                }
+            }
+        },
+        Err(err) => {
+            approval.dry_run_error = Some(err.to_string());
+        },
+    }
+
+    let pending = context.transaction_approvals().request(approval);
+    info!(
+        target: LOG_TARGET,
+        "Transaction submitted by '{}' is waiting for approval (id: {})",
+        requested_by,
+        pending.id()
+    );
+    context.notifier().notify(TransactionApprovalRequestedEvent {
+        id: pending.id(),
+        requested_by,
+    });
+
+    let decision = pending
+        .wait()
+        .await
+        .map_err(|e| application(ApplicationErrorCode::TransactionRejected, e))?;
+    match decision {
+        ApprovalDecision::Approved => Ok(()),
+        ApprovalDecision::Rejected { reason } => Err(application(
+            ApplicationErrorCode::TransactionRejected,
+            format!(
+                "Transaction was rejected by the wallet{}",
+                reason.map(|r| format!(": {}", r)).unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Returns the changes to the revealed balances of this wallet's vaults in the given result, including vaults created
+/// in this wallet's accounts. The balance before the transaction is read from the vault version consumed by the dry run
+/// rather than from the local database, which may be stale. Confidential balances are not included because the wallet
+/// cannot determine the value of the new commitments before they are received.
+async fn get_balance_changes(
+    context: &HandlerContext,
+    finalize: &FinalizeResult,
+) -> Result<Vec<BalanceChange>, anyhow::Error> {
+    let Some(diff) = finalize.accept() else {
+        return Ok(vec![]);
+    };
+    let sdk = context.wallet_sdk();
+    let accounts_api = sdk.accounts_api();
+
+    // Vaults that are referenced by this wallet's accounts after the transaction, including new vaults
+    let mut account_vaults = HashMap::new();
+    for (substate_id, substate) in diff.up_iter() {
+        let Some(component) = substate.substate_value().component() else {
+            continue;
+        };
+        if !accounts_api.exists_by_address(substate_id)? {
+            continue;
+        }
+        let indexed = IndexedWellKnownTypes::from_value(component.state())?;
+        for vault_id in indexed.vault_ids() {
+            account_vaults.insert(SubstateId::from(*vault_id), substate_id.clone());
+        }
+    }
+
+    let mut changes = vec![];
+    for (substate_id, substate) in diff.up_iter() {
+        let Some(vault) = substate.substate_value().vault() else {
+            continue;
+        };
+        let known_vault = accounts_api.get_vault(&substate_id).optional()?;
+        let account_address = match (&known_vault, account_vaults.get(substate_id)) {
+            (Some(known_vault), _) => known_vault.account_address.clone(),
+            (None, Some(account_address)) => account_address.clone(),
+            (None, None) => continue,
+        };
+
+        let input_version = diff
+            .down_iter()
+            .find(|(id, _)| id == substate_id)
+            .map(|(_, version)| *version);
+        let balance_before = match input_version {
+            Some(version) => {
+                let input = sdk
+                    .get_network_interface()
+                    .query_substate(substate_id, Some(version), false)
+                    .await?;
+                input
+                    .substate
+                    .vault()
+                    .map(|v| v.balance())
+                    .ok_or_else(|| anyhow!("Input substate {} is not a vault", substate_id))?
+            },
+            // The vault is created by this transaction
+            None => Amount::zero(),
+        };
+        if balance_before == vault.balance() {
+            continue;
+        }
+        changes.push(BalanceChange {
+            account_address,
+            vault_address: substate_id.clone(),
+            resource_address: *vault.resource_address(),
+            token_symbol: known_vault.and_then(|v| v.token_symbol),
+            balance_before,
+            balance_after: vault.balance(),
+        });
+    }
+    Ok(changes)
+}
+
+pub async fn handle_list_pending(
+    context: &HandlerContext,
+    token: Option<String>,
+    _req: TransactionListPendingRequest,
+) -> Result<TransactionListPendingResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::Admin])?;
+    Ok(TransactionListPendingResponse {
+        pending: context.transaction_approvals().get_all_pending(),
+    })
+}
+
+pub async fn handle_approve(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionApproveRequest,
+) -> Result<TransactionApproveResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::Admin])?;
+    context
+        .transaction_approvals()
+        .approve(req.id)
+        .map_err(|e| not_found(e.to_string()))?;
+    info!(target: LOG_TARGET, "Pending transaction {} approved", req.id);
+    Ok(TransactionApproveResponse {})
+}
+
+pub async fn handle_reject(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionRejectRequest,
+) -> Result<TransactionRejectResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::Admin])?;
+    context
+        .transaction_approvals()
+        .reject(req.id, req.reason)
+        .map_err(|e| not_found(e.to_string()))?;
+    info!(target: LOG_TARGET, "Pending transaction {} rejected", req.id);
+    Ok(TransactionRejectResponse {})
+}
+
 pub async fn handle_submit_dry_run(
     context: &HandlerContext,
     token: Option<String>,
@@ -209,6 +463,12 @@ pub async fn handle_submit_dry_run(
         .with_unsigned_transaction(req.transaction)
         .with_inputs(detected_inputs)
         .build_and_seal(&key.key);
+    let inputs = transaction
+        .inputs()
+        .iter()
+        .cloned()
+        .chain(autofill_inputs.clone())
+        .collect::<Vec<_>>();
 
     for proof_id in req.proof_ids {
         // update the proofs table with the corresponding transaction hash
@@ -227,11 +487,15 @@ pub async fn handle_submit_dry_run(
         .await?;
 
     let json_result = json_encoding::encode_finalize_result_into_json(&exec_result.finalize)?;
+    let inferred_inputs = infer_input_lock_intents(inputs, &exec_result.finalize)
+        .into_iter()
+        .collect();
 
     Ok(TransactionSubmitDryRunResponse {
         transaction_id: exec_result.finalize.transaction_hash.into_array().into(),
         result: exec_result,
         json_result,
+        inferred_inputs,
     })
 }
 
@@ -441,6 +705,7 @@ pub async fn handle_publish_template(
         detect_inputs: req.detect_inputs,
         detect_inputs_use_unversioned: true,
         proof_ids: vec![],
+        infer_lock_intents: false,
     };
     let resp = handle_submit(context, token, request).await?;
     Ok(PublishTemplateResponse {
@@ -8,7 +8,7 @@ use axum::{
     http::{Request, StatusCode},
     middleware::Next,
     response::Response,
-    routing::post,
+    routing::{get, post},
     Router,
 };
 use axum_jrpc::{
@@ -27,18 +27,22 @@ use tokio::task;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 use super::handlers::{substates, templates, HandlerContext};
-use crate::handlers::{
-    accounts,
-    confidential,
-    error::HandlerError,
-    keys,
-    nfts,
-    rpc,
-    settings,
-    transaction,
-    validator,
-    webrtc,
-    Handler,
+use crate::{
+    event_stream,
+    handlers::{
+        accounts,
+        batch_transfer,
+        confidential,
+        error::HandlerError,
+        keys,
+        nfts,
+        rpc,
+        settings,
+        transaction,
+        validator,
+        webrtc,
+        Handler,
+    },
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::json_rpc";
@@ -67,8 +71,12 @@ pub fn spawn_listener(
     let router = Router::new()
         .route("/", post(handler))
         .route("/json_rpc", post(handler))
+        .route("/events", get(event_stream::handler))
         // TODO: Get these traces to work
-        .layer(TraceLayer::new_for_http())
+        // The query is not traced because the event stream accepts the auth token as a query parameter
+        .layer(TraceLayer::new_for_http().make_span_with(|request: &Request<_>| {
+            tracing::debug_span!("request", method = %request.method(), path = %request.uri().path())
+        }))
         .layer(Extension(Arc::new(context)))
         .layer(Extension((preferred_address,signaling_server_address)))
         .layer(Extension(Arc::new(shutdown_signal.clone())))
@@ -131,6 +139,9 @@ async fn handler(
             "get_result" => call_handler(context, value, token, transaction::handle_get_result).await,
             "wait_result" => call_handler(context, value, token, transaction::handle_wait_result).await,
             "get_all" => call_handler(context, value, token, transaction::handle_get_all).await,
+            "list_pending" => call_handler(context, value, token, transaction::handle_list_pending).await,
+            "approve" => call_handler(context, value, token, transaction::handle_approve).await,
+            "reject" => call_handler(context, value, token, transaction::handle_reject).await,
             _ => Ok(value.method_not_found(&value.method)),
         },
         Some(("accounts", method)) => match method {
@@ -143,6 +154,7 @@ async fn handler(
             "get" => call_handler(context, value, token, accounts::handle_get).await,
             "get_default" => call_handler(context, value, token, accounts::handle_get_default).await,
             "transfer" => call_handler(context, value, token, accounts::handle_transfer).await,
+            "batch_transfer" => call_handler(context, value, token, batch_transfer::handle_batch_transfer).await,
             "confidential_transfer" => {
                 call_handler(context, value, token, accounts::handle_confidential_transfer).await
             },
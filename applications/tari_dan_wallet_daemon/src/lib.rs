@@ -22,12 +22,14 @@
 
 pub mod cli;
 pub mod config;
+mod event_stream;
 mod handlers;
 mod http_ui;
 pub mod indexer_jrpc_impl;
 mod jrpc_server;
 mod notify;
 mod services;
+mod transaction_approvals;
 mod webrtc;
 
 use std::{fs, panic, process};
@@ -49,11 +51,13 @@ use tokio::task;
 
 use crate::{
     config::ApplicationConfig,
+    event_stream::EventHistory,
     handlers::HandlerContext,
     http_ui::server::run_http_ui_server,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     notify::Notify,
     services::spawn_services,
+    transaction_approvals::TransactionApprovals,
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon";
@@ -74,6 +78,8 @@ pub async fn run_tari_dan_wallet_daemon(
         .key_manager_api()
         .get_or_create_initial(key_manager::TRANSACTION_BRANCH)?;
     let notify = Notify::new(100);
+    let event_history = EventHistory::spawn(&notify, shutdown_signal.clone());
+    let transaction_approvals = TransactionApprovals::new(&config.dan_wallet_daemon)?;
 
     let services = spawn_services(shutdown_signal.clone(), notify.clone(), wallet_sdk.clone());
 
@@ -82,6 +88,8 @@ pub async fn run_tari_dan_wallet_daemon(
     let handlers = HandlerContext::new(
         wallet_sdk.clone(),
         notify,
+        event_history,
+        transaction_approvals,
         services.transaction_service_handle.clone(),
         services.account_monitor_handle.clone(),
         config.dan_wallet_daemon.clone(),
@@ -284,6 +284,7 @@ where
                 *vault.resource_address(),
                 vault.resource_type(),
                 token_symbol,
+                resource.divisibility(),
             )?;
             has_changed = true;
         }
@@ -519,7 +520,10 @@ where
             },
         };
 
-        let token_symbol = maybe_resource.and_then(|r| r.metadata().get(TOKEN_SYMBOL).map(|s| s.to_string()));
+        let token_symbol = maybe_resource
+            .as_ref()
+            .and_then(|r| r.metadata().get(TOKEN_SYMBOL).map(|s| s.to_string()));
+        let divisibility = maybe_resource.as_ref().map(|r| r.divisibility()).unwrap_or_default();
         info!(
             target: LOG_TARGET,
             "👁️‍🗨️ New {} in account {}",
@@ -532,6 +536,7 @@ where
             *vault.resource_address(),
             vault.resource_type(),
             token_symbol,
+            divisibility,
         )?;
 
         Ok(())
@@ -552,7 +557,10 @@ where
             WalletEvent::TransactionInvalid(event) => {
                 self.pending_accounts.remove(&event.transaction_id);
             },
-            WalletEvent::AccountCreated(_) | WalletEvent::AccountChanged(_) | WalletEvent::AuthLoginRequest(_) => {},
+            WalletEvent::AccountCreated(_) |
+            WalletEvent::AccountChanged(_) |
+            WalletEvent::TransactionApprovalRequested(_) |
+            WalletEvent::AuthLoginRequest(_) => {},
         }
         Ok(())
     }
@@ -13,6 +13,7 @@ pub enum WalletEvent {
     TransactionInvalid(TransactionInvalidEvent),
     AccountCreated(AccountCreatedEvent),
     AccountChanged(AccountChangedEvent),
+    TransactionApprovalRequested(TransactionApprovalRequestedEvent),
     AuthLoginRequest(#[allow(dead_code)] AuthLoginRequestEvent),
 }
 
@@ -46,6 +47,12 @@ impl From<AuthLoginRequestEvent> for WalletEvent {
     }
 }
 
+impl From<TransactionApprovalRequestedEvent> for WalletEvent {
+    fn from(value: TransactionApprovalRequestedEvent) -> Self {
+        Self::TransactionApprovalRequested(value)
+    }
+}
+
 impl From<AccountCreatedEvent> for WalletEvent {
     fn from(value: AccountCreatedEvent) -> Self {
         Self::AccountCreated(value)
@@ -70,7 +77,6 @@ pub struct TransactionFinalizedEvent {
 #[derive(Debug, Clone)]
 pub struct AccountCreatedEvent {
     pub account: Account,
-    #[allow(dead_code)]
     pub created_by_tx: TransactionId,
 }
 
@@ -89,3 +95,9 @@ pub struct TransactionInvalidEvent {
 
 #[derive(Debug, Clone)]
 pub struct AuthLoginRequestEvent;
+
+#[derive(Debug, Clone)]
+pub struct TransactionApprovalRequestedEvent {
+    pub id: u64,
+    pub requested_by: String,
+}
@@ -305,6 +305,7 @@ where
             WalletEvent::TransactionInvalid(_) |
             WalletEvent::TransactionFinalized(_) |
             WalletEvent::AccountChanged(_) |
+            WalletEvent::TransactionApprovalRequested(_) |
             WalletEvent::AuthLoginRequest(_) |
             WalletEvent::AccountCreated(_) => {},
         }
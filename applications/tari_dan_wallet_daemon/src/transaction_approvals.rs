@@ -0,0 +1,272 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tari_dan_wallet_sdk::apis::jwt::{InvalidJrpcPermissionsFormat, JrpcPermission, JrpcPermissions};
+use tari_wallet_daemon_client::types::PendingTransactionApproval;
+use tokio::{sync::oneshot, time};
+
+use crate::config::WalletDaemonConfig;
+
+/// Holds transactions submitted by non-admin clients until an admin approves or rejects them
+#[derive(Debug, Clone)]
+pub struct TransactionApprovals {
+    state: Arc<Mutex<ApprovalsState>>,
+    is_enabled: bool,
+    auto_approve_permissions: Vec<JrpcPermission>,
+    timeout: Duration,
+}
+
+#[derive(Debug)]
+struct ApprovalsState {
+    next_id: u64,
+    pending: BTreeMap<u64, PendingEntry>,
+}
+
+#[derive(Debug)]
+struct PendingEntry {
+    approval: PendingTransactionApproval,
+    reply: oneshot::Sender<ApprovalDecision>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ApprovalDecision {
+    Approved,
+    Rejected { reason: Option<String> },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionApprovalError {
+    #[error("No pending transaction with id {id}")]
+    NotFound { id: u64 },
+    #[error("Timed out after {timeout:.0?} waiting for transaction approval")]
+    TimedOut { timeout: Duration },
+}
+
+impl TransactionApprovals {
+    pub fn new(config: &WalletDaemonConfig) -> Result<Self, InvalidJrpcPermissionsFormat> {
+        let auto_approve_permissions = config
+            .auto_approve_permissions
+            .iter()
+            .map(|p| p.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            state: Arc::new(Mutex::new(ApprovalsState {
+                next_id: 1,
+                pending: BTreeMap::new(),
+            })),
+            is_enabled: config.require_transaction_approval,
+            auto_approve_permissions,
+            timeout: config.transaction_approval_timeout,
+        })
+    }
+
+    /// Returns true if a transaction submitted with the given permissions must be approved before it is sent
+    pub fn is_approval_required(&self, permissions: &JrpcPermissions) -> bool {
+        if !self.is_enabled || permissions.0.contains(&JrpcPermission::Admin) {
+            return false;
+        }
+        !self
+            .auto_approve_permissions
+            .iter()
+            .any(|permission| permissions.0.contains(permission))
+    }
+
+    /// Adds a transaction to the queue. The `id` of the given approval is replaced with the ID assigned by the queue.
+    /// The transaction is removed from the queue when the returned [PendingApproval] is dropped.
+    pub fn request(&self, mut approval: PendingTransactionApproval) -> PendingApproval {
+        let (reply, receiver) = oneshot::channel();
+        let mut state = self.lock_state();
+        let id = state.next_id;
+        state.next_id += 1;
+        approval.id = id;
+        state.pending.insert(id, PendingEntry { approval, reply });
+
+        PendingApproval {
+            id,
+            receiver,
+            timeout: self.timeout,
+            approvals: self.clone(),
+        }
+    }
+
+    pub fn get_all_pending(&self) -> Vec<PendingTransactionApproval> {
+        self.lock_state()
+            .pending
+            .values()
+            .map(|entry| entry.approval.clone())
+            .collect()
+    }
+
+    pub fn approve(&self, id: u64) -> Result<(), TransactionApprovalError> {
+        self.decide(id, ApprovalDecision::Approved)
+    }
+
+    pub fn reject(&self, id: u64, reason: Option<String>) -> Result<(), TransactionApprovalError> {
+        self.decide(id, ApprovalDecision::Rejected { reason })
+    }
+
+    fn decide(&self, id: u64, decision: ApprovalDecision) -> Result<(), TransactionApprovalError> {
+        let entry = self
+            .lock_state()
+            .pending
+            .remove(&id)
+            .ok_or(TransactionApprovalError::NotFound { id })?;
+        // The entry is removed when the submitter stops waiting, so this can only fail if it stopped waiting after the
+        // entry was removed above
+        entry
+            .reply
+            .send(decision)
+            .map_err(|_| TransactionApprovalError::NotFound { id })
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ApprovalsState> {
+        self.state.lock().expect("transaction approvals lock poisoned")
+    }
+}
+
+pub struct PendingApproval {
+    id: u64,
+    receiver: oneshot::Receiver<ApprovalDecision>,
+    timeout: Duration,
+    approvals: TransactionApprovals,
+}
+
+impl PendingApproval {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Waits for an admin to approve or reject the transaction
+    pub async fn wait(mut self) -> Result<ApprovalDecision, TransactionApprovalError> {
+        match time::timeout(self.timeout, &mut self.receiver).await {
+            Ok(Ok(decision)) => Ok(decision),
+            // The sender is never dropped without sending while the entry is pending
+            Ok(Err(_)) => Err(TransactionApprovalError::NotFound { id: self.id }),
+            Err(_) => Err(TransactionApprovalError::TimedOut { timeout: self.timeout }),
+        }
+    }
+}
+
+impl Drop for PendingApproval {
+    fn drop(&mut self) {
+        self.approvals.lock_state().pending.remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_approvals(require_approval: bool, auto_approve_permissions: &[&str]) -> TransactionApprovals {
+        let config = WalletDaemonConfig {
+            require_transaction_approval: require_approval,
+            auto_approve_permissions: auto_approve_permissions.iter().map(|p| p.to_string()).collect(),
+            transaction_approval_timeout: Duration::from_secs(5),
+            ..Default::default()
+        };
+        TransactionApprovals::new(&config).unwrap()
+    }
+
+    fn permissions(s: &str) -> JrpcPermissions {
+        s.parse().unwrap()
+    }
+
+    fn pending_approval() -> PendingTransactionApproval {
+        PendingTransactionApproval {
+            id: 0,
+            requested_by: "test".to_string(),
+            requested_at: Default::default(),
+            transaction: Default::default(),
+            instructions: vec![],
+            balance_changes: vec![],
+            estimated_fee: None,
+            dry_run_error: None,
+        }
+    }
+
+    #[test]
+    fn it_requires_approval_for_non_admin_tokens() {
+        let approvals = create_approvals(true, &["TransactionGet"]);
+        assert!(approvals.is_approval_required(&permissions("TransactionSend")));
+        assert!(!approvals.is_approval_required(&permissions("Admin")));
+        assert!(!approvals.is_approval_required(&permissions("TransactionSend,TransactionGet")));
+
+        let approvals = create_approvals(false, &[]);
+        assert!(!approvals.is_approval_required(&permissions("TransactionSend")));
+    }
+
+    #[test]
+    fn it_rejects_invalid_auto_approve_permissions() {
+        let config = WalletDaemonConfig {
+            auto_approve_permissions: vec!["NotAPermission".to_string()],
+            ..Default::default()
+        };
+        TransactionApprovals::new(&config).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn it_delivers_the_decision_to_the_submitter() {
+        let approvals = create_approvals(true, &[]);
+        let approved = approvals.request(pending_approval());
+        let rejected = approvals.request(pending_approval());
+        assert_ne!(approved.id(), rejected.id());
+        let pending_ids = approvals.get_all_pending().iter().map(|a| a.id).collect::<Vec<_>>();
+        assert_eq!(pending_ids, vec![approved.id(), rejected.id()]);
+
+        approvals.approve(approved.id()).unwrap();
+        approvals.reject(rejected.id(), Some("no".to_string())).unwrap();
+        assert!(approvals.get_all_pending().is_empty());
+
+        assert!(matches!(approved.wait().await.unwrap(), ApprovalDecision::Approved));
+        let decision = rejected.wait().await.unwrap();
+        assert!(matches!(decision, ApprovalDecision::Rejected { reason: Some(reason) } if reason == "no"));
+    }
+
+    #[test]
+    fn it_returns_not_found_for_unknown_ids() {
+        let approvals = create_approvals(true, &[]);
+        let pending = approvals.request(pending_approval());
+        approvals.approve(pending.id()).unwrap();
+        let err = approvals.approve(pending.id()).unwrap_err();
+        assert!(matches!(err, TransactionApprovalError::NotFound { .. }));
+        let err = approvals.reject(123, None).unwrap_err();
+        assert!(matches!(err, TransactionApprovalError::NotFound { id: 123 }));
+    }
+
+    #[tokio::test]
+    async fn it_times_out_and_removes_the_transaction() {
+        let config = WalletDaemonConfig {
+            transaction_approval_timeout: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let approvals = TransactionApprovals::new(&config).unwrap();
+        let pending = approvals.request(pending_approval());
+        let id = pending.id();
+
+        let err = pending.wait().await.unwrap_err();
+        assert!(matches!(err, TransactionApprovalError::TimedOut { .. }));
+        assert!(approvals.get_all_pending().is_empty());
+        assert!(approvals.approve(id).is_err());
+    }
+
+    #[test]
+    fn it_removes_the_transaction_when_the_submitter_stops_waiting() {
+        let approvals = create_approvals(true, &[]);
+        let pending = approvals.request(pending_approval());
+        let id = pending.id();
+        assert_eq!(approvals.get_all_pending().len(), 1);
+
+        drop(pending);
+        assert!(approvals.get_all_pending().is_empty());
+        assert!(matches!(
+            approvals.approve(id).unwrap_err(),
+            TransactionApprovalError::NotFound { .. }
+        ));
+    }
+}
@@ -79,6 +79,7 @@ export const useAccountsCreate = (
         max_fee: fee,
         is_default,
         key_id: null,
+        policy: null,
       });
     },
     {
@@ -111,6 +112,7 @@ export const useAccountsTransfer = (params: TransferParams) => {
       let transferRequest = {
         account: params.account,
         amount: params.amount,
+        decimal_amount: null,
         resource_address: params.resource_address,
         destination_public_key: params.destination_public_key,
         max_fee: params.max_fee,
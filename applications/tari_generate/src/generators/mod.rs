@@ -42,6 +42,8 @@ pub struct LiquidGeneratorOpts {
 #[derive(Debug, Clone, Copy)]
 pub enum GeneratorType {
     RustTemplateCli,
+    RustClient,
+    TypeScriptClient,
 }
 
 impl FromStr for GeneratorType {
@@ -51,6 +53,8 @@ impl FromStr for GeneratorType {
         match s {
             "rust" => Ok(GeneratorType::RustTemplateCli),
             "rust-template-cli" => Ok(GeneratorType::RustTemplateCli),
+            "rust-client" => Ok(GeneratorType::RustClient),
+            "typescript-client" | "ts-client" => Ok(GeneratorType::TypeScriptClient),
             _ => Err(anyhow::anyhow!("Invalid generator type")),
         }
     }
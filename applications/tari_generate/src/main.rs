@@ -350,6 +350,10 @@ async fn scaffold(args: ScaffoldArgs) -> anyhow::Result<()> {
     let template = loaded_template.into();
     match args.generator {
         GeneratorType::RustTemplateCli => LiquidGenerator::new(LiquidTemplate::RustCli, opts).generate(&template)?,
+        GeneratorType::RustClient => LiquidGenerator::new(LiquidTemplate::RustClient, opts).generate(&template)?,
+        GeneratorType::TypeScriptClient => {
+            LiquidGenerator::new(LiquidTemplate::TypeScriptClient, opts).generate(&template)?
+        },
     };
     Ok(())
 }
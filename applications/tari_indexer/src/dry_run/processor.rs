@@ -128,6 +128,9 @@ where TSubstateCache: SubstateCache + 'static
                 per_byte_storage_cost: 1,
                 per_event_cost: 1,
                 per_log_cost: 1,
+                per_signature_verification_cost: 1,
+                per_hash_cost: 1,
+                per_commitment_operation_cost: 1,
             }
         } else {
             FeeTable::zero_rated()
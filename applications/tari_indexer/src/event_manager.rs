@@ -37,7 +37,7 @@ use tari_transaction::TransactionId;
 use tari_validator_node_rpc::client::TariValidatorNodeRpcClientFactory;
 
 use crate::substate_storage_sqlite::{
-    models::events::NewEvent,
+    models::events::{decode_typed_payload, NewEvent},
     sqlite_substate_store_factory::{
         SqliteSubstateStore,
         SubstateStore,
@@ -86,6 +86,7 @@ impl EventManager {
                 payload: payload.to_json().expect("Failed to convert to JSON"),
                 version: version as i32,
                 timestamp: timestamp as i64,
+                typed_payload: None,
             };
             tx.save_event(new_event)
         })?;
@@ -212,8 +213,23 @@ impl EventManager {
             let template_address = Hash::from_hex(&row.template_address)?;
             let tx_hash = Hash::from_hex(&row.tx_hash)?;
             let topic = row.topic;
-            let payload = Metadata::from(serde_json::from_str::<BTreeMap<String, String>>(row.payload.as_str())?);
-            events.push(Event::new(substate_id, template_address, tx_hash, topic, payload));
+            match row.typed_payload {
+                Some(typed_payload) => {
+                    let typed_payload = decode_typed_payload(&typed_payload)?;
+                    events.push(Event::new_typed(
+                        substate_id,
+                        template_address,
+                        tx_hash,
+                        topic,
+                        typed_payload,
+                    ));
+                },
+                None => {
+                    let payload =
+                        Metadata::from(serde_json::from_str::<BTreeMap<String, String>>(row.payload.as_str())?);
+                    events.push(Event::new(substate_id, template_address, tx_hash, topic, payload));
+                },
+            }
         }
 
         Ok(events)
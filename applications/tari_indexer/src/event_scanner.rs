@@ -41,7 +41,7 @@ use crate::{
     config::EventFilterConfig,
     substate_storage_sqlite::{
         models::{
-            events::{NewEvent, NewScannedBlockId},
+            events::{encode_typed_payload, NewEvent, NewScannedBlockId},
             substate::NewSubstate,
         },
         sqlite_substate_store_factory::{
@@ -249,6 +249,7 @@ impl EventScanner {
                 tx_hash: event.tx_hash().to_string(),
                 topic: event.topic(),
                 payload: event.payload().to_json().expect("Failed to convert to JSON"),
+                typed_payload: event.typed_payload().map(encode_typed_payload).transpose()?,
                 substate_id: event.substate_id().map(|s| s.to_string()),
                 // TODO: include substate version in event?
                 version: 0_i32,
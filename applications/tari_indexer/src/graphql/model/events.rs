@@ -22,9 +22,10 @@
 
 use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
-use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Json, Object, Schema, SimpleObject};
 use log::*;
 use serde::{Deserialize, Serialize};
+use tari_dan_app_utilities::json_encoding::encode_cbor_value_into_json;
 use tari_engine_types::substate::SubstateId;
 use tari_template_lib::Hash;
 use tari_transaction::TransactionId;
@@ -41,6 +42,8 @@ pub struct Event {
     pub tx_hash: [u8; 32],
     pub topic: String,
     pub payload: BTreeMap<String, String>,
+    /// The decoded payload of events declared in the template ABI
+    pub typed_payload: Option<Json<serde_json::Value>>,
 }
 
 impl Event {
@@ -50,6 +53,11 @@ impl Event {
             template_address: event.template_address().into_array(),
             tx_hash: event.tx_hash().into_array(),
             topic: event.topic(),
+            typed_payload: event
+                .typed_payload()
+                .map(encode_cbor_value_into_json)
+                .transpose()?
+                .map(Json),
             payload: event.into_payload().into_iter().collect(),
         })
     }
@@ -195,6 +203,7 @@ impl EventQuery {
             tx_hash: tx_hash.into_array(),
             topic,
             payload: payload.into_iter().collect(),
+            typed_payload: None,
         })
     }
 }
@@ -42,6 +42,7 @@ use tari_dan_common_types::{optional::Optional, public_key_to_peer_id, PeerAddre
 use tari_dan_engine::{template::TemplateModuleLoader, wasm::WasmModule};
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::consensus_models::Decision;
+use tari_engine_types::substate::{SubstateId, SubstateValue};
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
 use tari_indexer_client::types::{
     self,
@@ -81,6 +82,7 @@ use tari_indexer_client::types::{
 };
 use tari_networking::{is_supported_multiaddr, NetworkingHandle, NetworkingService};
 use tari_template_manager::{implementation::TemplateManager, interface::TemplateExecutable};
+use tari_transaction::infer_input_lock_intents;
 use tari_validator_node_rpc::client::{SubstateResult, TariValidatorNodeRpcClientFactory, TransactionResultStatus};
 
 use crate::{
@@ -296,12 +298,16 @@ impl JsonRpcHandlers {
             })?;
 
         match maybe_substate {
-            Some(substate_resp) => Ok(JsonRpcResponse::success(answer_id, GetSubstateResponse {
-                address: substate_resp.address,
-                version: substate_resp.version,
-                substate: substate_resp.substate,
-                created_by_transaction: substate_resp.created_by_transaction,
-            })),
+            Some(substate_resp) => {
+                let display_amount = self.get_display_amount(&substate_resp.substate).await;
+                Ok(JsonRpcResponse::success(answer_id, GetSubstateResponse {
+                    address: substate_resp.address,
+                    version: substate_resp.version,
+                    substate: substate_resp.substate,
+                    created_by_transaction: substate_resp.created_by_transaction,
+                    display_amount,
+                }))
+            },
             None => {
                 if request.local_search_only {
                     Err(JsonRpcResponse::error(
@@ -350,12 +356,16 @@ impl JsonRpcHandlers {
                             id,
                             substate,
                             created_by_tx,
-                        } => Ok(JsonRpcResponse::success(answer_id, GetSubstateResponse {
-                            address: id,
-                            version: substate.version(),
-                            substate: substate.into_substate_value(),
-                            created_by_transaction: created_by_tx,
-                        })),
+                        } => {
+                            let display_amount = self.get_display_amount(substate.substate_value()).await;
+                            Ok(JsonRpcResponse::success(answer_id, GetSubstateResponse {
+                                address: id,
+                                version: substate.version(),
+                                substate: substate.into_substate_value(),
+                                created_by_transaction: created_by_tx,
+                                display_amount,
+                            }))
+                        },
                         SubstateResult::Down { version, .. } => Err(JsonRpcResponse::error(
                             answer_id,
                             JsonRpcError::new(
@@ -403,11 +413,13 @@ impl JsonRpcHandlers {
                 )
             })?;
 
+        let display_amount = self.get_display_amount(&resp.substate).await;
         Ok(JsonRpcResponse::success(answer_id, InspectSubstateResponse {
             address: resp.address,
             version: resp.version,
             substate: resp.substate,
             created_by_transaction: resp.created_by_transaction,
+            display_amount,
         }))
     }
 
@@ -475,6 +487,13 @@ impl JsonRpcHandlers {
 
         if request.is_dry_run {
             let transaction_id = *request.transaction.id();
+            let inputs = request
+                .transaction
+                .inputs()
+                .iter()
+                .chain(&request.required_substates)
+                .cloned()
+                .collect::<Vec<_>>();
             let exec_result = self
                 .dry_run_transaction_processor
                 .process_transaction(request.transaction, request.required_substates)
@@ -483,6 +502,9 @@ impl JsonRpcHandlers {
 
             let json_results = encode_finalize_result_into_json(&exec_result.finalize)
                 .map_err(|e| Self::internal_error(answer_id, e))?;
+            let inferred_inputs = infer_input_lock_intents(inputs, &exec_result.finalize)
+                .into_iter()
+                .collect();
 
             return Ok(JsonRpcResponse::success(answer_id, SubmitTransactionResponse {
                 result: IndexerTransactionFinalizedResult::Finalized {
@@ -494,6 +516,7 @@ impl JsonRpcHandlers {
                     json_results,
                 },
                 transaction_id,
+                inferred_inputs,
             }));
         }
 
@@ -539,6 +562,7 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, SubmitTransactionResponse {
             result: IndexerTransactionFinalizedResult::Pending,
             transaction_id,
+            inferred_inputs: vec![],
         }))
     }
 
@@ -737,4 +761,29 @@ impl JsonRpcHandlers {
         let msg = error.to_string();
         Self::error_response(answer_id, JsonRpcErrorReason::InternalError, msg)
     }
+
+    /// Formats the (revealed) balance of a vault or the total supply of a fungible or confidential resource using the
+    /// resource divisibility.
+    async fn get_display_amount(&self, substate: &SubstateValue) -> Option<String> {
+        match substate {
+            SubstateValue::Resource(resource) if !resource.resource_type().is_non_fungible() => {
+                Some(resource.format_amount(resource.total_supply()))
+            },
+            SubstateValue::Vault(vault) if !vault.resource_type().is_non_fungible() => {
+                let resource_id = SubstateId::Resource(*vault.resource_address());
+                match self.substate_manager.get_substate(&resource_id, None).await {
+                    Ok(Some(resp)) => resp
+                        .substate
+                        .as_resource()
+                        .map(|resource| resource.format_amount(vault.balance())),
+                    Ok(None) => None,
+                    Err(e) => {
+                        warn!(target: LOG_TARGET, "Error getting resource {}: {}", resource_id, e);
+                        None
+                    },
+                }
+            },
+            _ => None,
+        }
+    }
 }
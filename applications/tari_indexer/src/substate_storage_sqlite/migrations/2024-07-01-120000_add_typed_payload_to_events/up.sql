@@ -0,0 +1,3 @@
+-- Structured CBOR payload for events declared in the template ABI, stored as JSON
+alter table events
+    add column typed_payload text NULL;
@@ -25,7 +25,12 @@ use std::{convert::TryFrom, str::FromStr};
 
 use diesel::sql_types::{Integer, Nullable, Text};
 use serde::{Deserialize, Serialize};
-use tari_engine_types::substate::SubstateId;
+use tari_bor::{
+    json_encoding::{CborValueJsonDeserializeWrapper, CborValueJsonSerializeWrapper},
+    FromTagAndValue,
+};
+use tari_dan_app_utilities::json_encoding::encode_cbor_value_into_json;
+use tari_engine_types::{indexed_value::WellKnownTariValue, substate::SubstateId};
 use tari_template_lib::Hash;
 
 use crate::substate_storage_sqlite::schema::*;
@@ -41,6 +46,7 @@ pub struct Event {
     pub version: i32,
     pub substate_id: Option<String>,
     pub timestamp: i64,
+    pub typed_payload: Option<String>,
 }
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -54,6 +60,7 @@ pub struct NewEvent {
     pub version: i32,
     pub substate_id: Option<String>,
     pub timestamp: i64,
+    pub typed_payload: Option<String>,
 }
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -79,6 +86,8 @@ pub struct EventData {
     pub version: i32,
     #[diesel(sql_type = Nullable<Text>)]
     pub substate_id: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub typed_payload: Option<String>,
 }
 
 impl TryFrom<EventData> for crate::graphql::model::events::Event {
@@ -92,12 +101,21 @@ impl TryFrom<EventData> for crate::graphql::model::events::Event {
         let tx_hash = Hash::from_hex(&event_data.tx_hash)?.into_array();
 
         let payload = serde_json::from_str(event_data.payload.as_str())?;
+        let typed_payload = event_data
+            .typed_payload
+            .as_deref()
+            .map(decode_typed_payload)
+            .transpose()?
+            .map(|v| encode_cbor_value_into_json(&v))
+            .transpose()?
+            .map(async_graphql::Json);
 
         Ok(Self {
             substate_id,
             template_address,
             tx_hash,
             payload,
+            typed_payload,
             topic: event_data.topic,
         })
     }
@@ -116,16 +134,57 @@ impl TryFrom<EventData> for tari_engine_types::events::Event {
         let tx_hash = Hash::from_hex(&event_data.tx_hash)?;
         let payload = serde_json::from_str(event_data.payload.as_str())?;
 
-        Ok(Self::new(
-            substate_id,
-            template_address,
-            tx_hash,
-            event_data.topic,
-            payload,
-        ))
+        match event_data.typed_payload {
+            Some(typed_payload) => Ok(Self::new_typed(
+                substate_id,
+                template_address,
+                tx_hash,
+                event_data.topic,
+                decode_typed_payload(&typed_payload)?,
+            )),
+            None => Ok(Self::new(
+                substate_id,
+                template_address,
+                tx_hash,
+                event_data.topic,
+                payload,
+            )),
+        }
     }
 }
 
+/// Encodes a typed event payload into JSON for storage. The encoding preserves CBOR tags so that the payload can be
+/// decoded without loss.
+pub fn encode_typed_payload(value: &tari_bor::Value) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&CborValueJsonSerializeWrapper(value))
+}
+
+pub fn decode_typed_payload(s: &str) -> Result<tari_bor::Value, serde_json::Error> {
+    let wrapper = serde_json::from_str::<CborValueJsonDeserializeWrapper>(s)?;
+    Ok(wrapper.0)
+}
+
+/// Returns the string that a top-level typed payload field is indexed by, so that it can be queried in the same way as
+/// string payload fields. Text and well-known address values are indexed by their string representation, all other
+/// values by their JSON encoding.
+pub fn typed_payload_field_to_string(value: &tari_bor::Value) -> Result<String, anyhow::Error> {
+    if let Some(text) = value.as_text() {
+        return Ok(text.to_string());
+    }
+
+    if let tari_bor::Value::Tag(tag, inner) = value {
+        match WellKnownTariValue::try_from_tag_and_value(*tag, inner) {
+            Ok(WellKnownTariValue::ComponentAddress(addr)) => return Ok(addr.to_string()),
+            Ok(WellKnownTariValue::ResourceAddress(addr)) => return Ok(addr.to_string()),
+            Ok(WellKnownTariValue::NonFungibleAddress(addr)) => return Ok(addr.to_string()),
+            Ok(WellKnownTariValue::VaultId(id)) => return Ok(id.to_string()),
+            _ => {},
+        }
+    }
+
+    Ok(encode_cbor_value_into_json(value)?.to_string())
+}
+
 // To keep track of the latest blocks that we scanned for events
 
 #[derive(Debug, Identifiable, Queryable)]
@@ -19,6 +19,7 @@ diesel::table! {
         version -> Integer,
         substate_id -> Nullable<Text>,
         timestamp -> BigInt,
+        typed_payload -> Nullable<Text>,
     }
 }
 
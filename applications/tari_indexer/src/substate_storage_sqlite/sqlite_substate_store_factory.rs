@@ -53,7 +53,7 @@ use super::models::{
     non_fungible_index::{IndexedNftSubstate, NewNonFungibleIndex},
 };
 use crate::substate_storage_sqlite::models::{
-    events::{Event, NewEventPayloadField, ScannedBlockId},
+    events::{decode_typed_payload, typed_payload_field_to_string, Event, NewEventPayloadField, ScannedBlockId},
     substate::{NewSubstate, Substate},
 };
 
@@ -440,7 +440,8 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
             "Querying substate scanner database: get_events_for_transaction with tx_hash = {}", tx_id
         );
         let res = sql_query(
-            "SELECT substate_id, template_address, tx_hash, topic, payload, version FROM events WHERE tx_hash = ?",
+            "SELECT substate_id, template_address, tx_hash, topic, payload, version, typed_payload FROM events WHERE \
+             tx_hash = ?",
         )
         .bind::<Text, _>(tx_id.to_string())
         .get_results::<EventData>(self.connection())
@@ -493,8 +494,8 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
             version
         );
         let res = sql_query(
-            "SELECT substate_id, template_address, tx_hash, topic, payload FROM events WHERE substate_id = ? AND \
-             version = ?",
+            "SELECT substate_id, template_address, tx_hash, topic, payload, version, typed_payload FROM events WHERE \
+             substate_id = ? AND version = ?",
         )
         .bind::<Nullable<Text>, _>(Some(substate_id.to_string()))
         .bind::<Integer, _>(version as i32)
@@ -520,8 +521,9 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
             payload_value
         );
         let res = sql_query(
-            "SELECT substate_id, template_address, tx_hash, topic, payload, version FROM events e INNER JOIN \
-             event_payloads p ON p.event_id = e.id WHERE p.payload_key = ? AND p.payload_value = ? LIMIT ?,?",
+            "SELECT substate_id, template_address, tx_hash, topic, payload, version, typed_payload FROM events e \
+             INNER JOIN event_payloads p ON p.event_id = e.id WHERE p.payload_key = ? AND p.payload_value = ? LIMIT \
+             ?,?",
         )
         .bind::<Text, _>(payload_key)
         .bind::<Text, _>(payload_value)
@@ -537,7 +539,8 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
 
     fn get_all_events(&mut self, substate_id: &SubstateId) -> Result<Vec<EventData>, StorageError> {
         let res = sql_query(
-            "SELECT substate_id, template_address, tx_hash, topic, payload, version FROM events WHERE substate_id = ?",
+            "SELECT substate_id, template_address, tx_hash, topic, payload, version, typed_payload FROM events WHERE \
+             substate_id = ?",
         )
         .bind::<Text, _>(substate_id.to_string())
         .get_results::<EventData>(self.connection())
@@ -591,7 +594,7 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
     fn event_exists(&mut self, value: &NewEvent) -> Result<bool, StorageError> {
         use crate::substate_storage_sqlite::schema::events;
 
-        let count = events::table
+        let mut query = events::table
             .filter(
                 events::substate_id
                     .eq(&value.substate_id)
@@ -601,6 +604,14 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
                     .and(events::payload.eq(&value.payload))
                     .and(events::tx_hash.eq(&value.tx_hash)),
             )
+            .into_boxed();
+
+        query = match &value.typed_payload {
+            Some(typed_payload) => query.filter(events::typed_payload.eq(typed_payload)),
+            None => query.filter(events::typed_payload.is_null()),
+        };
+
+        let count = query
             .count()
             .get_result::<i64>(self.connection())
             .map_err(|e| StorageError::QueryError {
@@ -797,7 +808,7 @@ impl SubstateStoreWriteTransaction for SqliteSubstateStoreWriteTransaction<'_> {
             serde_json::from_str(new_event.payload.as_str()).map_err(|e| StorageError::QueryError {
                 reason: format!("save_event: {}", e),
             })?;
-        let new_payload_fields = payload
+        let mut new_payload_fields = payload
             .into_iter()
             .map(|(key, value)| NewEventPayloadField {
                 payload_key: key,
@@ -805,6 +816,26 @@ impl SubstateStoreWriteTransaction for SqliteSubstateStoreWriteTransaction<'_> {
                 event_id: event_row.id,
             })
             .collect::<Vec<_>>();
+
+        // The top-level fields of typed payloads are also saved so that they can be queried in the same way
+        if let Some(typed_payload) = new_event.typed_payload.as_deref() {
+            let typed_payload = decode_typed_payload(typed_payload).map_err(|e| StorageError::QueryError {
+                reason: format!("save_event: {}", e),
+            })?;
+            for (key, value) in typed_payload.as_map().into_iter().flatten() {
+                let Some(key) = key.as_text() else {
+                    continue;
+                };
+                let value = typed_payload_field_to_string(value).map_err(|e| StorageError::QueryError {
+                    reason: format!("save_event: {}", e),
+                })?;
+                new_payload_fields.push(NewEventPayloadField {
+                    payload_key: key.to_string(),
+                    payload_value: value,
+                    event_id: event_row.id,
+                });
+            }
+        }
         // diesel fails if we try to pass all the new rows in a single insert
         // so the workaround is to loop over them
         // TODO: make diesel work with a single insert instruction instead of looping
@@ -0,0 +1,227 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_engine::abi::Type;
+
+/// Types exported by the tari_template_lib prelude that can be used as-is in a generated Rust client
+const TEMPLATE_LIB_TYPES: &[&str] = &[
+    "Amount",
+    "Bucket",
+    "BucketId",
+    "ComponentAddress",
+    "ConfidentialOutputStatement",
+    "ConfidentialWithdrawProof",
+    "Metadata",
+    "NonFungibleAddress",
+    "NonFungibleId",
+    "Proof",
+    "ProofId",
+    "ResourceAddress",
+    "ResourceType",
+    "RistrettoPublicKeyBytes",
+    "TemplateAddress",
+    "Vault",
+    "VaultId",
+];
+
+/// Types that have a generated definition in the typescript bindings
+const BINDINGS_TYPES: &[&str] = &[
+    "Amount",
+    "BucketId",
+    "ComponentAddress",
+    "Metadata",
+    "NonFungibleAddress",
+    "NonFungibleId",
+    "ProofId",
+    "ResourceAddress",
+    "VaultId",
+];
+
+/// Returns true if the argument must be passed in from the transaction workspace rather than as a literal value.
+pub fn is_workspace_type(ty: &Type) -> bool {
+    matches!(ty.other(), Some("Bucket" | "Proof"))
+}
+
+/// Returns true if the argument is the component receiver (&self or &mut self) of a method.
+pub fn is_receiver_type(ty: &Type) -> bool {
+    matches!(ty.other(), Some("&self" | "&mut self"))
+}
+
+pub fn to_rust_type(ty: &Type) -> String {
+    match ty {
+        Type::Unit => "()".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::I8 => "i8".to_string(),
+        Type::I16 => "i16".to_string(),
+        Type::I32 => "i32".to_string(),
+        Type::I64 => "i64".to_string(),
+        Type::I128 => "i128".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::String => "String".to_string(),
+        Type::Vec(ty) => format!("Vec<{}>", to_rust_type(ty)),
+        Type::Tuple(types) if types.len() == 1 => format!("({},)", to_rust_type(&types[0])),
+        Type::Tuple(types) => format!("({})", types.iter().map(to_rust_type).collect::<Vec<_>>().join(", ")),
+        Type::Other { name } if name.starts_with("Component<") => "ComponentAddress".to_string(),
+        Type::Other { name } if TEMPLATE_LIB_TYPES.contains(&name.as_str()) => name.clone(),
+        // The ABI does not describe custom types, so we fall back to a generic CBOR value
+        Type::Other { .. } => "tari_bor::Value".to_string(),
+    }
+}
+
+/// Returns true if values of the type may not fit in a JS number without losing precision
+fn is_ts_bigint(ty: &Type) -> bool {
+    matches!(ty, Type::I64 | Type::I128 | Type::U64 | Type::U128)
+}
+
+fn contains_ts_bigint(ty: &Type) -> bool {
+    match ty {
+        Type::Vec(ty) => contains_ts_bigint(ty),
+        Type::Tuple(types) => types.iter().any(contains_ts_bigint),
+        ty => is_ts_bigint(ty),
+    }
+}
+
+/// Returns true if the argument is made up of primitive values only, which the generated client encodes as a CBOR
+/// literal itself. Other values are passed as JSON and converted by the wallet.
+pub fn is_literal_type(ty: &Type) -> bool {
+    match ty {
+        Type::Vec(ty) => is_literal_type(ty),
+        Type::Tuple(types) => types.iter().all(is_literal_type),
+        Type::Other { .. } => false,
+        _ => true,
+    }
+}
+
+pub fn to_ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Unit => "null".to_string(),
+        Type::Bool => "boolean".to_string(),
+        Type::I64 | Type::I128 | Type::U64 | Type::U128 => "bigint".to_string(),
+        Type::I8 | Type::I16 | Type::I32 | Type::U8 | Type::U16 | Type::U32 => "number".to_string(),
+        Type::String => "string".to_string(),
+        Type::Vec(ty) => format!("Array<{}>", to_ts_type(ty)),
+        Type::Tuple(types) => format!("[{}]", types.iter().map(to_ts_type).collect::<Vec<_>>().join(", ")),
+        Type::Other { name } if name.starts_with("Component<") => "ComponentAddress".to_string(),
+        Type::Other { name } if BINDINGS_TYPES.contains(&name.as_str()) => name.clone(),
+        Type::Other { name } if name == "Bucket" => "BucketId".to_string(),
+        Type::Other { name } if name == "Proof" => "ProofId".to_string(),
+        Type::Other { .. } => "any".to_string(),
+    }
+}
+
+/// Returns a TS expression that converts the decoded JSON value `expr` to the TS type of `ty`. JSON numbers are
+/// converted to bigint where the TS type requires it.
+pub fn ts_from_json(ty: &Type, expr: &str) -> String {
+    match ty {
+        ty if is_ts_bigint(ty) => format!("BigInt({})", expr),
+        Type::Vec(ty) if contains_ts_bigint(ty) => {
+            format!("({} as Array<any>).map((v) => {})", expr, ts_from_json(ty, "v"))
+        },
+        Type::Tuple(types) if types.iter().any(contains_ts_bigint) => format!(
+            "[{}]",
+            types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| ts_from_json(ty, &format!("{}[{}]", expr, i)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => expr.to_string(),
+    }
+}
+
+/// Returns the bindings types referenced by the given type so that the generated module can import them
+pub fn ts_imports(ty: &Type, imports: &mut Vec<String>) {
+    match ty {
+        Type::Vec(ty) => ts_imports(ty, imports),
+        Type::Tuple(types) => types.iter().for_each(|ty| ts_imports(ty, imports)),
+        Type::Other { .. } => {
+            let ts_type = to_ts_type(ty);
+            if ts_type != "any" && !imports.contains(&ts_type) {
+                imports.push(ts_type);
+            }
+        },
+        _ => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn other(name: &str) -> Type {
+        Type::Other { name: name.to_string() }
+    }
+
+    #[test]
+    fn it_maps_abi_types_to_rust_types() {
+        assert_eq!(to_rust_type(&Type::U64), "u64");
+        assert_eq!(to_rust_type(&Type::Vec(Box::new(other("Amount")))), "Vec<Amount>");
+        assert_eq!(to_rust_type(&Type::Tuple(vec![Type::Bool])), "(bool,)");
+        assert_eq!(
+            to_rust_type(&Type::Tuple(vec![other("Bucket"), Type::String])),
+            "(Bucket, String)"
+        );
+        assert_eq!(to_rust_type(&other("Component<Counter>")), "ComponentAddress");
+        assert_eq!(to_rust_type(&other("MyStruct")), "tari_bor::Value");
+    }
+
+    #[test]
+    fn it_maps_abi_types_to_ts_types() {
+        assert_eq!(to_ts_type(&Type::U32), "number");
+        assert_eq!(to_ts_type(&Type::U64), "bigint");
+        assert_eq!(to_ts_type(&Type::U128), "bigint");
+        assert_eq!(to_ts_type(&Type::I128), "bigint");
+        assert_eq!(
+            to_ts_type(&Type::Tuple(vec![Type::U64, Type::Vec(Box::new(Type::I64))])),
+            "[bigint, Array<bigint>]"
+        );
+        assert_eq!(to_ts_type(&Type::Vec(Box::new(Type::String))), "Array<string>");
+        assert_eq!(
+            to_ts_type(&Type::Tuple(vec![other("Bucket"), other("ResourceAddress")])),
+            "[BucketId, ResourceAddress]"
+        );
+        assert_eq!(to_ts_type(&other("Option")), "any");
+
+        let mut imports = vec![];
+        ts_imports(
+            &Type::Tuple(vec![
+                other("Amount"),
+                Type::Vec(Box::new(other("Amount"))),
+                other("Foo"),
+            ]),
+            &mut imports,
+        );
+        assert_eq!(imports, vec!["Amount".to_string()]);
+    }
+
+    #[test]
+    fn it_converts_json_values_to_bigints() {
+        assert_eq!(ts_from_json(&Type::U32, "value"), "value");
+        assert_eq!(ts_from_json(&Type::U64, "value"), "BigInt(value)");
+        assert_eq!(
+            ts_from_json(&Type::Vec(Box::new(Type::U128)), "value"),
+            "(value as Array<any>).map((v) => BigInt(v))"
+        );
+        assert_eq!(
+            ts_from_json(&Type::Tuple(vec![Type::String, Type::I128]), "value"),
+            "[value[0], BigInt(value[1])]"
+        );
+        assert_eq!(ts_from_json(&Type::Vec(Box::new(other("Amount"))), "value"), "value");
+    }
+
+    #[test]
+    fn it_encodes_primitive_args_as_literals() {
+        assert!(is_literal_type(&Type::U128));
+        assert!(is_literal_type(&Type::Tuple(vec![
+            Type::String,
+            Type::Vec(Box::new(Type::U64))
+        ])));
+        assert!(!is_literal_type(&other("Amount")));
+        assert!(!is_literal_type(&Type::Vec(Box::new(other("ResourceAddress")))));
+    }
+}
@@ -1,6 +1,7 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+mod abi_types;
 mod snake_case;
 
 use std::fs;
@@ -12,6 +13,8 @@ use crate::generators::{CodeGenerator, GeneratorOpts, TemplateDefinition};
 
 pub enum LiquidTemplate {
     RustCli,
+    RustClient,
+    TypeScriptClient,
 }
 
 impl LiquidTemplate {
@@ -54,8 +57,58 @@ impl LiquidTemplate {
                     )),
                 ),
             ],
+            LiquidTemplate::RustClient => &[
+                (
+                    "Cargo.toml",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/rust_client/Cargo.toml.liquid"
+                    )),
+                ),
+                (
+                    ".gitignore",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/rust_client/.gitignore.liquid"
+                    )),
+                ),
+                (
+                    "src/lib.rs",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/rust_client/src/lib.rs.liquid"
+                    )),
+                ),
+            ],
+            LiquidTemplate::TypeScriptClient => &[
+                (
+                    "package.json",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/typescript_client/package.json.liquid"
+                    )),
+                ),
+                (
+                    "tsconfig.json",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/typescript_client/tsconfig.json.liquid"
+                    )),
+                ),
+                (
+                    "src/index.ts",
+                    include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/liquid_templates/typescript_client/src/index.ts.liquid"
+                    )),
+                ),
+            ],
         }
     }
+
+    const fn is_rust(&self) -> bool {
+        matches!(self, LiquidTemplate::RustCli | LiquidTemplate::RustClient)
+    }
 }
 
 pub struct LiquidGenerator {
@@ -73,7 +126,9 @@ impl LiquidGenerator {
 
         let mut globals = liquid::object!({
             "template_name": &template.name,
-            "commands": []
+            "template_title": template.name.to_case(Case::UpperCamel),
+            "commands": [],
+            "ts_imports": [],
         });
         globals.extend(
             opts.variables
@@ -81,16 +136,29 @@ impl LiquidGenerator {
                 .map(|(k, v)| (k.clone().into(), json_value_to_liquid_value(v.clone()))),
         );
 
+        let mut ts_imports = vec![];
         for f in template.template.functions() {
             let mut args = vec![];
+            let mut params = vec![];
             let mut is_method = false;
             let mut requires_buckets = false;
             let mut bucket_output = false;
             for a in &f.arguments {
-                args.push(liquid::object!({
+                abi_types::ts_imports(&a.arg_type, &mut ts_imports);
+                let arg = liquid::object!({
                     "name": a.name,
+                    "ts_name": a.name.to_case(Case::Camel),
                     "arg_type": a.arg_type.to_string(),
-                }));
+                    "rust_type": abi_types::to_rust_type(&a.arg_type),
+                    "ts_type": abi_types::to_ts_type(&a.arg_type),
+                    "is_receiver": abi_types::is_receiver_type(&a.arg_type),
+                    "is_workspace": abi_types::is_workspace_type(&a.arg_type),
+                    "is_literal": abi_types::is_literal_type(&a.arg_type),
+                });
+                if !abi_types::is_receiver_type(&a.arg_type) {
+                    params.push(arg.clone());
+                }
+                args.push(arg);
                 if a.arg_type.to_string() == "Bucket" {
                     requires_buckets = true;
                 }
@@ -105,18 +173,29 @@ impl LiquidGenerator {
                 }
             }
 
+            abi_types::ts_imports(&f.output, &mut ts_imports);
+
             let arr = globals.get_mut("commands").unwrap().as_array_mut().unwrap();
             arr.push(liquid_core::Value::Object(liquid::object!({
                 "name": f.name,
                 "title": f.name.to_case(Case::UpperCamel),
+                "ts_name": f.name.to_case(Case::Camel),
                 "args" : args,
+                "params": params,
                 "is_method": is_method,
                 "is_mut": f.is_mut,
                 "output": f.output.to_string(),
+                "rust_output": abi_types::to_rust_type(&f.output),
+                "ts_output": abi_types::to_ts_type(&f.output),
+                "ts_decode": abi_types::ts_from_json(&f.output, "value"),
                 "requires_buckets": requires_buckets,
                 "bucket_output": bucket_output,
             })));
         }
+
+        ts_imports.sort();
+        let arr = globals.get_mut("ts_imports").unwrap().as_array_mut().unwrap();
+        arr.extend(ts_imports.into_iter().map(liquid_core::Value::scalar));
         globals
     }
 }
@@ -134,7 +213,7 @@ impl CodeGenerator for LiquidGenerator {
             fs::write(opts.output_path.join(out_file), replace_tokens(content, &vars)?)?;
         }
 
-        if !self.opts.liquid.as_ref().unwrap().skip_format {
+        if self.template.is_rust() && !self.opts.liquid.as_ref().unwrap().skip_format {
             std::process::Command::new("cargo")
                 .args(["fmt"])
                 .current_dir(&opts.output_path)
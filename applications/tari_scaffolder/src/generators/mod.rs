@@ -42,6 +42,8 @@ pub struct LiquidGeneratorOpts {
 #[derive(Debug, Clone, Copy)]
 pub enum GeneratorType {
     RustTemplateCli,
+    RustClient,
+    TypeScriptClient,
 }
 
 impl FromStr for GeneratorType {
@@ -50,6 +52,8 @@ impl FromStr for GeneratorType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "rust-template-cli" => Ok(GeneratorType::RustTemplateCli),
+            "rust-client" => Ok(GeneratorType::RustClient),
+            "typescript-client" | "ts-client" => Ok(GeneratorType::TypeScriptClient),
             _ => Err(anyhow::anyhow!("Invalid generator type")),
         }
     }
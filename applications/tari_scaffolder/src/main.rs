@@ -64,6 +64,12 @@ fn main() -> anyhow::Result<()> {
                 generators::GeneratorType::RustTemplateCli => {
                     LiquidGenerator::new(LiquidTemplate::RustCli, opts).generate(&template)?
                 },
+                generators::GeneratorType::RustClient => {
+                    LiquidGenerator::new(LiquidTemplate::RustClient, opts).generate(&template)?
+                },
+                generators::GeneratorType::TypeScriptClient => {
+                    LiquidGenerator::new(LiquidTemplate::TypeScriptClient, opts).generate(&template)?
+                },
             }
         },
     }
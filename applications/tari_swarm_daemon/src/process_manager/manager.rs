@@ -179,7 +179,10 @@ impl ProcessManager {
 
         let mut client = process.connect_client()?;
         Ok(client
-            .get_active_templates(GetTemplatesRequest { limit: 10_000 })
+            .get_active_templates(GetTemplatesRequest {
+                limit: 10_000,
+                ..Default::default()
+            })
             .await?
             .templates
             .iter()
@@ -27,6 +27,7 @@ use futures::{future, FutureExt};
 use libp2p::identity;
 use log::*;
 use minotari_app_utilities::identity_management;
+use sqlite_message_logger::SqliteMessageLogger;
 use tari_base_node_client::grpc::GrpcBaseNodeClient;
 use tari_common::{
     configuration::Network,
@@ -55,8 +56,15 @@ use tari_epoch_manager::{
     EpochManagerReader,
 };
 use tari_indexer_lib::substate_scanner::SubstateScanner;
-use tari_networking::{MessagingMode, NetworkingHandle, RelayCircuitLimits, RelayReservationLimits, SwarmConfig};
-use tari_rpc_framework::RpcServer;
+use tari_networking::{
+    MessagingCompression,
+    MessagingMode,
+    NetworkingHandle,
+    RelayCircuitLimits,
+    RelayReservationLimits,
+    SwarmConfig,
+};
+use tari_rpc_framework::{RpcCompressionConfig, RpcQuota, RpcServer};
 use tari_shutdown::ShutdownSignal;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_template_manager::{implementation::TemplateManager, interface::TemplateManagerHandle};
@@ -73,6 +81,9 @@ use crate::{
     consensus::{self, ConsensusHandle, TariDanBlockTransactionExecutor, ValidationContext},
     dry_run_transaction_processor::DryRunTransactionProcessor,
     file_l1_submitter::FileLayerOneSubmitter,
+    l1_outbox::{LayerOneOutbox, OutboxLayerOneSubmitter},
+    l1_outbox_worker::spawn_outbox_worker,
+    l1_submitter::LayerOneSubmitter,
     p2p::{
         create_tari_validator_node_rpc_service,
         services::{
@@ -80,7 +91,6 @@ use crate::{
             mempool::{self, MempoolHandle},
             messaging::{ConsensusInboundMessaging, ConsensusOutboundMessaging},
         },
-        NopLogger,
     },
     state_bootstrap::bootstrap_state,
     substate_resolver::TariSubstateResolver,
@@ -161,6 +171,12 @@ pub async fn spawn_services(
                 // TODO: allow node operator to configure
                 relay_circuit_limits: RelayCircuitLimits::high(),
                 relay_reservation_limits: RelayReservationLimits::high(),
+                messaging_compression: config.validator_node.p2p.messaging_compression_level.map(|level| {
+                    MessagingCompression {
+                        level,
+                        ..Default::default()
+                    }
+                }),
                 ..Default::default()
             },
             reachability_mode: config.validator_node.p2p.reachability_mode.into(),
@@ -198,13 +214,34 @@ pub async fn spawn_services(
         validator_node_sidechain_id: config.validator_node.validator_node_sidechain_id.clone(),
         num_preshards: consensus_constants.num_preshards,
     };
+    // Layer one transaction submission
+    let (layer_one_submitter, layer_one_outbox) = match config.validator_node.layer_one_wallet_grpc_url.clone() {
+        Some(wallet_grpc_url) => {
+            let outbox = LayerOneOutbox::open(config.validator_node.layer_one_outbox_path())?;
+            handles.push(spawn_outbox_worker(
+                outbox.clone(),
+                wallet_grpc_url,
+                config.validator_node.layer_one_fee_per_gram,
+                shutdown.clone(),
+            ));
+            (
+                LayerOneSubmitter::Outbox(OutboxLayerOneSubmitter::new(outbox.clone())),
+                Some(outbox),
+            )
+        },
+        None => (
+            LayerOneSubmitter::File(FileLayerOneSubmitter::new(config.get_layer_one_transaction_base_path())),
+            None,
+        ),
+    };
+
     // Epoch manager
     let (epoch_manager, epoch_manager_join_handle) = tari_epoch_manager::base_layer::spawn_service(
         epoch_manager_config,
         global_db.clone(),
         base_node_client.clone(),
         keypair.public_key().clone(),
-        FileLayerOneSubmitter::new(config.get_layer_one_transaction_base_path()),
+        layer_one_submitter,
         shutdown.clone(),
     );
 
@@ -237,13 +274,7 @@ pub async fn spawn_services(
 
     info!(target: LOG_TARGET, "Payload processor initializing");
     // Payload processor
-    let fee_table = FeeTable {
-        per_transaction_weight_cost: 1,
-        per_module_call_cost: 1,
-        per_byte_storage_cost: 1,
-        per_event_cost: 1,
-        per_log_cost: 1,
-    };
+    let fee_table = create_fee_table();
 
     let (tx_hotstuff_events, _) = broadcast::channel(100);
     // Consensus gossip
@@ -256,7 +287,11 @@ pub async fn spawn_services(
     handles.push(join_handle);
 
     // Messaging
-    let message_logger = NopLogger; // SqliteMessageLogger::new(config.validator_node.data_dir.join("message_log.sqlite"));
+    let message_logger = if config.validator_node.message_log_enabled {
+        SqliteMessageLogger::new(config.validator_node.message_log_path())
+    } else {
+        SqliteMessageLogger::disabled()
+    };
     let local_address = PeerAddress::from(keypair.public_key().clone());
     let (loopback_sender, loopback_receiver) = mpsc::unbounded_channel();
     let inbound_messaging = ConsensusInboundMessaging::new(
@@ -382,6 +417,7 @@ pub async fn spawn_services(
         // global_db,
         state_store,
         dry_run_transaction_processor,
+        layer_one_outbox,
         handles,
         // validator_node_client_factory,
         // consensus_gossip_service,
@@ -449,6 +485,7 @@ pub struct Services {
     // pub validator_node_client_factory: TariValidatorNodeRpcClientFactory,
     // pub consensus_gossip_service: ConsensusGossipHandle,
     pub state_store: SqliteStateStore<PeerAddress>,
+    pub layer_one_outbox: Option<LayerOneOutbox>,
 
     pub handles: Vec<JoinHandle<Result<(), anyhow::Error>>>,
 }
@@ -474,6 +511,24 @@ async fn spawn_p2p_rpc(
     let rpc_server = RpcServer::builder()
         .with_maximum_simultaneous_sessions(config.validator_node.rpc.max_simultaneous_sessions)
         .with_maximum_sessions_per_client(config.validator_node.rpc.max_sessions_per_client)
+        .with_maximum_concurrent_requests_per_session(config.validator_node.rpc.max_concurrent_requests_per_session)
+        .with_compression(
+            config
+                .validator_node
+                .rpc
+                .compression_level
+                .map(|level| RpcCompressionConfig {
+                    level,
+                    ..Default::default()
+                }),
+        )
+        .with_peer_quota(
+            config
+                .validator_node
+                .rpc
+                .peer_rate_limit
+                .map(|rate| RpcQuota::new(rate, config.validator_node.rpc.peer_burst)),
+        )
         .finish()
         .add_service(create_tari_validator_node_rpc_service(
             epoch_manager,
@@ -511,3 +566,16 @@ pub fn create_consensus_transaction_validator(
         .map_context(|_| (), create_mempool_transaction_validator(network, template_manager))
         .map_context(|c| c.current_epoch, EpochRangeValidator::new())
 }
+
+pub fn create_fee_table() -> FeeTable {
+    FeeTable {
+        per_transaction_weight_cost: 1,
+        per_module_call_cost: 1,
+        per_byte_storage_cost: 1,
+        per_event_cost: 1,
+        per_log_cost: 1,
+        per_signature_verification_cost: 1,
+        per_hash_cost: 1,
+        per_commitment_operation_cost: 1,
+    }
+}
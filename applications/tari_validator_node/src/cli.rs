@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 use clap::Parser;
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
@@ -59,6 +59,32 @@ pub struct Cli {
     /// FOR DEBUGGING PURPOSES ONLY
     #[clap(long, short = 'd')]
     pub debug_templates: Vec<String>,
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum Command {
+    /// Replay a recorded message log into a standalone consensus worker and report where it diverges from the
+    /// recorded outbound messages. FOR DEBUGGING PURPOSES ONLY
+    Replay(ReplayArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReplayArgs {
+    /// The message log to replay. Defaults to the message log in the node's data directory.
+    #[clap(long)]
+    pub message_log: Option<PathBuf>,
+    /// A copy of the node's state database taken when the message log was started
+    #[clap(long)]
+    pub state_db: PathBuf,
+    /// How long to wait for the consensus worker to go quiet before feeding it the next message
+    #[clap(long, default_value = "500")]
+    pub idle_timeout_ms: u64,
+    /// Feed messages with the gaps between them as they were recorded, so that pacemaker timeouts fire as they did on
+    /// the node
+    #[clap(long)]
+    pub realtime: bool,
 }
 
 impl ConfigOverrideProvider for Cli {
@@ -114,8 +114,16 @@ pub struct ValidatorNodeConfig {
     pub template_sidechain_id: Option<RistrettoPublicKey>,
     /// The burnt utxo sidechain id
     pub burnt_utxo_sidechain_id: Option<RistrettoPublicKey>,
-    /// The path to store layer one transactions.
+    /// The path to store layer one transactions for tari_watcher to submit. Only used if
+    /// layer_one_wallet_grpc_url is not set.
     pub layer_one_transaction_path: PathBuf,
+    /// The Minotari wallet's GRPC URL. If set, layer one transactions (e.g. eviction proofs) are submitted directly
+    /// through the wallet and tracked in the layer one outbox until they are mined.
+    pub layer_one_wallet_grpc_url: Option<Url>,
+    /// The fee per gram used for layer one transactions submitted through the wallet
+    pub layer_one_fee_per_gram: u64,
+    /// Record all consensus messages in the message log database
+    pub message_log_enabled: bool,
 }
 
 impl ValidatorNodeConfig {
@@ -123,6 +131,14 @@ impl ValidatorNodeConfig {
         self.data_dir.join("state.db")
     }
 
+    pub fn message_log_path(&self) -> PathBuf {
+        self.data_dir.join("message_log.sqlite")
+    }
+
+    pub fn layer_one_outbox_path(&self) -> PathBuf {
+        self.data_dir.join("layer_one_outbox.json")
+    }
+
     pub fn set_base_path<P: AsRef<Path>>(&mut self, base_path: P) {
         if !self.shard_key_file.is_absolute() {
             self.shard_key_file = base_path.as_ref().join(&self.shard_key_file);
@@ -160,6 +176,9 @@ impl Default for ValidatorNodeConfig {
             template_sidechain_id: None,
             burnt_utxo_sidechain_id: None,
             layer_one_transaction_path: PathBuf::from("data/layer_one_transactions"),
+            layer_one_wallet_grpc_url: None,
+            layer_one_fee_per_gram: 10,
+            message_log_enabled: false,
         }
     }
 }
@@ -1,6 +1,7 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+use sqlite_message_logger::SqliteMessageLogger;
 use tari_common::configuration::Network;
 use tari_consensus::{
     hotstuff::{ConsensusWorker, ConsensusWorkerContext, HotstuffConfig, HotstuffWorker},
@@ -35,6 +36,7 @@ mod handle;
 mod leader_selection;
 #[cfg(feature = "metrics")]
 pub mod metrics;
+pub mod replay;
 mod signature_service;
 mod spec;
 
@@ -44,8 +46,6 @@ pub use signature_service::*;
 use tari_consensus::{consensus_constants::ConsensusConstants, hotstuff::HotstuffEvent};
 use tari_template_manager::interface::TemplateManagerHandle;
 
-use crate::p2p::NopLogger;
-
 pub type ConsensusTransactionValidator = BoxedValidator<ValidationContext, Transaction, TransactionValidationError>;
 
 pub async fn spawn(
@@ -55,8 +55,8 @@ pub async fn spawn(
     local_addr: PeerAddress,
     signing_service: TariSignatureService,
     epoch_manager: EpochManagerHandle<PeerAddress>,
-    inbound_messaging: ConsensusInboundMessaging<NopLogger>,
-    outbound_messaging: ConsensusOutboundMessaging<NopLogger>,
+    inbound_messaging: ConsensusInboundMessaging<SqliteMessageLogger>,
+    outbound_messaging: ConsensusOutboundMessaging<SqliteMessageLogger>,
     client_factory: TariValidatorNodeRpcClientFactory,
     hooks: <TariConsensusSpec as ConsensusSpec>::Hooks,
     shutdown_signal: ShutdownSignal,
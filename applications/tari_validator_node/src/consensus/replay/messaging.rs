@@ -0,0 +1,125 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_consensus::{
+    messages::HotstuffMessage,
+    traits::{InboundMessaging, InboundMessagingError, OutboundMessaging, OutboundMessagingError},
+};
+use tari_dan_common_types::{PeerAddress, ShardGroup};
+use tokio::sync::mpsc;
+
+/// A message sent by the replayed worker. The destination is formatted in the same way as the message logger does.
+#[derive(Debug, Clone)]
+pub struct SentMessage {
+    pub destination: String,
+    pub message: HotstuffMessage,
+}
+
+pub struct ReplayInboundMessaging {
+    local_address: PeerAddress,
+    rx_inbound: mpsc::UnboundedReceiver<(PeerAddress, HotstuffMessage)>,
+    rx_loopback: mpsc::UnboundedReceiver<HotstuffMessage>,
+}
+
+impl ReplayInboundMessaging {
+    pub fn new(
+        local_address: PeerAddress,
+        rx_inbound: mpsc::UnboundedReceiver<(PeerAddress, HotstuffMessage)>,
+        rx_loopback: mpsc::UnboundedReceiver<HotstuffMessage>,
+    ) -> Self {
+        Self {
+            local_address,
+            rx_inbound,
+            rx_loopback,
+        }
+    }
+}
+
+impl InboundMessaging for ReplayInboundMessaging {
+    type Addr = PeerAddress;
+
+    async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+        tokio::select! {
+            // BIASED: messaging priority is loopback, then recorded messages
+            biased;
+            maybe_msg = self.rx_loopback.recv() => maybe_msg.map(|msg| Ok((self.local_address, msg))),
+            maybe_msg = self.rx_inbound.recv() => maybe_msg.map(Ok),
+        }
+    }
+}
+
+/// Captures all messages sent by the replayed worker instead of sending them over the network. Messages sent to self
+/// are looped back to the worker.
+#[derive(Debug, Clone)]
+pub struct ReplayOutboundMessaging {
+    local_address: PeerAddress,
+    loopback_sender: mpsc::UnboundedSender<HotstuffMessage>,
+    tx_sent: mpsc::UnboundedSender<SentMessage>,
+}
+
+impl ReplayOutboundMessaging {
+    pub fn new(
+        local_address: PeerAddress,
+        loopback_sender: mpsc::UnboundedSender<HotstuffMessage>,
+        tx_sent: mpsc::UnboundedSender<SentMessage>,
+    ) -> Self {
+        Self {
+            local_address,
+            loopback_sender,
+            tx_sent,
+        }
+    }
+
+    fn capture(&self, destination: String, message: HotstuffMessage) -> Result<(), OutboundMessagingError> {
+        self.tx_sent.send(SentMessage { destination, message }).map_err(|_| {
+            OutboundMessagingError::FailedToEnqueueMessage {
+                reason: "replay capture channel closed".to_string(),
+            }
+        })
+    }
+}
+
+impl OutboundMessaging for ReplayOutboundMessaging {
+    type Addr = PeerAddress;
+
+    async fn send_self<T: Into<HotstuffMessage> + Send>(&mut self, message: T) -> Result<(), OutboundMessagingError> {
+        let message = message.into();
+        self.capture(self.local_address.to_string(), message.clone())?;
+        self.loopback_sender
+            .send(message)
+            .map_err(|_| OutboundMessagingError::FailedToEnqueueMessage {
+                reason: "loopback sender closed".to_string(),
+            })?;
+        Ok(())
+    }
+
+    async fn send<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+    ) -> Result<(), OutboundMessagingError> {
+        if to == self.local_address {
+            return self.send_self(message).await;
+        }
+        self.capture(to.to_string(), message.into())
+    }
+
+    async fn multicast<T, I>(&mut self, addresses: I, message: T) -> Result<(), OutboundMessagingError>
+    where
+        I: IntoIterator<Item = Self::Addr> + Send,
+        T: Into<HotstuffMessage> + Send,
+    {
+        let destination = addresses
+            .into_iter()
+            .filter(|addr| *addr != self.local_address)
+            .map(|addr| addr.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.capture(destination, message.into())
+    }
+
+    async fn broadcast<T>(&mut self, shard_group: ShardGroup, message: T) -> Result<(), OutboundMessagingError>
+    where T: Into<HotstuffMessage> + Send {
+        self.capture(shard_group.to_string(), message.into())
+    }
+}
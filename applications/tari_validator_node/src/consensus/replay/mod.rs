@@ -0,0 +1,363 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Replays a node's recorded consensus messages into a standalone hotstuff worker for post-mortem debugging.
+//!
+//! The worker runs against a copy of a state database snapshot, taken when the message log was started, with all
+//! networking replaced by channels. Recorded inbound messages are fed to the worker in the order they were received
+//! and everything the worker sends is compared, in order, to the recorded outbound messages. Messages that the node
+//! sent to itself are not fed in, because the replayed worker sends them again.
+
+mod messaging;
+mod report;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use libp2p::PeerId;
+use log::*;
+pub use messaging::*;
+pub use report::*;
+use sqlite_message_logger::{LoggedMessage, SqliteMessageLogger};
+use tari_base_node_client::grpc::GrpcBaseNodeClient;
+use tari_common::configuration::bootstrap::{grpc_default_port, ApplicationType};
+use tari_consensus::{
+    consensus_constants::ConsensusConstants,
+    hotstuff::{HotstuffConfig, HotstuffWorker},
+    messages::HotstuffMessage,
+    traits::{hooks::NoopHooks, ConsensusSpec, SyncManager, SyncStatus},
+};
+use tari_dan_app_utilities::{keypair::setup_keypair_prompt, transaction_executor::TariDanTransactionProcessor};
+use tari_dan_common_types::PeerAddress;
+use tari_dan_engine::transaction::TransactionProcessorConfig;
+use tari_dan_storage::{consensus_models::TransactionPool, global::DbFactory};
+use tari_dan_storage_sqlite::SqliteDbFactory;
+use tari_epoch_manager::base_layer::{EpochManagerConfig, EpochManagerHandle};
+use tari_shutdown::Shutdown;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_template_manager::implementation::TemplateManager;
+use tokio::{
+    sync::{broadcast, mpsc},
+    time::Instant,
+};
+
+use crate::{
+    bootstrap::{create_consensus_transaction_validator, create_fee_table},
+    consensus::{
+        leader_selection::RoundRobinLeaderStrategy,
+        ConsensusTransactionValidator,
+        TariDanBlockTransactionExecutor,
+        TariSignatureService,
+    },
+    file_l1_submitter::FileLayerOneSubmitter,
+    ApplicationConfig,
+};
+
+const LOG_TARGET: &str = "tari::validator_node::consensus::replay";
+
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    /// The message log database recorded by the node
+    pub message_log_path: PathBuf,
+    /// A snapshot of the node's state database taken when the message log was started. The snapshot is copied and
+    /// is not modified by the replay.
+    pub state_db_path: PathBuf,
+    /// How long the worker must be quiet before the next inbound message is fed to it
+    pub idle_timeout: Duration,
+    /// Keep the recorded gaps between inbound messages, so that pacemaker timeouts fire as they did on the node
+    pub realtime: bool,
+}
+
+#[derive(Clone)]
+pub struct ReplayConsensusSpec;
+
+impl ConsensusSpec for ReplayConsensusSpec {
+    type Addr = PeerAddress;
+    type EpochManager = EpochManagerHandle<Self::Addr>;
+    type Hooks = NoopHooks;
+    type InboundMessaging = ReplayInboundMessaging;
+    type LeaderStrategy = RoundRobinLeaderStrategy;
+    type OutboundMessaging = ReplayOutboundMessaging;
+    type SignatureService = TariSignatureService;
+    type StateStore = SqliteStateStore<Self::Addr>;
+    type SyncManager = NoSyncManager;
+    type TransactionExecutor = TariDanBlockTransactionExecutor<
+        TariDanTransactionProcessor<TemplateManager<PeerAddress>>,
+        ConsensusTransactionValidator,
+    >;
+}
+
+/// The replayed worker only sees recorded messages, so it never syncs from peers
+#[derive(Clone)]
+pub struct NoSyncManager;
+
+impl SyncManager for NoSyncManager {
+    type Error = std::convert::Infallible;
+
+    async fn check_sync(&self) -> Result<SyncStatus, Self::Error> {
+        Ok(SyncStatus::UpToDate)
+    }
+
+    async fn sync(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+struct RecordedInbound {
+    id: i32,
+    from: PeerAddress,
+    message: HotstuffMessage,
+    /// Seconds since the unix epoch. The message logger records timestamps with second resolution.
+    received_at: i64,
+}
+
+#[allow(clippy::too_many_lines)]
+pub async fn run_message_replay(
+    config: &ApplicationConfig,
+    replay_config: ReplayConfig,
+) -> Result<ReplayReport, anyhow::Error> {
+    if !replay_config.message_log_path.is_file() {
+        bail!(
+            "Message log {} does not exist",
+            replay_config.message_log_path.display()
+        );
+    }
+    if !replay_config.state_db_path.is_file() {
+        bail!(
+            "State database {} does not exist",
+            replay_config.state_db_path.display()
+        );
+    }
+
+    let keypair = setup_keypair_prompt(&config.validator_node.identity_file, false)?;
+    let local_address = PeerAddress::from(keypair.public_key().clone());
+
+    let message_log = SqliteMessageLogger::new(&replay_config.message_log_path);
+    let inbound = message_log
+        .get_inbound_messages()
+        .context("failed to read inbound messages from the message log")?
+        .into_iter()
+        // The replayed worker loops back the messages it sends to itself
+        .filter(|msg| msg.pubkey != local_address.to_string())
+        .map(|msg| {
+            Ok(RecordedInbound {
+                id: msg.id,
+                from: parse_peer_address(&msg.pubkey)
+                    .with_context(|| format!("inbound message {} has an invalid sender", msg.id))?,
+                message: parse_message(&msg)?,
+                received_at: msg.timestamp.and_utc().timestamp(),
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+    let recorded_outbound = message_log
+        .get_outbound_messages()
+        .context("failed to read outbound messages from the message log")?
+        .iter()
+        .map(|msg| {
+            Ok(OutboundRecord {
+                log_id: Some(msg.id),
+                destination: msg.pubkey.clone(),
+                summary: parse_message(msg)?.to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+    info!(
+        target: LOG_TARGET,
+        "🔁 Loaded {} inbound and {} outbound message(s) from {}",
+        inbound.len(),
+        recorded_outbound.len(),
+        replay_config.message_log_path.display()
+    );
+
+    let replay_dir = config.validator_node.data_dir.join("replay");
+    let state_db_path = copy_sqlite_db(&replay_config.state_db_path, &replay_dir.join("state.db"))?;
+    let state_store = SqliteStateStore::connect(&format!("sqlite://{}", state_db_path.display()))?;
+
+    // The replay runs against a copy of the global DB so that the live node's DB is never migrated or written to
+    copy_sqlite_db(
+        &config.validator_node.data_dir.join("global_storage.sqlite"),
+        &replay_dir.join("global_storage.sqlite"),
+    )?;
+    let db_factory = SqliteDbFactory::new(replay_dir.clone());
+    db_factory.migrate()?;
+    let global_db = db_factory.get_or_create_global_db()?;
+
+    let consensus_constants = ConsensusConstants::from(config.network);
+    let mut shutdown = Shutdown::new();
+
+    // The base node client connects lazily and is not used unless the epoch manager scans the base layer
+    let base_node_address = config.validator_node.base_node_grpc_url.clone().unwrap_or_else(|| {
+        let port = grpc_default_port(ApplicationType::BaseNode, config.network);
+        format!("http://127.0.0.1:{port}")
+            .parse()
+            .expect("Default base node GRPC URL is malformed")
+    });
+    let (epoch_manager, _epoch_manager_join_handle) = tari_epoch_manager::base_layer::spawn_service(
+        EpochManagerConfig {
+            base_layer_confirmations: consensus_constants.base_layer_confirmations,
+            committee_size: consensus_constants
+                .committee_size
+                .try_into()
+                .context("committee size must be non-zero")?,
+            validator_node_sidechain_id: config.validator_node.validator_node_sidechain_id.clone(),
+            num_preshards: consensus_constants.num_preshards,
+        },
+        global_db.clone(),
+        GrpcBaseNodeClient::new(base_node_address),
+        keypair.public_key().clone(),
+        FileLayerOneSubmitter::new(replay_dir.join("layer_one_transactions")),
+        shutdown.to_signal(),
+    );
+
+    let template_manager = TemplateManager::initialize(global_db, config.validator_node.templates.clone())?;
+    let payload_processor = TariDanTransactionProcessor::new(
+        TransactionProcessorConfig::builder()
+            .with_network(config.network)
+            .with_template_binary_max_size_bytes(consensus_constants.template_binary_max_size_bytes)
+            .build(),
+        template_manager.clone(),
+        create_fee_table(),
+    );
+    let transaction_executor = TariDanBlockTransactionExecutor::new(
+        payload_processor,
+        create_consensus_transaction_validator(config.network, template_manager).boxed(),
+    );
+
+    let (tx_inbound, rx_inbound) = mpsc::unbounded_channel();
+    let (loopback_sender, loopback_receiver) = mpsc::unbounded_channel();
+    let (tx_sent, mut rx_sent) = mpsc::unbounded_channel();
+    // Transactions are only received through recorded messages
+    let (_tx_new_transactions, rx_new_transactions) = mpsc::channel(1);
+    let (tx_hotstuff_events, _) = broadcast::channel(100);
+
+    let mut worker = HotstuffWorker::<ReplayConsensusSpec>::new(
+        HotstuffConfig {
+            network: config.network,
+            sidechain_id: config.validator_node.validator_node_sidechain_id.clone(),
+            consensus_constants,
+        },
+        local_address,
+        ReplayInboundMessaging::new(local_address, rx_inbound, loopback_receiver),
+        ReplayOutboundMessaging::new(local_address, loopback_sender, tx_sent),
+        rx_new_transactions,
+        state_store,
+        epoch_manager,
+        RoundRobinLeaderStrategy::new(),
+        TariSignatureService::new(keypair),
+        TransactionPool::new(),
+        transaction_executor,
+        tx_hotstuff_events,
+        NoopHooks,
+        shutdown.to_signal(),
+    );
+    let worker_handle = tokio::spawn(async move { worker.start().await });
+
+    let mut replayed_outbound = Vec::new();
+    let mut last_inbound = None;
+    let mut num_replayed = 0;
+    let mut prev_received_at = None;
+    for msg in &inbound {
+        let min_wait = match prev_received_at {
+            Some(prev) if replay_config.realtime => {
+                Duration::from_secs(msg.received_at.saturating_sub(prev).max(0) as u64)
+            },
+            _ => Duration::ZERO,
+        };
+        collect_sent(
+            &mut rx_sent,
+            &mut replayed_outbound,
+            last_inbound,
+            min_wait,
+            replay_config.idle_timeout,
+        )
+        .await;
+        if worker_handle.is_finished() {
+            break;
+        }
+
+        debug!(target: LOG_TARGET, "🔁 Replaying #{} from {}: {}", msg.id, msg.from, msg.message);
+        if tx_inbound.send((msg.from, msg.message.clone())).is_err() {
+            break;
+        }
+        last_inbound = Some(msg.id);
+        num_replayed += 1;
+        prev_received_at = Some(msg.received_at);
+    }
+    collect_sent(
+        &mut rx_sent,
+        &mut replayed_outbound,
+        last_inbound,
+        Duration::ZERO,
+        replay_config.idle_timeout,
+    )
+    .await;
+
+    shutdown.trigger();
+    let worker_error = match worker_handle.await {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err.to_string()),
+        Err(err) => Some(err.to_string()),
+    };
+
+    Ok(ReplayReport::new(
+        num_replayed,
+        &recorded_outbound,
+        &replayed_outbound,
+        worker_error,
+    ))
+}
+
+/// Collects the messages sent by the worker until at least `min_wait` has passed and the worker has not sent
+/// anything for `idle_timeout`.
+async fn collect_sent(
+    rx_sent: &mut mpsc::UnboundedReceiver<SentMessage>,
+    replayed: &mut Vec<OutboundRecord>,
+    last_inbound: Option<i32>,
+    min_wait: Duration,
+    idle_timeout: Duration,
+) {
+    let min_deadline = Instant::now() + min_wait;
+    loop {
+        let deadline = (Instant::now() + idle_timeout).max(min_deadline);
+        match tokio::time::timeout_at(deadline, rx_sent.recv()).await {
+            Ok(Some(sent)) => replayed.push(OutboundRecord {
+                log_id: last_inbound,
+                destination: sent.destination,
+                summary: sent.message.to_string(),
+            }),
+            Ok(None) | Err(_) => break,
+        }
+    }
+}
+
+fn parse_message(msg: &LoggedMessage) -> Result<HotstuffMessage, anyhow::Error> {
+    serde_json::from_str(&msg.message_json)
+        .with_context(|| format!("failed to parse {} message {}", msg.message_type, msg.id))
+}
+
+fn parse_peer_address(s: &str) -> Result<PeerAddress, anyhow::Error> {
+    let peer_id = PeerId::from_str(s).map_err(|e| anyhow!("invalid peer id '{s}': {e}"))?;
+    Ok(peer_id.into())
+}
+
+/// Copies a SQLite database, along with any write-ahead log, so that the replay does not modify the original
+fn copy_sqlite_db(src: &Path, dest: &Path) -> Result<PathBuf, anyhow::Error> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    for suffix in ["", "-wal", "-shm"] {
+        let src = PathBuf::from(format!("{}{suffix}", src.display()));
+        let dest = PathBuf::from(format!("{}{suffix}", dest.display()));
+        if src.is_file() {
+            fs::copy(&src, &dest).with_context(|| format!("failed to copy {}", src.display()))?;
+        } else if dest.is_file() {
+            fs::remove_file(&dest)?;
+        }
+    }
+    Ok(dest.to_path_buf())
+}
@@ -0,0 +1,169 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+/// An outbound message, either recorded in the message log or sent by the replayed worker. Messages are compared by
+/// destination and summary, which leaves out signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundRecord {
+    /// The message log id of the recorded message, or of the inbound message that was last fed to the replayed worker
+    /// before it sent this message
+    pub log_id: Option<i32>,
+    pub destination: String,
+    pub summary: String,
+}
+
+impl OutboundRecord {
+    fn matches(&self, other: &OutboundRecord) -> bool {
+        self.destination == other.destination && self.summary == other.summary
+    }
+}
+
+impl Display for OutboundRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.summary, self.destination)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// The position in the outbound message sequence of the first message that differs
+    pub index: usize,
+    pub expected: Option<OutboundRecord>,
+    pub actual: Option<OutboundRecord>,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Diverged at outbound message #{}", self.index)?;
+        match &self.expected {
+            Some(expected) => writeln!(
+                f,
+                "  recorded (log id {}): {}",
+                expected.log_id.map(|id| id.to_string()).unwrap_or_default(),
+                expected
+            )?,
+            None => writeln!(f, "  recorded: <no more messages>")?,
+        }
+        match &self.actual {
+            Some(actual) => write!(
+                f,
+                "  replayed (after inbound log id {}): {}",
+                actual
+                    .log_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "<none>".to_string()),
+                actual
+            ),
+            None => write!(f, "  replayed: <no more messages>"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub num_inbound_replayed: usize,
+    pub num_recorded_outbound: usize,
+    pub num_replayed_outbound: usize,
+    pub divergence: Option<Divergence>,
+    /// Set if the replayed worker exited with an error
+    pub worker_error: Option<String>,
+}
+
+impl ReplayReport {
+    pub fn new(
+        num_inbound_replayed: usize,
+        recorded: &[OutboundRecord],
+        replayed: &[OutboundRecord],
+        worker_error: Option<String>,
+    ) -> Self {
+        Self {
+            num_inbound_replayed,
+            num_recorded_outbound: recorded.len(),
+            num_replayed_outbound: replayed.len(),
+            divergence: find_divergence(recorded, replayed),
+            worker_error,
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.divergence.is_none() && self.worker_error.is_none()
+    }
+}
+
+impl Display for ReplayReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} inbound message(s). Recorded {} outbound message(s), replay sent {}.",
+            self.num_inbound_replayed, self.num_recorded_outbound, self.num_replayed_outbound
+        )?;
+        if let Some(err) = &self.worker_error {
+            writeln!(f, "Consensus worker exited with an error: {}", err)?;
+        }
+        match &self.divergence {
+            Some(divergence) => write!(f, "{}", divergence),
+            None => write!(f, "Replayed outbound messages match the recording"),
+        }
+    }
+}
+
+/// Returns the first position at which the replayed outbound messages differ from the recorded messages
+pub fn find_divergence(recorded: &[OutboundRecord], replayed: &[OutboundRecord]) -> Option<Divergence> {
+    let len = recorded.len().max(replayed.len());
+    (0..len).find_map(|index| {
+        let expected = recorded.get(index);
+        let actual = replayed.get(index);
+        match (expected, actual) {
+            (Some(expected), Some(actual)) if expected.matches(actual) => None,
+            _ => Some(Divergence {
+                index,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            }),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(log_id: i32, summary: &str) -> OutboundRecord {
+        OutboundRecord {
+            log_id: Some(log_id),
+            destination: "peer".to_string(),
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_ignores_log_ids_when_comparing() {
+        let recorded = vec![record(1, "Vote(1)"), record(2, "Vote(2)")];
+        let replayed = vec![record(10, "Vote(1)"), record(11, "Vote(2)")];
+        assert!(find_divergence(&recorded, &replayed).is_none());
+    }
+
+    #[test]
+    fn it_reports_the_first_differing_message() {
+        let recorded = vec![record(1, "Vote(1)"), record(2, "Vote(2)"), record(3, "Vote(3)")];
+        let replayed = vec![record(1, "Vote(1)"), record(1, "NewView(2)")];
+        let divergence = find_divergence(&recorded, &replayed).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert_eq!(divergence.expected.unwrap().summary, "Vote(2)");
+        assert_eq!(divergence.actual.unwrap().summary, "NewView(2)");
+    }
+
+    #[test]
+    fn it_reports_missing_messages() {
+        let recorded = vec![record(1, "Vote(1)"), record(2, "Vote(2)")];
+        let replayed = vec![record(1, "Vote(1)")];
+        let divergence = find_divergence(&recorded, &replayed).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert!(divergence.actual.is_none());
+
+        let divergence = find_divergence(&replayed, &recorded).unwrap();
+        assert!(divergence.expected.is_none());
+    }
+}
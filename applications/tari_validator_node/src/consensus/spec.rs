@@ -1,6 +1,7 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+use sqlite_message_logger::SqliteMessageLogger;
 #[cfg(not(feature = "metrics"))]
 use tari_consensus::traits::hooks::NoopHooks;
 use tari_consensus::traits::ConsensusSpec;
@@ -20,10 +21,7 @@ use crate::{
         ConsensusTransactionValidator,
         TariDanBlockTransactionExecutor,
     },
-    p2p::{
-        services::messaging::{ConsensusInboundMessaging, ConsensusOutboundMessaging},
-        NopLogger,
-    },
+    p2p::services::messaging::{ConsensusInboundMessaging, ConsensusOutboundMessaging},
 };
 
 #[derive(Clone)]
@@ -36,9 +34,9 @@ impl ConsensusSpec for TariConsensusSpec {
     type Hooks = NoopHooks;
     #[cfg(feature = "metrics")]
     type Hooks = PrometheusConsensusMetrics;
-    type InboundMessaging = ConsensusInboundMessaging<NopLogger>;
+    type InboundMessaging = ConsensusInboundMessaging<SqliteMessageLogger>;
     type LeaderStrategy = RoundRobinLeaderStrategy;
-    type OutboundMessaging = ConsensusOutboundMessaging<NopLogger>;
+    type OutboundMessaging = ConsensusOutboundMessaging<SqliteMessageLogger>;
     type SignatureService = TariSignatureService;
     type StateStore = SqliteStateStore<Self::Addr>;
     type SyncManager = RpcStateSyncClientProtocol<Self>;
@@ -22,6 +22,7 @@
 
 use log::*;
 use tari_consensus::hotstuff::HotstuffEvent;
+use tari_dan_engine::{flow::FlowFactory, template::LoadedManifestTemplate};
 use tari_dan_storage::{consensus_models::Block, StateStore};
 use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
 use tari_networking::NetworkingService;
@@ -107,15 +108,29 @@ impl DanNode {
                     template_address,
                     block
                 );
+                let executable = if FlowFactory::is_flow_json(code) {
+                    match String::from_utf8(code.to_vec()) {
+                        Ok(flow_json) => TemplateExecutable::Flow(flow_json),
+                        Err(err) => {
+                            error!(target: LOG_TARGET, "🚨Published flow {} is not valid UTF-8: {}", template_address, err);
+                            continue;
+                        },
+                    }
+                } else if LoadedManifestTemplate::is_manifest_source(code) {
+                    TemplateExecutable::Manifest(String::from_utf8_lossy(code).into_owned())
+                } else {
+                    TemplateExecutable::CompiledWasm(code.to_vec())
+                };
                 if let Err(err) = self
                     .services
                     .template_manager
                     .add_template(
                         author_pk.clone(),
                         template_address.as_hash(),
-                        TemplateExecutable::CompiledWasm(code.to_vec()),
+                        executable,
                         None,
                         epoch,
+                        None,
                     )
                     .await
                 {
@@ -35,6 +35,7 @@ use tari_dan_common_types::{optional::Optional, public_key_to_peer_id, Epoch, Pe
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::{
     consensus_models::{Block, ExecutedTransaction, LeafBlock, QuorumDecision, SubstateRecord, TransactionRecord},
+    global::DbTemplateQuery,
     Ordering,
     StateStore,
     StateStoreReadTransaction,
@@ -66,7 +67,10 @@ use tari_validator_node_client::types::{
     GetEpochManagerStatsResponse,
     GetFilteredBlocksCountRequest,
     GetIdentityResponse,
+    GetLayerOneOutboxRequest,
+    GetLayerOneOutboxResponse,
     GetMempoolStatsResponse,
+    GetRecentTransactionsRequest,
     GetRecentTransactionsResponse,
     GetShardKeyRequest,
     GetShardKeyResponse,
@@ -98,11 +102,15 @@ use crate::{
     consensus::ConsensusHandle,
     dry_run_transaction_processor::DryRunTransactionProcessor,
     json_rpc::jrpc_errors::{internal_error, not_found},
+    l1_outbox::LayerOneOutbox,
     p2p::services::mempool::MempoolHandle,
     Services,
 };
 
 const LOG_TARGET: &str = "tari::validator_node::json_rpc::handlers";
+const DEFAULT_RECENT_TRANSACTIONS_LIMIT: u64 = 1000;
+/// The maximum number of blocks that a filtered list_blocks request walks back through before returning a partial page
+const MAX_LIST_BLOCKS_SCAN: usize = 1000;
 
 pub struct JsonRpcHandlers {
     keypair: RistrettoKeypair,
@@ -114,6 +122,7 @@ pub struct JsonRpcHandlers {
     base_node_client: GrpcBaseNodeClient,
     state_store: SqliteStateStore<PeerAddress>,
     dry_run_transaction_processor: DryRunTransactionProcessor,
+    layer_one_outbox: Option<LayerOneOutbox>,
 }
 
 impl JsonRpcHandlers {
@@ -128,6 +137,7 @@ impl JsonRpcHandlers {
             base_node_client,
             state_store: services.state_store.clone(),
             dry_run_transaction_processor: services.dry_run_transaction_processor.clone(),
+            layer_one_outbox: services.layer_one_outbox.clone(),
         }
     }
 
@@ -269,11 +279,30 @@ impl JsonRpcHandlers {
 
     pub async fn get_recent_transactions(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
+        // Params were not required before pagination was added
+        let req = value
+            .parse_params::<Option<GetRecentTransactionsRequest>>()?
+            .unwrap_or_default();
+        let limit = req.limit.unwrap_or(DEFAULT_RECENT_TRANSACTIONS_LIMIT);
         let tx = self.state_store.create_read_tx().map_err(internal_error(answer_id))?;
-        match TransactionRecord::get_paginated(&tx, 1000, 0, Some(Ordering::Descending)) {
-            Ok(recent_transactions) => {
+        // Fetch one more than requested to determine if there is a next page
+        match TransactionRecord::get_paginated(
+            &tx,
+            limit.saturating_add(1),
+            req.cursor.as_ref(),
+            req.status,
+            Some(req.ordering.unwrap_or(Ordering::Descending)),
+        ) {
+            Ok(mut recent_transactions) => {
+                let next_cursor = if recent_transactions.len() as u64 > limit {
+                    recent_transactions.truncate(limit as usize);
+                    recent_transactions.last().map(|t| *t.id())
+                } else {
+                    None
+                };
                 let res = GetRecentTransactionsResponse {
                     transactions: recent_transactions.into_iter().map(|t| t.transaction).collect(),
+                    next_cursor,
                 };
                 Ok(JsonRpcResponse::success(answer_id, res))
             },
@@ -300,7 +329,7 @@ impl JsonRpcHandlers {
 
         let tx = self.state_store.create_read_tx().map_err(internal_error(answer_id))?;
 
-        let start_block = match req.from_id {
+        let mut start_block = match req.from_id {
             Some(id) => Block::get(&tx, &id)
                 .optional()
                 .map_err(internal_error(answer_id))?
@@ -312,11 +341,49 @@ impl JsonRpcHandlers {
                 .get_block(&tx)
                 .map_err(internal_error(answer_id))?,
         };
-        let blocks = start_block
-            .get_parent_chain(&tx, req.limit)
-            .map_err(internal_error(answer_id))?;
 
-        let res = ListBlocksResponse { blocks };
+        // Walk back through the chain until we have a full page of matching blocks. Without a filter, this is a single
+        // iteration.
+        let chunk_size = req.limit.max(1);
+        let mut blocks = Vec::with_capacity(req.limit);
+        let mut num_scanned = 0;
+        let next_cursor = 'scan: loop {
+            if blocks.len() >= req.limit {
+                // An empty page has no next page, otherwise the client would request the same page forever
+                break (req.limit > 0).then(|| *start_block.id());
+            }
+
+            let chain = start_block
+                .get_parent_chain(&tx, chunk_size)
+                .map_err(internal_error(answer_id))?;
+            let is_end_of_chain = chain.len() < chunk_size || chain.last().map_or(true, |b| b.is_genesis());
+            num_scanned += chain.len();
+            let Some(last_parent) = chain.last().map(|b| *b.parent()) else {
+                break None;
+            };
+
+            for block in chain {
+                if req.proposed_by.as_ref().map_or(true, |pk| block.proposed_by() == pk) {
+                    let parent = *block.parent();
+                    let is_genesis = block.is_genesis();
+                    blocks.push(block);
+                    if blocks.len() >= req.limit {
+                        // Stop at this block so that the next page starts from its parent
+                        break 'scan (!is_genesis).then_some(parent);
+                    }
+                }
+            }
+
+            if is_end_of_chain {
+                break None;
+            }
+            if num_scanned >= MAX_LIST_BLOCKS_SCAN {
+                break Some(last_parent);
+            }
+            start_block = Block::get(&tx, &last_parent).map_err(internal_error(answer_id))?;
+        };
+
+        let res = ListBlocksResponse { blocks, next_cursor };
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
@@ -460,12 +527,14 @@ impl JsonRpcHandlers {
     pub async fn get_blocks(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let req: GetBlocksRequest = value.parse_params()?;
-        let blocks = self
+        let mut blocks = self
             .state_store
             .with_read_tx(|tx| {
                 tx.blocks_get_paginated(
-                    req.limit,
+                    // Fetch one more than requested to determine if there is a next page
+                    if req.limit == 0 { 0 } else { req.limit.saturating_add(1) },
                     req.offset,
+                    req.cursor.as_ref(),
                     req.filter_index,
                     req.filter,
                     req.ordering_index,
@@ -473,20 +542,42 @@ impl JsonRpcHandlers {
                 )
             })
             .map_err(internal_error(answer_id))?;
-        let res = GetBlocksResponse { blocks };
+        let limit = usize::try_from(req.limit).unwrap_or(usize::MAX);
+        let next_cursor = if limit > 0 && blocks.len() > limit {
+            blocks.truncate(limit);
+            blocks.last().map(|b| *b.id())
+        } else {
+            None
+        };
+        let res = GetBlocksResponse { blocks, next_cursor };
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
     pub async fn get_templates(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let req: GetTemplatesRequest = value.parse_params()?;
+        let limit = req.limit as usize;
 
-        let templates = self
+        let mut templates = self
             .template_manager
-            .get_templates(req.limit as usize)
+            .get_templates(DbTemplateQuery {
+                // Fetch one more than requested to determine if there is a next page
+                limit: if limit == 0 { 0 } else { limit.saturating_add(1) },
+                after: req.cursor,
+                ordering: req.ordering,
+                author_public_key: req.author_public_key,
+                name: req.name,
+            })
             .await
             .map_err(internal_error(answer_id))?;
 
+        let next_cursor = if limit > 0 && templates.len() > limit {
+            templates.truncate(limit);
+            templates.last().map(|t| t.address)
+        } else {
+            None
+        };
+
         Ok(JsonRpcResponse::success(answer_id, GetTemplatesResponse {
             templates: templates
                 .into_iter()
@@ -494,8 +585,10 @@ impl JsonRpcHandlers {
                     name: t.name,
                     address: t.address,
                     binary_sha: t.binary_sha.to_vec(),
+                    author_public_key: t.author_public_key,
                 })
                 .collect(),
+            next_cursor,
         }))
     }
 
@@ -520,6 +613,7 @@ impl JsonRpcHandlers {
                 name: template.metadata.name,
                 address: template.metadata.address,
                 binary_sha: template.metadata.binary_sha.to_vec(),
+                author_public_key: template.metadata.author_public_key,
             },
             abi,
         }))
@@ -829,4 +923,20 @@ impl JsonRpcHandlers {
                 .collect(),
         }))
     }
+
+    pub async fn get_layer_one_outbox(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request = value.parse_params::<GetLayerOneOutboxRequest>()?;
+        let Some(ref outbox) = self.layer_one_outbox else {
+            return Err(not_found(
+                answer_id,
+                "Layer one outbox is not enabled. Set layer_one_wallet_grpc_url to submit layer one transactions \
+                 through the wallet.",
+            ));
+        };
+
+        Ok(JsonRpcResponse::success(answer_id, GetLayerOneOutboxResponse {
+            entries: outbox.entries(request.status).await,
+        }))
+    }
 }
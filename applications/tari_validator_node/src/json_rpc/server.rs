@@ -95,6 +95,7 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         "get_consensus_status" => handlers.get_consensus_status(value).await,
         // "get_network_committees" => handlers.get_network_committees(value).await,
         "get_fees" => handlers.get_validator_fees(value).await,
+        "get_layer_one_outbox" => handlers.get_layer_one_outbox(value).await,
         // Comms
         "add_peer" => handlers.add_peer(value).await,
         "get_comms_stats" => handlers.get_comms_stats(value).await,
@@ -0,0 +1,326 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::layer_one_transaction::LayerOneTransactionDef;
+use tari_epoch_manager::traits::LayerOneTransactionSubmitter;
+use tari_validator_node_client::types::{LayerOneOutboxEntry, LayerOneTransactionStatus};
+use tokio::{
+    io::AsyncWriteExt,
+    sync::{Mutex, MutexGuard, Notify},
+};
+
+const LOG_TARGET: &str = "tari::validator_node::l1_outbox";
+/// How long mined entries are kept in the outbox. Mined entries are only kept so that an identical transaction is not
+/// submitted again shortly after it was mined.
+const MINED_ENTRY_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A persistent queue of layer one transactions that are submitted to the base layer by the
+/// [LayerOneOutboxWorker](crate::l1_outbox_worker::LayerOneOutboxWorker). Every change is written to disk so that
+/// transactions are not lost if the node restarts before they are mined. Mined entries are pruned after
+/// [MINED_ENTRY_RETENTION].
+#[derive(Debug, Clone)]
+pub struct LayerOneOutbox {
+    path: Arc<PathBuf>,
+    state: Arc<Mutex<OutboxState>>,
+    notify: Arc<Notify>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OutboxState {
+    next_id: u64,
+    entries: Vec<LayerOneOutboxEntry>,
+}
+
+impl LayerOneOutbox {
+    /// Opens the outbox at the given path, loading any existing entries
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = if path.exists() {
+            let file = fs::File::open(&path)?;
+            serde_json::from_reader(file)?
+        } else {
+            OutboxState::default()
+        };
+        info!(
+            target: LOG_TARGET,
+            "Loaded layer one outbox from {} ({} entries)",
+            path.display(),
+            state.entries.len()
+        );
+        Ok(Self {
+            path: Arc::new(path),
+            state: Arc::new(Mutex::new(state)),
+            notify: Arc::new(Notify::new()),
+        })
+    }
+
+    /// Adds a transaction to the outbox and returns its ID. If an identical transaction is already in the outbox, it is
+    /// not added again and the ID of the existing entry is returned. An identical transaction that previously failed is
+    /// queued for submission again.
+    pub async fn enqueue(&self, transaction: LayerOneTransactionDef<serde_json::Value>) -> io::Result<u64> {
+        let now = unix_now();
+        let mut state = self.lock_state().await;
+        let existing = state
+            .entries
+            .iter_mut()
+            .find(|e| e.proof_type == transaction.proof_type && e.payload == transaction.payload);
+
+        let id = match existing {
+            Some(entry) => {
+                if entry.status != LayerOneTransactionStatus::Failed {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Layer one {} transaction is already in the outbox (id: {}, status: {:?})",
+                        entry.proof_type,
+                        entry.id,
+                        entry.status
+                    );
+                    return Ok(entry.id);
+                }
+                entry.status = LayerOneTransactionStatus::Pending;
+                entry.attempts = 0;
+                entry.wallet_tx_id = None;
+                entry.next_attempt_at = now;
+                entry.updated_at = now;
+                entry.id
+            },
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.entries.push(LayerOneOutboxEntry {
+                    id,
+                    proof_type: transaction.proof_type,
+                    payload: transaction.payload,
+                    status: LayerOneTransactionStatus::Pending,
+                    attempts: 0,
+                    wallet_tx_id: None,
+                    last_error: None,
+                    next_attempt_at: now,
+                    created_at: now,
+                    updated_at: now,
+                });
+                id
+            },
+        };
+
+        self.persist(&mut state).await?;
+        drop(state);
+        self.notify.notify_one();
+        Ok(id)
+    }
+
+    /// Returns all entries, optionally filtered by status
+    pub async fn entries(&self, status: Option<LayerOneTransactionStatus>) -> Vec<LayerOneOutboxEntry> {
+        self.lock_state()
+            .await
+            .entries
+            .iter()
+            .filter(|e| status.map_or(true, |s| e.status == s))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns pending entries that are due for a submission attempt
+    pub async fn due_for_submission(&self) -> Vec<LayerOneOutboxEntry> {
+        let now = unix_now();
+        self.lock_state()
+            .await
+            .entries
+            .iter()
+            .filter(|e| e.status == LayerOneTransactionStatus::Pending && e.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the time until the next pending entry is due, if any
+    pub async fn time_until_next_due(&self) -> Option<Duration> {
+        let now = unix_now();
+        self.lock_state()
+            .await
+            .entries
+            .iter()
+            .filter(|e| e.status == LayerOneTransactionStatus::Pending)
+            .map(|e| Duration::from_secs(e.next_attempt_at.saturating_sub(now)))
+            .min()
+    }
+
+    /// Updates an entry. The change is applied in memory even if it cannot be written to disk.
+    pub async fn update<F>(&self, id: u64, f: F) -> io::Result<()>
+    where F: FnOnce(&mut LayerOneOutboxEntry) {
+        let mut state = self.lock_state().await;
+        let Some(entry) = state.entries.iter_mut().find(|e| e.id == id) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Layer one outbox entry {} not found", id),
+            ));
+        };
+        f(entry);
+        entry.updated_at = unix_now();
+        self.persist(&mut state).await
+    }
+
+    /// Waits until a new transaction is added to the outbox
+    pub async fn wait_for_new_transaction(&self) {
+        self.notify.notified().await;
+    }
+
+    async fn lock_state(&self) -> MutexGuard<'_, OutboxState> {
+        self.state.lock().await
+    }
+
+    /// Prunes old mined entries and writes the outbox to disk. The state lock is held until the write completes so
+    /// that writes are never reordered.
+    async fn persist(&self, state: &mut OutboxState) -> io::Result<()> {
+        let prune_before = unix_now().saturating_sub(MINED_ENTRY_RETENTION.as_secs());
+        state
+            .entries
+            .retain(|e| e.status != LayerOneTransactionStatus::Mined || e.updated_at >= prune_before);
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let contents = serde_json::to_vec(state)?;
+        // Write to a temporary file and rename so that a crash never leaves a partially written outbox
+        let tmp_path = self.path.with_extension("json.tmp");
+        let mut file = tokio::fs::File::create(&tmp_path).await?;
+        file.write_all(&contents).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(tmp_path, &*self.path).await
+    }
+}
+
+/// Submits layer one transactions by adding them to the [LayerOneOutbox]
+#[derive(Debug, Clone)]
+pub struct OutboxLayerOneSubmitter {
+    outbox: LayerOneOutbox,
+}
+
+impl OutboxLayerOneSubmitter {
+    pub fn new(outbox: LayerOneOutbox) -> Self {
+        Self { outbox }
+    }
+}
+
+impl LayerOneTransactionSubmitter for OutboxLayerOneSubmitter {
+    type Error = io::Error;
+
+    async fn submit_transaction<T: Serialize + Send>(
+        &self,
+        proof: LayerOneTransactionDef<T>,
+    ) -> Result<(), Self::Error> {
+        let proof_type = proof.proof_type;
+        let transaction = LayerOneTransactionDef {
+            proof_type,
+            payload: serde_json::to_value(&proof.payload)?,
+        };
+        let id = self.outbox.enqueue(transaction).await?;
+        info!(
+            target: LOG_TARGET,
+            "Queued layer one {} transaction for submission (id: {})", proof_type, id
+        );
+        Ok(())
+    }
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tari_dan_common_types::layer_one_transaction::LayerOnePayloadType;
+
+    use super::*;
+
+    fn transaction(n: u64) -> LayerOneTransactionDef<serde_json::Value> {
+        LayerOneTransactionDef {
+            proof_type: LayerOnePayloadType::EvictionProof,
+            payload: json!({ "proof": n }),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_deduplicates_identical_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = LayerOneOutbox::open(dir.path().join("outbox.json")).unwrap();
+        let id1 = outbox.enqueue(transaction(1)).await.unwrap();
+        let id2 = outbox.enqueue(transaction(2)).await.unwrap();
+        assert_ne!(id1, id2);
+        assert_eq!(outbox.enqueue(transaction(1)).await.unwrap(), id1);
+        assert_eq!(outbox.entries(None).await.len(), 2);
+
+        // A failed transaction is requeued
+        outbox
+            .update(id1, |e| {
+                e.status = LayerOneTransactionStatus::Failed;
+                e.attempts = 10;
+            })
+            .await
+            .unwrap();
+        assert_eq!(outbox.enqueue(transaction(1)).await.unwrap(), id1);
+        let pending = outbox.entries(Some(LayerOneTransactionStatus::Pending)).await;
+        assert_eq!(pending.len(), 2);
+        assert!(pending.iter().all(|e| e.attempts == 0));
+    }
+
+    #[tokio::test]
+    async fn it_persists_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        let outbox = LayerOneOutbox::open(&path).unwrap();
+        let id = outbox.enqueue(transaction(1)).await.unwrap();
+        outbox
+            .update(id, |e| {
+                e.status = LayerOneTransactionStatus::Submitted;
+                e.wallet_tx_id = Some(123);
+            })
+            .await
+            .unwrap();
+
+        let outbox = LayerOneOutbox::open(&path).unwrap();
+        let entries = outbox.entries(Some(LayerOneTransactionStatus::Submitted)).await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].wallet_tx_id, Some(123));
+        // IDs are not reused
+        assert_ne!(outbox.enqueue(transaction(2)).await.unwrap(), id);
+    }
+
+    #[tokio::test]
+    async fn it_prunes_old_mined_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        let outbox = LayerOneOutbox::open(&path).unwrap();
+        let mined_id = outbox.enqueue(transaction(1)).await.unwrap();
+        let recently_mined_id = outbox.enqueue(transaction(2)).await.unwrap();
+        for id in [mined_id, recently_mined_id] {
+            outbox
+                .update(id, |e| e.status = LayerOneTransactionStatus::Mined)
+                .await
+                .unwrap();
+        }
+        outbox.lock_state().await.entries[0].updated_at = 0;
+
+        // Pruning happens on the next write
+        outbox.enqueue(transaction(3)).await.unwrap();
+        let ids = outbox.entries(None).await.iter().map(|e| e.id).collect::<Vec<_>>();
+        assert!(!ids.contains(&mined_id));
+        assert!(ids.contains(&recently_mined_id));
+
+        let outbox = LayerOneOutbox::open(&path).unwrap();
+        assert_eq!(outbox.entries(None).await.len(), 2);
+    }
+}
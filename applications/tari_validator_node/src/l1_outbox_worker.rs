@@ -0,0 +1,438 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{cmp, time::Duration};
+
+use anyhow::anyhow;
+use log::*;
+use minotari_app_grpc::tari_rpc as grpc;
+use minotari_wallet_grpc_client::WalletGrpcClient;
+use tari_dan_common_types::layer_one_transaction::LayerOnePayloadType;
+use tari_shutdown::ShutdownSignal;
+use tari_sidechain::EvictionProof;
+use tari_validator_node_client::types::{LayerOneOutboxEntry, LayerOneTransactionStatus};
+use tokio::{task, task::JoinHandle, time};
+use url::Url;
+
+use crate::l1_outbox::{unix_now, LayerOneOutbox};
+
+const LOG_TARGET: &str = "tari::validator_node::l1_outbox_worker";
+
+/// How often submitted transactions are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(10);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+const MAX_ATTEMPTS: u32 = 10;
+
+pub fn spawn_outbox_worker(
+    outbox: LayerOneOutbox,
+    wallet_grpc_url: Url,
+    fee_per_gram: u64,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<anyhow::Result<()>> {
+    task::spawn(
+        LayerOneOutboxWorker {
+            outbox,
+            wallet_grpc_url,
+            fee_per_gram,
+        }
+        .run(shutdown),
+    )
+}
+
+/// Submits transactions in the [LayerOneOutbox] to the base layer through the Minotari wallet and tracks them until
+/// they are mined. Failed submissions are retried with exponential backoff.
+///
+/// Each submission attempt is recorded in the outbox before the wallet is called and is tagged with a unique wallet
+/// message. If the node stops while a submission is in flight, the attempt is matched against the wallet's
+/// transactions by its message on the next run, so a transaction that the wallet accepted is not submitted twice.
+pub struct LayerOneOutboxWorker {
+    outbox: LayerOneOutbox,
+    wallet_grpc_url: Url,
+    fee_per_gram: u64,
+}
+
+impl LayerOneOutboxWorker {
+    pub async fn run(self, mut shutdown: ShutdownSignal) -> anyhow::Result<()> {
+        info!(
+            target: LOG_TARGET,
+            "Layer one outbox worker started. Submitting transactions to wallet at {}", self.wallet_grpc_url
+        );
+        loop {
+            self.reconcile_interrupted_submissions().await;
+            self.submit_due_transactions().await;
+            self.check_submitted_transactions().await;
+
+            let wait = self
+                .outbox
+                .time_until_next_due()
+                .await
+                .map_or(POLL_INTERVAL, |due| cmp::min(due, POLL_INTERVAL));
+            tokio::select! {
+                _ = self.outbox.wait_for_new_transaction() => {},
+                _ = time::sleep(wait) => {},
+                _ = shutdown.wait() => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn submit_due_transactions(&self) {
+        for mut entry in self.outbox.due_for_submission().await {
+            entry.attempts += 1;
+            let attempts = entry.attempts;
+            // Record the attempt before calling the wallet so that an interrupted submission is reconciled rather than
+            // blindly submitted again
+            if let Err(err) = self
+                .outbox
+                .update(entry.id, |e| {
+                    e.status = LayerOneTransactionStatus::Submitting;
+                    e.attempts = attempts;
+                })
+                .await
+            {
+                error!(
+                    target: LOG_TARGET,
+                    "Failed to record submission of layer one outbox entry {}: {}. Not submitting.", entry.id, err
+                );
+                continue;
+            }
+
+            match self.submit(&entry).await {
+                Ok(tx_id) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Submitted layer one {} transaction {} (wallet tx_id: {})", entry.proof_type, entry.id, tx_id
+                    );
+                    self.update_entry(entry.id, |e| {
+                        e.status = LayerOneTransactionStatus::Submitted;
+                        e.wallet_tx_id = Some(tx_id);
+                        e.last_error = None;
+                    })
+                    .await;
+                },
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to submit layer one {} transaction {}: {}", entry.proof_type, entry.id, err
+                    );
+                    self.update_entry(entry.id, |e| schedule_retry(e, err.to_string()))
+                        .await;
+                },
+            }
+        }
+    }
+
+    /// Resolves entries that were left submitting by a previous run. An entry whose submission message is found in the
+    /// wallet is marked as submitted, otherwise the submission never reached the wallet and is retried. Entries are
+    /// left as they are if the wallet cannot be queried.
+    async fn reconcile_interrupted_submissions(&self) {
+        let submitting = self.outbox.entries(Some(LayerOneTransactionStatus::Submitting)).await;
+        if submitting.is_empty() {
+            return;
+        }
+
+        let transactions = match self.get_completed_transactions().await {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to get transactions from wallet to reconcile {} interrupted layer one submission(s): {}",
+                    submitting.len(),
+                    err
+                );
+                return;
+            },
+        };
+
+        for entry in submitting {
+            match find_submission(&entry, &transactions) {
+                Some(tx) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Interrupted layer one {} transaction {} was submitted (wallet tx_id: {})",
+                        entry.proof_type,
+                        entry.id,
+                        tx.tx_id
+                    );
+                    let tx_id = tx.tx_id;
+                    self.update_entry(entry.id, |e| {
+                        e.status = LayerOneTransactionStatus::Submitted;
+                        e.wallet_tx_id = Some(tx_id);
+                        e.last_error = None;
+                    })
+                    .await;
+                },
+                None => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Interrupted layer one {} transaction {} was not submitted to the wallet", entry.proof_type, entry.id
+                    );
+                    self.update_entry(entry.id, |e| {
+                        schedule_retry(e, "Submission was interrupted".to_string())
+                    })
+                    .await;
+                },
+            }
+        }
+    }
+
+    async fn check_submitted_transactions(&self) {
+        let submitted = self.outbox.entries(Some(LayerOneTransactionStatus::Submitted)).await;
+        let tx_ids = submitted.iter().filter_map(|e| e.wallet_tx_id).collect::<Vec<_>>();
+        if tx_ids.is_empty() {
+            return;
+        }
+
+        let transactions = match self.get_transaction_info(tx_ids).await {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to get layer one transaction status from wallet: {}", err
+                );
+                return;
+            },
+        };
+
+        for entry in submitted {
+            let Some(info) = transactions.iter().find(|t| Some(t.tx_id) == entry.wallet_tx_id) else {
+                continue;
+            };
+            let status = info.status();
+            match status {
+                grpc::TransactionStatus::MinedConfirmed => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Layer one {} transaction {} mined", entry.proof_type, entry.id
+                    );
+                },
+                grpc::TransactionStatus::Rejected | grpc::TransactionStatus::NotFound => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Layer one {} transaction {} was not accepted by the wallet ({:?})",
+                        entry.proof_type,
+                        entry.id,
+                        status
+                    );
+                },
+                _ => continue,
+            }
+            self.update_entry(entry.id, |e| apply_wallet_status(e, status)).await;
+        }
+    }
+
+    /// Updates an outbox entry. The update is applied in memory even if it cannot be written to disk, so a write
+    /// error is logged rather than stopping the worker.
+    async fn update_entry<F>(&self, id: u64, f: F)
+    where F: FnOnce(&mut LayerOneOutboxEntry) {
+        if let Err(err) = self.outbox.update(id, f).await {
+            error!(
+                target: LOG_TARGET,
+                "Failed to update layer one outbox entry {}: {}", id, err
+            );
+        }
+    }
+
+    async fn submit(&self, entry: &LayerOneOutboxEntry) -> anyhow::Result<u64> {
+        match entry.proof_type {
+            LayerOnePayloadType::EvictionProof => {
+                let proof = serde_json::from_value::<EvictionProof>(entry.payload.clone())?;
+                let resp = self
+                    .connect_wallet()
+                    .await?
+                    .submit_validator_eviction_proof(grpc::SubmitValidatorEvictionProofRequest {
+                        proof: Some((&proof).into()),
+                        fee_per_gram: self.fee_per_gram,
+                        message: submission_message(entry),
+                        sidechain_deployment_key: vec![],
+                    })
+                    .await?
+                    .into_inner();
+                Ok(resp.tx_id)
+            },
+        }
+    }
+
+    async fn get_transaction_info(&self, tx_ids: Vec<u64>) -> anyhow::Result<Vec<grpc::TransactionInfo>> {
+        let resp = self
+            .connect_wallet()
+            .await?
+            .get_transaction_info(grpc::GetTransactionInfoRequest {
+                transaction_ids: tx_ids,
+            })
+            .await?
+            .into_inner();
+        Ok(resp.transactions)
+    }
+
+    async fn get_completed_transactions(&self) -> anyhow::Result<Vec<grpc::TransactionInfo>> {
+        let mut stream = self
+            .connect_wallet()
+            .await?
+            .get_completed_transactions(grpc::GetCompletedTransactionsRequest::default())
+            .await?
+            .into_inner();
+        let mut transactions = Vec::new();
+        while let Some(resp) = stream.message().await? {
+            transactions.extend(resp.transaction);
+        }
+        Ok(transactions)
+    }
+
+    async fn connect_wallet(&self) -> anyhow::Result<WalletGrpcClient<tonic::transport::Channel>> {
+        WalletGrpcClient::connect(self.wallet_grpc_url.as_str())
+            .await
+            .map_err(|err| anyhow!("Failed to connect to wallet at {}: {}", self.wallet_grpc_url, err))
+    }
+}
+
+/// The wallet message of a submission attempt. It is unique per outbox entry and attempt so that an interrupted
+/// submission can be found in the wallet.
+fn submission_message(entry: &LayerOneOutboxEntry) -> String {
+    format!(
+        "Validator: Automatically submitted {} transaction (outbox entry {}, attempt {})",
+        entry.proof_type, entry.id, entry.attempts
+    )
+}
+
+/// Returns the most recent wallet transaction for the entry's current submission attempt, if any
+fn find_submission<'a>(
+    entry: &LayerOneOutboxEntry,
+    transactions: &'a [grpc::TransactionInfo],
+) -> Option<&'a grpc::TransactionInfo> {
+    let message = submission_message(entry);
+    transactions
+        .iter()
+        .filter(|tx| tx.message == message)
+        .max_by_key(|tx| tx.timestamp)
+}
+
+/// Applies the wallet status of a submitted transaction to its entry. A transaction that the wallet rejected or no
+/// longer knows about is submitted again.
+fn apply_wallet_status(entry: &mut LayerOneOutboxEntry, status: grpc::TransactionStatus) {
+    match status {
+        grpc::TransactionStatus::MinedConfirmed => {
+            entry.status = LayerOneTransactionStatus::Mined;
+        },
+        grpc::TransactionStatus::Rejected | grpc::TransactionStatus::NotFound => {
+            entry.wallet_tx_id = None;
+            schedule_retry(entry, format!("Wallet transaction status: {:?}", status));
+        },
+        _ => {},
+    }
+}
+
+/// Returns the entry to pending with an exponential backoff, or marks it as failed once all attempts are used. The
+/// failed attempt has already been counted when it was submitted.
+fn schedule_retry(entry: &mut LayerOneOutboxEntry, error: String) {
+    entry.last_error = Some(error);
+    if entry.attempts >= MAX_ATTEMPTS {
+        error!(
+            target: LOG_TARGET,
+            "Giving up on layer one {} transaction {} after {} attempts", entry.proof_type, entry.id, entry.attempts
+        );
+        entry.status = LayerOneTransactionStatus::Failed;
+        return;
+    }
+    entry.status = LayerOneTransactionStatus::Pending;
+    entry.next_attempt_at = unix_now() + retry_delay(entry.attempts).as_secs();
+}
+
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    cmp::min(MIN_RETRY_DELAY.saturating_mul(factor), MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn entry(status: LayerOneTransactionStatus, attempts: u32) -> LayerOneOutboxEntry {
+        LayerOneOutboxEntry {
+            id: 1,
+            proof_type: LayerOnePayloadType::EvictionProof,
+            payload: json!({ "proof": 1 }),
+            status,
+            attempts,
+            wallet_tx_id: None,
+            last_error: None,
+            next_attempt_at: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn it_backs_off_exponentially_up_to_the_maximum_delay() {
+        assert_eq!(retry_delay(0), MIN_RETRY_DELAY);
+        assert_eq!(retry_delay(1), MIN_RETRY_DELAY);
+        assert_eq!(retry_delay(2), MIN_RETRY_DELAY * 2);
+        assert_eq!(retry_delay(3), MIN_RETRY_DELAY * 4);
+        assert_eq!(retry_delay(8), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn it_schedules_retries_until_all_attempts_are_used() {
+        let mut e = entry(LayerOneTransactionStatus::Submitting, 2);
+        let now = unix_now();
+        schedule_retry(&mut e, "error".to_string());
+        assert_eq!(e.status, LayerOneTransactionStatus::Pending);
+        assert_eq!(e.attempts, 2);
+        assert_eq!(e.last_error.as_deref(), Some("error"));
+        assert!(e.next_attempt_at >= now + retry_delay(2).as_secs());
+
+        let mut e = entry(LayerOneTransactionStatus::Submitting, MAX_ATTEMPTS);
+        schedule_retry(&mut e, "error".to_string());
+        assert_eq!(e.status, LayerOneTransactionStatus::Failed);
+    }
+
+    #[test]
+    fn it_retries_transactions_rejected_by_the_wallet() {
+        let mut e = entry(LayerOneTransactionStatus::Submitted, 1);
+        e.wallet_tx_id = Some(123);
+        let now = unix_now();
+        apply_wallet_status(&mut e, grpc::TransactionStatus::Rejected);
+        assert_eq!(e.status, LayerOneTransactionStatus::Pending);
+        assert_eq!(e.wallet_tx_id, None);
+        assert!(e.last_error.is_some());
+        assert!(e.next_attempt_at >= now + retry_delay(1).as_secs());
+
+        // Transactions that are still in progress are left as they are
+        let mut e = entry(LayerOneTransactionStatus::Submitted, 1);
+        e.wallet_tx_id = Some(123);
+        apply_wallet_status(&mut e, grpc::TransactionStatus::Broadcast);
+        assert_eq!(e.status, LayerOneTransactionStatus::Submitted);
+        assert_eq!(e.wallet_tx_id, Some(123));
+
+        apply_wallet_status(&mut e, grpc::TransactionStatus::MinedConfirmed);
+        assert_eq!(e.status, LayerOneTransactionStatus::Mined);
+    }
+
+    #[test]
+    fn it_finds_the_wallet_transaction_of_an_interrupted_submission() {
+        let e = entry(LayerOneTransactionStatus::Submitting, 2);
+        let transaction = |tx_id, message, timestamp| grpc::TransactionInfo {
+            tx_id,
+            message,
+            timestamp,
+            ..Default::default()
+        };
+        let mut previous_attempt = e.clone();
+        previous_attempt.attempts = 1;
+        let transactions = vec![
+            transaction(1, submission_message(&previous_attempt), 1),
+            transaction(2, "Some other transaction".to_string(), 2),
+            transaction(3, submission_message(&e), 3),
+        ];
+        assert_eq!(find_submission(&e, &transactions).map(|tx| tx.tx_id), Some(3));
+
+        let mut not_submitted = e.clone();
+        not_submitted.attempts = 3;
+        assert!(find_submission(&not_submitted, &transactions).is_none());
+    }
+}
@@ -0,0 +1,33 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::io;
+
+use serde::Serialize;
+use tari_dan_common_types::layer_one_transaction::LayerOneTransactionDef;
+use tari_epoch_manager::traits::LayerOneTransactionSubmitter;
+
+use crate::{file_l1_submitter::FileLayerOneSubmitter, l1_outbox::OutboxLayerOneSubmitter};
+
+/// Selects how the validator node submits layer one transactions
+#[derive(Debug, Clone)]
+pub enum LayerOneSubmitter {
+    /// Write transactions to files for an external process (e.g. tari_watcher) to submit
+    File(FileLayerOneSubmitter),
+    /// Submit transactions directly through the Minotari wallet
+    Outbox(OutboxLayerOneSubmitter),
+}
+
+impl LayerOneTransactionSubmitter for LayerOneSubmitter {
+    type Error = io::Error;
+
+    async fn submit_transaction<T: Serialize + Send>(
+        &self,
+        proof: LayerOneTransactionDef<T>,
+    ) -> Result<(), Self::Error> {
+        match self {
+            LayerOneSubmitter::File(submitter) => submitter.submit_transaction(proof).await,
+            LayerOneSubmitter::Outbox(submitter) => submitter.submit_transaction(proof).await,
+        }
+    }
+}
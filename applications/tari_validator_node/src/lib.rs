@@ -35,6 +35,9 @@ mod p2p;
 mod substate_resolver;
 
 mod file_l1_submitter;
+mod l1_outbox;
+mod l1_outbox_worker;
+mod l1_submitter;
 mod state_bootstrap;
 pub mod transaction_validators;
 mod validator;
@@ -58,13 +61,16 @@ use tari_shutdown::ShutdownSignal;
 use tokio::task;
 pub use validator_registration_file::ValidatorRegistrationFile;
 
-pub use crate::config::{ApplicationConfig, ValidatorNodeConfig};
 use crate::{
     bootstrap::{spawn_services, Services},
     dan_node::DanNode,
     http_ui::server::run_http_ui_server,
     json_rpc::{spawn_json_rpc, JsonRpcHandlers},
 };
+pub use crate::{
+    config::{ApplicationConfig, ValidatorNodeConfig},
+    consensus::replay::{run_message_replay, ReplayConfig, ReplayReport},
+};
 
 const LOG_TARGET: &str = "tari::validator_node::app";
 
@@ -22,7 +22,7 @@
 
 mod cli;
 
-use std::{fs, panic, process};
+use std::{fs, panic, process, time::Duration};
 
 use clap::Parser;
 use log::*;
@@ -32,7 +32,13 @@ use tari_common::{
 };
 use tari_dan_app_utilities::configuration::load_configuration;
 use tari_shutdown::Shutdown;
-use tari_validator_node::{cli::Cli, run_validator_node, ApplicationConfig};
+use tari_validator_node::{
+    cli::{Cli, Command, ReplayArgs},
+    run_message_replay,
+    run_validator_node,
+    ApplicationConfig,
+    ReplayConfig,
+};
 
 const LOG_TARGET: &str = "tari::validator_node::app";
 
@@ -81,6 +87,10 @@ async fn main_inner() -> Result<(), ExitError> {
         eprintln!("{}", e);
     }
 
+    if let Some(Command::Replay(args)) = &cli.command {
+        return run_replay(&config, args).await;
+    }
+
     match run_validator_node(&config, shutdown.to_signal()).await {
         Ok(_) => info!(target: LOG_TARGET, "Validator node shutdown successfully"),
         Err(e) => match e.downcast() {
@@ -99,3 +109,26 @@ async fn main_inner() -> Result<(), ExitError> {
 
     Ok(())
 }
+
+async fn run_replay(config: &ApplicationConfig, args: &ReplayArgs) -> Result<(), ExitError> {
+    let replay_config = ReplayConfig {
+        message_log_path: args
+            .message_log
+            .clone()
+            .unwrap_or_else(|| config.validator_node.message_log_path()),
+        state_db_path: args.state_db.clone(),
+        idle_timeout: Duration::from_millis(args.idle_timeout_ms),
+        realtime: args.realtime,
+    };
+    let report = run_message_replay(config, replay_config)
+        .await
+        .map_err(|e| ExitError::new(ExitCode::UnknownError, e))?;
+    println!("{}", report);
+    if !report.is_consistent() {
+        return Err(ExitError::new(
+            ExitCode::UnknownError,
+            "Replay diverged from the recorded messages",
+        ));
+    }
+    Ok(())
+}
@@ -108,14 +108,25 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
         T: Into<HotstuffMessage> + Send,
     {
         let message = message.into();
+        let addresses = addresses
+            .into_iter()
+            .filter(|addr| *addr != self.our_node_addr)
+            .collect::<Vec<_>>();
 
+        self.msg_logger.log_outbound_message(
+            "multicast",
+            &addresses
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            message.as_type_str(),
+            "",
+            &message,
+        );
         self.networking
             .send_multicast(
-                addresses
-                    .into_iter()
-                    .filter(|addr| *addr != self.our_node_addr)
-                    .map(|addr| addr.as_peer_id())
-                    .collect::<Vec<_>>(),
+                addresses.iter().map(|addr| addr.as_peer_id()).collect::<Vec<_>>(),
                 proto::consensus::HotStuffMessage::from(&message),
             )
             .await
@@ -128,6 +139,13 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
     where T: Into<HotstuffMessage> + Send {
         let message = message.into();
 
+        self.msg_logger.log_outbound_message(
+            "broadcast",
+            &shard_group.to_string(),
+            message.as_type_str(),
+            "",
+            &message,
+        );
         self.consensus_gossip
             .publish(shard_group, message)
             .await
@@ -35,12 +35,13 @@ use tari_template_lib::{
     constants::{
         CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
         PUBLIC_IDENTITY_RESOURCE_ADDRESS,
+        XTR_DIVISIBILITY,
         XTR_FAUCET_COMPONENT_ADDRESS,
         XTR_FAUCET_VAULT_ADDRESS,
     },
     models::{Amount, EntityId, Metadata},
     prelude::ResourceType,
-    resource::TOKEN_SYMBOL,
+    resource::{TransferHooks, TOKEN_SYMBOL},
 };
 
 pub fn has_bootstrapped<TTx: StateStoreReadTransaction>(tx: &TTx) -> Result<bool, StorageError> {
@@ -71,6 +72,8 @@ where
         Metadata::from([(TOKEN_SYMBOL, "ID".to_string())]),
         None,
         None,
+        0,
+        TransferHooks::default(),
     );
     create_substate(
         tx,
@@ -89,6 +92,8 @@ where
         Metadata::from([(TOKEN_SYMBOL, "XTR".to_string())]),
         None,
         None,
+        XTR_DIVISIBILITY,
+        TransferHooks::default(),
     );
 
     // Create faucet component
@@ -73,6 +73,7 @@ pub async fn handle_create(
         owner_rule: None,
         access_rules: None,
         workspace_bucket: None,
+        policy: None,
     };
 
     let common = CommonSubmitArgs {
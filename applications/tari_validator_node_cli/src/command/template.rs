@@ -74,7 +74,12 @@ async fn handle_get(template_address: TemplateAddress, mut client: ValidatorNode
 }
 
 async fn handle_list(mut client: ValidatorNodeClient) -> Result<(), anyhow::Error> {
-    let templates = client.get_active_templates(GetTemplatesRequest { limit: 10 }).await?;
+    let templates = client
+        .get_active_templates(GetTemplatesRequest {
+            limit: 10,
+            ..Default::default()
+        })
+        .await?;
 
     let mut table = Table::new();
     table.set_titles(vec!["Name", "Address", "Status"]).enable_row_count();
@@ -595,10 +595,7 @@ fn load_inputs(
                 .ok_or_else(|| anyhow!("Component {} not found", component_address))?;
             println!("Loaded inputs");
             println!("- {} v{}", addr, component.latest_version());
-            inputs.push(SubstateRequirement {
-                substate_id: addr,
-                version: Some(component.latest_version()),
-            });
+            inputs.push(SubstateRequirement::versioned(addr, component.latest_version()));
             for child in component.get_children() {
                 println!("  - {} v{:?}", child.substate_id, child.version);
             }
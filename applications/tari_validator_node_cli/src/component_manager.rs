@@ -91,10 +91,7 @@ impl ComponentManager {
                 addr @ SubstateId::Vault(_) |
                 addr @ SubstateId::NonFungible(_) |
                 addr @ SubstateId::NonFungibleIndex(_) => {
-                    children.push(SubstateRequirement {
-                        substate_id: addr.clone(),
-                        version: Some(substate.version()),
-                    });
+                    children.push(SubstateRequirement::versioned(addr.clone(), substate.version()));
                 },
                 addr => {
                     todo!("{} not expected", addr);
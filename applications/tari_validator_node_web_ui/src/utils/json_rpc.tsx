@@ -38,6 +38,7 @@ import type {
   VNGetIdentityResponse,
   GetMempoolStatsResponse,
   GetNetworkCommitteeResponse,
+  GetRecentTransactionsRequest,
   GetRecentTransactionsResponse,
   GetShardKeyRequest,
   GetShardKeyResponse,
@@ -109,7 +110,8 @@ async function jsonRpc(method: string, params: any = null) {
 // Transaction
 export const submitTransaction = (request: VNSubmitTransactionRequest): Promise<VNSubmitTransactionResponse> =>
   jsonRpc("submit_transaction", request);
-export const getRecentTransactions = (): Promise<GetRecentTransactionsResponse> => jsonRpc("get_recent_transactions");
+export const getRecentTransactions = (request: GetRecentTransactionsRequest = {}): Promise<GetRecentTransactionsResponse> =>
+  jsonRpc("get_recent_transactions", request);
 export const getTransaction = (request: GetTransactionRequest): Promise<GetTransactionResponse> =>
   jsonRpc("get_transaction", request);
 export const getTransactionResult = (request: VNGetTransactionResultRequest): Promise<VNGetTransactionResultResponse> =>
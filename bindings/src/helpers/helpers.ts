@@ -115,6 +115,10 @@ export function rejectReasonToString(reason: RejectReason | null): string {
     const r = reason.ForeignShardGroupDecidedToAbort;
     return `ForeignShardGroupDecidedToAbort(${r.start_shard}-${r.end_shard}, ${r.abort_reason})`;
   }
+  if ("ForeignProposalTimeout" in reason) {
+    const r = reason.ForeignProposalTimeout;
+    return `ForeignProposalTimeout(${r.start_shard}-${r.end_shard}, ${r.timeout_blocks} blocks)`;
+  }
   if ("InvalidTransaction" in reason) {
     return `InvalidTransaction(${reason.InvalidTransaction})`;
   }
@@ -3,6 +3,8 @@
 
 export * from "./types/AbortReason";
 export * from "./types/AccessRule";
+export * from "./types/AccountMethodRule";
+export * from "./types/AccountPolicy";
 export * from "./types/Account";
 export * from "./types/Amount";
 export * from "./types/ArgDef";
@@ -32,6 +34,7 @@ export * from "./types/EntityId";
 export * from "./types/Epoch";
 export * from "./types/Era";
 export * from "./types/Event";
+export * from "./types/EventDef";
 export * from "./types/EvictNodeAtom";
 export * from "./types/EvidenceInputLockData";
 export * from "./types/Evidence";
@@ -49,6 +52,7 @@ export * from "./types/ForeignProposalAtom";
 export * from "./types/FunctionDef";
 export * from "./types/IndexedValue";
 export * from "./types/IndexedWellKnownTypes";
+export * from "./types/InputLockIntent";
 export * from "./types/InstructionResult";
 export * from "./types/Instruction";
 export * from "./types/JrpcPermissions";
@@ -86,9 +90,12 @@ export * from "./types/Resource";
 export * from "./types/ResourceType";
 export * from "./types/RestrictedAccessRule";
 export * from "./types/RuleRequirement";
+export * from "./types/SessionKey";
 export * from "./types/ShardGroupEvidence";
 export * from "./types/ShardGroup";
+export * from "./types/SignerBitmap";
 export * from "./types/Shard";
+export * from "./types/SpendingLimit";
 export * from "./types/SubstateAddress";
 export * from "./types/SubstateDestroyed";
 export * from "./types/SubstateDiff";
@@ -110,8 +117,13 @@ export * from "./types/TransactionResult";
 export * from "./types/TransactionSealSignature";
 export * from "./types/TransactionSignature";
 export * from "./types/TransactionStatus";
+export * from "./types/TransactionStatusFilter";
 export * from "./types/Transaction";
 export * from "./types/TransactionV1";
+export * from "./types/TransferAction";
+export * from "./types/TransferHook";
+export * from "./types/TransferHookContext";
+export * from "./types/TransferHooks";
 export * from "./types/Type";
 export * from "./types/UnclaimedConfidentialOutputAddress";
 export * from "./types/UnclaimedConfidentialOutput";
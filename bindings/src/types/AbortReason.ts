@@ -14,4 +14,5 @@ export type AbortReason =
   | "OneOrMoreInputsNotFound"
   | "ForeignShardGroupDecidedToAbort"
   | "FeesNotPaid"
-  | "EarlyAbort";
+  | "EarlyAbort"
+  | "ForeignProposalTimeout";
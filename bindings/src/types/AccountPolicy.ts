@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountMethodRule } from "./AccountMethodRule";
+import type { ComponentAddress } from "./ComponentAddress";
+import type { ResourceAddress } from "./ResourceAddress";
+import type { SessionKey } from "./SessionKey";
+import type { SpendingLimit } from "./SpendingLimit";
+
+export interface AccountPolicy {
+  method_rules: Record<string, AccountMethodRule>;
+  spending_limits: Record<ResourceAddress, SpendingLimit>;
+  session_keys: Array<SessionKey>;
+  allowed_destinations: Array<ComponentAddress> | null;
+  guardians: Array<Array<number>>;
+  recovery_threshold: number;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArgDef } from "./ArgDef";
+
+export interface EventDef {
+  name: string;
+  topic: string;
+  fields: Array<ArgDef>;
+}
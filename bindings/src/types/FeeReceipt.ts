@@ -6,4 +6,5 @@ export interface FeeReceipt {
   total_fee_payment: Amount;
   total_fees_paid: Amount;
   cost_breakdown: FeeBreakdown;
+  storage_rebate: Amount;
 }
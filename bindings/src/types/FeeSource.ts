@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type FeeSource = "Initial" | "RuntimeCall" | "Storage" | "Events" | "Logs";
+export type FeeSource = "Initial" | "RuntimeCall" | "Storage" | "Events" | "Logs" | "TransactionWeight" | "Crypto";
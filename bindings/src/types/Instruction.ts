@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountPolicy } from "./AccountPolicy";
 import type { Amount } from "./Amount";
 import type { Arg } from "./Arg";
 import type { ComponentAccessRules } from "./ComponentAccessRules";
@@ -15,10 +16,11 @@ export type Instruction =
         owner_rule: OwnerRule | null;
         access_rules: ComponentAccessRules | null;
         workspace_bucket: string | null;
+        policy: AccountPolicy | null;
       };
     }
-  | { CallFunction: { template_address: Uint8Array; function: string; args: Array<Arg> } }
-  | { CallMethod: { component_address: ComponentAddress; method: string; args: Array<string> } }
+  | { CallFunction: { template_address: string; function: string; args: Array<Arg> } }
+  | { CallMethod: { component_address: ComponentAddress; method: string; args: Array<Arg | string> } }
   | { PutLastInstructionOutputOnWorkspace: { key: Array<number> } }
   | { EmitLog: { level: LogLevel; message: string } }
   | { ClaimBurn: { claim: ConfidentialClaim } }
@@ -3,7 +3,7 @@ import type { Epoch } from "./Epoch";
 import type { NodeHeight } from "./NodeHeight";
 import type { QuorumDecision } from "./QuorumDecision";
 import type { ShardGroup } from "./ShardGroup";
-import type { ValidatorSignature } from "./ValidatorSignature";
+import type { SignerBitmap } from "./SignerBitmap";
 
 export interface QuorumCertificate {
   qc_id: string;
@@ -13,7 +13,8 @@ export interface QuorumCertificate {
   block_height: NodeHeight;
   epoch: Epoch;
   shard_group: ShardGroup;
-  signatures: Array<ValidatorSignature>;
+  signers: SignerBitmap;
+  signatures: Array<{ public_nonce: string; signature: string }>;
   leaf_hashes: Array<string>;
   decision: QuorumDecision;
   is_shares_processed: boolean;
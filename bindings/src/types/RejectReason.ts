@@ -9,4 +9,5 @@ export type RejectReason =
   | "ForeignPledgeInputConflict"
   | { ForeignShardGroupDecidedToAbort: { start_shard: number; end_shard: number; abort_reason: string } }
   | { FeesNotPaid: string }
+  | { ForeignProposalTimeout: { start_shard: number; end_shard: number; timeout_blocks: number } }
   | "Unknown";
@@ -2,9 +2,11 @@
 import type { Amount } from "./Amount";
 import type { AuthHook } from "./AuthHook";
 import type { Metadata } from "./Metadata";
+import type { NonFungibleId } from "./NonFungibleId";
 import type { OwnerRule } from "./OwnerRule";
 import type { ResourceAccessRules } from "./ResourceAccessRules";
 import type { ResourceType } from "./ResourceType";
+import type { TransferHooks } from "./TransferHooks";
 
 export interface Resource {
   resource_type: ResourceType;
@@ -15,4 +17,7 @@ export interface Resource {
   total_supply: Amount;
   view_key: string | null;
   auth_hook: AuthHook | null;
+  divisibility: number;
+  transfer_hooks: TransferHooks;
+  destroyed_non_fungible_ids: Array<NonFungibleId>;
 }
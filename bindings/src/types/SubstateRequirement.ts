@@ -1,7 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InputLockIntent } from "./InputLockIntent";
 import type { SubstateId } from "./SubstateId";
 
 export interface SubstateRequirement {
   substate_id: SubstateId;
   version: number | null;
+  lock_intent?: InputLockIntent;
 }
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "./Amount";
+import type { ComponentAddress } from "./ComponentAddress";
+import type { NonFungibleId } from "./NonFungibleId";
+import type { ResourceAddress } from "./ResourceAddress";
+import type { TransferAction } from "./TransferAction";
+import type { VaultId } from "./VaultId";
+
+export interface TransferHookContext {
+  action: TransferAction;
+  resource_address: ResourceAddress;
+  vault_id: VaultId;
+  amount: Amount;
+  non_fungible_ids: Array<NonFungibleId>;
+  caller_component: ComponentAddress | null;
+}
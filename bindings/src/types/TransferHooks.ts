@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransferHook } from "./TransferHook";
+
+export interface TransferHooks {
+  on_deposit: TransferHook | null;
+  on_withdraw: TransferHook | null;
+}
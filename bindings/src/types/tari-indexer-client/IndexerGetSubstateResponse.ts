@@ -7,4 +7,5 @@ export interface IndexerGetSubstateResponse {
   version: number;
   substate: SubstateValue;
   created_by_transaction: string;
+  display_amount: string | null;
 }
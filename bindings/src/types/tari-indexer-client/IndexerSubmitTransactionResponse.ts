@@ -1,7 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { IndexerTransactionFinalizedResult } from "./IndexerTransactionFinalizedResult";
+import type { SubstateRequirement } from "../SubstateRequirement";
 
 export interface IndexerSubmitTransactionResponse {
   transaction_id: string;
   result: IndexerTransactionFinalizedResult;
+  inferred_inputs: Array<SubstateRequirement>;
 }
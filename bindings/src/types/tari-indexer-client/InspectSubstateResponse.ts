@@ -7,4 +7,5 @@ export interface InspectSubstateResponse {
   version: number;
   substate: SubstateValue;
   created_by_transaction: string;
+  display_amount: string | null;
 }
@@ -8,4 +8,5 @@ export interface GetBlocksRequest {
   ordering: Ordering | null;
   filter_index: number | null;
   filter: string | null;
+  cursor?: string;
 }
@@ -3,4 +3,5 @@ import type { Block } from "../Block";
 
 export interface GetBlocksResponse {
   blocks: Array<Block>;
+  next_cursor: string | null;
 }
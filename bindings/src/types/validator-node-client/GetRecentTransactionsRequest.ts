@@ -1,3 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ordering } from "../Ordering";
+import type { TransactionStatusFilter } from "../TransactionStatusFilter";
 
-export type GetRecentTransactionsRequest = Record<string, never>;
+export interface GetRecentTransactionsRequest {
+  limit?: number;
+  cursor?: string;
+  ordering?: Ordering;
+  status?: TransactionStatusFilter;
+}
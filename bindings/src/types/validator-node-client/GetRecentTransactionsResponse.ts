@@ -3,4 +3,5 @@ import type { Transaction } from "../Transaction";
 
 export interface GetRecentTransactionsResponse {
   transactions: Array<Transaction>;
+  next_cursor: string | null;
 }
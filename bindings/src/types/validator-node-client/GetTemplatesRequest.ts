@@ -1,5 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ordering } from "../Ordering";
 
 export interface GetTemplatesRequest {
   limit: number;
+  cursor?: string;
+  ordering?: Ordering;
+  author_public_key?: string;
+  name?: string;
 }
@@ -3,4 +3,5 @@ import type { TemplateMetadata } from "./TemplateMetadata";
 
 export interface GetTemplatesResponse {
   templates: Array<TemplateMetadata>;
+  next_cursor: string | null;
 }
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LayerOneTransactionStatus } from "./LayerOneTransactionStatus";
+
+export interface LayerOneOutboxEntry {
+  id: number;
+  proof_type: string;
+  payload: any;
+  status: LayerOneTransactionStatus;
+  attempts: number;
+  wallet_tx_id: number | null;
+  last_error: string | null;
+  next_attempt_at: number;
+  created_at: number;
+  updated_at: number;
+}
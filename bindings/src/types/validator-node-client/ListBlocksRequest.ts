@@ -3,4 +3,5 @@
 export interface ListBlocksRequest {
   from_id: string | null;
   limit: number;
+  proposed_by?: string;
 }
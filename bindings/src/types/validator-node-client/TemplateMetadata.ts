@@ -4,4 +4,5 @@ export interface TemplateMetadata {
   name: string;
   address: string;
   binary_sha: Array<number>;
+  author_public_key: string;
 }
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { BatchTransferRecipient } from "./BatchTransferRecipient";
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+
+export interface AccountsBatchTransferRequest {
+  account: ComponentAddressOrName | null;
+  recipients: Array<BatchTransferRecipient>;
+  max_fee: Amount | null;
+  max_recipients_per_transaction: number | null;
+  dry_run: boolean;
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { BatchTransferRecipientResult } from "./BatchTransferRecipientResult";
+import type { BatchTransferTransaction } from "./BatchTransferTransaction";
+
+export interface AccountsBatchTransferResponse {
+  transactions: Array<BatchTransferTransaction>;
+  recipients: Array<BatchTransferRecipientResult>;
+  total_fees: Amount;
+}
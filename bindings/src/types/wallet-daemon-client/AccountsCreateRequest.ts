@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountPolicy } from "../AccountPolicy";
 import type { Amount } from "../Amount";
 import type { ComponentAccessRules } from "../ComponentAccessRules";
 
@@ -8,4 +9,5 @@ export interface AccountsCreateRequest {
   max_fee: Amount | null;
   is_default: boolean;
   key_id: number | null;
+  policy: AccountPolicy | null;
 }
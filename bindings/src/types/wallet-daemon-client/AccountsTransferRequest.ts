@@ -6,6 +6,7 @@ import type { ResourceAddress } from "../ResourceAddress";
 export interface AccountsTransferRequest {
   account: ComponentAddressOrName | null;
   amount: Amount;
+  decimal_amount: string | null;
   resource_address: ResourceAddress;
   destination_public_key: string;
   max_fee: Amount | null;
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { NonFungibleId } from "../NonFungibleId";
+import type { ResourceAddress } from "../ResourceAddress";
+
+export interface BatchTransferRecipient {
+  destination_public_key: string;
+  resource_address: ResourceAddress;
+  amount: Amount;
+  non_fungible_ids: Array<NonFungibleId>;
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BatchTransferStatus } from "./BatchTransferStatus";
+import type { ResourceAddress } from "../ResourceAddress";
+
+export interface BatchTransferRecipientResult {
+  destination_public_key: string;
+  resource_address: ResourceAddress;
+  transaction_id: string | null;
+  status: BatchTransferStatus;
+}
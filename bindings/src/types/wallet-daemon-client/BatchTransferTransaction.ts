@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { BatchTransferStatus } from "./BatchTransferStatus";
+
+export interface BatchTransferTransaction {
+  transaction_id: string;
+  fee: Amount;
+  recipients: Array<number>;
+  status: BatchTransferStatus;
+}
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { BalanceChange } from "./BalanceChange";
+import type { UnsignedTransaction } from "../UnsignedTransaction";
+
+export interface PendingTransactionApproval {
+  id: number;
+  requested_by: string;
+  requested_at: string;
+  transaction: UnsignedTransaction;
+  instructions: Array<string>;
+  balance_changes: Array<BalanceChange>;
+  estimated_fee: Amount | null;
+  dry_run_error: string | null;
+}
@@ -9,4 +9,5 @@ export interface TransactionSubmitRequest {
   detect_inputs: boolean;
   detect_inputs_use_unversioned: boolean;
   proof_ids: Array<number>;
+  infer_lock_intents: boolean;
 }
@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Account } from "../Account";
+import type { Amount } from "../Amount";
+import type { FinalizeResult } from "../FinalizeResult";
+import type { SubstateId } from "../SubstateId";
+import type { TransactionStatus } from "../TransactionStatus";
+
+export type WalletEventNotification =
+  | { TransactionSubmitted: { transaction_id: string; new_account_name: string | null } }
+  | {
+      TransactionFinalized: {
+        transaction_id: string;
+        status: TransactionStatus;
+        final_fee: Amount;
+        result: FinalizeResult;
+      };
+    }
+  | {
+      TransactionInvalid: {
+        transaction_id: string;
+        status: TransactionStatus;
+        final_fee: Amount | null;
+        result: FinalizeResult | null;
+      };
+    }
+  | { AccountCreated: { account: Account; created_by_tx: string } }
+  | { AccountChanged: { account_address: SubstateId } }
+  | { TransactionApprovalRequested: { id: number; requested_by: string } }
+  | "Lagged";
@@ -61,3 +61,7 @@ export * from "./types/validator-node-client/VNArgDef";
 export * from "./types/validator-node-client/GetEpochManagerStatsResponse";
 export * from "./types/validator-node-client/GetBlockResponse";
 export * from "./types/validator-node-client/VNCommitteeShardInfo";
+export * from "./types/validator-node-client/LayerOneTransactionStatus";
+export * from "./types/validator-node-client/LayerOneOutboxEntry";
+export * from "./types/validator-node-client/GetLayerOneOutboxRequest";
+export * from "./types/validator-node-client/GetLayerOneOutboxResponse";
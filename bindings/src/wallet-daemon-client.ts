@@ -34,6 +34,12 @@ export * from "./types/wallet-daemon-client/AccountsTransferRequest";
 export * from "./types/wallet-daemon-client/SettingsSetResponse";
 export * from "./types/wallet-daemon-client/KeysListRequest";
 export * from "./types/wallet-daemon-client/AccountsTransferResponse";
+export * from "./types/wallet-daemon-client/AccountsBatchTransferRequest";
+export * from "./types/wallet-daemon-client/AccountsBatchTransferResponse";
+export * from "./types/wallet-daemon-client/BatchTransferRecipient";
+export * from "./types/wallet-daemon-client/BatchTransferRecipientResult";
+export * from "./types/wallet-daemon-client/BatchTransferStatus";
+export * from "./types/wallet-daemon-client/BatchTransferTransaction";
 export * from "./types/wallet-daemon-client/TransactionGetResultResponse";
 export * from "./types/wallet-daemon-client/ClaimBurnRequest";
 export * from "./types/wallet-daemon-client/KeysListResponse";
@@ -95,3 +101,12 @@ export * from "./types/wallet-daemon-client/ConfidentialTransferRequest";
 export * from "./types/wallet-daemon-client/AccountsCreateResponse";
 export * from "./types/wallet-daemon-client/TransactionWaitResultResponse";
 export * from "./types/wallet-daemon-client/AccountGetRequest";
+export * from "./types/wallet-daemon-client/WalletEventNotification";
+export * from "./types/wallet-daemon-client/PendingTransactionApproval";
+export * from "./types/wallet-daemon-client/BalanceChange";
+export * from "./types/wallet-daemon-client/TransactionListPendingRequest";
+export * from "./types/wallet-daemon-client/TransactionListPendingResponse";
+export * from "./types/wallet-daemon-client/TransactionApproveRequest";
+export * from "./types/wallet-daemon-client/TransactionApproveResponse";
+export * from "./types/wallet-daemon-client/TransactionRejectRequest";
+export * from "./types/wallet-daemon-client/TransactionRejectResponse";
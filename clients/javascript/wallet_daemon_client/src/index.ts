@@ -9,6 +9,8 @@ import {
   AccountGetResponse,
   AccountSetDefaultRequest,
   AccountSetDefaultResponse,
+  AccountsBatchTransferRequest,
+  AccountsBatchTransferResponse,
   AccountsCreateFreeTestCoinsRequest,
   AccountsCreateFreeTestCoinsResponse,
   AccountsCreateRequest,
@@ -54,12 +56,18 @@ import {
   SubstatesListResponse,
   TemplatesGetRequest,
   TemplatesGetResponse,
+  TransactionApproveRequest,
+  TransactionApproveResponse,
   TransactionGetAllRequest,
   TransactionGetAllResponse,
   TransactionGetRequest,
   TransactionGetResponse,
   TransactionGetResultRequest,
   TransactionGetResultResponse,
+  TransactionListPendingRequest,
+  TransactionListPendingResponse,
+  TransactionRejectRequest,
+  TransactionRejectResponse,
   TransactionSubmitRequest,
   TransactionSubmitResponse,
   TransactionWaitResultRequest,
@@ -92,6 +100,8 @@ export type {
   AccountGetResponse,
   AccountSetDefaultRequest,
   AccountSetDefaultResponse,
+  AccountsBatchTransferRequest,
+  AccountsBatchTransferResponse,
   AccountsCreateFreeTestCoinsRequest,
   AccountsCreateFreeTestCoinsResponse,
   AccountsCreateRequest,
@@ -240,6 +250,10 @@ export class WalletDaemonClient {
     return this.__invokeRpc("accounts.transfer", params);
   }
 
+  public accountsBatchTransfer(params: AccountsBatchTransferRequest): Promise<AccountsBatchTransferResponse> {
+    return this.__invokeRpc("accounts.batch_transfer", params);
+  }
+
   public confidentialTransfer(params: ConfidentialTransferRequest): Promise<ConfidentialTransferResponse> {
     return this.__invokeRpc("accounts.confidential_transfer", params);
   }
@@ -286,6 +300,18 @@ export class WalletDaemonClient {
     return this.__invokeRpc("transactions.wait_result", params);
   }
 
+  public transactionsListPending(params: TransactionListPendingRequest): Promise<TransactionListPendingResponse> {
+    return this.__invokeRpc("transactions.list_pending", params);
+  }
+
+  public transactionsApprove(params: TransactionApproveRequest): Promise<TransactionApproveResponse> {
+    return this.__invokeRpc("transactions.approve", params);
+  }
+
+  public transactionsReject(params: TransactionRejectRequest): Promise<TransactionRejectResponse> {
+    return this.__invokeRpc("transactions.reject", params);
+  }
+
   public templatesGet(params: TemplatesGetRequest): Promise<TemplatesGetResponse> {
     return this.__invokeRpc("templates.get", params);
   }
@@ -95,6 +95,10 @@ pub struct GetSubstateResponse {
     pub substate: SubstateValue,
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub created_by_transaction: TransactionId,
+    /// The revealed vault balance or resource total supply formatted as a decimal string using the resource
+    /// divisibility. None for non-fungible and other substate types.
+    #[serde(default)]
+    pub display_amount: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,6 +124,10 @@ pub struct InspectSubstateResponse {
     pub substate: SubstateValue,
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub created_by_transaction: TransactionId,
+    /// The revealed vault balance or resource total supply formatted as a decimal string using the resource
+    /// divisibility. None for non-fungible and other substate types.
+    #[serde(default)]
+    pub display_amount: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,6 +161,10 @@ pub struct SubmitTransactionResponse {
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub transaction_id: TransactionId,
     pub result: IndexerTransactionFinalizedResult,
+    /// For dry runs, the inputs of the transaction with the lock intents inferred from the dry run result. Always
+    /// empty for submitted transactions.
+    #[serde(default)]
+    pub inferred_inputs: Vec<SubstateRequirement>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +96,13 @@ impl ValidatorNodeClient {
         self.send_request("get_fees", request).await
     }
 
+    pub async fn get_layer_one_outbox(
+        &mut self,
+        request: GetLayerOneOutboxRequest,
+    ) -> Result<GetLayerOneOutboxResponse, ValidatorNodeClientError> {
+        self.send_request("get_layer_one_outbox", request).await
+    }
+
     pub async fn get_template(
         &mut self,
         request: GetTemplateRequest,
@@ -28,6 +28,7 @@ use tari_base_node_client::types::BaseLayerValidatorNode;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    layer_one_transaction::LayerOnePayloadType,
     shard::Shard,
     Epoch,
     NodeHeight,
@@ -46,6 +47,7 @@ use tari_dan_storage::{
     },
     global::models,
     Ordering,
+    TransactionStatusFilter,
 };
 use tari_engine_types::{
     commit_result::{ExecuteResult, FinalizeResult},
@@ -146,15 +148,31 @@ pub struct ArgDef {
     pub arg_type: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/validator-node-client/")
 )]
 pub struct GetTemplatesRequest {
+    /// The maximum number of templates to return. Zero returns all matching templates.
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub limit: u64,
+    /// The `next_cursor` from a previous response. Templates are ordered by address.
+    #[serde(default, with = "serde_with::string::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string", optional))]
+    pub cursor: Option<TemplateAddress>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub ordering: Option<Ordering>,
+    /// Only return templates authored by this public key
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string", optional))]
+    pub author_public_key: Option<PublicKey>,
+    /// Only return templates whose name contains this string
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,6 +183,10 @@ pub struct GetTemplatesRequest {
 )]
 pub struct GetTemplatesResponse {
     pub templates: Vec<TemplateMetadata>,
+    /// The cursor for the next page, or None if there are no more templates
+    #[serde(with = "serde_with::string::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub next_cursor: Option<TemplateAddress>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,6 +202,8 @@ pub struct TemplateMetadata {
     pub address: TemplateAddress,
     /// SHA hash of binary
     pub binary_sha: Vec<u8>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub author_public_key: PublicKey,
 }
 
 /// A request to submit a transaction
@@ -384,13 +408,29 @@ pub struct GetTransactionResultResponse {
     pub execution_time: Option<Duration>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/validator-node-client/")
 )]
-pub struct GetRecentTransactionsRequest {}
+pub struct GetRecentTransactionsRequest {
+    /// The maximum number of transactions to return. Defaults to 1000.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number", optional))]
+    pub limit: Option<u64>,
+    /// The `next_cursor` from a previous response
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string", optional))]
+    pub cursor: Option<TransactionId>,
+    /// Defaults to the most recent transactions first
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub ordering: Option<Ordering>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub status: Option<TransactionStatusFilter>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
@@ -400,6 +440,9 @@ pub struct GetRecentTransactionsRequest {}
 )]
 pub struct GetRecentTransactionsResponse {
     pub transactions: Vec<Transaction>,
+    /// The cursor for the next page, or None if there are no more transactions
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub next_cursor: Option<TransactionId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -414,6 +457,10 @@ pub struct ListBlocksRequest {
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub from_id: Option<BlockId>,
     pub limit: usize,
+    /// Only return blocks proposed by this validator
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string", optional))]
+    pub proposed_by: Option<PublicKey>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -424,6 +471,9 @@ pub struct ListBlocksRequest {
 )]
 pub struct ListBlocksResponse {
     pub blocks: Vec<Block>,
+    /// The `from_id` for the next page, or None if the genesis block has been reached
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub next_cursor: Option<BlockId>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -454,6 +504,9 @@ pub struct GetBlockResponse {
 )]
 pub struct GetBlocksResponse {
     pub blocks: Vec<Block>,
+    /// The `cursor` for the next page, or None if this is the last page
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub next_cursor: Option<BlockId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -817,6 +870,11 @@ pub struct GetBlocksRequest {
     pub ordering: Option<Ordering>,
     pub filter_index: Option<usize>,
     pub filter: Option<String>,
+    /// Only return blocks that come after this block in the requested ordering. Set to the `next_cursor` of the
+    /// previous page.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string", optional))]
+    pub cursor: Option<BlockId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -891,3 +949,73 @@ pub struct GetConnectionsResponse {
 pub struct GetMempoolStatsResponse {
     pub size: usize,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub enum LayerOneTransactionStatus {
+    /// Waiting to be submitted to the wallet
+    Pending,
+    /// Being submitted to the wallet. An entry that is still submitting when the node restarts is reconciled with the
+    /// wallet's transactions before it is submitted again.
+    Submitting,
+    /// Submitted to the wallet and waiting to be mined
+    Submitted,
+    /// Mined and confirmed on the base layer
+    Mined,
+    /// Submission failed after all retries
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct LayerOneOutboxEntry {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub proof_type: LayerOnePayloadType,
+    #[cfg_attr(feature = "ts", ts(type = "any"))]
+    pub payload: serde_json::Value,
+    pub status: LayerOneTransactionStatus,
+    pub attempts: u32,
+    /// The wallet transaction ID, once submitted
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub wallet_tx_id: Option<u64>,
+    pub last_error: Option<String>,
+    /// Unix timestamp (seconds) of the next submission attempt for pending entries
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub next_attempt_at: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub created_at: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetLayerOneOutboxRequest {
+    /// Only return entries with this status
+    #[serde(default)]
+    pub status: Option<LayerOneTransactionStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetLayerOneOutboxResponse {
+    pub entries: Vec<LayerOneOutboxEntry>,
+}
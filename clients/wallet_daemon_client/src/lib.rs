@@ -37,6 +37,8 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json as json;
 use serde_json::json;
 use types::{
+    AccountsBatchTransferRequest,
+    AccountsBatchTransferResponse,
     AccountsCreateFreeTestCoinsRequest,
     AccountsCreateFreeTestCoinsResponse,
     AccountsTransferRequest,
@@ -107,12 +109,18 @@ use crate::{
         RevealFundsRequest,
         RevealFundsResponse,
         SettingsGetResponse,
+        TransactionApproveRequest,
+        TransactionApproveResponse,
         TransactionGetAllRequest,
         TransactionGetAllResponse,
         TransactionGetRequest,
         TransactionGetResponse,
         TransactionGetResultRequest,
         TransactionGetResultResponse,
+        TransactionListPendingRequest,
+        TransactionListPendingResponse,
+        TransactionRejectRequest,
+        TransactionRejectResponse,
         TransactionSubmitDryRunRequest,
         TransactionSubmitDryRunResponse,
         TransactionSubmitRequest,
@@ -228,6 +236,27 @@ impl WalletDaemonClient {
         self.send_request("transactions.submit_dry_run", request.borrow()).await
     }
 
+    pub async fn list_pending_transactions(
+        &mut self,
+    ) -> Result<TransactionListPendingResponse, WalletDaemonClientError> {
+        self.send_request("transactions.list_pending", &TransactionListPendingRequest {})
+            .await
+    }
+
+    pub async fn approve_transaction<T: Borrow<TransactionApproveRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionApproveResponse, WalletDaemonClientError> {
+        self.send_request("transactions.approve", request.borrow()).await
+    }
+
+    pub async fn reject_transaction<T: Borrow<TransactionRejectRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionRejectResponse, WalletDaemonClientError> {
+        self.send_request("transactions.reject", request.borrow()).await
+    }
+
     pub async fn create_account<T: Borrow<AccountsCreateRequest>>(
         &mut self,
         request: T,
@@ -300,6 +329,13 @@ impl WalletDaemonClient {
         self.send_request("accounts.transfer", req.borrow()).await
     }
 
+    pub async fn accounts_batch_transfer<T: Borrow<AccountsBatchTransferRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<AccountsBatchTransferResponse, WalletDaemonClientError> {
+        self.send_request("accounts.batch_transfer", req.borrow()).await
+    }
+
     pub async fn accounts_confidential_transfer<T: Borrow<ConfidentialTransferRequest>>(
         &mut self,
         req: T,
@@ -48,7 +48,7 @@ use tari_engine_types::{
 use tari_template_abi::TemplateDef;
 use tari_template_lib::{
     args::Arg,
-    auth::ComponentAccessRules,
+    auth::{AccountPolicy, ComponentAccessRules},
     models::{Amount, ConfidentialOutputStatement, NonFungibleId, ResourceAddress, VaultId},
     prelude::{ComponentAddress, ConfidentialWithdrawProof, ResourceType},
 };
@@ -113,6 +113,11 @@ pub struct TransactionSubmitRequest {
     pub detect_inputs_use_unversioned: bool,
     #[cfg_attr(feature = "ts", ts(type = "Array<number>"))]
     pub proof_ids: Vec<ConfidentialProofId>,
+    /// If true, the wallet dry runs the transaction and declares each input as read or write depending on whether
+    /// the dry run wrote to it. Read-only inputs allow validators to process other transactions that read the same
+    /// substates concurrently.
+    #[serde(default)]
+    pub infer_lock_intents: bool,
 }
 
 const fn return_true() -> bool {
@@ -159,6 +164,8 @@ pub struct TransactionSubmitDryRunResponse {
     pub result: ExecuteResult,
     #[cfg_attr(feature = "ts", ts(type = "Array<any>"))]
     pub json_result: Vec<serde_json::Value>,
+    /// The inputs of the transaction with the lock intents inferred from the dry run result
+    pub inferred_inputs: Vec<SubstateRequirement>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -307,6 +314,106 @@ pub struct TransactionClaimBurnResponse {
     pub outputs: Vec<SubstateAddress>,
 }
 
+/// A transaction submitted under a non-admin token that is waiting to be approved or rejected by an admin
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct PendingTransactionApproval {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    /// The name of the token that submitted the transaction
+    pub requested_by: String,
+    pub requested_at: NaiveDateTime,
+    pub transaction: UnsignedTransaction,
+    /// The fee and main instructions of the transaction in human-readable form
+    pub instructions: Vec<String>,
+    /// The changes to the revealed balances of this wallet's vaults if the transaction is executed as in the dry run.
+    /// Changes to confidential balances are not included.
+    pub balance_changes: Vec<BalanceChange>,
+    /// The fee charged in the dry run
+    pub estimated_fee: Option<Amount>,
+    /// Set if the dry run failed or was rejected
+    pub dry_run_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BalanceChange {
+    pub account_address: SubstateId,
+    pub vault_address: SubstateId,
+    pub resource_address: ResourceAddress,
+    pub token_symbol: Option<String>,
+    pub balance_before: Amount,
+    pub balance_after: Amount,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionListPendingRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionListPendingResponse {
+    pub pending: Vec<PendingTransactionApproval>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionApproveRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionApproveResponse {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionRejectRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    /// Returned to the client that submitted the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionRejectResponse {}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -410,6 +517,9 @@ pub struct AccountsCreateRequest {
     pub is_default: bool,
     #[cfg_attr(feature = "ts", ts(type = "number | null"))]
     pub key_id: Option<u64>,
+    /// If set, a policy account is created that enforces this policy itself
+    #[serde(default)]
+    pub policy: Option<AccountPolicy>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -523,6 +633,10 @@ pub struct BalanceEntry {
     pub resource_type: ResourceType,
     pub confidential_balance: Amount,
     pub token_symbol: Option<String>,
+    /// The number of decimal places used to display amounts of the resource
+    pub divisibility: u8,
+    /// The total balance formatted as a decimal string using the resource divisibility
+    pub display_balance: String,
 }
 
 impl BalanceEntry {
@@ -530,7 +644,7 @@ impl BalanceEntry {
         let symbol = self.token_symbol.as_deref().unwrap_or_default();
         match self.resource_type {
             ResourceType::Fungible => {
-                format!("{} {}", self.balance, symbol)
+                format!("{} {}", self.format_amount(self.balance), symbol)
             },
             ResourceType::NonFungible => {
                 format!("{} {} tokens", self.balance, symbol)
@@ -538,14 +652,18 @@ impl BalanceEntry {
             ResourceType::Confidential => {
                 format!(
                     "{} revealed + {} blinded = {} {}",
-                    self.balance,
-                    self.confidential_balance,
-                    self.balance + self.confidential_balance,
+                    self.format_amount(self.balance),
+                    self.format_amount(self.confidential_balance),
+                    self.format_amount(self.balance + self.confidential_balance),
                     symbol
                 )
             },
         }
     }
+
+    fn format_amount(&self, amount: Amount) -> String {
+        amount.to_decimal_string(self.divisibility)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -609,7 +727,12 @@ pub struct AccountSetDefaultResponse {}
 pub struct AccountsTransferRequest {
     #[serde(deserialize_with = "opt_string_or_struct")]
     pub account: Option<ComponentAddressOrName>,
+    #[serde(default)]
     pub amount: Amount,
+    /// A human-readable decimal amount (e.g. "1.25") that is converted using the resource divisibility. If provided,
+    /// `amount` must be zero.
+    #[serde(default)]
+    pub decimal_amount: Option<String>,
     pub resource_address: ResourceAddress,
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub destination_public_key: PublicKey,
@@ -633,6 +756,103 @@ pub struct AccountsTransferResponse {
     pub result: FinalizeResult,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsBatchTransferRequest {
+    #[serde(deserialize_with = "opt_string_or_struct")]
+    pub account: Option<ComponentAddressOrName>,
+    pub recipients: Vec<BatchTransferRecipient>,
+    /// The maximum fee paid by each transaction in the batch
+    pub max_fee: Option<Amount>,
+    /// The maximum number of recipients included in a single transaction. Larger batches are split into multiple
+    /// transactions that are submitted one after the other.
+    #[serde(default)]
+    pub max_recipients_per_transaction: Option<u32>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BatchTransferRecipient {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub destination_public_key: PublicKey,
+    pub resource_address: ResourceAddress,
+    /// The amount of a fungible or confidential resource to send. Confidential amounts are sent as blinded outputs.
+    #[serde(default)]
+    pub amount: Amount,
+    /// The tokens of a non-fungible resource to send
+    #[serde(default)]
+    pub non_fungible_ids: Vec<NonFungibleId>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsBatchTransferResponse {
+    pub transactions: Vec<BatchTransferTransaction>,
+    /// The outcome for each recipient, in the same order as the request
+    pub recipients: Vec<BatchTransferRecipientResult>,
+    pub total_fees: Amount,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BatchTransferTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub fee: Amount,
+    /// Indexes of the recipients (in the request) that are paid by this transaction
+    pub recipients: Vec<u32>,
+    pub status: BatchTransferStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BatchTransferRecipientResult {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub destination_public_key: PublicKey,
+    pub resource_address: ResourceAddress,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub transaction_id: Option<TransactionId>,
+    pub status: BatchTransferStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub enum BatchTransferStatus {
+    Succeeded,
+    Failed { reason: String },
+}
+
+impl BatchTransferStatus {
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Succeeded)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -1265,3 +1485,64 @@ pub struct TemplatesGetRequest {
 pub struct TemplatesGetResponse {
     pub template_definition: TemplateDef,
 }
+
+/// An event streamed to clients by the wallet daemon's `/events` endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub enum WalletEventNotification {
+    TransactionSubmitted {
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        transaction_id: TransactionId,
+        /// The name of the account that this transaction creates, if any
+        new_account_name: Option<String>,
+    },
+    TransactionFinalized {
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        final_fee: Amount,
+        result: FinalizeResult,
+    },
+    TransactionInvalid {
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        final_fee: Option<Amount>,
+        result: Option<FinalizeResult>,
+    },
+    AccountCreated {
+        account: Account,
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        created_by_tx: TransactionId,
+    },
+    AccountChanged {
+        account_address: SubstateId,
+    },
+    /// A transaction is waiting for approval. Only sent to admin clients.
+    TransactionApprovalRequested {
+        #[cfg_attr(feature = "ts", ts(type = "number"))]
+        id: u64,
+        requested_by: String,
+    },
+    /// Some events could not be delivered, either because the client fell behind or because the events requested
+    /// when resuming are no longer held by the daemon. The client should refresh any state it derives from events.
+    Lagged,
+}
+
+impl WalletEventNotification {
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            WalletEventNotification::TransactionSubmitted { .. } => "TransactionSubmitted",
+            WalletEventNotification::TransactionFinalized { .. } => "TransactionFinalized",
+            WalletEventNotification::TransactionInvalid { .. } => "TransactionInvalid",
+            WalletEventNotification::AccountCreated { .. } => "AccountCreated",
+            WalletEventNotification::AccountChanged { .. } => "AccountChanged",
+            WalletEventNotification::TransactionApprovalRequested { .. } => "TransactionApprovalRequested",
+            WalletEventNotification::Lagged => "Lagged",
+        }
+    }
+}
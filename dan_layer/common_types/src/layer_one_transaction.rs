@@ -11,7 +11,7 @@ pub struct LayerOneTransactionDef<T> {
     pub payload: T,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LayerOnePayloadType {
     EvictionProof,
 }
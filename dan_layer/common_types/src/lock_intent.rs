@@ -96,6 +96,63 @@ impl FromStr for SubstateLockType {
 #[error("Failed to parse SubstateLockFlag")]
 pub struct SubstateLockFlagParseError;
 
+/// The lock that a transaction declares for one of its inputs. An input declared as `Read` may not be written to by
+/// the transaction, which allows validators to grant concurrent read locks to other transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum InputLockIntent {
+    Read,
+    Write,
+}
+
+impl InputLockIntent {
+    pub fn is_read(&self) -> bool {
+        matches!(self, Self::Read)
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, Self::Write)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "Read",
+            Self::Write => "Write",
+        }
+    }
+}
+
+impl From<InputLockIntent> for SubstateLockType {
+    fn from(value: InputLockIntent) -> Self {
+        match value {
+            InputLockIntent::Read => Self::Read,
+            InputLockIntent::Write => Self::Write,
+        }
+    }
+}
+
+impl fmt::Display for InputLockIntent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for InputLockIntent {
+    type Err = SubstateLockFlagParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Read" | "read" | "r" => Ok(Self::Read),
+            "Write" | "write" | "w" => Ok(Self::Write),
+            _ => Err(SubstateLockFlagParseError),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,7 +7,15 @@ use borsh::BorshSerialize;
 use serde::{Deserialize, Serialize};
 use tari_engine_types::{substate::SubstateId, transaction_receipt::TransactionReceiptAddress};
 
-use crate::{displayable::Displayable, shard::Shard, NumPreshards, ShardGroup, SubstateAddress, ToSubstateAddress};
+use crate::{
+    displayable::Displayable,
+    shard::Shard,
+    InputLockIntent,
+    NumPreshards,
+    ShardGroup,
+    SubstateAddress,
+    ToSubstateAddress,
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
@@ -18,6 +26,11 @@ use crate::{displayable::Displayable, shard::Shard, NumPreshards, ShardGroup, Su
 pub struct SubstateRequirement {
     pub substate_id: SubstateId,
     pub version: Option<u32>,
+    /// The lock that the transaction requires on this input. If not declared, validators assume the input may be
+    /// written to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub lock_intent: Option<InputLockIntent>,
 }
 
 impl SubstateRequirement {
@@ -25,6 +38,7 @@ impl SubstateRequirement {
         Self {
             substate_id: address,
             version,
+            lock_intent: None,
         }
     }
 
@@ -32,6 +46,7 @@ impl SubstateRequirement {
         Self {
             substate_id: id.into(),
             version: None,
+            lock_intent: None,
         }
     }
 
@@ -39,9 +54,28 @@ impl SubstateRequirement {
         Self {
             substate_id: id.into(),
             version: Some(version),
+            lock_intent: None,
         }
     }
 
+    pub fn with_lock_intent(mut self, lock_intent: InputLockIntent) -> Self {
+        self.lock_intent = Some(lock_intent);
+        self
+    }
+
+    pub fn read_only(self) -> Self {
+        self.with_lock_intent(InputLockIntent::Read)
+    }
+
+    pub fn lock_intent(&self) -> Option<InputLockIntent> {
+        self.lock_intent
+    }
+
+    /// Returns true if the input has been declared as read-only
+    pub fn is_read_only(&self) -> bool {
+        self.lock_intent.is_some_and(|l| l.is_read())
+    }
+
     pub fn substate_id(&self) -> &SubstateId {
         &self.substate_id
     }
@@ -51,7 +85,7 @@ impl SubstateRequirement {
     }
 
     pub fn into_unversioned(self) -> Self {
-        Self::unversioned(self.substate_id)
+        Self { version: None, ..self }
     }
 
     pub fn version(&self) -> Option<u32> {
@@ -122,6 +156,7 @@ impl FromStr for SubstateRequirement {
         Ok(Self {
             substate_id: address,
             version,
+            lock_intent: None,
         })
     }
 }
@@ -179,15 +214,28 @@ impl Borrow<SubstateId> for SubstateRequirement {
 pub struct SubstateRequirementRef<'a> {
     pub substate_id: &'a SubstateId,
     pub version: Option<u32>,
+    pub lock_intent: Option<InputLockIntent>,
 }
 
 impl<'a> SubstateRequirementRef<'a> {
     pub fn new(substate_id: &'a SubstateId, version: Option<u32>) -> Self {
-        Self { substate_id, version }
+        Self {
+            substate_id,
+            version,
+            lock_intent: None,
+        }
+    }
+
+    pub fn with_lock_intent(self, lock_intent: Option<InputLockIntent>) -> Self {
+        Self { lock_intent, ..self }
     }
 
     pub fn to_owned(&self) -> SubstateRequirement {
-        SubstateRequirement::new(self.substate_id.clone(), self.version)
+        SubstateRequirement {
+            substate_id: self.substate_id.clone(),
+            version: self.version,
+            lock_intent: self.lock_intent,
+        }
     }
 
     pub fn with_version(self, version: u32) -> VersionedSubstateIdRef<'a> {
@@ -206,6 +254,14 @@ impl<'a> SubstateRequirementRef<'a> {
     pub fn substate_id(&self) -> &SubstateId {
         self.substate_id
     }
+
+    pub fn lock_intent(&self) -> Option<InputLockIntent> {
+        self.lock_intent
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.lock_intent.is_some_and(|l| l.is_read())
+    }
 }
 
 impl<'a> From<&'a VersionedSubstateId> for SubstateRequirementRef<'a> {
@@ -213,6 +269,7 @@ impl<'a> From<&'a VersionedSubstateId> for SubstateRequirementRef<'a> {
         Self {
             substate_id: &value.substate_id,
             version: Some(value.version),
+            lock_intent: None,
         }
     }
 }
@@ -222,6 +279,7 @@ impl<'a> From<&'a SubstateRequirement> for SubstateRequirementRef<'a> {
         Self {
             substate_id: &value.substate_id,
             version: value.version,
+            lock_intent: value.lock_intent,
         }
     }
 }
@@ -231,6 +289,7 @@ impl<'a> From<VersionedSubstateIdRef<'a>> for SubstateRequirementRef<'a> {
         Self {
             substate_id: value.substate_id,
             version: Some(value.version),
+            lock_intent: None,
         }
     }
 }
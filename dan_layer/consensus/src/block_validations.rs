@@ -1,14 +1,12 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashSet;
-
 use log::{debug, warn};
 use tari_common::configuration::Network;
 use tari_crypto::{ristretto::RistrettoPublicKey, tari_utilities::ByteArray};
-use tari_dan_common_types::{committee::Committee, DerivableFromPublicKey, Epoch, ExtraFieldKey};
+use tari_dan_common_types::{committee::Committee, DerivableFromPublicKey, Epoch, ExtraFieldKey, NumPreshards};
 use tari_dan_storage::consensus_models::{Block, QuorumCertificate};
-use tari_epoch_manager::EpochManagerReader;
+use tari_epoch_manager::{EpochManagerError, EpochManagerReader};
 
 use crate::{
     hotstuff::{HotStuffError, HotstuffConfig, ProposalValidationError},
@@ -20,6 +18,7 @@ pub fn check_local_proposal<TConsensusSpec: ConsensusSpec>(
     current_epoch: Epoch,
     block: &Block,
     committee_for_block: &Committee<TConsensusSpec::Addr>,
+    justify_committee: &Committee<TConsensusSpec::Addr>,
     vote_signing_service: &TConsensusSpec::SignatureService,
     leader_strategy: &TConsensusSpec::LeaderStrategy,
     config: &HotstuffConfig,
@@ -27,6 +26,7 @@ pub fn check_local_proposal<TConsensusSpec: ConsensusSpec>(
     check_proposal::<TConsensusSpec>(
         block,
         committee_for_block,
+        justify_committee,
         vote_signing_service,
         leader_strategy,
         config,
@@ -39,6 +39,7 @@ pub fn check_local_proposal<TConsensusSpec: ConsensusSpec>(
 pub fn check_proposal<TConsensusSpec: ConsensusSpec>(
     block: &Block,
     committee_for_block: &Committee<TConsensusSpec::Addr>,
+    justify_committee: &Committee<TConsensusSpec::Addr>,
     vote_signing_service: &TConsensusSpec::SignatureService,
     leader_strategy: &TConsensusSpec::LeaderStrategy,
     config: &HotstuffConfig,
@@ -63,7 +64,7 @@ pub fn check_proposal<TConsensusSpec: ConsensusSpec>(
     check_proposed_by_leader(leader_strategy, committee_for_block, block)?;
     check_signature(block)?;
     check_block(block)?;
-    check_quorum_certificate::<TConsensusSpec>(block.justify(), committee_for_block, vote_signing_service)?;
+    check_quorum_certificate::<TConsensusSpec>(block.justify(), justify_committee, vote_signing_service)?;
     Ok(())
 }
 
@@ -212,6 +213,25 @@ pub fn check_block(candidate_block: &Block) -> Result<(), ProposalValidationErro
     Ok(())
 }
 
+/// Returns the committee that the signer bitmap of the given QC refers to. Votes are collected over the canonically
+/// ordered committee of the epoch and shard group that the QC was formed in, which may differ from the committee of the
+/// block or message that carries it (e.g. a high QC from the previous epoch).
+pub async fn get_committee_for_qc<TEpochManager: EpochManagerReader>(
+    epoch_manager: &TEpochManager,
+    qc: &QuorumCertificate,
+    num_preshards: NumPreshards,
+) -> Result<Committee<TEpochManager::Addr>, EpochManagerError> {
+    if qc.justifies_zero_block() {
+        // The zero block QC has no signers
+        return Ok(Committee::new(vec![]));
+    }
+    let substate_address = *qc.shard_group().to_substate_address_range(num_preshards).start();
+    epoch_manager
+        .get_committee_for_substate(qc.epoch(), substate_address)
+        .await
+}
+
+/// Checks the QC signatures against `committee`, which must be the committee returned by [get_committee_for_qc].
 pub fn check_quorum_certificate<TConsensusSpec: ConsensusSpec>(
     qc: &QuorumCertificate,
     committee: &Committee<TConsensusSpec::Addr>,
@@ -224,34 +244,32 @@ pub fn check_quorum_certificate<TConsensusSpec: ConsensusSpec>(
         return Ok(());
     }
 
-    if qc.signatures().len() < committee.quorum_threshold() {
+    let Some(signer_public_keys) = qc.signer_public_keys(committee) else {
+        return Err(ProposalValidationError::QcInvalidSigners {
+            qc: *qc.id(),
+            details: format!(
+                "Signer bitmap (committee size {}, {} signers) does not match committee {} of size {} with {} \
+                 signatures",
+                qc.signers().committee_size(),
+                qc.signers().count(),
+                qc.shard_group(),
+                committee.len(),
+                qc.signatures().len()
+            ),
+        });
+    };
+
+    if signer_public_keys.len() < committee.quorum_threshold() {
         return Err(ProposalValidationError::QuorumWasNotReached {
             qc: *qc.id(),
-            got: qc.signatures().len(),
+            got: signer_public_keys.len(),
             required: committee.quorum_threshold(),
         });
     }
 
-    let mut check_dups = HashSet::with_capacity(qc.signatures().len());
-    for signature in qc.signatures() {
-        if !committee.contains_public_key(signature.public_key()) {
-            return Err(ProposalValidationError::ValidatorNotInCommittee {
-                validator: signature.public_key().to_string(),
-                details: format!(
-                    "QC signed with validator {} that is not in committee {}",
-                    signature.public_key(),
-                    qc.shard_group(),
-                ),
-            });
-        }
-        if !check_dups.insert(signature.public_key()) {
-            return Err(ProposalValidationError::QcDuplicateSignature {
-                qc: *qc.id(),
-                validator: signature.public_key().clone(),
-            });
-        }
-        let message = vote_signing_service.create_message(qc.block_id(), &qc.decision());
-        if !signature.verify(message) {
+    let message = vote_signing_service.create_message(qc.block_id(), &qc.decision());
+    for (public_key, signature) in signer_public_keys.into_iter().zip(qc.signatures()) {
+        if !signature.verify(public_key, &message) {
             return Err(ProposalValidationError::QcInvalidSignature { qc: *qc.id() });
         }
     }
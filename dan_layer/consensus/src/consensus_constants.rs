@@ -48,6 +48,16 @@ pub struct ConsensusConstants {
     pub epochs_per_era: Epoch,
     /// Maximum size in bytes for a template WASM binary.
     pub template_binary_max_size_bytes: usize,
+    /// The number of blocks that a transaction may wait for a foreign proposal before the missing proposal is
+    /// requested from other members of the foreign shard group.
+    pub foreign_proposal_request_timeout_blocks: u64,
+    /// The number of blocks that a LocalPrepared transaction may wait for a foreign proposal before it is ABORTed.
+    /// This must be greater than `foreign_proposal_request_timeout_blocks`.
+    pub foreign_proposal_abort_timeout_blocks: u64,
+    /// The number of blocks that a LocalAccepted transaction may wait for a foreign LocalAccept from a shard group
+    /// that has not pledged anything for the transaction before it is ABORTed. This must be greater than
+    /// `foreign_proposal_abort_timeout_blocks`.
+    pub foreign_accept_abort_timeout_blocks: u64,
 }
 
 impl ConsensusConstants {
@@ -66,6 +76,9 @@ impl ConsensusConstants {
             fee_exhaust_divisor: 20, // 5%
             epochs_per_era: Epoch(10),
             template_binary_max_size_bytes: 1000 * 1000 * 5, // 5 MB
+            foreign_proposal_request_timeout_blocks: 50,
+            foreign_proposal_abort_timeout_blocks: 100,
+            foreign_accept_abort_timeout_blocks: 500,
         }
     }
 }
@@ -1,7 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_common_types::types::{FixedHash, PublicKey};
+use tari_common_types::types::FixedHash;
 use tari_crypto::ristretto::RistrettoPublicKey;
 use tari_dan_common_types::{Epoch, NodeHeight, ShardGroup, VersionedSubstateIdError};
 use tari_dan_storage::{
@@ -200,8 +200,8 @@ pub enum ProposalValidationError {
     InvalidSignature { block_id: BlockId, height: NodeHeight },
     #[error("QC has invalid signature: {qc}")]
     QcInvalidSignature { qc: QcId },
-    #[error("QC has duplicate signature: {qc} by {validator}")]
-    QcDuplicateSignature { qc: QcId, validator: PublicKey },
+    #[error("QC {qc} has invalid signers: {details}")]
+    QcInvalidSigners { qc: QcId, details: String },
     #[error("Quorum was not reached on QC {qc}. {got} out of {required} signatures")]
     QuorumWasNotReached { qc: QcId, got: usize, required: usize },
     #[error("Invalid network in block {block_id}: expected {expected_network}, given {block_network}")]
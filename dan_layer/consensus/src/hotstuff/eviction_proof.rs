@@ -2,6 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use log::*;
+use tari_dan_common_types::committee::Committee;
 use tari_dan_storage::{
     consensus_models::{Block, BlockHeader, QuorumCertificate, QuorumDecision},
     StateStoreReadTransaction,
@@ -21,8 +22,11 @@ use crate::hotstuff::HotStuffError;
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::eviction_proof";
 
-pub fn generate_eviction_proofs<TTx: StateStoreReadTransaction>(
+/// Generates eviction proofs for the committed blocks. The committee must be the local committee that signed the QCs,
+/// since the base layer requires the public key of each QC signer.
+pub fn generate_eviction_proofs<TTx: StateStoreReadTransaction, TAddr>(
     tx: &TTx,
+    committee: &Committee<TAddr>,
     tip_qc: &QuorumCertificate,
     committed_blocks_with_evictions: &[Block],
 ) -> Result<Vec<EvictionProof>, HotStuffError> {
@@ -34,7 +38,7 @@ pub fn generate_eviction_proofs<TTx: StateStoreReadTransaction>(
     let mut proofs = Vec::with_capacity(num_evictions);
     for block in committed_blocks_with_evictions {
         // First generate a commit proof for the block which is shared by all EvictionProofs
-        let block_commit_proof = generate_block_commit_proof(tx, tip_qc, block)?;
+        let block_commit_proof = generate_block_commit_proof(tx, committee, tip_qc, block)?;
 
         for (idx, command) in block.commands().iter().enumerate() {
             let Some(atom) = command.evict_node() else {
@@ -52,8 +56,9 @@ pub fn generate_eviction_proofs<TTx: StateStoreReadTransaction>(
     Ok(proofs)
 }
 
-fn generate_block_commit_proof<TTx: StateStoreReadTransaction>(
+fn generate_block_commit_proof<TTx: StateStoreReadTransaction, TAddr>(
     tx: &TTx,
+    committee: &Committee<TAddr>,
     tip_qc: &QuorumCertificate,
     commit_block: &Block,
 ) -> Result<SidechainBlockCommitProof, HotStuffError> {
@@ -66,13 +71,13 @@ fn generate_block_commit_proof<TTx: StateStoreReadTransaction>(
     }
 
     debug!(target: LOG_TARGET, "Add tip_qc: {tip_qc}");
-    proof_elements.push(convert_qc_to_proof_element(tip_qc));
+    proof_elements.push(convert_qc_to_proof_element(committee, tip_qc)?);
 
     let mut block = tip_qc.get_block(tx)?;
     while block.id() != commit_block.id() {
         if block.justifies_parent() {
             debug!(target: LOG_TARGET, "Add justify: {}", block.justify());
-            proof_elements.push(convert_qc_to_proof_element(block.justify()));
+            proof_elements.push(convert_qc_to_proof_element(committee, block.justify())?);
             block = block.get_parent(tx)?;
         } else {
             block = block.get_parent(tx)?;
@@ -102,7 +107,7 @@ fn generate_block_commit_proof<TTx: StateStoreReadTransaction>(
 
             proof_elements.push(CommitProofElement::DummyChain(dummy_chain));
             debug!(target: LOG_TARGET, "Add justify: {}", qc);
-            proof_elements.push(convert_qc_to_proof_element(&qc));
+            proof_elements.push(convert_qc_to_proof_element(committee, &qc)?);
         }
         // Prevent possibility of endless loop
         if block.height() < commit_block.height() {
@@ -146,21 +151,32 @@ pub fn convert_block_to_sidechain_block_header(header: &BlockHeader) -> Sidechai
     }
 }
 
-fn convert_qc_to_proof_element(qc: &QuorumCertificate) -> CommitProofElement {
-    CommitProofElement::QuorumCertificate(tari_sidechain::QuorumCertificate {
-        header_hash: *qc.header_hash(),
-        parent_id: *qc.parent_id().hash(),
-        signatures: qc
-            .signatures()
-            .iter()
-            .map(|s| ValidatorQcSignature {
-                public_key: s.public_key.clone(),
-                signature: s.signature.clone(),
-            })
-            .collect(),
-        decision: match qc.decision() {
-            QuorumDecision::Accept => tari_sidechain::QuorumDecision::Accept,
-            QuorumDecision::Reject => tari_sidechain::QuorumDecision::Reject,
+fn convert_qc_to_proof_element<TAddr>(
+    committee: &Committee<TAddr>,
+    qc: &QuorumCertificate,
+) -> Result<CommitProofElement, HotStuffError> {
+    let signatures = qc.expand_signatures(committee).ok_or_else(|| {
+        HotStuffError::InvariantError(format!(
+            "QC signers do not match the committee of size {} in convert_qc_to_proof_element ({qc})",
+            committee.len()
+        ))
+    })?;
+
+    Ok(CommitProofElement::QuorumCertificate(
+        tari_sidechain::QuorumCertificate {
+            header_hash: *qc.header_hash(),
+            parent_id: *qc.parent_id().hash(),
+            signatures: signatures
+                .into_iter()
+                .map(|s| ValidatorQcSignature {
+                    public_key: s.public_key,
+                    signature: s.signature,
+                })
+                .collect(),
+            decision: match qc.decision() {
+                QuorumDecision::Accept => tari_sidechain::QuorumDecision::Accept,
+                QuorumDecision::Reject => tari_sidechain::QuorumDecision::Reject,
+            },
         },
-    })
+    ))
 }
@@ -88,6 +88,7 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
                     return Ok(MessageValidationResult::Discard);
                 }
                 self.process_local_proposal(current_height, from, local_committee_info, local_committee, msg)
+                    .await
             },
             HotstuffMessage::ForeignProposal(proposal) => {
                 self.process_foreign_proposal(local_committee_info, from, proposal)
@@ -156,7 +157,7 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         req_id
     }
 
-    fn process_local_proposal(
+    async fn process_local_proposal(
         &mut self,
         current_height: NodeHeight,
         from: TConsensusSpec::Addr,
@@ -183,7 +184,24 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
             return Ok(MessageValidationResult::Discard);
         }
 
-        if let Err(err) = self.check_local_proposal(&proposal.block, local_committee) {
+        let justify_committee = match block_validations::get_committee_for_qc(
+            &self.epoch_manager,
+            proposal.block.justify(),
+            local_committee_info.num_preshards(),
+        )
+        .await
+        {
+            Ok(committee) => committee,
+            Err(err) => {
+                return Ok(MessageValidationResult::Invalid {
+                    from,
+                    message: HotstuffMessage::Proposal(proposal),
+                    err: err.into(),
+                });
+            },
+        };
+
+        if let Err(err) = self.check_local_proposal(&proposal.block, local_committee, &justify_committee) {
             return Ok(MessageValidationResult::Invalid {
                 from,
                 message: HotstuffMessage::Proposal(proposal),
@@ -234,11 +252,13 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         &self,
         block: &Block,
         committee_for_block: &Committee<TConsensusSpec::Addr>,
+        justify_committee: &Committee<TConsensusSpec::Addr>,
     ) -> Result<(), HotStuffError> {
         block_validations::check_local_proposal::<TConsensusSpec>(
             self.current_view.get_epoch(),
             block,
             committee_for_block,
+            justify_committee,
             &self.vote_signing_service,
             &self.leader_strategy,
             &self.config,
@@ -249,10 +269,12 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         &self,
         block: &Block,
         committee_for_block: &Committee<TConsensusSpec::Addr>,
+        justify_committee: &Committee<TConsensusSpec::Addr>,
     ) -> Result<(), HotStuffError> {
         block_validations::check_proposal::<TConsensusSpec>(
             block,
             committee_for_block,
+            justify_committee,
             &self.vote_signing_service,
             &self.leader_strategy,
             &self.config,
@@ -362,7 +384,24 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
             .get_committee_by_validator_public_key(msg.block.epoch(), msg.block.proposed_by().clone())
             .await?;
 
-        if let Err(err) = self.check_foreign_proposal(&msg.block, &committee) {
+        let justify_committee = match block_validations::get_committee_for_qc(
+            &self.epoch_manager,
+            msg.block.justify(),
+            local_committee_info.num_preshards(),
+        )
+        .await
+        {
+            Ok(committee) => committee,
+            Err(err) => {
+                return Ok(MessageValidationResult::Invalid {
+                    from,
+                    message: HotstuffMessage::ForeignProposal(msg),
+                    err: err.into(),
+                });
+            },
+        };
+
+        if let Err(err) = self.check_foreign_proposal(&msg.block, &committee, &justify_committee) {
             return Ok(MessageValidationResult::Invalid {
                 from,
                 message: HotstuffMessage::ForeignProposal(msg),
@@ -1,16 +1,17 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::num::NonZeroU64;
+use std::{cmp, num::NonZeroU64};
 
 use log::*;
 use tari_crypto::ristretto::RistrettoPublicKey;
 use tari_dan_common_types::{
-    committee::CommitteeInfo,
+    committee::{Committee, CommitteeInfo},
     displayable::Displayable,
     optional::Optional,
     shard::Shard,
     Epoch,
+    NodeHeight,
     ShardGroup,
     VersionedSubstateId,
 };
@@ -113,6 +114,7 @@ where TConsensusSpec: ConsensusSpec
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
         valid_block: &ValidBlock,
         local_committee_info: &CommitteeInfo,
+        local_committee: &Committee<TConsensusSpec::Addr>,
         proposer_claim_public_key_bytes: [u8; 32],
         can_propose_epoch_end: bool,
         change_set: &mut ProposedBlockChangeSet,
@@ -168,7 +170,8 @@ where TConsensusSpec: ConsensusSpec
                     self.on_lock_block(tx, block)
                 },
                 |tx, last_exec, commit_block| {
-                    let committed = self.on_commit(tx, last_exec, &commit_block, local_committee_info)?;
+                    let committed =
+                        self.on_commit(tx, last_exec, &commit_block, local_committee_info, local_committee)?;
                     if commit_block.is_epoch_end() {
                         end_of_epoch = Some(commit_block.epoch());
                     }
@@ -534,6 +537,14 @@ where TConsensusSpec: ConsensusSpec
             }
         }
 
+        self.abort_stalled_transactions(
+            tx,
+            block,
+            &locked_block,
+            local_committee_info,
+            proposed_block_change_set,
+        )?;
+
         if total_leader_fee != block.total_leader_fee() {
             warn!(
                 target: LOG_TARGET,
@@ -1853,6 +1864,116 @@ where TConsensusSpec: ConsensusSpec
         Ok(None)
     }
 
+    /// ABORTs transactions that have waited too long for a foreign proposal. The timeout is measured in local block
+    /// heights so that every local validator reaches the same decision for this block. The transaction is marked as
+    /// ready so that the next leader proposes the ABORT.
+    ///
+    /// A transaction that waited in `LocalPrepared` for `foreign_proposal_abort_timeout_blocks` is proposed as
+    /// SomePrepare and then as LocalAccept(ABORT). The LocalAccept block is sent to the other involved shard groups as
+    /// a foreign proposal, so a stalled group that resumes learns of the ABORT and releases its locks. A foreign group
+    /// cannot have committed the transaction because that requires a LocalAccept(COMMIT) from the local group.
+    ///
+    /// A transaction that waited in `LocalAccepted` is only ABORTed after `foreign_accept_abort_timeout_blocks` and
+    /// only if the evidence shows that the foreign group has not pledged anything for the transaction, i.e. it has
+    /// never been heard from. The local LocalAccept has already been sent, so the transaction is proposed as
+    /// SomeAccept and the ABORT is not sent to the foreign group. If the foreign group is only partitioned from the
+    /// local group, it could still accept the transaction, which is why this timeout is much longer.
+    fn abort_stalled_transactions(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+        block: &Block,
+        locked_block: &LockedBlock,
+        local_committee_info: &CommitteeInfo,
+        proposed_block_change_set: &mut ProposedBlockChangeSet,
+    ) -> Result<(), HotStuffError> {
+        let prepare_timeout_blocks = self.config.consensus_constants.foreign_proposal_abort_timeout_blocks;
+        let accept_timeout_blocks = self.config.consensus_constants.foreign_accept_abort_timeout_blocks;
+        let Some(max_since_height) = block
+            .height()
+            .as_u64()
+            .checked_sub(cmp::min(prepare_timeout_blocks, accept_timeout_blocks))
+        else {
+            return Ok(());
+        };
+
+        let stalled = self.transaction_pool.get_stalled(
+            tx,
+            NodeHeight(max_since_height),
+            self.config.consensus_constants.max_block_size,
+        )?;
+
+        for (transaction_id, since_height) in stalled {
+            let Some(mut tx_rec) = proposed_block_change_set
+                .get_transaction(tx, locked_block, &block.as_leaf_block(), &transaction_id)
+                .optional()?
+            else {
+                continue;
+            };
+
+            // CASE: the transaction progressed in this block or in a block that is not yet committed
+            if tx_rec.is_ready() || tx_rec.current_stage() != tx_rec.committed_stage() {
+                continue;
+            }
+
+            let awaiting = tx_rec.foreign_shard_groups_awaiting_proposal(local_committee_info.shard_group());
+            let (timeout_blocks, foreign_shard_group) = match tx_rec.current_stage() {
+                TransactionPoolStage::LocalPrepared => (prepare_timeout_blocks, awaiting.into_iter().next()),
+                // CASE: we have accepted the transaction. Only a foreign group that has not pledged anything for the
+                //       transaction is timed out.
+                TransactionPoolStage::LocalAccepted => (
+                    accept_timeout_blocks,
+                    awaiting.into_iter().find(|sg| {
+                        tx_rec
+                            .evidence()
+                            .get(sg)
+                            .is_some_and(|e| !e.is_prepare_justified() && !e.is_accept_justified())
+                    }),
+                ),
+                _ => continue,
+            };
+            let Some(foreign_shard_group) = foreign_shard_group else {
+                continue;
+            };
+            if since_height.as_u64() + timeout_blocks > block.height().as_u64() {
+                continue;
+            }
+
+            warn!(
+                target: LOG_TARGET,
+                "⏰ Transaction {} has waited for a foreign proposal from {} since block height {} ({} stage). ABORTing.",
+                transaction_id,
+                foreign_shard_group,
+                since_height,
+                tx_rec.current_stage(),
+            );
+
+            if tx_rec.current_decision().is_commit() {
+                // Add an abort execution since we previously decided to commit
+                let mut transaction = TransactionRecord::get(tx, &transaction_id)?;
+                transaction.abort(RejectReason::ForeignProposalTimeout {
+                    start_shard: foreign_shard_group.start().as_u32(),
+                    end_shard: foreign_shard_group.end().as_u32(),
+                    timeout_blocks,
+                });
+                tx_rec.set_local_decision(transaction.current_decision());
+                let exec = transaction.into_execution().ok_or_else(|| {
+                    HotStuffError::InvariantError(format!(
+                        "abort_stalled_transactions: transaction {transaction_id} has no execution after ABORT"
+                    ))
+                })?;
+                proposed_block_change_set.add_transaction_execution(exec)?;
+            }
+            tx_rec.set_remote_decision(Decision::Abort(AbortReason::ForeignProposalTimeout));
+
+            tx_rec.set_next_stage(tx_rec.current_stage())?;
+            // We are ready to propose ABORT even if no other shard group has prepared or accepted
+            tx_rec.set_ready(true);
+            proposed_block_change_set.set_next_transaction_update(tx_rec)?;
+        }
+
+        Ok(())
+    }
+
     fn evaluate_mint_confidential_output_command(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
@@ -1931,8 +2052,9 @@ where TConsensusSpec: ConsensusSpec
         last_executed: &LastExecuted,
         block: &Block,
         local_committee_info: &CommitteeInfo,
+        local_committee: &Committee<TConsensusSpec::Addr>,
     ) -> Result<Vec<TransactionPoolRecord>, HotStuffError> {
-        let committed_transactions = self.finalize_block(tx, block, local_committee_info)?;
+        let committed_transactions = self.finalize_block(tx, block, local_committee_info, local_committee)?;
         debug!(
             target: LOG_TARGET,
             "✅ COMMIT block {}, last executed height = {}",
@@ -1981,6 +2103,7 @@ where TConsensusSpec: ConsensusSpec
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
         block: &Block,
         local_committee_info: &CommitteeInfo,
+        local_committee: &Committee<TConsensusSpec::Addr>,
     ) -> Result<Vec<TransactionPoolRecord>, HotStuffError> {
         if block.is_dummy() {
             block.increment_leader_failure_count(
@@ -2052,7 +2175,7 @@ where TConsensusSpec: ConsensusSpec
             );
         }
 
-        block.justify().update_participation_shares(tx)?;
+        block.justify().update_participation_shares(tx, local_committee)?;
         block.clear_leader_failure_count(tx)?;
 
         Ok(finalized_transactions)
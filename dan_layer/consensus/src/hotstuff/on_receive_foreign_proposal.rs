@@ -11,6 +11,7 @@ use tari_dan_storage::{
     StorageError,
 };
 use tari_epoch_manager::EpochManagerReader;
+use tari_transaction::TransactionId;
 use tokio::task;
 
 use crate::{
@@ -27,6 +28,10 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_foreign_proposal";
 
+/// The maximum number of blocks to search when responding to a request for the proposals of a transaction. A
+/// transaction has at most a LocalPrepare and a LocalAccept block, but may also appear in other local commands.
+const MAX_BLOCKS_PER_TRANSACTION: usize = 10;
+
 #[derive(Clone)]
 pub struct OnReceiveForeignProposalHandler<TConsensusSpec: ConsensusSpec> {
     store: TConsensusSpec::StateStore,
@@ -189,6 +194,51 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 
+    /// Requests the proposal(s) for a transaction from a foreign shard group that has not sent them in time. The
+    /// request is sent to f + 1 members of the foreign shard group so that at least one honest member receives it.
+    pub async fn request_by_transaction_id(
+        &mut self,
+        current_epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        foreign_shard_group: ShardGroup,
+        transaction_id: TransactionId,
+    ) -> Result<(), HotStuffError> {
+        let f = local_committee_info.max_failures() as usize;
+        let committee = self
+            .epoch_manager
+            .get_committee_by_shard_group(current_epoch, foreign_shard_group, Some(f + 1))
+            .await?;
+
+        if committee.is_empty() {
+            warn!(
+                target: LOG_TARGET,
+                "FOREIGN PROPOSAL: No validators found for the shard group {}",
+                foreign_shard_group,
+            );
+            return Ok(());
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "🌐 REQUEST foreign proposal for transaction {} from {} member(s) of {}",
+            transaction_id,
+            committee.len(),
+            foreign_shard_group,
+        );
+        self.outbound_messaging
+            .multicast(
+                committee.into_addresses(),
+                HotstuffMessage::ForeignProposalRequest(ForeignProposalRequestMessage::ByTransactionId {
+                    transaction_id,
+                    for_shard_group: local_committee_info.shard_group(),
+                    epoch: current_epoch,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn handle_requested(
         &mut self,
         from: TConsensusSpec::Addr,
@@ -265,11 +315,60 @@ where TConsensusSpec: ConsensusSpec
                     )
                     .await?;
             },
-            ForeignProposalRequestMessage::ByTransactionId { .. } => {
-                error!(
+            ForeignProposalRequestMessage::ByTransactionId {
+                transaction_id,
+                for_shard_group,
+                ..
+            } => {
+                info!(
                     target: LOG_TARGET,
-                    "TODO FOREIGN PROPOSAL: Request by transaction id is not supported. Ignoring."
+                    "🌐 HANDLE foreign proposal request from {} for transaction {}",
+                    for_shard_group,
+                    transaction_id,
                 );
+                let proposals = store.with_read_tx(|tx| {
+                    Block::get_committed_by_transaction(tx, &transaction_id, MAX_BLOCKS_PER_TRANSACTION)?
+                        .into_iter()
+                        .filter(|block| {
+                            block.commands().iter().any(|cmd| {
+                                cmd.local_prepare().or_else(|| cmd.local_accept()).is_some_and(|atom| {
+                                    atom.id == transaction_id && atom.evidence.has(&for_shard_group)
+                                })
+                            })
+                        })
+                        .map(|block| {
+                            let justify_qc = block.get_justify_qc(tx)?;
+                            let block_pledge = block.get_block_pledge(tx, for_shard_group)?;
+                            Ok(ForeignProposalMessage {
+                                block,
+                                justify_qc,
+                                block_pledge,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, StorageError>>()
+                })?;
+
+                if proposals.is_empty() {
+                    warn!(
+                        target: LOG_TARGET,
+                        "FOREIGN PROPOSAL: No committed proposal found for transaction {}. Ignoring.",
+                        transaction_id,
+                    );
+                    return Ok(());
+                }
+
+                for proposal in proposals {
+                    info!(
+                        target: LOG_TARGET,
+                        "🌐 FOREIGN PROPOSAL REPLY to {} foreign proposal {} for transaction {}",
+                        for_shard_group,
+                        proposal.block.as_leaf_block(),
+                        transaction_id,
+                    );
+                    outbound_messaging
+                        .send(from.clone(), HotstuffMessage::ForeignProposal(proposal))
+                        .await?;
+                }
             },
         }
 
@@ -1,7 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{collections::HashSet, mem};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
 
 use log::*;
 use tari_dan_common_types::{
@@ -16,6 +19,7 @@ use tari_dan_storage::{
         ForeignProposalStatus,
         HighQc,
         LastSentVote,
+        LeafBlock,
         QuorumDecision,
         TransactionPool,
         ValidBlock,
@@ -25,6 +29,7 @@ use tari_dan_storage::{
     StateStoreWriteTransaction,
 };
 use tari_epoch_manager::EpochManagerReader;
+use tari_transaction::TransactionId;
 use tokio::{sync::broadcast, task};
 
 use crate::{
@@ -68,6 +73,9 @@ pub struct OnReceiveLocalProposalHandler<TConsensusSpec: ConsensusSpec> {
     outbound_messaging: TConsensusSpec::OutboundMessaging,
     vote_signing_service: TConsensusSpec::SignatureService,
     on_receive_foreign_proposal: OnReceiveForeignProposalHandler<TConsensusSpec>,
+    transaction_pool: TransactionPool<TConsensusSpec::StateStore>,
+    /// The block height at which missing foreign proposals were last requested for a transaction
+    foreign_proposal_requests: HashMap<TransactionId, NodeHeight>,
     tx_events: broadcast::Sender<HotstuffEvent>,
     hooks: TConsensusSpec::Hooks,
 }
@@ -110,10 +118,12 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             on_ready_to_vote_on_local_block: OnReadyToVoteOnLocalBlock::new(
                 local_validator_pk,
                 config,
-                transaction_pool,
+                transaction_pool.clone(),
                 tx_events,
                 transaction_manager,
             ),
+            transaction_pool,
+            foreign_proposal_requests: HashMap::new(),
             change_set: None,
         }
     }
@@ -161,19 +171,6 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
 
         // First validate and save the attached foreign proposals
         let is_all_foreign_proposals_valid = self.store.with_write_tx(|tx| {
-            // TODO: Implement guaranteed finality in the face of a non-cooperating remote shard group.
-            // Suggested strategy:
-            // Given a transaction that is awaiting a foreign proposal for REQUEST_FOREIGN_PROPOSAL_TIMEOUT (e.g. 50)
-            // blocks
-            // - Load pending transactions that are awaiting foreign proposal for >= REQUEST_FOREIGN_PROPOSAL_TIMEOUT
-            // - Request foreign proposal from remote shard group [END]
-            //
-            // Given a transaction that is awaiting a foreign proposal for MISSING_FOREIGN_PROPOSAL_TIMEOUT (e.g. 100)
-            // blocks
-            // - Load pending transactions that are awaiting foreign proposal for >= MISSING_FOREIGN_PROPOSAL_TIMEOUT
-            // - Set abort and ready = true
-            // self.update_foreign_proposal_transactions(tx, valid_block.block())?;
-
             for mut foreign_proposal in foreign_proposals {
                 if foreign_proposal.exists(&**tx)? {
                     // This is expected behaviour, we may receive the same foreign proposal multiple times
@@ -217,6 +214,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .get_validator_node_by_public_key(valid_block.epoch(), valid_block.proposed_by().clone())
             .await?;
         let proposer_claim_public_key_bytes = to_public_key_bytes(&proposer_vn.fee_claim_public_key);
+        let leaf_block = valid_block.block().as_leaf_block();
 
         let result = self
             .process_block(
@@ -229,7 +227,19 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .await;
 
         match result {
-            Ok(is_accept) => Ok(is_accept),
+            Ok(is_accept) => {
+                // Transactions that wait too long are ABORTed when processing the block. Before that happens, we
+                // try to get the missing foreign proposals from other members of the foreign shard group.
+                if is_accept {
+                    if let Err(err) = self
+                        .request_stalled_foreign_proposals(current_epoch, local_committee_info, &leaf_block)
+                        .await
+                    {
+                        warn!(target: LOG_TARGET, "Failed to request missing foreign proposals: {}", err);
+                    }
+                }
+                Ok(is_accept)
+            },
             Err(err) => {
                 if let Err(err) = self.pacemaker.resume_leader_failure().await {
                     error!(target: LOG_TARGET, "Error resuming leader failure: {:?}", err);
@@ -271,6 +281,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 .unwrap_or_else(|| ProposedBlockChangeSet::new(valid_block.block().as_leaf_block()));
 
             let store = self.store.clone();
+            let local_committee = local_committee.clone();
 
             // Task closure
             move || {
@@ -279,6 +290,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                         tx,
                         &valid_block,
                         &local_committee_info,
+                        &local_committee,
                         proposer_claim_public_key_bytes,
                         can_propose_epoch_end,
                         &mut change_set,
@@ -302,9 +314,10 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         if is_accept_decision && !block_decision.committed_blocks_with_evictions.is_empty() {
             let store = self.store.clone();
             let qc = valid_block.justify().clone();
+            let committee = local_committee.clone();
             let committed_blocks_with_evictions = mem::take(&mut block_decision.committed_blocks_with_evictions);
             let proofs = task::spawn_blocking(move || {
-                store.with_read_tx(|tx| generate_eviction_proofs(tx, &qc, &committed_blocks_with_evictions))
+                store.with_read_tx(|tx| generate_eviction_proofs(tx, &committee, &qc, &committed_blocks_with_evictions))
             })
             .await??;
             info!(target: LOG_TARGET, "🦶 Generated {} eviction proofs", proofs.len());
@@ -416,6 +429,65 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         Ok(is_accept_decision)
     }
 
+    /// Requests missing foreign proposals for transactions that have waited for at least
+    /// `foreign_proposal_request_timeout_blocks`. The request is repeated every
+    /// `foreign_proposal_request_timeout_blocks` until the transaction progresses or is ABORTed.
+    async fn request_stalled_foreign_proposals(
+        &mut self,
+        current_epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        leaf_block: &LeafBlock,
+    ) -> Result<(), HotStuffError> {
+        let timeout_blocks = self.config.consensus_constants.foreign_proposal_request_timeout_blocks;
+        let Some(max_since_height) = leaf_block.height().as_u64().checked_sub(timeout_blocks) else {
+            return Ok(());
+        };
+
+        let stalled = self.store.with_read_tx(|tx| {
+            let stalled = self.transaction_pool.get_stalled(
+                tx,
+                NodeHeight(max_since_height),
+                self.config.consensus_constants.max_block_size,
+            )?;
+
+            let mut awaiting = Vec::with_capacity(stalled.len());
+            for (transaction_id, _) in stalled {
+                let Some(tx_rec) = self.transaction_pool.get(tx, leaf_block, &transaction_id).optional()? else {
+                    continue;
+                };
+                let shard_groups = tx_rec.foreign_shard_groups_awaiting_proposal(local_committee_info.shard_group());
+                if !shard_groups.is_empty() {
+                    awaiting.push((transaction_id, shard_groups));
+                }
+            }
+            Ok::<_, HotStuffError>(awaiting)
+        })?;
+
+        // Forget transactions that are no longer stalled
+        self.foreign_proposal_requests
+            .retain(|id, _| stalled.iter().any(|(transaction_id, _)| transaction_id == id));
+
+        for (transaction_id, shard_groups) in stalled {
+            let is_due = self
+                .foreign_proposal_requests
+                .get(&transaction_id)
+                .map_or(true, |last| leaf_block.height() >= *last + NodeHeight(timeout_blocks));
+            if !is_due {
+                continue;
+            }
+
+            for shard_group in shard_groups {
+                self.on_receive_foreign_proposal
+                    .request_by_transaction_id(current_epoch, local_committee_info, shard_group, transaction_id)
+                    .await?;
+            }
+            self.foreign_proposal_requests
+                .insert(transaction_id, leaf_block.height());
+        }
+
+        Ok(())
+    }
+
     fn publish_event(&self, event: HotstuffEvent) {
         let _ignore = self.tx_events.send(event);
     }
@@ -586,42 +658,6 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         }
     }
 
-    // fn update_foreign_proposal_transactions(
-    //     &self,
-    //     tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
-    //     block: &Block,
-    // ) -> Result<(), HotStuffError> {
-    //     // TODO: Move this to consensus constants
-    //     const FOREIGN_PROPOSAL_TIMEOUT: u64 = 1000;
-    //     let all_proposed = ForeignProposal::get_all_proposed(
-    //         &**tx,
-    //         block.height().saturating_sub(NodeHeight(FOREIGN_PROPOSAL_TIMEOUT)),
-    //     )?;
-    //     for proposal in all_proposed {
-    //         let mut has_unresolved_transactions = false;
-    //
-    //         let (transactions, _missing) = TransactionRecord::get_any(&**tx, &proposal.transactions)?;
-    //         for transaction in transactions {
-    //             if transaction.is_finalized() {
-    //                 // We don't know the transaction at all, or we know it but it's not finalised.
-    //                 let mut tx_rec = self
-    //                     .transaction_pool
-    //                     .get(&**tx, block.as_leaf_block(), transaction.id())?;
-    //                 // If the transaction is still in the pool we have to check if it was at least locally prepared,
-    //                 // otherwise abort it.
-    //                 if tx_rec.current_stage().is_new() || tx_rec.current_stage().is_prepared() {
-    //                     tx_rec.update_local_decision(tx, Decision::Abort)?;
-    //                     has_unresolved_transactions = true;
-    //                 }
-    //             }
-    //         }
-    //         if !has_unresolved_transactions {
-    //             proposal.delete(tx)?;
-    //         }
-    //     }
-    //     Ok(())
-    // }
-
     // TODO: fix
     // fn check_foreign_indexes(
     //     &self,
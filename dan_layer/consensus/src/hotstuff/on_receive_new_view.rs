@@ -14,10 +14,11 @@ use tari_dan_storage::{
     consensus_models::{Block, BlockId, LeafBlock, QuorumCertificate},
     StateStore,
 };
+use tari_epoch_manager::EpochManagerReader;
 
 use super::vote_collector::VoteCollector;
 use crate::{
-    block_validations::check_quorum_certificate,
+    block_validations::{check_quorum_certificate, get_committee_for_qc},
     hotstuff::{error::HotStuffError, pacemaker_handle::PaceMakerHandle},
     messages::NewViewMessage,
     tracing::TraceTimer,
@@ -29,6 +30,7 @@ const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_new_view";
 pub struct OnReceiveNewViewHandler<TConsensusSpec: ConsensusSpec> {
     local_validator_addr: TConsensusSpec::Addr,
     store: TConsensusSpec::StateStore,
+    epoch_manager: TConsensusSpec::EpochManager,
     leader_strategy: TConsensusSpec::LeaderStrategy,
     newview_message_counts: HashMap<(NodeHeight, BlockId), HashSet<TConsensusSpec::Addr>>,
     pacemaker: PaceMakerHandle,
@@ -41,6 +43,7 @@ where TConsensusSpec: ConsensusSpec
     pub fn new(
         local_validator_addr: TConsensusSpec::Addr,
         store: TConsensusSpec::StateStore,
+        epoch_manager: TConsensusSpec::EpochManager,
         leader_strategy: TConsensusSpec::LeaderStrategy,
         pacemaker: PaceMakerHandle,
         vote_receiver: VoteCollector<TConsensusSpec>,
@@ -48,6 +51,7 @@ where TConsensusSpec: ConsensusSpec
         Self {
             local_validator_addr,
             store,
+            epoch_manager,
             leader_strategy,
             newview_message_counts: HashMap::default(),
             pacemaker,
@@ -105,10 +109,21 @@ where TConsensusSpec: ConsensusSpec
             return Ok(());
         }
 
+        // The high QC may have been formed in a previous epoch, so its signers are resolved against the committee of
+        // that epoch
+        let qc_committee =
+            match get_committee_for_qc(&self.epoch_manager, &high_qc, local_committee_info.num_preshards()).await {
+                Ok(committee) => committee,
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "❌ NEWVIEW: Unable to get committee for QC {}: {}", high_qc, err);
+                    return Ok(());
+                },
+            };
+
         let is_qc_valid = self.store.with_read_tx(|tx| {
             // If we already have this QC (locally calculated hash matches), we do not need to validate this again
             if !high_qc.exists(tx)? {
-                if let Err(err) = self.validate_qc(&high_qc, local_committee, self.vote_collector.signing_service()) {
+                if let Err(err) = self.validate_qc(&high_qc, &qc_committee, self.vote_collector.signing_service()) {
                     warn!(target: LOG_TARGET, "❌ NEWVIEW: Invalid QC: {}", err);
                     return Ok(false);
                 }
@@ -82,7 +82,8 @@ impl<TStateStore: StateStore, TExecutor: BlockTransactionExecutor<TStateStore>>
                     let id = input.with_version(version).to_owned();
                     store.lock_assert_is_up(&id)?;
                     info!(target: LOG_TARGET, "Resolved LOCAL substate: {id}");
-                    resolved_substates.insert(id.into(), version);
+                    // Keep the declared lock intent of the input
+                    resolved_substates.insert(input.to_owned(), version);
                 },
                 None => {
                     let latest = store.get_latest_version(input.substate_id())?;
@@ -374,16 +375,23 @@ impl<TStateStore: StateStore, TExecutor: BlockTransactionExecutor<TStateStore>>
                     }
                 },
                 None => {
-                    // TODO: We do not know if the inputs locks required are Read/Write. Either we allow the user to
-                    //       specify this or we can correct the locks after execution. Currently, this limitation
-                    //       prevents concurrent multi-shard read locks.
-                    let requested_locks = local_versions.iter().map(|(substate_id, version)| {
-                        // TODO: we assume all resources are not being written to. How can we do this in the vast
-                        // majority of cases but still allow (presumably rare) Access Rule updates?
-                        if substate_id.substate_id().is_read_only() || substate_id.substate_id().is_resource() {
-                            SubstateRequirementLockIntent::read(substate_id.clone(), *version)
+                    // We have not executed the transaction, so we use the lock intents declared by the transaction.
+                    // The engine rejects the transaction if it writes to an input declared as read-only.
+                    let requested_locks = local_versions.iter().map(|(requirement, version)| {
+                        if let Some(lock_intent) = requirement.lock_intent() {
+                            return SubstateRequirementLockIntent::new(
+                                requirement.clone(),
+                                *version,
+                                lock_intent.into(),
+                            );
+                        }
+                        // TODO: for inputs without a declared intent, we assume all resources are not being written
+                        // to. How can we do this in the vast majority of cases but still allow (presumably rare)
+                        // Access Rule updates?
+                        if requirement.substate_id().is_read_only() || requirement.substate_id().is_resource() {
+                            SubstateRequirementLockIntent::read(requirement.clone(), *version)
                         } else {
-                            SubstateRequirementLockIntent::write(substate_id.clone(), *version)
+                            SubstateRequirementLockIntent::write(requirement.clone(), *version)
                         }
                     });
 
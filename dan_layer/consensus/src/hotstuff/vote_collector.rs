@@ -4,9 +4,21 @@
 use log::*;
 use tari_common::configuration::Network;
 use tari_common_types::types::FixedHash;
-use tari_dan_common_types::{committee::CommitteeInfo, optional::Optional, Epoch};
+use tari_dan_common_types::{
+    committee::{Committee, CommitteeInfo},
+    optional::Optional,
+    Epoch,
+};
 use tari_dan_storage::{
-    consensus_models::{Block, HighQc, QuorumCertificate, QuorumDecision, ValidatorSignature, Vote},
+    consensus_models::{
+        Block,
+        HighQc,
+        QuorumCertificate,
+        QuorumDecision,
+        SignerBitmap,
+        ValidatorSchnorrSignature,
+        Vote,
+    },
     global::models::ValidatorNode,
     StateStore,
 };
@@ -66,7 +78,9 @@ where TConsensusSpec: ConsensusSpec
 
         self.validate_vote_message(current_epoch, &message)?;
         let sender_vn = self.check_eligibility(from, &message, local_committee_info).await?;
-        let maybe_qc = self.collect_vote(message, local_committee_info, sender_vn)?;
+        // The QC signer bitmap is over the canonical (shard key) ordering of the local committee
+        let committee = self.epoch_manager.get_local_committee(message.epoch).await?;
+        let maybe_qc = self.collect_vote(message, local_committee_info, &committee, sender_vn)?;
         if let Some((ref qc, ref high_qc)) = maybe_qc {
             if qc.id() == high_qc.qc_id() {
                 info!(target: LOG_TARGET, "🔥 New HIGH {}", qc);
@@ -114,6 +128,7 @@ where TConsensusSpec: ConsensusSpec
         &self,
         message: VoteMessage,
         local_committee_info: &CommitteeInfo,
+        committee: &Committee<TConsensusSpec::Addr>,
         sender_vn: ValidatorNode<TConsensusSpec::Addr>,
     ) -> Result<Option<(QuorumCertificate, HighQc)>, HotStuffError> {
         self.store.with_write_tx(|tx| {
@@ -189,19 +204,30 @@ where TConsensusSpec: ConsensusSpec
                 return Ok(None);
             };
 
-            let mut signatures = Vec::with_capacity(votes.len());
+            let mut signers = SignerBitmap::new(committee.len());
+            let mut indexed_signatures = Vec::with_capacity(votes.len());
             let mut leaf_hashes = Vec::with_capacity(votes.len());
             for vote in votes {
                 if vote.decision != quorum_decision {
                     // We don't include votes that don't match the quorum decision
                     continue;
                 }
-                signatures.push(vote.signature);
+                let Some(index) = committee.public_keys().position(|pk| *pk == vote.signature.public_key) else {
+                    return Err(HotStuffError::InvariantError(format!(
+                        "Vote for block {} from {} is not from a committee member",
+                        vote.block_id, vote.signature.public_key
+                    )));
+                };
+                signers.set(index);
+                indexed_signatures.push((index, vote.signature.signature));
                 leaf_hashes.push(vote.sender_leaf_hash);
             }
+            // Signatures are ordered by committee index to match the signer bitmap
+            indexed_signatures.sort_by_key(|(index, _)| *index);
 
             let vote_data = VoteData {
-                signatures,
+                signers,
+                signatures: indexed_signatures.into_iter().map(|(_, sig)| sig).collect(),
                 leaf_hashes,
                 quorum_decision,
                 block,
@@ -259,6 +285,7 @@ where TConsensusSpec: ConsensusSpec
 
 fn create_qc(vote_data: VoteData) -> QuorumCertificate {
     let VoteData {
+        signers,
         signatures,
         leaf_hashes,
         quorum_decision,
@@ -270,6 +297,7 @@ fn create_qc(vote_data: VoteData) -> QuorumCertificate {
         block.height(),
         block.epoch(),
         block.shard_group(),
+        signers,
         signatures,
         leaf_hashes,
         quorum_decision,
@@ -277,7 +305,8 @@ fn create_qc(vote_data: VoteData) -> QuorumCertificate {
 }
 
 struct VoteData {
-    signatures: Vec<ValidatorSignature>,
+    signers: SignerBitmap,
+    signatures: Vec<ValidatorSchnorrSignature>,
     leaf_hashes: Vec<FixedHash>,
     quorum_decision: QuorumDecision,
     block: Block,
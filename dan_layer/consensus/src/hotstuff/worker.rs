@@ -177,6 +177,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             on_receive_new_view: OnReceiveNewViewHandler::new(
                 local_validator_addr,
                 state_store.clone(),
+                epoch_manager.clone(),
                 leader_strategy.clone(),
                 pacemaker.clone_handle(),
                 vote_receiver,
@@ -1,7 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-mod block_validations;
+pub mod block_validations;
 pub mod consensus_constants;
 pub mod hotstuff;
 pub mod messages;
@@ -3,7 +3,7 @@
 
 use std::fmt::{Display, Formatter};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{Epoch, ShardGroup};
 use tari_dan_storage::consensus_models::{
     Block,
@@ -15,7 +15,7 @@ use tari_dan_storage::consensus_models::{
 };
 use tari_transaction::TransactionId;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignProposalMessage {
     pub block: Block,
     pub justify_qc: QuorumCertificate,
@@ -67,7 +67,7 @@ impl Display for ForeignProposalMessage {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignProposalNotificationMessage {
     pub block_id: BlockId,
     pub epoch: Epoch,
@@ -83,7 +83,7 @@ impl Display for ForeignProposalNotificationMessage {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ForeignProposalRequestMessage {
     /// Request a foreign proposal for a specific block ID in response to a ForeignProposalNotificationMessage that has
     /// not already been received.
@@ -3,7 +3,7 @@
 
 use std::fmt::Display;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::Epoch;
 
 use super::{
@@ -17,8 +17,8 @@ use super::{
 };
 use crate::messages::{MissingTransactionsRequest, SyncRequestMessage, SyncResponseMessage};
 
-// Serialize is implemented for the message logger
-#[derive(Debug, Clone, Serialize)]
+// Serialize and Deserialize are implemented for the message logger and message replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum HotstuffMessage {
     NewView(NewViewMessage),
     Proposal(ProposalMessage),
@@ -3,10 +3,10 @@
 
 use std::fmt::{Display, Formatter};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_storage::consensus_models::{Block, ForeignProposal};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposalMessage {
     pub block: Block,
     pub foreign_proposals: Vec<ForeignProposal>,
@@ -3,12 +3,12 @@
 
 use std::collections::HashSet;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::Epoch;
 use tari_dan_storage::consensus_models::BlockId;
 use tari_transaction::TransactionId;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MissingTransactionsRequest {
     pub request_id: u32,
     pub epoch: Epoch,
@@ -1,12 +1,12 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::Epoch;
 use tari_dan_storage::consensus_models::BlockId;
 use tari_transaction::Transaction;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MissingTransactionsResponse {
     pub request_id: u32,
     pub epoch: Epoch,
@@ -3,11 +3,11 @@
 
 use std::fmt::Display;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{Epoch, NodeHeight};
 use tari_dan_storage::consensus_models::{BlockId, LastSentVote, QuorumDecision, ValidatorSignature};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoteMessage {
     pub epoch: Epoch,
     pub block_id: BlockId,
@@ -0,0 +1,170 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! # Byzantine consensus tests
+//!
+//! Byzantine validators run honest consensus but the test network rewrites the messages they send. Each test checks
+//! that honest validators never commit conflicting blocks (safety) and, where the behaviour allows it, that they
+//! eventually commit all transactions (liveness).
+
+use std::time::Duration;
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Decision, TransactionRecord};
+
+use crate::support::{
+    logging::setup_logger,
+    DoubleVoter,
+    EquivocatingLeader,
+    ForgedForeignPledges,
+    InvalidQcProposer,
+    LinkConditions,
+    Test,
+    TestBuilder,
+    VoteWithholder,
+};
+
+fn builder() -> TestBuilder {
+    Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .modify_consensus_constants(|config_mut| {
+            // Byzantine leaders stall their rounds, so we want leader failures to be detected quickly
+            config_mut.pacemaker_block_time = Duration::from_secs(2);
+        })
+}
+
+async fn send_transactions(test: &Test, num: usize) -> Vec<TransactionRecord> {
+    let mut transactions = Vec::with_capacity(num);
+    for _ in 0..num {
+        let (tx, _, _) = test.send_transaction_to_all(Decision::Commit, 1, 2, 1).await;
+        transactions.push(tx);
+    }
+    transactions
+}
+
+async fn run_until_honest_pool_empty(test: &mut Test, max_height: NodeHeight) {
+    loop {
+        let (_, _, _, committed_height) = test.on_block_committed().await;
+        test.assert_honest_validators_agree();
+
+        if test.is_honest_transaction_pool_empty() {
+            break;
+        }
+        if committed_height >= max_height {
+            panic!("Not all transactions committed after {} blocks", committed_height);
+        }
+    }
+}
+
+async fn assert_safe_and_live(mut test: Test, transactions: &[TransactionRecord]) {
+    run_until_honest_pool_empty(&mut test, NodeHeight(60)).await;
+
+    test.assert_honest_validators_agree();
+    for tx in transactions {
+        test.assert_honest_validators_committed(tx.id());
+    }
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn equivocating_leader() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        // 1 and 2 receive the honest proposal, 3 and 4 receive a conflicting proposal for the same height
+        .add_byzantine_node("1", EquivocatingLeader::new(vec!["3", "4"]))
+        .start()
+        .await;
+    let transactions = send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    assert_safe_and_live(test, &transactions).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn double_voting_validator() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .add_byzantine_node("2", DoubleVoter)
+        .start()
+        .await;
+    let transactions = send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    assert_safe_and_live(test, &transactions).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn vote_withholding_validator() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .add_byzantine_node("3", VoteWithholder)
+        .start()
+        .await;
+    let transactions = send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    assert_safe_and_live(test, &transactions).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn leader_sends_invalid_qc() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .add_byzantine_node("1", InvalidQcProposer)
+        .start()
+        .await;
+    let transactions = send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    assert_safe_and_live(test, &transactions).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn foreign_proposals_with_forged_pledges() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .add_committee(1, vec!["5", "6", "7", "8"])
+        .add_byzantine_node("5", ForgedForeignPledges)
+        .start()
+        .await;
+    send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    // A foreign proposal with forged pledges is marked as invalid and is not re-requested from another member, so
+    // transactions involving it may never finalise. We only require that honest validators keep making progress
+    // without committing conflicting blocks.
+    loop {
+        let (_, _, _, committed_height) = test.on_block_committed().await;
+        test.assert_honest_validators_agree();
+
+        if test.is_honest_transaction_pool_empty() || committed_height >= NodeHeight(20) {
+            break;
+        }
+    }
+
+    test.assert_honest_validators_agree();
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn delayed_and_reordered_links() {
+    setup_logger();
+    let mut test = builder()
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .with_link_conditions("1", "2", LinkConditions::delayed(Duration::from_millis(500)))
+        .with_link_conditions("2", "3", LinkConditions::reordered(Duration::from_millis(300)))
+        .with_link_conditions("3", "1", LinkConditions::reordered(Duration::from_millis(300)))
+        .with_link_conditions("4", "1", LinkConditions::reordered(Duration::from_millis(300)))
+        .start()
+        .await;
+    let transactions = send_transactions(&test, 10).await;
+    test.start_epoch(Epoch(1)).await;
+
+    assert_safe_and_live(test, &transactions).await;
+}
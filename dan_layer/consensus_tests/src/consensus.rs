@@ -143,6 +143,48 @@ async fn single_transaction_multi_vn() {
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn validator_restarts_from_persisted_state() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2", "3", "4"]).start().await;
+    let (tx1, _, _) = test.send_transaction_to_all(Decision::Commit, 1, 1, 1).await;
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(10) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    // Validator 4 must rebuild its view of the chain from its database to take part in the next transaction
+    test.restart_validator(&TestAddress::new("4")).await;
+
+    let (tx2, _, _) = test.send_transaction_to_all(Decision::Commit, 1, 1, 1).await;
+    loop {
+        test.on_block_committed().await;
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(30) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed(tx1.id());
+    test.assert_all_validators_committed(tx2.id());
+    test.assert_all_validators_have_decision(tx2.id(), Decision::Commit)
+        .await;
+
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn single_transaction_abort() {
     setup_logger();
@@ -478,6 +520,171 @@ async fn foreign_shard_group_decides_to_abort() {
     log::info!("total messages sent: {}", test.network().total_messages_sent());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn foreign_shard_group_stops_cooperating() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .modify_consensus_constants(|config_mut| {
+            config_mut.pacemaker_block_time = Duration::from_secs(1);
+            config_mut.foreign_proposal_request_timeout_blocks = 3;
+            config_mut.foreign_proposal_abort_timeout_blocks = 8;
+        })
+        .with_message_filter(Box::new(move |from: &TestAddress, to: &TestAddress, msg| {
+            // Committee 1 never sends its proposals to committee 0, even when they are requested
+            let is_foreign_proposal = matches!(
+                msg,
+                HotstuffMessage::ForeignProposal(_) | HotstuffMessage::ForeignProposalNotification(_)
+            );
+            let is_from_committee_1 = from == "4" || from == "5" || from == "6";
+            let is_to_committee_0 = to == "1" || to == "2" || to == "3";
+            !(is_foreign_proposal && is_from_committee_1 && is_to_committee_0)
+        }))
+        .add_committee(0, vec!["1", "2", "3"])
+        .add_committee(1, vec!["4", "5", "6"])
+        .start()
+        .await;
+
+    let (tx, _, _) = test.send_transaction_to_all(Decision::Commit, 1, 5, 1).await;
+
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        let leaf2 = test.get_validator(&TestAddress::new("4")).get_leaf_block();
+        if leaf1.height > NodeHeight(40) || leaf2.height > NodeHeight(40) {
+            panic!(
+                "Transaction was not aborted after {}/{} blocks",
+                leaf1.height, leaf2.height,
+            );
+        }
+    }
+
+    test.assert_all_validators_did_not_commit(tx.id());
+    for addr in ["1", "2", "3"] {
+        let decision = test
+            .get_validator(&TestAddress::new(addr))
+            .state_store
+            .with_read_tx(|tx_store| TransactionRecord::get(tx_store, tx.id()))
+            .unwrap()
+            .final_decision();
+        assert_eq!(decision, Some(Decision::Abort(AbortReason::ForeignProposalTimeout)));
+    }
+    // All validators release their locks. Committee 1 has accepted the transaction and learns of the ABORT from the
+    // LocalAccept proposal of committee 0.
+    for addr in ["1", "2", "3", "4", "5", "6"] {
+        let (decision, locks) = test
+            .get_validator(&TestAddress::new(addr))
+            .state_store
+            .with_read_tx(|tx_store| {
+                let decision = TransactionRecord::get(tx_store, tx.id())?.final_decision();
+                let locks = tx_store.substate_locks_get_locked_substates_for_transaction(tx.id())?;
+                Ok::<_, HotStuffError>((decision, locks))
+            })
+            .unwrap();
+        assert!(
+            decision.is_some_and(|d| d.is_abort()),
+            "Validator {addr} did not finalize ABORT: {decision:?}"
+        );
+        assert!(locks.is_empty(), "Validator {addr} did not release its locks");
+    }
+
+    log::info!("total messages sent: {}", test.network().total_messages_sent());
+    log::info!("total messages filtered: {}", test.network().total_messages_filtered());
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn foreign_output_shard_group_never_accepts() {
+    setup_logger();
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .modify_consensus_constants(|config_mut| {
+            config_mut.pacemaker_block_time = Duration::from_secs(1);
+            config_mut.foreign_proposal_request_timeout_blocks = 3;
+            config_mut.foreign_proposal_abort_timeout_blocks = 8;
+            config_mut.foreign_accept_abort_timeout_blocks = 12;
+        })
+        .with_message_filter(Box::new(move |from: &TestAddress, to: &TestAddress, msg| {
+            // The committees never exchange proposals, so committee 1 never pledges anything for the transaction
+            let is_foreign_proposal = matches!(
+                msg,
+                HotstuffMessage::ForeignProposal(_) | HotstuffMessage::ForeignProposalNotification(_)
+            );
+            let is_committee_0 = |addr: &TestAddress| addr == "1" || addr == "2" || addr == "3";
+            !(is_foreign_proposal && is_committee_0(from) != is_committee_0(to))
+        }))
+        .add_committee(0, vec!["1", "2", "3"])
+        .add_committee(1, vec!["4", "5", "6"])
+        .start()
+        .await;
+
+    // Committee 0 has the inputs and committee 1 only has outputs, so committee 0 accepts without hearing from
+    // committee 1
+    let inputs = test.create_substates_on_vns(TestVnDestination::Committee(0), 2);
+    let outputs = test.build_outputs_for_committee(1, 1);
+    let tx = build_transaction_from(
+        Transaction::builder()
+            .with_inputs(inputs.iter().cloned().map(|i| i.into()))
+            .build_and_seal(&PrivateKey::default()),
+        Decision::Commit,
+    );
+    test.create_execution_at_destination_for_transaction(
+        TestVnDestination::All,
+        &tx,
+        inputs
+            .into_iter()
+            .map(|input| (input.substate_id().clone(), SubstateLockType::Write))
+            .collect(),
+        outputs,
+    );
+    test.send_transaction_to_destination(TestVnDestination::Committee(0), tx.clone())
+        .await;
+
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        let is_committee_0_done = ["1", "2", "3"]
+            .into_iter()
+            .all(|addr| test.get_validator(&TestAddress::new(addr)).get_transaction_pool_count() == 0);
+        if is_committee_0_done {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf1.height > NodeHeight(50) {
+            panic!("Transaction was not aborted after {} blocks", leaf1.height);
+        }
+    }
+
+    test.assert_all_validators_did_not_commit(tx.id());
+    for addr in ["1", "2", "3"] {
+        let (decision, locks) = test
+            .get_validator(&TestAddress::new(addr))
+            .state_store
+            .with_read_tx(|tx_store| {
+                let decision = TransactionRecord::get(tx_store, tx.id())?.final_decision();
+                let locks = tx_store.substate_locks_get_locked_substates_for_transaction(tx.id())?;
+                Ok::<_, HotStuffError>((decision, locks))
+            })
+            .unwrap();
+        assert_eq!(decision, Some(Decision::Abort(AbortReason::ForeignProposalTimeout)));
+        assert!(locks.is_empty(), "Validator {addr} did not release its locks");
+    }
+
+    log::info!("total messages sent: {}", test.network().total_messages_sent());
+    log::info!("total messages filtered: {}", test.network().total_messages_filtered());
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn multishard_local_inputs_foreign_outputs() {
     setup_logger();
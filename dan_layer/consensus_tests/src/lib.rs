@@ -1,12 +1,18 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 #[cfg(test)]
+mod byzantine;
+#[cfg(test)]
 mod consensus;
 #[cfg(test)]
 mod dummy_blocks;
 #[cfg(test)]
 mod eviction_proof;
 #[cfg(test)]
+mod quorum_certificate;
+#[cfg(test)]
+mod simulation;
+#[cfg(test)]
 mod substate_store;
 #[cfg(test)]
 mod support;
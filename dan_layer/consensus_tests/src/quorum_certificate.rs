@@ -0,0 +1,110 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use tari_common_types::types::FixedHash;
+use tari_consensus::{
+    block_validations::{check_quorum_certificate, get_committee_for_qc},
+    traits::VoteSignatureService,
+};
+use tari_dan_common_types::{committee::Committee, Epoch, NodeHeight, ShardGroup};
+use tari_dan_storage::consensus_models::{BlockId, QuorumCertificate, QuorumDecision, SignerBitmap};
+use tari_epoch_manager::EpochManagerReader;
+use tokio::sync::broadcast;
+
+use crate::support::{
+    helpers,
+    TestAddress,
+    TestConsensusSpec,
+    TestEpochManager,
+    TestVoteSignatureService,
+    TEST_NUM_PRESHARDS,
+};
+
+fn build_committee(addresses: &[&str]) -> Committee<TestAddress> {
+    Committee::new(
+        addresses
+            .iter()
+            .map(|addr| {
+                let addr = TestAddress::new(*addr);
+                let (_, public_key) = helpers::derive_keypair_from_address(&addr);
+                (addr, public_key)
+            })
+            .collect(),
+    )
+}
+
+/// Creates a QC for a block in `epoch` that is signed by `signers`, with the signer bitmap built over `committee` in
+/// the same way as the vote collector.
+fn build_signed_qc(
+    epoch: Epoch,
+    shard_group: ShardGroup,
+    committee: &Committee<TestAddress>,
+    signers: &[&str],
+) -> QuorumCertificate {
+    let create_qc = |bitmap, signatures| {
+        QuorumCertificate::new(
+            FixedHash::from([1u8; 32]),
+            BlockId::zero(),
+            NodeHeight(1),
+            epoch,
+            shard_group,
+            bitmap,
+            signatures,
+            vec![],
+            QuorumDecision::Accept,
+        )
+    };
+    let block_id = *create_qc(SignerBitmap::new(committee.len()), vec![]).block_id();
+
+    let mut bitmap = SignerBitmap::new(committee.len());
+    let mut signatures = vec![];
+    for (i, (addr, _)) in committee.members.iter().enumerate() {
+        if signers.contains(&addr.as_str()) {
+            bitmap.set(i);
+            signatures.push(
+                TestVoteSignatureService::new(addr.clone())
+                    .sign_vote(&block_id, &QuorumDecision::Accept)
+                    .signature,
+            );
+        }
+    }
+    create_qc(bitmap, signatures)
+}
+
+#[tokio::test]
+async fn it_validates_a_qc_against_the_committee_of_its_own_epoch() {
+    let epoch_manager = TestEpochManager::new(broadcast::channel(1).0);
+    let shard_group = ShardGroup::all_shards(TEST_NUM_PRESHARDS);
+    let epoch1_committee = build_committee(&["1", "2", "3", "4"]);
+    epoch_manager
+        .add_committees(HashMap::from([(shard_group, epoch1_committee.clone())]))
+        .await;
+    // Validator 1 leaves and validator 5 joins at the epoch change. The committee size stays the same so only the
+    // signer indexes shift.
+    let epoch2_committee = build_committee(&["2", "3", "4", "5"]);
+    epoch_manager
+        .set_committee_for_epoch(Epoch(2), shard_group, epoch2_committee.clone())
+        .await;
+
+    let qc = build_signed_qc(Epoch(1), shard_group, &epoch1_committee, &["1", "2", "3"]);
+    let signing_service = TestVoteSignatureService::new(TestAddress::new("2"));
+
+    let committee = get_committee_for_qc(&epoch_manager, &qc, TEST_NUM_PRESHARDS)
+        .await
+        .unwrap();
+    assert_eq!(committee, epoch1_committee);
+    check_quorum_certificate::<TestConsensusSpec>(&qc, &committee, &signing_service).unwrap();
+
+    // Resolving the signers against the committee of the current epoch attributes the signatures to the wrong members
+    let current_committee = epoch_manager
+        .get_committee_for_substate(
+            Epoch(2),
+            *shard_group.to_substate_address_range(TEST_NUM_PRESHARDS).start(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(current_committee, epoch2_committee);
+    check_quorum_certificate::<TestConsensusSpec>(&qc, &current_committee, &signing_service).unwrap_err();
+}
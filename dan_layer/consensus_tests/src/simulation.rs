@@ -0,0 +1,24 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! # Consensus simulation
+//!
+//! Runs randomized traces against the test network. A failing run prints its seed and a minimized trace. To
+//! reproduce it, run with `CONSENSUS_SIM_SEED=<seed>`. Set `CONSENSUS_SIM_RUNS=<n>` to try more random seeds.
+
+use crate::support::simulation::{check_seed, generate_events, seeds_from_env, SimulationConfig};
+
+#[test]
+fn randomized_consensus_simulation() {
+    let config = SimulationConfig::default();
+    for seed in seeds_from_env(1) {
+        check_seed(&config, seed);
+    }
+}
+
+#[test]
+fn traces_are_reproducible_from_the_seed() {
+    let config = SimulationConfig::default();
+    assert_eq!(generate_events(&config, 1234), generate_events(&config, 1234));
+    assert_ne!(generate_events(&config, 1234), generate_events(&config, 4321));
+}
@@ -0,0 +1,218 @@
+//    Copyright 2024 The Tari Project
+//    SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashSet;
+
+use tari_consensus::{
+    messages::{ForeignProposalMessage, HotstuffMessage, ProposalMessage, VoteMessage},
+    traits::{ValidatorSignatureService, VoteSignatureService},
+};
+use tari_dan_storage::consensus_models::{Block, BlockHeader, BlockPledge, QuorumCertificate, QuorumDecision};
+
+use super::{signing_service::TestVoteSignatureService, TestAddress};
+
+/// Adversarial behaviour for a validator. The validator itself runs honest consensus, the behaviour rewrites the
+/// messages it sends as they pass through the test network.
+pub trait ByzantineBehaviour: Send + Sync + 'static {
+    /// Returns the messages that are delivered to `to` in place of `msg`. An empty vec drops the message.
+    fn on_outbound(&self, context: &ByzantineContext, to: &TestAddress, msg: HotstuffMessage) -> Vec<HotstuffMessage>;
+}
+
+/// The keys of the byzantine validator, used to produce validly signed but malicious messages.
+pub struct ByzantineContext {
+    address: TestAddress,
+    signing_service: TestVoteSignatureService,
+}
+
+impl ByzantineContext {
+    pub fn new(address: TestAddress) -> Self {
+        Self {
+            signing_service: TestVoteSignatureService::new(address.clone()),
+            address,
+        }
+    }
+
+    pub fn address(&self) -> &TestAddress {
+        &self.address
+    }
+
+    /// Rebuilds and re-signs the block with the given justify QC and timestamp. The result is a different block at
+    /// the same height that passes signature and leader checks.
+    fn resign_block(&self, block: &Block, justify: QuorumCertificate, timestamp: u64) -> Block {
+        let mut header = BlockHeader::create(
+            block.network(),
+            *block.parent(),
+            *justify.id(),
+            block.height(),
+            block.epoch(),
+            block.shard_group(),
+            block.proposed_by().clone(),
+            *block.state_merkle_root(),
+            block.commands(),
+            block.total_leader_fee(),
+            block.foreign_indexes().clone(),
+            None,
+            timestamp,
+            block.base_layer_block_height(),
+            *block.base_layer_block_hash(),
+            block.extra_data().clone(),
+        )
+        .expect("command merkle root was already computed for this block");
+        header.set_signature(self.signing_service.sign(header.id()));
+        Block::new(header, justify, block.commands().clone())
+    }
+}
+
+pub struct ByzantineNode {
+    context: ByzantineContext,
+    behaviour: Box<dyn ByzantineBehaviour>,
+}
+
+impl ByzantineNode {
+    pub fn new(address: TestAddress, behaviour: Box<dyn ByzantineBehaviour>) -> Self {
+        Self {
+            context: ByzantineContext::new(address),
+            behaviour,
+        }
+    }
+
+    pub fn on_outbound(&self, to: &TestAddress, msg: HotstuffMessage) -> Vec<HotstuffMessage> {
+        log::debug!("😈 {} -> {}: {}", self.context.address(), to, msg);
+        self.behaviour.on_outbound(&self.context, to, msg)
+    }
+}
+
+/// A leader that sends a conflicting proposal for the same height to some of the committee.
+pub struct EquivocatingLeader {
+    conflicting_recipients: HashSet<TestAddress>,
+}
+
+impl EquivocatingLeader {
+    pub fn new<T: Into<TestAddress>>(conflicting_recipients: Vec<T>) -> Self {
+        Self {
+            conflicting_recipients: conflicting_recipients.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl ByzantineBehaviour for EquivocatingLeader {
+    fn on_outbound(&self, context: &ByzantineContext, to: &TestAddress, msg: HotstuffMessage) -> Vec<HotstuffMessage> {
+        match msg {
+            HotstuffMessage::Proposal(proposal)
+                if !proposal.block.is_dummy() && self.conflicting_recipients.contains(to) =>
+            {
+                let block = &proposal.block;
+                let conflicting =
+                    context.resign_block(block, block.justify().clone(), block.timestamp().saturating_add(1));
+                vec![HotstuffMessage::Proposal(ProposalMessage {
+                    block: conflicting,
+                    foreign_proposals: proposal.foreign_proposals,
+                })]
+            },
+            msg => vec![msg],
+        }
+    }
+}
+
+/// A voter that sends a second, validly signed vote with the opposite decision for every vote it casts.
+pub struct DoubleVoter;
+
+impl ByzantineBehaviour for DoubleVoter {
+    fn on_outbound(&self, context: &ByzantineContext, _to: &TestAddress, msg: HotstuffMessage) -> Vec<HotstuffMessage> {
+        match msg {
+            HotstuffMessage::Vote(vote) => {
+                let decision = match vote.decision {
+                    QuorumDecision::Accept => QuorumDecision::Reject,
+                    QuorumDecision::Reject => QuorumDecision::Accept,
+                };
+                let conflicting = VoteMessage {
+                    epoch: vote.epoch,
+                    block_id: vote.block_id,
+                    unverified_block_height: vote.unverified_block_height,
+                    decision,
+                    signature: context.signing_service.sign_vote(&vote.block_id, &decision),
+                };
+                vec![HotstuffMessage::Vote(vote), HotstuffMessage::Vote(conflicting)]
+            },
+            msg => vec![msg],
+        }
+    }
+}
+
+/// A voter that never sends its votes.
+pub struct VoteWithholder;
+
+impl ByzantineBehaviour for VoteWithholder {
+    fn on_outbound(
+        &self,
+        _context: &ByzantineContext,
+        _to: &TestAddress,
+        msg: HotstuffMessage,
+    ) -> Vec<HotstuffMessage> {
+        match msg {
+            HotstuffMessage::Vote(_) => vec![],
+            msg => vec![msg],
+        }
+    }
+}
+
+/// A leader that justifies its proposals with a QC whose signatures were not produced by the signers.
+pub struct InvalidQcProposer;
+
+impl ByzantineBehaviour for InvalidQcProposer {
+    fn on_outbound(&self, context: &ByzantineContext, _to: &TestAddress, msg: HotstuffMessage) -> Vec<HotstuffMessage> {
+        match msg {
+            // The zero block QC has no signatures to forge
+            HotstuffMessage::Proposal(proposal)
+                if !proposal.block.is_dummy() && !proposal.block.justify().justifies_zero_block() =>
+            {
+                let block = &proposal.block;
+                let qc = block.justify();
+                let forged_qc = QuorumCertificate::new(
+                    *qc.header_hash(),
+                    *qc.parent_id(),
+                    qc.block_height(),
+                    qc.epoch(),
+                    qc.shard_group(),
+                    qc.signers().clone(),
+                    qc.signatures()
+                        .iter()
+                        .map(|_| context.signing_service.sign(qc.block_id()))
+                        .collect(),
+                    qc.leaf_hashes().to_vec(),
+                    qc.decision(),
+                );
+                let forged = context.resign_block(block, forged_qc, block.timestamp());
+                vec![HotstuffMessage::Proposal(ProposalMessage {
+                    block: forged,
+                    foreign_proposals: proposal.foreign_proposals,
+                })]
+            },
+            msg => vec![msg],
+        }
+    }
+}
+
+/// A foreign committee member that replies to foreign proposal requests with a block pledge that pledges none of the
+/// substates that the block locked.
+pub struct ForgedForeignPledges;
+
+impl ByzantineBehaviour for ForgedForeignPledges {
+    fn on_outbound(
+        &self,
+        _context: &ByzantineContext,
+        _to: &TestAddress,
+        msg: HotstuffMessage,
+    ) -> Vec<HotstuffMessage> {
+        match msg {
+            HotstuffMessage::ForeignProposal(proposal) => {
+                vec![HotstuffMessage::ForeignProposal(ForeignProposalMessage {
+                    block: proposal.block,
+                    justify_qc: proposal.justify_qc,
+                    block_pledge: BlockPledge::new(),
+                })]
+            },
+            msg => vec![msg],
+        }
+    }
+}
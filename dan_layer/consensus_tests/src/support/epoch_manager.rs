@@ -108,6 +108,20 @@ impl TestEpochManager {
         }
     }
 
+    /// Sets the committee of a shard group for the given epoch only. Other epochs keep the committees added with
+    /// [TestEpochManager::add_committees].
+    pub async fn set_committee_for_epoch(
+        &self,
+        epoch: Epoch,
+        shard_group: ShardGroup,
+        committee: Committee<TestAddress>,
+    ) {
+        self.state_lock()
+            .await
+            .epoch_committees
+            .insert((epoch, shard_group), committee);
+    }
+
     pub async fn all_validators(&self) -> Vec<(ValidatorNode<TestAddress>, ShardGroup)> {
         self.state_lock().await.validator_nodes.values().cloned().collect()
     }
@@ -135,11 +149,14 @@ impl EpochManagerReader for TestEpochManager {
 
     async fn get_committee_for_substate(
         &self,
-        _epoch: Epoch,
+        epoch: Epoch,
         substate_address: SubstateAddress,
     ) -> Result<Committee<Self::Addr>, EpochManagerError> {
         let state = self.state_lock().await;
         let shard_group = substate_address.to_shard_group(TEST_NUM_PRESHARDS, state.committees.len() as u32);
+        if let Some(committee) = state.epoch_committees.get(&(epoch, shard_group)) {
+            return Ok(committee.clone());
+        }
         Ok(state.committees[&shard_group].clone())
     }
 
@@ -346,6 +363,7 @@ pub struct TestEpochManagerState {
     #[allow(clippy::type_complexity)]
     pub validator_nodes: HashMap<TestAddress, (ValidatorNode<TestAddress>, ShardGroup)>,
     pub committees: HashMap<ShardGroup, Committee<TestAddress>>,
+    pub epoch_committees: HashMap<(Epoch, ShardGroup), Committee<TestAddress>>,
     pub address_shard: HashMap<TestAddress, ShardGroup>,
     pub eviction_proofs: Vec<tari_sidechain::EvictionProof>,
 }
@@ -359,6 +377,7 @@ impl Default for TestEpochManagerState {
             validator_nodes: HashMap::new(),
             is_epoch_active: false,
             committees: HashMap::new(),
+            epoch_committees: HashMap::new(),
             address_shard: HashMap::new(),
             eviction_proofs: Vec::new(),
         }
@@ -10,6 +10,7 @@ use std::{
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
 use itertools::Itertools;
 use log::info;
+use rand::{rngs::StdRng, SeedableRng};
 use tari_common::configuration::Network;
 use tari_consensus::{
     consensus_constants::ConsensusConstants,
@@ -17,6 +18,7 @@ use tari_consensus::{
 };
 use tari_dan_common_types::{
     committee::Committee,
+    optional::Optional,
     shard::Shard,
     Epoch,
     NodeHeight,
@@ -33,7 +35,7 @@ use tari_dan_storage::{
 };
 use tari_engine_types::substate::SubstateId;
 use tari_epoch_manager::EpochManagerReader;
-use tari_shutdown::{Shutdown, ShutdownSignal};
+use tari_shutdown::Shutdown;
 use tari_transaction::TransactionId;
 use tokio::{sync::broadcast, task, time::sleep};
 
@@ -42,6 +44,9 @@ use super::{
     build_transaction,
     helpers,
     random_substates_ids_for_committee_generator,
+    ByzantineBehaviour,
+    ByzantineNode,
+    LinkConditions,
     MessageFilter,
     TEST_NUM_PRESHARDS,
 };
@@ -59,9 +64,11 @@ use crate::support::{
 pub struct Test {
     validators: HashMap<TestAddress, Validator>,
     network: TestNetwork,
-    _leader_strategy: RoundRobinLeaderStrategy,
+    leader_strategy: RoundRobinLeaderStrategy,
     epoch_manager: TestEpochManager,
+    config: HotstuffConfig,
     num_committees: u32,
+    byzantine_nodes: HashSet<TestAddress>,
     shutdown: Shutdown,
     timeout: Option<Duration>,
 }
@@ -215,6 +222,14 @@ impl Test {
         &self.validators
     }
 
+    pub fn is_byzantine(&self, addr: &TestAddress) -> bool {
+        self.byzantine_nodes.contains(addr)
+    }
+
+    pub fn honest_validators_iter(&self) -> impl Iterator<Item = &Validator> + '_ {
+        self.validators.values().filter(|v| !self.is_byzantine(&v.address))
+    }
+
     pub async fn on_hotstuff_event(&mut self) -> (TestAddress, HotstuffEvent) {
         if self.network.task_handle().is_finished() {
             panic!("Network task exited while waiting for Hotstuff event");
@@ -248,6 +263,10 @@ impl Test {
                 log::info!("[{}] Ignoring event for offline node: {:?}", address, event);
                 continue;
             }
+            if self.is_byzantine(&address) {
+                log::info!("[{}] Ignoring event for byzantine node: {:?}", address, event);
+                continue;
+            }
 
             match event {
                 HotstuffEvent::BlockCommitted {
@@ -323,6 +342,33 @@ impl Test {
         }
     }
 
+    /// Stops the validator and starts it again from its persisted state. Messages and transactions sent to the
+    /// validator while it restarts are lost.
+    pub async fn restart_validator(&mut self, address: &TestAddress) {
+        info!("🔄 Restarting {address}");
+        let mut validator = self
+            .validators
+            .remove(address)
+            .unwrap_or_else(|| panic!("No validator with address {}", address));
+        self.network.disconnect(address).await;
+        validator.shutdown.trigger();
+        validator.handle.await.unwrap();
+
+        let (sk, _) = helpers::derive_keypair_from_address(address);
+        let (channels, validator) = Validator::builder()
+            .with_config(self.config.clone())
+            .with_address_and_secret_key(address.clone(), sk)
+            .with_shard(validator._shard_address)
+            .with_shard_group(validator.shard_group)
+            .with_epoch_manager(validator.epoch_manager.clone())
+            .with_leader_strategy(self.leader_strategy)
+            .with_num_committees(validator.num_committees)
+            .with_transaction_executions(validator.transaction_executions.clone())
+            .spawn_with_state_store(validator.state_store.clone());
+        self.network.connect(channels).await;
+        self.validators.insert(address.clone(), validator);
+    }
+
     #[allow(dead_code)]
     pub fn get_validator_mut(&mut self, addr: &TestAddress) -> &mut Validator {
         self.validators.get_mut(addr).unwrap()
@@ -344,11 +390,21 @@ impl Test {
         })
     }
 
+    pub fn is_honest_transaction_pool_empty(&self) -> bool {
+        self.honest_validators_iter().all(|v| {
+            let c = v.get_transaction_pool_count();
+            if c > 0 {
+                log::info!("🐞 {} has {} transactions in pool", v.address, c);
+            }
+            c == 0
+        })
+    }
+
     pub async fn wait_for_n_to_be_finalized(&self, n: usize) {
         self.wait_all_for_predicate("waiting for n to be finalized", |vn| {
             let transactions = vn
                 .state_store
-                .with_read_tx(|tx| tx.transactions_get_paginated(10000, 0, None))
+                .with_read_tx(|tx| tx.transactions_get_paginated(10000, None, None, None))
                 .unwrap();
             log::info!("{} has {} transactions in pool", vn.address, transactions.len());
             transactions.iter().filter(|tx| tx.is_finalized()).count() >= n
@@ -493,6 +549,62 @@ impl Test {
         });
     }
 
+    pub fn assert_honest_validators_committed(&self, tx_id: &TransactionId) {
+        self.honest_validators_iter().for_each(|v| {
+            assert!(
+                v.has_committed_substates(tx_id),
+                "Honest validator {} did not commit transaction {}",
+                v.address,
+                tx_id
+            );
+        });
+    }
+
+    /// Asserts that no two honest validators in the same committee committed different blocks at the same height.
+    pub fn assert_honest_validators_agree(&self) {
+        let mut committed = HashMap::<_, (&TestAddress, BlockId)>::new();
+        for v in self.honest_validators_iter() {
+            for (height, block_id) in v.get_committed_blocks() {
+                match committed.entry((v.shard_group, height)) {
+                    hash_map::Entry::Occupied(entry) => {
+                        let (other, other_block_id) = entry.get();
+                        assert_eq!(
+                            *other_block_id, block_id,
+                            "Safety violation: honest validator {} committed block {} at height {} but honest \
+                             validator {} committed block {}",
+                            v.address, block_id, height, other, other_block_id
+                        );
+                    },
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert((&v.address, block_id));
+                    },
+                }
+            }
+        }
+    }
+
+    /// Asserts that all validators that have finalized the transaction reached the same decision
+    pub fn assert_validators_agree_on_decision(&self, tx_id: &TransactionId) {
+        let mut decisions = self.validators.values().filter_map(|v| {
+            let decision = v
+                .state_store
+                .with_read_tx(|tx| TransactionRecord::get(tx, tx_id).optional())
+                .unwrap()
+                .and_then(|rec| rec.final_decision())?;
+            Some((&v.address, decision))
+        });
+        let Some((first_addr, first_decision)) = decisions.next() else {
+            return;
+        };
+        for (addr, decision) in decisions {
+            assert_eq!(
+                first_decision, decision,
+                "Validator {} decided {} but validator {} decided {} for transaction {}",
+                first_addr, first_decision, addr, decision, tx_id
+            );
+        }
+    }
+
     pub fn assert_all_validators_did_not_commit(&self, tx_id: &TransactionId) {
         self.validators.values().for_each(|v| {
             assert!(
@@ -506,14 +618,16 @@ impl Test {
 
     pub async fn assert_clean_shutdown(&mut self) {
         self.shutdown.trigger();
-        for (_, v) in self.validators.drain() {
+        for (_, mut v) in self.validators.drain() {
+            v.shutdown.trigger();
             v.handle.await.unwrap();
         }
     }
 
     pub async fn assert_clean_shutdown_except(&mut self, except: &[TestAddress]) {
         self.shutdown.trigger();
-        for (_, v) in self.validators.drain() {
+        for (_, mut v) in self.validators.drain() {
+            v.shutdown.trigger();
             if !except.contains(&v.address) {
                 v.handle.await.unwrap();
             }
@@ -528,6 +642,9 @@ pub struct TestBuilder {
     debug_sql_file: Option<String>,
     message_filter: Option<MessageFilter>,
     failure_nodes: Vec<TestAddress>,
+    byzantine_nodes: HashMap<TestAddress, Box<dyn ByzantineBehaviour>>,
+    link_conditions: HashMap<(TestAddress, TestAddress), LinkConditions>,
+    seed: Option<u64>,
     config: HotstuffConfig,
 }
 
@@ -540,6 +657,9 @@ impl TestBuilder {
             debug_sql_file: None,
             message_filter: None,
             failure_nodes: Vec::new(),
+            byzantine_nodes: HashMap::new(),
+            link_conditions: HashMap::new(),
+            seed: None,
             config: HotstuffConfig {
                 network: Network::LocalNet,
                 sidechain_id: None,
@@ -557,6 +677,9 @@ impl TestBuilder {
                     fee_exhaust_divisor: 20,
                     epochs_per_era: Epoch(10),
                     template_binary_max_size_bytes: 1000 * 1000 * 5,
+                    foreign_proposal_request_timeout_blocks: 50,
+                    foreign_proposal_abort_timeout_blocks: 100,
+                    foreign_accept_abort_timeout_blocks: 500,
                 },
             },
         }
@@ -585,14 +708,14 @@ impl TestBuilder {
         self
     }
 
-    pub fn add_committee(mut self, committee_num: u32, addresses: Vec<&'static str>) -> Self {
+    pub fn add_committee<T: Into<TestAddress>>(mut self, committee_num: u32, addresses: Vec<T>) -> Self {
         let entry = self
             .committees
             .entry(committee_num)
             .or_insert_with(|| Committee::new(vec![]));
 
         for addr in addresses {
-            let addr = TestAddress::new(addr);
+            let addr: TestAddress = addr.into();
             let (_, pk) = helpers::derive_keypair_from_address(&addr);
             entry.members.push((addr, pk));
         }
@@ -604,6 +727,24 @@ impl TestBuilder {
         self
     }
 
+    /// Runs the node with honest consensus but rewrites the messages it sends according to the behaviour
+    pub fn add_byzantine_node<T: Into<TestAddress>, B: ByzantineBehaviour>(mut self, node: T, behaviour: B) -> Self {
+        self.byzantine_nodes.insert(node.into(), Box::new(behaviour));
+        self
+    }
+
+    /// Applies the link conditions to messages sent from `from` to `to`
+    pub fn with_link_conditions<T: Into<TestAddress>>(mut self, from: T, to: T, conditions: LinkConditions) -> Self {
+        self.link_conditions.insert((from.into(), to.into()), conditions);
+        self
+    }
+
+    /// Seeds the network so that link delays are sampled reproducibly
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     pub fn with_message_filter(mut self, message_filter: MessageFilter) -> Self {
         self.message_filter = Some(message_filter);
         self
@@ -618,9 +759,8 @@ impl TestBuilder {
         leader_strategy: &RoundRobinLeaderStrategy,
         epoch_manager: &TestEpochManager,
         sql_address: String,
-        config: HotstuffConfig,
+        config: &HotstuffConfig,
         failure_nodes: &[TestAddress],
-        shutdown_signal: ShutdownSignal,
     ) -> (Vec<ValidatorChannels>, HashMap<TestAddress, Validator>) {
         let num_committees = epoch_manager.get_num_committees(Epoch(0)).await.unwrap();
         epoch_manager
@@ -648,7 +788,7 @@ impl TestBuilder {
                     .with_epoch_manager(epoch_manager.clone_for(vn.address.clone(), pk, vn.shard_key))
                     .with_leader_strategy(*leader_strategy)
                     .with_num_committees(num_committees)
-                    .spawn(shutdown_signal.clone());
+                    .spawn();
                 (channels, (vn.address, validator))
             })
             .unzip()
@@ -680,20 +820,34 @@ impl TestBuilder {
             &leader_strategy,
             &epoch_manager,
             self.sql_address,
-            self.config,
+            &self.config,
             &self.failure_nodes,
-            shutdown.to_signal(),
         )
         .await;
-        let network = spawn_network(channels, shutdown.to_signal(), self.message_filter);
+        let byzantine_addresses = self.byzantine_nodes.keys().cloned().collect();
+        let byzantine_nodes = self
+            .byzantine_nodes
+            .into_iter()
+            .map(|(addr, behaviour)| (addr.clone(), ByzantineNode::new(addr, behaviour)))
+            .collect();
+        let network = spawn_network(
+            channels,
+            shutdown.to_signal(),
+            self.message_filter,
+            byzantine_nodes,
+            self.link_conditions,
+            self.seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
+        );
 
         Test {
             validators,
             network,
             num_committees,
+            byzantine_nodes: byzantine_addresses,
 
-            _leader_strategy: leader_strategy,
+            leader_strategy,
             epoch_manager,
+            config: self.config,
             shutdown,
             timeout: self.timeout,
         }
@@ -7,6 +7,7 @@
 pub const TEST_NUM_PRESHARDS: NumPreshards = NumPreshards::P64;
 
 mod address;
+mod byzantine;
 mod epoch_manager;
 mod executions_store;
 mod fixtures;
@@ -17,6 +18,7 @@ pub mod logging;
 mod messaging_impls;
 mod network;
 mod signing_service;
+pub mod simulation;
 mod spec;
 mod sync;
 mod transaction;
@@ -24,11 +26,14 @@ mod transaction_executor;
 mod validator;
 
 pub use address::*;
+pub use byzantine::*;
+pub use epoch_manager::TestEpochManager;
 pub use executions_store::ExecuteSpec;
 pub use fixtures::*;
 pub use harness::*;
 pub use leader_strategy::*;
 pub use network::*;
+pub use signing_service::TestVoteSignatureService;
 pub use spec::*;
 use tari_dan_common_types::NumPreshards;
 pub use transaction::*;
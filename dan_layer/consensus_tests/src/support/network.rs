@@ -4,10 +4,12 @@
 use std::{
     collections::HashMap,
     sync::{atomic::AtomicUsize, Arc},
+    time::Duration,
 };
 
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
 use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
 use tari_consensus::messages::HotstuffMessage;
 use tari_dan_common_types::ShardGroup;
 use tari_dan_storage::consensus_models::TransactionRecord;
@@ -17,14 +19,17 @@ use tari_transaction::{Transaction, TransactionId};
 use tokio::{
     sync::{
         mpsc::{self},
+        oneshot,
         watch,
         RwLock,
     },
     task,
+    time::sleep,
 };
 
 use crate::support::{
     address::TestAddress,
+    byzantine::ByzantineNode,
     committee_number_to_shard_group,
     Validator,
     ValidatorChannels,
@@ -33,10 +38,45 @@ use crate::support::{
 
 pub type MessageFilter = Box<dyn Fn(&TestAddress, &TestAddress, &HotstuffMessage) -> bool + Sync + Send + 'static>;
 
+/// Delivery conditions for messages sent from one validator to another.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkConditions {
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl LinkConditions {
+    /// Delays every message on the link by the same duration
+    pub fn delayed(delay: Duration) -> Self {
+        Self {
+            min_delay: delay,
+            max_delay: delay,
+        }
+    }
+
+    /// Delays each message on the link by a random duration up to `max_delay`, so messages may arrive out of order
+    pub fn reordered(max_delay: Duration) -> Self {
+        Self {
+            min_delay: Duration::ZERO,
+            max_delay,
+        }
+    }
+
+    fn sample_delay<R: Rng>(&self, rng: &mut R) -> Duration {
+        if self.min_delay >= self.max_delay {
+            return self.min_delay;
+        }
+        rng.gen_range(self.min_delay..=self.max_delay)
+    }
+}
+
 pub fn spawn_network(
     channels: Vec<ValidatorChannels>,
     shutdown_signal: ShutdownSignal,
     message_filter: Option<MessageFilter>,
+    byzantine_nodes: HashMap<TestAddress, ByzantineNode>,
+    link_conditions: HashMap<(TestAddress, TestAddress), LinkConditions>,
+    rng: StdRng,
 ) -> TestNetwork {
     let tx_new_transactions = channels
         .iter()
@@ -61,6 +101,7 @@ pub fn spawn_network(
         .map(|c| ((c.address.clone(), c.rx_broadcast), (c.address.clone(), c.rx_leader)))
         .multiunzip();
     let (tx_new_transaction, rx_new_transaction) = mpsc::channel(100);
+    let (tx_command, rx_command) = mpsc::channel(10);
     let (tx_network_status, network_status) = watch::channel(NetworkStatus::Paused);
     let (tx_on_message, rx_on_message) = watch::channel(None);
     let num_sent_messages = Arc::new(AtomicUsize::new(0));
@@ -71,7 +112,8 @@ pub fn spawn_network(
     let network_task_handle = TestNetworkWorker {
         network_status,
         rx_new_transaction: Some(rx_new_transaction),
-        tx_new_transactions,
+        rx_command: Some(rx_command),
+        tx_new_transactions: Arc::new(RwLock::new(tx_new_transactions)),
         tx_hs_message,
         rx_broadcast: Some(rx_broadcast),
         rx_leader: Some(rx_leader),
@@ -82,12 +124,16 @@ pub fn spawn_network(
         offline_destinations: offline_destinations.clone(),
         shutdown_signal,
         message_filter,
+        byzantine_nodes,
+        link_conditions,
+        rng,
     }
     .spawn();
 
     TestNetwork {
         network_task_handle,
         tx_new_transaction,
+        tx_command,
         network_status: tx_network_status,
         offline_destinations,
         num_sent_messages,
@@ -108,9 +154,18 @@ impl NetworkStatus {
     }
 }
 
+enum NetworkCommand {
+    Connect(ValidatorChannels),
+    Disconnect {
+        address: TestAddress,
+        reply: oneshot::Sender<()>,
+    },
+}
+
 pub struct TestNetwork {
     network_task_handle: task::JoinHandle<()>,
     tx_new_transaction: mpsc::Sender<(TestVnDestination, TransactionRecord)>,
+    tx_command: mpsc::Sender<NetworkCommand>,
     network_status: watch::Sender<NetworkStatus>,
     offline_destinations: Arc<RwLock<Vec<TestVnDestination>>>,
     num_sent_messages: Arc<AtomicUsize>,
@@ -135,6 +190,33 @@ impl TestNetwork {
         self
     }
 
+    pub async fn go_online(&self, address: &TestAddress) -> &Self {
+        self.offline_destinations
+            .write()
+            .await
+            .retain(|d| !matches!(d, TestVnDestination::Address(a) if a == address));
+        self
+    }
+
+    /// Connects a (re)started validator to the network
+    pub async fn connect(&self, channels: ValidatorChannels) {
+        self.tx_command.send(NetworkCommand::Connect(channels)).await.unwrap();
+    }
+
+    /// Removes the validator from the network. Once this returns, no further messages or transactions are delivered
+    /// to the validator.
+    pub async fn disconnect(&self, address: &TestAddress) {
+        let (reply, rx_reply) = oneshot::channel();
+        self.tx_command
+            .send(NetworkCommand::Disconnect {
+                address: address.clone(),
+                reply,
+            })
+            .await
+            .unwrap();
+        rx_reply.await.unwrap();
+    }
+
     pub async fn is_offline(&self, address: &TestAddress, num_committees: u32) -> bool {
         let read = self.offline_destinations.read().await;
         read.iter()
@@ -195,15 +277,20 @@ impl TestVnDestination {
 
 pub struct TestNetworkWorker {
     rx_new_transaction: Option<mpsc::Receiver<(TestVnDestination, TransactionRecord)>>,
+    rx_command: Option<mpsc::Receiver<NetworkCommand>>,
     #[allow(clippy::type_complexity)]
-    tx_new_transactions: HashMap<
-        TestAddress,
-        (
-            ShardGroup,
-            u32, // num_committees
-            mpsc::Sender<(Transaction, usize)>,
-            SqliteStateStore<TestAddress>,
-        ),
+    tx_new_transactions: Arc<
+        RwLock<
+            HashMap<
+                TestAddress,
+                (
+                    ShardGroup,
+                    u32, // num_committees
+                    mpsc::Sender<(Transaction, usize)>,
+                    SqliteStateStore<TestAddress>,
+                ),
+            >,
+        >,
     >,
     tx_hs_message: HashMap<TestAddress, mpsc::Sender<(TestAddress, HotstuffMessage)>>,
     #[allow(clippy::type_complexity)]
@@ -219,6 +306,10 @@ pub struct TestNetworkWorker {
     offline_destinations: Arc<RwLock<Vec<TestVnDestination>>>,
     shutdown_signal: ShutdownSignal,
     message_filter: Option<MessageFilter>,
+    byzantine_nodes: HashMap<TestAddress, ByzantineNode>,
+    link_conditions: HashMap<(TestAddress, TestAddress), LinkConditions>,
+    /// Samples link delays so that a seeded network delivers messages in a reproducible order
+    rng: StdRng,
 }
 
 impl TestNetworkWorker {
@@ -231,6 +322,7 @@ impl TestNetworkWorker {
         let mut rx_leader = self.rx_leader.take().unwrap();
 
         let mut rx_new_transaction = self.rx_new_transaction.take().unwrap();
+        let mut rx_command = self.rx_command.take().unwrap();
         let tx_new_transactions = self.tx_new_transactions.clone();
         let transaction_store = self.transaction_store.clone();
 
@@ -256,7 +348,9 @@ impl TestNetworkWorker {
                     .await
                     .insert(*tx_record.transaction().id(), tx_record.clone());
 
-                for (addr, (shard_group, num_committees, tx_new_transaction_to_consensus, _)) in &tx_new_transactions {
+                for (addr, (shard_group, num_committees, tx_new_transaction_to_consensus, _)) in
+                    tx_new_transactions.read().await.iter()
+                {
                     if dest.is_for(addr, *shard_group, *num_committees) {
                         tx_new_transaction_to_consensus
                             .send((tx_record.transaction().clone(), 0))
@@ -277,46 +371,97 @@ impl TestNetworkWorker {
         });
 
         loop {
-            let mut rx_broadcast = rx_broadcast
-                .iter_mut()
-                .map(|(from, rx)| rx.recv().map(|r| (from.clone(), r)))
-                .collect::<FuturesUnordered<_>>();
-            let mut rx_leader = rx_leader
-                .iter_mut()
-                .map(|(from, rx)| rx.recv().map(|r| (from.clone(), r)))
-                .collect::<FuturesUnordered<_>>();
-
-            tokio::select! {
-                biased;
-
-                  _ = self.shutdown_signal.wait() => {
-                    break;
-                }
-                result = &mut mempool_task => {
-                    result.expect("Test Mempool task failed");
-                    break;
-                }
-
-                Ok(_) = self.network_status.changed() => {
-                    if let NetworkStatus::Started = *self.network_status.borrow() {
-                        continue;
+            let command = {
+                let mut rx_broadcast = rx_broadcast
+                    .iter_mut()
+                    .map(|(from, rx)| rx.recv().map(|r| (from.clone(), r)))
+                    .collect::<FuturesUnordered<_>>();
+                let mut rx_leader = rx_leader
+                    .iter_mut()
+                    .map(|(from, rx)| rx.recv().map(|r| (from.clone(), r)))
+                    .collect::<FuturesUnordered<_>>();
+
+                tokio::select! {
+                    biased;
+
+                      _ = self.shutdown_signal.wait() => {
+                        break;
+                    }
+                    result = &mut mempool_task => {
+                        result.expect("Test Mempool task failed");
+                        break;
                     }
-                    loop{
-                        self.network_status.changed().await.unwrap();
+
+                    Ok(_) = self.network_status.changed() => {
                         if let NetworkStatus::Started = *self.network_status.borrow() {
-                            break;
+                            continue;
                         }
+                        loop{
+                            self.network_status.changed().await.unwrap();
+                            if let NetworkStatus::Started = *self.network_status.borrow() {
+                                break;
+                            }
+                        }
+                        None
                     }
+
+                    // Commands are handled before messages so that a disconnected validator's channels are not
+                    // polled once it has shut down
+                    Some(command) = rx_command.recv() => Some(command),
+                    Some((from, Some((to, msg)))) = rx_broadcast.next() => {
+                        self.handle_broadcast(from, to, msg).await;
+                        None
+                    },
+                    Some((from, Some((to, msg)))) = rx_leader.next() => {
+                        self.handle_leader(from, to, msg).await;
+                        None
+                    },
                 }
+            };
 
-                Some((from, Some((to, msg)))) = rx_broadcast.next() => self.handle_broadcast(from, to, msg).await,
-                Some((from, Some((to, msg)))) = rx_leader.next() => self.handle_leader(from, to, msg).await,
+            if let Some(command) = command {
+                self.handle_command(command, &mut rx_broadcast, &mut rx_leader).await;
             }
         }
 
         log::info!("🛑 Network stopped");
     }
 
+    #[allow(clippy::type_complexity)]
+    async fn handle_command(
+        &mut self,
+        command: NetworkCommand,
+        rx_broadcast: &mut HashMap<TestAddress, mpsc::Receiver<(Vec<TestAddress>, HotstuffMessage)>>,
+        rx_leader: &mut HashMap<TestAddress, mpsc::Receiver<(TestAddress, HotstuffMessage)>>,
+    ) {
+        match command {
+            NetworkCommand::Connect(channels) => {
+                log::info!("🔌 {} connected", channels.address);
+                self.tx_new_transactions.write().await.insert(
+                    channels.address.clone(),
+                    (
+                        channels.shard_group,
+                        channels.num_committees,
+                        channels.tx_new_transactions,
+                        channels.state_store,
+                    ),
+                );
+                self.tx_hs_message
+                    .insert(channels.address.clone(), channels.tx_hs_message);
+                rx_broadcast.insert(channels.address.clone(), channels.rx_broadcast);
+                rx_leader.insert(channels.address, channels.rx_leader);
+            },
+            NetworkCommand::Disconnect { address, reply } => {
+                log::info!("🔌 {} disconnected", address);
+                self.tx_new_transactions.write().await.remove(&address);
+                self.tx_hs_message.remove(&address);
+                rx_broadcast.remove(&address);
+                rx_leader.remove(&address);
+                let _ignore = reply.send(());
+            },
+        }
+    }
+
     pub async fn handle_broadcast(&mut self, from: TestAddress, to_addrs: Vec<TestAddress>, msg: HotstuffMessage) {
         log::debug!("✉️ Broadcast {} from {} to {}", msg, from, to_addrs.iter().join(", "));
         for to in to_addrs {
@@ -335,14 +480,7 @@ impl TestNetworkWorker {
                 continue;
             }
 
-            self.tx_hs_message
-                .get(&to)
-                .unwrap()
-                .send((from.clone(), msg.clone()))
-                .await
-                .unwrap();
-            self.num_sent_messages
-                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.deliver(&from, &to, msg.clone()).await;
         }
         self.on_message.send(Some(msg.clone())).unwrap();
     }
@@ -364,9 +502,40 @@ impl TestNetworkWorker {
         }
         log::debug!("✉️ Message {} sent from {} to {}", msg, from, to);
         self.on_message.send(Some(msg.clone())).unwrap();
-        self.num_sent_messages
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        self.tx_hs_message.get(&to).unwrap().send((from, msg)).await.unwrap();
+        self.deliver(&from, &to, msg).await;
+    }
+
+    /// Sends the message to the destination validator, applying the sender's byzantine behaviour (if any) and the
+    /// link conditions between the two validators.
+    async fn deliver(&mut self, from: &TestAddress, to: &TestAddress, msg: HotstuffMessage) {
+        let messages = match self.byzantine_nodes.get(from) {
+            // Byzantine nodes are honest with themselves
+            Some(node) if from != to => node.on_outbound(to, msg),
+            _ => vec![msg],
+        };
+
+        let Some(tx_hs_message) = self.tx_hs_message.get(to) else {
+            log::info!("🗑️ Discarding message from {from}. {to} is disconnected");
+            return;
+        };
+        let link_conditions = self.link_conditions.get(&(from.clone(), to.clone()));
+        for msg in messages {
+            self.num_sent_messages
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            match link_conditions {
+                Some(conditions) => {
+                    let delay = conditions.sample_delay(&mut self.rng);
+                    let tx_hs_message = tx_hs_message.clone();
+                    let from = from.clone();
+                    task::spawn(async move {
+                        sleep(delay).await;
+                        // The validator may have shut down while the message was in flight
+                        let _ignore = tx_hs_message.send((from, msg)).await;
+                    });
+                },
+                None => tx_hs_message.send((from.clone(), msg)).await.unwrap(),
+            }
+        }
     }
 
     async fn is_offline_destination(&self, from: &TestAddress, to: &TestAddress, shard: ShardGroup) -> bool {
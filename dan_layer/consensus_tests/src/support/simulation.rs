@@ -0,0 +1,355 @@
+//    Copyright 2024 The Tari Project
+//    SPDX-License-Identifier: BSD-3-Clause
+
+//! Randomized consensus simulation driven by a single seed.
+//!
+//! The seed generates a trace of workload and fault events, and seeds the delays (and therefore the delivery order)
+//! of every network link. Each run executes on a single threaded runtime with a paused clock, so timers fire in
+//! virtual time and tasks are polled in a reproducible order. Validator internals that draw from the OS (signature
+//! nonces, substate ids) are not seeded, but they do not influence scheduling.
+//!
+//! When a run fails, the trace is shrunk by removing events while the failure still reproduces and the seed is
+//! printed along with the minimized trace.
+
+use std::{
+    collections::HashSet,
+    env,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use tari_dan_common_types::Epoch;
+use tari_dan_storage::consensus_models::Decision;
+
+use super::{LinkConditions, Test, TestAddress, TestVnDestination};
+
+pub const SEED_ENV_VAR: &str = "CONSENSUS_SIM_SEED";
+pub const RUNS_ENV_VAR: &str = "CONSENSUS_SIM_RUNS";
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub num_committees: u32,
+    pub committee_size: usize,
+    pub num_events: usize,
+    pub max_link_delay: Duration,
+    /// Number of committed blocks to wait for the transaction pools to drain after the trace has been applied
+    pub max_blocks_to_drain: usize,
+    /// Maximum number of simulations to run while shrinking a failing trace
+    pub max_shrink_runs: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_committees: 2,
+            // Tolerates one faulty member per committee
+            committee_size: 4,
+            num_events: 20,
+            max_link_delay: Duration::from_millis(200),
+            max_blocks_to_drain: 100,
+            max_shrink_runs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimEvent {
+    SendTransactions {
+        count: usize,
+        num_inputs: usize,
+        num_outputs: usize,
+    },
+    WaitForBlocks {
+        count: usize,
+    },
+    /// Cuts the nodes off from the network. At most one node per committee is taken offline at a time.
+    Partition {
+        nodes: Vec<TestAddress>,
+    },
+    Heal,
+    /// Restarts the node from its persisted state and keeps it offline for a number of committed blocks
+    Restart {
+        node: TestAddress,
+        down_for_blocks: usize,
+    },
+    NextEpoch,
+}
+
+impl Display for SimEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimEvent::SendTransactions {
+                count,
+                num_inputs,
+                num_outputs,
+            } => write!(
+                f,
+                "SendTransactions(count={count}, inputs={num_inputs}, outputs={num_outputs})"
+            ),
+            SimEvent::WaitForBlocks { count } => write!(f, "WaitForBlocks({count})"),
+            SimEvent::Partition { nodes } => write!(f, "Partition({})", nodes.iter().join(", ")),
+            SimEvent::Heal => write!(f, "Heal"),
+            SimEvent::Restart { node, down_for_blocks } => write!(f, "Restart({node}, down_for={down_for_blocks})"),
+            SimEvent::NextEpoch => write!(f, "NextEpoch"),
+        }
+    }
+}
+
+pub fn committee_addresses(config: &SimulationConfig) -> Vec<Vec<TestAddress>> {
+    (0..config.num_committees as usize)
+        .map(|committee| {
+            (0..config.committee_size)
+                .map(|member| TestAddress::new((committee * config.committee_size + member + 1).to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn generate_events(config: &SimulationConfig, seed: u64) -> Vec<SimEvent> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let committees = committee_addresses(config);
+    let nodes = committees.iter().flatten().collect::<Vec<_>>();
+
+    (0..config.num_events)
+        .map(|_| match rng.gen_range(0..100) {
+            0..=39 => SimEvent::SendTransactions {
+                count: rng.gen_range(1..=5),
+                num_inputs: rng.gen_range(1..=3),
+                num_outputs: rng.gen_range(0..=2),
+            },
+            40..=69 => SimEvent::WaitForBlocks {
+                count: rng.gen_range(1..=3),
+            },
+            70..=79 => SimEvent::Partition {
+                nodes: committees
+                    .iter()
+                    .filter_map(|members| {
+                        if rng.gen_bool(0.5) {
+                            members.choose(&mut rng).cloned()
+                        } else {
+                            None
+                        }
+                    })
+                    .collect(),
+            },
+            80..=86 => SimEvent::Heal,
+            87..=95 => SimEvent::Restart {
+                node: (*nodes.choose(&mut rng).unwrap()).clone(),
+                down_for_blocks: rng.gen_range(1..=3),
+            },
+            _ => SimEvent::NextEpoch,
+        })
+        .collect()
+}
+
+/// Runs the trace on a fresh single threaded runtime with a paused clock. Returns the panic message of the first
+/// failed invariant or harness assertion.
+pub fn run_simulation(config: &SimulationConfig, seed: u64, events: &[SimEvent]) -> Result<(), String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("Failed to build simulation runtime");
+
+    panic::catch_unwind(AssertUnwindSafe(|| runtime.block_on(simulate(config, seed, events))))
+        .map_err(|err| panic_message(err.as_ref()))
+}
+
+/// Removes chunks of events from a failing trace for as long as it keeps failing. Returns the smallest failing trace
+/// found and its failure.
+pub fn shrink(
+    config: &SimulationConfig,
+    seed: u64,
+    mut events: Vec<SimEvent>,
+    mut failure: String,
+) -> (Vec<SimEvent>, String) {
+    let mut num_runs = 0;
+    let mut chunk_size = (events.len() / 2).max(1);
+    while chunk_size > 0 && !events.is_empty() {
+        let mut removed_any = false;
+        let mut start = 0;
+        while start < events.len() {
+            if num_runs >= config.max_shrink_runs {
+                return (events, failure);
+            }
+            num_runs += 1;
+
+            let end = (start + chunk_size).min(events.len());
+            let candidate = [&events[..start], &events[end..]].concat();
+            match run_simulation(config, seed, &candidate) {
+                Err(err) => {
+                    events = candidate;
+                    failure = err;
+                    removed_any = true;
+                },
+                Ok(()) => start = end,
+            }
+        }
+
+        if !removed_any {
+            chunk_size /= 2;
+        }
+    }
+
+    (events, failure)
+}
+
+/// Runs the simulation for the seed, and panics with the seed and the minimized trace if it fails.
+pub fn check_seed(config: &SimulationConfig, seed: u64) {
+    eprintln!("🎲 Consensus simulation seed {seed}");
+    let events = generate_events(config, seed);
+    if let Err(failure) = run_simulation(config, seed, &events) {
+        let (trace, failure) = shrink(config, seed, events, failure);
+        panic!(
+            "Consensus simulation failed with seed {seed}: {failure}\nMinimized trace ({} event(s)):\n{}\nReproduce \
+             with {SEED_ENV_VAR}={seed}",
+            trace.len(),
+            trace
+                .iter()
+                .enumerate()
+                .map(|(i, event)| format!("  {i}: {event}"))
+                .join("\n")
+        );
+    }
+}
+
+/// Returns the seed from the environment, or `default_runs` (overridden by the runs environment variable) random seeds.
+pub fn seeds_from_env(default_runs: usize) -> Vec<u64> {
+    if let Ok(seed) = env::var(SEED_ENV_VAR) {
+        return vec![seed
+            .parse()
+            .unwrap_or_else(|_| panic!("{SEED_ENV_VAR} must be a u64, got '{seed}'"))];
+    }
+    let runs = env::var(RUNS_ENV_VAR)
+        .ok()
+        .map(|runs| {
+            runs.parse()
+                .unwrap_or_else(|_| panic!("{RUNS_ENV_VAR} must be a number, got '{runs}'"))
+        })
+        .unwrap_or(default_runs);
+    (0..runs).map(|_| rand::random()).collect()
+}
+
+async fn simulate(config: &SimulationConfig, seed: u64, events: &[SimEvent]) {
+    let committees = committee_addresses(config);
+    let nodes = committees.iter().flatten().cloned().collect::<Vec<_>>();
+
+    let mut builder = Test::builder()
+        .with_seed(seed)
+        .with_test_timeout(Duration::from_secs(60));
+    for (num, members) in committees.iter().enumerate() {
+        builder = builder.add_committee(num as u32, members.clone());
+    }
+    for from in &nodes {
+        for to in nodes.iter().filter(|to| *to != from) {
+            builder = builder.with_link_conditions(
+                from.clone(),
+                to.clone(),
+                LinkConditions::reordered(config.max_link_delay),
+            );
+        }
+    }
+    let mut test = builder.start().await;
+
+    let mut epoch = Epoch(1);
+    test.start_epoch(epoch).await;
+
+    let mut transactions = Vec::new();
+    let mut offline = HashSet::new();
+    for event in events {
+        log::info!("🎲 {event}");
+        match event {
+            SimEvent::SendTransactions {
+                count,
+                num_inputs,
+                num_outputs,
+            } => {
+                for _ in 0..*count {
+                    let (tx, _, _) = test
+                        .send_transaction_to_all(Decision::Commit, 1, *num_inputs, *num_outputs)
+                        .await;
+                    transactions.push(*tx.id());
+                }
+            },
+            SimEvent::WaitForBlocks { count } => wait_for_blocks(&mut test, *count).await,
+            SimEvent::Partition { nodes } => {
+                for node in nodes {
+                    if can_go_offline(&committees, &offline, node) {
+                        test.network()
+                            .go_offline(TestVnDestination::Address(node.clone()))
+                            .await;
+                        offline.insert(node.clone());
+                    }
+                }
+            },
+            SimEvent::Heal => {
+                for node in offline.drain() {
+                    test.network().go_online(&node).await;
+                }
+            },
+            SimEvent::Restart { node, down_for_blocks } => {
+                if can_go_offline(&committees, &offline, node) {
+                    test.network()
+                        .go_offline(TestVnDestination::Address(node.clone()))
+                        .await;
+                    test.restart_validator(node).await;
+                    wait_for_blocks(&mut test, *down_for_blocks).await;
+                    test.network().go_online(node).await;
+                }
+            },
+            SimEvent::NextEpoch => {
+                epoch = Epoch(epoch.as_u64() + 1);
+                test.start_epoch(epoch).await;
+            },
+        }
+    }
+
+    for node in offline.drain() {
+        test.network().go_online(&node).await;
+    }
+
+    let mut num_blocks = 0;
+    while !test.is_transaction_pool_empty() {
+        test.on_block_committed().await;
+        test.assert_honest_validators_agree();
+        num_blocks += 1;
+        if num_blocks > config.max_blocks_to_drain {
+            test.dump_pool_info();
+            panic!(
+                "Transaction pools did not drain after {} committed blocks",
+                config.max_blocks_to_drain
+            );
+        }
+    }
+
+    test.assert_honest_validators_agree();
+    for tx_id in &transactions {
+        test.assert_validators_agree_on_decision(tx_id);
+    }
+    test.assert_clean_shutdown().await;
+}
+
+async fn wait_for_blocks(test: &mut Test, count: usize) {
+    for _ in 0..count {
+        test.on_block_committed().await;
+        test.assert_honest_validators_agree();
+    }
+}
+
+/// A committee stays live as long as at most one member is offline
+fn can_go_offline(committees: &[Vec<TestAddress>], offline: &HashSet<TestAddress>, node: &TestAddress) -> bool {
+    committees
+        .iter()
+        .find(|members| members.contains(node))
+        .is_some_and(|members| !members.iter().any(|m| offline.contains(m)))
+}
+
+fn panic_message(err: &(dyn std::any::Any + Send)) -> String {
+    err.downcast_ref::<String>()
+        .cloned()
+        .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
@@ -103,6 +103,7 @@ pub fn create_execution_result_for_transaction(
                     total_fee_payment: fee.try_into().unwrap(),
                     total_fees_paid: fee.try_into().unwrap(),
                     cost_breakdown: FeeBreakdown::default(),
+                    storage_rebate: Default::default(),
                 },
             }),
         );
@@ -124,6 +125,7 @@ pub fn create_execution_result_for_transaction(
                 total_fee_payment: fee.try_into().unwrap(),
                 total_fees_paid: fee.try_into().unwrap(),
                 cost_breakdown: FeeBreakdown::default(),
+                storage_rebate: Default::default(),
             },
         ),
         execution_time: Duration::from_secs(0),
@@ -9,7 +9,7 @@ use tari_consensus::{
 use tari_crypto::keys::PublicKey as _;
 use tari_dan_common_types::{ShardGroup, SubstateAddress};
 use tari_dan_storage::consensus_models::TransactionPool;
-use tari_shutdown::ShutdownSignal;
+use tari_shutdown::Shutdown;
 use tari_state_store_sqlite::SqliteStateStore;
 use tokio::sync::{broadcast, mpsc, watch};
 
@@ -101,7 +101,18 @@ impl ValidatorBuilder {
         self
     }
 
-    pub fn spawn(&self, shutdown_signal: ShutdownSignal) -> (ValidatorChannels, Validator) {
+    pub fn with_transaction_executions(&mut self, transaction_executions: TestExecutionSpecStore) -> &mut Self {
+        self.transaction_executions = transaction_executions;
+        self
+    }
+
+    pub fn spawn(&self) -> (ValidatorChannels, Validator) {
+        let store = SqliteStateStore::connect(&self.sql_url).unwrap();
+        self.spawn_with_state_store(store)
+    }
+
+    /// Spawns the validator on an existing state store, e.g. to restart a validator from its persisted state
+    pub fn spawn_with_state_store(&self, store: SqliteStateStore<TestAddress>) -> (ValidatorChannels, Validator) {
         log::info!(
             "Spawning validator with address {} and public key {}",
             self.address,
@@ -123,7 +134,8 @@ impl ValidatorBuilder {
             TestOutboundMessaging::create(epoch_manager.clone(), tx_leader, tx_broadcast);
         let inbound_messaging = TestInboundMessaging::new(self.address.clone(), rx_hs_message, rx_loopback);
 
-        let store = SqliteStateStore::connect(&self.sql_url).unwrap();
+        let shutdown = Shutdown::new();
+        let shutdown_signal = shutdown.to_signal();
         let signing_service = TestVoteSignatureService::new(self.address.clone());
         let transaction_pool = TransactionPool::new();
         let (tx_events, _) = broadcast::channel(100);
@@ -181,6 +193,7 @@ impl ValidatorBuilder {
             epoch_manager,
             events: tx_events.subscribe(),
             current_state_machine_state: rx_current_state,
+            shutdown,
             handle,
         };
         (channels, validator)
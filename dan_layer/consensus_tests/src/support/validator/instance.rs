@@ -7,10 +7,11 @@ use tari_consensus::{
 };
 use tari_dan_common_types::{optional::Optional, NodeHeight, ShardGroup, SubstateAddress};
 use tari_dan_storage::{
-    consensus_models::{BlockId, LeafBlock, TransactionRecord},
+    consensus_models::{Block, BlockId, LeafBlock, TransactionRecord},
     StateStore,
     StateStoreReadTransaction,
 };
+use tari_shutdown::Shutdown;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_transaction::{Transaction, TransactionId};
 use tokio::{
@@ -49,6 +50,7 @@ pub struct Validator {
     pub events: broadcast::Receiver<HotstuffEvent>,
     pub current_state_machine_state: watch::Receiver<ConsensusCurrentState>,
 
+    pub shutdown: Shutdown,
     pub handle: JoinHandle<()>,
 }
 
@@ -88,6 +90,29 @@ impl Validator {
             })
     }
 
+    /// Returns the (height, block id) of every committed block in the current epoch
+    pub fn get_committed_blocks(&self) -> Vec<(NodeHeight, BlockId)> {
+        let epoch = self.epoch_manager.get_current_epoch();
+        let leaf = self.get_leaf_block();
+        self.state_store
+            .with_read_tx(|tx| {
+                Block::get_all_blocks_between(
+                    tx,
+                    epoch,
+                    self.shard_group,
+                    NodeHeight::zero(),
+                    leaf.height,
+                    true,
+                    10000,
+                )
+            })
+            .unwrap()
+            .into_iter()
+            .filter(|block| block.is_committed())
+            .map(|block| (block.height(), *block.id()))
+            .collect()
+    }
+
     pub fn has_committed_substates(&self, tx_id: &TransactionId) -> bool {
         let tx = self.state_store().create_read_tx().unwrap();
         tx.substates_exists_for_transaction(tx_id).unwrap()
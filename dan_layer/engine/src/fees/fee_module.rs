@@ -7,7 +7,14 @@ use tari_bor::encode_into_std_writer;
 use tari_engine_types::fees::FeeSource;
 
 use super::FeeTable;
-use crate::runtime::{RuntimeModule, RuntimeModuleError, StateTracker};
+use crate::runtime::{CryptoOperation, RuntimeModule, RuntimeModuleError, StateTracker};
+
+// TODO: Cost per byte of storage is reduced by a pretty arbitrarily chosen factor (floor(cost/0.333...))
+const STORAGE_COST_REDUCTION_DIVISOR: u64 = 3;
+/// Half of the storage cost of a destroyed substate is rebated
+const STORAGE_REBATE_DIVISOR: u64 = 2;
+/// The number of bytes that are charged as a single hash
+const HASH_BLOCK_SIZE: usize = 1024;
 
 pub struct FeeModule {
     initial_cost: u64,
@@ -38,6 +45,22 @@ impl RuntimeModule for FeeModule {
         Ok(())
     }
 
+    fn on_crypto_operation(&self, track: &StateTracker, operation: CryptoOperation) -> Result<(), RuntimeModuleError> {
+        let cost = match operation {
+            CryptoOperation::SignatureVerification => self.fee_table.per_signature_verification_cost(),
+            CryptoOperation::Hash { num_bytes } => num_hash_blocks(num_bytes) * self.fee_table.per_hash_cost(),
+            CryptoOperation::CommitmentArithmetic { num_commitments } => {
+                num_commitments as u64 * self.fee_table.per_commitment_operation_cost()
+            },
+            // One hash for the leaf and one for each node in the path to the root
+            CryptoOperation::MerkleInclusion { num_bytes, num_nodes } => {
+                (num_hash_blocks(num_bytes) + num_nodes as u64) * self.fee_table.per_hash_cost()
+            },
+        };
+        track.add_fee_charge(FeeSource::Crypto, cost);
+        Ok(())
+    }
+
     fn on_before_finalize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
         let total_storage = track.with_substates_to_persist(|changes| {
             let mut counter = ByteCounter::new();
@@ -47,8 +70,6 @@ impl RuntimeModule for FeeModule {
             Ok::<_, RuntimeModuleError>(counter.get())
         })?;
 
-        // TODO: Cost per byte of storage is reduced by a pretty arbitrarily chosen factor (floor(cost/0.333...))
-        const STORAGE_COST_REDUCTION_DIVISOR: u64 = 3;
         track.add_fee_charge(
             FeeSource::Storage,
             // Divide a storage cost reduction factor
@@ -62,10 +83,36 @@ impl RuntimeModule for FeeModule {
             track.num_events() as u64 * self.fee_table.per_event_cost(),
         );
 
+        // Rebate a portion of the storage cost of each destroyed substate
+        let total_destroyed = track.with_destroyed_substates(|destroyed| {
+            let mut counter = ByteCounter::new();
+            for (_, substate) in destroyed {
+                encode_into_std_writer(substate.substate_value(), &mut counter)?;
+            }
+            Ok::<_, RuntimeModuleError>(counter.get())
+        })?;
+        let rebate = self.fee_table.per_byte_storage_cost() * total_destroyed as u64 /
+            STORAGE_COST_REDUCTION_DIVISOR /
+            STORAGE_REBATE_DIVISOR;
+        if rebate > 0 {
+            // The rebate may not reduce the fee below the initial cost of the transaction
+            let max_rebate = track
+                .total_fee_charges()
+                .as_u64_checked()
+                .unwrap_or(0)
+                .saturating_sub(self.initial_cost);
+            track.add_storage_rebate(rebate.min(max_rebate));
+        }
+
         Ok(())
     }
 }
 
+fn num_hash_blocks(num_bytes: usize) -> u64 {
+    // Hashing empty data still costs one block
+    num_bytes.div_ceil(HASH_BLOCK_SIZE).max(1) as u64
+}
+
 // TODO: This may become available in tari_utilities in future
 #[derive(Debug, Clone, Default)]
 struct ByteCounter {
@@ -8,6 +8,11 @@ pub struct FeeTable {
     pub per_byte_storage_cost: u64,
     pub per_event_cost: u64,
     pub per_log_cost: u64,
+    pub per_signature_verification_cost: u64,
+    /// Charged for each 1KiB (or part thereof) of hashed data
+    pub per_hash_cost: u64,
+    /// Charged for each commitment that is an operand of a commitment operation
+    pub per_commitment_operation_cost: u64,
 }
 
 impl FeeTable {
@@ -18,6 +23,9 @@ impl FeeTable {
             per_byte_storage_cost: 0,
             per_event_cost: 0,
             per_log_cost: 0,
+            per_signature_verification_cost: 0,
+            per_hash_cost: 0,
+            per_commitment_operation_cost: 0,
         }
     }
 
@@ -40,4 +48,16 @@ impl FeeTable {
     pub fn per_log_cost(&self) -> u64 {
         self.per_log_cost
     }
+
+    pub fn per_signature_verification_cost(&self) -> u64 {
+        self.per_signature_verification_cost
+    }
+
+    pub fn per_hash_cost(&self) -> u64 {
+        self.per_hash_cost
+    }
+
+    pub fn per_commitment_operation_cost(&self) -> u64 {
+        self.per_commitment_operation_cost
+    }
 }
@@ -9,6 +9,14 @@ pub enum FlowEngineError {
     InstructionFailed { inner: String },
     #[error("Missing argument: {name}")]
     MissingArgument { name: String },
+    #[error("Invalid flow: {details}")]
+    InvalidFlow { details: String },
+    #[error("Flow exceeded the maximum of {max_steps} steps")]
+    MaxStepsExceeded { max_steps: usize },
+    #[error("Flow returned a value but does not declare an output type")]
+    UnexpectedReturnValue,
+    #[error("Flow node {node} ({name}) failed: {details}")]
+    NodeFailed { node: i64, name: String, details: String },
     #[error(transparent)]
     ExecutionError(#[from] anyhow::Error),
 }
@@ -1,4 +1,192 @@
 //  Copyright 2022 The Tari Project
 //  SPDX-License-Identifier: BSD-3-Clause
 
-pub struct FlowDefinition {}
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map as JsMap, Value as JsValue};
+
+use crate::flow::{workers::worker_info, FlowEngineError, WorkerKind, EXEC_SOCKET};
+
+/// A flow graph in the format exported by the rete.js node editor. Unknown fields (e.g. node positions) are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowDefinition {
+    #[serde(default)]
+    pub id: String,
+    pub nodes: BTreeMap<String, FlowNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowNode {
+    pub id: i64,
+    /// The name of the worker that executes this node
+    pub name: String,
+    /// Literal values for inputs that are not connected to another node
+    #[serde(default)]
+    pub data: JsMap<String, JsValue>,
+    #[serde(default)]
+    pub inputs: BTreeMap<String, FlowInput>,
+    #[serde(default)]
+    pub outputs: BTreeMap<String, FlowOutput>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FlowInput {
+    #[serde(default)]
+    pub connections: Vec<FlowInputConnection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowInputConnection {
+    pub node: i64,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FlowOutput {
+    #[serde(default)]
+    pub connections: Vec<FlowOutputConnection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowOutputConnection {
+    pub node: i64,
+    pub input: String,
+}
+
+impl FlowNode {
+    /// Returns the node and output connected to the given input, if any
+    pub fn input_connection(&self, input: &str) -> Option<&FlowInputConnection> {
+        self.inputs.get(input).and_then(|i| i.connections.first())
+    }
+
+    pub fn output_connections(&self, output: &str) -> &[FlowOutputConnection] {
+        self.outputs
+            .get(output)
+            .map(|o| o.connections.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl FlowDefinition {
+    /// Checks that the flow can be executed. Returns the nodes indexed by id and the id of the start node.
+    pub fn validate(&self) -> Result<(HashMap<i64, FlowNode>, i64), FlowEngineError> {
+        let mut nodes = HashMap::with_capacity(self.nodes.len());
+        let mut start_node = None;
+        for (key, node) in &self.nodes {
+            if key.parse::<i64>().ok() != Some(node.id) {
+                return Err(invalid_flow(format!(
+                    "node key `{}` does not match node id {}",
+                    key, node.id
+                )));
+            }
+            match worker_info(&node.name).map(|info| info.kind) {
+                Some(WorkerKind::Start) => {
+                    if start_node.replace(node.id).is_some() {
+                        return Err(invalid_flow("the flow has more than one start node"));
+                    }
+                },
+                Some(_) => {},
+                None => {
+                    return Err(invalid_flow(format!(
+                        "node {} uses unknown worker `{}`",
+                        node.id, node.name
+                    )));
+                },
+            }
+            nodes.insert(node.id, node.clone());
+        }
+
+        let start_node = start_node.ok_or_else(|| invalid_flow("the flow has no start node"))?;
+
+        for node in nodes.values() {
+            for (name, input) in &node.inputs {
+                if name != EXEC_SOCKET && input.connections.len() > 1 {
+                    return Err(invalid_flow(format!(
+                        "input `{}` of node {} has more than one connection",
+                        name, node.id
+                    )));
+                }
+                for conn in &input.connections {
+                    if !nodes.contains_key(&conn.node) {
+                        return Err(invalid_flow(format!(
+                            "input `{}` of node {} is connected to missing node {}",
+                            name, node.id, conn.node
+                        )));
+                    }
+                    if (name == EXEC_SOCKET) != is_control_output(&nodes, conn.node, &conn.output) {
+                        return Err(invalid_flow(format!(
+                            "input `{}` of node {} cannot be connected to output `{}` of node {}",
+                            name, node.id, conn.output, conn.node
+                        )));
+                    }
+                }
+            }
+            for (name, output) in &node.outputs {
+                for conn in &output.connections {
+                    if !nodes.contains_key(&conn.node) {
+                        return Err(invalid_flow(format!(
+                            "output `{}` of node {} is connected to missing node {}",
+                            name, node.id, conn.node
+                        )));
+                    }
+                }
+            }
+        }
+
+        check_data_cycles(&nodes)?;
+
+        Ok((nodes, start_node))
+    }
+}
+
+/// Control outputs trigger the execution of the next action node. All other outputs carry values.
+fn is_control_output(nodes: &HashMap<i64, FlowNode>, node_id: i64, output: &str) -> bool {
+    nodes
+        .get(&node_id)
+        .and_then(|n| worker_info(&n.name))
+        .is_some_and(|info| info.control_outputs.contains(&output))
+}
+
+/// Data nodes are evaluated on demand, so a cycle between them would never terminate
+fn check_data_cycles(nodes: &HashMap<i64, FlowNode>) -> Result<(), FlowEngineError> {
+    fn visit(
+        nodes: &HashMap<i64, FlowNode>,
+        id: i64,
+        visiting: &mut HashSet<i64>,
+        visited: &mut HashSet<i64>,
+    ) -> Result<(), FlowEngineError> {
+        if visited.contains(&id) {
+            return Ok(());
+        }
+        if !visiting.insert(id) {
+            return Err(invalid_flow(format!("node {} depends on its own output", id)));
+        }
+        let node = &nodes[&id];
+        for conn in node.inputs.values().flat_map(|i| &i.connections) {
+            let is_data_node = nodes
+                .get(&conn.node)
+                .and_then(|n| worker_info(&n.name))
+                .is_some_and(|info| info.kind == WorkerKind::Data);
+            if is_data_node {
+                visit(nodes, conn.node, visiting, visited)?;
+            }
+        }
+        visiting.remove(&id);
+        visited.insert(id);
+        Ok(())
+    }
+
+    let mut visiting = HashSet::new();
+    let mut visited = HashSet::new();
+    for id in nodes.keys() {
+        visit(nodes, *id, &mut visiting, &mut visited)?;
+    }
+    Ok(())
+}
+
+fn invalid_flow<T: Into<String>>(details: T) -> FlowEngineError {
+    FlowEngineError::InvalidFlow {
+        details: details.into(),
+    }
+}
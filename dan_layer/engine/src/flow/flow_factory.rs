@@ -3,15 +3,13 @@
 
 use std::sync::Arc;
 
-use d3ne::WorkersBuilder;
-use serde_json::Value as JsValue;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 use tari_engine_types::instruction_result::InstructionResult;
 use tari_template_abi::{ArgDef, FunctionDef, TemplateDef, TemplateDefV1, Type};
 
 use crate::{
-    flow::{FlowContext, FlowEngineError, FlowInstance},
-    function_definitions::{FlowFunctionDefinition, FunctionArgDefinition},
+    flow::{FlowEngineError, FlowInstance},
+    function_definitions::{ArgType, FlowFunctionDefinition, FunctionArgDefinition},
     runtime::Runtime,
     template::LoadedTemplate,
 };
@@ -22,13 +20,14 @@ pub const TARI_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub struct FlowFactory {
     name: String,
     args: Vec<FunctionArgDefinition>,
-    flow_definition: JsValue,
+    output: Option<ArgType>,
+    instance: FlowInstance,
     template_def: TemplateDef,
 }
 impl FlowFactory {
-    pub fn try_create<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
-        flow_definition: FlowFunctionDefinition,
-    ) -> Result<Self, FlowEngineError> {
+    /// Validates the flow definition and creates a factory for it
+    pub fn try_create(flow_definition: FlowFunctionDefinition) -> Result<Self, FlowEngineError> {
+        let instance = FlowInstance::try_build(&flow_definition.flow)?;
         let template_def = TemplateDef::V1(TemplateDefV1 {
             template_name: flow_definition.name.clone(),
             tari_version: TARI_VERSION.to_owned(),
@@ -42,23 +41,37 @@ impl FlowFactory {
                         arg_type: a.arg_type.to_type(),
                     })
                     .collect(),
-                output: Type::Unit,
+                output: flow_definition
+                    .output
+                    .as_ref()
+                    .map(|o| o.to_type())
+                    .unwrap_or(Type::Unit),
                 is_mut: false,
             }],
+            events: vec![],
         });
 
-        let _test_build = FlowInstance::try_build(
-            flow_definition.flow.clone(),
-            WorkersBuilder::<FlowContext<TTemplateProvider>>::default().build(),
-        )?;
         Ok(Self {
             name: flow_definition.name,
             args: flow_definition.args,
-            flow_definition: flow_definition.flow,
+            output: flow_definition.output,
+            instance,
             template_def,
         })
     }
 
+    /// Returns true if the published template code is a JSON flow definition rather than a WASM binary
+    pub fn is_flow_json(code: &[u8]) -> bool {
+        code.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+    }
+
+    /// Parses and validates a JSON flow definition
+    pub fn try_from_json(json: &[u8]) -> Result<Self, FlowEngineError> {
+        let definition: FlowFunctionDefinition =
+            serde_json::from_slice(json).map_err(|e| FlowEngineError::InvalidFlow { details: e.to_string() })?;
+        Self::try_create(definition)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -77,15 +90,12 @@ impl FlowFactory {
         call_depth: usize,
         max_call_depth: usize,
     ) -> Result<InstructionResult, FlowEngineError> {
-        let new_instance = FlowInstance::try_build::<TTemplateProvider>(
-            self.flow_definition.clone(),
-            WorkersBuilder::default().build(),
-        )?;
-        new_instance.invoke(
+        self.instance.invoke(
             template_provider,
             runtime,
             &args,
             &self.args,
+            self.output.as_ref(),
             call_depth,
             max_call_depth,
         )
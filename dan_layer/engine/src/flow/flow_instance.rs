@@ -3,64 +3,65 @@
 
 use std::{collections::HashMap, sync::Arc};
 
-use d3ne::{Engine, Node, Workers, WorkersBuilder};
-use serde_json::Value as JsValue;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
-use tari_engine_types::instruction_result::InstructionResult;
+use tari_engine_types::{indexed_value::IndexedValue, instruction_result::InstructionResult};
 
 use crate::{
     flow::{
-        workers::{ArgWorker, CallMethodWorker},
+        coerce_value,
+        workers::{get_worker, worker_info, RepeatWorker},
         FlowContext,
+        FlowControl,
+        FlowDefinition,
         FlowEngineError,
+        FlowInputs,
+        FlowNode,
+        WorkerKind,
+        WorkerOutput,
+        EXEC_SOCKET,
     },
-    function_definitions::FunctionArgDefinition,
+    function_definitions::{ArgType, FunctionArgDefinition},
     runtime::Runtime,
     template::LoadedTemplate,
 };
 
+/// The maximum number of nodes and loop iterations that are executed in a single flow invocation. This bounds loops
+/// and recursion.
+pub const MAX_FLOW_STEPS: usize = 1000;
+
 #[derive(Clone, Debug)]
 pub struct FlowInstance {
-    // engine: Engine,
-    // TODO: engine is not Send so can't be added here
-    // process: JsValue,
     start_node: i64,
-    nodes: HashMap<i64, Node>,
+    nodes: Arc<HashMap<i64, FlowNode>>,
 }
 
 impl FlowInstance {
-    pub fn try_build<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
-        value: JsValue,
-        workers: Workers<FlowContext<TTemplateProvider>>,
-    ) -> Result<Self, FlowEngineError> {
-        let engine = Engine::new("tari_engine@0.1.0".to_string(), workers);
-        // dbg!(&value);
-        let nodes = engine.parse_value(value).expect("could not create engine");
+    pub fn try_build(definition: &FlowDefinition) -> Result<Self, FlowEngineError> {
+        let (nodes, start_node) = definition.validate()?;
         Ok(FlowInstance {
-            // process: value,
-            nodes,
-            start_node: 1,
+            start_node,
+            nodes: Arc::new(nodes),
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn invoke<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
         &self,
         template_provider: Arc<TTemplateProvider>,
         runtime: Runtime,
         args: &[tari_bor::Value],
         arg_defs: &[FunctionArgDefinition],
+        output: Option<&ArgType>,
         call_depth: usize,
         max_call_depth: usize,
     ) -> Result<InstructionResult, FlowEngineError> {
-        let engine = Engine::new("tari@0.1.0".to_string(), load_workers());
         let mut args_map = HashMap::new();
         for (i, arg_def) in arg_defs.iter().enumerate() {
-            if i >= args.len() {
-                return Err(FlowEngineError::MissingArgument {
-                    name: arg_def.name.clone(),
-                });
-            }
-            args_map.insert(arg_def.name.clone(), (args[i].clone(), arg_def.clone()));
+            let arg = args.get(i).ok_or_else(|| FlowEngineError::MissingArgument {
+                name: arg_def.name.clone(),
+            })?;
+            let arg = coerce_value(arg.clone(), &arg_def.arg_type.to_type())?;
+            args_map.insert(arg_def.name.clone(), (arg, arg_def.clone()));
         }
 
         let context = FlowContext {
@@ -70,32 +71,137 @@ impl FlowInstance {
             call_depth,
             max_call_depth,
         };
-        let result = engine.process(&context, &self.nodes, self.start_node)?;
-        if result.is_empty() {
-            Ok(InstructionResult::empty())
-        } else {
-            todo!("Returning results from a flow is not yet implemented")
+
+        let mut execution = FlowExecution {
+            context: &context,
+            nodes: &self.nodes,
+            values: HashMap::new(),
+            steps: 0,
+        };
+
+        match (execution.execute(self.start_node)?, output) {
+            (Some(value), Some(output)) => {
+                let indexed = IndexedValue::from_value(value).map_err(|e| anyhow::anyhow!("{}", e))?;
+                Ok(InstructionResult {
+                    indexed,
+                    return_type: output.to_type(),
+                })
+            },
+            (Some(_), None) => Err(FlowEngineError::UnexpectedReturnValue),
+            (None, _) => Ok(InstructionResult::empty()),
         }
     }
 }
 
-fn load_workers<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
-) -> Workers<FlowContext<TTemplateProvider>> {
-    let mut workers = WorkersBuilder::default();
-
-    // workers.add(StartWorker {});
-    workers.add(CallMethodWorker {});
-    // workers.add(CreateBucketWorker {
-    //     state_db: state_db.clone(),
-    // });
-    // workers.add(StoreBucketWorker {
-    //     state_db: state_db.clone(),
-    // });
-    // workers.add(ArgWorker { args: args.clone() });
-    workers.add(ArgWorker {});
-    // workers.add(SenderWorker { sender });
-    // workers.add(TextWorker {});
-    // workers.add(HasRoleWorker { state_db });
-    // workers.add(MintBucketWorker {});
-    workers.build()
+/// The state of a single flow invocation
+struct FlowExecution<'a, TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> {
+    context: &'a FlowContext<TTemplateProvider>,
+    nodes: &'a HashMap<i64, FlowNode>,
+    /// The outputs of the action nodes that have executed, keyed by node id and output name
+    values: HashMap<(i64, String), tari_bor::Value>,
+    steps: usize,
+}
+
+impl<'a, TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowExecution<'a, TTemplateProvider> {
+    /// Executes an action node and the nodes that follow it. Returns the value of the first return node reached.
+    fn execute(&mut self, node_id: i64) -> Result<Option<tari_bor::Value>, FlowEngineError> {
+        let node = self.node(node_id)?;
+        let output = self.run_worker(node)?;
+        for (name, value) in output.values {
+            self.values.insert((node_id, name), value);
+        }
+
+        match output.control {
+            FlowControl::Continue(control_output) => self.follow(node, control_output),
+            FlowControl::Repeat { iterations } => {
+                for i in 0..iterations {
+                    // Charge each iteration so that a loop with an empty body is still bounded
+                    self.charge_step()?;
+                    self.values.insert(
+                        (node_id, RepeatWorker::INDEX.to_string()),
+                        tari_bor::Value::Integer(i.into()),
+                    );
+                    if let Some(value) = self.follow(node, RepeatWorker::BODY)? {
+                        return Ok(Some(value));
+                    }
+                }
+                self.follow(node, RepeatWorker::DONE)
+            },
+            FlowControl::Return(value) => Ok(Some(value)),
+        }
+    }
+
+    /// Executes the nodes connected to a control output in order
+    fn follow(&mut self, node: &FlowNode, control_output: &str) -> Result<Option<tari_bor::Value>, FlowEngineError> {
+        for conn in node.output_connections(control_output) {
+            if let Some(value) = self.execute(conn.node)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn charge_step(&mut self) -> Result<(), FlowEngineError> {
+        self.steps += 1;
+        if self.steps > MAX_FLOW_STEPS {
+            return Err(FlowEngineError::MaxStepsExceeded {
+                max_steps: MAX_FLOW_STEPS,
+            });
+        }
+        Ok(())
+    }
+
+    fn run_worker(&mut self, node: &'a FlowNode) -> Result<WorkerOutput, FlowEngineError> {
+        self.charge_step()?;
+
+        let worker = get_worker::<TTemplateProvider>(&node.name).ok_or_else(|| FlowEngineError::InvalidFlow {
+            details: format!("node {} uses unknown worker `{}`", node.id, node.name),
+        })?;
+        let inputs = self.resolve_inputs(node)?;
+        worker
+            .work(self.context, node, &inputs)
+            .map_err(|e| FlowEngineError::NodeFailed {
+                node: node.id,
+                name: node.name.clone(),
+                details: e.to_string(),
+            })
+    }
+
+    fn resolve_inputs(&mut self, node: &'a FlowNode) -> Result<FlowInputs<'a>, FlowEngineError> {
+        let mut connected = HashMap::new();
+        for (name, input) in &node.inputs {
+            if name == EXEC_SOCKET {
+                continue;
+            }
+            if let Some(conn) = input.connections.first() {
+                let value = self.read_output(conn.node, &conn.output)?;
+                connected.insert(name.clone(), value);
+            }
+        }
+        Ok(FlowInputs::new(node, connected))
+    }
+
+    /// Data nodes are evaluated each time they are read. Action nodes must have executed before their outputs are
+    /// read.
+    fn read_output(&mut self, node_id: i64, output: &str) -> Result<tari_bor::Value, FlowEngineError> {
+        let node = self.node(node_id)?;
+        let is_data_node = worker_info(&node.name).is_some_and(|info| info.kind == WorkerKind::Data);
+        let value = if is_data_node {
+            self.run_worker(node)?.values.remove(output)
+        } else {
+            self.values.get(&(node_id, output.to_string())).cloned()
+        };
+
+        value.ok_or_else(|| FlowEngineError::NodeFailed {
+            node: node.id,
+            name: node.name.clone(),
+            details: format!("output `{}` has no value", output),
+        })
+    }
+
+    fn node(&self, node_id: i64) -> Result<&'a FlowNode, FlowEngineError> {
+        self.nodes.get(&node_id).ok_or_else(|| FlowEngineError::InvalidFlow {
+            details: format!("node {} does not exist", node_id),
+        })
+    }
 }
@@ -3,32 +3,26 @@
 
 pub mod error;
 mod flow_context;
+mod flow_definition;
 mod flow_factory;
 mod flow_instance;
+mod worker;
 pub mod workers;
-use std::any::Any;
 
 pub use error::FlowEngineError;
 pub use flow_context::FlowContext;
+pub use flow_definition::{FlowDefinition, FlowInput, FlowInputConnection, FlowNode, FlowOutput, FlowOutputConnection};
 pub use flow_factory::FlowFactory;
 pub use flow_instance::FlowInstance;
-use tari_common_types::types::PublicKey;
-
-#[derive(Clone, Debug)]
-pub enum ArgValue {
-    String(String),
-    Byte(u8),
-    PublicKey(PublicKey),
-    Uint(u64),
-}
-
-impl ArgValue {
-    pub fn into_any(self) -> Box<dyn Any> {
-        match self {
-            ArgValue::String(s) => Box::new(s),
-            ArgValue::Byte(b) => Box::new(b),
-            ArgValue::PublicKey(k) => Box::new(k),
-            ArgValue::Uint(u) => Box::new(u),
-        }
-    }
-}
+pub use worker::{
+    coerce_value,
+    json_to_value,
+    FlowControl,
+    FlowInputs,
+    FlowWorker,
+    WorkerInfo,
+    WorkerKind,
+    WorkerOutput,
+    DEFAULT_OUTPUT,
+    EXEC_SOCKET,
+};
@@ -0,0 +1,180 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, str::FromStr};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsValue;
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_abi::Type;
+use tari_template_lib::models::{ComponentAddress, ResourceAddress, VaultId};
+
+use crate::{
+    flow::{FlowContext, FlowNode},
+    template::LoadedTemplate,
+};
+
+/// The name of the sockets that carry control flow between action nodes
+pub const EXEC_SOCKET: &str = "exec";
+/// The name of the output socket of nodes that produce a single value
+pub const DEFAULT_OUTPUT: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerKind {
+    /// The entry point of the flow. Every flow has exactly one start node.
+    Start,
+    /// Executed when triggered by a control output of another node. The outputs of an action node are available to
+    /// other nodes once it has executed.
+    Action,
+    /// Evaluated on demand each time one of its outputs is read. Data nodes have no side effects.
+    Data,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerInfo {
+    pub kind: WorkerKind,
+    pub control_outputs: &'static [&'static str],
+}
+
+/// Determines which nodes are executed after an action node
+#[derive(Debug, Clone)]
+pub enum FlowControl {
+    /// Continue with the nodes connected to the given control output
+    Continue(&'static str),
+    /// Execute the nodes connected to the `body` output `iterations` times, setting the `index` output to the current
+    /// iteration, then continue with the nodes connected to the `done` output
+    Repeat { iterations: u64 },
+    /// Stop executing the flow and return the value
+    Return(tari_bor::Value),
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerOutput {
+    pub values: HashMap<String, tari_bor::Value>,
+    pub control: FlowControl,
+}
+
+impl WorkerOutput {
+    pub fn next(control_output: &'static str) -> Self {
+        Self {
+            values: HashMap::new(),
+            control: FlowControl::Continue(control_output),
+        }
+    }
+
+    pub fn value(value: tari_bor::Value) -> Self {
+        Self::next(EXEC_SOCKET).with_value(DEFAULT_OUTPUT, value)
+    }
+
+    pub fn with_value<T: Into<String>>(mut self, output: T, value: tari_bor::Value) -> Self {
+        self.values.insert(output.into(), value);
+        self
+    }
+
+    pub fn with_control(mut self, control: FlowControl) -> Self {
+        self.control = control;
+        self
+    }
+}
+
+pub trait FlowWorker<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> {
+    fn work(
+        &self,
+        context: &FlowContext<TTemplateProvider>,
+        node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error>;
+}
+
+/// The input values of a node. Connected inputs take precedence over literal values in the node data.
+pub struct FlowInputs<'a> {
+    node: &'a FlowNode,
+    connected: HashMap<String, tari_bor::Value>,
+}
+
+impl<'a> FlowInputs<'a> {
+    pub fn new(node: &'a FlowNode, connected: HashMap<String, tari_bor::Value>) -> Self {
+        Self { node, connected }
+    }
+
+    pub fn get(&self, name: &str) -> Option<tari_bor::Value> {
+        self.connected
+            .get(name)
+            .cloned()
+            .or_else(|| self.node.data.get(name).map(json_to_value))
+    }
+
+    pub fn require(&self, name: &str) -> Result<tari_bor::Value, anyhow::Error> {
+        self.get(name)
+            .ok_or_else(|| anyhow::anyhow!("node {} ({}) is missing input `{}`", self.node.id, self.node.name, name))
+    }
+
+    pub fn require_as<T: DeserializeOwned>(&self, name: &str) -> Result<T, anyhow::Error> {
+        let value = self.require(name)?;
+        tari_bor::from_value(&value).map_err(|e| {
+            anyhow::anyhow!(
+                "input `{}` of node {} ({}) has an invalid value: {}",
+                name,
+                self.node.id,
+                self.node.name,
+                e
+            )
+        })
+    }
+
+    /// Returns a string input from the node data. Used for node configuration such as method names.
+    pub fn require_data_str(&self, name: &str) -> Result<&str, anyhow::Error> {
+        self.node.data.get(name).and_then(|v| v.as_str()).ok_or_else(|| {
+            anyhow::anyhow!(
+                "node {} ({}) is missing string data `{}`",
+                self.node.id,
+                self.node.name,
+                name
+            )
+        })
+    }
+
+    /// Returns the names and values of all connected inputs, excluding control inputs
+    pub fn connected(&self) -> impl Iterator<Item = (&str, &tari_bor::Value)> + '_ {
+        self.connected.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+/// Converts a literal JSON value in the flow definition to CBOR
+pub fn json_to_value(value: &JsValue) -> tari_bor::Value {
+    match value {
+        JsValue::Null => tari_bor::Value::Null,
+        JsValue::Bool(b) => tari_bor::Value::Bool(*b),
+        JsValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                tari_bor::Value::Integer(i.into())
+            } else if let Some(u) = n.as_u64() {
+                tari_bor::Value::Integer(u.into())
+            } else {
+                tari_bor::Value::Float(n.as_f64().unwrap_or_default())
+            }
+        },
+        JsValue::String(s) => tari_bor::Value::Text(s.clone()),
+        JsValue::Array(arr) => tari_bor::Value::Array(arr.iter().map(json_to_value).collect()),
+        JsValue::Object(obj) => tari_bor::Value::Map(
+            obj.iter()
+                .map(|(k, v)| (tari_bor::Value::Text(k.clone()), json_to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts string literals to the address types expected by a template function argument. Other values are returned
+/// unchanged.
+pub fn coerce_value(value: tari_bor::Value, ty: &Type) -> Result<tari_bor::Value, anyhow::Error> {
+    let (Some(name), tari_bor::Value::Text(s)) = (ty.other(), &value) else {
+        return Ok(value);
+    };
+    let coerced = match name {
+        "ComponentAddress" => tari_bor::to_value(&ComponentAddress::from_str(s)?)?,
+        "ResourceAddress" => tari_bor::to_value(&ResourceAddress::from_str(s)?)?,
+        "VaultId" => tari_bor::to_value(&VaultId::from_str(s)?)?,
+        _ => return Ok(value),
+    };
+    Ok(coerced)
+}
@@ -1,46 +1,33 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashMap;
-
-use d3ne::{Node, OutputValue, Worker};
-use tari_bor::from_value;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 
-use crate::{flow::FlowContext, function_definitions::ArgType, template::LoadedTemplate};
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
 
+/// Outputs the value of a flow argument
 pub struct ArgWorker {}
 
-impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> Worker<FlowContext<TTemplateProvider>>
-    for ArgWorker
-{
+impl ArgWorker {
+    pub const NAME: &'static str = "tari::arg";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for ArgWorker {
     fn work(
         &self,
         context: &FlowContext<TTemplateProvider>,
-        node: &Node,
-        _input_data: HashMap<String, OutputValue>,
-    ) -> Result<HashMap<String, OutputValue>, anyhow::Error> {
-        let arg_name: String = node
-            .get_data("name")?
-            .ok_or_else(|| anyhow::anyhow!("could not find arg `name`"))?;
-        let (value, arg_def) = context
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let arg_name = inputs.require_data_str("name")?;
+        let (value, _) = context
             .args
-            .get(arg_name.as_str())
-            .ok_or_else(|| anyhow::anyhow!("could not find arg"))?;
-
-        let mut result = HashMap::new();
-        match arg_def.arg_type {
-            ArgType::String => {
-                result.insert("default".to_string(), OutputValue::String(from_value(value)?));
-            },
-            ArgType::Bytes => {
-                result.insert("default".to_string(), OutputValue::Bytes(from_value(value)?));
-            },
-        };
-        Ok(result)
-    }
+            .get(arg_name)
+            .ok_or_else(|| anyhow::anyhow!("could not find arg `{}`", arg_name))?;
 
-    fn name(&self) -> &str {
-        "tari::arg"
+        Ok(WorkerOutput::value(value.clone()))
     }
 }
@@ -0,0 +1,56 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_lib::models::TemplateAddress;
+
+use crate::{
+    flow::{
+        workers::call_method_worker::{collect_args, find_function},
+        FlowContext,
+        FlowInputs,
+        FlowNode,
+        FlowWorker,
+        WorkerOutput,
+    },
+    template::LoadedTemplate,
+    transaction::TransactionProcessor,
+};
+
+/// Calls the function named in the `function` node data on the template given by the `template` node data. The
+/// function arguments are read from the inputs of the same name. Outputs the return value of the function.
+pub struct CallFunctionWorker {}
+
+impl CallFunctionWorker {
+    pub const NAME: &'static str = "tari::dan::call_function";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider>
+    for CallFunctionWorker
+{
+    fn work(
+        &self,
+        context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let template_hash = inputs.require_data_str("template")?;
+        let template_hash = template_hash.strip_prefix("0x").unwrap_or(template_hash);
+        let template_address = TemplateAddress::from_hex(template_hash)
+            .map_err(|e| anyhow::anyhow!("Template address `{}` was not a valid hash: {}", template_hash, e))?;
+        let function_name = inputs.require_data_str("function")?;
+
+        let function_def = find_function(context, &template_address, function_name)?;
+        let args = collect_args(inputs, &function_def)?;
+
+        let exec_result = TransactionProcessor::call_function(
+            &*context.template_provider,
+            &context.runtime,
+            &template_address,
+            function_name,
+            args,
+        )?;
+
+        Ok(WorkerOutput::value(exec_result.indexed.into_value()))
+    }
+}
@@ -1,106 +1,83 @@
 //  Copyright 2022 The Tari Project
 //  SPDX-License-Identifier: BSD-3-Clause
 
-use std::{collections::HashMap, str::FromStr};
-
-use d3ne::{Node, OutputValue, Worker};
-use tari_bor::encode;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_abi::FunctionDef;
 use tari_template_lib::{
     args::Arg,
     models::{ComponentAddress, TemplateAddress},
 };
 
-use crate::{flow::FlowContext, template::LoadedTemplate, transaction::TransactionProcessor};
+use crate::{
+    flow::{coerce_value, FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    function_definitions::ArgType,
+    template::LoadedTemplate,
+    transaction::TransactionProcessor,
+};
 
+/// Calls the method named in the `method` node data on the component given by the `self` input. The remaining method
+/// arguments are read from the inputs of the same name. Outputs the return value of the method.
 pub struct CallMethodWorker {}
 
-impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> Worker<FlowContext<TTemplateProvider>>
+impl CallMethodWorker {
+    pub const NAME: &'static str = "tari::dan::call_method";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider>
     for CallMethodWorker
 {
-    fn name(&self) -> &str {
-        "tari::dan::call_method"
-    }
-
     fn work(
         &self,
         context: &FlowContext<TTemplateProvider>,
-        node: &Node,
-        input_data: HashMap<String, OutputValue>,
-    ) -> Result<HashMap<String, OutputValue>, anyhow::Error> {
-        let component_address = input_data
-            .get("self")
-            .cloned()
-            .or(node.get_data("self")?.map(OutputValue::Bytes))
-            .ok_or_else(|| anyhow::anyhow!("could not find arg `self`"))?;
-        let component_address = String::from_utf8(component_address.as_bytes()?.to_vec())?;
-        let component_address = ComponentAddress::from_str(&component_address)?;
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let component_address = inputs.require("self")?;
+        let component_address = coerce_value(component_address, &ArgType::ComponentAddress.to_type())?;
+        let component_address: ComponentAddress = tari_bor::from_value(&component_address)?;
+        let method_name = inputs.require_data_str("method")?;
 
-        let method_name = &node
-            .get_data::<String>("method")?
-            .ok_or_else(|| anyhow::anyhow!("could not find arg `method`"))?;
-        // TODO: There might be a better way to get the template, but for now, you must specify it
-        // on the node...
-        let mut template_hash = node
-            .get_data::<String>("template")?
-            .ok_or_else(|| anyhow::anyhow!("Template not set in data"))?;
-        if template_hash.starts_with("0x") {
-            template_hash = template_hash[2..].to_string();
-        }
-        let template_address: TemplateAddress = TemplateAddress::from_hex(&template_hash).map_err(|e| {
-            anyhow::anyhow!(format!(
-                "Template address `{}` was not a valid hash:{}",
-                &template_hash, e
-            ))
-        })?;
-
-        let function_definition = context
-            .template_provider
-            .get_template_module(&template_address)?
-            .ok_or_else(|| anyhow::anyhow!("could not find template {}", template_address))?
-            .template_def()
-            .functions()
-            .iter()
-            .find(|f| f.name == *method_name)
-            .ok_or_else(|| anyhow::anyhow!("could not find method"))?
-            .clone();
-
-        let mut args = Vec::new();
-        for arg in &function_definition.arguments {
-            if arg.name == "self" {
-                // self has already been added
-                continue;
-            }
-            let arg_value = input_data
-                .get(arg.name.as_str())
-                .cloned()
-                .or(node.get_data(arg.name.as_str())?.map(OutputValue::Bytes))
-                .ok_or_else(|| anyhow::anyhow!("could not find arg `{}`", arg.name))?;
-            args.push(Arg::literal(convert_d3ne_value_to_tari_bor(arg_value))?);
-        }
+        let component = context.runtime.interface().load_component(&component_address)?;
+        let function_def = find_function(context, &component.template_address, method_name)?;
+        let args = collect_args(inputs, &function_def)?;
 
         let exec_result = TransactionProcessor::call_method(
             &*context.template_provider,
             &context.runtime,
             &component_address,
             method_name,
-            // TODO: put in rest of args
             args,
         )?;
 
-        let result = exec_result.indexed.into_value();
-
-        let mut h = HashMap::new();
-        h.insert("default".to_string(), OutputValue::Bytes(encode(&result)?));
-        Ok(h)
+        Ok(WorkerOutput::value(exec_result.indexed.into_value()))
     }
 }
 
-fn convert_d3ne_value_to_tari_bor(value: OutputValue) -> tari_bor::Value {
-    match value {
-        OutputValue::String(s) => tari_bor::Value::Text(s),
-        OutputValue::Bytes(b) => tari_bor::Value::Bytes(b),
-        OutputValue::I64(i) => tari_bor::Value::Integer(i.into()),
-        OutputValue::U64(i) => tari_bor::Value::Integer(i.into()),
-    }
+pub(super) fn find_function<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
+    context: &FlowContext<TTemplateProvider>,
+    template_address: &TemplateAddress,
+    name: &str,
+) -> Result<FunctionDef, anyhow::Error> {
+    context
+        .template_provider
+        .get_template_module(template_address)
+        .map_err(|e| anyhow::anyhow!("could not load template {}: {}", template_address, e))?
+        .ok_or_else(|| anyhow::anyhow!("could not find template {}", template_address))?
+        .template_def()
+        .get_function(name)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("template {} has no function `{}`", template_address, name))
+}
+
+pub(super) fn collect_args(inputs: &FlowInputs<'_>, function_def: &FunctionDef) -> Result<Vec<Arg>, anyhow::Error> {
+    function_def
+        .arguments
+        .iter()
+        // self is supplied separately
+        .filter(|arg| arg.name != "self")
+        .map(|arg| {
+            let value = coerce_value(inputs.require(&arg.name)?, &arg.arg_type)?;
+            Ok(Arg::literal(value)?)
+        })
+        .collect()
 }
@@ -0,0 +1,53 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::cmp::Ordering;
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Compares the inputs `a` and `b` using the `op` in the node data (one of eq, ne, lt, le, gt, ge) and outputs a bool.
+/// Integers are compared numerically, all other values must be equal types and can only be compared for equality.
+pub struct CompareWorker {}
+
+impl CompareWorker {
+    pub const NAME: &'static str = "core::compare";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for CompareWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let a = inputs.require("a")?;
+        let b = inputs.require("b")?;
+        let op = inputs.require_data_str("op")?;
+
+        let ordering = match (&a, &b) {
+            (tari_bor::Value::Integer(a), tari_bor::Value::Integer(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+            (tari_bor::Value::Text(a), tari_bor::Value::Text(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+
+        let result = match (op, ordering) {
+            ("eq", _) => a == b,
+            ("ne", _) => a != b,
+            ("lt", Some(ord)) => ord == Ordering::Less,
+            ("le", Some(ord)) => ord != Ordering::Greater,
+            ("gt", Some(ord)) => ord == Ordering::Greater,
+            ("ge", Some(ord)) => ord != Ordering::Less,
+            ("lt" | "le" | "gt" | "ge", None) => {
+                return Err(anyhow::anyhow!("cannot order values {:?} and {:?}", a, b));
+            },
+            (op, _) => return Err(anyhow::anyhow!("unknown comparison operator `{}`", op)),
+        };
+
+        Ok(WorkerOutput::value(tari_bor::Value::Bool(result)))
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Outputs the literal `value` from the node data
+pub struct ConstantWorker {}
+
+impl ConstantWorker {
+    pub const NAME: &'static str = "core::constant";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for ConstantWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        Ok(WorkerOutput::value(inputs.require("value")?))
+    }
+}
@@ -0,0 +1,49 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_lib::models::Metadata;
+
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput, EXEC_SOCKET},
+    template::LoadedTemplate,
+};
+
+/// Emits an event with the `topic` from the node data. Every connected input is added to the event payload, keyed by
+/// the input name.
+pub struct EmitEventWorker {}
+
+impl EmitEventWorker {
+    pub const NAME: &'static str = "tari::emit_event";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for EmitEventWorker {
+    fn work(
+        &self,
+        context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let topic = inputs.require_data_str("topic")?;
+
+        let mut payload = Metadata::new();
+        for (name, value) in inputs.connected() {
+            let value = match value {
+                tari_bor::Value::Text(s) => s.clone(),
+                tari_bor::Value::Integer(i) => i128::from(*i).to_string(),
+                tari_bor::Value::Bool(b) => b.to_string(),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "event payload field `{}` must be text, an integer or a bool",
+                        name
+                    ))
+                },
+            };
+            payload.insert(name, value);
+        }
+
+        context.runtime.interface().emit_event(topic.to_string(), payload)?;
+
+        Ok(WorkerOutput::next(EXEC_SOCKET))
+    }
+}
@@ -0,0 +1,34 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Continues with the `then` output if the `condition` input is true, otherwise with the `else` output
+pub struct IfWorker {}
+
+impl IfWorker {
+    pub const ELSE: &'static str = "else";
+    pub const NAME: &'static str = "core::if";
+    pub const THEN: &'static str = "then";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for IfWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let condition: bool = inputs.require_as("condition")?;
+        if condition {
+            Ok(WorkerOutput::next(Self::THEN))
+        } else {
+            Ok(WorkerOutput::next(Self::ELSE))
+        }
+    }
+}
@@ -1,21 +1,93 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 mod arg_worker;
-// mod create_bucket_worker;
-// mod has_role_worker;
-// mod mint_bucket_worker;
-// mod sender_worker;
-// mod start_worker;
-// mod store_bucket_worker;
-// mod text_worker;
+mod call_function_worker;
 mod call_method_worker;
+mod compare_worker;
+mod constant_worker;
+mod emit_event_worker;
+mod if_worker;
+mod repeat_worker;
+mod return_worker;
+mod start_worker;
+mod vault_deposit_worker;
+mod vault_withdraw_worker;
 
 pub use arg_worker::ArgWorker;
-// pub use create_bucket_worker::CreateBucketWorker;
-// pub use has_role_worker::HasRoleWorker;
-// pub use mint_bucket_worker::MintBucketWorker;
-// pub use sender_worker::SenderWorker;
-// pub use start_worker::StartWorker;
-// pub use store_bucket_worker::StoreBucketWorker;
-// pub use text_worker::TextWorker;
+pub use call_function_worker::CallFunctionWorker;
 pub use call_method_worker::CallMethodWorker;
+pub use compare_worker::CompareWorker;
+pub use constant_worker::ConstantWorker;
+pub use emit_event_worker::EmitEventWorker;
+pub use if_worker::IfWorker;
+pub use repeat_worker::RepeatWorker;
+pub use return_worker::ReturnWorker;
+pub use start_worker::StartWorker;
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+pub use vault_deposit_worker::VaultDepositWorker;
+pub use vault_withdraw_worker::VaultWithdrawWorker;
+
+use crate::{
+    flow::{FlowWorker, WorkerInfo, WorkerKind, EXEC_SOCKET},
+    template::LoadedTemplate,
+};
+
+const ACTION: WorkerInfo = WorkerInfo {
+    kind: WorkerKind::Action,
+    control_outputs: &[EXEC_SOCKET],
+};
+const DATA: WorkerInfo = WorkerInfo {
+    kind: WorkerKind::Data,
+    control_outputs: &[],
+};
+
+/// Returns how the node with the given worker name is executed, or None if the worker does not exist
+pub fn worker_info(name: &str) -> Option<WorkerInfo> {
+    let info = match name {
+        StartWorker::NAME => WorkerInfo {
+            kind: WorkerKind::Start,
+            control_outputs: &[EXEC_SOCKET],
+        },
+        IfWorker::NAME => WorkerInfo {
+            kind: WorkerKind::Action,
+            control_outputs: &[IfWorker::THEN, IfWorker::ELSE],
+        },
+        RepeatWorker::NAME => WorkerInfo {
+            kind: WorkerKind::Action,
+            control_outputs: &[RepeatWorker::BODY, RepeatWorker::DONE],
+        },
+        ArgWorker::NAME | ConstantWorker::NAME | CompareWorker::NAME => DATA,
+        CallMethodWorker::NAME |
+        CallFunctionWorker::NAME |
+        VaultWithdrawWorker::NAME |
+        VaultDepositWorker::NAME |
+        EmitEventWorker::NAME => ACTION,
+        ReturnWorker::NAME => WorkerInfo {
+            kind: WorkerKind::Action,
+            control_outputs: &[],
+        },
+        _ => return None,
+    };
+    Some(info)
+}
+
+pub fn get_worker<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
+    name: &str,
+) -> Option<Box<dyn FlowWorker<TTemplateProvider>>> {
+    let worker: Box<dyn FlowWorker<TTemplateProvider>> = match name {
+        StartWorker::NAME => Box::new(StartWorker {}),
+        ArgWorker::NAME => Box::new(ArgWorker {}),
+        ConstantWorker::NAME => Box::new(ConstantWorker {}),
+        CompareWorker::NAME => Box::new(CompareWorker {}),
+        IfWorker::NAME => Box::new(IfWorker {}),
+        RepeatWorker::NAME => Box::new(RepeatWorker {}),
+        CallMethodWorker::NAME => Box::new(CallMethodWorker {}),
+        CallFunctionWorker::NAME => Box::new(CallFunctionWorker {}),
+        VaultWithdrawWorker::NAME => Box::new(VaultWithdrawWorker {}),
+        VaultDepositWorker::NAME => Box::new(VaultDepositWorker {}),
+        EmitEventWorker::NAME => Box::new(EmitEventWorker {}),
+        ReturnWorker::NAME => Box::new(ReturnWorker {}),
+        _ => return None,
+    };
+    Some(worker)
+}
@@ -0,0 +1,32 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+
+use crate::{
+    flow::{FlowContext, FlowControl, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Executes the nodes connected to the `body` output `count` times, then continues with the `done` output. The
+/// current iteration is available on the `index` output.
+pub struct RepeatWorker {}
+
+impl RepeatWorker {
+    pub const BODY: &'static str = "body";
+    pub const DONE: &'static str = "done";
+    pub const INDEX: &'static str = "index";
+    pub const NAME: &'static str = "core::repeat";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for RepeatWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let iterations: u64 = inputs.require_as("count")?;
+        Ok(WorkerOutput::next(Self::DONE).with_control(FlowControl::Repeat { iterations }))
+    }
+}
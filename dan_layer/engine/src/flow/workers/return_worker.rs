@@ -0,0 +1,28 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+
+use crate::{
+    flow::{FlowContext, FlowControl, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Stops the flow and returns the `value` input to the caller
+pub struct ReturnWorker {}
+
+impl ReturnWorker {
+    pub const NAME: &'static str = "tari::return";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for ReturnWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let value = inputs.require("value")?;
+        Ok(WorkerOutput::next(crate::flow::EXEC_SOCKET).with_control(FlowControl::Return(value)))
+    }
+}
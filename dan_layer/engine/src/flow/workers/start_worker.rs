@@ -1,22 +1,26 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use d3ne::{InputData, Node, OutputData, OutputDataBuilder, Worker};
+use tari_dan_common_types::services::template_provider::TemplateProvider;
 
-pub struct StartWorker {}
+use crate::{
+    flow::{FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput, EXEC_SOCKET},
+    template::LoadedTemplate,
+};
 
-impl Worker for StartWorker {
-    // fn call(&self, _node: Node, _inputs: InputData) -> OutputData {
-    //     let mut map = HashMap::new();
-    //     map.insert("default".to_string(), Ok(IOData { data: Box::new(()) }));
-    //     Rc::new(map)
-    // }
+pub struct StartWorker {}
 
-    fn name(&self) -> &str {
-        "core::start"
-    }
+impl StartWorker {
+    pub const NAME: &'static str = "core::start";
+}
 
-    fn work(&self, _node: &Node, _input_data: InputData) -> anyhow::Result<OutputData> {
-        Ok(OutputDataBuilder::new().data("default", Box::new(())).build())
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider> for StartWorker {
+    fn work(
+        &self,
+        _context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        _inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        Ok(WorkerOutput::next(EXEC_SOCKET))
     }
 }
@@ -0,0 +1,50 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_lib::{
+    args::VaultAction,
+    invoke_args,
+    models::{BucketId, VaultRef},
+};
+
+use crate::{
+    flow::{
+        workers::vault_withdraw_worker::require_vault_id,
+        FlowContext,
+        FlowInputs,
+        FlowNode,
+        FlowWorker,
+        WorkerOutput,
+    },
+    template::LoadedTemplate,
+};
+
+/// Deposits the `bucket` input into the `vault`
+pub struct VaultDepositWorker {}
+
+impl VaultDepositWorker {
+    pub const NAME: &'static str = "tari::vault::deposit";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider>
+    for VaultDepositWorker
+{
+    fn work(
+        &self,
+        context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let vault_id = require_vault_id(inputs)?;
+        let bucket_id: BucketId = inputs.require_as("bucket")?;
+
+        context.runtime.interface().vault_invoke(
+            VaultRef::Ref(vault_id),
+            VaultAction::Deposit,
+            invoke_args![bucket_id].into(),
+        )?;
+
+        Ok(WorkerOutput::next(crate::flow::EXEC_SOCKET))
+    }
+}
@@ -0,0 +1,50 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::services::template_provider::TemplateProvider;
+use tari_template_lib::{
+    args::{VaultAction, VaultWithdrawArg},
+    invoke_args,
+    models::{Amount, VaultId, VaultRef},
+};
+
+use crate::{
+    flow::{coerce_value, FlowContext, FlowInputs, FlowNode, FlowWorker, WorkerOutput},
+    template::LoadedTemplate,
+};
+
+/// Withdraws `amount` from the fungible `vault` and outputs the resulting bucket
+pub struct VaultWithdrawWorker {}
+
+impl VaultWithdrawWorker {
+    pub const NAME: &'static str = "tari::vault::withdraw";
+}
+
+impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> FlowWorker<TTemplateProvider>
+    for VaultWithdrawWorker
+{
+    fn work(
+        &self,
+        context: &FlowContext<TTemplateProvider>,
+        _node: &FlowNode,
+        inputs: &FlowInputs<'_>,
+    ) -> Result<WorkerOutput, anyhow::Error> {
+        let vault_id = require_vault_id(inputs)?;
+        let amount: Amount = inputs.require_as("amount")?;
+
+        let result = context.runtime.interface().vault_invoke(
+            VaultRef::Ref(vault_id),
+            VaultAction::Withdraw,
+            invoke_args![VaultWithdrawArg::Fungible { amount }].into(),
+        )?;
+
+        Ok(WorkerOutput::value(result.into_value()?))
+    }
+}
+
+pub(super) fn require_vault_id(inputs: &FlowInputs<'_>) -> Result<VaultId, anyhow::Error> {
+    let vault = coerce_value(inputs.require("vault")?, &tari_template_abi::Type::Other {
+        name: "VaultId".to_string(),
+    })?;
+    Ok(tari_bor::from_value(&vault)?)
+}
@@ -1,13 +1,18 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsValue;
 
-use crate::function_definitions::FunctionArgDefinition;
+use crate::{
+    flow::FlowDefinition,
+    function_definitions::{ArgType, FunctionArgDefinition},
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FlowFunctionDefinition {
     pub name: String,
     pub args: Vec<FunctionArgDefinition>,
-    pub flow: JsValue,
+    /// The type of the value returned by a `tari::return` node. Flows that do not declare an output return unit.
+    #[serde(default)]
+    pub output: Option<ArgType>,
+    pub flow: FlowDefinition,
 }
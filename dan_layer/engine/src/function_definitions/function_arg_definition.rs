@@ -16,6 +16,13 @@ pub struct FunctionArgDefinition {
 pub enum ArgType {
     String,
     Bytes,
+    Bool,
+    U64,
+    I64,
+    Amount,
+    ComponentAddress,
+    ResourceAddress,
+    Bucket,
 }
 
 impl ArgType {
@@ -25,6 +32,21 @@ impl ArgType {
             ArgType::Bytes => Type::Other {
                 name: "Bytes".to_string(),
             },
+            ArgType::Bool => Type::Bool,
+            ArgType::U64 => Type::U64,
+            ArgType::I64 => Type::I64,
+            ArgType::Amount => Type::Other {
+                name: "Amount".to_string(),
+            },
+            ArgType::ComponentAddress => Type::Other {
+                name: "ComponentAddress".to_string(),
+            },
+            ArgType::ResourceAddress => Type::Other {
+                name: "ResourceAddress".to_string(),
+            },
+            ArgType::Bucket => Type::Other {
+                name: "Bucket".to_string(),
+            },
         }
     }
 }
@@ -0,0 +1,197 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use blake2::{
+    digest::{consts::U32, Digest},
+    Blake2b,
+};
+use sha2::Sha256;
+use sha3::Keccak256;
+use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_crypto::{commitment::HomomorphicCommitmentFactory, ristretto::RistrettoSchnorr, tari_utilities::ByteArray};
+use tari_engine_types::confidential::get_commitment_factory;
+use tari_template_lib::{
+    args::{CryptoVerifyMerkleInclusionArg, CryptoVerifySchnorrSignatureArg},
+    crypto::{HashAlgorithm, MerkleSide, PedersonCommitmentBytes},
+    Hash,
+};
+
+use crate::runtime::RuntimeError;
+
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// A cryptographic operation performed by the engine on behalf of a template. This is passed to runtime modules so that
+/// the operation can be charged for.
+#[derive(Debug, Clone, Copy)]
+pub enum CryptoOperation {
+    SignatureVerification,
+    Hash { num_bytes: usize },
+    CommitmentArithmetic { num_commitments: usize },
+    MerkleInclusion { num_bytes: usize, num_nodes: usize },
+}
+
+pub fn verify_schnorr_signature(arg: &CryptoVerifySchnorrSignatureArg) -> bool {
+    let Ok(public_key) = PublicKey::from_canonical_bytes(arg.public_key.as_bytes()) else {
+        return false;
+    };
+    let Ok(public_nonce) = PublicKey::from_canonical_bytes(arg.public_nonce.as_bytes()) else {
+        return false;
+    };
+    let Ok(signature) = PrivateKey::from_canonical_bytes(arg.signature.as_bytes()) else {
+        return false;
+    };
+
+    RistrettoSchnorr::new(public_nonce, signature).verify(&public_key, &arg.message)
+}
+
+pub fn hash(algorithm: HashAlgorithm, domain: &str, data: &[u8]) -> Hash {
+    domain_separated_hash(algorithm, domain, &[data])
+}
+
+pub fn add_commitments(commitments: &[PedersonCommitmentBytes]) -> Result<PedersonCommitmentBytes, RuntimeError> {
+    let sum = commitments.iter().try_fold(PublicKey::default(), |sum, commitment| {
+        Ok::<_, RuntimeError>(sum + &decode_commitment(commitment)?)
+    })?;
+    Ok(encode_commitment(&sum))
+}
+
+pub fn subtract_commitments(
+    lhs: &PedersonCommitmentBytes,
+    rhs: &PedersonCommitmentBytes,
+) -> Result<PedersonCommitmentBytes, RuntimeError> {
+    let diff = decode_commitment(lhs)? - &decode_commitment(rhs)?;
+    Ok(encode_commitment(&diff))
+}
+
+pub fn commit_value(value: u64) -> PedersonCommitmentBytes {
+    let commitment = get_commitment_factory().commit_value(&PrivateKey::default(), value);
+    encode_commitment(commitment.as_public_key())
+}
+
+pub fn verify_merkle_inclusion(arg: &CryptoVerifyMerkleInclusionArg) -> bool {
+    let leaf_hash = domain_separated_hash(arg.algorithm, &arg.domain, &[&[MERKLE_LEAF_PREFIX], &arg.leaf]);
+    let root = arg.proof.iter().fold(leaf_hash, |current, node| {
+        let (left, right) = match node.side {
+            MerkleSide::Left => (node.sibling, current),
+            MerkleSide::Right => (current, node.sibling),
+        };
+        domain_separated_hash(arg.algorithm, &arg.domain, &[
+            &[MERKLE_NODE_PREFIX],
+            left.as_slice(),
+            right.as_slice(),
+        ])
+    });
+    root == arg.root
+}
+
+fn domain_separated_hash(algorithm: HashAlgorithm, domain: &str, parts: &[&[u8]]) -> Hash {
+    match algorithm {
+        HashAlgorithm::Blake2b256 => digest_parts::<Blake2b<U32>>(domain, parts),
+        HashAlgorithm::Sha256 => digest_parts::<Sha256>(domain, parts),
+        HashAlgorithm::Keccak256 => digest_parts::<Keccak256>(domain, parts),
+    }
+}
+
+fn digest_parts<D: Digest>(domain: &str, parts: &[&[u8]]) -> Hash {
+    let mut hasher = D::new();
+    hasher.update((domain.len() as u64).to_le_bytes());
+    hasher.update(domain.as_bytes());
+    for part in parts {
+        hasher.update(part);
+    }
+    Hash::try_from(hasher.finalize().as_slice()).expect("All supported hash algorithms produce 32-byte digests")
+}
+
+fn decode_commitment(commitment: &PedersonCommitmentBytes) -> Result<PublicKey, RuntimeError> {
+    PublicKey::from_canonical_bytes(commitment.as_bytes()).map_err(|_| RuntimeError::InvalidArgument {
+        argument: "commitment",
+        reason: format!("{} is not a valid Pedersen commitment", commitment),
+    })
+}
+
+fn encode_commitment(commitment: &PublicKey) -> PedersonCommitmentBytes {
+    PedersonCommitmentBytes::from_bytes(commitment.as_bytes()).expect("Ristretto points are 32 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_crypto::keys::{PublicKey as _, SecretKey};
+    use tari_template_lib::crypto::{MerkleProofNode, RistrettoPublicKeyBytes, SchnorrSignatureBytes};
+
+    use super::*;
+
+    #[test]
+    fn it_verifies_schnorr_signatures() {
+        let mut rng = rand::rngs::OsRng;
+        let secret = PrivateKey::random(&mut rng);
+        let public_key = PublicKey::from_secret_key(&secret);
+        let signature = RistrettoSchnorr::sign(&secret, b"attestation", &mut rng).unwrap();
+
+        let mut arg = CryptoVerifySchnorrSignatureArg {
+            public_key: RistrettoPublicKeyBytes::from_bytes(public_key.as_bytes()).unwrap(),
+            public_nonce: RistrettoPublicKeyBytes::from_bytes(signature.get_public_nonce().as_bytes()).unwrap(),
+            signature: SchnorrSignatureBytes::from_bytes(signature.get_signature().as_bytes()).unwrap(),
+            message: b"attestation".to_vec(),
+        };
+        assert!(verify_schnorr_signature(&arg));
+
+        arg.message = b"forged".to_vec();
+        assert!(!verify_schnorr_signature(&arg));
+    }
+
+    #[test]
+    fn it_separates_hash_domains() {
+        for algo in [
+            HashAlgorithm::Blake2b256,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Keccak256,
+        ] {
+            assert_eq!(hash(algo, "a", b"data"), hash(algo, "a", b"data"));
+            assert_ne!(hash(algo, "a", b"data"), hash(algo, "b", b"data"));
+            // The domain length prefix prevents moving bytes between the domain and the data
+            assert_ne!(hash(algo, "ab", b"c"), hash(algo, "a", b"bc"));
+        }
+    }
+
+    #[test]
+    fn it_adds_and_subtracts_commitments() {
+        let a = commit_value(100);
+        let b = commit_value(30);
+        assert_eq!(add_commitments(&[a, b]).unwrap(), commit_value(130));
+        assert_eq!(subtract_commitments(&a, &b).unwrap(), commit_value(70));
+        assert_eq!(add_commitments(&[]).unwrap(), commit_value(0));
+    }
+
+    #[test]
+    fn it_verifies_merkle_inclusion() {
+        let algorithm = HashAlgorithm::Sha256;
+        let leaf = |data: &[u8]| domain_separated_hash(algorithm, "test", &[&[MERKLE_LEAF_PREFIX], data]);
+        let node = |l: Hash, r: Hash| {
+            domain_separated_hash(algorithm, "test", &[&[MERKLE_NODE_PREFIX], l.as_slice(), r.as_slice()])
+        };
+        let (a, b, c) = (leaf(b"a"), leaf(b"b"), leaf(b"c"));
+        let root = node(node(a, b), c);
+
+        let mut arg = CryptoVerifyMerkleInclusionArg {
+            algorithm,
+            domain: "test".to_string(),
+            root,
+            leaf: b"b".to_vec(),
+            proof: vec![
+                MerkleProofNode {
+                    sibling: a,
+                    side: MerkleSide::Left,
+                },
+                MerkleProofNode {
+                    sibling: c,
+                    side: MerkleSide::Right,
+                },
+            ],
+        };
+        assert!(verify_merkle_inclusion(&arg));
+
+        arg.leaf = b"c".to_vec();
+        assert!(!verify_merkle_inclusion(&arg));
+    }
+}
@@ -33,6 +33,15 @@ impl EngineArgs {
             })
     }
 
+    /// Returns the argument at the given index, or None if fewer arguments were provided
+    pub fn get_optional<T: DeserializeOwned>(&self, index: usize) -> Result<Option<T>, RuntimeError> {
+        if index < self.len() {
+            self.get(index).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn assert_one_arg<T: DeserializeOwned>(&self) -> Result<T, RuntimeError> {
         if self.len() == 1 {
             self.get(0)
@@ -54,6 +63,16 @@ impl EngineArgs {
         Ok(())
     }
 
+    pub fn assert_arg_count_between<T: DeserializeOwned>(&self, min: usize, max: usize) -> Result<(), RuntimeError> {
+        if self.len() < min || self.len() > max {
+            return Err(RuntimeError::InvalidArgument {
+                argument: type_name::<T>(),
+                reason: format!("Expected between {} and {} arguments but got {}", min, max, self.len()),
+            });
+        }
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         self.args.len()
     }
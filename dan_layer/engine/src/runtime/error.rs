@@ -37,16 +37,19 @@ use tari_engine_types::{
     transaction_receipt::TransactionReceiptAddress,
     virtual_substate::VirtualSubstateId,
 };
-use tari_template_lib::models::{
-    Amount,
-    BucketId,
-    ComponentAddress,
-    NonFungibleId,
-    ProofId,
-    ResourceAddress,
-    TemplateAddress,
-    UnclaimedConfidentialOutputAddress,
-    VaultId,
+use tari_template_lib::{
+    models::{
+        Amount,
+        BucketId,
+        ComponentAddress,
+        NonFungibleId,
+        ProofId,
+        ResourceAddress,
+        TemplateAddress,
+        UnclaimedConfidentialOutputAddress,
+        VaultId,
+    },
+    resource::TransferAction,
 };
 
 use super::workspace::WorkspaceError;
@@ -146,6 +149,8 @@ pub enum RuntimeError {
     TooManyEntities(#[from] EntityIdProviderError),
     #[error("Duplicate NFT token id: {token_id}")]
     DuplicateNonFungibleId { token_id: NonFungibleId },
+    #[error("Non-fungible id {token_id} was destroyed and cannot be minted again")]
+    DestroyedNonFungibleId { token_id: NonFungibleId },
     #[error("Access Denied: {action_ident}")]
     AccessDenied { action_ident: ActionIdent },
     #[error("Access Denied: attempt to set state on component {attempted_on} from another component {attempted_by}")]
@@ -222,6 +227,20 @@ pub enum RuntimeError {
     CallFrameRemainingOnStack { remaining: usize },
     #[error("Duplicate reference to substate {address}")]
     DuplicateReference { address: SubstateId },
+    #[error("Reference to destroyed substate {address}")]
+    DestroyedSubstateReferenced { address: SubstateId },
+    #[error("Cannot destroy vault {vault_id} because it is not empty")]
+    CannotDestroyNonEmptyVault { vault_id: VaultId },
+    #[error("Cannot destroy component {address} because it holds vault {vault_id}")]
+    CannotDestroyComponentWithVault {
+        address: ComponentAddress,
+        vault_id: VaultId,
+    },
+    #[error("Cannot destroy non-fungible {resource_address} id {nf_id} because it has not been burnt")]
+    CannotDestroyNonFungibleNotBurnt {
+        resource_address: ResourceAddress,
+        nf_id: NonFungibleId,
+    },
 
     #[error("BUG: [{function}] Invariant error {details}")]
     InvariantError { function: &'static str, details: String },
@@ -247,11 +266,33 @@ pub enum RuntimeError {
 
     #[error("Invalid event topic '{topic}': 'std' prefix is reserved for built-in events")]
     InvalidEventTopicStdPrefix { topic: String },
+    #[error("Event '{topic}' is not declared by template {template_address}")]
+    UndeclaredEvent {
+        template_address: TemplateAddress,
+        topic: String,
+    },
+    #[error("Invalid payload for event '{topic}': {details}")]
+    InvalidEventPayload { topic: String, details: String },
 
     #[error("Numeric conversion error: {details}")]
     NumericConversionError { details: String },
     #[error("Auth callback MUST return null, but it returned non-null")]
     UnexpectedNonNullInAuthHookReturn,
+    #[error("Transfer hook {hook} rejected {action} of resource {resource_address}: {details}")]
+    TransferHookRejected {
+        hook: String,
+        action: TransferAction,
+        resource_address: ResourceAddress,
+        details: String,
+    },
+    #[error("Transfer hook callback MUST return null, but it returned non-null")]
+    UnexpectedNonNullInTransferHookReturn,
+    #[error("Payment bucket {bucket_id} was not accepted by a {action} transfer hook of resource {resource_address}")]
+    TransferHookPaymentNotAccepted {
+        bucket_id: BucketId,
+        action: TransferAction,
+        resource_address: ResourceAddress,
+    },
 
     #[error("Assert error: {0}")]
     AssertError(#[from] AssertError),
@@ -328,9 +369,16 @@ pub enum TransactionCommitError {
     StateStoreError(#[from] StateStoreError),
     #[error(transparent)]
     IdProviderError(#[from] IdProviderError),
+    #[error("Vault {vault_id} was destroyed but is still referenced by component {component_address}")]
+    DestroyedVaultStillReferenced {
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+    },
     #[error("trying to mutate non fungible index of resource {resource_address} at index {index}")]
     NonFungibleIndexMutation {
         resource_address: ResourceAddress,
         index: u64,
     },
+    #[error("Input {substate_id} was declared read-only but was written to by the transaction")]
+    ReadOnlyInputWritten { substate_id: SubstateId },
 }
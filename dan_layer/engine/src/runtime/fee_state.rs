@@ -8,6 +8,7 @@ use tari_template_lib::models::{Amount, VaultId};
 pub struct FeeState {
     pub fee_payments: Vec<(ResourceContainer, VaultId)>,
     pub fee_charges: FeeBreakdown,
+    pub storage_rebate: u64,
 }
 
 impl FeeState {
@@ -15,8 +16,9 @@ impl FeeState {
         Self::default()
     }
 
+    /// Returns the total fees charged less any storage rebate
     pub fn total_charges(&self) -> u64 {
-        self.fee_charges.get_total()
+        self.fee_charges.get_total().saturating_sub(self.storage_rebate)
     }
 
     pub fn total_payments(&self) -> Amount {
@@ -34,7 +34,7 @@ use tari_engine_types::{
     entity_id_provider::EntityIdProvider,
     events::Event,
     hashing::hash_template_code,
-    indexed_value::IndexedValue,
+    indexed_value::{IndexedValue, IndexedWellKnownTypes},
     instruction_result::InstructionResult,
     lock::LockFlag,
     logs::LogEntry,
@@ -46,7 +46,7 @@ use tari_engine_types::{
     vn_fee_pool::ValidatorFeePoolAddress,
     TemplateAddress,
 };
-use tari_template_abi::{TemplateDef, Type};
+use tari_template_abi::{EventDef, TemplateDef, Type};
 use tari_template_builtin::{ACCOUNT_NFT_TEMPLATE_ADDRESS, ACCOUNT_TEMPLATE_ADDRESS};
 use tari_template_lib::{
     args,
@@ -65,6 +65,13 @@ use tari_template_lib::{
         ConsensusAction,
         CreateComponentArg,
         CreateResourceArg,
+        CryptoAction,
+        CryptoAddCommitmentsArg,
+        CryptoCommitValueArg,
+        CryptoHashArg,
+        CryptoSubtractCommitmentsArg,
+        CryptoVerifyMerkleInclusionArg,
+        CryptoVerifySchnorrSignatureArg,
         GenerateRandomAction,
         InvokeResult,
         LogLevel,
@@ -75,6 +82,7 @@ use tari_template_lib::{
         ProofRef,
         RecallResourceArg,
         ResourceAction,
+        ResourceDestroyBurntNonFungiblesArg,
         ResourceGetNonFungibleArg,
         ResourceRef,
         ResourceUpdateNonFungibleDataArg,
@@ -89,6 +97,7 @@ use tari_template_lib::{
     crypto::RistrettoPublicKeyBytes,
     models::{
         Amount,
+        Bucket,
         BucketId,
         ComponentAddress,
         EntityId,
@@ -101,18 +110,21 @@ use tari_template_lib::{
         VaultRef,
     },
     prelude::ResourceType,
+    resource::{TransferAction, TransferHook, TransferHookContext, MAX_DIVISIBILITY},
     template::BuiltinTemplate,
 };
 
 use super::{working_state::WorkingState, Runtime};
 use crate::{
     runtime::{
+        crypto,
         engine_args::EngineArgs,
         error::AssertError,
         locking::{LockError, LockedSubstate},
         scope::PushCallFrame,
         tracker::StateTracker,
         utils::to_ristretto_public_key_bytes,
+        CryptoOperation,
         RuntimeError,
         RuntimeInterface,
         RuntimeModule,
@@ -171,6 +183,13 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn invoke_modules_on_crypto_operation(&self, operation: CryptoOperation) -> Result<(), RuntimeError> {
+        for module in &self.modules {
+            module.on_crypto_operation(&self.tracker, operation)?;
+        }
+        Ok(())
+    }
+
     fn invoke_modules_on_before_finalize(&self) -> Result<(), RuntimeError> {
         for module in &self.modules {
             module.on_before_finalize(&self.tracker)?;
@@ -311,6 +330,88 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn invoke_resource_transfer_hook(
+        &self,
+        transfer_hook: TransferHook,
+        context: TransferHookContext,
+        payment: Option<BucketId>,
+    ) -> Result<(), RuntimeError> {
+        self.invoke_modules_on_runtime_call("invoke_resource_transfer_hook")?;
+        let (skip_hook, payment_amount) = self.tracker.read_with(|state| {
+            let payment_amount = payment
+                .map(|bucket_id| state.get_bucket(bucket_id).map(|b| b.amount()))
+                .transpose()?;
+            // As with auth hooks, transfer hooks are only executed if the resource is being transferred by an external
+            // component and the hook component exists
+            if state.current_component()? == Some(transfer_hook.component_address) {
+                return Ok::<_, RuntimeError>((true, payment_amount));
+            }
+            let exists = state.store().exists(&transfer_hook.component_address.into())?;
+            Ok((!exists, payment_amount))
+        })?;
+
+        if skip_hook {
+            // The payment would be left dangling, so we reject it rather than silently leaving it with the caller
+            if let Some(bucket_id) = payment {
+                return Err(RuntimeError::TransferHookPaymentNotAccepted {
+                    bucket_id,
+                    action: context.action,
+                    resource_address: context.resource_address,
+                });
+            }
+            return Ok(());
+        }
+
+        let action = context.action;
+        let resource_address = context.resource_address;
+        let payload = Metadata::from_iter([
+            ("hook", transfer_hook.to_string()),
+            ("action", action.to_string()),
+            ("vault_id", context.vault_id.to_string()),
+            ("resource_address", resource_address.to_string()),
+            ("amount", context.amount.to_string()),
+            (
+                "payment_amount",
+                payment_amount.map(|a| a.to_string()).unwrap_or_default(),
+            ),
+        ]);
+
+        // The signature of a transfer hook is (context: TransferHookContext, payment: Option<Bucket>)
+        let ret = self
+            .invoke_component_method(&transfer_hook.component_address, &transfer_hook.method, args![
+                context,
+                payment.map(Bucket::from_id)
+            ])
+            .map_err(|e| match e {
+                RuntimeError::CrossTemplateCallMethodError { details, .. } => RuntimeError::TransferHookRejected {
+                    hook: transfer_hook.to_string(),
+                    action,
+                    resource_address,
+                    details,
+                },
+                _ => e,
+            })?;
+        if !ret.indexed.value().is_null() {
+            return Err(RuntimeError::UnexpectedNonNullInTransferHookReturn);
+        }
+
+        self.tracker.write_with(|state| {
+            let tx_hash = self.entity_id_provider.transaction_hash();
+            let (&template_address, _) = state.current_template()?;
+            let event = Event::std(
+                Some(SubstateId::Resource(resource_address)),
+                template_address,
+                tx_hash,
+                "resource",
+                "transfer_hook",
+                payload,
+            );
+            debug!(target: LOG_TARGET, "Emitted transfer hook event {}", event);
+            state.push_event(event);
+            Ok(())
+        })
+    }
+
     fn invoke_component_method(
         &self,
         component_address: &ComponentAddress,
@@ -400,6 +501,54 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
 
         Ok(())
     }
+
+    fn check_resource_transfer_hook(&self, hook: &TransferHook) -> Result<(), RuntimeError> {
+        let template_address = self
+            .tracker
+            .write_with(|state| state.get_template_for_component(&hook.component_address))?;
+        let template = self.get_template_def(&template_address)?;
+        let func = template
+            .get_function(&hook.method)
+            .ok_or(RuntimeError::InvalidArgument {
+                argument: "CreateResourceArg",
+                reason: format!("Transfer hook '{}' not found", hook),
+            })?;
+
+        if !matches!(func.output, Type::Unit) {
+            return Err(RuntimeError::InvalidArgument {
+                argument: "CreateResourceArg",
+                reason: format!("Transfer hook '{}' must return unit", hook),
+            });
+        }
+
+        if func.arguments.len() != 3 {
+            return Err(RuntimeError::InvalidArgument {
+                argument: "CreateResourceArg",
+                reason: format!(
+                    "Transfer hook '{}' must take 3 arguments (incl &self), but found {}",
+                    hook,
+                    func.arguments.len()
+                ),
+            });
+        }
+
+        if !matches!(func.arguments[1].arg_type.other(), Some("TransferHookContext")) {
+            return Err(RuntimeError::InvalidArgument {
+                argument: "CreateResourceArg",
+                reason: format!("Transfer hook '{}' must take a TransferHookContext as argument 1", hook),
+            });
+        }
+
+        // The ABI only records the outer type name, so we can only check that this is an Option
+        if !matches!(func.arguments[2].arg_type.other(), Some("Option")) {
+            return Err(RuntimeError::InvalidArgument {
+                argument: "CreateResourceArg",
+                reason: format!("Transfer hook '{}' must take an Option<Bucket> as argument 2", hook),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInterface
@@ -435,6 +584,35 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn emit_typed_event(&self, topic: String, payload: tari_bor::Value) -> Result<(), RuntimeError> {
+        self.invoke_modules_on_runtime_call("emit_typed_event")?;
+
+        let template_address = self.tracker.get_template_address()?;
+        let template_def = self.get_template_def(&template_address)?;
+        let event_def = template_def
+            .get_event(&topic)
+            .ok_or_else(|| RuntimeError::UndeclaredEvent {
+                template_address,
+                topic: topic.clone(),
+            })?;
+        validate_event_payload(event_def, &payload)?;
+
+        let substate_id = self.tracker.read_with(|state| {
+            Ok::<_, RuntimeError>(
+                state
+                    .current_call_scope()?
+                    .get_current_component_lock()
+                    .and_then(|l| l.address().as_component_address())
+                    .map(SubstateId::Component),
+            )
+        })?;
+        let tx_hash = self.entity_id_provider.transaction_hash();
+
+        self.tracker
+            .add_event(Event::new_typed(substate_id, template_address, tx_hash, topic, payload));
+        Ok(())
+    }
+
     fn emit_log(&self, level: LogLevel, message: String) -> Result<(), RuntimeError> {
         self.invoke_modules_on_runtime_call("emit_log")?;
 
@@ -506,6 +684,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                 let allocation = state.new_address_allocation(address)?;
                 Ok(InvokeResult::encode(&allocation)?)
             }),
+            CallerContextAction::IsTopLevelCall => self.tracker.read_with(|state| {
+                args.assert_no_args("CallerContextAction::IsTopLevelCall")?;
+                // The first call frame is pushed for the instruction itself, any deeper frames are cross-template
+                // calls
+                Ok(InvokeResult::encode(&(state.call_frame_depth() == 1))?)
+            }),
         }
     }
 
@@ -638,6 +822,11 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         });
                     }
 
+                    // The component was destroyed during this call, so there is no state to set
+                    if state.store().is_destroyed(component_lock.address()) {
+                        return Ok(InvokeResult::unit());
+                    }
+
                     state.modify_component_with(&component_lock, |component| {
                         if component_state == *component.state() {
                             return false;
@@ -719,6 +908,54 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::encode(&component.template_address)?)
                 })
             },
+            ComponentAction::Destroy => {
+                let component_address =
+                    component_ref
+                        .as_component_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "component_ref",
+                            reason: "Destroy component action requires a component address".to_string(),
+                        })?;
+
+                args.assert_no_args("ComponentAction::Destroy")?;
+
+                self.tracker.write_with(|state| {
+                    let component_lock = state
+                        .current_call_scope()?
+                        .get_current_component_lock()
+                        .cloned()
+                        .ok_or(RuntimeError::NotInComponentContext {
+                            action: ComponentAction::Destroy.into(),
+                        })?;
+                    // Components can only be destroyed from within their own context
+                    if *component_lock.address() != component_address {
+                        return Err(RuntimeError::LockError(LockError::SubstateNotLocked {
+                            address: SubstateId::Component(component_address),
+                        }));
+                    }
+                    let component = state.get_component(&component_lock)?;
+                    state
+                        .authorization()
+                        .require_ownership(ComponentAction::Destroy, component.as_ownership())?;
+
+                    // Any vaults held by the component must already be destroyed
+                    let indexed = IndexedWellKnownTypes::from_value(component.state())?;
+                    if let Some(vault_id) = indexed
+                        .vault_ids()
+                        .iter()
+                        .find(|vault_id| !state.store().is_destroyed(&(**vault_id).into()))
+                    {
+                        return Err(RuntimeError::CannotDestroyComponentWithVault {
+                            address: component_address,
+                            vault_id: *vault_id,
+                        });
+                    }
+
+                    state.destroy_substate(&component_lock)?;
+
+                    Ok(InvokeResult::unit())
+                })
+            },
         }
     }
 
@@ -761,6 +998,23 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     });
                 }
 
+                if arg.resource_type.is_non_fungible() && arg.divisibility != 0 {
+                    return Err(RuntimeError::InvalidArgument {
+                        argument: "CreateResourceArg",
+                        reason: "Divisibility cannot be set for non-fungible resources".to_string(),
+                    });
+                }
+
+                if arg.divisibility > MAX_DIVISIBILITY {
+                    return Err(RuntimeError::InvalidArgument {
+                        argument: "CreateResourceArg",
+                        reason: format!(
+                            "Divisibility {} exceeds the maximum of {}",
+                            arg.divisibility, MAX_DIVISIBILITY
+                        ),
+                    });
+                }
+
                 let owner_key = match &arg.owner_rule {
                     OwnerRule::OwnedBySigner => {
                         Some(to_ristretto_public_key_bytes(&self.transaction_signer_public_key))
@@ -782,6 +1036,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                 if let Some(hook) = arg.authorize_hook.as_ref() {
                     self.check_resource_auth_hook(hook)?;
                 }
+                for hook in [&arg.transfer_hooks.on_deposit, &arg.transfer_hooks.on_withdraw]
+                    .into_iter()
+                    .flatten()
+                {
+                    self.check_resource_transfer_hook(hook)?;
+                }
 
                 self.tracker.write_with(|state| {
                     let resource = Resource::new(
@@ -792,6 +1052,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         arg.metadata,
                         maybe_view_key,
                         arg.authorize_hook,
+                        arg.divisibility,
+                        arg.transfer_hooks,
                     );
 
                     let resource_address = state.id_provider()?.new_resource_address()?;
@@ -849,6 +1111,25 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::encode(&resource_type)?)
                 })
             },
+            ResourceAction::GetDivisibility => {
+                let resource_address =
+                    resource_ref
+                        .as_resource_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "resource_ref",
+                            reason: "GetDivisibility resource action requires a resource address".to_string(),
+                        })?;
+
+                args.assert_no_args("ResourceAction::GetDivisibility")?;
+
+                self.tracker.write_with(|state| {
+                    let locked = state.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
+                    let resource = state.get_resource(&locked)?;
+                    let divisibility = resource.divisibility();
+                    state.unlock_substate(locked)?;
+                    Ok(InvokeResult::encode(&divisibility)?)
+                })
+            },
             ResourceAction::Mint => {
                 let resource_address =
                     resource_ref
@@ -1060,6 +1341,45 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::unit())
                 })
             },
+            ResourceAction::DestroyBurntNonFungibles => {
+                let resource_address =
+                    resource_ref
+                        .as_resource_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "resource_ref",
+                            reason: "DestroyBurntNonFungibles resource action requires a resource address".to_string(),
+                        })?;
+                let arg: ResourceDestroyBurntNonFungiblesArg = args.assert_one_arg()?;
+
+                self.tracker.write_with(|state| {
+                    let resource_lock =
+                        state.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Write)?;
+                    let resource = state.get_resource(&resource_lock)?;
+                    state
+                        .authorization()
+                        .require_ownership(ResourceAuthAction::Burn, resource.as_ownership())?;
+
+                    for nf_id in arg.ids {
+                        let addr = SubstateId::NonFungible(NonFungibleAddress::new(resource_address, nf_id.clone()));
+                        let nft_lock = state.lock_substate(&addr, LockFlag::Write)?;
+                        if !state.get_non_fungible(&nft_lock)?.is_burnt() {
+                            return Err(RuntimeError::CannotDestroyNonFungibleNotBurnt {
+                                resource_address,
+                                nf_id,
+                            });
+                        }
+                        state.destroy_substate(&nft_lock)?;
+                        state.unlock_substate(nft_lock)?;
+                        // Keep a tombstone so that the ID cannot be minted again
+                        state
+                            .get_resource_mut(&resource_lock)?
+                            .add_destroyed_non_fungible_id(nf_id);
+                    }
+
+                    state.unlock_substate(resource_lock)?;
+                    Ok(InvokeResult::unit())
+                })
+            },
         }
     }
 
@@ -1141,16 +1461,19 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     reason: "Put vault action requires a vault id".to_string(),
                 })?;
 
-                let bucket_id: BucketId = args.assert_one_arg()?;
+                args.assert_arg_count_between::<BucketId>(1, 2)?;
+                let bucket_id: BucketId = args.get(0)?;
+                // An optional payment bucket that is passed to the deposit hook
+                let maybe_payment: Option<BucketId> = args.get_optional(1)?;
 
-                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller, maybe_transfer_hook) =
                     self.tracker.write_with(|state_mut| {
                         let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
 
-                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+                        let resource_address = *state_mut.get_vault(&vault_lock)?.resource_address();
 
                         let resource_lock =
-                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Read)?;
+                            state_mut.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
 
                         let resource = state_mut.get_resource(&resource_lock)?;
 
@@ -1160,14 +1483,49 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                             resource.access_rules(),
                         )?;
 
+                        let maybe_auth_hook = resource.auth_hook().cloned();
+                        let maybe_transfer_hook = resource.transfer_hooks().on_deposit.clone();
+                        if let Some(bucket_id) = maybe_payment.filter(|_| maybe_transfer_hook.is_none()) {
+                            return Err(RuntimeError::TransferHookPaymentNotAccepted {
+                                bucket_id,
+                                action: TransferAction::Deposit,
+                                resource_address,
+                            });
+                        }
                         let auth_caller = state_mut.get_auth_caller()?;
-                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+
+                        let maybe_transfer_hook = maybe_transfer_hook
+                            .map(|hook| {
+                                let bucket = state_mut.get_bucket(bucket_id)?;
+                                let context = TransferHookContext {
+                                    action: TransferAction::Deposit,
+                                    resource_address,
+                                    vault_id,
+                                    amount: bucket.amount(),
+                                    non_fungible_ids: bucket.non_fungible_ids().iter().cloned().collect(),
+                                    caller_component: auth_caller.component().copied(),
+                                };
+                                Ok::<_, RuntimeError>((hook, context))
+                            })
+                            .transpose()?;
+
+                        Ok::<_, RuntimeError>((
+                            vault_lock,
+                            resource_lock,
+                            maybe_auth_hook,
+                            auth_caller,
+                            maybe_transfer_hook,
+                        ))
                     })?;
 
                 if let Some(auth_hook) = maybe_auth_hook {
                     self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Deposit)?;
                 }
 
+                if let Some((transfer_hook, context)) = maybe_transfer_hook {
+                    self.invoke_resource_transfer_hook(transfer_hook, context, maybe_payment)?;
+                }
+
                 self.tracker.write_with(move |state_mut| {
                     let bucket = state_mut.take_bucket(bucket_id)?;
                     // It is invalid to deposit a bucket that has locked funds
@@ -1202,16 +1560,19 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     argument: "vault_ref",
                     reason: "Withdraw vault action requires a vault id".to_string(),
                 })?;
-                let arg: VaultWithdrawArg = args.assert_one_arg()?;
+                args.assert_arg_count_between::<VaultWithdrawArg>(1, 2)?;
+                let arg: VaultWithdrawArg = args.get(0)?;
+                // An optional payment bucket that is passed to the withdraw hook
+                let maybe_payment: Option<BucketId> = args.get_optional(1)?;
 
-                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller, maybe_transfer_hook) =
                     self.tracker.write_with(|state_mut| {
                         let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
 
-                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+                        let resource_address = *state_mut.get_vault(&vault_lock)?.resource_address();
 
                         let resource_lock =
-                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Read)?;
+                            state_mut.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
 
                         let resource = state_mut.get_resource(&resource_lock)?;
 
@@ -1221,14 +1582,58 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                             resource.access_rules(),
                         )?;
 
+                        let maybe_auth_hook = resource.auth_hook().cloned();
+                        let maybe_transfer_hook = resource.transfer_hooks().on_withdraw.clone();
+                        if let Some(bucket_id) = maybe_payment.filter(|_| maybe_transfer_hook.is_none()) {
+                            return Err(RuntimeError::TransferHookPaymentNotAccepted {
+                                bucket_id,
+                                action: TransferAction::Withdraw,
+                                resource_address,
+                            });
+                        }
                         let auth_caller = state_mut.get_auth_caller()?;
-                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+
+                        let maybe_transfer_hook = maybe_transfer_hook.map(|hook| {
+                            let (amount, non_fungible_ids) = match &arg {
+                                VaultWithdrawArg::Fungible { amount } => (*amount, vec![]),
+                                VaultWithdrawArg::NonFungible { ids } => {
+                                    let amount = Amount::try_from(ids.len()).map_err(|_| {
+                                        RuntimeError::NumericConversionError {
+                                            details: "Could not convert to i64".to_owned(),
+                                        }
+                                    })?;
+                                    (amount, ids.iter().cloned().collect())
+                                },
+                                VaultWithdrawArg::Confidential { proof } => (proof.revealed_input_amount(), vec![]),
+                            };
+                            let context = TransferHookContext {
+                                action: TransferAction::Withdraw,
+                                resource_address,
+                                vault_id,
+                                amount,
+                                non_fungible_ids,
+                                caller_component: auth_caller.component().copied(),
+                            };
+                            Ok::<_, RuntimeError>((hook, context))
+                        });
+
+                        Ok::<_, RuntimeError>((
+                            vault_lock,
+                            resource_lock,
+                            maybe_auth_hook,
+                            auth_caller,
+                            maybe_transfer_hook.transpose()?,
+                        ))
                     })?;
 
                 if let Some(auth_hook) = maybe_auth_hook {
                     self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Withdraw)?;
                 }
 
+                if let Some((transfer_hook, context)) = maybe_transfer_hook {
+                    self.invoke_resource_transfer_hook(transfer_hook, context, maybe_payment)?;
+                }
+
                 self.tracker.write_with(|state| {
                     let resource = state.get_resource(&resource_lock)?;
                     let maybe_view_key = resource.view_key().cloned();
@@ -1612,6 +2017,20 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(result)
                 })
             },
+            VaultAction::Destroy => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "Destroy vault action requires a vault id".to_string(),
+                })?;
+                args.assert_no_args("Vault::Destroy")?;
+
+                self.tracker.write_with(|state| {
+                    let vault_lock = state.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
+                    state.destroy_vault(&vault_lock)?;
+                    state.unlock_substate(vault_lock)?;
+                    Ok(InvokeResult::unit())
+                })
+            },
         }
     }
 
@@ -2127,6 +2546,55 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         }
     }
 
+    fn crypto_invoke(&self, action: CryptoAction, args: EngineArgs) -> Result<InvokeResult, RuntimeError> {
+        self.invoke_modules_on_runtime_call("crypto_invoke")?;
+        debug!(target: LOG_TARGET, "Crypto invoke: {:?}", action);
+
+        match action {
+            CryptoAction::VerifySchnorrSignature => {
+                let arg: CryptoVerifySchnorrSignatureArg = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::SignatureVerification)?;
+                Ok(InvokeResult::encode(&crypto::verify_schnorr_signature(&arg))?)
+            },
+            CryptoAction::Hash => {
+                let CryptoHashArg {
+                    algorithm,
+                    domain,
+                    data,
+                } = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::Hash {
+                    num_bytes: domain.len() + data.len(),
+                })?;
+                Ok(InvokeResult::encode(&crypto::hash(algorithm, &domain, &data))?)
+            },
+            CryptoAction::AddCommitments => {
+                let CryptoAddCommitmentsArg { commitments } = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::CommitmentArithmetic {
+                    num_commitments: commitments.len(),
+                })?;
+                Ok(InvokeResult::encode(&crypto::add_commitments(&commitments)?)?)
+            },
+            CryptoAction::SubtractCommitments => {
+                let CryptoSubtractCommitmentsArg { lhs, rhs } = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::CommitmentArithmetic { num_commitments: 2 })?;
+                Ok(InvokeResult::encode(&crypto::subtract_commitments(&lhs, &rhs)?)?)
+            },
+            CryptoAction::CommitValue => {
+                let CryptoCommitValueArg { value } = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::CommitmentArithmetic { num_commitments: 1 })?;
+                Ok(InvokeResult::encode(&crypto::commit_value(value))?)
+            },
+            CryptoAction::VerifyMerkleInclusion => {
+                let arg: CryptoVerifyMerkleInclusionArg = args.assert_one_arg()?;
+                self.invoke_modules_on_crypto_operation(CryptoOperation::MerkleInclusion {
+                    num_bytes: arg.domain.len() + arg.leaf.len(),
+                    num_nodes: arg.proof.len(),
+                })?;
+                Ok(InvokeResult::encode(&crypto::verify_merkle_inclusion(&arg))?)
+            },
+        }
+    }
+
     fn call_invoke(&self, action: CallAction, args: EngineArgs) -> Result<InvokeResult, RuntimeError> {
         self.invoke_modules_on_runtime_call("call_invoke")?;
         debug!(
@@ -2168,6 +2636,20 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         })
     }
 
+    fn workspace_allocate_namespace(&self, template_address: &TemplateAddress) -> Result<String, RuntimeError> {
+        self.invoke_modules_on_runtime_call("workspace_allocate_namespace")?;
+        Ok(self
+            .tracker
+            .with_workspace_mut(|workspace| workspace.allocate_namespace(template_address)))
+    }
+
+    fn workspace_drop_namespace(&self, namespace: &str) -> Result<(), RuntimeError> {
+        self.invoke_modules_on_runtime_call("workspace_drop_namespace")?;
+        self.tracker
+            .with_workspace_mut(|workspace| workspace.drop_namespace(namespace));
+        Ok(())
+    }
+
     fn set_last_instruction_output(&self, value: IndexedValue) -> Result<(), RuntimeError> {
         self.invoke_modules_on_runtime_call("set_last_instruction_output")?;
         self.tracker.write_with(|state| {
@@ -2369,3 +2851,89 @@ fn validate_component_access_rule_methods(
     }
     Ok(())
 }
+
+/// Checks that the payload is a map containing exactly the fields declared for the event and that each field value
+/// matches its declared type
+fn validate_event_payload(event_def: &EventDef, payload: &tari_bor::Value) -> Result<(), RuntimeError> {
+    let invalid = |details: String| RuntimeError::InvalidEventPayload {
+        topic: event_def.topic.clone(),
+        details,
+    };
+
+    let entries = payload
+        .as_map()
+        .ok_or_else(|| invalid("payload is not a map".to_string()))?;
+    let mut fields = entries
+        .iter()
+        .map(|(k, v)| {
+            k.as_text()
+                .map(|k| (k, v))
+                .ok_or_else(|| invalid("payload key is not a string".to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    fields.sort_unstable_by_key(|(name, _)| *name);
+
+    let mut expected = event_def.fields.iter().collect::<Vec<_>>();
+    expected.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    if fields
+        .iter()
+        .map(|(name, _)| *name)
+        .ne(expected.iter().map(|f| f.name.as_str()))
+    {
+        return Err(invalid(format!(
+            "expected fields [{}] but got [{}]",
+            expected.iter().map(|f| f.name.as_str()).collect::<Vec<_>>().join(", "),
+            fields.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    for ((name, value), field) in fields.into_iter().zip(expected) {
+        if !is_value_of_type(&field.arg_type, value) {
+            return Err(invalid(format!(
+                "field '{}' does not match the declared type {}",
+                name, field.arg_type
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the value is an encoding of the given type. Other types (e.g. Amount, Option or other structs) are
+/// not described in enough detail by the ABI to be checked, so any value is accepted.
+fn is_value_of_type(ty: &Type, value: &tari_bor::Value) -> bool {
+    use tari_bor::Value;
+
+    fn is_integer<T: TryFrom<i128>>(value: &Value) -> bool {
+        value.as_integer().map_or(false, |i| T::try_from(i128::from(i)).is_ok())
+    }
+
+    match ty {
+        Type::Unit => value.is_null(),
+        Type::Bool => value.is_bool(),
+        Type::I8 => is_integer::<i8>(value),
+        Type::I16 => is_integer::<i16>(value),
+        Type::I32 => is_integer::<i32>(value),
+        Type::I64 => is_integer::<i64>(value),
+        Type::U8 => is_integer::<u8>(value),
+        Type::U16 => is_integer::<u16>(value),
+        Type::U32 => is_integer::<u32>(value),
+        Type::U64 => is_integer::<u64>(value),
+        // Values outside of the range of a CBOR integer are encoded as tagged big numbers
+        Type::I128 | Type::U128 => value.is_integer() || value.is_tag(),
+        Type::String => value.is_text(),
+        Type::Vec(item_ty) => match value {
+            Value::Array(items) => items.iter().all(|item| is_value_of_type(item_ty, item)),
+            Value::Bytes(_) => **item_ty == Type::U8,
+            _ => false,
+        },
+        Type::Tuple(item_tys) => value.as_array().map_or(false, |items| {
+            items.len() == item_tys.len() &&
+                items
+                    .iter()
+                    .zip(item_tys)
+                    .all(|(item, item_ty)| is_value_of_type(item_ty, item))
+        }),
+        Type::Other { .. } => true,
+    }
+}
@@ -38,6 +38,9 @@ pub use actions::*;
 mod module;
 pub use module::{RuntimeModule, RuntimeModuleError};
 
+mod crypto;
+pub use crypto::CryptoOperation;
+
 mod fee_state;
 mod tracker;
 
@@ -62,6 +65,7 @@ use tari_engine_types::{
     lock::LockFlag,
     substate::SubstateValue,
     vn_fee_pool::ValidatorFeePoolAddress,
+    TemplateAddress,
 };
 use tari_template_lib::{
     args::{
@@ -74,6 +78,7 @@ use tari_template_lib::{
         ComponentAction,
         ComponentRef,
         ConsensusAction,
+        CryptoAction,
         GenerateRandomAction,
         InvokeResult,
         LogLevel,
@@ -96,6 +101,8 @@ pub trait RuntimeInterface: Send + Sync {
     fn next_entity_id(&self) -> Result<EntityId, RuntimeError>;
     fn emit_event(&self, topic: String, payload: Metadata) -> Result<(), RuntimeError>;
 
+    fn emit_typed_event(&self, topic: String, payload: tari_bor::Value) -> Result<(), RuntimeError>;
+
     fn emit_log(&self, level: LogLevel, message: String) -> Result<(), RuntimeError>;
 
     fn load_component(&self, address: &ComponentAddress) -> Result<ComponentHeader, RuntimeError>;
@@ -138,6 +145,8 @@ pub trait RuntimeInterface: Send + Sync {
         args: EngineArgs,
     ) -> Result<InvokeResult, RuntimeError>;
     fn workspace_invoke(&self, action: WorkspaceAction, args: EngineArgs) -> Result<InvokeResult, RuntimeError>;
+    fn workspace_allocate_namespace(&self, template_address: &TemplateAddress) -> Result<String, RuntimeError>;
+    fn workspace_drop_namespace(&self, namespace: &str) -> Result<(), RuntimeError>;
 
     fn non_fungible_invoke(
         &self,
@@ -150,6 +159,8 @@ pub trait RuntimeInterface: Send + Sync {
 
     fn generate_random_invoke(&self, action: GenerateRandomAction) -> Result<InvokeResult, RuntimeError>;
 
+    fn crypto_invoke(&self, action: CryptoAction, args: EngineArgs) -> Result<InvokeResult, RuntimeError>;
+
     fn generate_uuid(&self) -> Result<[u8; 32], RuntimeError>;
 
     fn set_last_instruction_output(&self, value: IndexedValue) -> Result<(), RuntimeError>;
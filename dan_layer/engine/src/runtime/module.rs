@@ -1,7 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use crate::runtime::StateTracker;
+use crate::runtime::{CryptoOperation, StateTracker};
 
 pub trait RuntimeModule: Send + Sync {
     fn on_initialize(&self, _track: &StateTracker) -> Result<(), RuntimeModuleError> {
@@ -12,6 +12,14 @@ pub trait RuntimeModule: Send + Sync {
         Ok(())
     }
 
+    fn on_crypto_operation(
+        &self,
+        _track: &StateTracker,
+        _operation: CryptoOperation,
+    ) -> Result<(), RuntimeModuleError> {
+        Ok(())
+    }
+
     fn on_before_finalize(&self, _track: &StateTracker) -> Result<(), RuntimeModuleError> {
         Ok(())
     }
@@ -121,6 +121,13 @@ impl CallScope {
         Ok(())
     }
 
+    /// Removes a destroyed substate from this scope
+    pub fn remove_substate_from_scope(&mut self, address: &SubstateId) {
+        self.orphans.swap_remove(address);
+        self.owned.swap_remove(address);
+        self.referenced.swap_remove(address);
+    }
+
     pub fn get_current_component_lock(&self) -> Option<&LockedSubstate> {
         self.component_lock.as_ref()
     }
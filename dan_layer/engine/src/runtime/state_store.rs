@@ -25,6 +25,9 @@ use crate::{
 pub struct WorkingStateStore {
     // This must be ordered deterministically since we use this to create the substate diff
     new_substates: IndexMap<SubstateId, SubstateValue>,
+    // Substates that have been destroyed in this transaction along with their previously stored value, or None if the
+    // substate was created in this transaction. Also ordered deterministically for the substate diff.
+    destroyed_substates: IndexMap<SubstateId, Option<Substate>>,
 
     loaded_substates: HashMap<SubstateId, Substate>,
     locked_substates: LockedSubstates,
@@ -36,6 +39,7 @@ impl WorkingStateStore {
     pub fn new(state_store: ReadOnlyMemoryStateStore) -> Self {
         Self {
             new_substates: IndexMap::new(),
+            destroyed_substates: IndexMap::new(),
             loaded_substates: HashMap::new(),
             locked_substates: Default::default(),
             state_store,
@@ -132,6 +136,9 @@ impl WorkingStateStore {
     }
 
     pub fn exists(&self, id: &SubstateId) -> Result<bool, RuntimeError> {
+        if self.destroyed_substates.contains_key(id) {
+            return Ok(false);
+        }
         let exists = self.new_substates.contains_key(id) ||
             self.loaded_substates.contains_key(id) ||
             self.state_store.exists(id)?;
@@ -142,11 +149,54 @@ impl WorkingStateStore {
         if self.exists(&id)? {
             return Err(RuntimeError::DuplicateSubstate { address: id });
         }
+        // A substate that is destroyed and recreated in the same transaction is simply updated
+        self.destroyed_substates.shift_remove(&id);
         self.new_substates.insert(id, value);
         Ok(())
     }
 
+    /// Destroys a write-locked substate. The lock is not released.
+    pub fn destroy_locked_substate(&mut self, lock_id: LockId) -> Result<SubstateId, RuntimeError> {
+        let lock = self.locked_substates.get(lock_id, LockFlag::Write)?;
+        let id = lock.address().clone();
+        self.new_substates.shift_remove(&id);
+        self.loaded_substates.remove(&id);
+        let existing = self.state_store.get_state(&id).optional()?.cloned();
+        self.destroyed_substates.insert(id.clone(), existing);
+        Ok(id)
+    }
+
+    /// Returns the substates that existed before this transaction and have been destroyed, along with their last
+    /// stored value. These are downed without being upped in the substate diff.
+    pub fn destroyed_substates(&self) -> impl Iterator<Item = (&SubstateId, &Substate)> + '_ {
+        self.destroyed_substates
+            .iter()
+            .filter_map(|(id, maybe_substate)| maybe_substate.as_ref().map(|s| (id, s)))
+    }
+
+    pub fn is_destroyed(&self, id: &SubstateId) -> bool {
+        self.destroyed_substates.contains_key(id)
+    }
+
+    /// Returns the current value of the substate without requiring a lock, or None if it does not exist
+    pub fn get_current(&self, id: &SubstateId) -> Result<Option<&SubstateValue>, RuntimeError> {
+        if self.destroyed_substates.contains_key(id) {
+            return Ok(None);
+        }
+        if let Some(value) = self.new_substates.get(id) {
+            return Ok(Some(value));
+        }
+        if let Some(substate) = self.loaded_substates.get(id) {
+            return Ok(Some(substate.substate_value()));
+        }
+        let maybe_substate = self.state_store.get_state(id).optional()?;
+        Ok(maybe_substate.map(|s| s.substate_value()))
+    }
+
     fn load(&mut self, id: &SubstateId) -> Result<(), RuntimeError> {
+        if self.destroyed_substates.contains_key(id) {
+            return Err(RuntimeError::SubstateNotFound { id: id.clone() });
+        }
         if self.new_substates.contains_key(id) {
             return Ok(());
         }
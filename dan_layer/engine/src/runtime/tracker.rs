@@ -21,6 +21,7 @@
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashSet,
     convert::TryFrom,
     sync::{Arc, Mutex, RwLock},
 };
@@ -37,7 +38,7 @@ use tari_engine_types::{
     indexed_value::{IndexedValue, IndexedWellKnownTypes},
     lock::LockFlag,
     logs::LogEntry,
-    substate::{InvalidSubstateIdVariant, SubstateId, SubstateValue},
+    substate::{InvalidSubstateIdVariant, Substate, SubstateId, SubstateValue},
     virtual_substate::VirtualSubstates,
     TemplateAddress,
 };
@@ -74,6 +75,7 @@ impl StateTracker {
         state_store: ReadOnlyMemoryStateStore,
         virtual_substates: VirtualSubstates,
         initial_call_scope: CallScope,
+        read_only_inputs: HashSet<SubstateId>,
         transaction_hash: Hash,
         transaction_weight: TransactionWeight,
     ) -> Self {
@@ -82,6 +84,7 @@ impl StateTracker {
                 state_store,
                 virtual_substates,
                 initial_call_scope,
+                read_only_inputs,
                 transaction_hash,
             ))),
             fee_checkpoint: Arc::new(Mutex::new(None)),
@@ -286,6 +289,13 @@ impl StateTracker {
         })
     }
 
+    pub fn add_storage_rebate(&self, amount: u64) {
+        self.write_with(|state| {
+            debug!(target: LOG_TARGET, "Add storage rebate: {}", amount);
+            state.fee_state_mut().storage_rebate += amount;
+        })
+    }
+
     pub fn finalize(
         &self,
         mut substates_to_persist: IndexMap<SubstateId, SubstateValue>,
@@ -334,8 +344,10 @@ impl StateTracker {
         let mut checkpoint = self.fee_checkpoint.lock().unwrap();
         if let Some(checkpoint) = checkpoint.take() {
             self.write_with(|state| {
-                let fee_state = state.fee_state().clone();
+                let mut fee_state = state.fee_state().clone();
                 *state = checkpoint;
+                // Substates destroyed after the checkpoint are restored, so no rebate applies
+                fee_state.storage_rebate = 0;
                 // Preserve fee state across resets
                 *state.fee_state_mut() = fee_state;
             });
@@ -357,6 +369,13 @@ impl StateTracker {
         self.write_with(|state| f(state.mutated_substates()))
     }
 
+    pub fn with_destroyed_substates<F: FnOnce(&mut dyn Iterator<Item = (&SubstateId, &Substate)>) -> R, R>(
+        &self,
+        f: F,
+    ) -> R {
+        self.read_with(|state| f(&mut state.store().destroyed_substates()))
+    }
+
     pub fn are_fees_paid_in_full(&self) -> bool {
         self.read_with(|state| {
             let total_payments = state.fee_state().total_payments();
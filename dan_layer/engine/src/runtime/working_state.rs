@@ -80,12 +80,17 @@ pub(super) struct WorkingState {
     store: WorkingStateStore,
 
     claimed_confidential_outputs: Vec<UnclaimedConfidentialOutputAddress>,
+    // Vaults destroyed within the context of a component. The component must not reference these once the transaction
+    // is finalized.
+    destroyed_component_vaults: Vec<(ComponentAddress, VaultId)>,
     virtual_substates: VirtualSubstates,
 
     last_instruction_output: Option<IndexedValue>,
     workspace: Workspace,
     call_frames: Vec<CallFrame>,
     initial_call_scope: CallScope,
+    // Inputs that the transaction declared as read-only. These must not be written to or destroyed.
+    read_only_inputs: HashSet<SubstateId>,
 
     fee_state: FeeState,
 }
@@ -95,6 +100,7 @@ impl WorkingState {
         state_store: ReadOnlyMemoryStateStore,
         virtual_substates: VirtualSubstates,
         initial_call_scope: CallScope,
+        read_only_inputs: HashSet<SubstateId>,
         transaction_hash: Hash,
     ) -> Self {
         Self {
@@ -109,12 +115,14 @@ impl WorkingState {
             store: WorkingStateStore::new(state_store),
 
             claimed_confidential_outputs: Vec::new(),
+            destroyed_component_vaults: Vec::new(),
             last_instruction_output: None,
 
             workspace: Workspace::default(),
             virtual_substates,
             call_frames: Vec::new(),
             initial_call_scope,
+            read_only_inputs,
             fee_state: FeeState::new(),
             object_ids: ObjectIds::new(1000),
         }
@@ -252,6 +260,43 @@ impl WorkingState {
         Ok(non_fungible)
     }
 
+    /// Destroys the write-locked substate and removes it from the current scope. The caller is responsible for
+    /// releasing the lock.
+    pub fn destroy_substate(&mut self, locked: &LockedSubstate) -> Result<(), RuntimeError> {
+        let id = self.store.destroy_locked_substate(locked.lock_id())?;
+        self.current_call_scope_mut()?.remove_substate_from_scope(&id);
+        debug!(target: LOG_TARGET, "Substate {} destroyed", id);
+        Ok(())
+    }
+
+    /// Destroys an empty vault. If called within a component context, the component must no longer reference the vault
+    /// by the end of the transaction.
+    pub fn destroy_vault(&mut self, vault_lock: &LockedSubstate) -> Result<(), RuntimeError> {
+        let vault_id = vault_lock
+            .address()
+            .as_vault_id()
+            .ok_or_else(|| RuntimeError::LockSubstateMismatch {
+                lock_id: vault_lock.lock_id(),
+                address: vault_lock.address().clone(),
+                expected_type: "Vault",
+            })?;
+        let vault = self.get_vault(vault_lock)?;
+        if !vault.balance().is_zero() ||
+            !vault.locked_balance().is_zero() ||
+            vault.get_commitment_count() > 0 ||
+            !vault.get_non_fungible_ids().is_empty()
+        {
+            return Err(RuntimeError::CannotDestroyNonEmptyVault { vault_id });
+        }
+
+        let maybe_component = self.current_component()?;
+        self.destroy_substate(vault_lock)?;
+        if let Some(component_address) = maybe_component {
+            self.destroyed_component_vaults.push((component_address, vault_id));
+        }
+        Ok(())
+    }
+
     pub fn claim_confidential_output(&mut self, addr: &UnclaimedConfidentialOutputAddress) -> Result<(), RuntimeError> {
         if self.claimed_confidential_outputs.contains(addr) {
             return Err(RuntimeError::ConfidentialOutputAlreadyClaimed { address: *addr });
@@ -354,6 +399,36 @@ impl WorkingState {
             }
         }
 
+        for (component_address, vault_id) in &self.destroyed_component_vaults {
+            // If the component was also destroyed, there is nothing to check
+            let Some(substate) = self.store.get_current(&(*component_address).into())? else {
+                continue;
+            };
+            let component = substate.component().ok_or_else(|| RuntimeError::InvariantError {
+                function: "validate_finalized",
+                details: format!("Substate at address {} is not a component", component_address),
+            })?;
+            let indexed = IndexedWellKnownTypes::from_value(component.state())?;
+            if indexed.vault_ids().contains(vault_id) {
+                return Err(TransactionCommitError::DestroyedVaultStillReferenced {
+                    component_address: *component_address,
+                    vault_id: *vault_id,
+                }
+                .into());
+            }
+        }
+
+        if let Some(substate_id) = self
+            .read_only_inputs
+            .iter()
+            .find(|id| self.store.mutated_substates().contains_key(*id) || self.store.is_destroyed(id))
+        {
+            return Err(TransactionCommitError::ReadOnlyInputWritten {
+                substate_id: substate_id.clone(),
+            }
+            .into());
+        }
+
         if self.call_frame_depth() != 0 {
             return Err(RuntimeError::CallFrameRemainingOnStack {
                 remaining: self.call_frame_depth(),
@@ -516,6 +591,13 @@ impl WorkingState {
                 );
                 let mut token_ids = BTreeSet::new();
 
+                let resource = self.get_resource(locked_resource)?;
+                if let Some(token_id) = tokens.keys().find(|id| resource.is_non_fungible_id_destroyed(id)) {
+                    return Err(RuntimeError::DestroyedNonFungibleId {
+                        token_id: token_id.clone(),
+                    });
+                }
+
                 // let resource = self.get_resource(locked_resource)?;
                 // TODO: This isn't correct (assumes tokens are never burnt), we'll need to rethink this
                 // let mut index = resource
@@ -778,8 +860,10 @@ impl WorkingState {
         // Check that no vaults were dropped
         if let Some(prev_state) = previous_state {
             for existing_vault in prev_state.vault_ids() {
-                // Vaults can never be removed from components
-                if !next_state.vault_ids().contains(existing_vault) {
+                // Vaults can never be removed from components unless they have been destroyed
+                if !next_state.vault_ids().contains(existing_vault) &&
+                    !self.store.is_destroyed(&(*existing_vault).into())
+                {
                     return Err(RuntimeError::OrphanedSubstate {
                         address: (*existing_vault).into(),
                     });
@@ -787,9 +871,14 @@ impl WorkingState {
             }
         }
 
-        // Check that no vaults are duplicated
+        // Check that no vaults are duplicated or destroyed
         let mut dup_check = HashSet::with_capacity(next_state.vault_ids().len());
         for vault_id in next_state.vault_ids() {
+            if self.store.is_destroyed(&(*vault_id).into()) {
+                return Err(RuntimeError::DestroyedSubstateReferenced {
+                    address: (*vault_id).into(),
+                });
+            }
             if !dup_check.insert(vault_id) {
                 return Err(RuntimeError::DuplicateReference {
                     address: (*vault_id).into(),
@@ -889,6 +978,15 @@ impl WorkingState {
                 total_fee_payment,
                 total_fees_paid: fee_resource.amount(),
                 cost_breakdown: mem::take(&mut self.fee_state.fee_charges),
+                storage_rebate: Amount::try_from(self.fee_state.storage_rebate).map_err(|_| {
+                    RuntimeError::InvariantError {
+                        function: "finalize_fees",
+                        details: format!(
+                            "Storage rebate {} could not be converted to Amount",
+                            self.fee_state.storage_rebate
+                        ),
+                    }
+                })?,
             },
         })
     }
@@ -1011,6 +1109,7 @@ impl WorkingState {
             self.store.state_store().clone(),
             VirtualSubstates::new(),
             CallScope::new(),
+            HashSet::new(),
             self.transaction_hash,
         );
         mem::replace(self, new_state)
@@ -1143,6 +1242,11 @@ impl WorkingState {
             substate_diff.down(SubstateId::UnclaimedConfidentialOutput(*claimed), 0);
         }
 
+        // Destroyed substates are downed without being upped
+        for (address, substate) in self.store.destroyed_substates() {
+            substate_diff.down(address.clone(), substate.version());
+        }
+
         substate_diff.up(
             SubstateId::TransactionReceipt(transaction_receipt.transaction_hash.into()),
             Substate::new(0, SubstateValue::TransactionReceipt(transaction_receipt)),
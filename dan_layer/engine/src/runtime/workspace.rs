@@ -6,7 +6,10 @@ use std::{
     mem,
 };
 
-use tari_engine_types::indexed_value::{IndexedValue, IndexedValueError};
+use tari_engine_types::{
+    indexed_value::{IndexedValue, IndexedValueError},
+    TemplateAddress,
+};
 use tari_template_lib::models::ProofId;
 
 #[derive(Debug, thiserror::Error)]
@@ -21,6 +24,7 @@ pub enum WorkspaceError {
 pub struct Workspace {
     variables: HashMap<Vec<u8>, IndexedValue>,
     proofs: HashSet<ProofId>,
+    namespace_counter: u32,
 }
 
 impl Workspace {
@@ -60,11 +64,25 @@ impl Workspace {
     pub fn drain_all_proofs(&mut self) -> HashSet<ProofId> {
         mem::take(&mut self.proofs)
     }
+
+    /// Allocates a key prefix that is unique within the transaction. Manifest template functions put their
+    /// variables under this prefix so that they cannot collide with the caller's variables.
+    pub fn allocate_namespace(&mut self, template_address: &TemplateAddress) -> String {
+        let id = self.namespace_counter;
+        self.namespace_counter += 1;
+        format!("{}#{}/", template_address, id)
+    }
+
+    /// Removes all variables with keys in the given namespace. Proofs held by the removed values remain tracked
+    /// until they are dropped.
+    pub fn drop_namespace(&mut self, namespace: &str) {
+        self.variables.retain(|key, _| !key.starts_with(namespace.as_bytes()));
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use tari_engine_types::indexed_value::IndexedValue;
+    use tari_engine_types::{indexed_value::IndexedValue, TemplateAddress};
     use tari_utilities::ByteArray;
 
     use super::Workspace;
@@ -93,4 +111,23 @@ mod tests {
         let value = workspace.get(b"tuple.1").unwrap();
         assert_eq!(*value, expected);
     }
+
+    #[test]
+    fn namespaces() {
+        let mut workspace = Workspace::default();
+        let ns1 = workspace.allocate_namespace(&TemplateAddress::default());
+        let ns2 = workspace.allocate_namespace(&TemplateAddress::default());
+        assert_ne!(ns1, ns2);
+
+        let value = IndexedValue::from_type(&(1u32, 2u32)).unwrap();
+        workspace.insert(b"x".to_vec(), value.clone()).unwrap();
+        workspace.insert(format!("{ns1}x").into_bytes(), value.clone()).unwrap();
+        workspace.insert(format!("{ns2}x").into_bytes(), value).unwrap();
+
+        workspace.drop_namespace(&ns1);
+        assert!(workspace.get(format!("{ns1}x").as_bytes()).is_none());
+        assert!(workspace.get(format!("{ns1}x.0").as_bytes()).is_none());
+        assert!(workspace.get(format!("{ns2}x").as_bytes()).is_some());
+        assert!(workspace.get(b"x").is_some());
+    }
 }
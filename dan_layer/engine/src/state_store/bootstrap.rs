@@ -7,10 +7,10 @@ use tari_engine_types::{
 };
 use tari_template_lib::{
     auth::ResourceAccessRules,
-    constants::{CONFIDENTIAL_TARI_RESOURCE_ADDRESS, PUBLIC_IDENTITY_RESOURCE_ADDRESS},
+    constants::{CONFIDENTIAL_TARI_RESOURCE_ADDRESS, PUBLIC_IDENTITY_RESOURCE_ADDRESS, XTR_DIVISIBILITY},
     models::Metadata,
     prelude::{OwnerRule, ResourceType},
-    resource::TOKEN_SYMBOL,
+    resource::{TransferHooks, TOKEN_SYMBOL},
 };
 
 use crate::state_store::{memory::MemoryStateStore, StateStoreError, StateWriter};
@@ -40,6 +40,8 @@ fn add_global_resources<T: StateWriter>(state_db: &mut T) -> Result<(), StateSto
                 metadata,
                 None,
                 None,
+                0,
+                TransferHooks::default(),
             ),
         ),
     )?;
@@ -60,6 +62,8 @@ fn add_global_resources<T: StateWriter>(state_db: &mut T) -> Result<(), StateSto
                 metadata,
                 None,
                 None,
+                XTR_DIVISIBILITY,
+                TransferHooks::default(),
             ),
         ),
     )?;
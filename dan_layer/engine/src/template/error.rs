@@ -36,6 +36,8 @@ pub enum TemplateLoaderError {
     ExportError(#[from] wasmer::ExportError),
     #[error("Runtime error: {0}")]
     RuntimeError(#[from] wasmer::RuntimeError),
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] tari_transaction_manifest::ManifestError),
 }
 
 impl From<wasmer::InstantiationError> for TemplateLoaderError {
@@ -0,0 +1,58 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use tari_engine_types::instruction::Instruction;
+use tari_template_abi::{TemplateDef, TemplateDefV1};
+use tari_transaction_manifest::{ManifestError, ManifestTemplate};
+
+use crate::template::TemplateLoaderError;
+
+/// A template whose functions are manifest scripts. Calling a function expands it into instructions that are
+/// executed in the calling transaction's context.
+#[derive(Debug, Clone)]
+pub struct LoadedManifestTemplate {
+    template: ManifestTemplate,
+    template_def: TemplateDef,
+}
+
+impl LoadedManifestTemplate {
+    pub fn try_from_source(source: &str) -> Result<Self, TemplateLoaderError> {
+        let template = ManifestTemplate::parse(source)?;
+        let template_def = TemplateDef::V1(TemplateDefV1 {
+            template_name: template.name().to_string(),
+            tari_version: env!("CARGO_PKG_VERSION").to_string(),
+            functions: template.functions().to_vec(),
+            events: vec![],
+        });
+        Ok(Self { template, template_def })
+    }
+
+    /// Returns true if the published template code is manifest source rather than a WASM binary or flow
+    pub fn is_manifest_source(code: &[u8]) -> bool {
+        std::str::from_utf8(code).is_ok_and(|s| s.trim_start().starts_with("mod "))
+    }
+
+    pub fn template_name(&self) -> &str {
+        self.template.name()
+    }
+
+    pub fn template_def(&self) -> &TemplateDef {
+        &self.template_def
+    }
+
+    pub fn code_size(&self) -> usize {
+        self.template.source().len()
+    }
+
+    pub fn expand(
+        &self,
+        function: &str,
+        args: Vec<tari_bor::Value>,
+        workspace_namespace: &str,
+    ) -> Result<Vec<Instruction>, ManifestError> {
+        self.template
+            .expand(function, args, HashMap::new(), workspace_namespace)
+    }
+}
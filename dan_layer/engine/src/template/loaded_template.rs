@@ -22,12 +22,13 @@
 
 use tari_template_abi::TemplateDef;
 
-use crate::{flow::FlowFactory, wasm::LoadedWasmTemplate};
+use crate::{flow::FlowFactory, template::LoadedManifestTemplate, wasm::LoadedWasmTemplate};
 
 #[derive(Debug, Clone)]
 pub enum LoadedTemplate {
     Wasm(LoadedWasmTemplate),
     Flow(FlowFactory),
+    Manifest(LoadedManifestTemplate),
 }
 
 impl LoadedTemplate {
@@ -37,6 +38,7 @@ impl LoadedTemplate {
         match self {
             LoadedTemplate::Wasm(wasm) => wasm.template_name(),
             LoadedTemplate::Flow(flow) => flow.name(),
+            LoadedTemplate::Manifest(manifest) => manifest.template_name(),
         }
     }
 
@@ -44,6 +46,7 @@ impl LoadedTemplate {
         match self {
             LoadedTemplate::Wasm(wasm) => wasm.template_def(),
             LoadedTemplate::Flow(flow) => flow.template_def(),
+            LoadedTemplate::Manifest(manifest) => manifest.template_def(),
         }
     }
 
@@ -54,6 +57,7 @@ impl LoadedTemplate {
                 // todo: idk what this should be
                 128
             },
+            LoadedTemplate::Manifest(manifest) => manifest.code_size(),
         }
     }
 }
@@ -23,6 +23,9 @@
 mod error;
 pub use error::TemplateLoaderError;
 
+mod loaded_manifest_template;
+pub use loaded_manifest_template::LoadedManifestTemplate;
+
 mod loaded_template;
 pub use loaded_template::LoadedTemplate;
 
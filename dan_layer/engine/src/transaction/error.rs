@@ -47,6 +47,16 @@ pub enum TransactionError {
     InvariantError { details: String },
     #[error("Load template error: {0}")]
     LoadTemplate(#[from] TemplateLoaderError),
+    #[error("Manifest template '{template_name}' can only be called from a transaction instruction")]
+    ManifestNotInvokable { template_name: String },
+    #[error("Failed to expand manifest function '{function}' of template {address}: {details}")]
+    ManifestExpansionFailed {
+        address: TemplateAddress,
+        function: String,
+        details: String,
+    },
+    #[error("Manifest templates exceeded the maximum call depth of {max_depth}")]
+    MaxManifestDepthExceeded { max_depth: usize },
     #[error("WASM binary too big! {0} bytes are greater than allowed maximum {1} bytes.")]
     WasmBinaryTooBig(usize, usize),
     #[error("Template provider error: {0}")]
@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{sync::Arc, time::Instant};
+use std::{collections::HashSet, sync::Arc, time::Instant};
 
 use log::*;
 use tari_bor::to_value;
@@ -43,7 +43,7 @@ use tari_template_lib::{
     arg,
     args,
     args::{Arg, WorkspaceAction},
-    auth::OwnerRule,
+    auth::{AccountPolicy, OwnerRule},
     crypto::RistrettoPublicKeyBytes,
     invoke_args,
     models::{Bucket, ComponentAddress, NonFungibleAddress},
@@ -53,6 +53,7 @@ use tari_transaction::Transaction;
 use tari_utilities::ByteArray;
 
 use crate::{
+    flow::FlowFactory,
     runtime::{
         scope::{CallScope, PushCallFrame},
         AuthParams,
@@ -63,7 +64,7 @@ use crate::{
         StateTracker,
     },
     state_store::memory::ReadOnlyMemoryStateStore,
-    template::LoadedTemplate,
+    template::{LoadedManifestTemplate, LoadedTemplate},
     traits::Invokable,
     transaction::TransactionError,
     wasm::{WasmModule, WasmProcess},
@@ -158,8 +159,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         let initial_auth_scope = AuthorizationScope::new(auth_params.initial_ownership_proofs);
         let mut initial_call_scope = CallScope::new();
         initial_call_scope.set_auth_scope(initial_auth_scope);
+        let mut read_only_inputs = HashSet::new();
         for input in transaction.all_inputs_iter() {
             initial_call_scope.add_substate_to_owned(input.substate_id.clone());
+            if input.is_read_only() {
+                read_only_inputs.insert(input.substate_id.clone());
+            }
         }
 
         let transaction_weight = transaction.calculate_transaction_weight();
@@ -167,6 +172,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             state_db,
             virtual_substates,
             initial_call_scope,
+            read_only_inputs,
             transaction.hash(),
             transaction_weight,
         );
@@ -296,6 +302,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                 owner_rule,
                 access_rules,
                 workspace_bucket,
+                policy,
             } => Self::create_account(
                 template_provider,
                 runtime,
@@ -303,12 +310,21 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                 owner_rule,
                 access_rules,
                 workspace_bucket,
+                policy,
             ),
             Instruction::CallFunction {
                 template_address,
                 function,
                 args,
-            } => Self::call_function(template_provider, runtime, &template_address, &function, args),
+            } => Self::call_function_instruction(
+                config,
+                template_provider,
+                runtime,
+                &template_address,
+                &function,
+                args,
+                0,
+            ),
             Instruction::CallMethod {
                 component_address,
                 method,
@@ -380,7 +396,14 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         }
 
         // validate binary
-        WasmModule::load_template_from_code(&binary)?;
+        if FlowFactory::is_flow_json(&binary) {
+            FlowFactory::try_from_json(&binary)?;
+        } else if LoadedManifestTemplate::is_manifest_source(&binary) {
+            // is_manifest_source checks that the code is valid UTF-8
+            LoadedManifestTemplate::try_from_source(&String::from_utf8_lossy(&binary))?;
+        } else {
+            WasmModule::load_template_from_code(&binary)?;
+        }
         // creating new substate
         runtime.interface().publish_template(binary)?;
 
@@ -394,6 +417,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         owner_rule: Option<OwnerRule>,
         access_rules: Option<AccessRules>,
         workspace_bucket: Option<String>,
+        policy: Option<AccountPolicy>,
     ) -> Result<InstructionResult, TransactionError> {
         let template = template_provider
             .get_template_module(&ACCOUNT_TEMPLATE_ADDRESS)
@@ -443,6 +467,9 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             args.push(arg![Literal(none)]);
         }
 
+        // add the optional policy, which makes the account authorize calls itself
+        args.push(arg![Literal(policy)]);
+
         let args = runtime.resolve_args(args)?;
         let arg_scope = args
             .iter()
@@ -465,6 +492,65 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         Ok(result)
     }
 
+    /// Calls a template function from a transaction instruction. Manifest template functions are expanded into
+    /// instructions that are executed in the transaction's context.
+    fn call_function_instruction(
+        config: &TransactionProcessorConfig,
+        template_provider: &TTemplateProvider,
+        runtime: &Runtime,
+        template_address: &TemplateAddress,
+        function: &str,
+        args: Vec<Arg>,
+        manifest_depth: usize,
+    ) -> Result<InstructionResult, TransactionError> {
+        let template = Self::load_template(template_provider, template_address)?;
+        let LoadedTemplate::Manifest(manifest) = template else {
+            return Self::call_loaded_function(template, template_provider, runtime, template_address, function, args);
+        };
+
+        if manifest_depth >= MAX_CALL_DEPTH {
+            return Err(TransactionError::MaxManifestDepthExceeded {
+                max_depth: MAX_CALL_DEPTH,
+            });
+        }
+
+        let args = runtime.resolve_args(args)?;
+        // Each expansion gets its own workspace namespace, so the function's variables neither clobber the caller's
+        // variables nor outlive the call
+        let namespace = runtime.interface().workspace_allocate_namespace(template_address)?;
+        let instructions =
+            manifest
+                .expand(function, args, &namespace)
+                .map_err(|e| TransactionError::ManifestExpansionFailed {
+                    address: *template_address,
+                    function: function.to_string(),
+                    details: e.to_string(),
+                })?;
+
+        for instruction in instructions {
+            match instruction {
+                Instruction::CallFunction {
+                    template_address,
+                    function,
+                    args,
+                } => Self::call_function_instruction(
+                    config,
+                    template_provider,
+                    runtime,
+                    &template_address,
+                    &function,
+                    args,
+                    manifest_depth + 1,
+                )?,
+                instruction => Self::process_instruction(config, template_provider, runtime, instruction)?,
+            };
+        }
+
+        runtime.interface().workspace_drop_namespace(&namespace)?;
+
+        Ok(InstructionResult::empty())
+    }
+
     pub fn call_function(
         template_provider: &TTemplateProvider,
         runtime: &Runtime,
@@ -472,7 +558,15 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         function: &str,
         args: Vec<Arg>,
     ) -> Result<InstructionResult, TransactionError> {
-        let template = template_provider
+        let template = Self::load_template(template_provider, template_address)?;
+        Self::call_loaded_function(template, template_provider, runtime, template_address, function, args)
+    }
+
+    fn load_template(
+        template_provider: &TTemplateProvider,
+        template_address: &TemplateAddress,
+    ) -> Result<LoadedTemplate, TransactionError> {
+        template_provider
             .get_template_module(template_address)
             .map_err(|e| TransactionError::FailedToLoadTemplate {
                 address: *template_address,
@@ -480,8 +574,17 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             })?
             .ok_or(TransactionError::TemplateNotFound {
                 address: *template_address,
-            })?;
+            })
+    }
 
+    fn call_loaded_function(
+        template: LoadedTemplate,
+        template_provider: &TTemplateProvider,
+        runtime: &Runtime,
+        template_address: &TemplateAddress,
+        function: &str,
+        args: Vec<Arg>,
+    ) -> Result<InstructionResult, TransactionError> {
         let function_def = template.template_def().get_function(function).cloned().ok_or_else(|| {
             TransactionError::FunctionNotFound {
                 name: function.to_string(),
@@ -602,6 +705,11 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                     MAX_CALL_DEPTH,
                 )?
             },
+            LoadedTemplate::Manifest(manifest) => {
+                return Err(TransactionError::ManifestNotInvokable {
+                    template_name: manifest.template_name().to_string(),
+                })
+            },
         };
         Ok(result)
     }
@@ -32,8 +32,10 @@ use tari_template_lib::{
         CallerContextInvokeArg,
         ComponentInvokeArg,
         ConsensusInvokeArg,
+        CryptoInvokeArg,
         EmitEventArg,
         EmitLogArg,
+        EmitTypedEventArg,
         GenerateRandomInvokeArg,
         LogLevel,
         NonFungibleInvokeArg,
@@ -192,6 +194,12 @@ impl WasmProcess {
                     env.interface().builtin_template_invoke(arg.action)
                 })
             },
+            EngineOp::CryptoInvoke => Self::handle(store, env_mut, arg, |env, arg: CryptoInvokeArg| {
+                env.interface().crypto_invoke(arg.action, arg.args.into())
+            }),
+            EngineOp::EmitTypedEvent => Self::handle(store, env_mut, arg, |env, arg: EmitTypedEventArg| {
+                env.interface().emit_typed_event(arg.topic, arg.payload)
+            }),
         };
 
         result.unwrap_or_else(|err| {
@@ -3,11 +3,15 @@
 
 use tari_crypto::{keys::PublicKey, ristretto::RistrettoPublicKey, tari_utilities::ByteArray};
 use tari_dan_engine::runtime::{ActionIdent, RuntimeError};
-use tari_engine_types::instruction::Instruction;
+use tari_engine_types::{
+    instruction::Instruction,
+    virtual_substate::{VirtualSubstate, VirtualSubstateId},
+};
 use tari_template_lib::{
     args,
-    auth::AccessRule,
+    auth::{AccessRule, AccountPolicy, SessionKey, SpendingLimit},
     constants::XTR,
+    crypto::RistrettoPublicKeyBytes,
     models::{Amount, ComponentAddress, ResourceAddress},
     prelude::AccessRules,
     rule,
@@ -299,3 +303,291 @@ fn custom_access_rules() {
         vec![user2_account_proof],
     );
 }
+
+mod policy_account {
+    use tari_crypto::ristretto::RistrettoSecretKey;
+
+    use super::*;
+
+    fn to_key_bytes(public_key: &RistrettoPublicKey) -> RistrettoPublicKeyBytes {
+        RistrettoPublicKeyBytes::from_bytes(public_key.as_bytes()).unwrap()
+    }
+
+    fn create_policy_account(
+        template_test: &mut TemplateTest,
+        owner_public_key: RistrettoPublicKey,
+        owner_secret_key: &RistrettoSecretKey,
+        policy: AccountPolicy,
+    ) -> ComponentAddress {
+        let result = template_test.execute_expect_success(
+            Transaction::builder()
+                .create_account_with_policy(owner_public_key, policy)
+                .build_and_seal(owner_secret_key),
+            vec![],
+        );
+        let account = result.finalize.execution_results[0].decode().unwrap();
+
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(test_faucet_component(), "take_free_coins", args![])
+                .put_last_instruction_output_on_workspace("bucket")
+                .call_method(account, "deposit", args![Workspace("bucket")])
+                .build_and_seal(owner_secret_key),
+            vec![],
+        );
+
+        account
+    }
+
+    #[test]
+    fn it_enforces_spending_limits_for_session_keys() {
+        let mut template_test = TemplateTest::new::<_, &str>([]);
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+        let (_, session_public_key, session_secret_key) = template_test.create_owner_proof();
+        let (receiver, _, _) = template_test.create_empty_account();
+
+        let policy = AccountPolicy::new()
+            .with_spending_limit(XTR, SpendingLimit::new(100, 10))
+            .with_session_key(SessionKey::new(to_key_bytes(&session_public_key), 20));
+        let account = create_policy_account(&mut template_test, owner_public_key, &owner_secret_key, policy);
+
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(60)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+
+        // 60 of the 100 limit has been spent in this window
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(41)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "exceeds the spending limit");
+
+        // The limit resets once the window has passed
+        template_test.set_virtual_substate(VirtualSubstateId::CurrentEpoch, VirtualSubstate::CurrentEpoch(10));
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(100)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+
+        // The session key expires at epoch 20
+        template_test.set_virtual_substate(VirtualSubstateId::CurrentEpoch, VirtualSubstate::CurrentEpoch(20));
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(1)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(
+            reason,
+            "can only be called by the account owner or an unexpired session key",
+        );
+    }
+
+    #[test]
+    fn it_counts_fees_towards_spending_limits() {
+        let mut template_test = TemplateTest::new::<_, &str>([]);
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+        let (_, session_public_key, session_secret_key) = template_test.create_owner_proof();
+        let (receiver, _, _) = template_test.create_empty_account();
+
+        let policy = AccountPolicy::new()
+            .with_spending_limit(XTR, SpendingLimit::new(100, 10))
+            .with_session_key(SessionKey::new(to_key_bytes(&session_public_key), 20));
+        let account = create_policy_account(&mut template_test, owner_public_key, &owner_secret_key, policy);
+
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "pay_fee", args![Amount(60)])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(41)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "exceeds the spending limit");
+    }
+
+    #[test]
+    fn it_prevents_intermediate_components_from_spending() {
+        let mut template_test = TemplateTest::new(["tests/templates/shenanigans"]);
+        let shenanigans_template = template_test.get_template_address("Shenanigans");
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+        let (attacker, _, _) = template_test.create_empty_account();
+
+        let account = create_policy_account(
+            &mut template_test,
+            owner_public_key,
+            &owner_secret_key,
+            AccountPolicy::new(),
+        );
+
+        // The owner signs a transaction that calls a malicious template, which tries to withdraw from the account
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(shenanigans_template, "attempt_to_drain_account", args![
+                    account,
+                    XTR,
+                    Amount(1000),
+                    attacker
+                ])
+                .build_and_seal(&owner_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "must be called directly by the transaction");
+
+        // Calling the account directly is still allowed
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(1000)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(attacker, "deposit", args![Workspace("b")])
+                .build_and_seal(&owner_secret_key),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn it_restricts_session_keys_to_allowed_destinations() {
+        let mut template_test = TemplateTest::new::<_, &str>([]);
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+        let (_, session_public_key, session_secret_key) = template_test.create_owner_proof();
+        let (allowed, _, _) = template_test.create_empty_account();
+        let (not_allowed, _, _) = template_test.create_empty_account();
+
+        let policy = AccountPolicy::new()
+            .with_session_key(SessionKey::new(to_key_bytes(&session_public_key), 100))
+            .with_allowed_destinations([allowed]);
+        let account = create_policy_account(&mut template_test, owner_public_key, &owner_secret_key, policy);
+
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "transfer", args![XTR, Amount(10), allowed])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "transfer", args![XTR, Amount(10), not_allowed])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "is not an allowed destination");
+
+        // Buckets could be deposited anywhere
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(10)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(allowed, "deposit", args![Workspace("b")])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "Session keys may only send funds to allowed destinations");
+
+        // The owner is not restricted
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "transfer", args![XTR, Amount(10), not_allowed])
+                .build_and_seal(&owner_secret_key),
+            vec![],
+        );
+
+        // Session keys cannot change the policy
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "set_policy", args![AccountPolicy::new()])
+                .build_and_seal(&session_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "can only be called by the account owner");
+    }
+
+    #[test]
+    fn it_allows_guardians_to_rotate_the_owner_key() {
+        let mut template_test = TemplateTest::new::<_, &str>([]);
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+        let (_, new_owner_public_key, new_owner_secret_key) = template_test.create_owner_proof();
+        let guardians = (0..3).map(|_| template_test.create_owner_proof()).collect::<Vec<_>>();
+        let (receiver, _, _) = template_test.create_empty_account();
+
+        let policy = AccountPolicy::new().with_guardians(guardians.iter().map(|(_, pk, _)| to_key_bytes(pk)), 2);
+        let account = create_policy_account(&mut template_test, owner_public_key, &owner_secret_key, policy);
+
+        // A key that is not a guardian cannot approve a recovery
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "approve_recovery", args![to_key_bytes(&new_owner_public_key)])
+                .build_and_seal(&new_owner_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "is not a guardian of this account");
+
+        for (_, _, guardian_secret_key) in guardians.iter().take(2) {
+            template_test.execute_expect_success(
+                Transaction::builder()
+                    .call_method(account, "approve_recovery", args![to_key_bytes(&new_owner_public_key)])
+                    .build_and_seal(guardian_secret_key),
+                vec![],
+            );
+        }
+
+        // The old owner key can no longer spend from the account
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(10)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&owner_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "can only be called by the account owner");
+
+        template_test.execute_expect_success(
+            Transaction::builder()
+                .call_method(account, "withdraw", args![XTR, Amount(10)])
+                .put_last_instruction_output_on_workspace("b")
+                .call_method(receiver, "deposit", args![Workspace("b")])
+                .build_and_seal(&new_owner_secret_key),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_policy() {
+        let mut template_test = TemplateTest::new::<_, &str>([]);
+        let (_, owner_public_key, owner_secret_key) = template_test.create_owner_proof();
+
+        let reason = template_test.execute_expect_failure(
+            Transaction::builder()
+                .create_account_with_policy(
+                    owner_public_key.clone(),
+                    AccountPolicy::new().with_guardians([to_key_bytes(&owner_public_key)], 2),
+                )
+                .build_and_seal(&owner_secret_key),
+            vec![],
+        );
+        assert_reject_reason(reason, "Invalid account policy");
+    }
+}
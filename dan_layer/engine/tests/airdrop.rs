@@ -36,6 +36,7 @@ fn airdrop() {
             owner_rule: None,
             access_rules: None,
             workspace_bucket: None,
+            policy: None,
         }
     })
     .take(100)
@@ -0,0 +1,111 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use rand::rngs::OsRng;
+use tari_common_types::types::PrivateKey;
+use tari_crypto::{
+    commitment::HomomorphicCommitmentFactory,
+    keys::{PublicKey as _, SecretKey},
+    ristretto::{RistrettoPublicKey, RistrettoSchnorr},
+    tari_utilities::ByteArray,
+};
+use tari_engine_types::{confidential::get_commitment_factory, fees::FeeSource};
+use tari_template_lib::{
+    args,
+    crypto::{PedersonCommitmentBytes, RistrettoPublicKeyBytes, SchnorrSignatureBytes},
+    models::Amount,
+    Hash,
+};
+use tari_template_test_tooling::TemplateTest;
+use tari_transaction::Transaction;
+
+#[test]
+fn it_verifies_schnorr_signatures() {
+    let mut test = TemplateTest::new(["tests/templates/crypto"]);
+    let secret = PrivateKey::random(&mut OsRng);
+    let public_key = RistrettoPublicKey::from_secret_key(&secret);
+    let signature = RistrettoSchnorr::sign(&secret, b"price=100", &mut OsRng).unwrap();
+
+    let public_key = RistrettoPublicKeyBytes::from_bytes(public_key.as_bytes()).unwrap();
+    let public_nonce = RistrettoPublicKeyBytes::from_bytes(signature.get_public_nonce().as_bytes()).unwrap();
+    let signature = SchnorrSignatureBytes::from_bytes(signature.get_signature().as_bytes()).unwrap();
+
+    let is_valid: bool = test.call_function(
+        "CryptoTest",
+        "verify_signature",
+        args![public_key, public_nonce, signature, b"price=100".to_vec()],
+        vec![],
+    );
+    assert!(is_valid);
+
+    let is_valid: bool = test.call_function(
+        "CryptoTest",
+        "verify_signature",
+        args![public_key, public_nonce, signature, b"price=999".to_vec()],
+        vec![],
+    );
+    assert!(!is_valid);
+}
+
+#[test]
+fn it_hashes_with_domain_separation() {
+    let mut test = TemplateTest::new(["tests/templates/crypto"]);
+    let a: Hash = test.call_function("CryptoTest", "hash_sha256", args!["oracle", b"data".to_vec()], vec![]);
+    let b: Hash = test.call_function("CryptoTest", "hash_sha256", args!["bridge", b"data".to_vec()], vec![]);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn it_performs_commitment_arithmetic() {
+    let mut test = TemplateTest::new(["tests/templates/crypto"]);
+    let mask = PrivateKey::random(&mut OsRng);
+    let commit = |value: u64| {
+        let commitment = get_commitment_factory().commit_value(&mask, value);
+        PedersonCommitmentBytes::from_bytes(commitment.as_bytes()).unwrap()
+    };
+
+    let is_balanced: bool = test.call_function(
+        "CryptoTest",
+        "commitments_balance",
+        args![vec![commit(100)], commit(60), 40u64],
+        vec![],
+    );
+    assert!(is_balanced);
+
+    let is_balanced: bool = test.call_function(
+        "CryptoTest",
+        "commitments_balance",
+        args![vec![commit(100)], commit(60), 41u64],
+        vec![],
+    );
+    assert!(!is_balanced);
+}
+
+#[test]
+fn it_charges_fees_for_crypto_operations() {
+    let mut test = TemplateTest::new(["tests/templates/crypto"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    test.enable_fees();
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_function(test.get_template_address("CryptoTest"), "hash_sha256", args![
+                "oracle",
+                vec![0u8; 3000]
+            ])
+            .build_and_seal(&private_key),
+        vec![owner_token],
+    );
+    test.disable_fees();
+
+    let crypto_cost = result
+        .finalize
+        .fee_receipt
+        .cost_breakdown
+        .iter()
+        .find_map(|(source, cost)| (*source == FeeSource::Crypto).then_some(*cost))
+        .unwrap();
+    // 3006 bytes is charged as 3 hash blocks
+    assert_eq!(crypto_cost, 3 * test.fee_table().per_hash_cost());
+}
@@ -0,0 +1,156 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_engine::runtime::{RuntimeError, TransactionCommitError};
+use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress, NonFungibleId, VaultId},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+fn setup(test: &mut TemplateTest) -> (ComponentAddress, VaultId) {
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(test.get_template_address("DestroyTest"), "new", args![])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![],
+    );
+    let diff = result.finalize.result.accept().unwrap();
+    let component = diff.up_iter().find_map(|(id, _)| id.as_component_address()).unwrap();
+    let vault_id = diff.up_iter().find_map(|(id, _)| id.as_vault_id()).unwrap();
+    (component, vault_id)
+}
+
+#[test]
+fn it_destroys_an_empty_vault_and_component() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (component, vault_id) = setup(&mut test);
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(component, "burn_all", args![])
+            .call_method(component, "destroy", args![])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    let diff = result.finalize.result.accept().unwrap();
+    let component_id = SubstateId::Component(component);
+    let vault_id = SubstateId::Vault(vault_id);
+    assert!(diff.down_iter().any(|(id, _)| *id == component_id));
+    assert!(diff.down_iter().any(|(id, _)| *id == vault_id));
+    assert!(!diff.up_iter().any(|(id, _)| *id == component_id || *id == vault_id));
+
+    assert!(test.read_only_state_store().get_component(component).is_err());
+}
+
+#[test]
+fn it_rejects_destroying_a_non_empty_vault() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (component, vault_id) = setup(&mut test);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "destroy_vault", args![])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    assert_reject_reason(reason, RuntimeError::CannotDestroyNonEmptyVault { vault_id });
+}
+
+#[test]
+fn it_rejects_destroying_a_component_without_ownership() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (component, _) = setup(&mut test);
+
+    let (_, _, other_key) = test.create_empty_account();
+    test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "burn_all", args![])
+            .call_method(component, "destroy", args![])
+            .build_and_seal(&other_key),
+        vec![],
+    );
+}
+
+#[test]
+fn it_rejects_a_destroyed_vault_that_is_still_referenced() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (component, vault_id) = setup(&mut test);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "burn_all", args![])
+            .call_method(component, "destroy_vault_but_keep_it", args![])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    assert_reject_reason(reason, TransactionCommitError::DestroyedVaultStillReferenced {
+        component_address: component,
+        vault_id,
+    });
+}
+
+#[test]
+fn it_rebates_storage_fees_for_destroyed_substates() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(test.get_template_address("DestroyTest"), "new", args![])
+            .build_and_seal(&private_key),
+        vec![owner_token.clone()],
+    );
+    let component = result
+        .finalize
+        .result
+        .accept()
+        .unwrap()
+        .up_iter()
+        .find_map(|(id, _)| id.as_component_address())
+        .unwrap();
+
+    test.enable_fees();
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_method(component, "burn_all", args![])
+            .call_method(component, "destroy", args![])
+            .build_and_seal(&private_key),
+        vec![owner_token],
+    );
+    test.disable_fees();
+
+    let fee_receipt = result.finalize.fee_receipt;
+    assert!(fee_receipt.storage_rebate().is_positive());
+    assert!(fee_receipt.is_paid_in_full());
+}
+
+#[test]
+fn it_rejects_minting_a_destroyed_non_fungible_id() {
+    let mut test = TemplateTest::new(["tests/templates/destroy"]);
+    let (component, _) = setup(&mut test);
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(component, "mint_and_burn_nft", args![1u64])
+            .call_method(component, "destroy_burnt_nft", args![1u64])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "mint_and_burn_nft", args![1u64])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+    assert_reject_reason(reason, RuntimeError::DestroyedNonFungibleId {
+        token_id: NonFungibleId::from_u64(1),
+    });
+}
@@ -1,12 +1,14 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_bor::cbor;
 use tari_dan_engine::runtime::RuntimeError;
 use tari_engine_types::instruction::Instruction;
+use tari_template_abi::Type;
 use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
 use tari_template_lib::{
     args,
-    models::{Amount, ComponentAddress},
+    models::{Amount, ComponentAddress, Metadata},
 };
 use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
 use tari_transaction::Transaction;
@@ -54,6 +56,110 @@ fn cannot_use_standard_topic() {
     });
 }
 
+#[test]
+fn emit_typed_event() {
+    let mut template_test = TemplateTest::new(vec!["tests/templates/events"]);
+    let event_emitter_template = template_test.get_template_address("EventEmitter");
+    let (account, _, _) = template_test.create_funded_account();
+
+    let template_def = template_test.get_module("EventEmitter").template_def().clone();
+    let event_def = template_def.get_event("Transferred").unwrap();
+    assert_eq!(
+        event_def.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+        vec!["amount", "recipient", "memo"]
+    );
+    // Manual TemplateEvent impls are not part of the ABI
+    assert!(template_def.get_event("Undeclared").is_none());
+
+    let result = template_test
+        .execute_and_commit(
+            vec![Instruction::CallFunction {
+                template_address: event_emitter_template,
+                function: "emit_typed".to_string(),
+                args: args![Amount(123), account],
+            }],
+            vec![],
+        )
+        .unwrap();
+    assert!(result.finalize.is_accept());
+    assert_eq!(result.finalize.events.len(), 1);
+    let event = &result.finalize.events[0];
+    assert_eq!(event.topic(), "Transferred");
+    assert_eq!(*event.payload(), Metadata::new());
+    assert_eq!(
+        *event.typed_payload().unwrap(),
+        cbor!({
+            "amount" => Amount(123),
+            "recipient" => account,
+            "memo" => "typed",
+        })
+        .unwrap()
+    );
+}
+
+#[test]
+fn it_rejects_undeclared_or_mismatched_typed_events() {
+    let mut template_test = TemplateTest::new(vec!["tests/templates/events"]);
+    let event_emitter_template = template_test.get_template_address("EventEmitter");
+    let (_, _, private_key) = template_test.create_funded_account();
+
+    let reason = template_test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(event_emitter_template, "emit_undeclared", args![])
+            .build_and_seal(&private_key),
+        [].into(),
+    );
+    assert_reject_reason(reason, RuntimeError::UndeclaredEvent {
+        template_address: event_emitter_template,
+        topic: "Undeclared".to_string(),
+    });
+
+    let reason = template_test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(event_emitter_template, "emit_mismatched", args![])
+            .build_and_seal(&private_key),
+        [].into(),
+    );
+    assert_reject_reason(reason, RuntimeError::InvalidEventPayload {
+        topic: "Transferred".to_string(),
+        details: "expected fields [amount, memo, recipient] but got [amount]".to_string(),
+    });
+
+    let reason = template_test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(event_emitter_template, "emit_mistyped", args![])
+            .build_and_seal(&private_key),
+        [].into(),
+    );
+    assert_reject_reason(reason, RuntimeError::InvalidEventPayload {
+        topic: "Counted".to_string(),
+        details: "field 'count' does not match the declared type U64".to_string(),
+    });
+}
+
+#[test]
+fn it_accepts_typed_events_declared_with_a_qualified_attribute() {
+    let mut template_test = TemplateTest::new(vec!["tests/templates/events"]);
+    let event_emitter_template = template_test.get_template_address("EventEmitter");
+
+    let template_def = template_test.get_module("EventEmitter").template_def().clone();
+    let event_def = template_def.get_event("Counted").unwrap();
+    assert_eq!(event_def.fields[1].arg_type, Type::Vec(Box::new(Type::String)));
+
+    let result = template_test
+        .execute_and_commit(
+            vec![Instruction::CallFunction {
+                template_address: event_emitter_template,
+                function: "emit_counted".to_string(),
+                args: args![5u64],
+            }],
+            vec![],
+        )
+        .unwrap();
+    assert!(result.finalize.is_accept());
+    assert_eq!(result.finalize.events[0].topic(), "Counted");
+}
+
 #[test]
 fn builtin_vault_events() {
     let mut template_test = TemplateTest::new(Vec::<&str>::new());
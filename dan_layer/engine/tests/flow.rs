@@ -0,0 +1,148 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::fs;
+
+use tari_dan_engine::flow::{FlowEngineError, FlowFactory};
+use tari_engine_types::{
+    commit_result::RejectReason,
+    hashing::hash_template_code,
+    instruction::Instruction,
+    substate::SubstateValue,
+};
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+#[test]
+fn it_returns_a_value_from_a_conditional_flow() {
+    let mut test = TemplateTest::new(["tests/flows/max.json"]);
+
+    let max: u64 = test.call_function("FlowMax", "main", args![3u64, 7u64], vec![]);
+    assert_eq!(max, 7);
+    let max: u64 = test.call_function("FlowMax", "main", args![9u64, 2u64], vec![]);
+    assert_eq!(max, 9);
+}
+
+#[test]
+fn it_repeats_the_loop_body() {
+    let mut test = TemplateTest::new(["tests/flows/ticks.json"]);
+    let flow_template = test.get_template_address("FlowTicks");
+
+    let result = test
+        .execute_and_commit(
+            vec![Instruction::CallFunction {
+                template_address: flow_template,
+                function: "main".to_string(),
+                args: args![3u64],
+            }],
+            vec![],
+        )
+        .unwrap();
+
+    let output: String = result.finalize.execution_results[0].decode().unwrap();
+    assert_eq!(output, "done");
+    let ticks = result
+        .finalize
+        .events
+        .iter()
+        .filter(|e| e.topic() == "tick")
+        .map(|e| e.get_payload("index").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ticks, ["0", "1", "2"]);
+}
+
+#[test]
+fn it_stops_flows_that_exceed_the_step_limit() {
+    let mut test = TemplateTest::new(["tests/flows/ticks.json"]);
+    let flow_template = test.get_template_address("FlowTicks");
+    let (_, _, private_key) = test.create_funded_account();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(flow_template, "main", args![10_000u64])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert_reject_reason(reason, FlowEngineError::MaxStepsExceeded { max_steps: 1000 });
+}
+
+#[test]
+fn it_stops_repeat_loops_with_an_empty_body() {
+    let mut test = TemplateTest::new(["tests/flows/empty_repeat.json"]);
+    let flow_template = test.get_template_address("FlowEmptyRepeat");
+    let (_, _, private_key) = test.create_funded_account();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(flow_template, "main", args![u64::MAX])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert_reject_reason(reason, FlowEngineError::MaxStepsExceeded { max_steps: 1000 });
+}
+
+#[test]
+fn it_calls_component_methods() {
+    let mut test = TemplateTest::new(["tests/templates/state", "tests/flows/set_state.json"]);
+    let component: ComponentAddress = test.call_function("State", "new", args![], vec![]);
+
+    let value: u64 = test.call_function("FlowSetState", "main", args![component, 42u64], vec![]);
+    assert_eq!(value, 42);
+    let value: u32 = test.call_method(component, "get", args![], vec![]);
+    assert_eq!(value, 42);
+}
+
+#[test]
+fn it_rejects_invalid_flows() {
+    let json = fs::read("tests/flows/max.json").unwrap();
+    let mut definition: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+    let mut no_start = definition.clone();
+    no_start["flow"]["nodes"].as_object_mut().unwrap().remove("1");
+    let err = FlowFactory::try_from_json(&serde_json::to_vec(&no_start).unwrap()).unwrap_err();
+    assert!(matches!(err, FlowEngineError::InvalidFlow { .. }), "{}", err);
+
+    definition["flow"]["nodes"]["3"]["name"] = "core::unknown".into();
+    let err = FlowFactory::try_from_json(&serde_json::to_vec(&definition).unwrap()).unwrap_err();
+    assert!(matches!(err, FlowEngineError::InvalidFlow { .. }), "{}", err);
+
+    let err = FlowFactory::try_from_json(b"{\"name\": \"NotAFlow\"}").unwrap_err();
+    assert!(matches!(err, FlowEngineError::InvalidFlow { .. }), "{}", err);
+}
+
+#[test]
+fn it_publishes_flow_templates() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(250_000));
+    let json = fs::read("tests/flows/max.json").unwrap();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(json.clone())
+            .build_and_seal(&account_key),
+        vec![owner_proof.clone()],
+    );
+    let diff = result.finalize.result.accept().unwrap();
+    let expected_hash = hash_template_code(&json);
+    assert!(diff.up_iter().any(|(_, s)| matches!(
+        s.substate_value(),
+        SubstateValue::Template(t) if t.binary_hash == expected_hash
+    )));
+
+    let mut invalid: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    invalid["flow"]["nodes"].as_object_mut().unwrap().remove("1");
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(serde_json::to_vec(&invalid).unwrap())
+            .build_and_seal(&account_key),
+        vec![owner_proof],
+    );
+    assert!(matches!(reason, RejectReason::ExecutionFailure(_)));
+    assert_reject_reason(reason, "Invalid flow: the flow has no start node");
+}
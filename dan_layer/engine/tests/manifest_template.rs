@@ -0,0 +1,161 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::fs;
+
+use tari_engine_types::{commit_result::RejectReason, hashing::hash_template_code, substate::SubstateValue};
+use tari_template_lib::{args, constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS, models::Amount};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, test_faucet_component, TemplateTest};
+use tari_transaction::Transaction;
+
+#[test]
+fn it_expands_manifest_functions_in_the_calling_transaction() {
+    let mut test = TemplateTest::new(["tests/manifests/airdrop.manifest"]);
+    let airdrop_template = test.get_template_address("Airdrop");
+    let (first, _, _) = test.create_empty_account();
+    let (second, _, _) = test.create_empty_account();
+    let (_, _, private_key) = test.create_funded_account();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(airdrop_template, "airdrop_two", args![
+                test_faucet_component(),
+                first,
+                second,
+                Amount(100)
+            ])
+            .call_method(first, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS])
+            .call_method(second, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+
+    assert_eq!(
+        result.finalize.execution_results[1].decode::<Amount>().unwrap(),
+        Amount(100)
+    );
+    assert_eq!(
+        result.finalize.execution_results[2].decode::<Amount>().unwrap(),
+        Amount(100)
+    );
+}
+
+#[test]
+fn it_keeps_manifest_function_variables_separate_from_the_caller() {
+    let mut test = TemplateTest::new(["tests/manifests/airdrop.manifest"]);
+    let airdrop_template = test.get_template_address("Airdrop");
+    let (first, _, _) = test.create_empty_account();
+    let (second, _, _) = test.create_empty_account();
+    let (_, _, private_key) = test.create_funded_account();
+
+    // The caller and the manifest function both use the "first_coins" variable
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(test_faucet_component(), "take_free_coins_custom", args![Amount(50)])
+            .put_last_instruction_output_on_workspace("first_coins")
+            .call_function(airdrop_template, "airdrop_two", args![
+                test_faucet_component(),
+                first,
+                second,
+                Amount(100)
+            ])
+            .call_method(second, "deposit", args![Workspace("first_coins")])
+            .call_method(first, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS])
+            .call_method(second, "balance", args![CONFIDENTIAL_TARI_RESOURCE_ADDRESS])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+
+    assert_eq!(
+        result.finalize.execution_results[4].decode::<Amount>().unwrap(),
+        Amount(100)
+    );
+    assert_eq!(
+        result.finalize.execution_results[5].decode::<Amount>().unwrap(),
+        Amount(150)
+    );
+
+    // Variables of the manifest function are dropped once it returns
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(airdrop_template, "take_coins", args![
+                test_faucet_component(),
+                Amount(100)
+            ])
+            .call_method(first, "deposit", args![Workspace("coins")])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert_reject_reason(reason, "No workspace item named coins");
+}
+
+#[test]
+fn it_rejects_invalid_manifest_calls() {
+    let mut test = TemplateTest::new(["tests/manifests/airdrop.manifest"]);
+    let airdrop_template = test.get_template_address("Airdrop");
+    let (account, _, _) = test.create_empty_account();
+    let (_, _, private_key) = test.create_funded_account();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(airdrop_template, "airdrop_two", args![test_faucet_component(), account])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert_reject_reason(reason, "expects 4 argument(s) but got 2");
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(airdrop_template, "airdrop_two", args![
+                "not a component",
+                account,
+                account,
+                Amount(100)
+            ])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert_reject_reason(reason, "Invalid argument 'faucet'");
+
+    // The expanded instructions are subject to the same checks as any other instruction
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(airdrop_template, "take_coins", args![
+                test_faucet_component(),
+                Amount(100)
+            ])
+            .build_and_seal(&private_key),
+        vec![],
+    );
+    assert!(matches!(reason, RejectReason::ExecutionFailure(_)));
+}
+
+#[test]
+fn it_publishes_manifest_templates() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(250_000));
+    let source = fs::read("tests/manifests/airdrop.manifest").unwrap();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(source.clone())
+            .build_and_seal(&account_key),
+        vec![owner_proof.clone()],
+    );
+    let diff = result.finalize.result.accept().unwrap();
+    let expected_hash = hash_template_code(&source);
+    assert!(diff.up_iter().any(|(_, s)| matches!(
+        s.substate_value(),
+        SubstateValue::Template(t) if t.binary_hash == expected_hash
+    )));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(b"mod Broken { fn oops( }".to_vec())
+            .build_and_seal(&account_key),
+        vec![owner_proof],
+    );
+    assert_reject_reason(reason, "Manifest error");
+}
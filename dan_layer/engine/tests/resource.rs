@@ -1,8 +1,13 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_template_lib::{args, models::ComponentAddress};
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress, ResourceAddress},
+    resource::MAX_DIVISIBILITY,
+};
 use tari_template_test_tooling::{support::confidential::generate_confidential_proof, TemplateTest};
+use tari_transaction::Transaction;
 
 #[test]
 fn fungible_join() {
@@ -25,3 +30,28 @@ fn confidential_join() {
     let (output, _, _) = generate_confidential_proof(1000.into(), None);
     test.call_method::<()>(component, "confidential_join", args![output], vec![]);
 }
+
+#[test]
+fn fungible_divisibility() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let resource_address: ResourceAddress =
+        test.call_function("ResourceTest", "create_with_divisibility", args![6u8], vec![]);
+    let resource = test.read_only_state_store().get_resource(&resource_address).unwrap();
+    assert_eq!(resource.divisibility(), 6);
+    assert_eq!(resource.format_amount(Amount(1_500_000)), "1.5");
+    assert_eq!(resource.parse_amount("0.25"), Some(Amount(250_000)));
+}
+
+#[test]
+fn it_rejects_divisibility_above_max() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let template_address = test.get_template_address("ResourceTest");
+    test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(template_address, "create_with_divisibility", args![
+                MAX_DIVISIBILITY + 1
+            ])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![],
+    );
+}
@@ -1,7 +1,8 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_engine_types::instruction::Instruction;
+use tari_bor::cbor;
+use tari_engine_types::{commit_result::ExecuteResult, instruction::Instruction, substate::SubstateId};
 use tari_template_lib::{
     args,
     models::{Amount, ComponentAddress},
@@ -124,7 +125,12 @@ fn fund_account(
         .unwrap();
 }
 
-fn swap(test: &mut TariSwapTest, input_resource: &ResourceAddress, output_resource: &ResourceAddress, amount: Amount) {
+fn swap(
+    test: &mut TariSwapTest,
+    input_resource: &ResourceAddress,
+    output_resource: &ResourceAddress,
+    amount: Amount,
+) -> ExecuteResult {
     test.template_test
         .execute_and_commit(
             vec![
@@ -153,7 +159,7 @@ fn swap(test: &mut TariSwapTest, input_resource: &ResourceAddress, output_resour
             // proof needed to withdraw
             vec![test.account_proof.clone()],
         )
-        .unwrap();
+        .unwrap()
 }
 
 fn add_liquidity(test: &mut TariSwapTest, a_amount: Amount, b_amount: Amount) {
@@ -261,7 +267,26 @@ fn assert_swap(
     let output_pool_balance = get_pool_balance(test, *output_resource);
 
     // call the component
-    swap(test, input_resource, output_resource, input_amount);
+    let result = swap(test, input_resource, output_resource, input_amount);
+
+    // check that the swap event was emitted with the typed payload
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "Swap")
+        .expect("Swap event not emitted");
+    assert_eq!(event.substate_id(), Some(&SubstateId::Component(test.tariswap)));
+    assert_eq!(
+        *event.typed_payload().unwrap(),
+        cbor!({
+            "input_resource" => input_resource,
+            "input_amount" => input_amount,
+            "output_resource" => output_resource,
+            "output_amount" => expected_output_amount,
+        })
+        .unwrap()
+    );
 
     // check that the new pool balances are expected
     let new_input_pool_balance = get_pool_balance(test, *input_resource);
@@ -0,0 +1,40 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::{
+    crypto::{self, HashAlgorithm, SchnorrSignatureBytes},
+    prelude::*,
+    Hash,
+};
+
+#[template]
+mod template {
+    use super::*;
+
+    pub struct CryptoTest;
+
+    impl CryptoTest {
+        pub fn verify_signature(
+            public_key: RistrettoPublicKeyBytes,
+            public_nonce: RistrettoPublicKeyBytes,
+            signature: SchnorrSignatureBytes,
+            message: Vec<u8>,
+        ) -> bool {
+            crypto::verify_schnorr_signature(&public_key, &public_nonce, &signature, message)
+        }
+
+        pub fn hash_sha256(domain: String, data: Vec<u8>) -> Hash {
+            crypto::hash(HashAlgorithm::Sha256, &domain, data)
+        }
+
+        pub fn commitments_balance(
+            inputs: Vec<PedersonCommitmentBytes>,
+            output: PedersonCommitmentBytes,
+            revealed: u64,
+        ) -> bool {
+            let total_in = crypto::add_commitments(inputs);
+            let total_out = crypto::add_commitments([output, crypto::commit_value(revealed)]);
+            crypto::subtract_commitments(&total_in, &total_out) == crypto::commit_value(0)
+        }
+    }
+}
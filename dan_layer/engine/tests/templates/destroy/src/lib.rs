@@ -0,0 +1,64 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod template {
+    use super::*;
+
+    pub struct DestroyTest {
+        vault: Option<Vault>,
+        nft_resource: ResourceAddress,
+    }
+
+    impl DestroyTest {
+        pub fn new() -> Component<Self> {
+            let bucket = ResourceBuilder::fungible()
+                .burnable(rule!(allow_all))
+                .initial_supply(1000)
+                .build_bucket();
+            let nft_resource = ResourceBuilder::non_fungible()
+                .mintable(rule!(allow_all))
+                .burnable(rule!(allow_all))
+                .build();
+            Component::new(Self {
+                vault: Some(Vault::from_bucket(bucket)),
+                nft_resource,
+            })
+            .with_access_rules(AccessRules::new().default(rule!(allow_all)))
+            .create()
+        }
+
+        pub fn burn_all(&mut self) {
+            let bucket = self.vault.as_mut().unwrap().withdraw_all();
+            bucket.burn();
+        }
+
+        pub fn destroy_vault(&mut self) {
+            self.vault.take().unwrap().destroy();
+        }
+
+        pub fn destroy_vault_but_keep_it(&mut self) {
+            let vault = self.vault.as_ref().unwrap();
+            Vault::for_test(vault.vault_id()).destroy();
+        }
+
+        pub fn mint_and_burn_nft(&self, id: u64) {
+            let bucket =
+                ResourceManager::get(self.nft_resource).mint_non_fungible(NonFungibleId::from_u64(id), &(), &());
+            bucket.burn();
+        }
+
+        pub fn destroy_burnt_nft(&self, id: u64) {
+            ResourceManager::get(self.nft_resource).destroy_burnt_non_fungibles([NonFungibleId::from_u64(id)]);
+        }
+
+        pub fn destroy(&mut self) {
+            if let Some(vault) = self.vault.take() {
+                vault.destroy();
+            }
+            ComponentManager::current().destroy();
+        }
+    }
+}
@@ -30,11 +30,82 @@ mod event {
 
     pub struct EventEmitter {}
 
+    #[event]
+    pub struct Transferred {
+        amount: Amount,
+        recipient: ComponentAddress,
+        memo: Option<String>,
+    }
+
+    #[tari_template_lib::prelude::event]
+    pub struct Counted {
+        count: u64,
+        labels: Vec<String>,
+    }
+
+    // Not declared as an event, so it is not included in the ABI
+    pub struct Undeclared {
+        value: u64,
+    }
+
+    impl TemplateEvent for Undeclared {
+        const TOPIC: &'static str = "Undeclared";
+    }
+
+    // Uses the topic of a declared event with fields that do not match the declaration
+    pub struct MismatchedTransferred {
+        amount: Amount,
+    }
+
+    impl TemplateEvent for MismatchedTransferred {
+        const TOPIC: &'static str = "Transferred";
+    }
+
+    // Uses the topic of a declared event with a field type that does not match the declaration
+    pub struct MistypedCounted {
+        count: String,
+        labels: Vec<String>,
+    }
+
+    impl TemplateEvent for MistypedCounted {
+        const TOPIC: &'static str = "Counted";
+    }
+
     impl EventEmitter {
         pub fn test_function(topic: String) {
             println!("Emitting a new event");
             let payload = [("my", "event")];
             emit_event(topic, payload);
         }
+
+        pub fn emit_typed(amount: Amount, recipient: ComponentAddress) {
+            emit(&Transferred {
+                amount,
+                recipient,
+                memo: Some("typed".to_string()),
+            });
+        }
+
+        pub fn emit_undeclared() {
+            emit(&Undeclared { value: 1 });
+        }
+
+        pub fn emit_mismatched() {
+            emit(&MismatchedTransferred { amount: Amount(1) });
+        }
+
+        pub fn emit_counted(count: u64) {
+            emit(&Counted {
+                count,
+                labels: vec!["count".to_string()],
+            });
+        }
+
+        pub fn emit_mistyped() {
+            emit(&MistypedCounted {
+                count: "1".to_string(),
+                labels: vec![],
+            });
+        }
     }
 }
@@ -50,6 +50,12 @@ mod template {
             .create()
         }
 
+        pub fn create_with_divisibility(divisibility: u8) -> ResourceAddress {
+            let resource_address = ResourceBuilder::fungible().with_divisibility(divisibility).build();
+            assert_eq!(ResourceManager::get(resource_address).divisibility(), divisibility);
+            resource_address
+        }
+
         pub fn fungible_join(&self) {
             let b1 = self.fungible.withdraw(10);
             let b2 = self.fungible.withdraw(900);
@@ -71,6 +71,17 @@ mod template {
             ComponentManager::get(dest_component).call("deposit", args![stolen])
         }
 
+        /// Withdraws from an account on behalf of the transaction signer and sends the funds elsewhere
+        pub fn attempt_to_drain_account(
+            account: ComponentAddress,
+            resource: ResourceAddress,
+            amount: Amount,
+            dest_component: ComponentAddress,
+        ) {
+            let stolen: Bucket = ComponentManager::get(account).call("withdraw", args![resource, amount]);
+            ComponentManager::get(dest_component).call("deposit", args![stolen])
+        }
+
         pub fn with_vault_copy() -> Self {
             let vault = Vault::new_empty(CONFIDENTIAL_TARI_RESOURCE_ADDRESS);
             let vault_copy = Vault::for_test(vault.vault_id());
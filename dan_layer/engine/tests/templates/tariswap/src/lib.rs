@@ -34,6 +34,14 @@ mod tariswap {
         fee: u16,
     }
 
+    #[event]
+    pub struct Swap {
+        input_resource: ResourceAddress,
+        input_amount: Amount,
+        output_resource: ResourceAddress,
+        output_amount: Amount,
+    }
+
     impl TariSwapPool {
         // Initialises a new pool component for for the pool A - B
         // the fees is represented as a per-mil quantity (e.g. "1" represents "0.1%")
@@ -106,10 +114,20 @@ mod tariswap {
 
             // perform the swap
             self.pools.get_mut(&input_resource).unwrap().deposit(input_bucket);
-            self.pools
+            let output_bucket = self
+                .pools
                 .get_mut(&output_resource)
                 .unwrap()
-                .withdraw(output_bucket_amount)
+                .withdraw(output_bucket_amount);
+
+            emit(&Swap {
+                input_resource,
+                input_amount: Amount::new(input_bucket_balance),
+                output_resource,
+                output_amount: output_bucket_amount,
+            });
+
+            output_bucket
         }
 
         pub fn add_liquidity(&mut self, a_bucket: Bucket, b_bucket: Bucket) -> Bucket {
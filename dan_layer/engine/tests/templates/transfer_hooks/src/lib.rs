@@ -0,0 +1,123 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+/// Deposits of this token are always rejected by the deposit hook
+pub const BLOCKED_NFT_ID: u32 = 3;
+
+#[template]
+mod transfer_hooks_template {
+    use super::*;
+
+    /// An NFT collection that charges a royalty, in its own coin resource, every time one of its NFTs is withdrawn by
+    /// another component
+    pub struct RoyaltyNft {
+        royalty: Amount,
+        nft_resource: ResourceAddress,
+        coin_resource: ResourceAddress,
+        nfts: Vault,
+        coins: Vault,
+        royalties: Vault,
+        num_transfers: u32,
+    }
+
+    impl RoyaltyNft {
+        pub fn new(royalty: Amount) -> Component<RoyaltyNft> {
+            let address_alloc = CallerContext::allocate_component_address(None);
+
+            let nfts = ResourceBuilder::non_fungible()
+                .with_deposit_hook(*address_alloc.address(), "on_deposit")
+                .with_withdraw_hook(*address_alloc.address(), "on_withdraw")
+                .initial_supply((1..=BLOCKED_NFT_ID).map(NonFungibleId::from_u32));
+
+            let nft_resource = nfts.resource_address();
+            let coins = ResourceBuilder::fungible().initial_supply(1000);
+            let coin_resource = coins.resource_address();
+
+            Component::new(Self {
+                royalty,
+                nft_resource,
+                coin_resource,
+                nfts: Vault::from_bucket(nfts),
+                coins: Vault::from_bucket(coins),
+                royalties: Vault::new_empty(coin_resource),
+                num_transfers: 0,
+            })
+            .with_address_allocation(address_alloc)
+            .with_access_rules(ComponentAccessRules::new().default(rule!(allow_all)))
+            .create()
+        }
+
+        pub fn with_withdraw_hook(hook: String) -> Component<RoyaltyNft> {
+            let address_alloc = CallerContext::allocate_component_address(None);
+
+            let nfts = ResourceBuilder::non_fungible()
+                .with_withdraw_hook(*address_alloc.address(), hook)
+                .initial_supply([NonFungibleId::from_u32(1)]);
+
+            let nft_resource = nfts.resource_address();
+            let coins = ResourceBuilder::fungible().initial_supply(1000);
+            let coin_resource = coins.resource_address();
+
+            Component::new(Self {
+                royalty: Amount::zero(),
+                nft_resource,
+                coin_resource,
+                nfts: Vault::from_bucket(nfts),
+                coins: Vault::from_bucket(coins),
+                royalties: Vault::new_empty(coin_resource),
+                num_transfers: 0,
+            })
+            .with_address_allocation(address_alloc)
+            .with_access_rules(ComponentAccessRules::new().default(rule!(allow_all)))
+            .create()
+        }
+
+        pub fn take_nft(&mut self, id: u32) -> Bucket {
+            self.nfts.withdraw_non_fungible(NonFungibleId::from_u32(id))
+        }
+
+        pub fn take_coins(&mut self, amount: Amount) -> Bucket {
+            self.coins.withdraw(amount)
+        }
+
+        pub fn royalty_balance(&self) -> Amount {
+            self.royalties.balance()
+        }
+
+        pub fn on_withdraw(&mut self, context: TransferHookContext, payment: Option<Bucket>) {
+            assert!(context.is_withdraw(), "Expected a withdraw context");
+            let payment = payment.unwrap_or_else(|| panic!("Royalty payment required"));
+            assert_eq!(
+                payment.resource_address(),
+                self.coin_resource,
+                "Royalty must be paid in the coin resource"
+            );
+            let required = self.royalty.saturating_mul(&context.amount);
+            if payment.amount() < required {
+                panic!("Royalty of {} required but {} was paid", required, payment.amount());
+            }
+            self.royalties.deposit(payment);
+            self.num_transfers += 1;
+        }
+
+        pub fn on_deposit(&self, context: TransferHookContext, payment: Option<Bucket>) {
+            assert!(context.is_deposit(), "Expected a deposit context");
+            assert!(payment.is_none(), "Deposits do not accept payment");
+            if context.non_fungible_ids.contains(&NonFungibleId::from_u32(BLOCKED_NFT_ID)) {
+                panic!("NFT {} cannot be deposited", BLOCKED_NFT_ID);
+            }
+        }
+
+        pub fn invalid_hook1(&self, _context: TransferHookContext, _payment: Option<Bucket>) -> u32 {
+            0
+        }
+
+        pub fn invalid_hook2(&self, _context: String, _payment: Option<Bucket>) {}
+
+        pub fn invalid_hook3(&self, _context: TransferHookContext, _payment: Bucket) {}
+
+        pub fn invalid_hook4(&self, _context: TransferHookContext) {}
+    }
+}
@@ -87,6 +87,34 @@ fn test_state() {
     assert_eq!(value, new_value);
 }
 
+#[test]
+fn it_rejects_writes_to_read_only_inputs() {
+    let mut template_test = TemplateTest::new(["tests/templates/state"]);
+    let component_address: ComponentAddress = template_test.call_function("State", "new", args![], vec![]);
+
+    // Reading from a read-only input is allowed
+    let result = template_test.execute_expect_success(
+        Transaction::builder()
+            .add_read_only_input(component_address)
+            .call_method(component_address, "get", args![])
+            .build_and_seal(template_test.get_test_secret_key()),
+        vec![],
+    );
+    assert_eq!(result.finalize.execution_results[0].decode::<u32>().unwrap(), 0);
+
+    let reason = template_test.execute_expect_failure(
+        Transaction::builder()
+            .add_read_only_input(component_address)
+            .call_method(component_address, "set", args![1u32])
+            .build_and_seal(template_test.get_test_secret_key()),
+        vec![],
+    );
+    assert_reject_reason(reason, "was declared read-only but was written to");
+
+    let value: u32 = template_test.call_method(component_address, "get", args![], vec![]);
+    assert_eq!(value, 0);
+}
+
 #[test]
 fn state_create_multiple_in_one_call() {
     let mut template_test = TemplateTest::new(["tests/templates/state"]);
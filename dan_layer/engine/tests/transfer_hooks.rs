@@ -0,0 +1,247 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_crypto::ristretto::RistrettoSecretKey;
+use tari_dan_engine::runtime::RuntimeError;
+use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress, NonFungibleAddress, NonFungibleId, ResourceAddress},
+    resource::TransferAction,
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+struct RoyaltyNftTest {
+    test: TemplateTest,
+    component: ComponentAddress,
+    nft_resource: ResourceAddress,
+    coin_resource: ResourceAddress,
+    seller: (ComponentAddress, NonFungibleAddress, RistrettoSecretKey),
+    buyer: ComponentAddress,
+}
+
+fn setup(royalty: Amount) -> RoyaltyNftTest {
+    let mut test = TemplateTest::new(["tests/templates/transfer_hooks"]);
+    let template = test.get_template_address("RoyaltyNft");
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(template, "new", args![royalty])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+    let component = result.finalize.execution_results[0]
+        .decode::<ComponentAddress>()
+        .unwrap();
+
+    let nft_resource = test.extract_component_value::<ResourceAddress>(component, "$.nft_resource");
+    let coin_resource = test.extract_component_value::<ResourceAddress>(component, "$.coin_resource");
+
+    let seller = test.create_empty_account();
+    let (buyer, _, _) = test.create_empty_account();
+
+    // The hook component withdraws without triggering its own withdraw hook, and the deposit hook accepts the NFT
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(component, "take_nft", args![1u32])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(component, "take_coins", args![Amount(100)])
+            .put_last_instruction_output_on_workspace("coins")
+            .call_method(seller.0, "deposit", args![Workspace("nft")])
+            .call_method(seller.0, "deposit", args![Workspace("coins")])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    RoyaltyNftTest {
+        test,
+        component,
+        nft_resource,
+        coin_resource,
+        seller,
+        buyer,
+    }
+}
+
+#[test]
+fn it_calls_the_withdraw_hook_with_a_payment() {
+    let RoyaltyNftTest {
+        mut test,
+        component,
+        nft_resource,
+        coin_resource,
+        seller: (seller, seller_proof, seller_key),
+        buyer,
+    } = setup(Amount(10));
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(seller, "withdraw", args![coin_resource, Amount(10)])
+            .put_last_instruction_output_on_workspace("royalty")
+            .call_method(seller, "withdraw_non_fungible_with_payment", args![
+                nft_resource,
+                NonFungibleId::from_u32(1),
+                Workspace("royalty")
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer, "deposit", args![Workspace("nft")])
+            .build_and_seal(&seller_key),
+        vec![seller_proof],
+    );
+
+    // The withdraw and the deposit hooks are both shown in the receipt
+    let hook_events = result
+        .finalize
+        .events
+        .iter()
+        .filter(|e| e.topic() == "std.resource.transfer_hook")
+        .collect::<Vec<_>>();
+    assert_eq!(hook_events.len(), 2);
+    assert_eq!(hook_events[0].substate_id(), Some(&SubstateId::Resource(nft_resource)));
+    assert_eq!(
+        hook_events[0].get_payload("action").unwrap(),
+        TransferAction::Withdraw.to_string()
+    );
+    assert_eq!(hook_events[0].get_payload("payment_amount").unwrap(), "10");
+    assert_eq!(
+        hook_events[1].get_payload("action").unwrap(),
+        TransferAction::Deposit.to_string()
+    );
+
+    let royalties: Amount = test.call_method(component, "royalty_balance", args![], vec![]);
+    assert_eq!(royalties, 10);
+    let num_transfers = test.extract_component_value::<u32>(component, "$.num_transfers");
+    assert_eq!(num_transfers, 1);
+}
+
+#[test]
+fn it_rejects_a_withdraw_without_payment() {
+    let RoyaltyNftTest {
+        mut test,
+        nft_resource,
+        seller: (seller, seller_proof, seller_key),
+        buyer,
+        ..
+    } = setup(Amount(10));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(seller, "withdraw_non_fungible", args![
+                nft_resource,
+                NonFungibleId::from_u32(1)
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer, "deposit", args![Workspace("nft")])
+            .build_and_seal(&seller_key),
+        vec![seller_proof],
+    );
+
+    assert_reject_reason(reason, "Royalty payment required");
+}
+
+#[test]
+fn it_rejects_a_withdraw_with_insufficient_payment() {
+    let RoyaltyNftTest {
+        mut test,
+        nft_resource,
+        coin_resource,
+        seller: (seller, seller_proof, seller_key),
+        buyer,
+        ..
+    } = setup(Amount(10));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(seller, "withdraw", args![coin_resource, Amount(9)])
+            .put_last_instruction_output_on_workspace("royalty")
+            .call_method(seller, "withdraw_non_fungible_with_payment", args![
+                nft_resource,
+                NonFungibleId::from_u32(1),
+                Workspace("royalty")
+            ])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer, "deposit", args![Workspace("nft")])
+            .build_and_seal(&seller_key),
+        vec![seller_proof],
+    );
+
+    assert_reject_reason(reason, "Royalty of 10 required but 9 was paid");
+}
+
+#[test]
+fn it_rejects_a_deposit_denied_by_the_deposit_hook() {
+    let RoyaltyNftTest {
+        mut test,
+        component,
+        buyer,
+        ..
+    } = setup(Amount(10));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "take_nft", args![3u32])
+            .put_last_instruction_output_on_workspace("nft")
+            .call_method(buyer, "deposit", args![Workspace("nft")])
+            .build_and_seal(test.get_test_secret_key()),
+        vec![test.get_test_proof()],
+    );
+
+    assert_reject_reason(reason, "NFT 3 cannot be deposited");
+}
+
+#[test]
+fn it_rejects_a_payment_if_the_resource_has_no_hook() {
+    let RoyaltyNftTest {
+        mut test,
+        coin_resource,
+        seller: (seller, seller_proof, seller_key),
+        buyer,
+        ..
+    } = setup(Amount(10));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(seller, "withdraw", args![coin_resource, Amount(10)])
+            .put_last_instruction_output_on_workspace("coins")
+            .call_method(seller, "withdraw", args![coin_resource, Amount(1)])
+            .put_last_instruction_output_on_workspace("payment")
+            .call_method(buyer, "deposit_with_payment", args![
+                Workspace("coins"),
+                Workspace("payment")
+            ])
+            .build_and_seal(&seller_key),
+        vec![seller_proof],
+    );
+
+    assert_reject_reason(reason, "was not accepted by a deposit transfer hook");
+}
+
+#[test]
+fn it_fails_if_transfer_hook_is_invalid() {
+    let mut test = TemplateTest::new(["tests/templates/transfer_hooks"]);
+    let template = test.get_template_address("RoyaltyNft");
+
+    [
+        "invalid_hook1",
+        "invalid_hook2",
+        "invalid_hook3",
+        "invalid_hook4",
+        "hook_doesnt_exist",
+    ]
+    .iter()
+    .for_each(|hook| {
+        let reason = test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(template, "with_withdraw_hook", args![hook])
+                .build_and_seal(test.get_test_secret_key()),
+            vec![test.get_test_proof()],
+        );
+
+        assert_reject_reason(reason, RuntimeError::InvalidArgument {
+            argument: "CreateResourceArg",
+            // Partial error text
+            reason: "Transfer hook".to_string(),
+        });
+    })
+}
@@ -307,6 +307,11 @@ pub enum RejectReason {
         abort_reason: String,
     },
     InsufficientFeesPaid(String),
+    ForeignProposalTimeout {
+        start_shard: u32,
+        end_shard: u32,
+        timeout_blocks: u64,
+    },
     Unknown,
 }
 
@@ -332,6 +337,17 @@ impl Display for RejectReason {
                 )
             },
             RejectReason::InsufficientFeesPaid(msg) => write!(f, "Insufficient fees paid: {}", msg),
+            RejectReason::ForeignProposalTimeout {
+                start_shard,
+                end_shard,
+                timeout_blocks,
+            } => {
+                write!(
+                    f,
+                    "Foreign shard group ({start_shard}-{end_shard}) did not send a proposal within {timeout_blocks} \
+                     blocks"
+                )
+            },
             RejectReason::Unknown => write!(f, "<unknown reject reason - this is not valid>"),
         }
     }
@@ -51,6 +51,15 @@ pub struct Event {
     tx_hash: Hash,
     topic: String,
     payload: Metadata,
+    /// The structured payload of an event declared in the template ABI. This is None for events emitted with a
+    /// string metadata payload.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serde_with::cbor_value::option"
+    )]
+    #[cfg_attr(feature = "ts", ts(type = "any", optional))]
+    typed_payload: Option<tari_bor::Value>,
 }
 
 impl Event {
@@ -67,6 +76,26 @@ impl Event {
             tx_hash,
             topic,
             payload,
+            typed_payload: None,
+        }
+    }
+
+    /// Creates an event with a structured payload. The top-level fields of the payload are not duplicated in the string
+    /// metadata payload.
+    pub fn new_typed(
+        substate_id: Option<SubstateId>,
+        template_address: TemplateAddress,
+        tx_hash: Hash,
+        topic: String,
+        typed_payload: tari_bor::Value,
+    ) -> Self {
+        Self {
+            substate_id,
+            template_address,
+            tx_hash,
+            topic,
+            payload: Metadata::new(),
+            typed_payload: Some(typed_payload),
         }
     }
 
@@ -119,6 +148,10 @@ impl Event {
         &self.payload
     }
 
+    pub fn typed_payload(&self) -> Option<&tari_bor::Value> {
+        self.typed_payload.as_ref()
+    }
+
     pub fn into_payload(self) -> Metadata {
         self.payload
     }
@@ -20,6 +20,9 @@ pub struct FeeReceipt {
     pub total_fees_paid: Amount,
     /// Breakdown of fee costs
     pub cost_breakdown: FeeBreakdown,
+    /// Storage fees rebated for destroying substates. This is deducted from the total fees charged.
+    #[serde(default)]
+    pub storage_rebate: Amount,
 }
 
 impl FeeReceipt {
@@ -32,7 +35,14 @@ impl FeeReceipt {
 
     /// The total amount of fees charged. This may be more than total_fees_paid if the user paid an insufficient amount.
     pub fn total_fees_charged(&self) -> Amount {
-        Amount::try_from(self.cost_breakdown.get_total()).unwrap()
+        Amount::try_from(self.cost_breakdown.get_total())
+            .unwrap()
+            .saturating_sub_positive(self.storage_rebate)
+    }
+
+    /// The storage fees rebated for destroying substates
+    pub fn storage_rebate(&self) -> Amount {
+        self.storage_rebate
     }
 
     pub fn total_refunded(&self) -> Amount {
@@ -73,6 +83,7 @@ pub enum FeeSource {
     Events,
     Logs,
     TransactionWeight,
+    Crypto,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
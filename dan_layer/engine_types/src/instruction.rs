@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use tari_common_types::types::PublicKey;
 use tari_template_lib::{
     args::{Arg, LogLevel},
-    auth::OwnerRule,
+    auth::{AccountPolicy, OwnerRule},
     models::{ComponentAddress, ResourceAddress, TemplateAddress},
     prelude::{AccessRules, Amount},
 };
@@ -26,10 +26,13 @@ pub enum Instruction {
         access_rules: Option<AccessRules>,
         #[cfg_attr(feature = "ts", ts(type = "string | null"))]
         workspace_bucket: Option<String>,
+        /// If set, the account enforces this policy itself instead of using owner token access rules
+        #[serde(default)]
+        policy: Option<AccountPolicy>,
     },
     CallFunction {
         #[serde(with = "serde_with::hex")]
-        #[cfg_attr(feature = "ts", ts(type = "Uint8Array"))]
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
         template_address: TemplateAddress,
         function: String,
         #[serde(deserialize_with = "crate::argument_parser::json_deserialize")]
@@ -39,8 +42,8 @@ pub enum Instruction {
         component_address: ComponentAddress,
         method: String,
         #[serde(deserialize_with = "crate::argument_parser::json_deserialize")]
-        // Argument parser takes an array of strings as input
-        #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+        // Argument parser takes an array of strings or args as input
+        #[cfg_attr(feature = "ts", ts(type = "Array<Arg | string>"))]
         args: Vec<Arg>,
     },
     PutLastInstructionOutputOnWorkspace {
@@ -92,6 +95,7 @@ impl Display for Instruction {
                 owner_rule,
                 access_rules,
                 workspace_bucket,
+                policy,
             } => {
                 write!(
                     f,
@@ -102,7 +106,7 @@ impl Display for Instruction {
                     Some(bucket) => write!(f, "{}", bucket)?,
                     None => write!(f, "None")?,
                 }
-                write!(f, " }}")
+                write!(f, ", policy: {:?} }}", policy)
             },
             Self::CallFunction {
                 template_address,
@@ -20,15 +20,15 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeSet};
 
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::PublicKey;
 use tari_template_lib::{
     auth::{AuthHook, OwnerRule, Ownership, ResourceAccessRules},
     crypto::RistrettoPublicKeyBytes,
-    models::{Amount, Metadata},
-    resource::{ResourceType, TOKEN_SYMBOL},
+    models::{Amount, Metadata, NonFungibleId},
+    resource::{ResourceType, TransferHooks, TOKEN_SYMBOL},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +48,16 @@ pub struct Resource {
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     view_key: Option<PublicKey>,
     auth_hook: Option<AuthHook>,
+    /// The number of decimal places used to display amounts of this resource. This is set on creation and cannot be
+    /// changed.
+    #[serde(default)]
+    divisibility: u8,
+    /// Component methods that are called when tokens of this resource are deposited into or withdrawn from a vault
+    #[serde(default)]
+    transfer_hooks: TransferHooks,
+    /// Non-fungible IDs that have been burnt and destroyed. These IDs can never be minted again.
+    #[serde(default)]
+    destroyed_non_fungible_ids: BTreeSet<NonFungibleId>,
 }
 
 impl Resource {
@@ -59,6 +69,8 @@ impl Resource {
         metadata: Metadata,
         view_key: Option<PublicKey>,
         auth_hook: Option<AuthHook>,
+        divisibility: u8,
+        transfer_hooks: TransferHooks,
     ) -> Self {
         Self {
             resource_type,
@@ -69,6 +81,9 @@ impl Resource {
             total_supply: 0.into(),
             view_key,
             auth_hook,
+            divisibility,
+            transfer_hooks,
+            destroyed_non_fungible_ids: BTreeSet::new(),
         }
     }
 
@@ -99,6 +114,10 @@ impl Resource {
         self.auth_hook.as_ref()
     }
 
+    pub fn transfer_hooks(&self) -> &TransferHooks {
+        &self.transfer_hooks
+    }
+
     pub fn access_rules(&self) -> &ResourceAccessRules {
         &self.access_rules
     }
@@ -145,4 +164,27 @@ impl Resource {
     pub fn token_symbol(&self) -> Option<&str> {
         self.metadata.get(TOKEN_SYMBOL).map(|s| s.as_str())
     }
+
+    pub fn divisibility(&self) -> u8 {
+        self.divisibility
+    }
+
+    /// Records that a burnt non-fungible has been destroyed so that its ID cannot be minted again
+    pub fn add_destroyed_non_fungible_id(&mut self, id: NonFungibleId) {
+        self.destroyed_non_fungible_ids.insert(id);
+    }
+
+    pub fn is_non_fungible_id_destroyed(&self, id: &NonFungibleId) -> bool {
+        self.destroyed_non_fungible_ids.contains(id)
+    }
+
+    /// Formats the amount as a decimal string using the divisibility of this resource
+    pub fn format_amount(&self, amount: Amount) -> String {
+        amount.to_decimal_string(self.divisibility)
+    }
+
+    /// Parses a decimal string into an amount using the divisibility of this resource
+    pub fn parse_amount(&self, s: &str) -> Option<Amount> {
+        Amount::from_decimal_str(s, self.divisibility)
+    }
 }
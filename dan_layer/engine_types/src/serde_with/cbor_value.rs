@@ -22,6 +22,28 @@ where D: Deserializer<'de> {
     }
 }
 
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(v: &Option<tari_bor::Value>, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            v.as_ref().map(CborValueJsonSerializeWrapper).serialize(s)
+        } else {
+            v.serialize(s)
+        }
+    }
+
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<tari_bor::Value>, D::Error>
+    where D: Deserializer<'de> {
+        if d.is_human_readable() {
+            let wrapper = Option::<CborValueJsonDeserializeWrapper>::deserialize(d)?;
+            Ok(wrapper.map(|w| w.0))
+        } else {
+            Option::<tari_bor::Value>::deserialize(d)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tari_bor::cbor;
@@ -23,7 +23,7 @@
 use std::{cmp, collections::HashMap, mem, num::NonZeroU32};
 
 use log::*;
-use tari_base_node_client::{grpc::GrpcBaseNodeClient, types::BaseLayerConsensusConstants, BaseNodeClient};
+use tari_base_node_client::{types::BaseLayerConsensusConstants, BaseNodeClient};
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_core::{blocks::BlockHeader, transactions::transaction_components::ValidatorNodeRegistration};
 use tari_dan_common_types::{
@@ -67,16 +67,17 @@ pub struct BaseLayerEpochManager<TGlobalStore, TBaseNodeClient, TLayerOneSubmitt
     is_initial_base_layer_sync_complete: bool,
 }
 
-impl<TAddr, TLayerOneSubmitter>
-    BaseLayerEpochManager<SqliteGlobalDbAdapter<TAddr>, GrpcBaseNodeClient, TLayerOneSubmitter>
+impl<TAddr, TBaseNodeClient, TLayerOneSubmitter>
+    BaseLayerEpochManager<SqliteGlobalDbAdapter<TAddr>, TBaseNodeClient, TLayerOneSubmitter>
 where
     TAddr: NodeAddressable + DerivableFromPublicKey,
+    TBaseNodeClient: BaseNodeClient,
     TLayerOneSubmitter: LayerOneTransactionSubmitter,
 {
     pub fn new(
         config: EpochManagerConfig,
         global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
-        base_node_client: GrpcBaseNodeClient,
+        base_node_client: TBaseNodeClient,
         layer_one_submitter: TLayerOneSubmitter,
         tx_events: broadcast::Sender<EpochManagerEvent>,
         node_public_key: PublicKey,
@@ -143,6 +144,49 @@ where
         Ok(())
     }
 
+    /// Reverts the effects of all base layer blocks above the given block, which must be on the current base layer
+    /// chain. Epochs and committees after the epoch of the block are removed and the current block is reset to the
+    /// given block.
+    ///
+    /// Validator node changes are fetched per epoch and do not record the block they came from, so all changes fetched
+    /// in the epoch of the block (the fork epoch) and later epochs are reverted. The caller MUST re-fetch the
+    /// validator node changes for the fork epoch from the new chain.
+    pub async fn rollback_to_block(
+        &mut self,
+        block_height: u64,
+        block_hash: FixedHash,
+    ) -> Result<(), EpochManagerError> {
+        let base_layer_constants = self.base_node_client.get_consensus_constants(block_height).await?;
+        let epoch = base_layer_constants.height_to_epoch(block_height);
+
+        let mut tx = self.global_db.create_transaction()?;
+        self.global_db
+            .validator_nodes(&mut tx)
+            .revert_changes_from_epoch(epoch)?;
+        self.global_db.epochs(&mut tx).delete_epochs_after(epoch.as_u64())?;
+        self.global_db.bmt(&mut tx).delete_bmts_after(epoch)?;
+        self.global_db
+            .base_layer(&mut tx)
+            .delete_base_layer_block_infos_above_height(block_height)?;
+        let mut metadata = self.global_db.metadata(&mut tx);
+        metadata.set_metadata(MetadataKey::EpochManagerCurrentBlockHeight, &(block_height, block_hash))?;
+        if self.current_epoch > epoch {
+            metadata.set_metadata(MetadataKey::EpochManagerCurrentEpoch, &epoch)?;
+        }
+        tx.commit()?;
+
+        if self.current_epoch > epoch {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ Base layer reorg rolled back the current epoch from {} to {}", self.current_epoch, epoch
+            );
+            self.current_epoch = epoch;
+        }
+        self.current_block_info = (block_height, block_hash);
+
+        Ok(())
+    }
+
     /// Assigns validators for the given epoch (makes them active) from the database.
     /// Max number of validators must be passed to limit the number of validators to make active in the given epoch.
     fn assign_validators_for_epoch(&mut self, epoch: Epoch) -> Result<(), EpochManagerError> {
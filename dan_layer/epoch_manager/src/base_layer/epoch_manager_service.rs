@@ -21,7 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use log::{error, info, trace};
-use tari_base_node_client::grpc::GrpcBaseNodeClient;
+use tari_base_node_client::BaseNodeClient;
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{optional::IsNotFoundError, DerivableFromPublicKey, NodeAddressable};
 use tari_dan_storage::global::GlobalDb;
@@ -50,10 +50,11 @@ pub struct EpochManagerService<TAddr, TGlobalStore, TBaseNodeClient, TLayerOneSu
     inner: BaseLayerEpochManager<TGlobalStore, TBaseNodeClient, TLayerOneSubmitter>,
 }
 
-impl<TAddr, TLayerOneSubmitter>
-    EpochManagerService<TAddr, SqliteGlobalDbAdapter<TAddr>, GrpcBaseNodeClient, TLayerOneSubmitter>
+impl<TAddr, TBaseNodeClient, TLayerOneSubmitter>
+    EpochManagerService<TAddr, SqliteGlobalDbAdapter<TAddr>, TBaseNodeClient, TLayerOneSubmitter>
 where
     TAddr: NodeAddressable + DerivableFromPublicKey + 'static,
+    TBaseNodeClient: BaseNodeClient + 'static,
     TLayerOneSubmitter: LayerOneTransactionSubmitter + Send + Sync + 'static,
 {
     pub fn spawn(
@@ -62,7 +63,7 @@ where
         rx_request: Receiver<EpochManagerRequest<TAddr>>,
         shutdown: ShutdownSignal,
         global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
-        base_node_client: GrpcBaseNodeClient,
+        base_node_client: TBaseNodeClient,
         layer_one_transaction_submitter: TLayerOneSubmitter,
         node_public_key: PublicKey,
     ) -> JoinHandle<anyhow::Result<()>> {
@@ -171,6 +172,17 @@ where
             } => {
                 handle(reply, self.inner.update_epoch(block_height, block_hash).await, context);
             },
+            EpochManagerRequest::RollbackToBlock {
+                block_height,
+                block_hash,
+                reply,
+            } => {
+                handle(
+                    reply,
+                    self.inner.rollback_to_block(block_height, block_hash).await,
+                    context,
+                );
+            },
             EpochManagerRequest::LastRegistrationEpoch { reply } => {
                 handle(reply, self.inner.last_registration_epoch(), context)
             },
@@ -65,6 +65,20 @@ impl<TAddr: NodeAddressable> EpochManagerHandle<TAddr> {
         rx.await.map_err(|_| EpochManagerError::ReceiveError)?
     }
 
+    /// Reverts the effects of all base layer blocks above the given block after a base layer reorg
+    pub async fn rollback_to_block(&self, block_height: u64, block_hash: FixedHash) -> Result<(), EpochManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx_request
+            .send(EpochManagerRequest::RollbackToBlock {
+                block_height,
+                block_hash,
+                reply: tx,
+            })
+            .await
+            .map_err(|_| EpochManagerError::SendError)?;
+        rx.await.map_err(|_| EpochManagerError::ReceiveError)?
+    }
+
     pub async fn get_base_layer_consensus_constants(&self) -> Result<BaseLayerConsensusConstants, EpochManagerError> {
         let (tx, rx) = oneshot::channel();
         self.tx_request
@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_base_node_client::grpc::GrpcBaseNodeClient;
+use tari_base_node_client::BaseNodeClient;
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{DerivableFromPublicKey, NodeAddressable};
 use tari_dan_storage::global::GlobalDb;
@@ -36,16 +36,17 @@ use crate::{
     traits::LayerOneTransactionSubmitter,
 };
 
-pub fn spawn_service<TAddr, TLayerOneSubmitter>(
+pub fn spawn_service<TAddr, TBaseNodeClient, TLayerOneSubmitter>(
     config: EpochManagerConfig,
     global_db: GlobalDb<SqliteGlobalDbAdapter<TAddr>>,
-    base_node_client: GrpcBaseNodeClient,
+    base_node_client: TBaseNodeClient,
     node_public_key: PublicKey,
     layer_one_submitter: TLayerOneSubmitter,
     shutdown: ShutdownSignal,
 ) -> (EpochManagerHandle<TAddr>, JoinHandle<anyhow::Result<()>>)
 where
     TAddr: NodeAddressable + DerivableFromPublicKey + 'static,
+    TBaseNodeClient: BaseNodeClient + 'static,
     TLayerOneSubmitter: LayerOneTransactionSubmitter + Send + Sync + 'static,
 {
     let (tx_request, rx_request) = mpsc::channel(10);
@@ -70,6 +70,11 @@ pub enum EpochManagerRequest<TAddr> {
         block_hash: FixedHash,
         reply: Reply<()>,
     },
+    RollbackToBlock {
+        block_height: u64,
+        block_hash: FixedHash,
+        reply: Reply<()>,
+    },
     LastRegistrationEpoch {
         reply: Reply<Option<Epoch>>,
     },
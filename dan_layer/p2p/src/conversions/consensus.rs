@@ -61,6 +61,7 @@ use tari_dan_storage::{
         QcId,
         QuorumCertificate,
         QuorumDecision,
+        SignerBitmap,
         SubstateDestroyed,
         SubstateRecord,
         TransactionAtom,
@@ -765,6 +766,7 @@ impl From<AbortReason> for proto::consensus::AbortReason {
             AbortReason::ForeignPledgeInputConflict => Self::ForeignPledgeInputConflict,
             AbortReason::InsufficientFeesPaid => Self::InsufficientFeesPaid,
             AbortReason::EarlyAbort => Self::EarlyAbort,
+            AbortReason::ForeignProposalTimeout => Self::ForeignProposalTimeout,
         }
     }
 }
@@ -786,6 +788,7 @@ impl From<proto::consensus::AbortReason> for AbortReason {
             proto::consensus::AbortReason::ForeignPledgeInputConflict => Self::ForeignPledgeInputConflict,
             proto::consensus::AbortReason::InsufficientFeesPaid => Self::InsufficientFeesPaid,
             proto::consensus::AbortReason::EarlyAbort => Self::EarlyAbort,
+            proto::consensus::AbortReason::ForeignProposalTimeout => Self::ForeignProposalTimeout,
         }
     }
 }
@@ -837,6 +840,8 @@ impl From<&QuorumCertificate> for proto::consensus::QuorumCertificate {
             block_height: source.block_height().as_u64(),
             epoch: source.epoch().as_u64(),
             shard_group: source.shard_group().encode_as_u32(),
+            committee_size: source.signers().committee_size() as u32,
+            signer_bitmap: source.signers().as_bytes().to_vec(),
             signatures: source.signatures().iter().map(Into::into).collect(),
             leaf_hashes: source.leaf_hashes().iter().map(|h| h.to_vec()).collect(),
             decision: i32::from(source.decision().as_u8()),
@@ -850,12 +855,19 @@ impl TryFrom<proto::consensus::QuorumCertificate> for QuorumCertificate {
     fn try_from(value: proto::consensus::QuorumCertificate) -> Result<Self, Self::Error> {
         let shard_group = ShardGroup::decode_from_u32(value.shard_group)
             .ok_or_else(|| anyhow!("QC shard_group ({}) is not a valid", value.shard_group))?;
+        let signers = SignerBitmap::from_parts(value.committee_size, value.signer_bitmap).ok_or_else(|| {
+            anyhow!(
+                "QC signer bitmap is not valid for committee size {}",
+                value.committee_size
+            )
+        })?;
         Ok(Self::new(
             value.header_hash.try_into().context("header_hash")?,
             value.parent_id.try_into().context("parent_id")?,
             NodeHeight(value.block_height),
             Epoch(value.epoch),
             shard_group,
+            signers,
             value
                 .signatures
                 .into_iter()
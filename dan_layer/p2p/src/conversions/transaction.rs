@@ -26,11 +26,11 @@ use anyhow::anyhow;
 use tari_bor::{decode_exact, encode};
 use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
 use tari_crypto::{ristretto::RistrettoComSig, tari_utilities::ByteArray};
-use tari_dan_common_types::{SubstateRequirement, VersionedSubstateId};
+use tari_dan_common_types::{InputLockIntent, SubstateRequirement, VersionedSubstateId};
 use tari_engine_types::{confidential::ConfidentialClaim, instruction::Instruction, substate::SubstateId};
 use tari_template_lib::{
     args::Arg,
-    auth::OwnerRule,
+    auth::{AccountPolicy, OwnerRule},
     crypto::{BalanceProofSignature, PedersonCommitmentBytes, RistrettoPublicKeyBytes},
     models::{
         Amount,
@@ -48,7 +48,7 @@ use tari_transaction::Transaction;
 use crate::{
     proto::{
         self,
-        transaction::{instruction::InstructionType, OptionalVersion},
+        transaction::{instruction::InstructionType, substate_requirement, OptionalVersion},
     },
     utils::checked_copy_fixed,
     NewTransactionMessage,
@@ -173,6 +173,7 @@ impl TryFrom<proto::transaction::Instruction> for Instruction {
                 owner_rule: request.create_account_owner_rule.map(TryInto::try_into).transpose()?,
                 access_rules: request.create_account_access_rules.map(TryInto::try_into).transpose()?,
                 workspace_bucket: Some(request.create_account_workspace_bucket).filter(|s| !s.is_empty()),
+                policy: request.create_account_policy.map(TryInto::try_into).transpose()?,
             },
             InstructionType::Function => {
                 let function = request.function;
@@ -251,12 +252,14 @@ impl From<Instruction> for proto::transaction::Instruction {
                 owner_rule,
                 access_rules,
                 workspace_bucket,
+                policy,
             } => {
                 result.instruction_type = InstructionType::CreateAccount as i32;
                 result.create_account_public_key = public_key_address.to_vec();
                 result.create_account_owner_rule = owner_rule.map(Into::into);
                 result.create_account_access_rules = access_rules.map(Into::into);
                 result.create_account_workspace_bucket = workspace_bucket.unwrap_or_default();
+                result.create_account_policy = policy.map(Into::into);
             },
             Instruction::CallFunction {
                 template_address,
@@ -364,7 +367,18 @@ impl TryFrom<proto::transaction::SubstateRequirement> for SubstateRequirement {
     fn try_from(val: proto::transaction::SubstateRequirement) -> Result<Self, Self::Error> {
         let substate_id = SubstateId::from_bytes(&val.substate_id)?;
         let version = val.version.map(|v| v.version);
-        let substate_specification = SubstateRequirement::new(substate_id, version);
+        let lock_intent = match substate_requirement::LockIntent::try_from(val.lock_intent)
+            .map_err(|e| anyhow!("invalid lock_intent {e}"))?
+        {
+            substate_requirement::LockIntent::Unspecified => None,
+            substate_requirement::LockIntent::Read => Some(InputLockIntent::Read),
+            substate_requirement::LockIntent::Write => Some(InputLockIntent::Write),
+        };
+        let substate_specification = SubstateRequirement {
+            substate_id,
+            version,
+            lock_intent,
+        };
         Ok(substate_specification)
     }
 }
@@ -380,6 +394,11 @@ impl From<&SubstateRequirement> for proto::transaction::SubstateRequirement {
         Self {
             substate_id: val.substate_id().to_bytes(),
             version: val.version().map(|v| OptionalVersion { version: v }),
+            lock_intent: match val.lock_intent() {
+                None => substate_requirement::LockIntent::Unspecified,
+                Some(InputLockIntent::Read) => substate_requirement::LockIntent::Read,
+                Some(InputLockIntent::Write) => substate_requirement::LockIntent::Write,
+            } as i32,
         }
     }
 }
@@ -615,3 +634,21 @@ impl TryFrom<proto::transaction::AccessRules> for AccessRules {
         Ok(decode_exact(&value.encoded_access_rules)?)
     }
 }
+
+// -------------------------------- AccountPolicy -------------------------------- //
+
+impl From<AccountPolicy> for proto::transaction::AccountPolicy {
+    fn from(value: AccountPolicy) -> Self {
+        Self {
+            encoded_account_policy: encode(&value).unwrap(),
+        }
+    }
+}
+
+impl TryFrom<proto::transaction::AccountPolicy> for AccountPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::transaction::AccountPolicy) -> Result<Self, Self::Error> {
+        Ok(decode_exact(&value.encoded_account_policy)?)
+    }
+}
@@ -49,6 +49,18 @@ create table blocks
 create unique index blocks_uniq_idx_id on blocks (block_id);
 create index blocks_idx_epoch_height on blocks (epoch, height);
 
+-- Maps transactions to the blocks that contain a command for them
+create table block_transactions
+(
+    id             integer not null primary key AUTOINCREMENT,
+    block_id       text    not null,
+    transaction_id text    not null,
+    FOREIGN KEY (block_id) REFERENCES blocks (block_id)
+);
+
+create unique index block_transactions_uniq_block_id_transaction_id on block_transactions (block_id, transaction_id);
+create index block_transactions_idx_transaction_id on block_transactions (transaction_id);
+
 create table parked_blocks
 (
     id                      integer   not null primary key AUTOINCREMENT,
@@ -10,7 +10,7 @@ use std::{
 use diesel::{
     query_builder::SqlQuery,
     sql_query,
-    sql_types::{BigInt, Text},
+    sql_types::{BigInt, Bool, Text},
     BoolExpressionMethods,
     ExpressionMethods,
     JoinOnDsl,
@@ -48,6 +48,7 @@ use tari_dan_storage::{
         BlockTransactionExecution,
         BurntUtxo,
         Command,
+        Decision,
         EpochCheckpoint,
         ForeignProposal,
         ForeignProposalAtom,
@@ -81,6 +82,7 @@ use tari_dan_storage::{
     Ordering,
     StateStoreReadTransaction,
     StorageError,
+    TransactionStatusFilter,
 };
 use tari_engine_types::{substate::SubstateId, template_models::UnclaimedConfidentialOutputAddress};
 use tari_state_tree::{Node, NodeKey, TreeNode, Version};
@@ -761,23 +763,53 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
     fn transactions_get_paginated(
         &self,
         limit: u64,
-        offset: u64,
-        asc_desc_created_at: Option<Ordering>,
+        after: Option<&TransactionId>,
+        status: Option<TransactionStatusFilter>,
+        ordering: Option<Ordering>,
     ) -> Result<Vec<TransactionRecord>, StorageError> {
         use crate::schema::transactions;
 
         let mut query = transactions::table.into_boxed();
 
-        if let Some(ordering) = asc_desc_created_at {
-            match ordering {
-                Ordering::Ascending => query = query.order_by(transactions::created_at.asc()),
-                Ordering::Descending => query = query.order_by(transactions::created_at.desc()),
-            }
+        let after_id = after
+            .map(|tx_id| {
+                transactions::table
+                    .select(transactions::id)
+                    .filter(transactions::transaction_id.eq(serialize_hex(tx_id)))
+                    .first::<i32>(self.connection())
+                    .map_err(|e| SqliteStorageError::DieselError {
+                        operation: "transactions_get_paginated",
+                        source: e,
+                    })
+            })
+            .transpose()?;
+
+        match ordering.unwrap_or(Ordering::Ascending) {
+            Ordering::Ascending => {
+                if let Some(after_id) = after_id {
+                    query = query.filter(transactions::id.gt(after_id));
+                }
+                query = query.order_by(transactions::id.asc());
+            },
+            Ordering::Descending => {
+                if let Some(after_id) = after_id {
+                    query = query.filter(transactions::id.lt(after_id));
+                }
+                query = query.order_by(transactions::id.desc());
+            },
+        }
+
+        match status {
+            Some(TransactionStatusFilter::Pending) => query = query.filter(transactions::final_decision.is_null()),
+            Some(TransactionStatusFilter::Committed) => {
+                query = query.filter(transactions::final_decision.eq(Decision::Commit.to_string()))
+            },
+            Some(TransactionStatusFilter::Aborted) => query = query.filter(transactions::final_decision.like("Abort%")),
+            None => {},
         }
 
         let transactions = query
             .limit(limit as i64)
-            .offset(offset as i64)
             .get_results::<sql_models::Transaction>(self.connection())
             .map_err(|e| SqliteStorageError::DieselError {
                 operation: "transactions_get_paginated",
@@ -1125,6 +1157,46 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .collect()
     }
 
+    fn blocks_get_committed_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: usize,
+    ) -> Result<Vec<Block>, StorageError> {
+        use crate::schema::{block_transactions, blocks, quorum_certificates};
+
+        let block_ids = block_transactions::table
+            .select(block_transactions::block_id)
+            .filter(block_transactions::transaction_id.eq(serialize_hex(transaction_id)));
+
+        let results = blocks::table
+            .left_join(quorum_certificates::table.on(blocks::qc_id.eq(quorum_certificates::qc_id)))
+            .select((blocks::all_columns, quorum_certificates::all_columns.nullable()))
+            .filter(blocks::block_id.eq_any(block_ids))
+            .filter(blocks::is_committed.eq(true))
+            .order_by(blocks::height.desc())
+            .limit(limit as i64)
+            .get_results::<(sql_models::Block, Option<sql_models::QuorumCertificate>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_get_committed_by_transaction",
+                source: e,
+            })?;
+
+        results
+            .into_iter()
+            .map(|(block, qc)| {
+                let qc = qc.ok_or_else(|| SqliteStorageError::DbInconsistency {
+                    operation: "blocks_get_committed_by_transaction",
+                    details: format!(
+                        "block {} references non-existent quorum certificate {}",
+                        block.block_id, block.qc_id
+                    ),
+                })?;
+
+                block.try_convert(qc)
+            })
+            .collect()
+    }
+
     fn blocks_get_parent_chain(&self, block_id: &BlockId, limit: usize) -> Result<Vec<Block>, StorageError> {
         if !self.blocks_exists(block_id)? {
             return Err(StorageError::QueryError {
@@ -1228,6 +1300,7 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         &self,
         limit: u64,
         offset: u64,
+        after: Option<&BlockId>,
         filter_index: Option<usize>,
         filter: Option<String>,
         ordering_index: Option<usize>,
@@ -1240,6 +1313,48 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .select((blocks::all_columns, quorum_certificates::all_columns.nullable()))
             .into_boxed();
 
+        if let Some(after) = after {
+            let after = serialize_hex(after);
+            // Check that the cursor exists so that an unknown cursor is an error rather than an empty page
+            blocks::table
+                .select(blocks::id)
+                .filter(blocks::block_id.eq(&after))
+                .first::<i32>(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "blocks_get_paginated",
+                    source: e,
+                })?;
+
+            // Compare the ordering key of each block (with the row id as a tie-breaker) to the key of the cursor block
+            let key_columns = blocks_ordering_key_columns(ordering_index);
+            let key = |table: &str| {
+                key_columns
+                    .iter()
+                    .map(|column| match *column {
+                        // NULLs sort first in SQLite, so they are compared as the lowest value
+                        "block_time" => format!("IFNULL({table}.block_time, -1)"),
+                        column => format!("{table}.{column}"),
+                    })
+                    .chain(Some(format!("{table}.id")))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let operator = match ordering {
+                Some(Ordering::Ascending) => ">",
+                _ => "<",
+            };
+            query = query.filter(
+                diesel::dsl::sql::<Bool>(&format!(
+                    "({}) {} (SELECT {} FROM blocks cursor_block WHERE cursor_block.block_id = ",
+                    key("blocks"),
+                    operator,
+                    key("cursor_block")
+                ))
+                .bind::<Text, _>(after)
+                .sql(")"),
+            );
+        }
+
         query = match ordering {
             Some(Ordering::Ascending) => match ordering_index {
                 Some(0) => query.order_by(blocks::block_id.asc()),
@@ -1251,7 +1366,8 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 Some(7) => query.order_by(blocks::created_at.asc()),
                 Some(8) => query.order_by(blocks::proposed_by.asc()),
                 _ => query.order_by(blocks::epoch.asc()).then_order_by(blocks::height.asc()),
-            },
+            }
+            .then_order_by(blocks::id.asc()),
             _ => match ordering_index {
                 Some(0) => query.order_by(blocks::block_id.desc()),
                 Some(1) => query.order_by(blocks::epoch.desc()),
@@ -1266,7 +1382,8 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 _ => query
                     .order_by(blocks::epoch.desc())
                     .then_order_by(blocks::height.desc()),
-            },
+            }
+            .then_order_by(blocks::id.desc()),
         };
 
         if let Some(filter) = filter {
@@ -1864,6 +1981,41 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count as usize)
     }
 
+    fn transaction_pool_get_stalled(
+        &self,
+        max_since_height: NodeHeight,
+        limit: usize,
+    ) -> Result<Vec<(TransactionId, NodeHeight)>, StorageError> {
+        // The earliest applied update for the current stage is the block in which the transaction entered that stage
+        let stalled = sql_query(
+            r#"
+            SELECT tp.transaction_id, MIN(tpsu.block_height) AS since_height
+            FROM transaction_pool AS tp
+            JOIN transaction_pool_state_updates AS tpsu
+                ON tpsu.transaction_id = tp.transaction_id AND tpsu.stage = tp.stage AND tpsu.is_applied = 1
+            WHERE tp.stage IN ('LocalPrepared', 'LocalAccepted') AND tp.is_ready = 0
+            GROUP BY tp.transaction_id
+            HAVING since_height <= ?
+            ORDER BY since_height ASC, tp.transaction_id ASC
+            LIMIT ?"#,
+        )
+        .bind::<BigInt, _>(max_since_height.as_u64() as i64)
+        .bind::<BigInt, _>(limit as i64)
+        .get_results::<StalledTransaction>(self.connection())
+        .map_err(|e| SqliteStorageError::DieselError {
+            operation: "transaction_pool_get_stalled",
+            source: e,
+        })?;
+
+        stalled
+            .into_iter()
+            .map(|s| {
+                let transaction_id = deserialize_hex_try_from(&s.transaction_id)?;
+                Ok((transaction_id, NodeHeight(s.since_height as u64)))
+            })
+            .collect()
+    }
+
     fn transactions_fetch_involved_shards(
         &self,
         transaction_ids: HashSet<TransactionId>,
@@ -2762,8 +2914,30 @@ struct Count {
     pub count: i64,
 }
 
+#[derive(QueryableByName)]
+struct StalledTransaction {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub transaction_id: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub since_height: i64,
+}
+
 #[derive(QueryableByName)]
 struct BlockIdSqlValue {
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub bid: String,
 }
+
+/// The columns that blocks are ordered by for the given `ordering_index` of `blocks_get_paginated`
+fn blocks_ordering_key_columns(ordering_index: Option<usize>) -> &'static [&'static str] {
+    match ordering_index {
+        Some(0) => &["block_id"],
+        Some(1) => &["epoch"],
+        Some(4) => &["command_count"],
+        Some(5) => &["total_leader_fee"],
+        Some(6) => &["block_time"],
+        Some(7) => &["created_at"],
+        Some(8) => &["proposed_by"],
+        _ => &["epoch", "height"],
+    }
+}
@@ -15,6 +15,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    block_transactions (id) {
+        id -> Integer,
+        block_id -> Text,
+        transaction_id -> Text,
+    }
+}
+
 diesel::table! {
     blocks (id) {
         id -> Integer,
@@ -559,6 +567,7 @@ diesel::joinable!(foreign_missing_transactions -> foreign_parked_blocks (parked_
 
 diesel::allow_tables_to_appear_in_same_query!(
     block_diffs,
+    block_transactions,
     blocks,
     burnt_utxos,
     diagnostic_deleted_blocks,
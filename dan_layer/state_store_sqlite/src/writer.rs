@@ -226,7 +226,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
     }
 
     fn blocks_insert(&mut self, block: &Block) -> Result<(), StorageError> {
-        use crate::schema::blocks;
+        use crate::schema::{block_transactions, blocks};
 
         let insert = (
             blocks::block_id.eq(serialize_hex(block.id())),
@@ -278,14 +278,43 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
             source: e,
         })?;
 
+        let block_transactions = block
+            .commands()
+            .iter()
+            .filter_map(|cmd| cmd.transaction())
+            .map(|atom| {
+                (
+                    block_transactions::block_id.eq(serialize_hex(block.id())),
+                    block_transactions::transaction_id.eq(serialize_hex(atom.id())),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        diesel::insert_into(block_transactions::table)
+            .values(block_transactions)
+            .on_conflict_do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_insert (block_transactions)",
+                source: e,
+            })?;
+
         Ok(())
     }
 
     fn blocks_delete(&mut self, block_id: &BlockId) -> Result<(), StorageError> {
-        use crate::schema::{blocks, diagnostic_deleted_blocks};
+        use crate::schema::{block_transactions, blocks, diagnostic_deleted_blocks};
 
         let block_id = serialize_hex(block_id);
 
+        diesel::delete(block_transactions::table)
+            .filter(block_transactions::block_id.eq(&block_id))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_delete (block_transactions)",
+                source: e,
+            })?;
+
         diesel::insert_into(diagnostic_deleted_blocks::table)
             .values(blocks::table.filter(blocks::block_id.eq(&block_id)))
             .execute(self.connection())
@@ -2170,6 +2199,24 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn burnt_utxos_delete_all_unproposed_above_base_layer_height(
+        &mut self,
+        height: u64,
+    ) -> Result<usize, StorageError> {
+        use crate::schema::burnt_utxos;
+
+        let num_deleted = diesel::delete(burnt_utxos::table)
+            .filter(burnt_utxos::base_layer_block_height.gt(height as i64))
+            .filter(burnt_utxos::proposed_in_block.is_null())
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "burnt_utxos_delete_all_unproposed_above_base_layer_height",
+                source: e,
+            })?;
+
+        Ok(num_deleted)
+    }
+
     fn lock_conflicts_insert_all<'a, I: IntoIterator<Item = (&'a TransactionId, &'a Vec<LockConflict>)>>(
         &mut self,
         block_id: &BlockId,
@@ -2250,59 +2297,6 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
-    fn validator_epoch_stats_add_participation_share(&mut self, qc_id: &QcId) -> Result<(), StorageError> {
-        use crate::schema::{quorum_certificates, validator_epoch_stats};
-
-        let qc_id = serialize_hex(qc_id);
-        let qc_json = quorum_certificates::table
-            .select(quorum_certificates::json)
-            .filter(quorum_certificates::qc_id.eq(&qc_id))
-            .filter(quorum_certificates::is_shares_processed.eq(false))
-            .first::<String>(self.connection())
-            .optional()
-            .map_err(|e| SqliteStorageError::DieselError {
-                operation: "validator_epoch_stats_add_participation_share",
-                source: e,
-            })?;
-        let Some(qc_json) = qc_json else {
-            return Ok(());
-        };
-
-        let qc = deserialize_json::<QuorumCertificate>(&qc_json)?;
-        let epoch = qc.epoch().as_u64() as i64;
-
-        for sig in qc.signatures() {
-            let values = (
-                validator_epoch_stats::epoch.eq(epoch),
-                validator_epoch_stats::public_key.eq(serialize_hex(sig.public_key().as_bytes())),
-                validator_epoch_stats::participation_shares.eq(1),
-            );
-
-            diesel::insert_into(validator_epoch_stats::table)
-                .values(values)
-                .on_conflict((validator_epoch_stats::epoch, validator_epoch_stats::public_key))
-                .do_update()
-                .set(validator_epoch_stats::participation_shares.eq(validator_epoch_stats::participation_shares + 1))
-                .execute(self.connection())
-                .map_err(|e| SqliteStorageError::DieselError {
-                    operation: "validator_epoch_stats_add_participation_share",
-                    source: e,
-                })?;
-        }
-
-        // Mark QC shares as processed
-        diesel::update(quorum_certificates::table)
-            .filter(quorum_certificates::qc_id.eq(qc_id))
-            .set(quorum_certificates::is_shares_processed.eq(true))
-            .execute(self.connection())
-            .map_err(|e| SqliteStorageError::DieselError {
-                operation: "validator_epoch_stats_add_participation_share",
-                source: e,
-            })?;
-
-        Ok(())
-    }
-
     fn validator_epoch_stats_updates<'a, I: IntoIterator<Item = ValidatorStatsUpdate<'a>>>(
         &mut self,
         epoch: Epoch,
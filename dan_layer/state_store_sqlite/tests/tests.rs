@@ -149,3 +149,194 @@ mod confirm_all_transitions {
         tx.rollback().unwrap();
     }
 }
+
+mod blocks_get_paginated {
+    use tari_dan_common_types::{ExtraData, NumPreshards, ShardGroup};
+    use tari_dan_storage::{consensus_models::BlockId, Ordering};
+
+    use super::*;
+
+    fn insert_chain<TTx: StateStoreWriteTransaction>(tx: &mut TTx, num_blocks: u64) -> Vec<BlockId> {
+        let network = Default::default();
+        let zero_block = Block::zero_block(network, NumPreshards::P64);
+        zero_block.justify().insert(tx).unwrap();
+        zero_block.insert(tx).unwrap();
+        let mut ids = vec![*zero_block.id()];
+        for height in 1..=num_blocks {
+            let block = Block::create(
+                network,
+                *ids.last().unwrap(),
+                zero_block.justify().clone(),
+                NodeHeight(height),
+                Epoch(0),
+                ShardGroup::all_shards(NumPreshards::P64),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                None,
+                EpochTime::now().as_u64(),
+                0,
+                FixedHash::zero(),
+                ExtraData::default(),
+            )
+            .unwrap();
+            block.insert(tx).unwrap();
+            ids.push(*block.id());
+        }
+        ids
+    }
+
+    fn get_all_pages<TTx: StateStoreReadTransaction>(tx: &TTx, ordering: Ordering) -> Vec<BlockId> {
+        let mut cursor = None;
+        let mut ids = vec![];
+        loop {
+            let page = tx
+                .blocks_get_paginated(2, 0, cursor.as_ref(), None, None, None, Some(ordering))
+                .unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(*last.id());
+            ids.extend(page.iter().map(|b| *b.id()));
+        }
+        ids
+    }
+
+    #[test]
+    fn it_returns_each_block_once_when_following_the_cursor() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let ids = insert_chain(&mut tx, 4);
+
+        let ascending = get_all_pages(&*tx, Ordering::Ascending);
+        assert_eq!(ascending, ids);
+
+        let descending = get_all_pages(&*tx, Ordering::Descending);
+        assert_eq!(descending, ids.iter().rev().copied().collect::<Vec<_>>());
+
+        tx.rollback().unwrap();
+    }
+
+    #[test]
+    fn it_errors_for_an_unknown_cursor() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        insert_chain(&mut tx, 1);
+
+        let unknown = BlockId::new(FixedHash::from([1u8; 32]));
+        assert!(tx
+            .blocks_get_paginated(2, 0, Some(&unknown), None, None, None, None)
+            .is_err());
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod blocks_get_committed_by_transaction {
+    use tari_dan_common_types::{ExtraData, NumPreshards, ShardGroup};
+    use tari_dan_storage::consensus_models::BlockId;
+
+    use super::*;
+
+    fn insert_block<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        parent: &Block,
+        height: u64,
+        commands: Vec<Command>,
+    ) -> BlockId {
+        let block = Block::create(
+            Default::default(),
+            *parent.id(),
+            parent.justify().clone(),
+            NodeHeight(height),
+            Epoch(0),
+            ShardGroup::all_shards(NumPreshards::P64),
+            Default::default(),
+            commands.into_iter().collect(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            EpochTime::now().as_u64(),
+            0,
+            FixedHash::zero(),
+            ExtraData::default(),
+        )
+        .unwrap();
+        block.insert(tx).unwrap();
+        *block.id()
+    }
+
+    #[test]
+    fn it_returns_committed_blocks_containing_the_transaction() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let zero_block = Block::zero_block(Default::default(), NumPreshards::P64);
+        zero_block.justify().insert(&mut tx).unwrap();
+        zero_block.insert(&mut tx).unwrap();
+
+        let atom1 = create_tx_atom();
+        let atom2 = create_tx_atom();
+        let prepare = insert_block(&mut tx, &zero_block, 1, vec![
+            Command::LocalPrepare(atom1.clone()),
+            Command::LocalPrepare(atom2.clone()),
+        ]);
+        let accept = insert_block(&mut tx, &zero_block, 2, vec![Command::LocalAccept(atom1.clone())]);
+        let uncommitted = insert_block(&mut tx, &zero_block, 3, vec![Command::SomeAccept(atom1.clone())]);
+        tx.blocks_set_flags(&prepare, Some(true), None).unwrap();
+        tx.blocks_set_flags(&accept, Some(true), None).unwrap();
+
+        let ids = Block::get_committed_by_transaction(&*tx, &atom1.id, 10)
+            .unwrap()
+            .iter()
+            .map(|b| *b.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [accept, prepare]);
+
+        let ids = Block::get_committed_by_transaction(&*tx, &atom2.id, 10)
+            .unwrap()
+            .iter()
+            .map(|b| *b.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [prepare]);
+
+        let ids = Block::get_committed_by_transaction(&*tx, &atom1.id, 1)
+            .unwrap()
+            .iter()
+            .map(|b| *b.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [accept]);
+
+        // The transaction mappings do not prevent the block from being deleted
+        tx.blocks_delete(&uncommitted).unwrap();
+        tx.rollback().unwrap();
+    }
+}
+
+mod update_participation_shares {
+    use tari_common_types::types::PublicKey;
+    use tari_dan_common_types::{committee::Committee, NumPreshards, ShardGroup};
+    use tari_dan_storage::consensus_models::QuorumCertificate;
+
+    use super::*;
+
+    #[test]
+    fn it_skips_the_unsigned_genesis_qc() {
+        let db = create_db();
+        let mut tx = db.create_write_tx().unwrap();
+        let genesis_qc = QuorumCertificate::genesis(Epoch(0), ShardGroup::all_shards(NumPreshards::P64));
+        genesis_qc.insert(&mut tx).unwrap();
+        let committee = Committee::new(
+            (0..4)
+                .map(|i| (format!("vn{i}"), PublicKey::default()))
+                .collect::<Vec<_>>(),
+        );
+
+        // Called when the first block after genesis is committed
+        genesis_qc.update_participation_shares(&mut tx, &committee).unwrap();
+
+        tx.rollback().unwrap();
+    }
+}
@@ -493,6 +493,14 @@ impl Block {
         tx.blocks_get_all_ids_by_height(epoch, height)
     }
 
+    pub fn get_committed_by_transaction<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        transaction_id: &TransactionId,
+        limit: usize,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.blocks_get_committed_by_transaction(transaction_id, limit)
+    }
+
     pub fn get_genesis_for_epoch<TTx: StateStoreReadTransaction>(tx: &TTx, epoch: Epoch) -> Result<Self, StorageError> {
         let ids = Self::get_ids_by_epoch_and_height(tx, epoch, NodeHeight::zero())?;
         if ids.is_empty() {
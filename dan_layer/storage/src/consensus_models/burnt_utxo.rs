@@ -64,6 +64,15 @@ impl BurntUtxo {
     pub fn has_unproposed<TTx: StateStoreReadTransaction>(tx: &TTx) -> Result<bool, StorageError> {
         Ok(tx.burnt_utxos_count()? > 0)
     }
+
+    /// Deletes burnt UTXOs that were found in base layer blocks above the given height and have not yet been proposed.
+    /// Returns the number of burnt UTXOs deleted.
+    pub fn delete_all_unproposed_above_base_layer_height<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        height: u64,
+    ) -> Result<usize, StorageError> {
+        tx.burnt_utxos_delete_all_unproposed_above_base_layer_height(height)
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
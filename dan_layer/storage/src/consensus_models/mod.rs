@@ -27,6 +27,7 @@ mod locked_block;
 mod no_vote;
 mod quorum;
 mod quorum_certificate;
+mod signer_bitmap;
 mod state_transition;
 mod state_tree_diff;
 mod substate;
@@ -68,6 +69,7 @@ pub use locked_block::*;
 pub use no_vote::*;
 pub use quorum::*;
 pub use quorum_certificate::*;
+pub use signer_bitmap::*;
 pub use state_transition::*;
 pub use state_tree_diff::*;
 pub use substate::*;
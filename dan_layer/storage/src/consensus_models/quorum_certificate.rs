@@ -6,8 +6,9 @@ use std::{fmt::Display, ops::Deref};
 use borsh::BorshSerialize;
 use log::*;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::{FixedHash, FixedHashSizeError};
+use tari_common_types::types::{FixedHash, FixedHashSizeError, PublicKey};
 use tari_dan_common_types::{
+    committee::Committee,
     hashing::quorum_certificate_hasher,
     optional::Optional,
     serde_with,
@@ -25,6 +26,8 @@ use crate::{
         LastVoted,
         LeafBlock,
         QuorumDecision,
+        SignerBitmap,
+        ValidatorSchnorrSignature,
         ValidatorSignature,
         ValidatorStatsUpdate,
     },
@@ -53,7 +56,14 @@ pub struct QuorumCertificate {
     block_height: NodeHeight,
     epoch: Epoch,
     shard_group: ShardGroup,
-    signatures: Vec<ValidatorSignature>,
+    /// The committee members that signed this QC, in committee order
+    signers: SignerBitmap,
+    /// One signature for each set bit in `signers`, in the same order. The signer public keys are no longer carried
+    /// in the QC, but the signatures themselves are not aggregated. Schnorr signatures cannot be aggregated without an
+    /// extra interactive round between the voters, so a single aggregate signature needs a different signature scheme
+    /// (e.g. BLS) and is not implemented yet.
+    #[cfg_attr(feature = "ts", ts(type = "Array<{public_nonce : string, signature: string}>"))]
+    signatures: Vec<ValidatorSchnorrSignature>,
     #[serde(with = "serde_with::hex::vec")]
     #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     leaf_hashes: Vec<FixedHash>,
@@ -68,7 +78,8 @@ impl QuorumCertificate {
         block_height: NodeHeight,
         epoch: Epoch,
         shard_group: ShardGroup,
-        signatures: Vec<ValidatorSignature>,
+        signers: SignerBitmap,
+        signatures: Vec<ValidatorSchnorrSignature>,
         mut leaf_hashes: Vec<FixedHash>,
         decision: QuorumDecision,
     ) -> Self {
@@ -81,6 +92,7 @@ impl QuorumCertificate {
             block_height,
             epoch,
             shard_group,
+            signers,
             signatures,
             leaf_hashes,
             decision,
@@ -99,6 +111,7 @@ impl QuorumCertificate {
             block_height: NodeHeight::zero(),
             epoch,
             shard_group,
+            signers: SignerBitmap::default(),
             signatures: vec![],
             leaf_hashes: vec![],
             decision: QuorumDecision::Accept,
@@ -115,6 +128,7 @@ impl QuorumCertificate {
             .chain(&self.header_hash)
             .chain(&self.parent_id)
             .chain(&self.block_height)
+            .chain(&self.signers)
             .chain(&self.signatures)
             .chain(&self.leaf_hashes)
             .chain(&self.decision)
@@ -144,10 +158,36 @@ impl QuorumCertificate {
         &self.leaf_hashes
     }
 
-    pub fn signatures(&self) -> &[ValidatorSignature] {
+    pub fn signers(&self) -> &SignerBitmap {
+        &self.signers
+    }
+
+    pub fn signatures(&self) -> &[ValidatorSchnorrSignature] {
         &self.signatures
     }
 
+    /// Returns the public keys of the signers from the given committee, which must be the committee for the QC's
+    /// epoch and shard group. Returns None if the signer bitmap does not match the committee or the signatures.
+    pub fn signer_public_keys<'a, TAddr>(&self, committee: &'a Committee<TAddr>) -> Option<Vec<&'a PublicKey>> {
+        if self.signers.committee_size() != committee.len() || self.signers.count() != self.signatures.len() {
+            return None;
+        }
+        Some(self.signers.iter_set().map(|i| &committee.members[i].1).collect())
+    }
+
+    /// Returns the signatures together with the signer public keys from the given committee. See
+    /// [QuorumCertificate::signer_public_keys].
+    pub fn expand_signatures<TAddr>(&self, committee: &Committee<TAddr>) -> Option<Vec<ValidatorSignature>> {
+        let public_keys = self.signer_public_keys(committee)?;
+        Some(
+            public_keys
+                .into_iter()
+                .zip(&self.signatures)
+                .map(|(pk, sig)| ValidatorSignature::new(pk.clone(), sig.clone()))
+                .collect(),
+        )
+    }
+
     pub fn block_height(&self) -> NodeHeight {
         self.block_height
     }
@@ -268,17 +308,29 @@ impl QuorumCertificate {
         Ok(high_qc)
     }
 
-    pub fn update_participation_shares<TTx: StateStoreWriteTransaction>(
+    pub fn update_participation_shares<TTx: StateStoreWriteTransaction, TAddr>(
         &self,
         tx: &mut TTx,
+        committee: &Committee<TAddr>,
     ) -> Result<(), StorageError> {
-        if self.is_shares_processed {
+        // The zero block and genesis QCs are not signed by any validator
+        if self.is_shares_processed || self.justifies_zero_block() || self.signers.committee_size() == 0 {
             return Ok(());
         }
 
+        let signers = self
+            .signer_public_keys(committee)
+            .ok_or_else(|| StorageError::DataInconsistency {
+                details: format!(
+                    "update_participation_shares: signers of {} do not match the committee of size {}",
+                    self,
+                    committee.len()
+                ),
+            })?;
+
         tx.validator_epoch_stats_updates(
             self.epoch,
-            self.signatures.iter().map(|s| s.public_key()).map(|pk| {
+            signers.into_iter().map(|pk| {
                 ValidatorStatsUpdate::new(pk)
                     .increment_participation_share()
                     .decrement_missed_proposal()
@@ -0,0 +1,129 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::Display;
+
+use borsh::BorshSerialize;
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::serde_with;
+
+/// A bitmap over the ordered members of a committee. Bit `i` is set if the `i`th committee member signed. This allows a
+/// QC to reference its signers without including their public keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct SignerBitmap {
+    committee_size: u32,
+    #[serde(with = "serde_with::hex")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    bits: Vec<u8>,
+}
+
+impl SignerBitmap {
+    pub fn new(committee_size: usize) -> Self {
+        Self {
+            committee_size: committee_size as u32,
+            bits: vec![0u8; committee_size.div_ceil(8)],
+        }
+    }
+
+    /// Constructs a bitmap from its encoded parts. Returns None if the number of bytes does not match the committee
+    /// size or if any bit beyond the committee size is set.
+    pub fn from_parts(committee_size: u32, bits: Vec<u8>) -> Option<Self> {
+        let bitmap = Self { committee_size, bits };
+        if bitmap.bits.len() != bitmap.committee_size().div_ceil(8) {
+            return None;
+        }
+        let num_trailing_bits = bitmap.bits.len() * 8 - bitmap.committee_size();
+        if num_trailing_bits > 0 {
+            let last = *bitmap
+                .bits
+                .last()
+                .expect("bits is not empty if there are trailing bits");
+            if last >> (8 - num_trailing_bits) != 0 {
+                return None;
+            }
+        }
+        Some(bitmap)
+    }
+
+    pub fn committee_size(&self) -> usize {
+        self.committee_size as usize
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Sets the bit for the committee member at `index`. Returns false if the index is out of range.
+    pub fn set(&mut self, index: usize) -> bool {
+        if index >= self.committee_size() {
+            return false;
+        }
+        self.bits[index / 8] |= 1 << (index % 8);
+        true
+    }
+
+    pub fn is_set(&self, index: usize) -> bool {
+        index < self.committee_size() && self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Returns the number of signers
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|b| *b == 0)
+    }
+
+    /// Returns the committee indexes of all signers in ascending order
+    pub fn iter_set(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.committee_size()).filter(|i| self.is_set(*i))
+    }
+}
+
+impl Display for SignerBitmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.committee_size() {
+            write!(f, "{}", if self.is_set(i) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_and_iterates_bits() {
+        let mut bitmap = SignerBitmap::new(10);
+        assert!(bitmap.is_empty());
+        assert!(bitmap.set(0));
+        assert!(bitmap.set(3));
+        assert!(bitmap.set(9));
+        assert!(!bitmap.set(10));
+        assert_eq!(bitmap.count(), 3);
+        assert_eq!(bitmap.iter_set().collect::<Vec<_>>(), vec![0, 3, 9]);
+        assert_eq!(bitmap.to_string(), "1001000001");
+    }
+
+    #[test]
+    fn it_rejects_invalid_parts() {
+        let mut bitmap = SignerBitmap::new(10);
+        bitmap.set(9);
+        assert_eq!(
+            SignerBitmap::from_parts(10, bitmap.as_bytes().to_vec()),
+            Some(bitmap.clone())
+        );
+        // Wrong number of bytes
+        assert!(SignerBitmap::from_parts(10, vec![0]).is_none());
+        assert!(SignerBitmap::from_parts(10, vec![0, 0, 0]).is_none());
+        // Bit set beyond the committee size
+        assert!(SignerBitmap::from_parts(10, vec![0, 0b0000_0100]).is_none());
+    }
+}
@@ -41,6 +41,7 @@ use crate::{
     StateStoreReadTransaction,
     StateStoreWriteTransaction,
     StorageError,
+    TransactionStatusFilter,
 };
 
 const LOG_TARGET: &str = "tari::dan::storage::consensus_models::transaction";
@@ -375,10 +376,11 @@ impl TransactionRecord {
     pub fn get_paginated<TTx: StateStoreReadTransaction>(
         tx: &TTx,
         limit: u64,
-        offset: u64,
+        after: Option<&TransactionId>,
+        status: Option<TransactionStatusFilter>,
         ordering: Option<Ordering>,
     ) -> Result<Vec<Self>, StorageError> {
-        tx.transactions_get_paginated(limit, offset, ordering)
+        tx.transactions_get_paginated(limit, after, status, ordering)
     }
 
     pub fn get_local_pledges<TTx: StateStoreReadTransaction>(&self, tx: &TTx) -> Result<SubstatePledges, StorageError> {
@@ -58,6 +58,7 @@ pub enum AbortReason {
     ForeignShardGroupDecidedToAbort,
     InsufficientFeesPaid,
     EarlyAbort,
+    ForeignProposalTimeout,
 }
 
 impl Display for AbortReason {
@@ -78,6 +79,7 @@ impl From<&RejectReason> for AbortReason {
             RejectReason::FailedToLockOutputs(_) => Self::LockOutputsFailed,
             RejectReason::ForeignShardGroupDecidedToAbort { .. } => Self::ForeignShardGroupDecidedToAbort,
             RejectReason::InsufficientFeesPaid(_) => Self::InsufficientFeesPaid,
+            RejectReason::ForeignProposalTimeout { .. } => Self::ForeignProposalTimeout,
         }
     }
 }
@@ -15,7 +15,9 @@ use tari_dan_common_types::{
     committee::CommitteeInfo,
     displayable::Displayable,
     optional::{IsNotFoundError, Optional},
+    NodeHeight,
     NumPreshards,
+    ShardGroup,
     SubstateAddress,
     SubstateLockType,
 };
@@ -207,6 +209,21 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
         Ok(false)
     }
 
+    /// Returns up to `limit` transactions that have been waiting for a foreign proposal since a block at or below
+    /// `max_since_height`, along with the height at which they started waiting.
+    pub fn get_stalled(
+        &self,
+        tx: &TStateStore::ReadTransaction<'_>,
+        max_since_height: NodeHeight,
+        limit: usize,
+    ) -> Result<Vec<(TransactionId, NodeHeight)>, TransactionPoolError> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let stalled = tx.transaction_pool_get_stalled(max_since_height, limit)?;
+        Ok(stalled)
+    }
+
     pub fn count(&self, tx: &TStateStore::ReadTransaction<'_>) -> Result<usize, TransactionPoolError> {
         let count = tx.transaction_pool_count(None, None, None, false)?;
         Ok(count)
@@ -766,6 +783,25 @@ impl TransactionPoolRecord {
         self.evidence.contains(&committee_info.shard_group())
     }
 
+    /// Returns the foreign shard groups whose proposal is required before the transaction can progress from its
+    /// current LocalPrepared or LocalAccepted stage. Empty for all other stages.
+    pub fn foreign_shard_groups_awaiting_proposal(&self, local_shard_group: ShardGroup) -> Vec<ShardGroup> {
+        let stage = self.current_stage();
+        self.evidence
+            .iter()
+            .filter(|(sg, _)| **sg != local_shard_group)
+            .filter(|(_, e)| match stage {
+                // Only input shard groups prepare
+                TransactionPoolStage::LocalPrepared => {
+                    !e.inputs().is_empty() && !e.is_prepare_justified() && !e.is_accept_justified()
+                },
+                TransactionPoolStage::LocalAccepted => !e.is_accept_justified(),
+                _ => false,
+            })
+            .map(|(sg, _)| *sg)
+            .collect()
+    }
+
     pub fn committee_involves_inputs(&self, committee_info: &CommitteeInfo) -> bool {
         self.evidence
             .get(&committee_info.shard_group())
@@ -911,6 +947,67 @@ mod tests {
         }
     }
 
+    mod foreign_shard_groups_awaiting_proposal {
+        use tari_engine_types::substate::SubstateId;
+        use tari_template_lib::models::{ComponentAddress, ObjectKey};
+
+        use super::*;
+        use crate::consensus_models::QcId;
+
+        fn create_record(stage: TransactionPoolStage, evidence: Evidence) -> TransactionPoolRecord {
+            TransactionPoolRecord {
+                transaction_id: TransactionId::new([0; 32]),
+                original_decision: Decision::Commit,
+                evidence,
+                transaction_fee: 0,
+                leader_fee: None,
+                stage,
+                is_global: false,
+                pending_stage: None,
+                local_decision: None,
+                remote_decision: None,
+                is_ready: false,
+            }
+        }
+
+        fn input(n: u8) -> SubstateId {
+            ComponentAddress::new(ObjectKey::from_array([n; ObjectKey::LENGTH])).into()
+        }
+
+        #[test]
+        fn it_returns_unjustified_foreign_shard_groups() {
+            let local = ShardGroup::new(0, 63);
+            let foreign_input = ShardGroup::new(64, 127);
+            let foreign_output = ShardGroup::new(128, 191);
+            let prepared_input = ShardGroup::new(192, 255);
+
+            let mut evidence = Evidence::default();
+            evidence
+                .add_shard_group(local)
+                .insert_unpledged_input(input(1))
+                .set_prepare_qc(QcId::zero());
+            evidence.add_shard_group(foreign_input).insert_unpledged_input(input(2));
+            evidence.add_shard_group(foreign_output).insert_output(input(3), 0);
+            evidence
+                .add_shard_group(prepared_input)
+                .insert_unpledged_input(input(4))
+                .set_prepare_qc(QcId::zero());
+
+            let rec = create_record(TransactionPoolStage::LocalPrepared, evidence.clone());
+            assert_eq!(rec.foreign_shard_groups_awaiting_proposal(local), vec![foreign_input]);
+
+            let rec = create_record(TransactionPoolStage::LocalAccepted, evidence.clone());
+            assert_eq!(rec.foreign_shard_groups_awaiting_proposal(local), vec![
+                foreign_input,
+                foreign_output,
+                prepared_input
+            ]);
+
+            let rec = create_record(TransactionPoolStage::AllPrepared, evidence);
+            assert!(rec.foreign_shard_groups_awaiting_proposal(local).is_empty());
+        }
+    }
+
     mod calculate_leader_fee {
         use super::*;
 
@@ -41,7 +41,7 @@ use crate::{
         base_layer_db::DbLayer1Transaction,
         metadata_db::MetadataKey,
         models::ValidatorNode,
-        template_db::{DbTemplate, DbTemplateUpdate},
+        template_db::{DbTemplate, DbTemplateQuery, DbTemplateUpdate},
     },
 };
 
@@ -75,7 +75,11 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
     ) -> Result<(), Self::Error>;
 
     fn get_template(&self, tx: &mut Self::DbTransaction<'_>, key: &[u8]) -> Result<Option<DbTemplate>, Self::Error>;
-    fn get_templates(&self, tx: &mut Self::DbTransaction<'_>, limit: usize) -> Result<Vec<DbTemplate>, Self::Error>;
+    fn get_templates(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        query: &DbTemplateQuery,
+    ) -> Result<Vec<DbTemplate>, Self::Error>;
     fn get_templates_by_addresses(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -88,6 +92,11 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
     ) -> Result<Vec<DbTemplate>, Self::Error>;
 
     fn insert_template(&self, tx: &mut Self::DbTransaction<'_>, template: DbTemplate) -> Result<(), Self::Error>;
+    fn delete_templates_registered_above_base_layer_height(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+    ) -> Result<usize, Self::Error>;
     fn update_template(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -112,6 +121,15 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
         deactivation_epoch: Epoch,
     ) -> Result<(), Self::Error>;
 
+    /// Reverts the validator node changes fetched from the base layer for the given epoch and later epochs. These are
+    /// registrations that activate after the given epoch and exits that deactivate in or after it. Committee
+    /// assignments for epochs after the given epoch are removed.
+    fn validator_nodes_revert_changes_from_epoch(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        epoch: Epoch,
+    ) -> Result<(), Self::Error>;
+
     fn get_validator_nodes_within_start_epoch(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -185,6 +203,7 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
 
     fn insert_epoch(&self, tx: &mut Self::DbTransaction<'_>, epoch: DbEpoch) -> Result<(), Self::Error>;
     fn get_epoch(&self, tx: &mut Self::DbTransaction<'_>, epoch: u64) -> Result<Option<DbEpoch>, Self::Error>;
+    fn delete_epochs_after(&self, tx: &mut Self::DbTransaction<'_>, epoch: u64) -> Result<(), Self::Error>;
 
     fn insert_base_layer_block_info(
         &self,
@@ -196,6 +215,18 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
         tx: &mut Self::DbTransaction<'_>,
         hash: FixedHash,
     ) -> Result<Option<DbBaseLayerBlockInfo>, Self::Error>;
+    /// Returns up to `limit` block infos with a height less than or equal to `height`, highest first
+    fn get_base_layer_block_infos_descending(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+        limit: usize,
+    ) -> Result<Vec<DbBaseLayerBlockInfo>, Self::Error>;
+    fn delete_base_layer_block_infos_above_height(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+    ) -> Result<(), Self::Error>;
 
     fn insert_bmt(
         &self,
@@ -208,6 +239,7 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
         tx: &mut Self::DbTransaction<'_>,
         epoch: Epoch,
     ) -> Result<Option<ValidatorNodeBalancedMerkleTree>, Self::Error>;
+    fn delete_bmts_after(&self, tx: &mut Self::DbTransaction<'_>, epoch: Epoch) -> Result<(), Self::Error>;
 
     fn insert_layer_one_transaction<T: Serialize>(
         &self,
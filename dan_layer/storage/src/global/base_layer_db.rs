@@ -53,6 +53,22 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> BaseLayerDb<'a, 'tx, TGlobalDbA
             .map_err(TGlobalDbAdapter::Error::into)
     }
 
+    pub fn get_base_layer_block_infos_descending(
+        &mut self,
+        height: u64,
+        limit: usize,
+    ) -> Result<Vec<DbBaseLayerBlockInfo>, TGlobalDbAdapter::Error> {
+        self.backend
+            .get_base_layer_block_infos_descending(self.tx, height, limit)
+            .map_err(TGlobalDbAdapter::Error::into)
+    }
+
+    pub fn delete_base_layer_block_infos_above_height(&mut self, height: u64) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend
+            .delete_base_layer_block_infos_above_height(self.tx, height)
+            .map_err(TGlobalDbAdapter::Error::into)
+    }
+
     pub fn insert_eviction_proof(&mut self, proof: &EvictionProof) -> Result<(), TGlobalDbAdapter::Error> {
         self.backend
             .insert_layer_one_transaction(self.tx, DbLayer1Transaction {
@@ -52,6 +52,12 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> BmtDb<'a, 'tx, TGlobalDbAdapter
             .get_bmt(self.tx, epoch)
             .map_err(TGlobalDbAdapter::Error::into)
     }
+
+    pub fn delete_bmts_after(&mut self, epoch: Epoch) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend
+            .delete_bmts_after(self.tx, epoch)
+            .map_err(TGlobalDbAdapter::Error::into)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +43,12 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> EpochDb<'a, 'tx, TGlobalDbAdapt
             .get_epoch(self.tx, epoch)
             .map_err(TGlobalDbAdapter::Error::into)
     }
+
+    pub fn delete_epochs_after(&mut self, epoch: u64) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend
+            .delete_epochs_after(self.tx, epoch)
+            .map_err(TGlobalDbAdapter::Error::into)
+    }
 }
 
 #[derive(Debug, Clone)]
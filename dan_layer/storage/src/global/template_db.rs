@@ -30,7 +30,7 @@ use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::Epoch;
 use tari_engine_types::TemplateAddress;
 
-use crate::global::GlobalDbAdapter;
+use crate::{global::GlobalDbAdapter, Ordering};
 
 pub struct TemplateDb<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> {
     backend: &'a TGlobalDbAdapter,
@@ -46,8 +46,9 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> TemplateDb<'a, 'tx, TGlobalDbAd
         self.backend.get_template(self.tx, key)
     }
 
-    pub fn get_templates(&mut self, limit: usize) -> Result<Vec<DbTemplate>, TGlobalDbAdapter::Error> {
-        self.backend.get_templates(self.tx, limit)
+    /// Returns active templates matching the query, ordered by template address
+    pub fn get_templates(&mut self, query: &DbTemplateQuery) -> Result<Vec<DbTemplate>, TGlobalDbAdapter::Error> {
+        self.backend.get_templates(self.tx, query)
     }
 
     pub fn get_templates_by_addresses(
@@ -69,6 +70,13 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> TemplateDb<'a, 'tx, TGlobalDbAd
         self.backend.update_template(self.tx, key, update)
     }
 
+    /// Deletes templates that were registered in a base layer block above the given height. Returns the number of
+    /// templates deleted.
+    pub fn delete_registered_above_base_layer_height(&mut self, height: u64) -> Result<usize, TGlobalDbAdapter::Error> {
+        self.backend
+            .delete_templates_registered_above_base_layer_height(self.tx, height)
+    }
+
     pub fn template_exists(
         &mut self,
         key: &[u8],
@@ -94,6 +102,8 @@ pub struct DbTemplate {
     pub url: Option<String>,
     pub status: TemplateStatus,
     pub added_at: NaiveDateTime,
+    /// The height of the base layer block that registered the template, if it was registered on the base layer
+    pub base_layer_block_height: Option<u64>,
 }
 
 impl DbTemplate {
@@ -109,10 +119,41 @@ impl DbTemplate {
             template_type: DbTemplateType::Wasm,
             url: None,
             epoch,
+            base_layer_block_height: None,
         }
     }
 }
 
+/// Filters and cursor for a page of templates
+#[derive(Debug, Clone, Default)]
+pub struct DbTemplateQuery {
+    /// The maximum number of templates to return. Zero returns all matching templates.
+    pub limit: usize,
+    /// Only return templates that come after this template address in the requested order
+    pub after: Option<TemplateAddress>,
+    pub ordering: Option<Ordering>,
+    pub author_public_key: Option<PublicKey>,
+    /// Only return templates whose name contains this string (case-insensitive)
+    pub name: Option<String>,
+}
+
+impl DbTemplateQuery {
+    /// Returns true if a template with the given properties matches the query filters and cursor
+    pub fn matches(&self, address: &TemplateAddress, name: &str, author_public_key: &PublicKey) -> bool {
+        let is_after_cursor = self.after.as_ref().map_or(true, |after| match self.ordering {
+            Some(Ordering::Descending) => address < after,
+            _ => address > after,
+        });
+        is_after_cursor &&
+            self.author_public_key
+                .as_ref()
+                .map_or(true, |pk| pk == author_public_key) &&
+            self.name
+                .as_ref()
+                .map_or(true, |n| name.to_lowercase().contains(&n.to_lowercase()))
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct DbTemplateUpdate {
     pub author_public_key: Option<PublicKey>,
@@ -67,6 +67,12 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> ValidatorNodeDb<'a, 'tx, TGloba
             .map_err(TGlobalDbAdapter::Error::into)
     }
 
+    pub fn revert_changes_from_epoch(&mut self, epoch: Epoch) -> Result<(), TGlobalDbAdapter::Error> {
+        self.backend
+            .validator_nodes_revert_changes_from_epoch(self.tx, epoch)
+            .map_err(TGlobalDbAdapter::Error::into)
+    }
+
     pub fn count(&mut self, epoch: Epoch) -> Result<u64, TGlobalDbAdapter::Error> {
         self.backend
             .validator_nodes_count(self.tx, epoch)
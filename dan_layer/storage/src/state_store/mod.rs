@@ -148,11 +148,14 @@ pub trait StateStoreReadTransaction: Sized {
         &self,
         tx_ids: I,
     ) -> Result<Vec<TransactionRecord>, StorageError>;
+    /// Returns up to `limit` transactions in insertion order. If `after` is provided, only transactions inserted after
+    /// (or before, for descending order) that transaction are returned.
     fn transactions_get_paginated(
         &self,
         limit: u64,
-        offset: u64,
-        asc_desc_created_at: Option<Ordering>,
+        after: Option<&TransactionId>,
+        status: Option<TransactionStatusFilter>,
+        ordering: Option<Ordering>,
     ) -> Result<Vec<TransactionRecord>, StorageError>;
 
     fn transaction_executions_get(
@@ -186,6 +189,12 @@ pub trait StateStoreReadTransaction: Sized {
     fn blocks_get_ids_by_parent(&self, parent: &BlockId) -> Result<Vec<BlockId>, StorageError>;
     fn blocks_get_parent_chain(&self, block_id: &BlockId, limit: usize) -> Result<Vec<Block>, StorageError>;
     fn blocks_get_pending_transactions(&self, block_id: &BlockId) -> Result<Vec<TransactionId>, StorageError>;
+    /// Returns committed blocks that contain a command for the given transaction, most recent first
+    fn blocks_get_committed_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: usize,
+    ) -> Result<Vec<Block>, StorageError>;
 
     fn blocks_get_any_with_epoch_range(
         &self,
@@ -196,6 +205,7 @@ pub trait StateStoreReadTransaction: Sized {
         &self,
         limit: u64,
         offset: u64,
+        after: Option<&BlockId>,
         filter_index: Option<usize>,
         filter: Option<String>,
         ordering_index: Option<usize>,
@@ -253,6 +263,14 @@ pub trait StateStoreReadTransaction: Sized {
         confirmed_stage: Option<Option<TransactionPoolConfirmedStage>>,
         skip_lock_conflicted: bool,
     ) -> Result<usize, StorageError>;
+    /// Returns the transactions that have been waiting for foreign proposals in the committed LocalPrepared or
+    /// LocalAccepted stage since a block at or below `max_since_height`, along with the height of the block in which
+    /// they entered that stage. Transactions that are ready to be proposed are excluded.
+    fn transaction_pool_get_stalled(
+        &self,
+        max_since_height: NodeHeight,
+        limit: usize,
+    ) -> Result<Vec<(TransactionId, NodeHeight)>, StorageError>;
 
     fn transactions_fetch_involved_shards(
         &self,
@@ -605,6 +623,8 @@ pub trait StateStoreWriteTransaction {
     ) -> Result<(), StorageError>;
     fn burnt_utxos_clear_proposed_block(&mut self, proposed_in_block: &BlockId) -> Result<(), StorageError>;
     fn burnt_utxos_delete(&mut self, commitment: &UnclaimedConfidentialOutputAddress) -> Result<(), StorageError>;
+    fn burnt_utxos_delete_all_unproposed_above_base_layer_height(&mut self, height: u64)
+        -> Result<usize, StorageError>;
 
     // -------------------------------- Lock conflicts -------------------------------- //
     fn lock_conflicts_insert_all<'a, I: IntoIterator<Item = (&'a TransactionId, &'a Vec<LockConflict>)>>(
@@ -621,7 +641,6 @@ pub trait StateStoreWriteTransaction {
     fn lock_conflicts_remove_by_block_id(&mut self, block_id: &BlockId) -> Result<(), StorageError>;
 
     // -------------------------------- ParticipationShares -------------------------------- //
-    fn validator_epoch_stats_add_participation_share(&mut self, qc_id: &QcId) -> Result<(), StorageError>;
     fn validator_epoch_stats_updates<'a, I: IntoIterator<Item = ValidatorStatsUpdate<'a>>>(
         &mut self,
         epoch: Epoch,
@@ -647,3 +666,15 @@ pub enum Ordering {
     Ascending,
     Descending,
 }
+
+/// Filters transactions by their final decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum TransactionStatusFilter {
+    /// Transactions that have not been finalized
+    Pending,
+    /// Transactions that were finalized with a COMMIT decision
+    Committed,
+    /// Transactions that were finalized with an ABORT decision
+    Aborted,
+}
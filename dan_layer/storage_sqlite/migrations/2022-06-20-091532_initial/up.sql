@@ -37,6 +37,8 @@ create table validator_nodes
     fee_claim_public_key blob                              not null
 );
 
+CREATE UNIQUE INDEX validator_nodes_public_key_start_epoch_uniq ON validator_nodes (public_key, start_epoch);
+
 CREATE TABLE committees
 (
     id                INTEGER PRIMARY KEY autoincrement NOT NULL,
@@ -69,7 +71,9 @@ create table templates
     -- template code for the given template type
     code              blob                              null,
     status            VARCHAR(20)                       NOT NULL DEFAULT 'New',
-    added_at          timestamp                         NOT NULL DEFAULT CURRENT_TIMESTAMP
+    added_at          timestamp                         NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- the height of the base layer block that registered the template, null if not registered on the base layer
+    base_layer_block_height bigint                      NULL
 );
 
 
@@ -38,6 +38,7 @@ use diesel::{
     QueryDsl,
     RunQueryDsl,
     SqliteConnection,
+    TextExpressionMethods,
 };
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
 use log::debug;
@@ -58,12 +59,14 @@ use tari_dan_storage::{
         DbEpoch,
         DbLayer1Transaction,
         DbTemplate,
+        DbTemplateQuery,
         DbTemplateUpdate,
         GlobalDbAdapter,
         MetadataKey,
         TemplateStatus,
     },
     AtomicDb,
+    Ordering,
 };
 use tari_engine_types::TemplateAddress;
 use tari_utilities::{hex, ByteArray};
@@ -226,19 +229,46 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                 url: t.url,
                 status: t.status.parse().expect("DB status corrupted"),
                 added_at: t.added_at,
+                base_layer_block_height: t.base_layer_block_height.map(|h| h as u64),
             })),
             None => Ok(None),
         }
     }
 
-    fn get_templates(&self, tx: &mut Self::DbTransaction<'_>, limit: usize) -> Result<Vec<DbTemplate>, Self::Error> {
+    fn get_templates(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        query: &DbTemplateQuery,
+    ) -> Result<Vec<DbTemplate>, Self::Error> {
         use crate::global::schema::templates;
 
         let mut templates = templates::table
             .filter(templates::status.eq(TemplateStatus::Active.as_str()))
             .into_boxed();
 
-        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        match query.ordering {
+            Some(Ordering::Descending) => {
+                if let Some(after) = &query.after {
+                    templates = templates.filter(templates::template_address.lt(after.as_ref()));
+                }
+                templates = templates.order_by(templates::template_address.desc());
+            },
+            _ => {
+                if let Some(after) = &query.after {
+                    templates = templates.filter(templates::template_address.gt(after.as_ref()));
+                }
+                templates = templates.order_by(templates::template_address.asc());
+            },
+        }
+
+        if let Some(author_public_key) = &query.author_public_key {
+            templates = templates.filter(templates::author_public_key.eq(author_public_key.as_bytes()));
+        }
+        if let Some(name) = &query.name {
+            templates = templates.filter(templates::template_name.like(format!("%{name}%")));
+        }
+
+        let limit = i64::try_from(query.limit).unwrap_or(i64::MAX);
         if limit > 0 {
             templates = templates.limit(limit);
         }
@@ -306,6 +336,7 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                     status: t.status.parse().expect("DB status corrupted"),
                     added_at: t.added_at,
                     epoch: Epoch(t.epoch as u64),
+                    base_layer_block_height: t.base_layer_block_height.map(|h| h as u64),
                 })
             })
             .collect()
@@ -322,6 +353,7 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
             code: item.code,
             epoch: item.epoch.as_u64() as i64,
             status: item.status.as_str().to_string(),
+            base_layer_block_height: item.base_layer_block_height.map(|h| h as i64),
         };
         diesel::insert_into(templates::table)
             .values(new_template)
@@ -334,6 +366,24 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         Ok(())
     }
 
+    fn delete_templates_registered_above_base_layer_height(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+    ) -> Result<usize, Self::Error> {
+        use crate::global::schema::templates;
+
+        let num_deleted = diesel::delete(templates::table)
+            .filter(templates::base_layer_block_height.gt(height as i64))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete_templates_registered_above_base_layer_height".to_string(),
+            })?;
+
+        Ok(num_deleted)
+    }
+
     fn update_template(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -425,6 +475,7 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         use crate::global::schema::validator_nodes;
         let addr = serialize_json(&address)?;
 
+        // The validator node changes for an epoch may be fetched more than once, e.g. after a base layer reorg
         diesel::insert_into(validator_nodes::table)
             .values((
                 validator_nodes::address.eq(&addr),
@@ -433,6 +484,7 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                 validator_nodes::start_epoch.eq(start_epoch.as_u64() as i64),
                 validator_nodes::fee_claim_public_key.eq(ByteArray::as_bytes(&fee_claim_public_key)),
             ))
+            .on_conflict_do_nothing()
             .execute(tx.connection())
             .map_err(|source| SqliteStorageError::DieselError {
                 source,
@@ -462,6 +514,46 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         Ok(())
     }
 
+    fn validator_nodes_revert_changes_from_epoch(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        epoch: Epoch,
+    ) -> Result<(), Self::Error> {
+        use crate::global::schema::{committees, validator_nodes};
+
+        let epoch = epoch.as_u64() as i64;
+
+        // Committees reference the validator nodes that are deleted below, and only exist for epochs after the start
+        // epoch of those validators
+        diesel::delete(committees::table)
+            .filter(committees::epoch.gt(epoch))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "validator_nodes_revert_changes_from_epoch::committees".to_string(),
+            })?;
+
+        diesel::delete(validator_nodes::table)
+            .filter(validator_nodes::start_epoch.gt(epoch))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "validator_nodes_revert_changes_from_epoch::registrations".to_string(),
+            })?;
+
+        // Exits record the epoch in which they were fetched as the deactivation epoch
+        diesel::update(validator_nodes::table)
+            .set(validator_nodes::end_epoch.eq(None::<i64>))
+            .filter(validator_nodes::end_epoch.ge(epoch))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "validator_nodes_revert_changes_from_epoch::exits".to_string(),
+            })?;
+
+        Ok(())
+    }
+
     fn get_validator_node_by_address(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -825,6 +917,20 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         }
     }
 
+    fn delete_epochs_after(&self, tx: &mut Self::DbTransaction<'_>, epoch: u64) -> Result<(), Self::Error> {
+        use crate::global::schema::epochs;
+
+        diesel::delete(epochs::table)
+            .filter(epochs::epoch.gt(epoch as i64))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete::epochs".to_string(),
+            })?;
+
+        Ok(())
+    }
+
     fn insert_base_layer_block_info(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -865,6 +971,45 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         }
     }
 
+    fn get_base_layer_block_infos_descending(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+        limit: usize,
+    ) -> Result<Vec<DbBaseLayerBlockInfo>, Self::Error> {
+        use crate::global::schema::base_layer_block_info::dsl;
+
+        let infos = dsl::base_layer_block_info
+            .filter(dsl::height.le(height as i64))
+            .order_by(dsl::height.desc())
+            .limit(i64::try_from(limit).unwrap_or(i64::MAX))
+            .get_results::<models::BaseLayerBlockInfo>(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "get_base_layer_block_infos_descending".to_string(),
+            })?;
+
+        infos.into_iter().map(TryInto::try_into).collect()
+    }
+
+    fn delete_base_layer_block_infos_above_height(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        height: u64,
+    ) -> Result<(), Self::Error> {
+        use crate::global::schema::base_layer_block_info;
+
+        diesel::delete(base_layer_block_info::table)
+            .filter(base_layer_block_info::height.gt(height as i64))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete::base_layer_block_info".to_string(),
+            })?;
+
+        Ok(())
+    }
+
     fn insert_bmt(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -908,6 +1053,20 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
         }
     }
 
+    fn delete_bmts_after(&self, tx: &mut Self::DbTransaction<'_>, epoch: Epoch) -> Result<(), Self::Error> {
+        use crate::global::schema::bmt_cache;
+
+        diesel::delete(bmt_cache::table)
+            .filter(bmt_cache::epoch.gt(epoch.as_u64() as i64))
+            .execute(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "delete::bmt".to_string(),
+            })?;
+
+        Ok(())
+    }
+
     fn insert_layer_one_transaction<T: Serialize>(
         &self,
         tx: &mut Self::DbTransaction<'_>,
@@ -45,6 +45,7 @@ pub struct TemplateModel {
     pub code: Option<Vec<u8>>,
     pub status: String,
     pub added_at: NaiveDateTime,
+    pub base_layer_block_height: Option<i64>,
 }
 
 #[derive(Debug, Error)]
@@ -73,6 +74,7 @@ impl TryInto<DbTemplate> for TemplateModel {
             status: self.status.parse().expect("DB status corrupted"),
             added_at: self.added_at,
             epoch: Epoch(self.epoch as u64),
+            base_layer_block_height: self.base_layer_block_height.map(|h| h as u64),
         })
     }
 }
@@ -88,6 +90,7 @@ pub struct NewTemplateModel {
     pub code: Option<Vec<u8>>,
     pub epoch: i64,
     pub status: String,
+    pub base_layer_block_height: Option<i64>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -62,6 +62,7 @@ diesel::table! {
         code -> Nullable<Binary>,
         status -> Text,
         added_at -> Timestamp,
+        base_layer_block_height -> Nullable<BigInt>,
     }
 }
 
@@ -6,8 +6,12 @@ use rand::rngs::OsRng;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_crypto::keys::PublicKey as _;
 use tari_dan_common_types::{Epoch, NumPreshards, PeerAddress, ShardGroup, SubstateAddress};
-use tari_dan_storage::global::{GlobalDb, ValidatorNodeDb};
+use tari_dan_storage::{
+    global::{DbTemplate, DbTemplateQuery, GlobalDb, TemplateStatus, ValidatorNodeDb},
+    Ordering,
+};
 use tari_dan_storage_sqlite::global::SqliteGlobalDbAdapter;
+use tari_engine_types::TemplateAddress;
 use tari_utilities::ByteArray;
 
 fn create_db() -> GlobalDb<SqliteGlobalDbAdapter<PeerAddress>> {
@@ -103,3 +107,113 @@ fn change_committee_shard_group() {
         .unwrap();
     assert_eq!(vns.len(), 2);
 }
+
+#[test]
+fn revert_changes_from_epoch() {
+    let db = create_db();
+    let mut tx = db.create_transaction().unwrap();
+    let mut validator_nodes = db.validator_nodes(&mut tx);
+    let pk = new_public_key();
+    insert_vn_with_public_key(&mut validator_nodes, pk.clone(), Epoch(0));
+    insert_vns(&mut validator_nodes, 2, Epoch(1));
+    // Changes fetched in epoch 1 activate in epoch 2, exits deactivate in epoch 1
+    insert_vns(&mut validator_nodes, 2, Epoch(2));
+    validator_nodes.deactivate(pk.clone(), Epoch(1)).unwrap();
+    assert_eq!(
+        validator_nodes
+            .get_all_registered_within_start_epoch(Epoch(2))
+            .unwrap()
+            .len(),
+        4
+    );
+
+    validator_nodes.revert_changes_from_epoch(Epoch(1)).unwrap();
+    let vns = validator_nodes.get_all_registered_within_start_epoch(Epoch(2)).unwrap();
+    assert_eq!(vns.len(), 3);
+    assert!(vns.iter().any(|vn| vn.public_key == pk));
+    let all_shards = ShardGroup::all_shards(NumPreshards::P256);
+    let committee = validator_nodes
+        .get_committee_for_shard_group(Epoch(2), all_shards, false, 100)
+        .unwrap();
+    assert!(committee.is_empty());
+    let committee = validator_nodes
+        .get_committee_for_shard_group(Epoch(1), all_shards, false, 100)
+        .unwrap();
+    assert_eq!(committee.len(), 2);
+}
+
+#[test]
+fn insert_same_registration_twice() {
+    let db = create_db();
+    let mut tx = db.create_transaction().unwrap();
+    let mut validator_nodes = db.validator_nodes(&mut tx);
+    let pk = new_public_key();
+    insert_vn_with_public_key(&mut validator_nodes, pk.clone(), Epoch(1));
+    insert_vn_with_public_key(&mut validator_nodes, pk, Epoch(1));
+    assert_eq!(
+        validator_nodes
+            .get_all_registered_within_start_epoch(Epoch(1))
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn get_templates_paginated() {
+    let db = create_db();
+    let mut tx = db.create_transaction().unwrap();
+    let mut templates = db.templates(&mut tx);
+    let author = new_public_key();
+    for i in 1..=5u8 {
+        let mut template = DbTemplate::empty_pending(
+            TemplateAddress::from_array([i; 32]),
+            if i % 2 == 0 { author.clone() } else { new_public_key() },
+            Epoch(0),
+        );
+        template.template_name = format!("template_{i}");
+        template.status = TemplateStatus::Active;
+        templates.insert_template(template).unwrap();
+    }
+
+    let page = templates
+        .get_templates(&DbTemplateQuery {
+            limit: 2,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[1].template_address, TemplateAddress::from_array([2; 32]));
+
+    let page = templates
+        .get_templates(&DbTemplateQuery {
+            limit: 2,
+            after: Some(page[1].template_address),
+            ..Default::default()
+        })
+        .unwrap();
+    let addresses = page.iter().map(|t| t.template_address).collect::<Vec<_>>();
+    assert_eq!(addresses, [
+        TemplateAddress::from_array([3; 32]),
+        TemplateAddress::from_array([4; 32])
+    ]);
+
+    let page = templates
+        .get_templates(&DbTemplateQuery {
+            after: Some(TemplateAddress::from_array([5; 32])),
+            ordering: Some(Ordering::Descending),
+            author_public_key: Some(author),
+            ..Default::default()
+        })
+        .unwrap();
+    let names = page.iter().map(|t| t.template_name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["template_4", "template_2"]);
+
+    let page = templates
+        .get_templates(&DbTemplateQuery {
+            name: Some("PLATE_3".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(page.len(), 1);
+}
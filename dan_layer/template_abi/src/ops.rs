@@ -40,6 +40,8 @@ pub enum EngineOp {
     CallInvoke = 0x0C,
     ProofInvoke = 0x0D,
     BuiltinTemplateInvoke = 0x0E,
+    CryptoInvoke = 0x0F,
+    EmitTypedEvent = 0x10,
 }
 
 impl EngineOp {
@@ -60,6 +62,8 @@ impl EngineOp {
             0x0C => Some(EngineOp::CallInvoke),
             0x0D => Some(EngineOp::ProofInvoke),
             0x0E => Some(EngineOp::BuiltinTemplateInvoke),
+            0x0F => Some(EngineOp::CryptoInvoke),
+            0x10 => Some(EngineOp::EmitTypedEvent),
             _ => None,
         }
     }
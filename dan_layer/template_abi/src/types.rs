@@ -58,6 +58,18 @@ impl TemplateDef {
             TemplateDef::V1(def) => &def.functions,
         }
     }
+
+    pub fn events(&self) -> &[EventDef] {
+        match self {
+            TemplateDef::V1(def) => &def.events,
+        }
+    }
+
+    pub fn get_event(&self, topic: &str) -> Option<&EventDef> {
+        match self {
+            TemplateDef::V1(def) => def.get_event(topic),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,12 +82,20 @@ pub struct TemplateDefV1 {
     pub template_name: String,
     pub tari_version: String,
     pub functions: Vec<FunctionDef>,
+    /// Events with structured payloads that may be emitted by the template. Templates compiled before typed events
+    /// were supported do not include this field.
+    #[serde(default)]
+    pub events: Vec<EventDef>,
 }
 
 impl TemplateDefV1 {
     pub fn get_function(&self, name: &str) -> Option<&FunctionDef> {
         self.functions.iter().find(|f| f.name.as_str() == name)
     }
+
+    pub fn get_event(&self, topic: &str) -> Option<&EventDef> {
+        self.events.iter().find(|e| e.topic.as_str() == topic)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +111,19 @@ pub struct FunctionDef {
     pub is_mut: bool,
 }
 
+/// Describes an event declared with `#[event]`. The payload of the event is a CBOR map containing the fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct EventDef {
+    pub name: String,
+    pub topic: String,
+    pub fields: Vec<ArgDef>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -30,30 +30,76 @@ mod account_template {
     pub struct Account {
         // TODO: Lazy key value map/store
         vaults: BTreeMap<ResourceAddress, Vault>,
+        /// Only set for policy accounts, which authorize calls themselves instead of using the component access rules
+        #[serde(default)]
+        policy: Option<PolicyState>,
+    }
+
+    pub struct PolicyState {
+        owner_key: RistrettoPublicKeyBytes,
+        policy: AccountPolicy,
+        spent: BTreeMap<ResourceAddress, SpendingWindow>,
+        pending_recoveries: Vec<PendingRecovery>,
+    }
+
+    pub struct SpendingWindow {
+        start_epoch: u64,
+        spent: Amount,
+    }
+
+    pub struct PendingRecovery {
+        new_owner_key: RistrettoPublicKeyBytes,
+        approvals: Vec<RistrettoPublicKeyBytes>,
     }
 
     impl Account {
-        pub fn create(public_key_token: NonFungibleAddress, owner_rule: Option<OwnerRule>, access_rules: Option<AccessRules>, bucket: Option<Bucket>) -> Component<Account> {
+        pub fn create(
+            public_key_token: NonFungibleAddress,
+            owner_rule: Option<OwnerRule>,
+            access_rules: Option<AccessRules>,
+            bucket: Option<Bucket>,
+            policy: Option<AccountPolicy>,
+        ) -> Component<Account> {
             // extract the public key from the token
             // we only allow tokens that correspond to public keys
             let public_key = public_key_token
                 .to_public_key()
                 .unwrap_or_else(|| panic!("public_key_token is not a valid public key: {}", public_key_token));
 
-            let owner_rule = owner_rule.unwrap_or(
-                OwnerRule::ByPublicKey(public_key)
-            );
-
-            let access_rules = access_rules.unwrap_or(
-                AccessRules::new()
-                    .add_method_rule("balance", rule!(allow_all))
-                    .add_method_rule("get_balances", rule!(allow_all))
-                    .add_method_rule("deposit", rule!(allow_all))
-                    .add_method_rule("deposit_all", rule!(allow_all))
-                    .add_method_rule("get_non_fungible_ids", rule!(allow_all))
-                    // By defaul, only the owner of the token will be able to withdraw funds from the account
-                    .default(rule!(non_fungible(public_key_token)))
-            );
+            let (owner_rule, access_rules, policy) = match policy {
+                Some(policy) => {
+                    if let Err(err) = policy.validate() {
+                        panic!("Invalid account policy: {}", err);
+                    }
+                    // The policy can rotate the owner key, so the account authorizes every call itself. Nobody may
+                    // change the access rules, otherwise the old owner key could bypass the policy.
+                    let owner_rule = owner_rule.unwrap_or(OwnerRule::None);
+                    let access_rules = access_rules.unwrap_or(AccessRules::new().default(rule!(allow_all)));
+                    let policy = PolicyState {
+                        owner_key: public_key,
+                        policy,
+                        spent: BTreeMap::new(),
+                        pending_recoveries: Vec::new(),
+                    };
+                    (owner_rule, access_rules, Some(policy))
+                },
+                None => {
+                    let owner_rule = owner_rule.unwrap_or(OwnerRule::ByPublicKey(public_key));
+                    let access_rules = access_rules.unwrap_or(
+                        AccessRules::new()
+                            .add_method_rule("balance", rule!(allow_all))
+                            .add_method_rule("get_balances", rule!(allow_all))
+                            .add_method_rule("deposit", rule!(allow_all))
+                            .add_method_rule("deposit_all", rule!(allow_all))
+                            .add_method_rule("deposit_with_payment", rule!(allow_all))
+                            .add_method_rule("get_non_fungible_ids", rule!(allow_all))
+                            .add_method_rule("get_policy", rule!(allow_all))
+                            // By defaul, only the owner of the token will be able to withdraw funds from the account
+                            .default(rule!(non_fungible(public_key_token))),
+                    );
+                    (owner_rule, access_rules, None)
+                },
+            };
 
             // add the funds from the (optional) bucket
             let mut vaults = BTreeMap::new();
@@ -61,7 +107,7 @@ mod account_template {
                 vaults.insert(b.resource_address(), Vault::from_bucket(b));
             }
 
-            Component::new(Self { vaults })
+            Component::new(Self { vaults, policy })
                 .with_access_rules(access_rules)
                 .with_public_key_address(public_key)
                 .with_owner_rule(owner_rule)
@@ -70,6 +116,7 @@ mod account_template {
 
         // #[access_rule(allow_all)]
         pub fn balance(&self, resource: ResourceAddress) -> Amount {
+            self.authorize("balance");
             self.vaults
                 .get(&resource)
                 .map(|v| v.balance())
@@ -77,11 +124,15 @@ mod account_template {
         }
 
         pub fn confidential_commitment_count(&self, resource: ResourceAddress) -> u32 {
+            self.authorize("confidential_commitment_count");
             self.get_vault(resource).commitment_count()
         }
 
         // #[access_rule(requires(owner_badge))]
         pub fn withdraw(&mut self, resource: ResourceAddress, amount: Amount) -> Bucket {
+            let is_session_key = self.authorize("withdraw");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.record_spend(resource, amount);
             // TODO: clean up hashmap api in emit_event
             emit_event("withdraw", [
                 ("amount", amount.to_string()),
@@ -93,6 +144,9 @@ mod account_template {
 
         // #[access_rules(requires(owner_badge))]
         pub fn withdraw_non_fungible(&mut self, resource: ResourceAddress, nf_id: NonFungibleId) -> Bucket {
+            let is_session_key = self.authorize("withdraw_non_fungible");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.record_spend(resource, Amount::new(1));
             emit_event("withdraw_non_fungible", [
                 ("id", nf_id.to_string()),
                 ("resource", resource.to_string()),
@@ -101,7 +155,31 @@ mod account_template {
             v.withdraw_non_fungibles([nf_id])
         }
 
+        /// Withdraws a non-fungible token of a resource that has a withdraw transfer hook (e.g. royalties), passing the
+        /// payment bucket to the hook
+        pub fn withdraw_non_fungible_with_payment(
+            &mut self,
+            resource: ResourceAddress,
+            nf_id: NonFungibleId,
+            payment: Bucket,
+        ) -> Bucket {
+            let is_session_key = self.authorize("withdraw_non_fungible_with_payment");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.record_spend(resource, Amount::new(1));
+            emit_event("withdraw_non_fungible_with_payment", [
+                ("id", nf_id.to_string()),
+                ("resource", resource.to_string()),
+                ("payment_amount", payment.amount().to_string()),
+                ("payment_resource", payment.resource_address().to_string()),
+            ]);
+            let v = self.get_vault_mut(resource);
+            v.withdraw_non_fungibles_with_payment([nf_id], payment)
+        }
+
         pub fn withdraw_many_non_fungibles(&mut self, resource: ResourceAddress, nf_ids: Vec<NonFungibleId>) -> Bucket {
+            let is_session_key = self.authorize("withdraw_many_non_fungibles");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.record_spend(resource, Amount::from(nf_ids.len() as u32));
             emit_event("withdraw_many_non_fungibles", [
                 ("resource", resource.to_string()),
                 (
@@ -119,6 +197,9 @@ mod account_template {
             resource: ResourceAddress,
             withdraw_proof: ConfidentialWithdrawProof,
         ) -> Bucket {
+            let is_session_key = self.authorize("withdraw_confidential");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.check_no_spending_limit(resource);
             emit_event("withdraw_confidential", [
                 ("num_inputs", withdraw_proof.inputs.len().to_string()),
                 ("resource", resource.to_string()),
@@ -128,32 +209,96 @@ mod account_template {
             v.withdraw_confidential(withdraw_proof)
         }
 
+        /// Withdraws an amount of a resource and deposits it into the destination account. Unlike `withdraw`, session
+        /// keys of a policy account that restricts destinations may call this method.
+        pub fn transfer(&mut self, resource: ResourceAddress, amount: Amount, destination: ComponentAddress) {
+            let is_session_key = self.authorize("transfer");
+            self.check_destination_allowed(is_session_key, &destination);
+            self.record_spend(resource, amount);
+            emit_event("transfer", [
+                ("amount", amount.to_string()),
+                ("resource", resource.to_string()),
+                ("destination", destination.to_string()),
+            ]);
+            let bucket = self.get_vault_mut(resource).withdraw(amount);
+            ComponentManager::get(destination).invoke("deposit", args![bucket]);
+        }
+
+        /// Withdraws non-fungible tokens and deposits them into the destination account. Unlike
+        /// `withdraw_many_non_fungibles`, session keys of a policy account that restricts destinations may call this
+        /// method.
+        pub fn transfer_non_fungibles(
+            &mut self,
+            resource: ResourceAddress,
+            nf_ids: Vec<NonFungibleId>,
+            destination: ComponentAddress,
+        ) {
+            let is_session_key = self.authorize("transfer_non_fungibles");
+            self.check_destination_allowed(is_session_key, &destination);
+            self.record_spend(resource, Amount::from(nf_ids.len() as u32));
+            emit_event("transfer_non_fungibles", [
+                ("resource", resource.to_string()),
+                (
+                    "ids",
+                    nf_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
+                ),
+                ("destination", destination.to_string()),
+            ]);
+            let bucket = self.get_vault_mut(resource).withdraw_non_fungibles(nf_ids);
+            ComponentManager::get(destination).invoke("deposit", args![bucket]);
+        }
+
         // #[access_rules(allow_all)]
         pub fn deposit(&mut self, bucket: Bucket) {
-            emit_event("deposit", [
+            self.authorize("deposit");
+            self.deposit_bucket(bucket);
+        }
+
+        /// Deposits a bucket of a resource that has a deposit transfer hook, passing the payment bucket to the hook
+        // #[access_rules(allow_all)]
+        pub fn deposit_with_payment(&mut self, bucket: Bucket, payment: Bucket) {
+            self.authorize("deposit_with_payment");
+            emit_event("deposit_with_payment", [
                 ("amount", bucket.amount().to_string()),
                 ("resource", bucket.resource_address().to_string()),
+                ("payment_amount", payment.amount().to_string()),
+                ("payment_resource", payment.resource_address().to_string()),
             ]);
             let resource_address = bucket.resource_address();
             let vault_mut = self
                 .vaults
                 .entry(resource_address)
                 .or_insert_with(|| Vault::new_empty(resource_address));
-            vault_mut.deposit(bucket);
+            vault_mut.deposit_with_payment(bucket, payment);
         }
 
         pub fn deposit_all(&mut self, buckets: Vec<Bucket>) {
+            self.authorize("deposit_all");
             for bucket in buckets {
-                self.deposit(bucket);
+                self.deposit_bucket(bucket);
             }
         }
 
         // #[access_rules(require(owner_badge))]
         pub fn get_non_fungible_ids(&self, resource: ResourceAddress) -> Vec<NonFungibleId> {
+            self.authorize("get_non_fungible_ids");
             let v = self.get_vault(resource);
             v.get_non_fungible_ids()
         }
 
+        fn deposit_bucket(&mut self, bucket: Bucket) {
+            emit_event("deposit", [
+                ("amount", bucket.amount().to_string()),
+                ("resource", bucket.resource_address().to_string()),
+            ]);
+            let resource_address = bucket.resource_address();
+            let vault_mut = self
+                .vaults
+                .entry(resource_address)
+                .or_insert_with(|| Vault::new_empty(resource_address));
+            vault_mut.deposit(bucket);
+        }
+
         fn get_vault(&self, resource: ResourceAddress) -> &Vault {
             self.vaults
                 .get(&resource)
@@ -167,10 +312,14 @@ mod account_template {
         }
 
         pub fn get_balances(&self) -> Vec<(ResourceAddress, Amount)> {
+            self.authorize("get_balances");
             self.vaults.iter().map(|(k, v)| (*k, v.balance())).collect()
         }
 
         pub fn reveal_confidential(&mut self, resource: ResourceAddress, proof: ConfidentialWithdrawProof) -> Bucket {
+            let is_session_key = self.authorize("reveal_confidential");
+            self.check_bucket_withdraw_allowed(is_session_key);
+            self.check_no_spending_limit(resource);
             emit_event("reveal_confidential", [
                 ("num_inputs", proof.inputs.len().to_string()),
                 ("resource", resource.to_string()),
@@ -180,6 +329,7 @@ mod account_template {
         }
 
         pub fn join_confidential(&mut self, resource: ResourceAddress, proof: ConfidentialWithdrawProof) {
+            self.authorize("join_confidential");
             emit_event("join_confidential", [
                 ("num_inputs", proof.inputs.len().to_string()),
                 ("resource", resource.to_string()),
@@ -191,18 +341,22 @@ mod account_template {
 
         /// Pay fees from previously revealed confidential resource.
         pub fn pay_fee(&mut self, amount: Amount) {
+            self.authorize("pay_fee");
+            self.record_spend(CONFIDENTIAL_TARI_RESOURCE_ADDRESS, amount);
             emit_event("pay_fee", [("amount", amount.to_string())]);
             self.get_vault_mut(CONFIDENTIAL_TARI_RESOURCE_ADDRESS).pay_fee(amount);
         }
 
         /// Reveal confidential tokens and return the revealed bucket to pay fees.
         pub fn pay_fee_confidential(&mut self, proof: ConfidentialWithdrawProof) {
+            self.authorize("pay_fee_confidential");
             emit_event("pay_fee_confidential", [("num_inputs", proof.inputs.len().to_string())]);
             self.get_vault_mut(CONFIDENTIAL_TARI_RESOURCE_ADDRESS)
                 .pay_fee_confidential(proof);
         }
 
         pub fn create_proof_for_resource(&mut self, resource: ResourceAddress) -> Proof {
+            self.authorize("create_proof_for_resource");
             emit_event("create_proof_for_resource", [("resource", resource.to_string())]);
             let v = self.get_vault_mut(resource);
             v.create_proof()
@@ -213,6 +367,7 @@ mod account_template {
             resource: ResourceAddress,
             ids: Vec<NonFungibleId>,
         ) -> Proof {
+            self.authorize("create_proof_by_non_fungible_ids");
             emit_event("create_proof_by_non_fungible_ids", [
                 ("resource", resource.to_string()),
                 ("ids", ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")),
@@ -222,6 +377,7 @@ mod account_template {
         }
 
         pub fn create_proof_by_amount(&mut self, resource: ResourceAddress, amount: Amount) -> Proof {
+            self.authorize("create_proof_by_amount");
             emit_event("create_proof_by_amount", [
                 ("resource", resource.to_string()),
                 ("amount", amount.to_string()),
@@ -235,5 +391,233 @@ mod account_template {
         pub fn get_non_fungible_ids_for_bucket(bucket: Bucket) -> Vec<NonFungibleId> {
             bucket.get_non_fungible_ids()
         }
+
+        // Policy methods. These are only available on accounts that were created with a policy.
+
+        /// Returns the policy of the account, or None if the account is not a policy account
+        pub fn get_policy(&self) -> Option<AccountPolicy> {
+            self.authorize("get_policy");
+            self.policy.as_ref().map(|state| state.policy.clone())
+        }
+
+        /// Replaces the policy of the account. Amounts already spent in the current spending windows still count
+        /// towards the new limits.
+        pub fn set_policy(&mut self, policy: AccountPolicy) {
+            self.authorize("set_policy");
+            if let Err(err) = policy.validate() {
+                panic!("Invalid account policy: {}", err);
+            }
+            let state = self.policy_state_mut();
+            state.spent.retain(|resource, _| policy.spending_limits.contains_key(resource));
+            state.pending_recoveries.clear();
+            state.policy = policy;
+            emit_event("set_policy", [("owner", state.owner_key.to_string())]);
+        }
+
+        pub fn add_session_key(&mut self, public_key: RistrettoPublicKeyBytes, expires_at_epoch: u64) {
+            self.authorize("add_session_key");
+            let current_epoch = Consensus::current_epoch();
+            assert!(
+                expires_at_epoch > current_epoch,
+                "Session key would expire at epoch {} which is not after the current epoch {}",
+                expires_at_epoch,
+                current_epoch
+            );
+            let state = self.policy_state_mut();
+            // Expired keys are removed whenever a key is added so that the list does not grow forever
+            state.policy.session_keys.retain(|k| k.public_key != public_key && !k.is_expired(current_epoch));
+            state.policy.session_keys.push(SessionKey::new(public_key, expires_at_epoch));
+            emit_event("add_session_key", [
+                ("public_key", public_key.to_string()),
+                ("expires_at_epoch", expires_at_epoch.to_string()),
+            ]);
+        }
+
+        pub fn revoke_session_key(&mut self, public_key: RistrettoPublicKeyBytes) {
+            self.authorize("revoke_session_key");
+            let state = self.policy_state_mut();
+            let num_keys = state.policy.session_keys.len();
+            state.policy.session_keys.retain(|k| k.public_key != public_key);
+            assert!(
+                state.policy.session_keys.len() < num_keys,
+                "{} is not a session key of this account",
+                public_key
+            );
+            emit_event("revoke_session_key", [("public_key", public_key.to_string())]);
+        }
+
+        /// Records the approval of the signing guardian to rotate the owner key of the account to `new_owner_key`. The
+        /// owner key is rotated, and all session keys are revoked, once the recovery threshold is reached.
+        pub fn approve_recovery(&mut self, new_owner_key: RistrettoPublicKeyBytes) {
+            self.authorize("approve_recovery");
+            assert_top_level_call("approve_recovery");
+            let guardian = CallerContext::transaction_signer_public_key();
+            let state = self.policy_state_mut();
+            assert!(
+                state.policy.is_guardian(&guardian),
+                "{} is not a guardian of this account",
+                guardian
+            );
+
+            let recovery = match state
+                .pending_recoveries
+                .iter()
+                .position(|r| r.new_owner_key == new_owner_key)
+            {
+                Some(pos) => &mut state.pending_recoveries[pos],
+                None => {
+                    state.pending_recoveries.push(PendingRecovery {
+                        new_owner_key,
+                        approvals: Vec::new(),
+                    });
+                    state.pending_recoveries.last_mut().unwrap()
+                },
+            };
+            if !recovery.approvals.contains(&guardian) {
+                recovery.approvals.push(guardian);
+            }
+            let num_approvals = recovery.approvals.len();
+            emit_event("approve_recovery", [
+                ("guardian", guardian.to_string()),
+                ("new_owner_key", new_owner_key.to_string()),
+                ("approvals", num_approvals.to_string()),
+            ]);
+
+            if num_approvals >= state.policy.recovery_threshold as usize {
+                emit_event("recover_owner", [
+                    ("old_owner_key", state.owner_key.to_string()),
+                    ("new_owner_key", new_owner_key.to_string()),
+                ]);
+                state.owner_key = new_owner_key;
+                state.policy.session_keys.clear();
+                state.pending_recoveries.clear();
+            }
+        }
+
+        /// Cancels all pending recoveries, for example if a guardian key was compromised
+        pub fn cancel_recovery(&mut self) {
+            self.authorize("cancel_recovery");
+            let state = self.policy_state_mut();
+            emit_event("cancel_recovery", [("num_recoveries", state.pending_recoveries.len().to_string())]);
+            state.pending_recoveries.clear();
+        }
+
+        /// Checks the policy rule for a method against the transaction signer, panicking if the call is not allowed.
+        /// Returns true if the call was authorized by a session key rather than the owner key. Accounts without a
+        /// policy are authorized by their component access rules, so this always returns false for them.
+        fn authorize(&self, method: &str) -> bool {
+            let Some(state) = self.policy.as_ref() else {
+                return false;
+            };
+            match state.policy.rule_for_method(method) {
+                AccountMethodRule::AllowAll => false,
+                AccountMethodRule::DenyAll => panic!("Method {} is denied by the account policy", method),
+                AccountMethodRule::Owner => {
+                    assert_top_level_call(method);
+                    let signer = CallerContext::transaction_signer_public_key();
+                    assert!(
+                        signer == state.owner_key,
+                        "Method {} can only be called by the account owner",
+                        method
+                    );
+                    false
+                },
+                AccountMethodRule::OwnerOrSessionKey => {
+                    assert_top_level_call(method);
+                    let signer = CallerContext::transaction_signer_public_key();
+                    if signer == state.owner_key {
+                        return false;
+                    }
+                    assert!(
+                        state
+                            .policy
+                            .has_valid_session_key(&signer, Consensus::current_epoch()),
+                        "Method {} can only be called by the account owner or an unexpired session key",
+                        method
+                    );
+                    true
+                },
+            }
+        }
+
+        /// Session keys may not withdraw buckets, which could be deposited anywhere, if the policy restricts the
+        /// destinations that they may send funds to
+        fn check_bucket_withdraw_allowed(&self, is_session_key: bool) {
+            let Some(state) = self.policy.as_ref() else {
+                return;
+            };
+            if is_session_key && state.policy.allowed_destinations.is_some() {
+                panic!("Session keys may only send funds to allowed destinations using the transfer methods");
+            }
+        }
+
+        fn check_destination_allowed(&self, is_session_key: bool, destination: &ComponentAddress) {
+            let Some(state) = self.policy.as_ref() else {
+                return;
+            };
+            if is_session_key && !state.policy.is_destination_allowed(destination) {
+                panic!("{} is not an allowed destination for session keys", destination);
+            }
+        }
+
+        /// Confidential amounts cannot be counted, so confidential withdrawals of a limited resource are not allowed
+        fn check_no_spending_limit(&self, resource: ResourceAddress) {
+            let Some(state) = self.policy.as_ref() else {
+                return;
+            };
+            if state.policy.spending_limits.contains_key(&resource) {
+                panic!(
+                    "Resource {} has a spending limit and cannot be withdrawn confidentially",
+                    resource
+                );
+            }
+        }
+
+        /// Adds the amount to the spent amount of the resource in the current spending window, panicking if this would
+        /// exceed the spending limit of the resource
+        fn record_spend(&mut self, resource: ResourceAddress, amount: Amount) {
+            let Some(state) = self.policy.as_mut() else {
+                return;
+            };
+            let Some(limit) = state.policy.spending_limits.get(&resource).copied() else {
+                return;
+            };
+
+            let current_epoch = Consensus::current_epoch();
+            let window = state.spent.entry(resource).or_insert_with(|| SpendingWindow {
+                start_epoch: current_epoch,
+                spent: Amount::zero(),
+            });
+            if current_epoch >= window.start_epoch.saturating_add(limit.window_epochs) {
+                window.start_epoch = current_epoch;
+                window.spent = Amount::zero();
+            }
+
+            let spent = window.spent + amount;
+            if spent > limit.amount {
+                panic!(
+                    "Withdrawing {} of resource {} exceeds the spending limit of {} per {} epoch(s). {} has already \
+                     been spent since epoch {}",
+                    amount, resource, limit.amount, limit.window_epochs, window.spent, window.start_epoch
+                );
+            }
+            window.spent = spent;
+        }
+
+        fn policy_state_mut(&mut self) -> &mut PolicyState {
+            self.policy
+                .as_mut()
+                .unwrap_or_else(|| panic!("This account does not have a policy"))
+        }
     }
 }
+
+/// Policy accounts authorize calls by the transaction signer. Only calls made directly by a transaction instruction
+/// may act with the signer's authority, otherwise any component that the signer calls could spend from the account.
+fn assert_top_level_call(method: &str) {
+    assert!(
+        CallerContext::is_top_level_call(),
+        "Method {} of a policy account must be called directly by the transaction",
+        method
+    );
+}
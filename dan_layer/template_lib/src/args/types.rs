@@ -33,7 +33,7 @@ use ts_rs::TS;
 use crate::{
     args::Arg,
     auth::{AuthHook, OwnerRule, ResourceAccessRules},
-    crypto::{PedersonCommitmentBytes, RistrettoPublicKeyBytes},
+    crypto::{HashAlgorithm, MerkleProofNode, PedersonCommitmentBytes, RistrettoPublicKeyBytes, SchnorrSignatureBytes},
     models::{
         AddressAllocation,
         Amount,
@@ -49,8 +49,9 @@ use crate::{
         VaultRef,
     },
     prelude::{ComponentAccessRules, ConfidentialOutputStatement, TemplateAddress},
-    resource::ResourceType,
+    resource::{ResourceType, TransferHooks},
     template::BuiltinTemplate,
+    Hash,
 };
 
 // -------------------------------- LOGS -------------------------------- //
@@ -127,6 +128,7 @@ pub enum ComponentAction {
     SetState,
     SetAccessRules,
     GetTemplateAddress,
+    Destroy,
 }
 
 /// Encapsulates all the ways that a component can be referenced
@@ -172,6 +174,14 @@ pub struct EmitEventArg {
     pub payload: Metadata,
 }
 
+/// An emission of an event declared in the template ABI. The payload is validated by the engine against the declared
+/// event fields.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EmitTypedEventArg {
+    pub topic: String,
+    pub payload: tari_bor::Value,
+}
+
 // -------------------------------- Resource -------------------------------- //
 
 /// An operation over a resource
@@ -224,6 +234,8 @@ pub enum ResourceAction {
     GetResourceType,
     GetNonFungible,
     UpdateAccessRules,
+    GetDivisibility,
+    DestroyBurntNonFungibles,
 }
 
 /// All the possible minting operation types
@@ -260,6 +272,13 @@ pub struct CreateResourceArg {
     pub mint_arg: Option<MintArg>,
     pub view_key: Option<RistrettoPublicKeyBytes>,
     pub authorize_hook: Option<AuthHook>,
+    /// The number of decimal places used to display amounts of a fungible or confidential resource. Must be 0 for
+    /// non-fungible resources.
+    #[serde(default)]
+    pub divisibility: u8,
+    /// Component methods that are called when tokens of the resource are deposited or withdrawn
+    #[serde(default)]
+    pub transfer_hooks: TransferHooks,
 }
 
 /// A resource minting operation argument
@@ -274,6 +293,12 @@ pub struct ResourceGetNonFungibleArg {
     pub id: NonFungibleId,
 }
 
+/// An argument for removing burnt non-fungible tokens from state
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResourceDestroyBurntNonFungiblesArg {
+    pub ids: BTreeSet<NonFungibleId>,
+}
+
 /// A non-fungible resource update operation argument
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ResourceUpdateNonFungibleDataArg {
@@ -331,6 +356,7 @@ pub enum VaultAction {
     CreateProofByNonFungibles,
     CreateProofByConfidentialResource,
     GetNonFungibles,
+    Destroy,
 }
 
 impl VaultAction {
@@ -517,6 +543,7 @@ pub enum CallerContextAction {
     GetCallerPublicKey,
     GetComponentAddress,
     AllocateNewComponentAddress,
+    IsTopLevelCall,
 }
 
 // -------------------------------- CallInvoke -------------------------------- //
@@ -638,3 +665,69 @@ pub struct BuiltinTemplateInvokeArg {
 pub enum BuiltinTemplateAction {
     GetTemplateAddress { bultin: BuiltinTemplate },
 }
+
+// -------------------------------- Crypto -------------------------------- //
+
+/// A cryptography operation argument
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoInvokeArg {
+    pub action: CryptoAction,
+    pub args: Vec<Vec<u8>>,
+}
+
+/// The possible cryptography operations that are provided by the engine
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum CryptoAction {
+    VerifySchnorrSignature,
+    Hash,
+    AddCommitments,
+    SubtractCommitments,
+    CommitValue,
+    VerifyMerkleInclusion,
+}
+
+/// Verifies a Ristretto Schnorr signature `(public_nonce, signature)` for `public_key` over an arbitrary message
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoVerifySchnorrSignatureArg {
+    pub public_key: RistrettoPublicKeyBytes,
+    pub public_nonce: RistrettoPublicKeyBytes,
+    pub signature: SchnorrSignatureBytes,
+    pub message: Vec<u8>,
+}
+
+/// Hashes `data` with the given algorithm, separated by `domain`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoHashArg {
+    pub algorithm: HashAlgorithm,
+    pub domain: String,
+    pub data: Vec<u8>,
+}
+
+/// Sums the given Pedersen commitments
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoAddCommitmentsArg {
+    pub commitments: Vec<PedersonCommitmentBytes>,
+}
+
+/// Subtracts the `rhs` Pedersen commitment from `lhs`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoSubtractCommitmentsArg {
+    pub lhs: PedersonCommitmentBytes,
+    pub rhs: PedersonCommitmentBytes,
+}
+
+/// Creates a Pedersen commitment to `value` with a zero mask
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoCommitValueArg {
+    pub value: u64,
+}
+
+/// Verifies that `leaf` is included in the Merkle tree with the given `root`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryptoVerifyMerkleInclusionArg {
+    pub algorithm: HashAlgorithm,
+    pub domain: String,
+    pub root: Hash,
+    pub leaf: Vec<u8>,
+    pub proof: Vec<MerkleProofNode>,
+}
@@ -0,0 +1,287 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_template_abi::rust::{collections::BTreeMap, fmt};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    crypto::RistrettoPublicKeyBytes,
+    models::{Amount, ComponentAddress, ResourceAddress},
+};
+
+/// Methods of the account template that anyone may call unless the policy says otherwise
+const PUBLIC_ACCOUNT_METHODS: &[&str] = &[
+    "balance",
+    "get_balances",
+    "confidential_commitment_count",
+    "get_non_fungible_ids",
+    "deposit",
+    "deposit_all",
+    "deposit_with_payment",
+    "get_policy",
+    // Guardians are checked by the account itself
+    "approve_recovery",
+];
+
+/// Methods of the account template that spend funds or create proofs. Session keys may call these unless the policy
+/// says otherwise.
+const SPENDING_ACCOUNT_METHODS: &[&str] = &[
+    "withdraw",
+    "withdraw_non_fungible",
+    "withdraw_non_fungible_with_payment",
+    "withdraw_many_non_fungibles",
+    "withdraw_confidential",
+    "reveal_confidential",
+    "join_confidential",
+    "transfer",
+    "transfer_non_fungibles",
+    "pay_fee",
+    "pay_fee_confidential",
+    "create_proof_for_resource",
+    "create_proof_by_non_fungible_ids",
+    "create_proof_by_amount",
+];
+
+/// A policy that an account component enforces itself, in place of the fixed owner token rules of a default account.
+///
+/// Because the owner of a policy account can be rotated by its guardians, the account authorizes every call by
+/// checking the transaction signer against the policy rather than relying on the component access rules. Calls that
+/// act with the signer's authority must be made directly by the transaction and not through another component.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct AccountPolicy {
+    /// Who may call each account method. Methods that are not listed use the default rule for the method, see
+    /// [`AccountPolicy::rule_for_method`].
+    #[cfg_attr(feature = "ts", ts(type = "Record<string, AccountMethodRule>"))]
+    pub method_rules: BTreeMap<String, AccountMethodRule>,
+    /// The maximum amount of each resource that may be withdrawn from the account in a window of epochs. Revealed
+    /// fees paid with `pay_fee` are counted towards the limit of the confidential Tari resource.
+    #[cfg_attr(feature = "ts", ts(type = "Record<ResourceAddress, SpendingLimit>"))]
+    pub spending_limits: BTreeMap<ResourceAddress, SpendingLimit>,
+    /// Keys, other than the owner key, that may sign transactions that spend from the account until they expire
+    pub session_keys: Vec<SessionKey>,
+    /// If set, session keys may only send funds to these components using the `transfer` methods. The owner is not
+    /// restricted.
+    pub allowed_destinations: Option<Vec<ComponentAddress>>,
+    /// Keys that may together rotate the owner key of the account
+    #[cfg_attr(feature = "ts", ts(type = "Array<Array<number>>"))]
+    pub guardians: Vec<RistrettoPublicKeyBytes>,
+    /// The number of guardian approvals required to rotate the owner key
+    pub recovery_threshold: u32,
+}
+
+impl AccountPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_method_rule<T: Into<String>>(mut self, method: T, rule: AccountMethodRule) -> Self {
+        self.method_rules.insert(method.into(), rule);
+        self
+    }
+
+    pub fn with_spending_limit(mut self, resource_address: ResourceAddress, limit: SpendingLimit) -> Self {
+        self.spending_limits.insert(resource_address, limit);
+        self
+    }
+
+    pub fn with_session_key(mut self, session_key: SessionKey) -> Self {
+        self.session_keys.push(session_key);
+        self
+    }
+
+    pub fn with_allowed_destinations<I: IntoIterator<Item = ComponentAddress>>(mut self, destinations: I) -> Self {
+        self.allowed_destinations = Some(destinations.into_iter().collect());
+        self
+    }
+
+    pub fn with_guardians<I: IntoIterator<Item = RistrettoPublicKeyBytes>>(
+        mut self,
+        guardians: I,
+        threshold: u32,
+    ) -> Self {
+        self.guardians = guardians.into_iter().collect();
+        self.recovery_threshold = threshold;
+        self
+    }
+
+    /// Returns the rule for a method. If the policy does not set a rule for the method, read-only and deposit methods
+    /// are allowed for anyone, spending methods require the owner or a session key and all other methods require the
+    /// owner.
+    pub fn rule_for_method(&self, method: &str) -> AccountMethodRule {
+        if let Some(rule) = self.method_rules.get(method) {
+            return *rule;
+        }
+        if PUBLIC_ACCOUNT_METHODS.contains(&method) {
+            AccountMethodRule::AllowAll
+        } else if SPENDING_ACCOUNT_METHODS.contains(&method) {
+            AccountMethodRule::OwnerOrSessionKey
+        } else {
+            AccountMethodRule::Owner
+        }
+    }
+
+    /// Returns true if the key is a session key that has not expired at the given epoch
+    pub fn has_valid_session_key(&self, public_key: &RistrettoPublicKeyBytes, current_epoch: u64) -> bool {
+        self.session_keys
+            .iter()
+            .any(|k| k.public_key == *public_key && !k.is_expired(current_epoch))
+    }
+
+    pub fn is_guardian(&self, public_key: &RistrettoPublicKeyBytes) -> bool {
+        self.guardians.contains(public_key)
+    }
+
+    pub fn is_destination_allowed(&self, destination: &ComponentAddress) -> bool {
+        self.allowed_destinations
+            .as_ref()
+            .map_or(true, |allowed| allowed.contains(destination))
+    }
+
+    /// Checks that the policy is internally consistent
+    pub fn validate(&self) -> Result<(), AccountPolicyError> {
+        if self.guardians.is_empty() {
+            if self.recovery_threshold != 0 {
+                return Err(AccountPolicyError::RecoveryThresholdWithoutGuardians);
+            }
+        } else if self.recovery_threshold == 0 || self.recovery_threshold as usize > self.guardians.len() {
+            return Err(AccountPolicyError::InvalidRecoveryThreshold {
+                threshold: self.recovery_threshold,
+                num_guardians: self.guardians.len(),
+            });
+        }
+
+        if let Some((resource, _)) = self
+            .spending_limits
+            .iter()
+            .find(|(_, limit)| limit.amount.is_negative() || limit.window_epochs == 0)
+        {
+            return Err(AccountPolicyError::InvalidSpendingLimit { resource: *resource });
+        }
+
+        Ok(())
+    }
+}
+
+/// Who may call an account method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum AccountMethodRule {
+    AllowAll,
+    Owner,
+    OwnerOrSessionKey,
+    DenyAll,
+}
+
+/// The maximum amount of a resource that may be withdrawn within a window of epochs. For example, a daily limit is
+/// expressed using the number of epochs in a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SpendingLimit {
+    pub amount: Amount,
+    pub window_epochs: u64,
+}
+
+impl SpendingLimit {
+    pub fn new<A: Into<Amount>>(amount: A, window_epochs: u64) -> Self {
+        Self {
+            amount: amount.into(),
+            window_epochs,
+        }
+    }
+}
+
+/// A key that may sign transactions for an account until the given epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SessionKey {
+    #[cfg_attr(feature = "ts", ts(type = "Array<number>"))]
+    pub public_key: RistrettoPublicKeyBytes,
+    /// The first epoch in which the key can no longer be used
+    pub expires_at_epoch: u64,
+}
+
+impl SessionKey {
+    pub fn new(public_key: RistrettoPublicKeyBytes, expires_at_epoch: u64) -> Self {
+        Self {
+            public_key,
+            expires_at_epoch,
+        }
+    }
+
+    pub fn is_expired(&self, current_epoch: u64) -> bool {
+        current_epoch >= self.expires_at_epoch
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountPolicyError {
+    RecoveryThresholdWithoutGuardians,
+    InvalidRecoveryThreshold { threshold: u32, num_guardians: usize },
+    InvalidSpendingLimit { resource: ResourceAddress },
+}
+
+impl fmt::Display for AccountPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RecoveryThresholdWithoutGuardians => {
+                write!(f, "A recovery threshold cannot be set without guardians")
+            },
+            Self::InvalidRecoveryThreshold {
+                threshold,
+                num_guardians,
+            } => write!(
+                f,
+                "Recovery threshold must be between 1 and the number of guardians ({}) but was {}",
+                num_guardians, threshold
+            ),
+            Self::InvalidSpendingLimit { resource } => write!(
+                f,
+                "Spending limit for resource {} must have a non-negative amount and a non-zero window",
+                resource
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_uses_default_rules_for_unlisted_methods() {
+        let policy = AccountPolicy::new().with_method_rule("withdraw", AccountMethodRule::Owner);
+        assert_eq!(policy.rule_for_method("withdraw"), AccountMethodRule::Owner);
+        assert_eq!(policy.rule_for_method("deposit"), AccountMethodRule::AllowAll);
+        assert_eq!(policy.rule_for_method("pay_fee"), AccountMethodRule::OwnerOrSessionKey);
+        assert_eq!(policy.rule_for_method("set_policy"), AccountMethodRule::Owner);
+    }
+
+    #[test]
+    fn it_validates_the_recovery_threshold() {
+        let guardians = [RistrettoPublicKeyBytes::default(); 3];
+        assert!(AccountPolicy::new().validate().is_ok());
+        assert!(AccountPolicy::new().with_guardians(guardians, 2).validate().is_ok());
+        assert_eq!(
+            AccountPolicy::new().with_guardians(guardians, 4).validate(),
+            Err(AccountPolicyError::InvalidRecoveryThreshold {
+                threshold: 4,
+                num_guardians: 3
+            })
+        );
+        assert_eq!(
+            AccountPolicy::new().with_guardians([], 1).validate(),
+            Err(AccountPolicyError::RecoveryThresholdWithoutGuardians)
+        );
+    }
+
+    #[test]
+    fn it_expires_session_keys() {
+        let key = RistrettoPublicKeyBytes::default();
+        let policy = AccountPolicy::new().with_session_key(SessionKey::new(key, 10));
+        assert!(policy.has_valid_session_key(&key, 9));
+        assert!(!policy.has_valid_session_key(&key, 10));
+    }
+}
@@ -6,6 +6,9 @@
 mod access_rules;
 pub use access_rules::*;
 
+mod account_policy;
+pub use account_policy::*;
+
 mod auth_hook;
 pub use auth_hook::*;
 
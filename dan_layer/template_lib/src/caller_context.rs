@@ -49,4 +49,17 @@ impl CallerContext {
         resp.decode()
             .expect("Failed to decode AddressAllocation<ComponentAddress>")
     }
+
+    /// Returns true if the current call was made directly by a transaction instruction rather than by another
+    /// component or template. Only top-level calls carry the signer's proofs, so a template that authorizes calls by
+    /// the transaction signer must check this to prevent an intermediate component from acting on the signer's
+    /// behalf.
+    pub fn is_top_level_call() -> bool {
+        let resp: InvokeResult = call_engine(EngineOp::CallerContextInvoke, &CallerContextInvokeArg {
+            action: CallerContextAction::IsTopLevelCall,
+            args: invoke_args![],
+        });
+
+        resp.decode().expect("Failed to decode bool")
+    }
 }
@@ -122,6 +122,18 @@ impl ComponentManager {
         });
     }
 
+    /// Removes the component from state, earning a partial storage fee rebate. This must be called by the component
+    /// owner from a `&mut self` method of the component. Any vaults held by the component must be destroyed first and
+    /// the component state is discarded when the method returns.
+    /// It will panic if the component cannot be destroyed
+    pub fn destroy(&self) {
+        call_engine::<_, InvokeResult>(EngineOp::ComponentInvoke, &ComponentInvokeArg {
+            component_ref: ComponentRef::Ref(self.address),
+            action: ComponentAction::Destroy,
+            args: invoke_args![],
+        });
+    }
+
     /// Returns the template address of the component that is being managed
     pub fn get_template_address(&self) -> TemplateAddress {
         let result = call_engine::<_, InvokeResult>(EngineOp::ComponentInvoke, &ComponentInvokeArg {
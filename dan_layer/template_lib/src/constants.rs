@@ -20,6 +20,9 @@ pub const CONFIDENTIAL_TARI_RESOURCE_ADDRESS: ResourceAddress =
 /// Shorthand version of the `CONFIDENTIAL_TARI_RESOURCE_ADDRESS` constant
 pub const XTR: ResourceAddress = CONFIDENTIAL_TARI_RESOURCE_ADDRESS;
 
+/// The number of decimal places of the Tari network native resource. The smallest unit is one microTari.
+pub const XTR_DIVISIBILITY: u8 = 6;
+
 /// Address of testnet faucet component
 pub const XTR_FAUCET_COMPONENT_ADDRESS: ComponentAddress = ComponentAddress::new(ObjectKey::from_array([
     1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
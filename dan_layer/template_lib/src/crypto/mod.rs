@@ -1,16 +1,19 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-//! Cryptography utilities related to public keys and balance proofs
+//! Cryptography utilities related to public keys and balance proofs, and cryptographic operations provided by the
+//! engine
 
 mod balance_proof;
 mod commitment;
 mod error;
+mod operations;
 mod ristretto;
 mod schnorr;
 
 pub use balance_proof::*;
 pub use commitment::*;
 pub use error::*;
+pub use operations::*;
 pub use ristretto::*;
 pub use schnorr::*;
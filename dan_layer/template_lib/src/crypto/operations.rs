@@ -0,0 +1,127 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Cryptographic operations that are executed natively by the engine. These are far cheaper than compiling the
+//! equivalent code into a template, as they are charged a fixed fee rather than being metered per WASM instruction.
+
+use serde::{Deserialize, Serialize};
+use tari_template_abi::{call_engine, EngineOp};
+
+use crate::{
+    args::{
+        CryptoAction,
+        CryptoAddCommitmentsArg,
+        CryptoCommitValueArg,
+        CryptoHashArg,
+        CryptoInvokeArg,
+        CryptoSubtractCommitmentsArg,
+        CryptoVerifyMerkleInclusionArg,
+        CryptoVerifySchnorrSignatureArg,
+        InvokeResult,
+    },
+    crypto::{PedersonCommitmentBytes, RistrettoPublicKeyBytes, SchnorrSignatureBytes},
+    invoke_args,
+    Hash,
+};
+
+/// The hash algorithms supported by the engine. All produce a 32-byte digest.
+///
+/// Domain separation is applied by prefixing the data with the length of the domain as a little-endian u64 followed
+/// by the domain bytes i.e. `H(len(domain) || domain || data)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    Blake2b256,
+    Sha256,
+    Keccak256,
+}
+
+/// The side on which a sibling hash is combined with the current hash when computing the parent node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MerkleSide {
+    Left,
+    Right,
+}
+
+/// A single step of a Merkle inclusion proof, ordered from the leaf to the root.
+///
+/// Leaves are hashed as `H(0x00 || leaf)` and nodes as `H(0x01 || left || right)`, both domain separated as described
+/// in [HashAlgorithm].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProofNode {
+    pub sibling: Hash,
+    pub side: MerkleSide,
+}
+
+/// Returns true if `(public_nonce, signature)` is a valid Ristretto Schnorr signature for `public_key` over `message`.
+/// The challenge is computed in the same way as `tari_crypto::ristretto::RistrettoSchnorr`.
+pub fn verify_schnorr_signature<T: AsRef<[u8]>>(
+    public_key: &RistrettoPublicKeyBytes,
+    public_nonce: &RistrettoPublicKeyBytes,
+    signature: &SchnorrSignatureBytes,
+    message: T,
+) -> bool {
+    invoke(CryptoAction::VerifySchnorrSignature, invoke_args![
+        CryptoVerifySchnorrSignatureArg {
+            public_key: *public_key,
+            public_nonce: *public_nonce,
+            signature: *signature,
+            message: message.as_ref().to_vec(),
+        }
+    ])
+}
+
+/// Returns the domain separated hash of `data` using the given algorithm
+pub fn hash<T: AsRef<[u8]>>(algorithm: HashAlgorithm, domain: &str, data: T) -> Hash {
+    invoke(CryptoAction::Hash, invoke_args![CryptoHashArg {
+        algorithm,
+        domain: domain.to_string(),
+        data: data.as_ref().to_vec(),
+    }])
+}
+
+/// Returns the sum of the given Pedersen commitments.
+/// It will panic if any of the commitments are not valid
+pub fn add_commitments<I: IntoIterator<Item = PedersonCommitmentBytes>>(commitments: I) -> PedersonCommitmentBytes {
+    invoke(CryptoAction::AddCommitments, invoke_args![CryptoAddCommitmentsArg {
+        commitments: commitments.into_iter().collect(),
+    }])
+}
+
+/// Returns `lhs - rhs`.
+/// It will panic if either of the commitments are not valid
+pub fn subtract_commitments(lhs: &PedersonCommitmentBytes, rhs: &PedersonCommitmentBytes) -> PedersonCommitmentBytes {
+    invoke(CryptoAction::SubtractCommitments, invoke_args![
+        CryptoSubtractCommitmentsArg { lhs: *lhs, rhs: *rhs }
+    ])
+}
+
+/// Returns a Pedersen commitment to `value` with a zero mask. This is typically used to check a commitment against a
+/// revealed value.
+pub fn commit_value(value: u64) -> PedersonCommitmentBytes {
+    invoke(CryptoAction::CommitValue, invoke_args![CryptoCommitValueArg { value }])
+}
+
+/// Returns true if `leaf` is included in the Merkle tree with the given `root`
+pub fn verify_merkle_inclusion<T: AsRef<[u8]>>(
+    algorithm: HashAlgorithm,
+    domain: &str,
+    root: &Hash,
+    leaf: T,
+    proof: Vec<MerkleProofNode>,
+) -> bool {
+    invoke(CryptoAction::VerifyMerkleInclusion, invoke_args![
+        CryptoVerifyMerkleInclusionArg {
+            algorithm,
+            domain: domain.to_string(),
+            root: *root,
+            leaf: leaf.as_ref().to_vec(),
+            proof,
+        }
+    ])
+}
+
+fn invoke<T: serde::de::DeserializeOwned>(action: CryptoAction, args: Vec<Vec<u8>>) -> T {
+    let resp: InvokeResult = call_engine(EngineOp::CryptoInvoke, &CryptoInvokeArg { action, args });
+    resp.decode()
+        .unwrap_or_else(|e| panic!("Crypto {:?} returned an invalid result: {}", action, e))
+}
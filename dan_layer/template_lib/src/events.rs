@@ -22,9 +22,20 @@
 
 //! A wrapper for engine calls related to events
 
+use serde::Serialize;
 use tari_template_abi::{call_engine, EngineOp};
 
-use crate::{args::EmitEventArg, models::Metadata};
+use crate::{
+    args::{EmitEventArg, EmitTypedEventArg},
+    models::Metadata,
+};
+
+/// An event with a structured payload that is declared in the template ABI. This is implemented by the `#[event]`
+/// attribute macro and should not usually be implemented manually.
+pub trait TemplateEvent: Serialize {
+    /// The topic of the event. This matches the topic of the event definition in the template ABI.
+    const TOPIC: &'static str;
+}
 
 /// Requests the engine to emit an event that will be permanently recorded in the transaction result
 pub fn emit_event<T: Into<String>, P: Into<Metadata>>(topic: T, payload: P) {
@@ -33,3 +44,14 @@ pub fn emit_event<T: Into<String>, P: Into<Metadata>>(topic: T, payload: P) {
         payload: payload.into(),
     });
 }
+
+/// Requests the engine to emit a typed event. The engine rejects the event if it is not declared by the calling
+/// template or if the payload does not match the declared fields.
+pub fn emit<E: TemplateEvent>(event: &E) {
+    let payload =
+        tari_bor::to_value(event).unwrap_or_else(|e| panic!("Failed to encode event payload for {}: {}", E::TOPIC, e));
+    call_engine::<_, ()>(EngineOp::EmitTypedEvent, &EmitTypedEventArg {
+        topic: E::TOPIC.to_string(),
+        payload,
+    });
+}
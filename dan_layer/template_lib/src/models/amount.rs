@@ -124,6 +124,62 @@ impl Amount {
     pub fn as_u64_checked(&self) -> Option<u64> {
         self.0.try_into().ok()
     }
+
+    /// Formats the amount as a decimal string with `divisibility` decimal places. Trailing zeros of the fractional part
+    /// are omitted, e.g. 1_500_000 with a divisibility of 6 is formatted as "1.5".
+    pub fn to_decimal_string(&self, divisibility: u8) -> String {
+        let Some(divisor) = 10u64.checked_pow(u32::from(divisibility)) else {
+            return self.0.to_string();
+        };
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let whole = abs / divisor;
+        let fraction = abs % divisor;
+        if fraction == 0 {
+            return format!("{sign}{whole}");
+        }
+        let fraction = format!("{fraction:0width$}", width = usize::from(divisibility));
+        format!("{sign}{whole}.{}", fraction.trim_end_matches('0'))
+    }
+
+    /// Parses a decimal string with at most `divisibility` decimal places into an amount of the smallest unit, e.g.
+    /// "1.5" with a divisibility of 6 is 1_500_000. Returns None if the string is not a decimal number, has too many
+    /// decimal places or the amount overflows.
+    pub fn from_decimal_str(s: &str, divisibility: u8) -> Option<Self> {
+        let s = s.trim();
+        let (is_negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if fraction.len() > usize::from(divisibility) {
+            return None;
+        }
+
+        let multiplier = 10i64.checked_pow(u32::from(divisibility))?;
+        let whole = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<i64>().ok()?
+        };
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            // The fraction is scaled up to the smallest unit e.g. "5" with a divisibility of 6 is 500_000
+            fraction
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(10i64.pow(u32::from(divisibility) - fraction.len() as u32))?
+        };
+        let value = whole.checked_mul(multiplier)?.checked_add(fraction)?;
+        Some(Amount(if is_negative { -value } else { value }))
+    }
 }
 
 impl TryFrom<u64> for Amount {
@@ -292,4 +348,32 @@ mod tests {
         assert!(e <= f);
         assert!(f >= e);
     }
+
+    #[test]
+    fn decimal_string_round_trip() {
+        let cases = [
+            (Amount(1_500_000), 6, "1.5"),
+            (Amount(1), 6, "0.000001"),
+            (Amount(-1_000_001), 6, "-1.000001"),
+            (Amount(42), 0, "42"),
+            (Amount(3_000_000), 6, "3"),
+            (Amount::MAX, 18, "9.223372036854775807"),
+        ];
+        for (amount, divisibility, expected) in cases {
+            assert_eq!(amount.to_decimal_string(divisibility), expected);
+            assert_eq!(Amount::from_decimal_str(expected, divisibility), Some(amount));
+        }
+    }
+
+    #[test]
+    fn from_decimal_str_rejects_invalid() {
+        assert_eq!(Amount::from_decimal_str(".5", 2), Some(Amount(50)));
+        assert_eq!(Amount::from_decimal_str("1.", 2), Some(Amount(100)));
+        assert_eq!(Amount::from_decimal_str("1.234", 2), None);
+        assert_eq!(Amount::from_decimal_str("1.5", 0), None);
+        assert_eq!(Amount::from_decimal_str(".", 2), None);
+        assert_eq!(Amount::from_decimal_str("1e5", 2), None);
+        assert_eq!(Amount::from_decimal_str("+1", 2), None);
+        assert_eq!(Amount::from_decimal_str("10", 18), None);
+    }
 }
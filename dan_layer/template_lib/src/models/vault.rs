@@ -201,6 +201,19 @@ impl Vault {
         result.decode::<()>().expect("deposit failed");
     }
 
+    /// Deposit all the tokens from the provided bucket into the vault, passing the `payment` bucket to the deposit
+    /// transfer hook of the resource.
+    /// It will panic if the resource does not have a deposit hook or if the hook rejects the deposit
+    pub fn deposit_with_payment(&self, bucket: Bucket, payment: Bucket) {
+        let result: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::Deposit,
+            args: invoke_args![bucket.id(), payment.id()],
+        });
+
+        result.decode::<()>().expect("deposit failed");
+    }
+
     /// Withdraw an `amount` of tokens from the vault into a new bucket.
     pub fn withdraw<T: Into<Amount>>(&self, amount: T) -> Bucket {
         let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
@@ -232,6 +245,42 @@ impl Vault {
         resp.decode().expect("failed to decode Bucket")
     }
 
+    /// Withdraw an `amount` of tokens from the vault into a new bucket, passing the `payment` bucket to the withdraw
+    /// transfer hook of the resource.
+    /// It will panic if the resource does not have a withdraw hook or if the hook rejects the withdrawal
+    pub fn withdraw_with_payment<T: Into<Amount>>(&self, amount: T, payment: Bucket) -> Bucket {
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::Withdraw,
+            args: invoke_args![VaultWithdrawArg::Fungible { amount: amount.into() }, payment.id()],
+        });
+
+        resp.decode().expect("failed to decode Bucket")
+    }
+
+    /// Withdraw multiple non-fungible tokens from the vault into a new bucket, passing the `payment` bucket to the
+    /// withdraw transfer hook of the resource.
+    /// It will panic if the vault does not contain the specified non-fungible tokens, if the resource does not have a
+    /// withdraw hook or if the hook rejects the withdrawal
+    pub fn withdraw_non_fungibles_with_payment<I: IntoIterator<Item = NonFungibleId>>(
+        &self,
+        ids: I,
+        payment: Bucket,
+    ) -> Bucket {
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::Withdraw,
+            args: invoke_args![
+                VaultWithdrawArg::NonFungible {
+                    ids: ids.into_iter().collect()
+                },
+                payment.id()
+            ],
+        });
+
+        resp.decode().expect("failed to decode Bucket")
+    }
+
     /// Withdraws an amount (specified in the `proof`) of confidential tokens from the vault into a new bucket.
     /// It will panic if the proof is invalid or there are not enough tokens in the vault
     pub fn withdraw_confidential(&self, proof: ConfidentialWithdrawProof) -> Bucket {
@@ -250,6 +299,17 @@ impl Vault {
         self.withdraw(self.balance())
     }
 
+    /// Removes the empty vault from state, earning a partial storage fee rebate. The vault must be removed from the
+    /// component state, or the component destroyed, in the same call.
+    /// It will panic if the vault is not empty
+    pub fn destroy(self) {
+        call_engine::<_, InvokeResult>(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::Destroy,
+            args: invoke_args![],
+        });
+    }
+
     /// Returns how many tokens this vault holds
     pub fn balance(&self) -> Amount {
         let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
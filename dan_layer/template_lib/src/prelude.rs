@@ -23,6 +23,8 @@
 //! The prelude contains all the commonly used types and functions that are used. To use it, add the import `use
 //! tari_template_lib::prelude::*;`
 
+#[cfg(feature = "macro")]
+pub use tari_template_macros::event;
 #[cfg(all(feature = "macro", target_arch = "wasm32"))]
 pub use tari_template_macros::template;
 #[cfg(all(feature = "macro", not(target_arch = "wasm32")))]
@@ -38,7 +40,7 @@ pub use crate::{
     crypto::{PedersonCommitmentBytes, RistrettoPublicKeyBytes},
     debug,
     error,
-    events::emit_event,
+    events::{emit, emit_event, TemplateEvent},
     info,
     invoke_args,
     log,
@@ -62,7 +64,7 @@ pub use crate::{
         VaultId,
     },
     rand,
-    resource::{ResourceBuilder, ResourceManager, ResourceType},
+    resource::{ResourceBuilder, ResourceManager, ResourceType, TransferHookContext},
     rule,
     template::{BuiltinTemplate, TemplateManager},
     warn,
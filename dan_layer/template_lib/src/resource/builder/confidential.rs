@@ -8,7 +8,7 @@ use crate::{
     crypto::RistrettoPublicKeyBytes,
     models::{Bucket, ComponentAddress, Metadata, ResourceAddress},
     prelude::ConfidentialOutputStatement,
-    resource::{ResourceManager, ResourceType},
+    resource::{ResourceManager, ResourceType, TransferHooks},
 };
 
 /// Utility for building confidential resources inside templates
@@ -19,6 +19,7 @@ pub struct ConfidentialResourceBuilder {
     token_symbol: Option<String>,
     owner_rule: OwnerRule,
     authorize_hook: Option<AuthHook>,
+    divisibility: u8,
 }
 
 impl ConfidentialResourceBuilder {
@@ -31,6 +32,7 @@ impl ConfidentialResourceBuilder {
             token_symbol: None,
             owner_rule: OwnerRule::default(),
             authorize_hook: None,
+            divisibility: 0,
         }
     }
 
@@ -104,6 +106,14 @@ impl ConfidentialResourceBuilder {
         self
     }
 
+    /// Sets up the number of decimal places used to display amounts of the resource. E.g. a divisibility of 6 displays
+    /// an amount of 1_500_000 as 1.5. This cannot be changed after the resource is created and must be at most
+    /// [MAX_DIVISIBILITY](crate::resource::MAX_DIVISIBILITY).
+    pub fn with_divisibility(mut self, divisibility: u8) -> Self {
+        self.divisibility = divisibility;
+        self
+    }
+
     /// Sets up the image URL of the resource
     pub fn with_image_url(self, url: String) -> Self {
         self.add_metadata(IMAGE_URL, url)
@@ -168,6 +178,8 @@ impl ConfidentialResourceBuilder {
             mint_arg,
             self.view_key,
             self.authorize_hook,
+            self.divisibility,
+            TransferHooks::default(),
         )
     }
 }
@@ -6,7 +6,7 @@ use crate::{
     args::MintArg,
     auth::{AccessRule, AuthHook, OwnerRule, ResourceAccessRules},
     models::{Amount, Bucket, ComponentAddress, Metadata, ResourceAddress},
-    resource::{ResourceManager, ResourceType},
+    resource::{ResourceManager, ResourceType, TransferHook, TransferHooks},
 };
 
 /// Utility for building fungible resources inside templates
@@ -16,6 +16,8 @@ pub struct FungibleResourceBuilder {
     token_symbol: Option<String>,
     metadata: Metadata,
     authorize_hook: Option<AuthHook>,
+    transfer_hooks: TransferHooks,
+    divisibility: u8,
 }
 
 impl FungibleResourceBuilder {
@@ -27,6 +29,8 @@ impl FungibleResourceBuilder {
             token_symbol: None,
             metadata: Metadata::new(),
             authorize_hook: None,
+            transfer_hooks: TransferHooks::default(),
+            divisibility: 0,
         }
     }
 
@@ -92,6 +96,14 @@ impl FungibleResourceBuilder {
         self
     }
 
+    /// Sets up the number of decimal places used to display amounts of the resource. E.g. a divisibility of 6 displays
+    /// an amount of 1_500_000 as 1.5. This cannot be changed after the resource is created and must be at most
+    /// [MAX_DIVISIBILITY](crate::resource::MAX_DIVISIBILITY).
+    pub fn with_divisibility(mut self, divisibility: u8) -> Self {
+        self.divisibility = divisibility;
+        self
+    }
+
     /// Sets up the image URL of the resource
     pub fn with_image_url(self, url: String) -> Self {
         self.add_metadata(IMAGE_URL, url)
@@ -127,6 +139,37 @@ impl FungibleResourceBuilder {
         self
     }
 
+    /// Specify a hook method that will be called whenever tokens of the resource are deposited into a vault.
+    /// The signature of the method must be `fn(&self, context: TransferHookContext, payment: Option<Bucket>)`.
+    /// The method should panic to deny the deposit. A depositor may pass a payment bucket (e.g. a royalty) to the
+    /// hook using `Vault::deposit_with_payment`.
+    /// The resource will fail to build if the component's template does not have a method with the specified signature.
+    /// Hooks are only run when the resource is transferred by a component other than the hook component.
+    ///
+    /// ## Examples
+    ///
+    /// ```ignore
+    /// use tari_template_lib::{caller_context::CallerContext, prelude::ResourceBuilder};
+    /// ResourceBuilder::fungible()
+    ///     .with_deposit_hook(CallerContext::current_component_address(), "on_deposit")
+    ///     .build();
+    /// ```
+    pub fn with_deposit_hook<T: Into<String>>(mut self, address: ComponentAddress, method: T) -> Self {
+        self.transfer_hooks.on_deposit = Some(TransferHook::new(address, method.into()));
+        self
+    }
+
+    /// Specify a hook method that will be called whenever tokens of the resource are withdrawn from a vault.
+    /// The signature of the method must be `fn(&self, context: TransferHookContext, payment: Option<Bucket>)`.
+    /// The method should panic to deny the withdrawal. This can be used to require a payment (e.g. a royalty) for
+    /// every transfer, which the withdrawer passes in using `Vault::withdraw_with_payment`.
+    /// The resource will fail to build if the component's template does not have a method with the specified signature.
+    /// Hooks are only run when the resource is transferred by a component other than the hook component.
+    pub fn with_withdraw_hook<T: Into<String>>(mut self, address: ComponentAddress, method: T) -> Self {
+        self.transfer_hooks.on_withdraw = Some(TransferHook::new(address, method.into()));
+        self
+    }
+
     /// Build the resource, returning the address
     pub fn build(self) -> ResourceAddress {
         let (address, _) = self.build_internal(None);
@@ -156,6 +199,8 @@ impl FungibleResourceBuilder {
             mint_arg,
             None,
             self.authorize_hook,
+            self.divisibility,
+            self.transfer_hooks,
         )
     }
 }
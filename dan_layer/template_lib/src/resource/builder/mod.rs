@@ -34,6 +34,8 @@ use crate::resource::builder::{
 /// user-friendly identification of the underlying token
 pub const TOKEN_SYMBOL: &str = "SYMBOL";
 pub const IMAGE_URL: &str = "IMAGE_URL";
+/// The maximum number of decimal places a resource can have. An `Amount` of 10^18 still fits in an i64.
+pub const MAX_DIVISIBILITY: u8 = 18;
 
 /// Utility for building resources inside templates
 pub struct ResourceBuilder;
@@ -9,7 +9,7 @@ use crate::{
     args::MintArg,
     auth::{AccessRule, AuthHook, OwnerRule, ResourceAccessRules},
     models::{Bucket, ComponentAddress, Metadata, NonFungibleId, ResourceAddress},
-    resource::{ResourceManager, ResourceType},
+    resource::{ResourceManager, ResourceType, TransferHook, TransferHooks},
 };
 
 /// Utility for building non-fungible resources inside templates
@@ -19,6 +19,7 @@ pub struct NonFungibleResourceBuilder {
     access_rules: ResourceAccessRules,
     token_symbol: Option<String>,
     authorize_hook: Option<AuthHook>,
+    transfer_hooks: TransferHooks,
 }
 
 impl NonFungibleResourceBuilder {
@@ -30,6 +31,7 @@ impl NonFungibleResourceBuilder {
             access_rules: ResourceAccessRules::new(),
             token_symbol: None,
             authorize_hook: None,
+            transfer_hooks: TransferHooks::default(),
         }
     }
 
@@ -136,6 +138,37 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    /// Specify a hook method that will be called whenever tokens of the resource are deposited into a vault.
+    /// The signature of the method must be `fn(&self, context: TransferHookContext, payment: Option<Bucket>)`.
+    /// The method should panic to deny the deposit. A depositor may pass a payment bucket (e.g. a royalty) to the
+    /// hook using `Vault::deposit_with_payment`.
+    /// The resource will fail to build if the component's template does not have a method with the specified signature.
+    /// Hooks are only run when the resource is transferred by a component other than the hook component.
+    ///
+    /// ## Examples
+    ///
+    /// ```ignore
+    /// use tari_template_lib::{caller_context::CallerContext, prelude::ResourceBuilder};
+    /// ResourceBuilder::non_fungible()
+    ///     .with_deposit_hook(CallerContext::current_component_address(), "on_deposit")
+    ///     .build();
+    /// ```
+    pub fn with_deposit_hook<T: Into<String>>(mut self, address: ComponentAddress, method: T) -> Self {
+        self.transfer_hooks.on_deposit = Some(TransferHook::new(address, method.into()));
+        self
+    }
+
+    /// Specify a hook method that will be called whenever tokens of the resource are withdrawn from a vault.
+    /// The signature of the method must be `fn(&self, context: TransferHookContext, payment: Option<Bucket>)`.
+    /// The method should panic to deny the withdrawal. This can be used to require a payment (e.g. a royalty) for
+    /// every transfer, which the withdrawer passes in using `Vault::withdraw_with_payment`.
+    /// The resource will fail to build if the component's template does not have a method with the specified signature.
+    /// Hooks are only run when the resource is transferred by a component other than the hook component.
+    pub fn with_withdraw_hook<T: Into<String>>(mut self, address: ComponentAddress, method: T) -> Self {
+        self.transfer_hooks.on_withdraw = Some(TransferHook::new(address, method.into()));
+        self
+    }
+
     /// Build the resource, returning the address
     pub fn build(self) -> ResourceAddress {
         let (address, _) = self.build_internal(None);
@@ -192,6 +225,8 @@ impl NonFungibleResourceBuilder {
             mint_arg,
             None,
             self.authorize_hook,
+            0,
+            self.transfer_hooks,
         )
     }
 }
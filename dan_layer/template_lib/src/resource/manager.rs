@@ -34,6 +34,7 @@ use crate::{
         MintResourceArg,
         RecallResourceArg,
         ResourceAction,
+        ResourceDestroyBurntNonFungiblesArg,
         ResourceDiscriminator,
         ResourceGetNonFungibleArg,
         ResourceInvokeArg,
@@ -53,6 +54,7 @@ use crate::{
         VaultId,
     },
     prelude::{AuthHook, ResourceType},
+    resource::TransferHooks,
 };
 
 /// Utility for managing resources inside templates
@@ -104,6 +106,10 @@ impl ResourceManager {
     /// * `access_rules` - Rules that will govern access to the resource
     /// * `metadata` - Collection of information used to describe the resource
     /// * `mint_arg` - Specification of the initial tokens that will be minted on resource creation
+    /// * `view_key` - The public key used to view confidential balances (confidential resources only)
+    /// * `authorize_hook` - A component method that authorizes actions on the resource
+    /// * `divisibility` - The number of decimal places used to display amounts. Cannot be changed after creation.
+    /// * `transfer_hooks` - Component methods that are called on vault deposits and withdrawals of the resource
     pub fn create(
         &self,
         resource_type: ResourceType,
@@ -113,6 +119,8 @@ impl ResourceManager {
         mint_arg: Option<MintArg>,
         view_key: Option<RistrettoPublicKeyBytes>,
         authorize_hook: Option<AuthHook>,
+        divisibility: u8,
+        transfer_hooks: TransferHooks,
     ) -> (ResourceAddress, Option<Bucket>) {
         let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
             resource_ref: ResourceRef::Resource,
@@ -125,6 +133,8 @@ impl ResourceManager {
                 mint_arg,
                 view_key,
                 authorize_hook,
+                divisibility,
+                transfer_hooks,
             }],
         });
 
@@ -331,6 +341,18 @@ impl ResourceManager {
         resp.decode().expect("[total_supply] Failed to decode Amount")
     }
 
+    /// Returns the number of decimal places used to display amounts of the resource being managed. This is always 0
+    /// for non-fungible resources.
+    pub fn divisibility(&self) -> u8 {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::GetDivisibility,
+            args: invoke_args![],
+        });
+
+        resp.decode().expect("[divisibility] Failed to decode u8")
+    }
+
     /// Returns the non-fungible token identified by `id`
     /// It will panic if the resource has no tokens identified with `id`
     pub fn get_non_fungible(&self, id: &NonFungibleId) -> NonFungible {
@@ -370,6 +392,21 @@ impl ResourceManager {
         resp.decode().expect("[set_access_rules] Failed")
     }
 
+    /// Removes burnt non-fungible tokens from state, earning a partial storage fee rebate. The resource keeps a record
+    /// of the removed token IDs, so they can never be minted again. Only the resource owner may perform this action.
+    /// It will panic if any of the tokens do not exist or have not been burnt
+    pub fn destroy_burnt_non_fungibles<I: IntoIterator<Item = NonFungibleId>>(&self, ids: I) {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::DestroyBurntNonFungibles,
+            args: invoke_args![ResourceDestroyBurntNonFungiblesArg {
+                ids: ids.into_iter().collect()
+            }],
+        });
+
+        resp.decode().expect("[destroy_burnt_non_fungibles] Failed")
+    }
+
     fn recall_internal(&self, arg: RecallResourceArg) -> Bucket {
         let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
             resource_ref: self.expect_resource_address(),
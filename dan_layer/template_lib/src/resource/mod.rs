@@ -29,6 +29,8 @@ use std::fmt::Display;
 pub use builder::*;
 mod manager;
 pub use manager::*;
+mod transfer_hook;
+pub use transfer_hook::*;
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
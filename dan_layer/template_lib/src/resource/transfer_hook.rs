@@ -0,0 +1,99 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_template_abi::rust::fmt;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::models::{Amount, ComponentAddress, NonFungibleId, ResourceAddress, VaultId};
+
+/// A component method that is called by the engine whenever tokens of a resource are deposited into or withdrawn from
+/// a vault.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransferHook {
+    pub component_address: ComponentAddress,
+    pub method: String,
+}
+
+impl TransferHook {
+    pub fn new(component_address: ComponentAddress, method: String) -> Self {
+        Self {
+            component_address,
+            method,
+        }
+    }
+}
+
+impl fmt::Display for TransferHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.component_address, self.method)
+    }
+}
+
+/// The transfer hooks declared for a resource. Hooks cannot be changed after the resource is created.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransferHooks {
+    pub on_deposit: Option<TransferHook>,
+    pub on_withdraw: Option<TransferHook>,
+}
+
+impl TransferHooks {
+    pub fn is_empty(&self) -> bool {
+        self.on_deposit.is_none() && self.on_withdraw.is_none()
+    }
+
+    pub fn get(&self, action: TransferAction) -> Option<&TransferHook> {
+        match action {
+            TransferAction::Deposit => self.on_deposit.as_ref(),
+            TransferAction::Withdraw => self.on_withdraw.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum TransferAction {
+    Deposit,
+    Withdraw,
+}
+
+impl fmt::Display for TransferAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deposit => write!(f, "deposit"),
+            Self::Withdraw => write!(f, "withdraw"),
+        }
+    }
+}
+
+/// Describes the transfer that a transfer hook is being called for.
+///
+/// The signature of a transfer hook method must be `fn(&self, context: TransferHookContext, payment: Option<Bucket>)`
+/// (`&mut self` is also allowed). The hook should panic to deny the transfer. Any payment bucket that is passed to the
+/// hook must be deposited or otherwise consumed by the hook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransferHookContext {
+    pub action: TransferAction,
+    pub resource_address: ResourceAddress,
+    pub vault_id: VaultId,
+    /// The amount of tokens being transferred. For non-fungible resources, this is the number of tokens.
+    pub amount: Amount,
+    /// The non-fungible tokens being transferred. Empty for fungible resources.
+    pub non_fungible_ids: Vec<NonFungibleId>,
+    /// The component that initiated the transfer, if any
+    pub caller_component: Option<ComponentAddress>,
+}
+
+impl TransferHookContext {
+    pub fn is_deposit(&self) -> bool {
+        matches!(self.action, TransferAction::Deposit)
+    }
+
+    pub fn is_withdraw(&self) -> bool {
+        matches!(self.action, TransferAction::Withdraw)
+    }
+}
@@ -0,0 +1,68 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse2, Error, Fields, ItemStruct, Result};
+
+pub fn generate_event(input: TokenStream) -> Result<TokenStream> {
+    let item = parse2::<ItemStruct>(input)?;
+    if !matches!(item.fields, Fields::Named(_)) {
+        return Err(Error::new(item.ident.span(), "an event must have named fields"));
+    }
+
+    let name = &item.ident;
+    let topic = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    Ok(quote! {
+        #item
+
+        impl #impl_generics ::tari_template_lib::events::TemplateEvent for #name #ty_generics #where_clause {
+            const TOPIC: &'static str = #topic;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use super::generate_event;
+
+    #[test]
+    fn it_implements_template_event() {
+        let input = TokenStream::from_str(indoc! {"
+            struct Swap {
+                amount: Amount,
+            }
+        "})
+        .unwrap();
+
+        let output = generate_event(input).unwrap();
+
+        assert_eq!(
+            output.to_string(),
+            quote! {
+                struct Swap {
+                    amount: Amount,
+                }
+
+                impl ::tari_template_lib::events::TemplateEvent for Swap {
+                    const TOPIC: &'static str = "Swap";
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn it_rejects_tuple_structs() {
+        let input = TokenStream::from_str("struct Swap(u64);").unwrap();
+        assert!(generate_event(input).is_err());
+    }
+}
@@ -20,6 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod event;
 mod template;
 
 use proc_macro::TokenStream;
@@ -40,3 +41,13 @@ pub fn template_non_wasm(_attr: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+/// Marks a struct declared within a template module as an event with a structured payload. The event is described in
+/// the template ABI and may be emitted using `tari_template_lib::events::emit`. The topic of the event is the name of
+/// the struct.
+#[proc_macro_attribute]
+pub fn event(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    event::generate_event(proc_macro2::TokenStream::from(item))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -25,6 +25,7 @@ use quote::{format_ident, quote};
 use syn::{AngleBracketedGenericArguments, GenericArgument, PathArguments, PathSegment, Result, Type, TypeTuple};
 use tari_template_abi::{
     ArgDef,
+    EventDef,
     FunctionDef,
     TemplateDef,
     TemplateDefV1,
@@ -62,6 +63,24 @@ pub fn generate_abi(ast: &TemplateAst) -> Result<TokenStream> {
                 })
             })
             .collect::<Result<_>>()?,
+        events: ast
+            .get_events()
+            .map(|event| {
+                let event = event?;
+                Ok::<_, syn::Error>(EventDef {
+                    topic: event.name.clone(),
+                    name: event.name,
+                    fields: event
+                        .fields
+                        .iter()
+                        .map(|(name, ty)| ArgDef {
+                            name: name.clone(),
+                            arg_type: convert_to_arg_type(&template_name_as_str, ty),
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Result<_>>()?,
     });
 
     let template_def_data = tari_bor::encode_with_len(&template_def);
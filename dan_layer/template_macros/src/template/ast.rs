@@ -27,12 +27,14 @@ use syn::{
     punctuated::Punctuated,
     token::Comma,
     Error,
+    Fields,
     FnArg,
     Ident,
     ImplItem,
     ImplItemMethod,
     Item,
     ItemMod,
+    ItemStruct,
     ItemUse,
     Result,
     ReturnType,
@@ -70,10 +72,10 @@ impl Parse for TemplateAst {
                     item.attrs
                         .push(syn::parse_quote!(#[derive(Debug, serde::Serialize, serde::Deserialize)]));
                     item.attrs.push(syn::parse_quote!(#[serde(crate = "self::serde")]));
-                    // Use the first struct name as the template name
+                    // Use the first struct name that is not an event as the template name
                     // TODO: remove this assumption in favor of "marking" the struct as a template struct
                     // #[template(Component)]
-                    if template_name.is_none() {
+                    if template_name.is_none() && !Self::is_event_struct(item) {
                         template_name = Some(item.ident.clone());
                     }
                 },
@@ -117,6 +119,42 @@ impl Parse for TemplateAst {
 }
 
 impl TemplateAst {
+    pub fn get_events(&self) -> impl Iterator<Item = Result<EventAst>> + '_ {
+        self.module_content
+            .iter()
+            .filter_map(|i| match i {
+                Item::Struct(item) if Self::is_event_struct(item) => Some(item),
+                _ => None,
+            })
+            .map(|item| {
+                let Fields::Named(fields) = &item.fields else {
+                    return Err(Error::new(item.ident.span(), "an event must have named fields"));
+                };
+                Ok(EventAst {
+                    name: item.ident.to_string(),
+                    fields: fields
+                        .named
+                        .iter()
+                        .map(|field| {
+                            let name = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+                            (name, Self::get_type_ast(None, &field.ty))
+                        })
+                        .collect(),
+                })
+            })
+    }
+
+    /// Returns true if the struct has an `#[event]` attribute, which may be path-qualified e.g.
+    /// `#[tari_template_lib::prelude::event]`
+    fn is_event_struct(item: &ItemStruct) -> bool {
+        item.attrs.iter().any(|attr| {
+            attr.path
+                .segments
+                .last()
+                .map_or(false, |segment| segment.ident == "event")
+        })
+    }
+
     pub fn get_functions(&self) -> impl Iterator<Item = FunctionAst> + '_ {
         self.module_content
             .iter()
@@ -222,6 +260,11 @@ impl TemplateAst {
     }
 }
 
+pub struct EventAst {
+    pub name: String,
+    pub fields: Vec<(String, TypeAst)>,
+}
+
 pub struct FunctionAst {
     pub name: String,
     pub input_types: Vec<TypeAst>,
@@ -38,12 +38,14 @@ use tari_dan_common_types::{
 };
 use tari_dan_engine::{
     flow::FlowFactory,
-    function_definitions::FlowFunctionDefinition,
-    template::{LoadedTemplate, TemplateModuleLoader},
+    template::{LoadedManifestTemplate, LoadedTemplate, TemplateModuleLoader},
     wasm::WasmModule,
 };
 use tari_dan_p2p::proto::rpc::TemplateType;
-use tari_dan_storage::global::{DbTemplate, DbTemplateType, DbTemplateUpdate, GlobalDb, TemplateStatus};
+use tari_dan_storage::{
+    global::{DbTemplate, DbTemplateQuery, DbTemplateType, DbTemplateUpdate, GlobalDb, TemplateStatus},
+    Ordering,
+};
 use tari_dan_storage_sqlite::global::SqliteGlobalDbAdapter;
 use tari_engine_types::{calculate_template_binary_hash, hashing::hash_template_code};
 use tari_template_builtin::{
@@ -243,14 +245,31 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         }
     }
 
-    pub fn fetch_template_metadata(&self, limit: usize) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
+    /// Returns the metadata of active and builtin templates that match the query, ordered by template address
+    pub fn fetch_template_metadata(
+        &self,
+        query: DbTemplateQuery,
+    ) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
         let mut tx = self.global_db.create_transaction()?;
         // TODO: we should be able to fetch just the metadata and not the compiled code
-        let templates = self.global_db.templates(&mut tx).get_templates(limit)?;
+        let templates = self.global_db.templates(&mut tx).get_templates(&query)?;
         let mut templates: Vec<TemplateMetadata> = templates.into_iter().map(Into::into).collect();
-        let mut builtin_metadata: Vec<TemplateMetadata> =
-            self.builtin_templates.values().map(|t| t.metadata.to_owned()).collect();
-        templates.append(&mut builtin_metadata);
+        let builtin_metadata = self
+            .builtin_templates
+            .values()
+            .map(|t| &t.metadata)
+            .filter(|m| query.matches(&m.address, &m.name, &m.author_public_key))
+            .cloned();
+        templates.extend(builtin_metadata);
+
+        // Builtin templates are interleaved with the database templates so that the cursor applies to both
+        match query.ordering {
+            Some(Ordering::Descending) => templates.sort_by(|a, b| b.address.cmp(&a.address)),
+            _ => templates.sort_by(|a, b| a.address.cmp(&b.address)),
+        }
+        if query.limit > 0 {
+            templates.truncate(query.limit);
+        }
 
         Ok(templates)
     }
@@ -293,6 +312,7 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
                 status: TemplateStatus::Pending,
                 epoch,
                 added_at: Default::default(),
+                base_layer_block_height: None,
             };
             templates_db.insert_template(template)?
         }
@@ -310,6 +330,7 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         template_name: Option<String>,
         template_status: Option<TemplateStatus>,
         epoch: Epoch,
+        base_layer_block_height: Option<u64>,
     ) -> Result<(), TemplateManagerError> {
         let mut code = None;
         let mut template_type = DbTemplateType::Wasm;
@@ -324,11 +345,15 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
                 template_name = loaded_template.template_name().to_string();
             },
             TemplateExecutable::Manifest(curr_manifest) => {
+                let loaded_template = LoadedManifestTemplate::try_from_source(&curr_manifest)?;
+                template_name = loaded_template.template_name().to_string();
                 template_hash = hash_template_code(curr_manifest.as_bytes()).into_array().into();
                 code = Some(curr_manifest.into_bytes());
                 template_type = DbTemplateType::Manifest;
             },
             TemplateExecutable::Flow(curr_flow_json) => {
+                let factory = FlowFactory::try_from_json(curr_flow_json.as_bytes())?;
+                template_name = factory.name().to_string();
                 template_hash = hash_template_code(curr_flow_json.as_bytes()).into_array().into();
                 code = Some(curr_flow_json.into_bytes());
                 template_type = DbTemplateType::Flow;
@@ -351,6 +376,7 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
             template_type,
             url: template_url,
             epoch,
+            base_layer_block_height,
         };
 
         let mut tx = self.global_db.create_transaction()?;
@@ -417,10 +443,11 @@ impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for Templa
                 let module = WasmModule::from_code(wasm);
                 module.load_template()?
             },
-            TemplateExecutable::Manifest(_) => return Err(TemplateManagerError::UnsupportedTemplateType),
+            TemplateExecutable::Manifest(manifest) => {
+                LoadedTemplate::Manifest(LoadedManifestTemplate::try_from_source(&manifest)?)
+            },
             TemplateExecutable::Flow(flow_json) => {
-                let definition: FlowFunctionDefinition = serde_json::from_str(&flow_json)?;
-                let factory = FlowFactory::try_create::<Self>(definition)?;
+                let factory = FlowFactory::try_from_json(flow_json.as_bytes())?;
                 LoadedTemplate::Flow(factory)
             },
             TemplateExecutable::DownloadableWasm(_, _) => {
@@ -23,7 +23,7 @@
 use log::*;
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{services::template_provider::TemplateProvider, Epoch, NodeAddressable, ToPeerId};
-use tari_dan_engine::{function_definitions::FlowFunctionDefinition, wasm::WasmModule};
+use tari_dan_engine::{flow::FlowFactory, template::LoadedManifestTemplate, wasm::WasmModule};
 use tari_dan_p2p::proto::rpc::TemplateType;
 use tari_dan_storage::global::{DbTemplateType, DbTemplateUpdate, TemplateStatus};
 use tari_engine_types::calculate_template_binary_hash;
@@ -209,18 +209,26 @@ impl<TAddr: NodeAddressable + ToPeerId + 'static> TemplateManagerService<TAddr>
                 template,
                 template_name,
                 epoch,
+                base_layer_block_height,
                 reply,
             } => {
                 handle(
                     reply,
-                    self.handle_add_template(author_public_key, template_address, template, template_name, epoch)
-                        .await,
+                    self.handle_add_template(
+                        author_public_key,
+                        template_address,
+                        template,
+                        template_name,
+                        epoch,
+                        base_layer_block_height,
+                    )
+                    .await,
                 );
             },
             GetTemplate { address, reply } => {
                 handle(reply, self.manager.fetch_template(&address));
             },
-            GetTemplates { limit, reply } => handle(reply, self.manager.fetch_template_metadata(limit)),
+            GetTemplates { query, reply } => handle(reply, self.manager.fetch_template_metadata(query)),
             LoadTemplateAbi { address, reply } => handle(reply, self.handle_load_template_abi(address)),
             TemplateExists { address, status, reply } => handle(reply, self.handle_template_exists(&address, status)),
             GetTemplatesByAddresses { addresses, reply } => handle(
@@ -300,14 +308,36 @@ impl<TAddr: NodeAddressable + ToPeerId + 'static> TemplateManagerService<TAddr>
                         ..Default::default()
                     },
                     DbTemplateType::Flow => {
-                        // make sure it deserializes correctly
+                        // make sure it is a valid flow
+                        let mut status = TemplateStatus::Invalid;
+                        match FlowFactory::try_from_json(&bytes) {
+                            Ok(_) => status = template_status,
+                            Err(e) => {
+                                warn!(
+                                    target: LOG_TARGET,
+                                    "⚠️ Template {} is not a valid flow: {}", download.template_address, e
+                                );
+                            },
+                        };
+
+                        DbTemplateUpdate {
+                            code: Some(bytes.to_vec()),
+                            status: Some(status),
+                            ..Default::default()
+                        }
+                    },
+                    DbTemplateType::Manifest => {
+                        // make sure it is a valid manifest template
                         let mut status = TemplateStatus::Invalid;
-                        match serde_json::from_slice::<FlowFunctionDefinition>(&bytes) {
+                        match std::str::from_utf8(&bytes)
+                            .map_err(|e| e.to_string())
+                            .and_then(|s| LoadedManifestTemplate::try_from_source(s).map_err(|e| e.to_string()))
+                        {
                             Ok(_) => status = template_status,
                             Err(e) => {
                                 warn!(
                                     target: LOG_TARGET,
-                                    "⚠️ Template {} is not valid json: {}", download.template_address, e
+                                    "⚠️ Template {} is not a valid manifest: {}", download.template_address, e
                                 );
                             },
                         };
@@ -318,7 +348,6 @@ impl<TAddr: NodeAddressable + ToPeerId + 'static> TemplateManagerService<TAddr>
                             ..Default::default()
                         }
                     },
-                    DbTemplateType::Manifest => todo!(),
                 };
                 self.manager.update_template(download.template_address, update)?;
             },
@@ -358,6 +387,7 @@ impl<TAddr: NodeAddressable + ToPeerId + 'static> TemplateManagerService<TAddr>
         template: TemplateExecutable,
         template_name: Option<String>,
         epoch: Epoch,
+        base_layer_block_height: Option<u64>,
     ) -> Result<(), TemplateManagerError> {
         let template_status = if matches!(template, TemplateExecutable::DownloadableWasm(_, _)) {
             TemplateStatus::New
@@ -371,6 +401,7 @@ impl<TAddr: NodeAddressable + ToPeerId + 'static> TemplateManagerService<TAddr>
             template_name,
             Some(template_status),
             epoch,
+            base_layer_block_height,
         )?;
 
         // TODO: remove when we remove support for base layer template registration
@@ -22,7 +22,7 @@
 
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::Epoch;
-use tari_dan_storage::global::TemplateStatus;
+use tari_dan_storage::global::{DbTemplateQuery, TemplateStatus};
 use tari_template_lib::models::TemplateAddress;
 use tari_validator_node_client::types::TemplateAbi;
 use tokio::sync::{mpsc, oneshot};
@@ -65,10 +65,10 @@ impl TemplateManagerHandle {
         rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
     }
 
-    pub async fn get_templates(&self, limit: usize) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
+    pub async fn get_templates(&self, query: DbTemplateQuery) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
         let (tx, rx) = oneshot::channel();
         self.request_tx
-            .send(TemplateManagerRequest::GetTemplates { limit, reply: tx })
+            .send(TemplateManagerRequest::GetTemplates { query, reply: tx })
             .await
             .map_err(|_| TemplateManagerError::ChannelClosed)?;
         rx.await.map_err(|_| TemplateManagerError::ChannelClosed)?
@@ -112,6 +112,7 @@ impl TemplateManagerHandle {
         template: TemplateExecutable,
         template_name: Option<String>,
         epoch: Epoch,
+        base_layer_block_height: Option<u64>,
     ) -> Result<(), TemplateManagerError> {
         let (tx, rx) = oneshot::channel();
         self.request_tx
@@ -121,6 +122,7 @@ impl TemplateManagerHandle {
                 template,
                 template_name,
                 epoch,
+                base_layer_block_height,
                 reply: tx,
             })
             .await
@@ -24,7 +24,7 @@ use reqwest::Url;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_dan_common_types::Epoch;
 use tari_dan_storage::{
-    global::{DbTemplate, DbTemplateType, TemplateStatus},
+    global::{DbTemplate, DbTemplateQuery, DbTemplateType, TemplateStatus},
     StorageError,
 };
 use tari_engine_types::published_template::PublishedTemplateAddress;
@@ -164,6 +164,8 @@ pub enum TemplateManagerRequest {
         template: TemplateExecutable,
         template_name: Option<String>,
         epoch: Epoch,
+        /// The height of the base layer block that registered the template, if registered on the base layer
+        base_layer_block_height: Option<u64>,
         reply: Reply<()>,
     },
     GetTemplate {
@@ -171,7 +173,7 @@ pub enum TemplateManagerRequest {
         reply: Reply<Template>,
     },
     GetTemplates {
-        limit: usize,
+        query: DbTemplateQuery,
         reply: Reply<Vec<TemplateMetadata>>,
     },
     GetTemplatesByAddresses {
@@ -3,6 +3,7 @@
 
 use std::{
     collections::HashMap,
+    fs,
     path::Path,
     sync::{Arc, Mutex},
 };
@@ -10,7 +11,8 @@ use std::{
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 use tari_dan_engine::{
     abi::TemplateDef,
-    template::{LoadedTemplate, TemplateLoaderError, TemplateModuleLoader},
+    flow::FlowFactory,
+    template::{LoadedManifestTemplate, LoadedTemplate, TemplateLoaderError, TemplateModuleLoader},
     wasm::{compile::compile_template, WasmModule},
 };
 use tari_engine_types::hashing::hash_template_code;
@@ -74,6 +76,25 @@ impl PackageBuilder {
         self
     }
 
+    /// Adds a flow template from a JSON flow definition file. The address is the hash of the JSON, as it would be for a
+    /// published flow.
+    pub fn add_flow_template<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let json = fs::read(path).unwrap();
+        let template_addr = hash_template_code(&json);
+        let flow = FlowFactory::try_from_json(&json).unwrap();
+        self.add_loaded_template(template_addr, LoadedTemplate::Flow(flow));
+        self
+    }
+
+    /// Adds a manifest template from a manifest source file
+    pub fn add_manifest_template<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let source = fs::read_to_string(path).unwrap();
+        let template_addr = hash_template_code(source.as_bytes());
+        let manifest = LoadedManifestTemplate::try_from_source(&source).unwrap();
+        self.add_loaded_template(template_addr, LoadedTemplate::Manifest(manifest));
+        self
+    }
+
     pub fn add_loaded_template(&mut self, address: TemplateAddress, template: LoadedTemplate) -> &mut Self {
         self.templates.insert(address, template);
         self
@@ -83,7 +83,14 @@ impl TemplateTest {
 
         // Add all of the templates specified in the argument
         for path in template_paths {
-            builder.add_template(path);
+            let extension = path.as_ref().extension();
+            if extension.is_some_and(|ext| ext == "json") {
+                builder.add_flow_template(path);
+            } else if extension.is_some_and(|ext| ext == "manifest") {
+                builder.add_manifest_template(path);
+            } else {
+                builder.add_template(path);
+            }
         }
 
         let package = builder.build();
@@ -129,6 +136,9 @@ impl TemplateTest {
                 per_byte_storage_cost: 1,
                 per_event_cost: 1,
                 per_log_cost: 1,
+                per_signature_verification_cost: 1,
+                per_hash_cost: 1,
+                per_commitment_operation_cost: 1,
             },
             key_seed: 1,
         }
@@ -260,7 +270,7 @@ impl TemplateTest {
         let addr = self.name_to_template.get(module_name).unwrap();
         match self.package.get_template_by_address(addr).unwrap() {
             LoadedTemplate::Wasm(wasm) => wasm,
-            LoadedTemplate::Flow(_) => {
+            LoadedTemplate::Flow(_) | LoadedTemplate::Manifest(_) => {
                 panic!("Not supported")
             },
         }
@@ -289,6 +299,7 @@ impl TemplateTest {
                     owner_rule: None,
                     access_rules: None,
                     workspace_bucket,
+                    policy: None,
                 }],
                 proofs,
             )
@@ -510,6 +521,7 @@ impl TemplateTest {
             eprintln!("Paid: {}", fee.total_fees_paid());
             eprintln!("Refund: {}", fee.total_refunded());
             eprintln!("Unpaid: {}", fee.unpaid_debt());
+            eprintln!("Storage rebate: {}", fee.storage_rebate());
             for (source, amount) in fee.cost_breakdown.iter() {
                 eprintln!("- {:?} {}", source, amount);
             }
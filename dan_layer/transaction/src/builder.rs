@@ -12,7 +12,7 @@ use tari_engine_types::{
 use tari_template_lib::{
     args,
     args::Arg,
-    auth::OwnerRule,
+    auth::{AccountPolicy, OwnerRule},
     models::{Amount, ComponentAddress, ConfidentialWithdrawProof, ResourceAddress},
     prelude::AccessRules,
 };
@@ -88,6 +88,7 @@ impl TransactionBuilder {
             owner_rule: None,
             access_rules: None,
             workspace_bucket: None,
+            policy: None,
         })
     }
 
@@ -97,6 +98,7 @@ impl TransactionBuilder {
             owner_rule: None,
             access_rules: None,
             workspace_bucket: Some(workspace_bucket.into()),
+            policy: None,
         })
     }
 
@@ -112,6 +114,18 @@ impl TransactionBuilder {
             owner_rule,
             access_rules,
             workspace_bucket: workspace_bucket.map(|b| b.into()),
+            policy: None,
+        })
+    }
+
+    /// Creates an account that enforces the given policy itself, instead of being owned by the owner public key token
+    pub fn create_account_with_policy(self, owner_public_key: PublicKey, policy: AccountPolicy) -> Self {
+        self.add_instruction(Instruction::CreateAccount {
+            public_key_address: owner_public_key,
+            owner_rule: None,
+            access_rules: None,
+            workspace_bucket: None,
+            policy: Some(policy),
         })
     }
 
@@ -220,6 +234,11 @@ impl TransactionBuilder {
         self
     }
 
+    /// Add an input that the transaction only reads from. The transaction is rejected if it writes to the input.
+    pub fn add_read_only_input<I: Into<SubstateRequirement>>(self, input_object: I) -> Self {
+        self.add_input(input_object.into().read_only())
+    }
+
     pub fn with_inputs<I: IntoIterator<Item = SubstateRequirement>>(mut self, inputs: I) -> Self {
         self.unsigned_transaction.inputs_mut().extend(inputs);
         // Reset the signatures as they are no longer valid
@@ -227,6 +246,17 @@ impl TransactionBuilder {
         self
     }
 
+    /// Add inputs to the transaction, replacing any existing inputs for the same substates
+    pub fn with_replaced_inputs<I: IntoIterator<Item = SubstateRequirement>>(mut self, inputs: I) -> Self {
+        let existing = self.unsigned_transaction.inputs_mut();
+        for input in inputs {
+            existing.replace(input);
+        }
+        // Reset the signatures as they are no longer valid
+        self.clear_signatures();
+        self
+    }
+
     pub fn with_min_epoch(mut self, min_epoch: Option<Epoch>) -> Self {
         self.unsigned_transaction.set_min_epoch(min_epoch);
         // Reset the signatures as they are no longer valid
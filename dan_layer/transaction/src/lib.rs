@@ -21,6 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod builder;
+mod lock_intents;
 mod transaction;
 mod transaction_id;
 mod unsigned_transaction;
@@ -28,6 +29,7 @@ mod v1;
 mod weight;
 
 pub use builder::TransactionBuilder;
+pub use lock_intents::*;
 pub use tari_engine_types::instruction::Instruction;
 pub use transaction::*;
 pub use transaction_id::*;
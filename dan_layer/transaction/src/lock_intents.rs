@@ -0,0 +1,95 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use indexmap::IndexSet;
+use tari_dan_common_types::{InputLockIntent, SubstateRequirement};
+use tari_engine_types::commit_result::FinalizeResult;
+
+/// Infers the lock intent of each input from the result of a dry run of the transaction. Inputs that were downed by
+/// the dry run are declared as `Write` and all other inputs as `Read`. Substates that were downed but are not in
+/// `inputs` are added as unversioned `Write` inputs.
+///
+/// If the dry run was not fully accepted, the inputs are returned unchanged since the substates that the transaction
+/// would write to are not known.
+pub fn infer_input_lock_intents<I: IntoIterator<Item = SubstateRequirement>>(
+    inputs: I,
+    dry_run_result: &FinalizeResult,
+) -> IndexSet<SubstateRequirement> {
+    let mut inputs = inputs.into_iter().collect::<IndexSet<_>>();
+    if !dry_run_result.is_full_accept() {
+        return inputs;
+    }
+    let Some(diff) = dry_run_result.accept() else {
+        return inputs;
+    };
+
+    let mut inferred = inputs
+        .drain(..)
+        .map(|input| {
+            let is_written = diff.down_iter().any(|(id, _)| id == input.substate_id());
+            if is_written {
+                input.with_lock_intent(InputLockIntent::Write)
+            } else {
+                input.with_lock_intent(InputLockIntent::Read)
+            }
+        })
+        .collect::<IndexSet<_>>();
+
+    for (id, _) in diff.down_iter() {
+        if !inferred.contains(id) {
+            inferred.insert(SubstateRequirement::unversioned(id.clone()).with_lock_intent(InputLockIntent::Write));
+        }
+    }
+
+    inferred
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_engine_types::{
+        commit_result::TransactionResult,
+        fees::FeeReceipt,
+        substate::{SubstateDiff, SubstateId},
+    };
+    use tari_template_lib::{
+        models::{ComponentAddress, ObjectKey},
+        Hash,
+    };
+
+    use super::*;
+
+    fn component(n: u8) -> SubstateId {
+        ComponentAddress::new(ObjectKey::from_array([n; ObjectKey::LENGTH])).into()
+    }
+
+    #[test]
+    fn it_infers_write_intents_for_downed_inputs() {
+        let mut diff = SubstateDiff::new();
+        diff.down(component(1), 0);
+        diff.down(component(3), 2);
+        let result = FinalizeResult::new(
+            Hash::default(),
+            vec![],
+            vec![],
+            TransactionResult::Accept(diff),
+            FeeReceipt::default(),
+        );
+
+        let inferred = infer_input_lock_intents(
+            [
+                SubstateRequirement::unversioned(component(1)),
+                SubstateRequirement::versioned(component(2), 5),
+            ],
+            &result,
+        )
+        .into_iter()
+        .collect::<Vec<_>>();
+
+        assert_eq!(inferred.len(), 3);
+        assert_eq!(inferred[0].lock_intent(), Some(InputLockIntent::Write));
+        assert_eq!(inferred[1].lock_intent(), Some(InputLockIntent::Read));
+        assert_eq!(inferred[1].version(), Some(5));
+        assert_eq!(*inferred[2].substate_id(), component(3));
+        assert_eq!(inferred[2].lock_intent(), Some(InputLockIntent::Write));
+    }
+}
@@ -165,7 +165,11 @@ impl TransactionV1 {
             // Filled inputs override other inputs as they are likely filled with versions
             .filter(|i| self.filled_inputs().iter().all(|fi| fi.substate_id() != i.substate_id()))
             .map(Into::into)
-            .chain(self.filled_inputs().iter().map(Into::into))
+            .chain(self.filled_inputs().iter().map(|fi| {
+                // Filled inputs carry the lock intent declared by the transaction for the same substate
+                let lock_intent = self.inputs().get(fi.substate_id()).and_then(|i| i.lock_intent());
+                SubstateRequirementRef::from(fi).with_lock_intent(lock_intent)
+            }))
     }
 
     pub fn all_inputs_substate_ids_iter(&self) -> impl Iterator<Item = &SubstateId> + '_ {
@@ -291,10 +295,12 @@ fn calc_instruction_weight(instruction: &Instruction) -> u64 {
         Instruction::CreateAccount {
             access_rules,
             workspace_bucket,
+            policy,
             ..
         } => {
             access_rules.as_ref().map(|a| a.num_access_rules() as u64).unwrap_or(0) +
-                workspace_bucket.as_ref().map(|_| 1).unwrap_or(0)
+                workspace_bucket.as_ref().map(|_| 1).unwrap_or(0) +
+                policy.as_ref().map(|_| 1).unwrap_or(0)
         },
         Instruction::CallFunction { args, .. } => calc_args_weight(args),
         Instruction::CallMethod { args, .. } => calc_args_weight(args),
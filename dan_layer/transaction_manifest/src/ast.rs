@@ -25,7 +25,7 @@ use syn::{
     Result,
 };
 
-use crate::parser::{ManifestParser, ParsedManifest};
+use crate::parser::{ManifestParser, ParsedManifest, ParsedManifestTemplate};
 
 #[derive(Debug, Clone)]
 pub struct ManifestAst {
@@ -39,3 +39,16 @@ impl Parse for ManifestAst {
         Ok(Self { parsed })
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct ManifestTemplateAst {
+    pub parsed: ParsedManifestTemplate,
+}
+
+impl Parse for ManifestTemplateAst {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let parser = ManifestParser::new();
+        let parsed = parser.parse_template(input)?;
+        Ok(Self { parsed })
+    }
+}
@@ -40,4 +40,16 @@ pub enum ManifestError {
     InvalidVariableType(String),
     #[error("Template alias '{alias}' not defined")]
     TemplateAliasNotDefined { alias: String },
+    #[error("Function '{name}' is not defined in the manifest template")]
+    FunctionNotFound { name: String },
+    #[error("Function '{name}' is defined more than once in the manifest template")]
+    DuplicateFunction { name: String },
+    #[error("Function '{function}' expects {expected} argument(s) but got {actual}")]
+    InvalidArgumentCount {
+        function: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Invalid argument '{name}': {details}")]
+    InvalidArgument { name: String, details: String },
 }
@@ -15,7 +15,7 @@ use tari_template_lib::{
 use crate::{
     ast::ManifestAst,
     error::ManifestError,
-    parser::{InvokeIntent, ManifestIntent, ManifestLiteral, SpecialLiteral},
+    parser::{InvokeIntent, ManifestImport, ManifestIntent, ManifestLiteral, SpecialLiteral},
     ManifestInstructions,
     ManifestValue,
 };
@@ -26,6 +26,7 @@ pub struct ManifestInstructionGenerator {
     globals: HashMap<String, ManifestValue>,
     variables: HashSet<String>,
     templates: HashMap<String, TemplateAddress>,
+    workspace_namespace: String,
 }
 
 impl ManifestInstructionGenerator {
@@ -36,26 +37,19 @@ impl ManifestInstructionGenerator {
             globals,
             variables: HashSet::new(),
             templates,
+            workspace_namespace: String::new(),
         }
     }
 
+    /// Prefixes all workspace keys with the given namespace so that the generated instructions do not clobber
+    /// variables that are already on the workspace.
+    pub fn with_workspace_namespace<T: Into<String>>(mut self, namespace: T) -> Self {
+        self.workspace_namespace = namespace.into();
+        self
+    }
+
     pub fn generate_instructions(&mut self, ast: ManifestAst) -> Result<ManifestInstructions, ManifestError> {
-        self.imported_templates = ast
-            .parsed
-            .defines
-            .into_iter()
-            .map(|import| match import.template_address {
-                Some(addr) => Ok((import.alias, addr)),
-                None => {
-                    let alias_str = import.alias.to_string();
-                    self.templates
-                        .get(&alias_str)
-                        .copied()
-                        .map(|addr| (import.alias, addr))
-                        .ok_or(ManifestError::TemplateAliasNotDefined { alias: alias_str })
-                },
-            })
-            .collect::<Result<_, _>>()?;
+        self.resolve_imports(ast.parsed.defines)?;
 
         let mut instructions = Vec::with_capacity(ast.parsed.instruction_intents.len());
         for intent in ast.parsed.instruction_intents {
@@ -73,6 +67,41 @@ impl ManifestInstructionGenerator {
         })
     }
 
+    /// Generates the instructions for a manifest template function. The function arguments are bound as variables.
+    pub fn generate_function_instructions(
+        &mut self,
+        defines: Vec<ManifestImport>,
+        args: HashMap<String, ManifestValue>,
+        intents: Vec<ManifestIntent>,
+    ) -> Result<Vec<Instruction>, ManifestError> {
+        self.resolve_imports(defines)?;
+        self.global_aliases.extend(args);
+
+        let mut instructions = Vec::with_capacity(intents.len());
+        for intent in intents {
+            instructions.extend(self.translate_intent(intent)?);
+        }
+        Ok(instructions)
+    }
+
+    fn resolve_imports(&mut self, defines: Vec<ManifestImport>) -> Result<(), ManifestError> {
+        self.imported_templates = defines
+            .into_iter()
+            .map(|import| match import.template_address {
+                Some(addr) => Ok((import.alias, addr)),
+                None => {
+                    let alias_str = import.alias.to_string();
+                    self.templates
+                        .get(&alias_str)
+                        .copied()
+                        .map(|addr| (import.alias, addr))
+                        .ok_or(ManifestError::TemplateAliasNotDefined { alias: alias_str })
+                },
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
     fn translate_intent(&mut self, intent: ManifestIntent) -> Result<Vec<Instruction>, ManifestError> {
         match intent {
             ManifestIntent::InvokeTemplate(InvokeIntent {
@@ -93,7 +122,7 @@ impl ManifestInstructionGenerator {
                 if let Some(var_name) = output_variable {
                     self.variables.insert(var_name.to_string());
                     instructions.push(Instruction::PutLastInstructionOutputOnWorkspace {
-                        key: var_name.to_string().into_bytes(),
+                        key: self.workspace_key(&var_name.to_string()),
                     });
                 }
                 Ok(instructions)
@@ -127,7 +156,7 @@ impl ManifestInstructionGenerator {
                 if let Some(var_name) = output_variable {
                     self.variables.insert(var_name.to_string());
                     instructions.push(Instruction::PutLastInstructionOutputOnWorkspace {
-                        key: var_name.to_string().into_bytes(),
+                        key: self.workspace_key(&var_name.to_string()),
                     });
                 }
                 Ok(instructions)
@@ -175,7 +204,7 @@ impl ManifestInstructionGenerator {
                         .or_else(|| {
                             // Or is it a variable on the worktop?
                             if self.variables.contains(&ident.to_string()) {
-                                Some(Ok(Arg::Workspace(ident.to_string().into_bytes())))
+                                Some(Ok(Arg::Workspace(self.workspace_key(&ident.to_string()))))
                             } else {
                                 None
                             }
@@ -196,6 +225,10 @@ impl ManifestInstructionGenerator {
             .collect()
     }
 
+    fn workspace_key(&self, name: &str) -> Vec<u8> {
+        format!("{}{}", self.workspace_namespace, name).into_bytes()
+    }
+
     fn get_imported_template(&self, name: &Ident) -> Result<TemplateAddress, ManifestError> {
         self.imported_templates
             .get(name)
@@ -27,13 +27,14 @@ use syn::parse2;
 use tari_engine_types::{instruction::Instruction, TemplateAddress};
 
 use self::ast::ManifestAst;
-pub use crate::value::ManifestValue;
-use crate::{error::ManifestError, generator::ManifestInstructionGenerator};
+use crate::generator::ManifestInstructionGenerator;
+pub use crate::{error::ManifestError, template::ManifestTemplate, value::ManifestValue};
 
 mod ast;
 mod error;
 mod generator;
 mod parser;
+mod template;
 mod value;
 
 pub fn parse_manifest(
@@ -14,8 +14,10 @@ use syn::{
     ExprMacro,
     ExprMethodCall,
     ExprPath,
+    FnArg,
     Item,
     ItemFn,
+    ItemMod,
     ItemUse,
     Lit,
     LitStr,
@@ -23,9 +25,11 @@ use syn::{
     Macro,
     Pat,
     PatIdent,
+    PatType,
     Path,
     Signature,
     Stmt,
+    Type,
     UseTree,
 };
 use tari_engine_types::TemplateAddress;
@@ -82,6 +86,20 @@ pub enum SpecialLiteral {
 
 pub struct ManifestParser;
 
+#[derive(Debug, Clone)]
+pub struct ParsedManifestTemplate {
+    pub name: Ident,
+    pub defines: Vec<ManifestImport>,
+    pub functions: Vec<ParsedManifestFunction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedManifestFunction {
+    pub name: Ident,
+    pub params: Vec<(Ident, Type)>,
+    pub intents: Vec<ManifestIntent>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedManifest {
     pub defines: Vec<ManifestImport>,
@@ -97,39 +115,12 @@ impl ManifestParser {
     pub fn parse(&self, input: ParseStream) -> Result<ParsedManifest, syn::Error> {
         let mut instruction_intents = vec![];
         let mut fee_instruction_intents = vec![];
-        let mut defines = vec![];
-        defines.push(ManifestImport {
-            template_address: Some(ACCOUNT_TEMPLATE_ADDRESS),
-            alias: Ident::new("Account", proc_macro2::Span::call_site()),
-        });
+        let mut defines = default_imports();
 
         for stmt in Block::parse_within(input)? {
             match stmt {
-                // use template_hash as TemplateName;
-                Stmt::Item(Item::Use(ItemUse {
-                    tree: UseTree::Rename(rename),
-                    ..
-                })) => {
-                    let template_id = rename.ident.to_string();
-                    let template_address = template_id
-                        .split_once('_')
-                        .and_then(|(_, s)| TemplateAddress::from_hex(s).ok())
-                        .ok_or_else(|| syn::Error::new_spanned(rename.clone(), "Invalid template address"))?;
-
-                    defines.push(ManifestImport {
-                        template_address: Some(template_address),
-                        alias: rename.rename,
-                    });
-                },
-                // use Name; // (predefined template)
-                Stmt::Item(Item::Use(ItemUse {
-                    tree: UseTree::Name(name),
-                    ..
-                })) => {
-                    defines.push(ManifestImport {
-                        template_address: None,
-                        alias: name.ident,
-                    });
+                Stmt::Item(Item::Use(item_use)) => {
+                    defines.push(parse_import(item_use)?);
                 },
                 Stmt::Item(Item::Fn(ItemFn {
                     block,
@@ -166,6 +157,43 @@ impl ManifestParser {
         })
     }
 
+    /// Parses a manifest template: a module containing `use` statements and functions whose bodies are manifest
+    /// scripts.
+    pub fn parse_template(&self, input: ParseStream) -> Result<ParsedManifestTemplate, syn::Error> {
+        let item_mod = input.parse::<ItemMod>()?;
+        let name = item_mod.ident;
+        let (_, items) = item_mod
+            .content
+            .ok_or_else(|| syn::Error::new_spanned(&name, "Manifest template module must have a body"))?;
+
+        let mut defines = default_imports();
+        let mut functions = vec![];
+        for item in items {
+            match item {
+                Item::Use(item_use) => defines.push(parse_import(item_use)?),
+                Item::Fn(ItemFn { sig, block, .. }) => {
+                    functions.push(ParsedManifestFunction {
+                        params: parse_params(sig.inputs)?,
+                        name: sig.ident,
+                        intents: self.parse_block(*block)?,
+                    });
+                },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "Only use statements and functions are allowed in a manifest template",
+                    ))
+                },
+            }
+        }
+
+        Ok(ParsedManifestTemplate {
+            name,
+            defines,
+            functions,
+        })
+    }
+
     fn parse_block(&self, block: Block) -> Result<Vec<ManifestIntent>, syn::Error> {
         block.stmts.into_iter().map(|stmt| self.parse_stmt(stmt)).collect()
     }
@@ -319,6 +347,56 @@ impl ManifestParser {
     }
 }
 
+fn default_imports() -> Vec<ManifestImport> {
+    vec![ManifestImport {
+        template_address: Some(ACCOUNT_TEMPLATE_ADDRESS),
+        alias: Ident::new("Account", proc_macro2::Span::call_site()),
+    }]
+}
+
+fn parse_import(item_use: ItemUse) -> Result<ManifestImport, syn::Error> {
+    match item_use.tree {
+        // use template_hash as TemplateName;
+        UseTree::Rename(rename) => {
+            let template_id = rename.ident.to_string();
+            let template_address = template_id
+                .split_once('_')
+                .and_then(|(_, s)| TemplateAddress::from_hex(s).ok())
+                .ok_or_else(|| syn::Error::new_spanned(rename.clone(), "Invalid template address"))?;
+
+            Ok(ManifestImport {
+                template_address: Some(template_address),
+                alias: rename.rename,
+            })
+        },
+        // use Name; // (predefined template)
+        UseTree::Name(name) => Ok(ManifestImport {
+            template_address: None,
+            alias: name.ident,
+        }),
+        tree => Err(syn::Error::new_spanned(tree, "Unsupported use statement")),
+    }
+}
+
+fn parse_params(inputs: Punctuated<FnArg, Comma>) -> Result<Vec<(Ident, Type)>, syn::Error> {
+    inputs
+        .into_iter()
+        .map(|input| match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => match *pat {
+                Pat::Ident(PatIdent { ident, .. }) => Ok((ident, *ty)),
+                pat => Err(syn::Error::new_spanned(
+                    pat,
+                    "Only simple parameter names are supported",
+                )),
+            },
+            FnArg::Receiver(receiver) => Err(syn::Error::new_spanned(
+                receiver,
+                "Manifest template functions cannot take self",
+            )),
+        })
+        .collect()
+}
+
 fn assignment_from_macro(var_name: Ident, mac: &Ident, tokens: TokenStream) -> Result<ManifestIntent, syn::Error> {
     match mac.to_string().as_str() {
         "global" | "var" | "arg" => Ok(ManifestIntent::AssignInput(AssignInputStmt {
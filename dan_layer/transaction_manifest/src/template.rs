@@ -0,0 +1,250 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-clause
+
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use proc_macro2::TokenStream;
+use syn::{parse2, GenericArgument, PathArguments};
+use tari_engine_types::{instruction::Instruction, substate::SubstateId, TemplateAddress};
+use tari_template_abi::{ArgDef, FunctionDef, Type};
+use tari_template_lib::models::{ComponentAddress, ResourceAddress, VaultId};
+
+use crate::{ast::ManifestTemplateAst, error::ManifestError, generator::ManifestInstructionGenerator, ManifestValue};
+
+/// A template whose functions are parameterized manifest scripts. Calling a function expands it into instructions
+/// that run in the context of the calling transaction.
+///
+/// ```ignore
+/// mod Airdrop {
+///     use template_<hex> as Faucet;
+///
+///     fn claim(faucet: ComponentAddress, account: ComponentAddress, amount: Amount) {
+///         let coins = faucet.take_free_coins(amount);
+///         account.deposit(coins);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestTemplate {
+    name: String,
+    source: String,
+    functions: Vec<FunctionDef>,
+}
+
+impl ManifestTemplate {
+    pub fn parse(source: &str) -> Result<Self, ManifestError> {
+        let ast = parse_template_ast(source)?;
+
+        let mut names = HashSet::new();
+        let functions = ast
+            .parsed
+            .functions
+            .iter()
+            .map(|function| {
+                let name = function.name.to_string();
+                if !names.insert(name.clone()) {
+                    return Err(ManifestError::DuplicateFunction { name });
+                }
+                let arguments = function
+                    .params
+                    .iter()
+                    .map(|(name, ty)| {
+                        Ok(ArgDef {
+                            name: name.to_string(),
+                            arg_type: syn_type_to_abi_type(ty)?,
+                        })
+                    })
+                    .collect::<Result<_, ManifestError>>()?;
+                Ok(FunctionDef {
+                    name,
+                    arguments,
+                    output: Type::Unit,
+                    is_mut: false,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: ast.parsed.name.to_string(),
+            source: source.to_string(),
+            functions,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn functions(&self) -> &[FunctionDef] {
+        &self.functions
+    }
+
+    /// Expands a function call into instructions. `templates` resolves `use Name;` imports. Workspace variables are
+    /// put under `workspace_namespace` so that they do not collide with the caller's variables.
+    pub fn expand(
+        &self,
+        function: &str,
+        args: Vec<tari_bor::Value>,
+        templates: HashMap<String, TemplateAddress>,
+        workspace_namespace: &str,
+    ) -> Result<Vec<Instruction>, ManifestError> {
+        let function_def =
+            self.functions
+                .iter()
+                .find(|f| f.name == function)
+                .ok_or_else(|| ManifestError::FunctionNotFound {
+                    name: function.to_string(),
+                })?;
+        if args.len() != function_def.arguments.len() {
+            return Err(ManifestError::InvalidArgumentCount {
+                function: function.to_string(),
+                expected: function_def.arguments.len(),
+                actual: args.len(),
+            });
+        }
+
+        let bound_args = function_def
+            .arguments
+            .iter()
+            .zip(args)
+            .map(|(arg_def, value)| Ok((arg_def.name.clone(), bind_arg(arg_def, value)?)))
+            .collect::<Result<_, ManifestError>>()?;
+
+        // The parsed AST is not Send so only the source is kept between calls
+        let ast = parse_template_ast(&self.source)?;
+        let parsed_function = ast
+            .parsed
+            .functions
+            .into_iter()
+            .find(|f| f.name == function)
+            .ok_or_else(|| ManifestError::FunctionNotFound {
+                name: function.to_string(),
+            })?;
+
+        ManifestInstructionGenerator::new(HashMap::new(), templates)
+            .with_workspace_namespace(workspace_namespace)
+            .generate_function_instructions(ast.parsed.defines, bound_args, parsed_function.intents)
+    }
+}
+
+fn parse_template_ast(source: &str) -> Result<ManifestTemplateAst, ManifestError> {
+    let tokens = TokenStream::from_str(source).map_err(|e| ManifestError::LexError(e.to_string()))?;
+    Ok(parse2::<ManifestTemplateAst>(tokens)?)
+}
+
+fn syn_type_to_abi_type(ty: &syn::Type) -> Result<Type, ManifestError> {
+    match ty {
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(Type::Unit),
+        syn::Type::Tuple(tuple) => Ok(Type::Tuple(
+            tuple.elems.iter().map(syn_type_to_abi_type).collect::<Result<_, _>>()?,
+        )),
+        syn::Type::Path(path) => {
+            let segment = path
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| ManifestError::UnsupportedExpr("Empty type path".to_string()))?;
+            let ty = match segment.ident.to_string().as_str() {
+                "bool" => Type::Bool,
+                "i8" => Type::I8,
+                "i16" => Type::I16,
+                "i32" => Type::I32,
+                "i64" => Type::I64,
+                "i128" => Type::I128,
+                "u8" => Type::U8,
+                "u16" => Type::U16,
+                "u32" => Type::U32,
+                "u64" => Type::U64,
+                "u128" => Type::U128,
+                "String" => Type::String,
+                "Vec" => {
+                    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                        return Err(ManifestError::UnsupportedExpr(
+                            "Vec requires a type argument".to_string(),
+                        ));
+                    };
+                    match args.args.first() {
+                        Some(GenericArgument::Type(inner)) => Type::Vec(Box::new(syn_type_to_abi_type(inner)?)),
+                        _ => {
+                            return Err(ManifestError::UnsupportedExpr(
+                                "Vec requires a type argument".to_string(),
+                            ))
+                        },
+                    }
+                },
+                name => Type::Other { name: name.to_string() },
+            };
+            Ok(ty)
+        },
+        _ => Err(ManifestError::UnsupportedExpr(
+            "Only named and tuple types are supported as parameters".to_string(),
+        )),
+    }
+}
+
+/// Checks the argument against its declared type. Addresses are bound as substate ids so that they can be used as
+/// the receiver of a method call.
+fn bind_arg(arg_def: &ArgDef, value: tari_bor::Value) -> Result<ManifestValue, ManifestError> {
+    let invalid = |details: String| ManifestError::InvalidArgument {
+        name: arg_def.name.clone(),
+        details,
+    };
+    let substate_id = match arg_def.arg_type.other() {
+        Some("ComponentAddress") => Some(SubstateId::Component(
+            tari_bor::from_value::<ComponentAddress>(&value).map_err(|e| invalid(e.to_string()))?,
+        )),
+        Some("ResourceAddress") => Some(SubstateId::Resource(
+            tari_bor::from_value::<ResourceAddress>(&value).map_err(|e| invalid(e.to_string()))?,
+        )),
+        Some("VaultId") => Some(SubstateId::Vault(
+            tari_bor::from_value::<VaultId>(&value).map_err(|e| invalid(e.to_string()))?,
+        )),
+        _ => None,
+    };
+    if let Some(substate_id) = substate_id {
+        return Ok(ManifestValue::SubstateId(substate_id));
+    }
+
+    if !value_matches_type(&value, &arg_def.arg_type) {
+        return Err(invalid(format!("expected a value of type {:?}", arg_def.arg_type)));
+    }
+    Ok(ManifestValue::Value(value))
+}
+
+fn value_matches_type(value: &tari_bor::Value, ty: &Type) -> bool {
+    use tari_bor::Value;
+
+    match (ty, value) {
+        (Type::Unit, Value::Null) | (Type::Bool, Value::Bool(_)) | (Type::String, Value::Text(_)) => true,
+        (Type::Vec(ty), Value::Array(items)) => items.iter().all(|item| value_matches_type(item, ty)),
+        (Type::Vec(ty), Value::Bytes(_)) => **ty == Type::U8,
+        (Type::Tuple(types), Value::Array(items)) => {
+            types.len() == items.len() && types.iter().zip(items).all(|(ty, item)| value_matches_type(item, ty))
+        },
+        (Type::Other { .. }, _) => true,
+        (ty, Value::Integer(i)) => {
+            let i = i128::from(*i);
+            match ty {
+                Type::I8 => i8::try_from(i).is_ok(),
+                Type::I16 => i16::try_from(i).is_ok(),
+                Type::I32 => i32::try_from(i).is_ok(),
+                Type::I64 => i64::try_from(i).is_ok(),
+                Type::I128 => true,
+                Type::U8 => u8::try_from(i).is_ok(),
+                Type::U16 => u16::try_from(i).is_ok(),
+                Type::U32 => u32::try_from(i).is_ok(),
+                Type::U64 => u64::try_from(i).is_ok(),
+                Type::U128 => i >= 0,
+                _ => false,
+            }
+        },
+        _ => false,
+    }
+}
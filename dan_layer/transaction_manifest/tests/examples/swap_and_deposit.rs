@@ -0,0 +1,24 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-clause
+
+mod SwapAndDeposit {
+    fn swap_and_deposit(
+        account: ComponentAddress,
+        pool: ComponentAddress,
+        input_resource: ResourceAddress,
+        amount: Amount,
+        output_resource: ResourceAddress,
+    ) {
+        info!("Swapping and depositing");
+        let input = account.withdraw(input_resource, amount);
+        let output = pool.swap(input, output_resource);
+        account.deposit(output);
+    }
+
+    fn deposit_twice(account: ComponentAddress, faucet: ComponentAddress, amount: Amount) {
+        let first = faucet.take_free_coins(amount);
+        account.deposit(first);
+        let second = faucet.take_free_coins(amount);
+        account.deposit(second);
+    }
+}
@@ -23,11 +23,13 @@
 use std::{collections::HashMap, fs};
 
 use tari_engine_types::{instruction::Instruction, substate::SubstateId};
+use tari_template_abi::Type;
 use tari_template_lib::{
     args,
+    args::LogLevel,
     models::{Amount, ComponentAddress, ObjectKey, ResourceAddress, TemplateAddress},
 };
-use tari_transaction_manifest::{parse_manifest, ManifestInstructions};
+use tari_transaction_manifest::{parse_manifest, ManifestError, ManifestInstructions, ManifestTemplate};
 
 #[test]
 #[allow(clippy::too_many_lines)]
@@ -103,3 +105,78 @@ fn manifest_smoke_test() {
     assert_eq!(instructions, expected);
     assert_eq!(fee_instructions, vec![]);
 }
+
+#[test]
+fn manifest_template_expands_functions() {
+    let input = fs::read_to_string("tests/examples/swap_and_deposit.rs").unwrap();
+    let template = ManifestTemplate::parse(&input).unwrap();
+    assert_eq!(template.name(), "SwapAndDeposit");
+    assert_eq!(template.functions().len(), 2);
+    let function = &template.functions()[0];
+    assert_eq!(function.name, "swap_and_deposit");
+    assert_eq!(function.arguments[0].arg_type, Type::Other {
+        name: "ComponentAddress".to_string()
+    });
+
+    let account = ComponentAddress::new([0u8; ObjectKey::LENGTH].into());
+    let pool = ComponentAddress::new([1u8; ObjectKey::LENGTH].into());
+    let input_resource = ResourceAddress::from([2u8; ObjectKey::LENGTH]);
+    let output_resource = ResourceAddress::from([3u8; ObjectKey::LENGTH]);
+    let args = [
+        tari_bor::to_value(&account).unwrap(),
+        tari_bor::to_value(&pool).unwrap(),
+        tari_bor::to_value(&input_resource).unwrap(),
+        tari_bor::to_value(&Amount(100)).unwrap(),
+        tari_bor::to_value(&output_resource).unwrap(),
+    ];
+
+    let instructions = template
+        .expand("swap_and_deposit", args.to_vec(), Default::default(), "ns/")
+        .unwrap();
+
+    let expected = vec![
+        Instruction::EmitLog {
+            level: LogLevel::Info,
+            message: "Swapping and depositing".to_string(),
+        },
+        Instruction::CallMethod {
+            component_address: account,
+            method: "withdraw".to_string(),
+            args: args![input_resource, Amount(100)],
+        },
+        Instruction::PutLastInstructionOutputOnWorkspace {
+            key: b"ns/input".to_vec(),
+        },
+        Instruction::CallMethod {
+            component_address: pool,
+            method: "swap".to_string(),
+            args: args![Variable("ns/input"), output_resource],
+        },
+        Instruction::PutLastInstructionOutputOnWorkspace {
+            key: b"ns/output".to_vec(),
+        },
+        Instruction::CallMethod {
+            component_address: account,
+            method: "deposit".to_string(),
+            args: args![Variable("ns/output")],
+        },
+    ];
+    assert_eq!(instructions, expected);
+
+    let err = template
+        .expand("swap_and_deposit", args[..2].to_vec(), Default::default(), "ns/")
+        .unwrap_err();
+    assert!(matches!(err, ManifestError::InvalidArgumentCount { .. }));
+
+    let mut bad_args = args.to_vec();
+    bad_args[0] = tari_bor::to_value(&"not an address").unwrap();
+    let err = template
+        .expand("swap_and_deposit", bad_args, Default::default(), "ns/")
+        .unwrap_err();
+    assert!(matches!(err, ManifestError::InvalidArgument { .. }));
+
+    let err = template
+        .expand("missing", vec![], Default::default(), "ns/")
+        .unwrap_err();
+    assert!(matches!(err, ManifestError::FunctionNotFound { .. }));
+}
@@ -8,49 +8,49 @@ use tari_rpc_macros::tari_rpc;
 #[tari_rpc(protocol_name = "/tari/validator/1.0.0", server_struct = ValidatorNodeRpcServer, client_struct = ValidatorNodeRpcClient
 )]
 pub trait ValidatorNodeRpcService: Send + Sync + 'static {
-    #[rpc(method = 1)]
+    #[rpc(method = 1, rate_limit = 20, burst = 50)]
     async fn submit_transaction(
         &self,
         request: Request<proto::SubmitTransactionRequest>,
     ) -> Result<Response<proto::SubmitTransactionResponse>, RpcStatus>;
 
-    #[rpc(method = 2)]
+    #[rpc(method = 2, rate_limit = 100, burst = 200)]
     async fn get_substate(
         &self,
         req: Request<proto::GetSubstateRequest>,
     ) -> Result<Response<proto::GetSubstateResponse>, RpcStatus>;
 
-    #[rpc(method = 3)]
+    #[rpc(method = 3, rate_limit = 50, burst = 100)]
     async fn get_transaction_result(
         &self,
         req: Request<proto::GetTransactionResultRequest>,
     ) -> Result<Response<proto::GetTransactionResultResponse>, RpcStatus>;
 
-    #[rpc(method = 4)]
+    #[rpc(method = 4, rate_limit = 1, burst = 5)]
     async fn sync_blocks(
         &self,
         request: Request<proto::SyncBlocksRequest>,
     ) -> Result<Streaming<proto::SyncBlocksResponse>, RpcStatus>;
 
-    #[rpc(method = 5)]
+    #[rpc(method = 5, rate_limit = 10, burst = 20)]
     async fn get_high_qc(
         &self,
         request: Request<proto::GetHighQcRequest>,
     ) -> Result<Response<proto::GetHighQcResponse>, RpcStatus>;
 
-    #[rpc(method = 6)]
+    #[rpc(method = 6, rate_limit = 1, burst = 5)]
     async fn get_checkpoint(
         &self,
         request: Request<proto::GetCheckpointRequest>,
     ) -> Result<Response<proto::GetCheckpointResponse>, RpcStatus>;
 
-    #[rpc(method = 7)]
+    #[rpc(method = 7, rate_limit = 1, burst = 5)]
     async fn sync_state(
         &self,
         request: Request<proto::SyncStateRequest>,
     ) -> Result<Streaming<proto::SyncStateResponse>, RpcStatus>;
 
-    #[rpc(method = 8)]
+    #[rpc(method = 8, rate_limit = 1, burst = 5)]
     async fn sync_templates(
         &self,
         request: Request<proto::SyncTemplatesRequest>,
@@ -23,7 +23,7 @@ use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
 use tari_template_lib::{
     args,
     constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
-    models::{Amount, ComponentAddress, ResourceAddress, VaultId},
+    models::{Amount, ComponentAddress, ConfidentialWithdrawProof, ResourceAddress, VaultId},
 };
 use tari_transaction::Transaction;
 
@@ -370,15 +370,30 @@ where
             Amount::zero(),
         )?;
 
-        // Reserve and lock input funds
+        let resource_view_key = resource_substate
+            .substate
+            .as_resource()
+            .ok_or_else(|| ConfidentialTransferApiError::UnexpectedIndexerResponse {
+                details: format!(
+                    "Expected indexer to return resource for address {}. It returned {}",
+                    params.resource_address, resource_substate.address
+                ),
+            })?
+            .view_key()
+            .cloned();
+
+        // Reserve and lock input funds and generate the withdraw proof
         // TODO: preserve atomicity across api calls - needed in many places
-        let inputs_to_spend = match self.resolved_inputs_for_transfer(
-            params.from_account,
-            params.resource_address,
-            params.amount,
-            params.input_selection,
-        ) {
-            Ok(inputs) => inputs,
+        let (proof, transaction_proof_id) = match self.create_withdraw_proof(WithdrawProofParams {
+            from_account: params.from_account,
+            resource_address: params.resource_address,
+            resource_view_key,
+            destination_public_key: &params.destination_public_key,
+            amount: params.amount,
+            output_to_revealed: params.output_to_revealed,
+            input_selection: params.input_selection,
+        }) {
+            Ok(proof) => proof,
             Err(e) => {
                 warn!(target: LOG_TARGET, "Unlocking fee fund locks after error: {}", e);
                 // This is a hack that addresses the case where input locking fails after the fee transaction. However
@@ -403,23 +418,82 @@ where
             },
         };
 
-        // Generate outputs
-        let resource_view_key = resource_substate
-            .substate
-            .as_resource()
-            .ok_or_else(|| ConfidentialTransferApiError::UnexpectedIndexerResponse {
-                details: format!(
-                    "Expected indexer to return resource for address {}. It returned {}",
-                    params.resource_address, resource_substate.address
-                ),
-            })?
-            .view_key()
-            .cloned();
+        let network = self.config_api.get_network()?;
+        let transaction = Transaction::builder()
+            .for_network(network.as_byte())
+            .fee_transaction_pay_from_component_confidential(from_account_address, fee_withdraw_proof)
+            .then(|builder| {
+                if dest_account_exists {
+                    builder
+                } else {
+                    builder.create_account(params.destination_public_key.clone())
+                }
+            })
+            .then(|builder| {
+                if let Some(ref badge) = params.proof_from_resource {
+                    builder
+                        .call_method(from_account_address, "create_proof_for_resource", args![badge])
+                        .put_last_instruction_output_on_workspace("proof")
+                } else {
+                    builder
+                }
+            })
+            .call_method(from_account_address, "withdraw_confidential", args![
+                params.resource_address,
+                proof
+            ])
+            .put_last_instruction_output_on_workspace("bucket")
+            .call_method(to_account.address, "deposit", args![Workspace("bucket")])
+            .then(|builder| {
+                if params.proof_from_resource.is_some() {
+                    builder.drop_all_proofs_in_workspace()
+                } else {
+                    builder
+                }
+            })
+            .with_inputs(inputs)
+            .build_and_seal(&account_secret.key);
+
+        self.outputs_api
+            .proofs_set_transaction_hash(transaction_proof_id, *transaction.id())?;
+        self.outputs_api
+            .proofs_set_transaction_hash(fee_inputs_to_spend.proof_id, *transaction.id())?;
+
+        Ok(TransferOutput {
+            transaction,
+            autofill_inputs: vec![],
+            fee_transaction_proof_id: Some(fee_inputs_to_spend.proof_id),
+            transaction_proof_id: Some(transaction_proof_id),
+        })
+    }
+
+    /// Locks inputs from the account vault for the given resource and generates a withdraw proof that outputs the
+    /// amount to the destination public key. Change is returned to the account. The caller must set the transaction
+    /// hash on the returned proof id once the transaction that contains the proof has been built.
+    pub fn create_withdraw_proof(
+        &self,
+        params: WithdrawProofParams<'_>,
+    ) -> Result<(ConfidentialWithdrawProof, ConfidentialProofId), ConfidentialTransferApiError> {
+        let account = self.accounts_api.get_account_by_address(&params.from_account.into())?;
+        let src_vault = self
+            .accounts_api
+            .get_vault_by_resource(&account.address, &params.resource_address)?;
+        let account_secret = self
+            .key_manager_api
+            .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        let account_public_key = PublicKey::from_secret_key(&account_secret.key);
+
+        let inputs_to_spend = self.resolved_inputs_for_transfer(
+            params.from_account,
+            params.resource_address,
+            params.amount,
+            params.input_selection,
+        )?;
 
         let output_statement = self.create_confidential_proof_statement(
-            &params.destination_public_key,
+            params.destination_public_key,
             params.confidential_amount(),
-            resource_view_key.clone(),
+            params.resource_view_key.clone(),
         )?;
 
         let remaining_left_to_pay = params
@@ -439,7 +513,7 @@ where
             let statement = self.create_confidential_proof_statement(
                 &account_public_key,
                 change_confidential_amount,
-                resource_view_key,
+                params.resource_view_key,
             )?;
 
             let change_value = statement.amount.as_u64_checked().unwrap();
@@ -471,53 +545,7 @@ where
             Amount::zero(),
         )?;
 
-        let network = self.config_api.get_network()?;
-        let transaction = Transaction::builder()
-            .for_network(network.as_byte())
-            .fee_transaction_pay_from_component_confidential(from_account_address, fee_withdraw_proof)
-            .then(|builder| {
-                if dest_account_exists {
-                    builder
-                } else {
-                    builder.create_account(params.destination_public_key.clone())
-                }
-            })
-            .then(|builder| {
-                if let Some(ref badge) = params.proof_from_resource {
-                    builder
-                        .call_method(from_account_address, "create_proof_for_resource", args![badge])
-                        .put_last_instruction_output_on_workspace("proof")
-                } else {
-                    builder
-                }
-            })
-            .call_method(from_account_address, "withdraw_confidential", args![
-                params.resource_address,
-                proof
-            ])
-            .put_last_instruction_output_on_workspace("bucket")
-            .call_method(to_account.address, "deposit", args![Workspace("bucket")])
-            .then(|builder| {
-                if params.proof_from_resource.is_some() {
-                    builder.drop_all_proofs_in_workspace()
-                } else {
-                    builder
-                }
-            })
-            .with_inputs(inputs)
-            .build_and_seal(&account_secret.key);
-
-        self.outputs_api
-            .proofs_set_transaction_hash(inputs_to_spend.proof_id, *transaction.id())?;
-        self.outputs_api
-            .proofs_set_transaction_hash(fee_inputs_to_spend.proof_id, *transaction.id())?;
-
-        Ok(TransferOutput {
-            transaction,
-            autofill_inputs: vec![],
-            fee_transaction_proof_id: Some(fee_inputs_to_spend.proof_id),
-            transaction_proof_id: Some(inputs_to_spend.proof_id),
-        })
+        Ok((proof, inputs_to_spend.proof_id))
     }
 
     fn create_confidential_proof_statement(
@@ -600,6 +628,42 @@ impl TransferParams {
     }
 }
 
+#[derive(Debug)]
+pub struct WithdrawProofParams<'a> {
+    /// Spend from this account
+    pub from_account: ComponentAddress,
+    /// Address of the confidential resource to withdraw
+    pub resource_address: ResourceAddress,
+    /// The view key of the resource, if any
+    pub resource_view_key: Option<PublicKey>,
+    /// The owner of the output
+    pub destination_public_key: &'a PublicKey,
+    /// Amount to output to the destination
+    pub amount: Amount,
+    /// If true, the output will contain only a revealed amount. Otherwise, only confidential amounts.
+    pub output_to_revealed: bool,
+    /// Strategy for input selection
+    pub input_selection: ConfidentialTransferInputSelection,
+}
+
+impl WithdrawProofParams<'_> {
+    pub fn confidential_amount(&self) -> Amount {
+        if self.output_to_revealed {
+            Amount::zero()
+        } else {
+            self.amount
+        }
+    }
+
+    pub fn revealed_amount(&self) -> Amount {
+        if self.output_to_revealed {
+            self.amount
+        } else {
+            Amount::zero()
+        }
+    }
+}
+
 impl TransferParams {
     pub fn total_amount(&self) -> Amount {
         self.amount + self.max_fee
@@ -225,10 +225,6 @@ impl<'a, TStore: WalletStore> JwtApi<'a, TStore> {
         Ok(claims)
     }
 
-    fn get_permissions(&self, token: &str) -> Result<JrpcPermissions, JwtApiError> {
-        self.get_token_claims(token).map(|claims| claims.permissions)
-    }
-
     pub fn grant(&self, name: String, auth_token: String) -> Result<String, JwtApiError> {
         let auth_claims = self.check_auth_token(auth_token.as_ref())?;
         let my_claims = Claims {
@@ -264,12 +260,17 @@ impl<'a, TStore: WalletStore> JwtApi<'a, TStore> {
         Ok(revoked)
     }
 
-    pub fn check_auth(&self, token: Option<String>, req_permissions: &[JrpcPermission]) -> Result<(), JwtApiError> {
+    /// Returns the claims of a token if it is valid and has not been revoked
+    pub fn check_token(&self, token: Option<String>) -> Result<Claims, JwtApiError> {
         let token = token.ok_or(JwtApiError::TokenMissing)?;
         if self.is_token_revoked(&token)? {
             return Err(JwtApiError::TokenRevoked {});
         }
-        let permissions = self.get_permissions(&token)?;
+        self.get_token_claims(&token)
+    }
+
+    pub fn check_auth(&self, token: Option<String>, req_permissions: &[JrpcPermission]) -> Result<(), JwtApiError> {
+        let permissions = self.check_token(token)?.permissions;
         for permission in req_permissions {
             permissions.check_permission(permission)?;
         }
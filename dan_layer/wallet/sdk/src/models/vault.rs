@@ -17,12 +17,18 @@ pub struct VaultModel {
     pub revealed_balance: Amount,
     pub locked_revealed_balance: Amount,
     pub token_symbol: Option<String>,
+    /// The number of decimal places used to display amounts of this vault's resource
+    pub divisibility: u8,
 }
 
 impl VaultModel {
     pub fn available_revealed_balance(&self) -> Amount {
         self.revealed_balance - self.locked_revealed_balance
     }
+
+    pub fn format_amount(&self, amount: Amount) -> String {
+        amount.to_decimal_string(self.divisibility)
+    }
 }
 
 #[derive(Debug, Clone)]
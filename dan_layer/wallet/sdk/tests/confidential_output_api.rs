@@ -158,6 +158,7 @@ impl Test {
                 CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
                 ResourceType::Confidential,
                 Some("TEST".to_string()),
+                0,
             )
             .unwrap();
 
@@ -101,6 +101,7 @@ CREATE TABLE vaults
     confidential_balance    BIGINT   NOT NULL DEFAULT 0,
     locked_revealed_balance BIGINT   NOT NULL DEFAULT 0,
     token_symbol            TEXT     NULL,
+    divisibility            INTEGER  NOT NULL DEFAULT 0,
     created_at              DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at              DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
@@ -26,6 +26,7 @@ pub struct Vault {
     pub confidential_balance: i64,
     pub locked_revealed_balance: i64,
     pub token_symbol: Option<String>,
+    pub divisibility: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -51,6 +52,11 @@ impl Vault {
             })?,
             resource_type: db_str_to_resource_type(&self.resource_type)?,
             token_symbol: self.token_symbol,
+            divisibility: u8::try_from(self.divisibility).map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_into_vault",
+                item: "vault.divisibility",
+                details: e.to_string(),
+            })?,
             revealed_balance: Amount(self.revealed_balance),
             locked_revealed_balance: Amount(self.locked_revealed_balance),
             confidential_balance: Amount(self.confidential_balance),
@@ -140,6 +140,7 @@ diesel::table! {
         confidential_balance -> BigInt,
         locked_revealed_balance -> BigInt,
         token_symbol -> Nullable<Text>,
+        divisibility -> Integer,
         created_at -> Timestamp,
         updated_at -> Timestamp,
     }
@@ -518,6 +518,7 @@ impl WalletStoreWriter for WriteTransaction<'_> {
             vaults::resource_address.eq(vault.resource_address.to_string()),
             vaults::resource_type.eq(format!("{:?}", vault.resource_type)),
             vaults::token_symbol.eq(vault.token_symbol),
+            vaults::divisibility.eq(i32::from(vault.divisibility)),
         );
         diesel::insert_into(vaults::table)
             .values(values)
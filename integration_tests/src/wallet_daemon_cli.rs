@@ -229,6 +229,7 @@ pub async fn transfer_confidential(
         detect_inputs: true,
         detect_inputs_use_unversioned: true,
         autofill_inputs: vec![],
+        infer_lock_intents: false,
     };
 
     let submit_resp = client.submit_transaction(submit_req).await.unwrap();
@@ -261,6 +262,7 @@ pub async fn create_account(world: &mut TariWorld, account_name: String, wallet_
         is_default: false,
         max_fee: None,
         key_id: None,
+        policy: None,
     };
 
     let resp = timeout(Duration::from_secs(240), client.create_account(request))
@@ -484,6 +486,7 @@ pub async fn submit_manifest_with_signing_keys(
         detect_inputs_use_unversioned: true,
         proof_ids: vec![],
         autofill_inputs: vec![],
+        infer_lock_intents: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -566,6 +569,7 @@ pub async fn submit_manifest(
         detect_inputs_use_unversioned: true,
         proof_ids: vec![],
         autofill_inputs: vec![],
+        infer_lock_intents: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -617,6 +621,7 @@ pub async fn submit_manifest(
 //         detect_inputs_use_unversioned: false,
 //         autofill_inputs: inputs,
 //         proof_ids: vec![],
+//         infer_lock_intents: false,
 //     };
 //
 //     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -675,6 +680,7 @@ pub async fn create_component(
         detect_inputs_use_unversioned: true,
         proof_ids: vec![],
         autofill_inputs: vec![],
+        infer_lock_intents: false,
     };
 
     let resp = client.submit_transaction(transaction_submit_req).await.unwrap();
@@ -870,6 +876,7 @@ pub async fn transfer(
     let request = AccountsTransferRequest {
         account,
         amount,
+        decimal_amount: None,
         resource_address,
         destination_public_key,
         max_fee,
@@ -950,6 +957,7 @@ async fn submit_unsigned_tx_and_wait_for_response(
         detect_inputs: true,
         detect_inputs_use_unversioned: use_unversioned_inputs,
         proof_ids: vec![],
+        infer_lock_intents: false,
     };
 
     let submit_resp = client.submit_transaction(submit_req).await?;
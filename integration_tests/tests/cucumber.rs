@@ -732,7 +732,7 @@ async fn successful_transaction(world: &mut TariWorld) {
     for vn_ps in world.validator_nodes.values() {
         let mut client = vn_ps.create_client();
 
-        let request = GetRecentTransactionsRequest {};
+        let request = GetRecentTransactionsRequest::default();
         let recent_transactions_res = client.get_recent_transactions(request).await.unwrap();
 
         let recent_transactions = recent_transactions_res.transactions;
@@ -474,6 +474,7 @@ async fn when_i_wait_for_validator_leaf_block_at_least(world: &mut TariWorld, na
                 ordering: None,
                 filter_index: None,
                 filter: None,
+                cursor: None,
             })
             .await
             .unwrap();
@@ -503,6 +504,7 @@ async fn when_i_wait_for_validator_leaf_block_at_least(world: &mut TariWorld, na
             ordering: None,
             filter_index: None,
             filter: None,
+            cursor: None,
         })
         .await
         .unwrap();
@@ -549,6 +551,7 @@ async fn then_validator_node_switches_epoch(world: &mut TariWorld, vn_name: Stri
                 ordering: None,
                 filter_index: Some(1),
                 filter: Some(epoch.to_string()),
+                cursor: None,
             })
             .await
             .unwrap();
@@ -35,6 +35,7 @@ pub use tari_swarm::{
     config::{Config as SwarmConfig, LimitPerInterval, RelayCircuitLimits, RelayReservationLimits},
     identity::PeerId,
     is_supported_multiaddr,
+    messaging::Compression as MessagingCompression,
     swarm::{dial_opts::DialOpts, DialError},
 };
 
@@ -0,0 +1,155 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::io::{self, Read};
+
+use libp2p::{
+    futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    StreamProtocol,
+};
+
+/// Suffix appended to the messaging protocol name for streams that carry compressed messages. Peers that do not support
+/// compression only support the plain protocol, so protocol negotiation falls back to uncompressed messages.
+const COMPRESSED_PROTOCOL_SUFFIX: &str = "/zstd";
+
+/// Message compression settings
+#[derive(Debug, Clone, Copy)]
+pub struct Compression {
+    /// The zstd compression level (1-22). Higher levels trade CPU time for smaller messages.
+    pub level: i32,
+    /// The maximum size of a decompressed message. Messages that expand beyond this are rejected, which protects
+    /// against decompression bombs.
+    pub max_decompressed_size: usize,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self {
+            level: 3,
+            max_decompressed_size: 4 * 1024 * 1024 + 4,
+        }
+    }
+}
+
+pub(crate) fn compressed_protocol(protocol: &StreamProtocol) -> StreamProtocol {
+    StreamProtocol::try_from_owned(format!("{}{}", protocol, COMPRESSED_PROTOCOL_SUFFIX))
+        .expect("appending a suffix to a valid protocol is always valid")
+}
+
+/// Compresses an encoded message and writes it as a single length-prefixed frame.
+pub(crate) async fn write_compressed<W>(writer: &mut W, encoded: &[u8], compression: &Compression) -> io::Result<()>
+where W: AsyncWrite + Unpin + Send {
+    let compressed = zstd::bulk::compress(encoded, compression.level)?;
+    let len = u32::try_from(compressed.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "message too large"))?;
+    writer.write_all(&len.to_be_bytes()).await?;
+    writer.write_all(&compressed).await?;
+    Ok(())
+}
+
+/// Reads a frame written by [write_compressed] and decompresses it. Returns the number of bytes read from the wire and
+/// the encoded message.
+pub(crate) async fn read_compressed<R>(reader: &mut R, compression: &Compression) -> io::Result<(usize, Vec<u8>)>
+where R: AsyncRead + Unpin + Send {
+    let mut len_buf = [0u8; 4];
+    reader.read_exact(&mut len_buf).await?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    // A compressed message that is larger than the decompressed limit cannot be valid
+    if len > compression.max_decompressed_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"));
+    }
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).await?;
+    let decompressed = decompress(&buf, compression.max_decompressed_size)?;
+    Ok((len + len_buf.len(), decompressed))
+}
+
+/// Decompresses a message, failing if it expands beyond `max_size`. The output buffer grows as the message is
+/// decompressed rather than being allocated at the maximum size up front.
+fn decompress(compressed: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+    let decoder = zstd::stream::read::Decoder::with_buffer(compressed)?;
+    let mut decompressed = Vec::new();
+    // Read one byte more than the limit to detect messages that exceed it
+    decoder
+        .take((max_size as u64).saturating_add(1))
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decompressed message exceeds the maximum size of {} byte(s)", max_size),
+        ));
+    }
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{executor::block_on, io::Cursor};
+
+    use super::*;
+
+    fn compression(max_decompressed_size: usize) -> Compression {
+        Compression {
+            max_decompressed_size,
+            ..Default::default()
+        }
+    }
+
+    fn write_frame(message: &[u8], compression: &Compression) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+        block_on(write_compressed(&mut writer, message, compression)).unwrap();
+        writer.into_inner()
+    }
+
+    #[test]
+    fn it_round_trips_messages() {
+        let compression = Compression::default();
+        let message = b"tari".repeat(1024);
+        let frame = write_frame(&message, &compression);
+        assert!(frame.len() < message.len());
+
+        let (num_read, decompressed) = block_on(read_compressed(&mut Cursor::new(&frame), &compression)).unwrap();
+        assert_eq!(num_read, frame.len());
+        assert_eq!(decompressed, message);
+    }
+
+    #[test]
+    fn it_accepts_messages_of_exactly_the_maximum_size() {
+        let compression = compression(1024);
+        let message = vec![0u8; 1024];
+        let frame = write_frame(&message, &compression);
+
+        let (_, decompressed) = block_on(read_compressed(&mut Cursor::new(&frame), &compression)).unwrap();
+        assert_eq!(decompressed, message);
+    }
+
+    #[test]
+    fn it_rejects_messages_that_decompress_beyond_the_maximum_size() {
+        let compression = compression(1024);
+        // Highly compressible, so the frame itself is well within the limit
+        let frame = write_frame(&vec![0u8; 1024 * 1024], &compression);
+        assert!(frame.len() < 1024);
+
+        let err = block_on(read_compressed(&mut Cursor::new(&frame), &compression)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn it_rejects_frames_larger_than_the_maximum_size() {
+        let compression = compression(16);
+        let mut frame = 17u32.to_be_bytes().to_vec();
+        frame.extend_from_slice(&[0u8; 17]);
+
+        let err = block_on(read_compressed(&mut Cursor::new(&frame), &compression)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn it_rejects_invalid_compressed_data() {
+        let compression = Compression::default();
+        let mut frame = 4u32.to_be_bytes().to_vec();
+        frame.extend_from_slice(b"tari");
+
+        block_on(read_compressed(&mut Cursor::new(&frame), &compression)).unwrap_err();
+    }
+}
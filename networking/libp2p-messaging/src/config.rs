@@ -3,11 +3,15 @@
 
 use std::time::Duration;
 
+use crate::Compression;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub max_concurrent_streams_per_peer: usize,
     pub send_recv_timeout: Duration,
     pub inbound_message_buffer_size: usize,
+    /// Compression for messages sent to peers that support it. None disables compression.
+    pub compression: Option<Compression>,
 }
 
 impl Default for Config {
@@ -16,6 +20,7 @@ impl Default for Config {
             max_concurrent_streams_per_peer: 3,
             send_recv_timeout: Duration::from_secs(10),
             inbound_message_buffer_size: 10,
+            compression: Some(Compression::default()),
         }
     }
 }
@@ -32,9 +32,12 @@ use libp2p::{
     Stream,
     StreamProtocol,
 };
+use smallvec::{smallvec, SmallVec};
 
 use crate::{
     codec::Codec,
+    compression,
+    compression::Compression,
     error::Error,
     event::Event,
     stream::MessageStream,
@@ -46,6 +49,8 @@ use crate::{
 pub struct Handler<TCodec: Codec> {
     peer_id: PeerId,
     protocol: StreamProtocol,
+    compressed_protocol: Option<StreamProtocol>,
+    compression: Option<Compression>,
     requested_stream: Option<MessageStream<TCodec::Message>>,
     pending_stream: Option<MessageStream<TCodec::Message>>,
     pending_events: VecDeque<Event<TCodec::Message>>,
@@ -60,6 +65,11 @@ impl<TCodec: Codec> Handler<TCodec> {
         let (pending_events_sender, pending_events_receiver) = mpsc::channel(20);
         Self {
             peer_id,
+            compressed_protocol: config
+                .compression
+                .as_ref()
+                .map(|_| compression::compressed_protocol(&protocol)),
+            compression: config.compression,
             protocol,
             requested_stream: None,
             pending_stream: None,
@@ -78,6 +88,24 @@ impl<TCodec: Codec> Handler<TCodec> {
 impl<TCodec> Handler<TCodec>
 where TCodec: Codec + Send + Clone + 'static
 {
+    /// Returns the supported protocols in order of preference
+    fn protocol(&self) -> Protocol<StreamProtocol> {
+        let protocols = match self.compressed_protocol {
+            Some(ref compressed) => smallvec![compressed.clone(), self.protocol.clone()],
+            None => smallvec![self.protocol.clone()],
+        };
+        Protocol { protocols }
+    }
+
+    /// Returns the compression settings if the negotiated protocol is the compressed variant
+    fn negotiated_compression(&self, protocol: &StreamProtocol) -> Option<Compression> {
+        if self.compressed_protocol.as_ref() == Some(protocol) {
+            self.compression
+        } else {
+            None
+        }
+    }
+
     fn on_listen_upgrade_error(&self, error: ListenUpgradeError<(), Protocol<StreamProtocol>>) {
         tracing::warn!("unexpected listen upgrade error: {:?}", error.error);
     }
@@ -121,7 +149,8 @@ where TCodec: Codec + Send + Clone + 'static
 
     fn on_fully_negotiated_outbound(&mut self, outbound: FullyNegotiatedOutbound<Protocol<StreamProtocol>, ()>) {
         let codec = self.codec.clone();
-        let (mut peer_stream, _protocol) = outbound.protocol;
+        let (mut peer_stream, protocol) = outbound.protocol;
+        let compression = self.negotiated_compression(&protocol);
 
         let mut msg_stream = self
             .requested_stream
@@ -144,7 +173,18 @@ where TCodec: Codec + Send + Clone + 'static
                     break Event::StreamClosed { peer_id, stream_id };
                 };
 
-                match codec.encode_to(&mut peer_stream, msg).await {
+                let result = match compression {
+                    Some(ref compression) => {
+                        let mut encoded = Vec::new();
+                        match codec.encode_to(&mut encoded, msg).await {
+                            Ok(()) => compression::write_compressed(&mut peer_stream, &encoded, compression).await,
+                            Err(e) => Err(e),
+                        }
+                    },
+                    None => codec.encode_to(&mut peer_stream, msg).await,
+                };
+
+                match result {
                     Ok(()) => {
                         events
                             .send(Event::MessageSent { message_id, stream_id })
@@ -169,7 +209,8 @@ where TCodec: Codec + Send + Clone + 'static
     fn on_fully_negotiated_inbound(&mut self, inbound: FullyNegotiatedInbound<Protocol<StreamProtocol>, ()>) {
         let codec = self.codec.clone();
         let peer_id = self.peer_id;
-        let (mut stream, _protocol) = inbound.protocol;
+        let (mut stream, protocol) = inbound.protocol;
+        let compression = self.negotiated_compression(&protocol);
         let mut events = self.pending_events_sender.clone();
 
         self.pending_events.push_back(Event::InboundStreamOpened { peer_id });
@@ -177,7 +218,18 @@ where TCodec: Codec + Send + Clone + 'static
         let fut = async move {
             loop {
                 // TODO: read timeout
-                match codec.decode_from(&mut stream).await {
+                let result = match compression {
+                    Some(ref compression) => match compression::read_compressed(&mut stream, compression).await {
+                        Ok((length, decompressed)) => codec
+                            .decode_from(&mut decompressed.as_slice())
+                            .await
+                            .map(|(_, msg)| (length, msg)),
+                        Err(e) => Err(e),
+                    },
+                    None => codec.decode_from(&mut stream).await,
+                };
+
+                match result {
                     Ok((length, msg)) => {
                         events
                             .send(Event::ReceivedMessage {
@@ -218,12 +270,7 @@ where TCodec: Codec + Send + Clone + 'static
     type ToBehaviour = Event<TCodec::Message>;
 
     fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
-        SubstreamProtocol::new(
-            Protocol {
-                protocol: self.protocol.clone(),
-            },
-            (),
-        )
+        SubstreamProtocol::new(self.protocol(), ())
     }
 
     fn poll(
@@ -260,12 +307,7 @@ where TCodec: Codec + Send + Clone + 'static
             self.requested_stream = Some(stream);
 
             return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
-                protocol: SubstreamProtocol::new(
-                    Protocol {
-                        protocol: self.protocol.clone(),
-                    },
-                    (),
-                ),
+                protocol: SubstreamProtocol::new(self.protocol(), ()),
             });
         }
 
@@ -302,17 +344,17 @@ where TCodec: Codec + Send + Clone + 'static
 }
 
 pub struct Protocol<P> {
-    pub(crate) protocol: P,
+    pub(crate) protocols: SmallVec<[P; 2]>,
 }
 
 impl<P> UpgradeInfo for Protocol<P>
 where P: AsRef<str> + Clone
 {
     type Info = P;
-    type InfoIter = std::option::IntoIter<Self::Info>;
+    type InfoIter = smallvec::IntoIter<[Self::Info; 2]>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        Some(self.protocol.clone()).into_iter()
+        self.protocols.clone().into_iter()
     }
 }
 
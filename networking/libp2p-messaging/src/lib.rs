@@ -3,6 +3,7 @@
 
 mod behaviour;
 mod codec;
+mod compression;
 mod config;
 pub mod error;
 mod event;
@@ -12,6 +13,7 @@ mod stream;
 
 pub use behaviour::*;
 pub use codec::*;
+pub use compression::Compression;
 pub use config::*;
 pub use error::Error;
 pub use event::*;
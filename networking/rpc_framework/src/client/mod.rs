@@ -28,6 +28,7 @@
 
 #[cfg(feature = "metrics")]
 mod metrics;
+mod multiplexed;
 
 use std::{
     convert::TryFrom,
@@ -63,12 +64,15 @@ use tracing::{span, Instrument, Level};
 use super::message::RpcMethod;
 use crate::{
     body::ClientStreaming,
+    compression::PayloadCompressor,
     framing::CanonicalFraming,
+    handshake::{RPC_VERSION_MULTIPLEXED, SUPPORTED_RPC_VERSIONS},
     message::{BaseRequest, RpcMessageFlags},
     proto,
     Handshake,
     NamedProtocolService,
     Response,
+    RpcCompressionConfig,
     RpcError,
     RpcHandshakeError,
     RpcServerError,
@@ -247,6 +251,30 @@ impl<TClient> RpcClientBuilder<TClient> {
         self
     }
 
+    /// Offer request multiplexing (RPC v1) in the handshake. If the server accepts, concurrent requests made using
+    /// clones of this client share the same session instead of being queued.
+    /// Default: true
+    pub fn with_multiplexing(mut self, is_enabled: bool) -> Self {
+        self.config.multiplexing = is_enabled;
+        self
+    }
+
+    /// Set the payload compression to offer in the handshake, or None to disable compression. Compression is only
+    /// used on multiplexed sessions.
+    /// Default: zstd with the default [RpcCompressionConfig]
+    pub fn with_compression(mut self, compression: Option<RpcCompressionConfig>) -> Self {
+        self.config.compression = compression;
+        self
+    }
+
+    /// Set the maximum number of requests that may be in flight at once on a multiplexed session. Further requests
+    /// are queued until an in-flight request completes. This has no effect if the session is not multiplexed.
+    /// Default: 32
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.config.max_concurrent_requests = limit;
+        self
+    }
+
     /// Set the protocol ID associated with this client. This is used for logging purposes only.
     pub fn with_protocol_id(mut self, protocol_id: StreamProtocol) -> Self {
         self.protocol_id = Some(protocol_id);
@@ -279,6 +307,9 @@ pub struct RpcClientConfig {
     pub deadline: Option<Duration>,
     pub deadline_grace_period: Duration,
     pub handshake_timeout: Duration,
+    pub multiplexing: bool,
+    pub max_concurrent_requests: usize,
+    pub compression: Option<RpcCompressionConfig>,
 }
 
 impl RpcClientConfig {
@@ -299,6 +330,9 @@ impl Default for RpcClientConfig {
             deadline: Some(Duration::from_secs(120)),
             deadline_grace_period: Duration::from_secs(60),
             handshake_timeout: Duration::from_secs(90),
+            multiplexing: true,
+            max_concurrent_requests: 32,
+            compression: Some(RpcCompressionConfig::default()),
         }
     }
 }
@@ -390,6 +424,7 @@ struct RpcClientWorker<TSubstream> {
     ready_tx: Option<oneshot::Sender<Result<(), RpcError>>>,
     protocol_id: StreamProtocol,
     shutdown_signal: ShutdownSignal,
+    compressor: Option<PayloadCompressor>,
 }
 
 impl<TSubstream> RpcClientWorker<TSubstream>
@@ -415,6 +450,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
             last_request_latency_tx,
             protocol_id,
             shutdown_signal,
+            compressor: None,
         }
     }
 
@@ -429,22 +465,38 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
             self.protocol_name()
         );
         let start = Instant::now();
-        let mut handshake = Handshake::new(&mut self.framed).with_timeout(self.config.handshake_timeout());
-        match handshake.perform_client_handshake().await {
-            Ok(_) => {
+        let supported_versions: &'static [u32] = if self.config.multiplexing {
+            SUPPORTED_RPC_VERSIONS
+        } else {
+            &[0]
+        };
+        let mut handshake = Handshake::new(&mut self.framed)
+            .with_timeout(self.config.handshake_timeout())
+            .with_supported_versions(supported_versions)
+            .with_compression(self.config.compression.is_some());
+        let version = match handshake.perform_client_handshake().await {
+            Ok(session) => {
                 let latency = start.elapsed();
                 debug!(
                     target: LOG_TARGET,
-                    "RPC Session ({}) negotiation completed. Latency: {:.0?}",
+                    "RPC Session ({}) v{} (compressed: {}) negotiation completed. Latency: {:.0?}",
                     self.protocol_name(),
+                    session.version,
+                    session.is_compressed,
                     latency
                 );
+                self.compressor = self
+                    .config
+                    .compression
+                    .filter(|_| session.is_compressed)
+                    .map(PayloadCompressor::new);
                 let _ = self.last_request_latency_tx.send(Some(latency));
                 if let Some(r) = self.ready_tx.take() {
                     let _result = r.send(Ok(()));
                 }
                 #[cfg(feature = "metrics")]
                 metrics::handshake_counter(&self.peer_id, &self.protocol_id).inc();
+                session.version
             },
             Err(err) => {
                 #[cfg(feature = "metrics")]
@@ -455,10 +507,37 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
 
                 return;
             },
-        }
+        };
 
         #[cfg(feature = "metrics")]
         metrics::num_sessions(&self.peer_id, &self.protocol_id).inc();
+        if version >= RPC_VERSION_MULTIPLEXED {
+            self.run_multiplexed().await;
+        } else {
+            self.run_sequential().await;
+        }
+        #[cfg(feature = "metrics")]
+        metrics::num_sessions(&self.peer_id, &self.protocol_id).dec();
+
+        if let Err(err) = self.framed.close().await {
+            debug!(
+                target: LOG_TARGET,
+                "(peer: {}) IO Error when closing substream: {}",
+                self.peer_id,
+                err
+            );
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "(peer: {}) RpcClientWorker ({}) terminated.",
+            self.peer_id,
+            self.protocol_name()
+        );
+    }
+
+    /// Runs a v0 session in which requests and responses alternate
+    async fn run_sequential(&mut self) {
         loop {
             tokio::select! {
                 // Check the futures in the order they are listed
@@ -504,24 +583,6 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
                 }
             }
         }
-        #[cfg(feature = "metrics")]
-        metrics::num_sessions(&self.peer_id, &self.protocol_id).dec();
-
-        if let Err(err) = self.framed.close().await {
-            debug!(
-                target: LOG_TARGET,
-                "(peer: {}) IO Error when closing substream: {}",
-                self.peer_id,
-                err
-            );
-        }
-
-        debug!(
-            target: LOG_TARGET,
-            "(peer: {}) RpcClientWorker ({}) terminated.",
-            self.peer_id,
-            self.protocol_name()
-        );
     }
 
     fn handle_interrupt_server_message(&self, msg: BytesMut) -> Result<(), RpcError> {
@@ -629,6 +690,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
             method,
             deadline: self.config.deadline.map(|t| t.as_secs()).unwrap_or(0),
             flags: 0,
+            window_increment: 0,
             payload: request.message.to_vec(),
         };
 
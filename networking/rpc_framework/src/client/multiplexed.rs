@@ -0,0 +1,573 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Client side of a multiplexed (v1) RPC session.
+//!
+//! Any number of requests (up to `max_concurrent_requests`) may be in flight on the session. Each response frame is
+//! routed to the request with the matching `request_id` and forwarded to the caller by a small task per request. The
+//! forwarding task enforces the response timeout and grants the server more stream credits as the caller consumes
+//! messages.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use futures::{AsyncRead, AsyncWrite, StreamExt};
+use log::*;
+use prost::Message;
+use tokio::{
+    sync::{mpsc, mpsc::error::TrySendError, oneshot},
+    time,
+};
+
+#[cfg(feature = "metrics")]
+use super::metrics;
+use super::{ClientRequest, RpcClientWorker, LOG_TARGET};
+use crate::{
+    message::RpcMessageFlags,
+    proto,
+    Response,
+    RpcError,
+    RpcServerError,
+    RpcStatus,
+    MULTIPLEXED_STREAM_WINDOW,
+};
+
+type ResponseResult = Result<Response<Bytes>, RpcStatus>;
+
+struct InFlightRequest {
+    method: u32,
+    sent_at: Instant,
+    has_response: bool,
+    responses: mpsc::Sender<ResponseResult>,
+}
+
+enum StreamEvent {
+    /// The caller has received `increment` messages, so the server may send that many more
+    WindowUpdate { request_id: u16, increment: u32 },
+    /// The caller dropped the response stream or the response timed out
+    Cancel { request_id: u16 },
+}
+
+struct MultiplexedSession {
+    in_flight: HashMap<u16, InFlightRequest>,
+    pings: HashMap<u16, oneshot::Sender<()>>,
+    events_tx: mpsc::UnboundedSender<StreamEvent>,
+    events_rx: mpsc::UnboundedReceiver<StreamEvent>,
+}
+
+impl MultiplexedSession {
+    fn new() -> Self {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        Self {
+            in_flight: HashMap::new(),
+            pings: HashMap::new(),
+            events_tx,
+            events_rx,
+        }
+    }
+
+    fn is_in_use(&self, request_id: u16) -> bool {
+        self.in_flight.contains_key(&request_id) || self.pings.contains_key(&request_id)
+    }
+}
+
+impl<TSubstream> RpcClientWorker<TSubstream>
+where TSubstream: AsyncRead + AsyncWrite + Unpin + Send
+{
+    /// Runs a v1 session in which requests are multiplexed
+    pub(super) async fn run_multiplexed(&mut self) {
+        let mut session = MultiplexedSession::new();
+        let mut is_request_channel_closed = false;
+        loop {
+            let can_send_request =
+                !is_request_channel_closed && session.in_flight.len() < self.config.max_concurrent_requests;
+
+            tokio::select! {
+                // Check the futures in the order they are listed
+                biased;
+                _ = &mut self.shutdown_signal => {
+                    break;
+                },
+                server_msg = self.framed.next() => {
+                    match server_msg {
+                        Some(Ok(msg)) => {
+                            if let Err(err) = self.route_response(&mut session, msg) {
+                                #[cfg(feature = "metrics")]
+                                metrics::client_errors(&self.peer_id, &self.protocol_id).inc();
+                                error!(target: LOG_TARGET, "(peer={}) Unexpected error: {}. Worker is terminating.", self.peer_id, err);
+                                break;
+                            }
+                        },
+                        Some(Err(err)) => {
+                            debug!(target: LOG_TARGET, "(peer={}) IO Error: {}. Worker is terminating.", self.peer_id, err);
+                            break;
+                        },
+                        None => {
+                            debug!(target: LOG_TARGET, "(peer={}) Substream closed. Worker is terminating.", self.peer_id);
+                            break;
+                        }
+                    }
+                },
+                Some(event) = session.events_rx.recv() => {
+                    if let Err(err) = self.handle_stream_event(&mut session, event).await {
+                        #[cfg(feature = "metrics")]
+                        metrics::client_errors(&self.peer_id, &self.protocol_id).inc();
+                        error!(target: LOG_TARGET, "(peer={}) Unexpected error: {}. Worker is terminating.", self.peer_id, err);
+                        break;
+                    }
+                },
+                req = self.request_rx.recv(), if can_send_request => {
+                    match req {
+                        Some(req) => {
+                            if let Err(err) = self.send_multiplexed_request(&mut session, req).await {
+                                #[cfg(feature = "metrics")]
+                                metrics::client_errors(&self.peer_id, &self.protocol_id).inc();
+                                error!(target: LOG_TARGET, "(peer={}) Unexpected error: {}. Worker is terminating.", self.peer_id, err);
+                                break;
+                            }
+                        }
+                        None => {
+                            debug!(target: LOG_TARGET, "(peer={}) Request channel closed. Completing {} in-flight request(s).", self.peer_id, session.in_flight.len());
+                            is_request_channel_closed = true;
+                        },
+                    }
+                }
+            }
+
+            if is_request_channel_closed && session.in_flight.is_empty() {
+                debug!(target: LOG_TARGET, "(peer={}) Request channel closed. Worker is terminating.", self.peer_id);
+                break;
+            }
+        }
+    }
+
+    async fn send_multiplexed_request(
+        &mut self,
+        session: &mut MultiplexedSession,
+        req: ClientRequest,
+    ) -> Result<(), RpcError> {
+        let mut request_id = self.next_request_id();
+        // Request IDs wrap around, so skip any that are still in use by a long-running request
+        while session.is_in_use(request_id) {
+            request_id = self.next_request_id();
+        }
+
+        match req {
+            ClientRequest::SendRequest { request, reply } => {
+                #[cfg(feature = "metrics")]
+                metrics::outbound_request_bytes(&self.peer_id, &self.protocol_id)
+                    .observe(request.get_ref().len() as f64);
+
+                let method = request.method.into();
+                let mut payload = request.message.to_vec();
+                let mut flags = RpcMessageFlags::empty();
+                if self.compressor.as_ref().map_or(false, |c| c.compress(&mut payload)) {
+                    flags |= RpcMessageFlags::COMPRESSED;
+                }
+                let req = proto::RpcRequest {
+                    request_id: u32::from(request_id),
+                    method,
+                    deadline: self.config.deadline.map(|t| t.as_secs()).unwrap_or(0),
+                    flags: flags.bits().into(),
+                    window_increment: 0,
+                    payload,
+                };
+                trace!(target: LOG_TARGET, "Sending multiplexed request: {}", req);
+
+                let (response_tx, response_rx) = mpsc::channel(5);
+                if let Err(mut rx) = reply.send(response_rx) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Client request was cancelled before request was sent (protocol = {})",
+                        self.protocol_name(),
+                    );
+                    rx.close();
+                    return Ok(());
+                }
+
+                if let Err(err) = self.send_request(req).await {
+                    warn!(target: LOG_TARGET, "{}", err);
+                    #[cfg(feature = "metrics")]
+                    metrics::client_errors(&self.peer_id, &self.protocol_id).inc();
+                    let _result = response_tx.send(Err(err.into())).await;
+                    return Ok(());
+                }
+
+                // The server may not send more than MULTIPLEXED_STREAM_WINDOW un-credited messages, so this channel
+                // never fills up unless the server ignores flow control
+                let (stream_tx, stream_rx) = mpsc::channel(MULTIPLEXED_STREAM_WINDOW as usize);
+                tokio::spawn(forward_responses(
+                    request_id,
+                    self.config.timeout_with_grace_period(),
+                    stream_rx,
+                    response_tx,
+                    session.events_tx.clone(),
+                ));
+                session.in_flight.insert(request_id, InFlightRequest {
+                    method,
+                    sent_at: Instant::now(),
+                    has_response: false,
+                    responses: stream_tx,
+                });
+            },
+            ClientRequest::SendPing(reply) => {
+                let ack = proto::RpcRequest {
+                    request_id: u32::from(request_id),
+                    flags: u32::from(RpcMessageFlags::ACK.bits()),
+                    deadline: self.config.deadline.map(|t| t.as_secs()).unwrap_or(0),
+                    ..Default::default()
+                };
+                self.send_request(ack).await?;
+
+                // Forget pings that have timed out
+                session.pings.retain(|_, ack_tx| !ack_tx.is_closed());
+                let (ack_tx, ack_rx) = oneshot::channel();
+                session.pings.insert(request_id, ack_tx);
+                let timeout = self.config.timeout_with_grace_period();
+                let start = Instant::now();
+                tokio::spawn(async move {
+                    let result = match timeout {
+                        Some(timeout) => time::timeout(timeout, ack_rx).await.ok(),
+                        None => Some(ack_rx.await),
+                    };
+                    match result {
+                        Some(Ok(())) => {
+                            let _result = reply.send(Ok(start.elapsed()));
+                        },
+                        // The session ended, dropping the reply cancels the ping
+                        Some(Err(_)) => {},
+                        None => {
+                            let _result = reply.send(Err(RpcStatus::timed_out("Response timed out")));
+                        },
+                    }
+                });
+            },
+        }
+
+        Ok(())
+    }
+
+    fn route_response(&mut self, session: &mut MultiplexedSession, msg: BytesMut) -> Result<(), RpcError> {
+        let mut resp = proto::RpcResponse::decode(msg.freeze())?;
+        let request_id = u16::try_from(resp.request_id)
+            .map_err(|_| RpcStatus::protocol_error(format!("invalid request_id: must be less than {}", u16::MAX)))?;
+
+        if resp.flags().map_or(false, |flags| flags.is_ack()) {
+            match session.pings.remove(&request_id) {
+                Some(ack_tx) => {
+                    let _result = ack_tx.send(());
+                },
+                None => {
+                    debug!(target: LOG_TARGET, "(peer={}) Ignoring unexpected ACK for request {}", self.peer_id, request_id);
+                },
+            }
+            return Ok(());
+        }
+
+        let Some(in_flight) = session.in_flight.get_mut(&request_id) else {
+            // The request was cancelled or timed out, the server may still send responses that were already in transit
+            debug!(
+                target: LOG_TARGET,
+                "(peer={}) Ignoring response for request {} that is no longer in flight", self.peer_id, request_id
+            );
+            return Ok(());
+        };
+
+        #[cfg(feature = "metrics")]
+        metrics::inbound_response_bytes(&self.peer_id, &self.protocol_id).observe(resp.payload.len() as f64);
+        if !in_flight.has_response {
+            in_flight.has_response = true;
+            let _ = self.last_request_latency_tx.send(Some(in_flight.sent_at.elapsed()));
+        }
+        trace!(
+            target: LOG_TARGET,
+            "Received response ({} byte(s)) from request #{} (protocol = {}, method={})",
+            resp.payload.len(),
+            request_id,
+            self.protocol_name(),
+            in_flight.method,
+        );
+
+        let is_compressed = resp.flags().map_or(false, |flags| flags.is_compressed());
+        if is_compressed {
+            if let Err(status) = self.decompress_payload(&mut resp) {
+                warn!(
+                    target: LOG_TARGET,
+                    "(peer={}) Request {} failed: {}", self.peer_id, request_id, status
+                );
+                let _result = in_flight.responses.try_send(Err(status));
+                // Remove the request and tell the server to stop sending responses
+                let _result = session.events_tx.send(StreamEvent::Cancel { request_id });
+                return Ok(());
+            }
+        }
+
+        let result = Self::convert_to_result(resp)?;
+        let is_finished = result.as_ref().map_or(true, |resp| resp.is_finished());
+        match in_flight.responses.try_send(result) {
+            Ok(()) => {},
+            Err(TrySendError::Full(_)) => {
+                return Err(RpcServerError::ProtocolError(format!(
+                    "Server sent more than {} un-credited messages for request {}",
+                    MULTIPLEXED_STREAM_WINDOW, request_id
+                ))
+                .into());
+            },
+            // The forwarding task has already ended and a cancel event is pending
+            Err(TrySendError::Closed(_)) => {},
+        }
+
+        if is_finished {
+            session.in_flight.remove(&request_id);
+        }
+
+        Ok(())
+    }
+
+    fn decompress_payload(&self, resp: &mut proto::RpcResponse) -> Result<(), RpcStatus> {
+        let compressor = self
+            .compressor
+            .as_ref()
+            .ok_or_else(|| RpcStatus::protocol_error("Received a compressed payload on an uncompressed session"))?;
+        resp.payload = compressor
+            .decompress(&resp.payload)
+            .map_err(|err| RpcStatus::protocol_error(format!("Failed to decompress payload: {}", err)))?;
+        resp.flags &= !u32::from(RpcMessageFlags::COMPRESSED.bits());
+        Ok(())
+    }
+
+    async fn handle_stream_event(
+        &mut self,
+        session: &mut MultiplexedSession,
+        event: StreamEvent,
+    ) -> Result<(), RpcError> {
+        match event {
+            StreamEvent::WindowUpdate { request_id, increment } => {
+                if !session.in_flight.contains_key(&request_id) {
+                    return Ok(());
+                }
+                let req = proto::RpcRequest {
+                    request_id: u32::from(request_id),
+                    flags: RpcMessageFlags::WINDOW_UPDATE.bits().into(),
+                    deadline: self.config.deadline.map(|d| d.as_secs()).unwrap_or(0),
+                    window_increment: increment,
+                    ..Default::default()
+                };
+                self.send_request(req).await?;
+            },
+            StreamEvent::Cancel { request_id } => {
+                if let Some(in_flight) = session.in_flight.remove(&request_id) {
+                    self.premature_close(request_id, in_flight.method).await?;
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Forwards responses for a single request to the caller, granting the server more credits as messages are consumed.
+async fn forward_responses(
+    request_id: u16,
+    timeout: Option<Duration>,
+    mut stream_rx: mpsc::Receiver<ResponseResult>,
+    response_tx: mpsc::Sender<ResponseResult>,
+    events_tx: mpsc::UnboundedSender<StreamEvent>,
+) {
+    let mut num_unacknowledged = 0;
+    loop {
+        let next_msg = async {
+            match timeout {
+                Some(timeout) => time::timeout(timeout, stream_rx.recv()).await,
+                None => Ok(stream_rx.recv().await),
+            }
+        };
+
+        let result = tokio::select! {
+            next_msg = next_msg => next_msg,
+            _ = response_tx.closed() => {
+                let _result = events_tx.send(StreamEvent::Cancel { request_id });
+                break;
+            }
+        };
+
+        let result = match result {
+            Ok(Some(result)) => result,
+            // The session has ended
+            Ok(None) => break,
+            Err(_) => {
+                debug!(target: LOG_TARGET, "Request {} timed out", request_id);
+                let _result = response_tx.send(Err(RpcStatus::timed_out("Response timed out"))).await;
+                let _result = events_tx.send(StreamEvent::Cancel { request_id });
+                break;
+            },
+        };
+
+        let is_finished = result.as_ref().map_or(true, |resp| resp.is_finished());
+        if response_tx.send(result).await.is_err() {
+            // The caller dropped the response stream before it completed
+            let _result = events_tx.send(StreamEvent::Cancel { request_id });
+            break;
+        }
+        if is_finished {
+            break;
+        }
+
+        num_unacknowledged += 1;
+        if num_unacknowledged >= MULTIPLEXED_STREAM_WINDOW / 2 {
+            let _result = events_tx.send(StreamEvent::WindowUpdate {
+                request_id,
+                increment: num_unacknowledged,
+            });
+            num_unacknowledged = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::SinkExt;
+    use libp2p::{PeerId, StreamProtocol};
+    use tari_shutdown::Shutdown;
+    use tokio::{io::DuplexStream, sync::watch};
+    use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+    use super::*;
+    use crate::{
+        client::RpcClientConfig,
+        framing,
+        framing::CanonicalFraming,
+        message::BaseRequest,
+        RPC_MAX_FRAME_SIZE,
+    };
+
+    type TestFraming = CanonicalFraming<Compat<DuplexStream>>;
+
+    struct TestClient {
+        request_tx: mpsc::Sender<ClientRequest>,
+        // Shuts the worker down when the test ends
+        _shutdown: Shutdown,
+    }
+
+    impl TestClient {
+        async fn request(&self, method: u32, payload: &'static [u8]) -> mpsc::Receiver<ResponseResult> {
+            let (reply, reply_rx) = oneshot::channel();
+            self.request_tx
+                .send(ClientRequest::SendRequest {
+                    request: BaseRequest::new(method.into(), Bytes::from_static(payload)),
+                    reply,
+                })
+                .await
+                .unwrap();
+            reply_rx.await.unwrap()
+        }
+    }
+
+    /// Starts a multiplexed client worker and returns the server end of the substream
+    fn start_worker() -> (TestClient, TestFraming) {
+        let (client, server) = tokio::io::duplex(1024 * 1024);
+        let (request_tx, request_rx) = mpsc::channel(10);
+        let (last_request_latency_tx, _) = watch::channel(None);
+        let (ready_tx, _) = oneshot::channel();
+        let shutdown = Shutdown::new();
+        let mut worker = RpcClientWorker::new(
+            RpcClientConfig::default(),
+            PeerId::random(),
+            request_rx,
+            last_request_latency_tx,
+            framing::canonical(client.compat(), RPC_MAX_FRAME_SIZE),
+            ready_tx,
+            StreamProtocol::new("/test/rpc/1.0"),
+            shutdown.to_signal(),
+        );
+        tokio::spawn(async move { worker.run_multiplexed().await });
+        (
+            TestClient {
+                request_tx,
+                _shutdown: shutdown,
+            },
+            framing::canonical(server.compat(), RPC_MAX_FRAME_SIZE),
+        )
+    }
+
+    async fn next_request(framed: &mut TestFraming) -> proto::RpcRequest {
+        let frame = time::timeout(Duration::from_secs(5), framed.next())
+            .await
+            .expect("timed out waiting for a request")
+            .expect("session closed")
+            .unwrap();
+        proto::RpcRequest::decode(frame.freeze()).unwrap()
+    }
+
+    async fn send_response(framed: &mut TestFraming, request_id: u32, flags: RpcMessageFlags, payload: &[u8]) {
+        let resp = proto::RpcResponse {
+            request_id,
+            status: RpcStatus::ok().as_code(),
+            flags: flags.bits().into(),
+            payload: payload.to_vec(),
+        };
+        framed.send(resp.encode_to_vec().into()).await.unwrap();
+    }
+
+    async fn next_response(responses: &mut mpsc::Receiver<ResponseResult>) -> Response<Bytes> {
+        time::timeout(Duration::from_secs(5), responses.recv())
+            .await
+            .expect("timed out waiting for a response")
+            .expect("response stream closed")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_routes_interleaved_responses_to_the_matching_request() {
+        let (client, mut server) = start_worker();
+        let mut responses1 = client.request(1, b"first").await;
+        let req1 = next_request(&mut server).await;
+        let mut responses2 = client.request(2, b"second").await;
+        let req2 = next_request(&mut server).await;
+        assert_ne!(req1.request_id, req2.request_id);
+        assert_eq!(req1.payload, b"first");
+        assert_eq!(req2.payload, b"second");
+
+        send_response(&mut server, req2.request_id, RpcMessageFlags::FIN, b"reply2").await;
+        send_response(&mut server, req1.request_id, RpcMessageFlags::FIN, b"reply1").await;
+
+        let resp = next_response(&mut responses2).await;
+        assert_eq!(resp.into_message(), Bytes::from_static(b"reply2"));
+        let resp = next_response(&mut responses1).await;
+        assert_eq!(resp.into_message(), Bytes::from_static(b"reply1"));
+    }
+
+    #[tokio::test]
+    async fn it_sends_fin_when_the_caller_drops_the_response_stream() {
+        let (client, mut server) = start_worker();
+        let responses = client.request(1, b"").await;
+        let req = next_request(&mut server).await;
+        drop(responses);
+
+        let cancel = next_request(&mut server).await;
+        assert_eq!(cancel.request_id, req.request_id);
+        assert!(cancel.flags().unwrap().is_fin());
+    }
+
+    #[tokio::test]
+    async fn it_grants_credits_as_the_caller_consumes_messages() {
+        let (client, mut server) = start_worker();
+        let mut responses = client.request(1, b"").await;
+        let req = next_request(&mut server).await;
+
+        for _ in 0..MULTIPLEXED_STREAM_WINDOW {
+            send_response(&mut server, req.request_id, RpcMessageFlags::empty(), b"item").await;
+        }
+        for _ in 0..MULTIPLEXED_STREAM_WINDOW / 2 {
+            next_response(&mut responses).await;
+        }
+
+        let update = next_request(&mut server).await;
+        assert_eq!(update.request_id, req.request_id);
+        assert!(update.flags().unwrap().is_window_update());
+        assert_eq!(update.window_increment, MULTIPLEXED_STREAM_WINDOW / 2);
+    }
+}
@@ -0,0 +1,149 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::io::{self, Read};
+
+use log::*;
+
+use crate::RPC_MAX_FRAME_SIZE;
+
+const LOG_TARGET: &str = "comms::rpc::compression";
+
+/// Payload compression settings. Compression is negotiated in the RPC handshake and is only used if both sides support
+/// it. Each side compresses using its own level and enforces its own decompressed size limit.
+#[derive(Debug, Clone, Copy)]
+pub struct RpcCompressionConfig {
+    /// The zstd compression level (1-22). Higher levels trade CPU time for smaller payloads.
+    pub level: i32,
+    /// Payloads smaller than this are sent uncompressed
+    pub min_payload_size: usize,
+    /// The maximum size of a decompressed payload. Compressed payloads that expand beyond this are rejected, which
+    /// protects against decompression bombs.
+    pub max_decompressed_size: usize,
+}
+
+impl Default for RpcCompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: 3,
+            min_payload_size: 1024,
+            max_decompressed_size: 4 * RPC_MAX_FRAME_SIZE,
+        }
+    }
+}
+
+/// Compresses and decompresses message payloads for a session that negotiated compression
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PayloadCompressor {
+    config: RpcCompressionConfig,
+}
+
+impl PayloadCompressor {
+    pub fn new(config: RpcCompressionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Compresses the payload in place if it is large enough to benefit. Returns true if the payload was compressed.
+    pub fn compress(&self, payload: &mut Vec<u8>) -> bool {
+        if payload.len() < self.config.min_payload_size {
+            return false;
+        }
+        match zstd::bulk::compress(payload, self.config.level) {
+            Ok(compressed) if compressed.len() < payload.len() => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Compressed payload from {} to {} byte(s)",
+                    payload.len(),
+                    compressed.len()
+                );
+                *payload = compressed;
+                true
+            },
+            // Incompressible payload
+            Ok(_) => false,
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Failed to compress payload: {}. Sending it uncompressed.", err);
+                false
+            },
+        }
+    }
+
+    /// Decompresses the payload, failing if it expands beyond the configured maximum size. The output buffer grows
+    /// as the payload is decompressed rather than being allocated at the maximum size up front.
+    pub fn decompress(&self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        let max_size = self.config.max_decompressed_size;
+        let decoder = zstd::stream::read::Decoder::with_buffer(payload)?;
+        let mut decompressed = Vec::new();
+        // Read one byte more than the limit to detect payloads that exceed it
+        decoder
+            .take((max_size as u64).saturating_add(1))
+            .read_to_end(&mut decompressed)?;
+        if decompressed.len() > max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("decompressed payload exceeds the maximum size of {} byte(s)", max_size),
+            ));
+        }
+        Ok(decompressed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_compressible_payloads() {
+        let compressor = PayloadCompressor::new(RpcCompressionConfig::default());
+        let original = b"tari".repeat(1024);
+        let mut payload = original.clone();
+        assert!(compressor.compress(&mut payload));
+        assert!(payload.len() < original.len());
+        assert_eq!(compressor.decompress(&payload).unwrap(), original);
+    }
+
+    #[test]
+    fn it_does_not_compress_small_payloads() {
+        let compressor = PayloadCompressor::new(RpcCompressionConfig::default());
+        let mut payload = b"tari".to_vec();
+        assert!(!compressor.compress(&mut payload));
+        assert_eq!(payload, b"tari");
+    }
+
+    #[test]
+    fn it_rejects_payloads_that_exceed_the_decompressed_size_limit() {
+        let compressor = PayloadCompressor::new(RpcCompressionConfig::default());
+        let mut payload = vec![0u8; 1024 * 1024];
+        assert!(compressor.compress(&mut payload));
+
+        let limited = PayloadCompressor::new(RpcCompressionConfig {
+            max_decompressed_size: 1024,
+            ..Default::default()
+        });
+        let err = limited.decompress(&payload).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Exactly at the limit is allowed
+        let limited = PayloadCompressor::new(RpcCompressionConfig {
+            max_decompressed_size: 1024 * 1024,
+            ..Default::default()
+        });
+        assert_eq!(limited.decompress(&payload).unwrap().len(), 1024 * 1024);
+    }
+
+    #[test]
+    fn it_does_not_allocate_the_maximum_size_up_front() {
+        let original = b"tari".repeat(1024);
+        let mut payload = original.clone();
+        assert!(PayloadCompressor::new(RpcCompressionConfig::default()).compress(&mut payload));
+
+        // Allocating the limit would fail
+        let compressor = PayloadCompressor::new(RpcCompressionConfig {
+            max_decompressed_size: usize::MAX,
+            ..Default::default()
+        });
+        let decompressed = compressor.decompress(&payload).unwrap();
+        assert_eq!(decompressed, original);
+        assert!(decompressed.capacity() < 1024 * 1024);
+    }
+}
@@ -3,19 +3,46 @@
 
 use std::{io, time::Duration};
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use futures::{AsyncRead, AsyncWrite, SinkExt, StreamExt};
 use prost::{DecodeError, Message};
 use tokio::time;
 use tracing::{debug, error, span, warn, Instrument, Level};
 
-use crate::{error::HandshakeRejectReason, framing::CanonicalFraming, proto};
+use crate::{error::HandshakeRejectReason, framing::CanonicalFraming, message::RpcMessageFlags, proto};
 
 const LOG_TARGET: &str = "comms::rpc::handshake";
 
-/// Supported RPC protocol versions.
-/// Currently only v0 is supported
-pub(super) const SUPPORTED_RPC_VERSIONS: &[u32] = &[0];
+/// Supported RPC protocol versions in order of preference.
+/// - v0: requests and responses alternate on the session
+/// - v1: requests are multiplexed over the session, see [RPC_VERSION_MULTIPLEXED]
+pub(super) const SUPPORTED_RPC_VERSIONS: &[u32] = &[RPC_VERSION_MULTIPLEXED, 0];
+
+/// RPC protocol version in which concurrent requests are multiplexed over a single session. Response frames are
+/// matched to requests by `request_id`, requests can be individually cancelled and streaming responses are subject to
+/// per-request flow control.
+pub(super) const RPC_VERSION_MULTIPLEXED: u32 = 1;
+
+/// The deadline sent with the ping that follows a client handshake
+const VERSION_PROBE_DEADLINE: Duration = Duration::from_secs(30);
+
+/// The session parameters agreed in the handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegotiatedSession {
+    /// The RPC protocol version
+    pub version: u32,
+    /// True if message payloads may be compressed
+    pub is_compressed: bool,
+}
+
+impl NegotiatedSession {
+    fn uncompressed(version: u32) -> Self {
+        Self {
+            version,
+            is_compressed: false,
+        }
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum RpcHandshakeError {
@@ -39,6 +66,8 @@ pub enum RpcHandshakeError {
 pub struct Handshake<'a, T> {
     framed: &'a mut CanonicalFraming<T>,
     timeout: Option<Duration>,
+    supported_versions: &'static [u32],
+    is_compression_supported: bool,
 }
 
 impl<'a, T> Handshake<'a, T>
@@ -46,7 +75,12 @@ where T: AsyncRead + AsyncWrite + Unpin
 {
     /// Create a Handshake using the given framing and no timeout. To set a timeout, use `with_timeout`.
     pub fn new(framed: &'a mut CanonicalFraming<T>) -> Self {
-        Self { framed, timeout: None }
+        Self {
+            framed,
+            timeout: None,
+            supported_versions: SUPPORTED_RPC_VERSIONS,
+            is_compression_supported: false,
+        }
     }
 
     /// Set the length of time that a client/server should wait for the other side to respond before timing out.
@@ -55,8 +89,20 @@ where T: AsyncRead + AsyncWrite + Unpin
         self
     }
 
+    /// Set the RPC versions offered by a client, in order of preference. Defaults to all supported versions.
+    pub fn with_supported_versions(mut self, supported_versions: &'static [u32]) -> Self {
+        self.supported_versions = supported_versions;
+        self
+    }
+
+    /// Offer (client) or accept (server) payload compression. Compression requires a v1 or later session.
+    pub fn with_compression(mut self, is_supported: bool) -> Self {
+        self.is_compression_supported = is_supported;
+        self
+    }
+
     /// Server-side handshake protocol
-    pub async fn perform_server_handshake(&mut self) -> Result<u32, RpcHandshakeError> {
+    pub async fn perform_server_handshake(&mut self) -> Result<NegotiatedSession, RpcHandshakeError> {
         match self.recv_next_frame().await {
             Ok(Some(Ok(msg))) => {
                 let msg = proto::RpcSession::decode(&mut msg.freeze())?;
@@ -65,13 +111,30 @@ where T: AsyncRead + AsyncWrite + Unpin
                     .find(|v| msg.supported_versions.contains(v));
                 if let Some(version) = version {
                     debug!(target: LOG_TARGET, "Server accepted version: {}", version);
-                    // let reply = proto::RpcSessionReply {
-                    //     session_result: Some(proto::rpc_session_reply::SessionResult::AcceptedVersion(*version)),
-                    //     ..Default::default()
-                    // };
-                    // let span = span!(Level::INFO, "rpc::server::handshake::send_accept_version_reply");
-                    // self.framed.send(reply.encode_to_vec().into()).instrument(span).await?;
-                    return Ok(*version);
+                    // v0 clients do not expect a reply when the session is accepted
+                    if *version < RPC_VERSION_MULTIPLEXED {
+                        return Ok(NegotiatedSession::uncompressed(*version));
+                    }
+
+                    let is_compressed = self.is_compression_supported &&
+                        msg.supported_compression
+                            .contains(&(proto::CompressionType::Zstd as i32));
+                    let compression = if is_compressed {
+                        proto::CompressionType::Zstd
+                    } else {
+                        proto::CompressionType::None
+                    };
+                    let reply = proto::RpcSessionReply {
+                        session_result: Some(proto::rpc_session_reply::SessionResult::AcceptedVersion(*version)),
+                        compression: compression as i32,
+                        ..Default::default()
+                    };
+                    let span = span!(Level::INFO, "rpc::server::handshake::send_accept_version_reply");
+                    self.framed.send(reply.encode_to_vec().into()).instrument(span).await?;
+                    return Ok(NegotiatedSession {
+                        version: *version,
+                        is_compressed,
+                    });
                 }
 
                 let span = span!(Level::INFO, "rpc::server::handshake::send_rejection");
@@ -100,16 +163,24 @@ where T: AsyncRead + AsyncWrite + Unpin
         let reply = proto::RpcSessionReply {
             session_result: Some(proto::rpc_session_reply::SessionResult::Rejected(true)),
             reject_reason: reject_reason.as_i32(),
+            ..Default::default()
         };
         self.framed.send(reply.encode_to_vec().into()).await?;
         self.framed.close().await?;
         Ok(())
     }
 
-    /// Client-side handshake protocol
-    pub async fn perform_client_handshake(&mut self) -> Result<(), RpcHandshakeError> {
+    /// Client-side handshake protocol. Returns the session parameters selected by the server.
+    pub async fn perform_client_handshake(&mut self) -> Result<NegotiatedSession, RpcHandshakeError> {
+        let is_multiplexing_supported = self.supported_versions.contains(&RPC_VERSION_MULTIPLEXED);
+        let supported_compression = if self.is_compression_supported && is_multiplexing_supported {
+            vec![proto::CompressionType::Zstd as i32]
+        } else {
+            vec![]
+        };
         let msg = proto::RpcSession {
-            supported_versions: SUPPORTED_RPC_VERSIONS.to_vec(),
+            supported_versions: self.supported_versions.to_vec(),
+            supported_compression,
         };
         let payload = msg.encode_to_vec();
         debug!(target: LOG_TARGET, "Sending client handshake ({} bytes)", payload.len());
@@ -123,27 +194,60 @@ where T: AsyncRead + AsyncWrite + Unpin
             );
         }
         self.framed.flush().await?;
-        // match self.recv_next_frame().await {
-        //     Ok(Some(Ok(msg))) => {
-        //         let msg = proto::RpcSessionReply::decode(&mut msg.freeze())?;
-        //         let version = msg.result()?;
-        //         debug!(target: LOG_TARGET, "Server accepted version {}", version);
-        //         Ok(())
-        //     },
-        //     Ok(Some(Err(err))) => {
-        //         error!(target: LOG_TARGET, "Error during handshake: {}", err);
-        //         Err(err.into())
-        //     },
-        //     Ok(None) => {
-        //         error!(target: LOG_TARGET, "Error during handshake, server closed connection");
-        //         Err(RpcHandshakeError::ServerClosedRequest)
-        //     },
-        //     Err(_) => {
-        //         error!(target: LOG_TARGET, "Error during handshake, timed out");
-        //         Err(RpcHandshakeError::TimedOut)
-        //     },
-        // }
-        Ok(())
+
+        if !is_multiplexing_supported {
+            return Ok(NegotiatedSession::uncompressed(0));
+        }
+
+        // A server that selects v0 accepts the session without replying. Rather than waiting for a reply that may
+        // never come, we follow the handshake with a ping. A server that selects a later version replies to the
+        // handshake before it replies to the ping.
+        let ping = proto::RpcRequest {
+            flags: u32::from(RpcMessageFlags::ACK.bits()),
+            deadline: VERSION_PROBE_DEADLINE.as_secs(),
+            ..Default::default()
+        };
+        self.framed.send(ping.encode_to_vec().into()).await?;
+
+        let mut session = NegotiatedSession::uncompressed(0);
+        loop {
+            match self.recv_next_frame().await {
+                Ok(Some(Ok(msg))) => {
+                    let msg = msg.freeze();
+                    if is_ping_reply(&msg) {
+                        debug!(target: LOG_TARGET, "Server accepted session {:?}", session);
+                        return Ok(session);
+                    }
+                    let reply = proto::RpcSessionReply::decode(msg)?;
+                    let version = reply.result()?;
+                    if !self.supported_versions.contains(&version) {
+                        return Err(RpcHandshakeError::Rejected(HandshakeRejectReason::UnsupportedVersion));
+                    }
+                    let is_compressed = match reply.compression() {
+                        proto::CompressionType::None => false,
+                        proto::CompressionType::Zstd if self.is_compression_supported => true,
+                        proto::CompressionType::Zstd => {
+                            return Err(RpcHandshakeError::Rejected(HandshakeRejectReason::Unknown(
+                                "server selected compression that was not offered",
+                            )));
+                        },
+                    };
+                    session = NegotiatedSession { version, is_compressed };
+                },
+                Ok(Some(Err(err))) => {
+                    error!(target: LOG_TARGET, "Error during handshake: {}", err);
+                    return Err(err.into());
+                },
+                Ok(None) => {
+                    error!(target: LOG_TARGET, "Error during handshake, server closed connection");
+                    return Err(RpcHandshakeError::ServerClosedRequest);
+                },
+                Err(_) => {
+                    error!(target: LOG_TARGET, "Error during handshake, timed out");
+                    return Err(RpcHandshakeError::TimedOut);
+                },
+            }
+        }
     }
 
     async fn recv_next_frame(&mut self) -> Result<Option<Result<BytesMut, io::Error>>, time::error::Elapsed> {
@@ -153,3 +257,136 @@ where T: AsyncRead + AsyncWrite + Unpin
         }
     }
 }
+
+/// Returns true if the frame is the server's reply to the ping sent after the client handshake.
+fn is_ping_reply(frame: &Bytes) -> bool {
+    // A session reply never decodes as an OK ACK response for request 0: accepted replies have a non-zero version in
+    // field 1 (request_id) and rejections set field 2 (status).
+    proto::RpcResponse::decode(frame.clone())
+        .ok()
+        .filter(|resp| resp.request_id == 0 && resp.status == 0)
+        .and_then(|resp| resp.flags().ok())
+        .map_or(false, |flags| flags.is_ack())
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::DuplexStream;
+    use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+    use super::*;
+    use crate::{framing, RPC_MAX_FRAME_SIZE};
+
+    type TestFraming = CanonicalFraming<Compat<DuplexStream>>;
+
+    fn create_framed_pair() -> (TestFraming, TestFraming) {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        (
+            framing::canonical(client.compat(), RPC_MAX_FRAME_SIZE),
+            framing::canonical(server.compat(), RPC_MAX_FRAME_SIZE),
+        )
+    }
+
+    /// Reads the ping that follows the client handshake and replies to it as the server session would
+    async fn reply_to_ping(framed: &mut TestFraming) {
+        let frame = framed.next().await.unwrap().unwrap();
+        let ping = proto::RpcRequest::decode(frame.freeze()).unwrap();
+        assert!(ping.flags().unwrap().is_ack());
+        let reply = proto::RpcResponse {
+            request_id: ping.request_id,
+            status: 0,
+            flags: RpcMessageFlags::ACK.bits().into(),
+            ..Default::default()
+        };
+        framed.send(reply.encode_to_vec().into()).await.unwrap();
+    }
+
+    async fn negotiate(client_compression: bool, server_compression: bool) -> (NegotiatedSession, NegotiatedSession) {
+        let (mut client, mut server) = create_framed_pair();
+        let server_task = async {
+            let session = Handshake::new(&mut server)
+                .with_compression(server_compression)
+                .perform_server_handshake()
+                .await
+                .unwrap();
+            reply_to_ping(&mut server).await;
+            session
+        };
+        let client_task = async {
+            Handshake::new(&mut client)
+                .with_timeout(Duration::from_secs(5))
+                .with_compression(client_compression)
+                .perform_client_handshake()
+                .await
+                .unwrap()
+        };
+        let (client_session, server_session) = tokio::join!(client_task, server_task);
+        (client_session, server_session)
+    }
+
+    #[tokio::test]
+    async fn it_negotiates_a_multiplexed_session() {
+        let (client_session, server_session) = negotiate(false, false).await;
+        let expected = NegotiatedSession {
+            version: RPC_VERSION_MULTIPLEXED,
+            is_compressed: false,
+        };
+        assert_eq!(client_session, expected);
+        assert_eq!(server_session, expected);
+    }
+
+    #[tokio::test]
+    async fn it_negotiates_compression_only_if_both_sides_support_it() {
+        let (client_session, server_session) = negotiate(true, true).await;
+        assert!(client_session.is_compressed);
+        assert!(server_session.is_compressed);
+
+        let (client_session, server_session) = negotiate(true, false).await;
+        assert!(!client_session.is_compressed);
+        assert!(!server_session.is_compressed);
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_v0_if_the_server_does_not_reply_to_the_handshake() {
+        let (mut client, mut server) = create_framed_pair();
+        // A v0 server accepts the session without replying and then replies to the ping
+        let server_task = async {
+            let frame = server.next().await.unwrap().unwrap();
+            let session = proto::RpcSession::decode(frame.freeze()).unwrap();
+            assert!(session.supported_versions.contains(&0));
+            reply_to_ping(&mut server).await;
+        };
+        let client_task = async {
+            Handshake::new(&mut client)
+                .with_timeout(Duration::from_secs(5))
+                .perform_client_handshake()
+                .await
+                .unwrap()
+        };
+        let (client_session, _) = tokio::join!(client_task, server_task);
+        assert_eq!(client_session, NegotiatedSession::uncompressed(0));
+    }
+
+    #[tokio::test]
+    async fn it_accepts_a_v0_client_without_replying() {
+        let (mut client, mut server) = create_framed_pair();
+        let client_session = Handshake::new(&mut client)
+            .with_supported_versions(&[0])
+            .with_compression(true)
+            .perform_client_handshake()
+            .await
+            .unwrap();
+        assert_eq!(client_session, NegotiatedSession::uncompressed(0));
+
+        let server_session = Handshake::new(&mut server)
+            .with_compression(true)
+            .perform_server_handshake()
+            .await
+            .unwrap();
+        assert_eq!(server_session, NegotiatedSession::uncompressed(0));
+
+        // The v0 client does not send a ping and the server does not send a reply
+        drop(server);
+        assert!(client.next().await.is_none());
+    }
+}
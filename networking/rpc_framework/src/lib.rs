@@ -53,11 +53,15 @@ const fn max_response_payload_size() -> usize {
     max_response_size() - MAX_HEADER_SIZE
 }
 
+/// The number of response messages that a server may send for a streaming request on a multiplexed (v1) session before
+/// the client must grant more credits using a WINDOW_UPDATE message.
+const MULTIPLEXED_STREAM_WINDOW: u32 = 16;
+
 mod body;
 pub use body::{Body, ClientStreaming, IntoBody, Streaming};
 
 mod server;
-pub use server::{NamedProtocolService, RpcServer, RpcServerBuilder, RpcServerError, RpcServerHandle};
+pub use server::{NamedProtocolService, RpcQuota, RpcServer, RpcServerBuilder, RpcServerError, RpcServerHandle};
 
 mod client;
 pub use client::{
@@ -77,7 +81,10 @@ mod error;
 pub use error::RpcError;
 
 mod handshake;
-pub use handshake::{Handshake, RpcHandshakeError};
+pub use handshake::{Handshake, NegotiatedSession, RpcHandshakeError};
+
+mod compression;
+pub use compression::RpcCompressionConfig;
 
 mod status;
 pub use status::{RpcStatus, RpcStatusCode, RpcStatusResultExt};
@@ -104,7 +111,7 @@ pub mod __macro_reexports {
         framing::CanonicalFraming,
         message::{Request, Response},
         // pool::RpcPoolClient,
-        server::{NamedProtocolService, RpcServerError},
+        server::{NamedProtocolService, RpcQuota, RpcServerError},
         Body,
         ClientStreaming,
         IntoBody,
@@ -146,6 +146,10 @@ bitflags! {
         const FIN = 0x01;
         /// Typically sent with empty contents and used to confirm a substream is alive.
         const ACK = 0x02;
+        /// Grants the server additional stream credits for a multiplexed request (RPC v1 only).
+        const WINDOW_UPDATE = 0x04;
+        /// The payload is compressed using the algorithm negotiated for the session.
+        const COMPRESSED = 0x08;
     }
 }
 impl RpcMessageFlags {
@@ -156,6 +160,14 @@ impl RpcMessageFlags {
     pub fn is_ack(self) -> bool {
         self.contains(Self::ACK)
     }
+
+    pub fn is_window_update(self) -> bool {
+        self.contains(Self::WINDOW_UPDATE)
+    }
+
+    pub fn is_compressed(self) -> bool {
+        self.contains(Self::COMPRESSED)
+    }
 }
 
 impl Default for RpcMessageFlags {
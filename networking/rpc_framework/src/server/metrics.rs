@@ -111,3 +111,16 @@ pub fn outbound_response_bytes(peer_id: &PeerId, protocol: &StreamProtocol) -> H
 
     METER.with_label_values(&[peer_id.to_string().as_str(), protocol.as_ref()])
 }
+
+pub fn throttled_requests_counter(peer_id: &PeerId, protocol: &StreamProtocol) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::rpc::server::throttled_request_count",
+            "The number of requests rejected because a quota was exceeded per peer per protocol",
+            &["peer_id", "protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[peer_id.to_string().as_str(), protocol.as_ref()])
+}
@@ -34,6 +34,10 @@ mod metrics;
 // pub mod mock;
 
 mod early_close;
+mod multiplexed;
+mod quota;
+use quota::QuotaLimiter;
+pub use quota::RpcQuota;
 mod router;
 
 use std::{
@@ -50,7 +54,7 @@ use std::{
 };
 
 use bytes::Bytes;
-use futures::{future, stream::FuturesUnordered, SinkExt, Stream, StreamExt};
+use futures::{future, stream::FuturesUnordered, AsyncRead, AsyncWrite, SinkExt, Stream, StreamExt};
 use libp2p::{PeerId, StreamProtocol};
 use libp2p_substream::{ProtocolEvent, ProtocolNotification};
 use log::*;
@@ -74,18 +78,24 @@ use super::{
 use crate::{
     body::BodyBytes,
     bounded_executor::BoundedExecutor,
+    compression::PayloadCompressor,
     framing,
     framing::CanonicalFraming,
+    handshake::RPC_VERSION_MULTIPLEXED,
     message::{RpcMethod, RpcResponse},
     notify::ProtocolNotificationRx,
     proto,
     server::early_close::EarlyClose,
+    RpcCompressionConfig,
 };
 
 const LOG_TARGET: &str = "comms::rpc::server";
 
 pub trait NamedProtocolService {
     const PROTOCOL_NAME: &'static str;
+    /// Request quotas for individual methods, keyed by method number. These are enforced per peer in addition to the
+    /// server's per-peer quota.
+    const METHOD_QUOTAS: &'static [(u32, RpcQuota)] = &[];
 
     /// Default implementation that returns a pointer to the static protocol name.
     fn as_protocol_name(&self) -> &'static str {
@@ -95,6 +105,7 @@ pub trait NamedProtocolService {
 
 pub struct RpcServer {
     builder: RpcServerBuilder,
+    method_quotas: HashMap<StreamProtocol, HashMap<u32, RpcQuota>>,
     request_tx: mpsc::Sender<RpcServerRequest>,
     request_rx: mpsc::Receiver<RpcServerRequest>,
 }
@@ -128,6 +139,16 @@ impl RpcServer {
         RpcServerHandle::new(self.request_tx.clone())
     }
 
+    pub(super) fn register_method_quotas(&mut self, protocol: StreamProtocol, quotas: &[(u32, RpcQuota)]) {
+        if quotas.is_empty() {
+            return;
+        }
+        self.method_quotas
+            .entry(protocol)
+            .or_default()
+            .extend(quotas.iter().copied());
+    }
+
     pub(super) async fn serve<S>(
         self,
         service: S,
@@ -147,7 +168,8 @@ impl RpcServer {
         S::Service: Send + 'static,
         <S::Service as Service<Request<Bytes>>>::Future: Send + 'static,
     {
-        PeerRpcServer::new(self.builder, service, notifications, self.request_rx)
+        let quotas = QuotaLimiter::new(self.builder.peer_quota, self.method_quotas);
+        PeerRpcServer::new(self.builder, service, notifications, self.request_rx, quotas)
             .serve()
             .await
     }
@@ -163,8 +185,11 @@ impl Default for RpcServer {
 pub struct RpcServerBuilder {
     maximum_simultaneous_sessions: Option<usize>,
     maximum_sessions_per_client: Option<usize>,
+    maximum_concurrent_requests_per_session: usize,
     minimum_client_deadline: Duration,
     handshake_timeout: Duration,
+    compression: Option<RpcCompressionConfig>,
+    peer_quota: Option<RpcQuota>,
 }
 
 impl RpcServerBuilder {
@@ -192,6 +217,27 @@ impl RpcServerBuilder {
         self
     }
 
+    /// Sets the maximum number of requests that a client may have in flight on a single multiplexed (v1) session.
+    /// Requests exceeding this limit are rejected.
+    pub fn with_maximum_concurrent_requests_per_session(mut self, limit: usize) -> Self {
+        self.maximum_concurrent_requests_per_session = limit;
+        self
+    }
+
+    /// Sets the payload compression accepted in the handshake, or None to disable compression. Compression is only
+    /// used on multiplexed (v1) sessions.
+    pub fn with_compression(mut self, compression: Option<RpcCompressionConfig>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the request quota for each peer across all sessions and protocols, or None for no limit. Requests that
+    /// exceed the quota are rejected with a QuotaExceeded status.
+    pub fn with_peer_quota(mut self, quota: Option<RpcQuota>) -> Self {
+        self.peer_quota = quota;
+        self
+    }
+
     pub fn with_minimum_client_deadline(mut self, deadline: Duration) -> Self {
         self.minimum_client_deadline = deadline;
         self
@@ -201,6 +247,7 @@ impl RpcServerBuilder {
         let (request_tx, request_rx) = mpsc::channel(10);
         RpcServer {
             builder: self,
+            method_quotas: HashMap::new(),
             request_tx,
             request_rx,
         }
@@ -212,8 +259,11 @@ impl Default for RpcServerBuilder {
         Self {
             maximum_simultaneous_sessions: None,
             maximum_sessions_per_client: None,
+            maximum_concurrent_requests_per_session: 32,
             minimum_client_deadline: Duration::from_secs(1),
             handshake_timeout: Duration::from_secs(15),
+            compression: Some(RpcCompressionConfig::default()),
+            peer_quota: None,
         }
     }
 }
@@ -226,6 +276,7 @@ pub(super) struct PeerRpcServer<TSvc> {
     request_rx: mpsc::Receiver<RpcServerRequest>,
     sessions: HashMap<PeerId, usize>,
     tasks: FuturesUnordered<JoinHandle<PeerId>>,
+    quotas: Arc<QuotaLimiter>,
 }
 
 impl<TSvc> PeerRpcServer<TSvc>
@@ -247,6 +298,7 @@ where
         service: TSvc,
         protocol_notifications: ProtocolNotificationRx<Substream>,
         request_rx: mpsc::Receiver<RpcServerRequest>,
+        quotas: QuotaLimiter,
     ) -> Self {
         Self {
             executor: match config.maximum_simultaneous_sessions {
@@ -259,6 +311,7 @@ where
             request_rx,
             sessions: HashMap::new(),
             tasks: FuturesUnordered::new(),
+            quotas: Arc::new(quotas),
         }
     }
 
@@ -381,7 +434,9 @@ where
         peer_id: PeerId,
         mut framed: CanonicalFraming<Substream>,
     ) -> Result<(), RpcServerError> {
-        let mut handshake = Handshake::new(&mut framed).with_timeout(self.config.handshake_timeout);
+        let mut handshake = Handshake::new(&mut framed)
+            .with_timeout(self.config.handshake_timeout)
+            .with_compression(self.config.compression.is_some());
 
         if !self.executor.can_spawn() {
             debug!(
@@ -428,13 +483,27 @@ where
             },
         }
 
-        let version = handshake.perform_server_handshake().await?;
+        let session = handshake.perform_server_handshake().await?;
         debug!(
             target: LOG_TARGET,
-            "Server negotiated RPC v{} with client node `{}`", version, peer_id
+            "Server negotiated RPC v{} (compressed: {}) with client node `{}`", session.version, session.is_compressed, peer_id
         );
 
-        let service = ActivePeerRpcService::new(self.config.clone(), protocol, peer_id, service, framed);
+        let compressor = self
+            .config
+            .compression
+            .filter(|_| session.is_compressed)
+            .map(PayloadCompressor::new);
+        let service = ActivePeerRpcService::new(
+            self.config.clone(),
+            protocol,
+            peer_id,
+            service,
+            framed,
+            session.version,
+            compressor,
+            self.quotas.clone(),
+        );
 
         let handle = self
             .executor
@@ -458,24 +527,33 @@ where
     }
 }
 
-struct ActivePeerRpcService<TSvc> {
+struct ActivePeerRpcService<TSvc, TSubstream = Substream> {
     config: RpcServerBuilder,
     protocol: StreamProtocol,
     peer_id: PeerId,
     service: TSvc,
-    framed: EarlyClose<CanonicalFraming<Substream>>,
+    framed: EarlyClose<CanonicalFraming<TSubstream>>,
+    version: u32,
+    compressor: Option<PayloadCompressor>,
+    quotas: Arc<QuotaLimiter>,
     logging_context_string: Arc<String>,
 }
 
-impl<TSvc> ActivePeerRpcService<TSvc>
-where TSvc: Service<Request<Bytes>, Response = Response<Body>, Error = RpcStatus>
+impl<TSvc, TSubstream> ActivePeerRpcService<TSvc, TSubstream>
+where
+    TSvc: Service<Request<Bytes>, Response = Response<Body>, Error = RpcStatus>,
+    TSvc::Future: Send + 'static,
+    TSubstream: AsyncRead + AsyncWrite + Unpin,
 {
     pub(self) fn new(
         config: RpcServerBuilder,
         protocol: StreamProtocol,
         node_id: PeerId,
         service: TSvc,
-        framed: CanonicalFraming<Substream>,
+        framed: CanonicalFraming<TSubstream>,
+        version: u32,
+        compressor: Option<PayloadCompressor>,
+        quotas: Arc<QuotaLimiter>,
     ) -> Self {
         Self {
             logging_context_string: Arc::new(format!("peer: {}, protocol: {}", node_id, protocol)),
@@ -485,15 +563,40 @@ where TSvc: Service<Request<Bytes>, Response = Response<Body>, Error = RpcStatus
             peer_id: node_id,
             service,
             framed: EarlyClose::new(framed),
+            version,
+            compressor,
+            quotas,
         }
     }
 
+    fn check_quota(&self, method: RpcMethod) -> Result<(), RpcStatus> {
+        self.quotas
+            .check(&self.peer_id, &self.protocol, method.id())
+            .map_err(|status| {
+                debug!(
+                    target: LOG_TARGET,
+                    "({}) Throttled request for method {}: {}",
+                    self.logging_context_string,
+                    method.id(),
+                    status
+                );
+                #[cfg(feature = "metrics")]
+                metrics::throttled_requests_counter(&self.peer_id, &self.protocol).inc();
+                status
+            })
+    }
+
     async fn start(mut self) {
         debug!(
             target: LOG_TARGET,
-            "({}) Rpc server started.", self.logging_context_string,
+            "({}) Rpc server started (v{}).", self.logging_context_string, self.version,
         );
-        if let Err(err) = self.run().await {
+        let result = if self.version >= RPC_VERSION_MULTIPLEXED {
+            self.run_multiplexed().await
+        } else {
+            self.run().await
+        };
+        if let Err(err) = result {
             #[cfg(feature = "metrics")]
             metrics::error_counter(&self.peer_id, &self.protocol, &err).inc();
             let level = match &err {
@@ -634,6 +737,17 @@ where TSvc: Service<Request<Bytes>, Response = Response<Body>, Error = RpcStatus
             method.id()
         );
 
+        if let Err(status) = self.check_quota(method) {
+            let resp = proto::RpcResponse {
+                request_id,
+                status: status.as_code(),
+                flags: RpcMessageFlags::FIN.bits().into(),
+                payload: status.to_details_bytes(),
+            };
+            self.framed.send(resp.encode_to_vec().into()).await?;
+            return Ok(());
+        }
+
         let req = Request::new(method, decoded_msg.payload.into());
 
         let service_call = log_timing(
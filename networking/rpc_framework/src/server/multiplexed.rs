@@ -0,0 +1,492 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Server side of a multiplexed (v1) RPC session.
+//!
+//! Each request is handled in its own task so that a slow request or a long-running stream does not hold up other
+//! requests on the session. Response frames from all tasks are written to the substream as they become available. A
+//! FIN from the client cancels the matching request and streaming responses are only sent while the client has granted
+//! credits for them.
+
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use futures::{stream::FuturesUnordered, AsyncRead, AsyncWrite, SinkExt, StreamExt};
+use log::*;
+use prost::Message;
+use tokio::{
+    sync::{mpsc, Semaphore},
+    task::{AbortHandle, JoinHandle},
+    time,
+};
+use tower::Service;
+
+use super::{into_response, log_timing, ActivePeerRpcService, LOG_TARGET};
+use crate::{
+    body::Body,
+    compression::PayloadCompressor,
+    max_response_payload_size,
+    message::{Request, Response, RpcMessageFlags, RpcMethod},
+    proto,
+    RpcServerError,
+    RpcStatus,
+    MULTIPLEXED_STREAM_WINDOW,
+};
+
+struct InFlightRequest {
+    abort_handle: AbortHandle,
+    window: Arc<Semaphore>,
+}
+
+impl<TSvc, TSubstream> ActivePeerRpcService<TSvc, TSubstream>
+where
+    TSvc: Service<Request<Bytes>, Response = Response<Body>, Error = RpcStatus>,
+    TSvc::Future: Send + 'static,
+    TSubstream: AsyncRead + AsyncWrite + Unpin,
+{
+    pub(super) async fn run_multiplexed(&mut self) -> Result<(), RpcServerError> {
+        let (frames_tx, mut frames_rx) = mpsc::channel(self.config.maximum_concurrent_requests_per_session.max(1));
+        let mut in_flight = HashMap::new();
+        let mut tasks = FuturesUnordered::<JoinHandle<u32>>::new();
+
+        let result = loop {
+            tokio::select! {
+                Some(frame) = frames_rx.recv() => {
+                    #[cfg(feature = "metrics")]
+                    super::metrics::outbound_response_bytes(&self.peer_id, &self.protocol).observe(frame.len() as f64);
+                    if let Err(err) = self.framed.send(frame).await {
+                        break Err(err.into());
+                    }
+                },
+
+                Some(result) = tasks.next() => {
+                    // Cancelled tasks have already been removed
+                    if let Ok(request_id) = result {
+                        in_flight.remove(&request_id);
+                    }
+                },
+
+                msg = self.framed.next() => {
+                    match msg {
+                        Some(Ok(frame)) => {
+                            #[cfg(feature = "metrics")]
+                            super::metrics::inbound_requests_bytes(&self.peer_id, &self.protocol).observe(frame.len() as f64);
+                            if let Err(err) = self.handle_multiplexed_frame(frame.freeze(), &mut in_flight, &mut tasks, &frames_tx).await {
+                                break Err(err);
+                            }
+                        },
+                        Some(Err(err)) => break Err(err.into()),
+                        None => break Ok(()),
+                    }
+                },
+            }
+        };
+
+        for (_, request) in in_flight.drain() {
+            request.abort_handle.abort();
+        }
+
+        if let Err(err) = self.framed.close().await {
+            let level = err.io().map(super::err_to_log_level).unwrap_or(log::Level::Error);
+            log!(
+                target: LOG_TARGET,
+                level,
+                "({}) Failed to close substream: {}",
+                self.logging_context_string,
+                err,
+            );
+        }
+        result
+    }
+
+    async fn handle_multiplexed_frame(
+        &mut self,
+        mut frame: Bytes,
+        in_flight: &mut HashMap<u32, InFlightRequest>,
+        tasks: &mut FuturesUnordered<JoinHandle<u32>>,
+        frames_tx: &mpsc::Sender<Bytes>,
+    ) -> Result<(), RpcServerError> {
+        let decoded_msg = proto::RpcRequest::decode(&mut frame)?;
+        let request_id = decoded_msg.request_id;
+        let msg_flags = decoded_msg.flags().map_err(RpcServerError::ProtocolError)?;
+
+        if msg_flags.is_fin() {
+            if let Some(request) = in_flight.remove(&request_id) {
+                debug!(
+                    target: LOG_TARGET,
+                    "({}) Client cancelled request {}.", self.logging_context_string, request_id
+                );
+                request.abort_handle.abort();
+            }
+            return Ok(());
+        }
+        if msg_flags.is_window_update() {
+            if let Some(request) = in_flight.get(&request_id) {
+                let increment = usize::try_from(decoded_msg.window_increment)
+                    .map_err(|_| RpcServerError::ProtocolError("window increment overflows usize".to_string()))?;
+                // A client never has more than the window in credits. Clamping also prevents add_permits from
+                // panicking when a client sends a large increment.
+                let max_increment =
+                    (MULTIPLEXED_STREAM_WINDOW as usize).saturating_sub(request.window.available_permits());
+                request.window.add_permits(increment.min(max_increment));
+            }
+            return Ok(());
+        }
+        if msg_flags.is_ack() {
+            let ack = proto::RpcResponse {
+                request_id,
+                status: RpcStatus::ok().as_code(),
+                flags: RpcMessageFlags::ACK.bits().into(),
+                ..Default::default()
+            };
+            self.framed.send(ack.encode_to_vec().into()).await?;
+            return Ok(());
+        }
+
+        let deadline = decoded_msg.deadline();
+        // The client side deadline MUST be greater or equal to the minimum_client_deadline
+        if deadline < self.config.minimum_client_deadline {
+            debug!(
+                target: LOG_TARGET,
+                "({}) Client has an invalid deadline. {}", self.logging_context_string, decoded_msg
+            );
+            let status = RpcStatus::bad_request(format!(
+                "Invalid deadline ({:.0?}). The deadline MUST be greater than {:.0?}.",
+                deadline, self.config.minimum_client_deadline,
+            ));
+            return self.send_status(request_id, status).await;
+        }
+        if in_flight.contains_key(&request_id) {
+            let status = RpcStatus::bad_request(format!("Request {} is already in flight", request_id));
+            return self.send_status(request_id, status).await;
+        }
+        if in_flight.len() >= self.config.maximum_concurrent_requests_per_session {
+            let status = RpcStatus::general(format!(
+                "Maximum number of concurrent requests ({}) reached",
+                self.config.maximum_concurrent_requests_per_session
+            ));
+            return self.send_status(request_id, status).await;
+        }
+
+        let method = RpcMethod::from(decoded_msg.method);
+        debug!(
+            target: LOG_TARGET,
+            "({}) Request: {}, Method: {}",
+            self.logging_context_string,
+            decoded_msg,
+            method.id()
+        );
+
+        if let Err(status) = self.check_quota(method) {
+            return self.send_status(request_id, status).await;
+        }
+
+        let mut payload = decoded_msg.payload;
+        if msg_flags.is_compressed() {
+            let decompressed = match &self.compressor {
+                Some(compressor) => compressor
+                    .decompress(&payload)
+                    .map_err(|err| RpcStatus::bad_request(format!("Failed to decompress payload: {}", err))),
+                None => Err(RpcStatus::bad_request(
+                    "Received a compressed payload on an uncompressed session",
+                )),
+            };
+            match decompressed {
+                Ok(decompressed) => payload = decompressed,
+                Err(status) => return self.send_status(request_id, status).await,
+            }
+        }
+
+        let req = Request::new(method, payload.into());
+        let service_call = self.service.call(req);
+        let window = Arc::new(Semaphore::new(MULTIPLEXED_STREAM_WINDOW as usize));
+        let handle = tokio::spawn(handle_multiplexed_request(
+            self.logging_context_string.clone(),
+            request_id,
+            deadline,
+            service_call,
+            window.clone(),
+            self.compressor,
+            frames_tx.clone(),
+        ));
+        in_flight.insert(request_id, InFlightRequest {
+            abort_handle: handle.abort_handle(),
+            window,
+        });
+        tasks.push(handle);
+
+        Ok(())
+    }
+
+    async fn send_status(&mut self, request_id: u32, status: RpcStatus) -> Result<(), RpcServerError> {
+        let resp = proto::RpcResponse {
+            request_id,
+            status: status.as_code(),
+            flags: RpcMessageFlags::FIN.bits().into(),
+            payload: status.to_details_bytes(),
+        };
+        #[cfg(feature = "metrics")]
+        super::metrics::status_error_counter(&self.peer_id, &self.protocol, status.as_status_code()).inc();
+        self.framed.send(resp.encode_to_vec().into()).await?;
+        Ok(())
+    }
+}
+
+/// Calls the service and sends the response frames for a single request. Returns the request ID once the response is
+/// complete.
+async fn handle_multiplexed_request<F>(
+    logging_context_string: Arc<String>,
+    request_id: u32,
+    deadline: Duration,
+    service_call: F,
+    window: Arc<Semaphore>,
+    compressor: Option<PayloadCompressor>,
+    frames_tx: mpsc::Sender<Bytes>,
+) -> u32
+where
+    F: Future<Output = Result<Response<Body>, RpcStatus>>,
+{
+    let service_call = log_timing(logging_context_string.clone(), request_id, "service call", service_call);
+    let body = match time::timeout(deadline, service_call).await {
+        Ok(Ok(body)) => body,
+        Ok(Err(err)) => {
+            debug!(
+                target: LOG_TARGET,
+                "{} Service returned an error: {}", logging_context_string, err
+            );
+            let resp = proto::RpcResponse {
+                request_id,
+                status: err.as_code(),
+                flags: RpcMessageFlags::FIN.bits().into(),
+                payload: err.to_details_bytes(),
+            };
+            let _result = frames_tx.send(resp.encode_to_vec().into()).await;
+            return request_id;
+        },
+        Err(_) => {
+            warn!(
+                target: LOG_TARGET,
+                "{} RPC service was not able to complete within the deadline ({:.0?}). Request aborted",
+                logging_context_string,
+                deadline,
+            );
+            return request_id;
+        },
+    };
+
+    let mut stream = body
+        .into_message()
+        .map(|result| into_response(request_id, result))
+        .map(move |message| {
+            let mut resp = message.to_proto();
+            if compressor.as_ref().map_or(false, |c| c.compress(&mut resp.payload)) {
+                resp.flags |= u32::from(RpcMessageFlags::COMPRESSED.bits());
+            }
+            // The size limit applies to the payload sent over the wire, so large payloads that compress well (e.g.
+            // template binaries) are allowed
+            if resp.payload.len() > max_response_payload_size() {
+                resp = message.exceeded_message_size().to_proto();
+            }
+            resp
+        })
+        .map(|resp| Bytes::from(resp.encode_to_vec()));
+
+    loop {
+        // Wait until the client has granted credit for another message. Waiting on a slow client does not count
+        // towards the deadline.
+        let Ok(permit) = window.acquire().await else {
+            break;
+        };
+        permit.forget();
+
+        let next_item = log_timing(
+            logging_context_string.clone(),
+            request_id,
+            "message read",
+            stream.next(),
+        );
+        match time::timeout(deadline, next_item).await {
+            Ok(Some(msg)) => {
+                if frames_tx.send(msg).await.is_err() {
+                    // The session has ended
+                    break;
+                }
+            },
+            Ok(None) => {
+                debug!(target: LOG_TARGET, "{} Request {} complete", logging_context_string, request_id);
+                break;
+            },
+            Err(_) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "({}) Failed to return result within client deadline ({:.0?})", logging_context_string, deadline
+                );
+                break;
+            },
+        }
+    }
+
+    request_id
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, future, sync::Arc, time::Duration};
+
+    use bytes::Bytes;
+    use futures::{stream, SinkExt, StreamExt};
+    use libp2p::{PeerId, StreamProtocol};
+    use prost::Message;
+    use tokio::{io::DuplexStream, time};
+    use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+    use tower::service_fn;
+
+    use crate::{
+        body::Body,
+        framing,
+        framing::CanonicalFraming,
+        handshake::RPC_VERSION_MULTIPLEXED,
+        message::{Request, Response, RpcMessageFlags},
+        proto,
+        server::{ActivePeerRpcService, QuotaLimiter, RpcServerBuilder},
+        RpcStatus,
+        MULTIPLEXED_STREAM_WINDOW,
+        RPC_MAX_FRAME_SIZE,
+    };
+
+    const SLOW_METHOD: u32 = 1;
+    const FAST_METHOD: u32 = 2;
+    const ENDLESS_STREAM_METHOD: u32 = 3;
+    const PENDING_METHOD: u32 = 4;
+
+    type TestFraming = CanonicalFraming<Compat<DuplexStream>>;
+
+    /// Starts a multiplexed server session and returns the client end of the substream
+    fn start_session(config: RpcServerBuilder) -> TestFraming {
+        let (client, server) = tokio::io::duplex(1024 * 1024);
+        let service = service_fn(|req: Request<Bytes>| async move {
+            let body = match req.method().id() {
+                SLOW_METHOD => {
+                    time::sleep(Duration::from_millis(200)).await;
+                    Body::single("slow")
+                },
+                FAST_METHOD => Body::single("fast"),
+                ENDLESS_STREAM_METHOD => Body::streaming(stream::repeat(Ok(Bytes::from_static(b"item")))),
+                PENDING_METHOD => future::pending().await,
+                _ => return Err(RpcStatus::not_implemented("unknown method")),
+            };
+            Ok::<_, RpcStatus>(Response::new(body))
+        });
+        let mut session = ActivePeerRpcService::new(
+            config,
+            StreamProtocol::new("/test/rpc/1.0"),
+            PeerId::random(),
+            service,
+            framing::canonical(server.compat(), RPC_MAX_FRAME_SIZE),
+            RPC_VERSION_MULTIPLEXED,
+            None,
+            Arc::new(QuotaLimiter::new(None, HashMap::new())),
+        );
+        tokio::spawn(async move { session.run_multiplexed().await });
+        framing::canonical(client.compat(), RPC_MAX_FRAME_SIZE)
+    }
+
+    async fn send_request(framed: &mut TestFraming, request_id: u32, method: u32) {
+        let req = proto::RpcRequest {
+            request_id,
+            method,
+            deadline: 10,
+            ..Default::default()
+        };
+        framed.send(req.encode_to_vec().into()).await.unwrap();
+    }
+
+    async fn send_control(framed: &mut TestFraming, request_id: u32, flags: RpcMessageFlags, window_increment: u32) {
+        let req = proto::RpcRequest {
+            request_id,
+            flags: flags.bits().into(),
+            deadline: 10,
+            window_increment,
+            ..Default::default()
+        };
+        framed.send(req.encode_to_vec().into()).await.unwrap();
+    }
+
+    async fn next_response(framed: &mut TestFraming) -> proto::RpcResponse {
+        let frame = time::timeout(Duration::from_secs(5), framed.next())
+            .await
+            .expect("timed out waiting for a response")
+            .expect("session closed")
+            .unwrap();
+        proto::RpcResponse::decode(frame.freeze()).unwrap()
+    }
+
+    /// Counts the responses received until none arrive for a short time
+    async fn count_responses_until_idle(framed: &mut TestFraming) -> u32 {
+        let mut count = 0;
+        while let Ok(Some(frame)) = time::timeout(Duration::from_millis(200), framed.next()).await {
+            frame.unwrap();
+            count += 1;
+        }
+        count
+    }
+
+    #[tokio::test]
+    async fn it_sends_responses_as_they_complete() {
+        let mut framed = start_session(RpcServerBuilder::default());
+        send_request(&mut framed, 1, SLOW_METHOD).await;
+        send_request(&mut framed, 2, FAST_METHOD).await;
+
+        let resp = next_response(&mut framed).await;
+        assert_eq!(resp.request_id, 2);
+        assert_eq!(resp.payload, b"fast");
+        assert!(resp.flags().unwrap().is_fin());
+
+        let resp = next_response(&mut framed).await;
+        assert_eq!(resp.request_id, 1);
+        assert_eq!(resp.payload, b"slow");
+        assert!(resp.flags().unwrap().is_fin());
+    }
+
+    #[tokio::test]
+    async fn it_only_streams_responses_that_the_client_has_credited() {
+        let mut framed = start_session(RpcServerBuilder::default());
+        send_request(&mut framed, 1, ENDLESS_STREAM_METHOD).await;
+        assert_eq!(count_responses_until_idle(&mut framed).await, MULTIPLEXED_STREAM_WINDOW);
+
+        send_control(&mut framed, 1, RpcMessageFlags::WINDOW_UPDATE, 4).await;
+        assert_eq!(count_responses_until_idle(&mut framed).await, 4);
+
+        // Credits are clamped to the window
+        send_control(&mut framed, 1, RpcMessageFlags::WINDOW_UPDATE, u32::MAX).await;
+        assert_eq!(count_responses_until_idle(&mut framed).await, MULTIPLEXED_STREAM_WINDOW);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_requests_over_the_concurrent_request_limit() {
+        let mut framed = start_session(RpcServerBuilder::default().with_maximum_concurrent_requests_per_session(2));
+        send_request(&mut framed, 1, PENDING_METHOD).await;
+        send_request(&mut framed, 2, PENDING_METHOD).await;
+        send_request(&mut framed, 3, FAST_METHOD).await;
+
+        let resp = next_response(&mut framed).await;
+        assert_eq!(resp.request_id, 3);
+        assert_ne!(resp.status, RpcStatus::ok().as_code());
+        assert!(resp.flags().unwrap().is_fin());
+        assert!(String::from_utf8_lossy(&resp.payload).contains("Maximum number of concurrent requests (2) reached"));
+    }
+
+    #[tokio::test]
+    async fn it_cancels_a_request_when_the_client_sends_fin() {
+        let mut framed = start_session(RpcServerBuilder::default().with_maximum_concurrent_requests_per_session(1));
+        send_request(&mut framed, 1, PENDING_METHOD).await;
+        send_control(&mut framed, 1, RpcMessageFlags::FIN, 0).await;
+
+        // The cancelled request no longer counts towards the limit
+        send_request(&mut framed, 2, FAST_METHOD).await;
+        let resp = next_response(&mut framed).await;
+        assert_eq!(resp.request_id, 2);
+        assert_eq!(resp.status, RpcStatus::ok().as_code());
+        assert_eq!(resp.payload, b"fast");
+    }
+}
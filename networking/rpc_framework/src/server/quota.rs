@@ -0,0 +1,251 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use libp2p::{PeerId, StreamProtocol};
+
+use crate::RpcStatus;
+
+/// How often buckets that have refilled completely are removed
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A token bucket quota. A client may make `burst` requests at once, after which requests are allowed at
+/// `rate_per_sec` requests per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcQuota {
+    pub rate_per_sec: u32,
+    pub burst: u32,
+}
+
+impl RpcQuota {
+    pub const fn new(rate_per_sec: u32, burst: u32) -> Self {
+        Self { rate_per_sec, burst }
+    }
+
+    /// The time taken to refill an empty bucket
+    fn refill_period(&self) -> Duration {
+        if self.rate_per_sec == 0 {
+            return Duration::MAX;
+        }
+        Duration::from_secs_f64(f64::from(self.burst) / f64::from(self.rate_per_sec))
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(quota: &RpcQuota, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(quota.burst),
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, quota: &RpcQuota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * f64::from(quota.rate_per_sec)).min(f64::from(quota.burst));
+        self.last_refill = now;
+    }
+
+    fn has_token(&self) -> bool {
+        self.tokens >= 1.0
+    }
+
+    /// Returns the time until the next token is available
+    fn retry_after(&self, quota: &RpcQuota) -> Duration {
+        if quota.rate_per_sec == 0 {
+            return Duration::MAX;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / f64::from(quota.rate_per_sec))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum BucketKey {
+    Peer(PeerId),
+    Method(PeerId, StreamProtocol, u32),
+}
+
+#[derive(Debug)]
+struct Buckets {
+    buckets: HashMap<BucketKey, TokenBucket>,
+    last_pruned: Instant,
+}
+
+/// Enforces per-peer and per-method request quotas. Buckets are shared by all sessions for a peer so that opening more
+/// sessions does not increase a peer's quota.
+#[derive(Debug)]
+pub(crate) struct QuotaLimiter {
+    peer_quota: Option<RpcQuota>,
+    method_quotas: HashMap<StreamProtocol, HashMap<u32, RpcQuota>>,
+    buckets: Mutex<Buckets>,
+}
+
+impl QuotaLimiter {
+    pub fn new(peer_quota: Option<RpcQuota>, method_quotas: HashMap<StreamProtocol, HashMap<u32, RpcQuota>>) -> Self {
+        Self {
+            peer_quota,
+            method_quotas,
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                last_pruned: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token from the peer and method buckets for a request, or returns a QuotaExceeded status if either bucket
+    /// is empty. No tokens are taken if the request is rejected.
+    pub fn check(&self, peer_id: &PeerId, protocol: &StreamProtocol, method: u32) -> Result<(), RpcStatus> {
+        self.check_at(peer_id, protocol, method, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        peer_id: &PeerId,
+        protocol: &StreamProtocol,
+        method: u32,
+        now: Instant,
+    ) -> Result<(), RpcStatus> {
+        let method_quota = self
+            .method_quotas
+            .get(protocol)
+            .and_then(|quotas| quotas.get(&method))
+            .copied();
+        let quotas = [
+            self.peer_quota.map(|quota| (BucketKey::Peer(*peer_id), quota)),
+            method_quota.map(|quota| (BucketKey::Method(*peer_id, protocol.clone(), method), quota)),
+        ];
+        if quotas.iter().all(Option::is_none) {
+            return Ok(());
+        }
+
+        let mut state = self.buckets.lock().expect("quota lock poisoned");
+        if now.saturating_duration_since(state.last_pruned) >= PRUNE_INTERVAL {
+            self.prune(&mut state.buckets, now);
+            state.last_pruned = now;
+        }
+
+        let buckets = &mut state.buckets;
+        for (key, quota) in quotas.iter().flatten() {
+            let bucket = buckets
+                .entry(key.clone())
+                .or_insert_with(|| TokenBucket::full(quota, now));
+            bucket.refill(quota, now);
+            if !bucket.has_token() {
+                let what = match key {
+                    BucketKey::Peer(_) => "Request quota".to_string(),
+                    BucketKey::Method(_, _, method) => format!("Request quota for method {}", method),
+                };
+                return Err(RpcStatus::quota_exceeded(format!(
+                    "{} exceeded. Retry after {:.0?}",
+                    what,
+                    bucket.retry_after(quota)
+                )));
+            }
+        }
+
+        for (key, _) in quotas.iter().flatten() {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes buckets that have refilled completely, as they are indistinguishable from new buckets. This bounds the
+    /// number of buckets to those of peers that made requests within the last refill period.
+    fn prune(&self, buckets: &mut HashMap<BucketKey, TokenBucket>, now: Instant) {
+        buckets.retain(|key, bucket| {
+            let refill_period = match key {
+                BucketKey::Peer(_) => self.peer_quota.map(|q| q.refill_period()),
+                BucketKey::Method(_, protocol, method) => self
+                    .method_quotas
+                    .get(protocol)
+                    .and_then(|quotas| quotas.get(method))
+                    .map(|q| q.refill_period()),
+            };
+            refill_period.map_or(false, |period| {
+                now.saturating_duration_since(bucket.last_refill) < period
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::RpcStatusCode;
+
+    fn protocol() -> StreamProtocol {
+        StreamProtocol::new("/test/1")
+    }
+
+    #[test]
+    fn it_limits_requests_per_peer() {
+        let limiter = QuotaLimiter::new(Some(RpcQuota::new(1, 2)), HashMap::new());
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+        limiter.check_at(&peer_id, &protocol(), 1, now).unwrap();
+        limiter.check_at(&peer_id, &protocol(), 2, now).unwrap();
+        let status = limiter.check_at(&peer_id, &protocol(), 1, now).unwrap_err();
+        assert_eq!(status.as_status_code(), RpcStatusCode::QuotaExceeded);
+
+        // Other peers are unaffected
+        limiter.check_at(&PeerId::random(), &protocol(), 1, now).unwrap();
+
+        // One token is refilled after a second
+        let now = now + Duration::from_secs(1);
+        limiter.check_at(&peer_id, &protocol(), 1, now).unwrap();
+        limiter.check_at(&peer_id, &protocol(), 1, now).unwrap_err();
+    }
+
+    #[test]
+    fn it_limits_requests_per_method() {
+        let method_quotas = HashMap::from([(protocol(), HashMap::from([(1, RpcQuota::new(1, 1))]))]);
+        let limiter = QuotaLimiter::new(Some(RpcQuota::new(10, 10)), method_quotas);
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+        limiter.check_at(&peer_id, &protocol(), 1, now).unwrap();
+        limiter.check_at(&peer_id, &protocol(), 1, now).unwrap_err();
+        // Methods without a quota are only limited by the peer quota
+        for _ in 0..8 {
+            limiter.check_at(&peer_id, &protocol(), 2, now).unwrap();
+        }
+        // The rejected request did not use a peer token
+        limiter.check_at(&peer_id, &protocol(), 2, now).unwrap();
+        limiter.check_at(&peer_id, &protocol(), 2, now).unwrap_err();
+    }
+
+    #[test]
+    fn it_prunes_refilled_buckets() {
+        let limiter = QuotaLimiter::new(Some(RpcQuota::new(1, 100)), HashMap::new());
+        let active_peer = PeerId::random();
+        let start = Instant::now();
+        for _ in 0..10 {
+            limiter.check_at(&PeerId::random(), &protocol(), 1, start).unwrap();
+        }
+        limiter
+            .check_at(&active_peer, &protocol(), 1, start + Duration::from_secs(50))
+            .unwrap();
+        assert_eq!(limiter.buckets.lock().unwrap().buckets.len(), 11);
+
+        // The buckets of the inactive peers have refilled after the refill period (100s) and are removed on the next
+        // check, regardless of whether the peers still have open sessions
+        limiter
+            .check_at(&active_peer, &protocol(), 1, start + Duration::from_secs(100))
+            .unwrap();
+        let state = limiter.buckets.lock().unwrap();
+        assert_eq!(state.buckets.len(), 1);
+        assert!(state.buckets.contains_key(&BucketKey::Peer(active_peer)));
+    }
+}
@@ -57,9 +57,10 @@ impl<A> Router<A, ProtocolServiceNotFound>
 where A: NamedProtocolService
 {
     /// Create a new Router
-    pub fn new(server: RpcServer, service: A) -> Self {
+    pub fn new(mut server: RpcServer, service: A) -> Self {
         let expected_protocol = StreamProtocol::new(<A as NamedProtocolService>::PROTOCOL_NAME);
         let protocols = vec![expected_protocol.clone()];
+        server.register_method_quotas(expected_protocol.clone(), A::METHOD_QUOTAS);
         let predicate = move |protocol: &StreamProtocol| expected_protocol == *protocol;
         Self {
             protocol_names: protocols,
@@ -75,6 +76,8 @@ impl<A, B> Router<A, B> {
     where T: NamedProtocolService {
         let expected_protocol = StreamProtocol::new(<T as NamedProtocolService>::PROTOCOL_NAME);
         self.protocol_names.push(expected_protocol.clone());
+        self.server
+            .register_method_quotas(expected_protocol.clone(), T::METHOD_QUOTAS);
         let predicate = move |protocol: &StreamProtocol| expected_protocol == *protocol;
         Router {
             protocol_names: self.protocol_names,
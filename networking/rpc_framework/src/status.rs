@@ -87,6 +87,14 @@ impl RpcStatus {
         }
     }
 
+    /// Returns a status indicating that the client has exceeded a request quota and should back off
+    pub fn quota_exceeded<T: Into<String>>(details: T) -> Self {
+        Self {
+            code: RpcStatusCode::QuotaExceeded,
+            details: details.into(),
+        }
+    }
+
     /// Returns a closure that logs the given error and returns a generic general error that does not leak any
     /// potentially sensitive error information. Use this function with map_err to catch "miscellaneous" errors.
     pub fn log_internal_error<'a, E: std::error::Error + 'a>(target: &'a str) -> impl Fn(E) -> Self + 'a {
@@ -126,6 +134,10 @@ impl RpcStatus {
     pub fn is_not_found(&self) -> bool {
         self.code.is_not_found()
     }
+
+    pub fn is_quota_exceeded(&self) -> bool {
+        self.code.is_quota_exceeded()
+    }
 }
 
 impl Display for RpcStatus {
@@ -222,6 +234,8 @@ pub enum RpcStatusCode {
     Conflict = 10,
     /// RPC handshake denied
     HandshakeDenied = 11,
+    /// The client has exceeded a request quota
+    QuotaExceeded = 12,
     // The following status represents anything that is not recognised (i.e not one of the above codes).
     /// Unrecognised RPC status code
     InvalidRpcStatusCode,
@@ -244,6 +258,10 @@ impl RpcStatusCode {
         self == Self::HandshakeDenied
     }
 
+    pub fn is_quota_exceeded(self) -> bool {
+        self == Self::QuotaExceeded
+    }
+
     pub fn as_u32(&self) -> u32 {
         *self as u32
     }
@@ -270,6 +288,7 @@ impl From<u32> for RpcStatusCode {
             9 => Forbidden,
             10 => Conflict,
             11 => HandshakeDenied,
+            12 => QuotaExceeded,
             _ => InvalidRpcStatusCode,
         }
     }
@@ -295,6 +314,7 @@ mod test {
         assert_eq!(RpcStatusCode::from(ProtocolError as u32), ProtocolError);
         assert_eq!(RpcStatusCode::from(Forbidden as u32), Forbidden);
         assert_eq!(RpcStatusCode::from(Conflict as u32), Conflict);
+        assert_eq!(RpcStatusCode::from(QuotaExceeded as u32), QuotaExceeded);
         assert_eq!(RpcStatusCode::from(123), InvalidRpcStatusCode);
     }
 
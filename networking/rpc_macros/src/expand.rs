@@ -67,6 +67,8 @@ impl TraitInfoCollector {
             is_server_streaming: false,
             request_type: None,
             return_type: None,
+            rate_limit: None,
+            burst: None,
         };
 
         self.parse_attr(node, &mut info)?;
@@ -110,6 +112,12 @@ impl TraitInfoCollector {
                                             ));
                                         }
                                     },
+                                    "rate_limit" => {
+                                        info.rate_limit = Some(extract_u32(ident, &name_value.lit)?);
+                                    },
+                                    "burst" => {
+                                        info.burst = Some(extract_u32(ident, &name_value.lit)?);
+                                    },
                                     s => {
                                         return Err(syn_error!(
                                             name_value,
@@ -143,6 +151,14 @@ impl TraitInfoCollector {
             },
         }
 
+        if info.burst.is_some() && info.rate_limit.is_none() {
+            return Err(syn_error!(
+                node,
+                "`burst` requires `rate_limit` in `#[rpc(...)]` attribute for method `{}`",
+                info.method_ident,
+            ));
+        }
+
         Ok(())
     }
 
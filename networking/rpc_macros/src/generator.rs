@@ -79,6 +79,17 @@ impl RpcCodeGenerator {
             })
             .collect::<TokenStream>();
 
+        let method_quotas = self
+            .rpc_methods
+            .iter()
+            .filter_map(|m| {
+                let method_num = m.method_num;
+                let rate_limit = m.rate_limit?;
+                let burst = m.burst.unwrap_or(rate_limit);
+                Some(quote!((#method_num, #dep_mod::RpcQuota::new(#rate_limit, #burst)),))
+            })
+            .collect::<TokenStream>();
+
         let service_method_select_body = quote! {
             match req.method().id() {
                 #match_branches
@@ -129,6 +140,7 @@ impl RpcCodeGenerator {
 
             impl<T> #dep_mod::NamedProtocolService for #server_struct<T> {
                 const PROTOCOL_NAME: &'static str = #protocol_name;
+                const METHOD_QUOTAS: &'static [(u32, #dep_mod::RpcQuota)] = &[#method_quotas];
             }
 
             /// A service maker for #server_struct
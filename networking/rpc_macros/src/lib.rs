@@ -26,7 +26,8 @@ mod options;
 ///     async fn say_hello(&self, request: Request<String>) -> Result<Response<String>, RpcStatus>;
 ///     #[rpc(method = 2)]
 ///     async fn return_error(&self, request: Request<()>) -> Result<Response<()>, RpcStatus>;
-///     #[rpc(method = 3)]
+///     // Each peer may call this method 10 times per second with bursts of up to 20 calls
+///     #[rpc(method = 3, rate_limit = 10, burst = 20)]
 ///     async fn get_greetings(&self, request: Request<u32>) -> Result<Streaming<String>, RpcStatus>;
 /// }
 ///
@@ -27,4 +27,8 @@ pub struct RpcMethodInfo {
     pub is_server_streaming: bool,
     pub request_type: Option<syn::Type>,
     pub return_type: Option<syn::Type>,
+    /// Requests per second allowed for each peer
+    pub rate_limit: Option<u32>,
+    /// The number of requests a peer may make at once before the rate limit applies
+    pub burst: Option<u32>,
 }
@@ -7,5 +7,5 @@ extern crate diesel;
 extern crate diesel_migrations;
 
 mod sqlite_message_log;
-pub use sqlite_message_log::{LoggedMessage, SqliteMessageLogger};
+pub use sqlite_message_log::{LoggedMessage, MessageLogError, SqliteMessageLogger};
 mod schema;
@@ -18,7 +18,16 @@ use crate::schema::*;
 
 const LOG_TARGET: &str = "tari::comms::logging::sqlite_message_log";
 
-// Note: this struct does not produce errors because it is for logging. Logs will be output on errors
+// Note: this struct does not produce errors when logging messages. Logs will be output on errors. Reading messages
+// back returns errors so that a failure is not mistaken for an empty log.
+
+#[derive(Debug, thiserror::Error)]
+pub enum MessageLogError {
+    #[error("Message log database is not connected")]
+    NotConnected,
+    #[error("Message log database error: {0}")]
+    Database(#[from] diesel::result::Error),
+}
 
 #[derive(Debug, Insertable)]
 #[diesel(table_name = outbound_messages)]
@@ -209,6 +218,44 @@ impl SqliteMessageLogger {
         }
     }
 
+    /// Returns all inbound messages in the order that they were received
+    pub fn get_inbound_messages(&self) -> Result<Vec<LoggedMessage>, MessageLogError> {
+        self.load_messages(
+            r#"
+            SELECT
+                "Inbound" as in_out,
+                id,
+                from_pubkey as pubkey,
+                message_type,
+                message_json,
+                received_at as timestamp
+            FROM inbound_messages
+            ORDER BY id ASC"#,
+        )
+    }
+
+    /// Returns all outbound messages in the order that they were sent
+    pub fn get_outbound_messages(&self) -> Result<Vec<LoggedMessage>, MessageLogError> {
+        self.load_messages(
+            r#"
+            SELECT
+                "Outbound" as in_out,
+                id,
+                destination_pubkey as pubkey,
+                message_type,
+                message_json,
+                sent_at as timestamp
+            FROM outbound_messages
+            ORDER BY id ASC"#,
+        )
+    }
+
+    fn load_messages(&self, query: &str) -> Result<Vec<LoggedMessage>, MessageLogError> {
+        let mut conn = self.connect().ok_or(MessageLogError::NotConnected)?;
+        let messages = sql_query(query).load::<LoggedMessage>(&mut *conn)?;
+        Ok(messages)
+    }
+
     fn connect(&self) -> Option<MutexGuard<SqliteConnection>> {
         Some(self.connection.as_ref()?.lock().unwrap())
     }
@@ -125,7 +125,10 @@ where
             let messaging = if config.enable_messaging {
                 Some(messaging::Behaviour::new(
                     StreamProtocol::try_from_owned(config.messaging_protocol)?,
-                    messaging::Config::default(),
+                    messaging::Config {
+                        compression: config.messaging_compression,
+                        ..Default::default()
+                    },
                 ))
             } else {
                 None
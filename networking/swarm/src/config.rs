@@ -4,6 +4,7 @@
 use std::{num::NonZeroU32, time::Duration};
 
 use libp2p::ping;
+use libp2p_messaging as messaging;
 
 use crate::protocol_version::ProtocolVersion;
 
@@ -12,6 +13,7 @@ pub struct Config {
     pub protocol_version: ProtocolVersion,
     pub user_agent: String,
     pub messaging_protocol: String,
+    pub messaging_compression: Option<messaging::Compression>,
     pub ping: ping::Config,
     pub max_connections_per_peer: Option<u32>,
     pub enable_mdns: bool,
@@ -30,6 +32,7 @@ impl Default for Config {
             protocol_version: "/tari/localnet/0.0.1".parse().unwrap(),
             user_agent: "/tari/unknown/0.0.1".to_string(),
             messaging_protocol: "/tari/messaging/0.0.1".to_string(),
+            messaging_compression: Some(messaging::Compression::default()),
             ping: ping::Config::default(),
             max_connections_per_peer: Some(3),
             enable_mdns: false,
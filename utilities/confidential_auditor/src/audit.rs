@@ -0,0 +1,378 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::ops::RangeInclusive;
+
+use anyhow::anyhow;
+use serde::Serialize;
+use tari_crypto::ristretto::RistrettoSecretKey;
+use tari_dan_common_types::substate_type::SubstateType;
+use tari_engine_types::{
+    confidential::{ElgamalVerifiableBalance, ValueLookupTable},
+    resource::Resource,
+    substate::SubstateId,
+    vault::Vault,
+};
+use tari_indexer_client::{
+    json_rpc_client::IndexerJsonRpcClient,
+    types::{GetSubstateRequest, ListSubstatesRequest},
+};
+use tari_template_lib::models::{Amount, ResourceAddress, VaultId};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultAudit {
+    pub vault_id: VaultId,
+    pub version: u32,
+    pub num_commitments: usize,
+    /// The sum of all confidential outputs in the vault, or None if any output could not be decrypted
+    pub confidential_balance: Option<u64>,
+    pub revealed_balance: Amount,
+    /// The number of outputs that could not be decrypted with the view key in the given value range
+    pub num_undecrypted: usize,
+}
+
+impl VaultAudit {
+    pub fn is_fully_decrypted(&self) -> bool {
+        self.num_undecrypted == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum SupplyMismatch {
+    /// The sum of revealed funds in all vaults does not match the total supply recorded by the engine. The engine only
+    /// tracks revealed amounts in the resource's total supply.
+    RevealedSupply { recorded: Amount, audited: Amount },
+    /// The decrypted circulating supply does not match the supply the issuer expects to have minted
+    CirculatingSupply { expected: u64, audited: u64 },
+    /// Some vaults could not be fully decrypted so the circulating supply is a lower bound
+    UndecryptedVaults { vault_ids: Vec<VaultId> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub resource_address: ResourceAddress,
+    pub recorded_total_supply: Amount,
+    pub vaults: Vec<VaultAudit>,
+    pub total_confidential: u64,
+    pub total_revealed: Amount,
+    /// Total confidential and revealed funds held in all audited vaults
+    pub circulating_supply: u64,
+    pub mismatches: Vec<SupplyMismatch>,
+}
+
+impl AuditReport {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+pub struct ConfidentialAuditor<TLookup> {
+    client: IndexerJsonRpcClient,
+    view_secret_key: RistrettoSecretKey,
+    value_range: RangeInclusive<u64>,
+    value_lookup: TLookup,
+    page_size: u64,
+}
+
+impl<TLookup> ConfidentialAuditor<TLookup>
+where
+    TLookup: ValueLookupTable,
+    TLookup::Error: std::error::Error + Send + Sync + 'static,
+{
+    pub fn new(
+        client: IndexerJsonRpcClient,
+        view_secret_key: RistrettoSecretKey,
+        value_range: RangeInclusive<u64>,
+        value_lookup: TLookup,
+        page_size: u64,
+    ) -> Self {
+        Self {
+            client,
+            view_secret_key,
+            value_range,
+            value_lookup,
+            page_size,
+        }
+    }
+
+    pub async fn audit(
+        &mut self,
+        resource_address: ResourceAddress,
+        expected_supply: Option<u64>,
+    ) -> Result<AuditReport, anyhow::Error> {
+        let resource = self.fetch_resource(resource_address).await?;
+        if resource.view_key().is_none() {
+            return Err(anyhow!(
+                "Resource {resource_address} was not created with a view key and cannot be audited"
+            ));
+        }
+
+        let vaults = self.fetch_vaults_for_resource(&resource_address).await?;
+        self.build_report(resource_address, resource.total_supply(), vaults, expected_supply)
+    }
+
+    /// Decrypts the given vaults and compares the audited supply against the recorded and expected supply
+    fn build_report(
+        &mut self,
+        resource_address: ResourceAddress,
+        recorded_total_supply: Amount,
+        vaults: Vec<(VaultId, u32, Vault)>,
+        expected_supply: Option<u64>,
+    ) -> Result<AuditReport, anyhow::Error> {
+        let mut audits = Vec::with_capacity(vaults.len());
+        for (vault_id, version, vault) in vaults {
+            audits.push(self.audit_vault(vault_id, version, &vault)?);
+        }
+
+        let total_confidential = audits.iter().filter_map(|v| v.confidential_balance).sum::<u64>();
+        let total_revealed = audits.iter().map(|v| v.revealed_balance).sum::<Amount>();
+        let circulating_supply = total_revealed
+            .as_u64_checked()
+            .and_then(|revealed| revealed.checked_add(total_confidential))
+            .ok_or_else(|| anyhow!("Circulating supply overflowed"))?;
+
+        let mut mismatches = vec![];
+        let undecrypted = audits
+            .iter()
+            .filter(|v| !v.is_fully_decrypted())
+            .map(|v| v.vault_id)
+            .collect::<Vec<_>>();
+        if !undecrypted.is_empty() {
+            mismatches.push(SupplyMismatch::UndecryptedVaults { vault_ids: undecrypted });
+        }
+        if total_revealed != recorded_total_supply {
+            mismatches.push(SupplyMismatch::RevealedSupply {
+                recorded: recorded_total_supply,
+                audited: total_revealed,
+            });
+        }
+        if let Some(expected) = expected_supply {
+            if expected != circulating_supply {
+                mismatches.push(SupplyMismatch::CirculatingSupply {
+                    expected,
+                    audited: circulating_supply,
+                });
+            }
+        }
+
+        Ok(AuditReport {
+            resource_address,
+            recorded_total_supply,
+            vaults: audits,
+            total_confidential,
+            total_revealed,
+            circulating_supply,
+            mismatches,
+        })
+    }
+
+    fn audit_vault(&mut self, vault_id: VaultId, version: u32, vault: &Vault) -> Result<VaultAudit, anyhow::Error> {
+        let commitments = vault
+            .get_confidential_commitments()
+            .ok_or_else(|| anyhow!("Vault {vault_id} does not contain a confidential resource"))?;
+
+        let balances = ElgamalVerifiableBalance::batched_brute_force(
+            &self.view_secret_key,
+            self.value_range.clone(),
+            &mut self.value_lookup,
+            commitments
+                .values()
+                .filter_map(|output| output.viewable_balance.as_ref()),
+        )?;
+
+        // Outputs without a viewable balance can never be decrypted
+        let num_undecrypted = balances.iter().filter(|b| b.is_none()).count() + commitments.len() - balances.len();
+        let confidential_balance = if num_undecrypted == 0 {
+            Some(balances.into_iter().flatten().sum())
+        } else {
+            None
+        };
+
+        Ok(VaultAudit {
+            vault_id,
+            version,
+            num_commitments: commitments.len(),
+            confidential_balance,
+            revealed_balance: vault.balance(),
+            num_undecrypted,
+        })
+    }
+
+    async fn fetch_resource(&mut self, resource_address: ResourceAddress) -> Result<Resource, anyhow::Error> {
+        let resp = self
+            .client
+            .get_substate(GetSubstateRequest {
+                address: resource_address.into(),
+                version: None,
+                local_search_only: false,
+            })
+            .await?;
+        resp.substate
+            .into_resource()
+            .ok_or_else(|| anyhow!("Substate {resource_address} is not a resource"))
+    }
+
+    /// Pages through all vaults known to the indexer and returns those that hold the given resource. Only vaults that
+    /// the indexer has indexed are audited.
+    async fn fetch_vaults_for_resource(
+        &mut self,
+        resource_address: &ResourceAddress,
+    ) -> Result<Vec<(VaultId, u32, Vault)>, anyhow::Error> {
+        let mut vaults = vec![];
+        let mut offset = 0;
+        loop {
+            let page = self
+                .client
+                .list_substates(ListSubstatesRequest {
+                    filter_by_template: None,
+                    filter_by_type: Some(SubstateType::Vault),
+                    limit: Some(self.page_size),
+                    offset: Some(offset),
+                })
+                .await?;
+            let num_items = page.substates.len() as u64;
+
+            for item in page.substates {
+                let SubstateId::Vault(vault_id) = item.substate_id else {
+                    continue;
+                };
+                let resp = self
+                    .client
+                    .get_substate(GetSubstateRequest {
+                        address: vault_id.into(),
+                        version: None,
+                        local_search_only: false,
+                    })
+                    .await?;
+                let Some(vault) = resp.substate.into_vault() else {
+                    return Err(anyhow!("Indexer returned a non-vault substate for {vault_id}"));
+                };
+                if vault.resource_address() == resource_address {
+                    vaults.push((vault_id, resp.version, vault));
+                }
+            }
+
+            if num_items < self.page_size {
+                break;
+            }
+            offset += num_items;
+        }
+
+        Ok(vaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+    use tari_crypto::{
+        keys::{PublicKey as _, SecretKey},
+        ristretto::RistrettoPublicKey,
+    };
+    use tari_dan_wallet_crypto::{
+        create_confidential_output_statement,
+        AlwaysMissLookupTable,
+        ConfidentialProofStatement,
+    };
+    use tari_engine_types::resource_container::ResourceContainer;
+    use tari_template_lib::{
+        constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+        models::{EncryptedData, ObjectKey},
+    };
+
+    use super::*;
+
+    fn create_auditor(view_secret_key: RistrettoSecretKey) -> ConfidentialAuditor<AlwaysMissLookupTable> {
+        // The client is not used when building a report from vaults that have already been fetched
+        let client = IndexerJsonRpcClient::connect("http://127.0.0.1:18300").unwrap();
+        ConfidentialAuditor::new(client, view_secret_key, 0..=1000, AlwaysMissLookupTable, 10)
+    }
+
+    fn mint(amount: Amount, revealed_amount: Amount, view_key: &RistrettoPublicKey) -> ResourceContainer {
+        let statement = ConfidentialProofStatement {
+            amount,
+            mask: RistrettoSecretKey::random(&mut OsRng),
+            sender_public_nonce: Default::default(),
+            minimum_value_promise: 0,
+            encrypted_data: EncryptedData::try_from(vec![0; EncryptedData::min_size()]).unwrap(),
+            resource_view_key: Some(view_key.clone()),
+        };
+        let proof =
+            create_confidential_output_statement(Some(&statement), revealed_amount, None, Amount::zero()).unwrap();
+        ResourceContainer::mint_confidential(CONFIDENTIAL_TARI_RESOURCE_ADDRESS, proof, Some(view_key)).unwrap()
+    }
+
+    fn vault_with(id: u8, containers: Vec<ResourceContainer>) -> (VaultId, u32, Vault) {
+        let mut containers = containers.into_iter();
+        let mut container = containers.next().unwrap();
+        for other in containers {
+            container.deposit(other).unwrap();
+        }
+        (
+            VaultId::new(ObjectKey::from_array([id; ObjectKey::LENGTH])),
+            0,
+            Vault::new(container),
+        )
+    }
+
+    #[test]
+    fn it_audits_a_known_commitment_set() {
+        let (view_secret_key, view_key) = RistrettoPublicKey::random_keypair(&mut OsRng);
+        let vaults = vec![
+            vault_with(1, vec![
+                mint(Amount(100), Amount(0), &view_key),
+                mint(Amount(250), Amount(50), &view_key),
+            ]),
+            vault_with(2, vec![mint(Amount(600), Amount(0), &view_key)]),
+        ];
+
+        let report = create_auditor(view_secret_key)
+            .build_report(CONFIDENTIAL_TARI_RESOURCE_ADDRESS, Amount(50), vaults, Some(1000))
+            .unwrap();
+
+        assert_eq!(report.vaults[0].num_commitments, 2);
+        assert_eq!(report.vaults[0].confidential_balance, Some(350));
+        assert_eq!(report.vaults[0].revealed_balance, Amount(50));
+        assert_eq!(report.vaults[1].confidential_balance, Some(600));
+        assert_eq!(report.total_confidential, 950);
+        assert_eq!(report.total_revealed, Amount(50));
+        assert_eq!(report.circulating_supply, 1000);
+        assert!(report.is_consistent(), "unexpected mismatches: {:?}", report.mismatches);
+    }
+
+    #[test]
+    fn it_reports_mismatches() {
+        let (view_secret_key, view_key) = RistrettoPublicKey::random_keypair(&mut OsRng);
+        let (_, other_view_key) = RistrettoPublicKey::random_keypair(&mut OsRng);
+        let vaults = vec![
+            vault_with(1, vec![mint(Amount(100), Amount(10), &view_key)]),
+            // Encrypted to a different view key so cannot be decrypted by the auditor
+            vault_with(2, vec![
+                mint(Amount(200), Amount(0), &view_key),
+                mint(Amount(300), Amount(0), &other_view_key),
+            ]),
+        ];
+
+        let report = create_auditor(view_secret_key)
+            .build_report(CONFIDENTIAL_TARI_RESOURCE_ADDRESS, Amount(20), vaults, Some(610))
+            .unwrap();
+
+        assert_eq!(report.vaults[1].num_undecrypted, 1);
+        assert_eq!(report.vaults[1].confidential_balance, None);
+        assert_eq!(report.circulating_supply, 110);
+        assert_eq!(report.mismatches.len(), 3);
+        assert!(matches!(
+            &report.mismatches[0],
+            SupplyMismatch::UndecryptedVaults { vault_ids } if *vault_ids == [report.vaults[1].vault_id]
+        ));
+        assert!(matches!(report.mismatches[1], SupplyMismatch::RevealedSupply {
+            recorded,
+            audited
+        } if recorded == Amount(20) && audited == Amount(10)));
+        assert!(matches!(report.mismatches[2], SupplyMismatch::CirculatingSupply {
+            expected: 610,
+            audited: 110
+        }));
+    }
+}
@@ -0,0 +1,49 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tari_template_lib::models::ResourceAddress;
+use url::Url;
+
+#[derive(Debug, Parser)]
+#[clap(author, version, about, long_about = None)]
+pub struct Cli {
+    /// The JSON-RPC URL of the indexer used to discover vaults
+    #[clap(short = 'u', long, default_value = "http://127.0.0.1:18300")]
+    pub indexer_url: Url,
+    /// The confidential resource to audit
+    #[clap(short = 'r', long)]
+    pub resource_address: ResourceAddress,
+    /// The hex-encoded view secret key for the resource. If not provided, the AUDITOR_VIEW_SECRET_KEY environment
+    /// variable is used.
+    #[clap(short = 'k', long, env = "AUDITOR_VIEW_SECRET_KEY", hide_env_values = true)]
+    pub view_secret_key: String,
+    /// Path to a value lookup file generated by generate_ristretto_value_lookup. If not provided, each value in the
+    /// range is computed on the fly, which is much slower.
+    #[clap(short = 'l', long)]
+    pub value_lookup_file: Option<PathBuf>,
+    /// The minimum value that a single output is expected to hold
+    #[clap(long, default_value = "0")]
+    pub min_value: u64,
+    /// The maximum value that a single output is expected to hold. Ignored if a value lookup file is provided.
+    #[clap(long, default_value = "10000000000")]
+    pub max_value: u64,
+    /// The total supply (confidential and revealed) that the issuer has minted. If provided, the decrypted circulating
+    /// supply is checked against it.
+    #[clap(short = 's', long)]
+    pub expected_supply: Option<u64>,
+    /// The number of substates to request from the indexer per page
+    #[clap(long, default_value = "100")]
+    pub page_size: u64,
+    /// Write the full audit report as JSON to this file
+    #[clap(short = 'o', long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn init() -> Self {
+        Self::parse()
+    }
+}
@@ -0,0 +1,124 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+mod audit;
+mod cli;
+
+use std::{fs, time::Instant};
+
+use anyhow::anyhow;
+use tari_crypto::{ristretto::RistrettoSecretKey, tari_utilities::hex::Hex};
+use tari_dan_wallet_crypto::{AlwaysMissLookupTable, IoReaderValueLookup};
+use tari_indexer_client::json_rpc_client::IndexerJsonRpcClient;
+
+use crate::{
+    audit::{AuditReport, ConfidentialAuditor, SupplyMismatch},
+    cli::Cli,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::init();
+
+    let view_secret_key =
+        RistrettoSecretKey::from_hex(&cli.view_secret_key).map_err(|e| anyhow!("Invalid view secret key: {e}"))?;
+    let client = IndexerJsonRpcClient::connect(cli.indexer_url.clone())?;
+
+    println!(
+        "Auditing resource {} using indexer at {}",
+        cli.resource_address, cli.indexer_url
+    );
+    let timer = Instant::now();
+    let report = match cli.value_lookup_file.as_ref() {
+        Some(path) => {
+            let mut file = fs::File::open(path)
+                .map_err(|e| anyhow!("Unable to load value lookup file '{}': {e}", path.display()))?;
+            let lookup = IoReaderValueLookup::load(&mut file)?;
+            let value_range = lookup.range();
+            ConfidentialAuditor::new(client, view_secret_key, value_range, lookup, cli.page_size)
+                .audit(cli.resource_address, cli.expected_supply)
+                .await?
+        },
+        None => {
+            ConfidentialAuditor::new(
+                client,
+                view_secret_key,
+                cli.min_value..=cli.max_value,
+                AlwaysMissLookupTable,
+                cli.page_size,
+            )
+            .audit(cli.resource_address, cli.expected_supply)
+            .await?
+        },
+    };
+    println!("Audit completed in {:.2?}", timer.elapsed());
+    println!();
+
+    print_report(&report);
+
+    if let Some(output_file) = cli.output_file {
+        fs::write(&output_file, serde_json::to_string_pretty(&report)?)?;
+        println!();
+        println!("Report written to {}", output_file.display());
+    }
+
+    if !report.is_consistent() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &AuditReport) {
+    println!(
+        "{:<72} {:>8} {:>20} {:>20}",
+        "Vault", "Outputs", "Confidential", "Revealed"
+    );
+    for vault in &report.vaults {
+        println!(
+            "{:<72} {:>8} {:>20} {:>20}",
+            vault.vault_id.to_string(),
+            vault.num_commitments,
+            vault
+                .confidential_balance
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| format!("<{} undecrypted>", vault.num_undecrypted)),
+            vault.revealed_balance
+        );
+    }
+    println!();
+    println!("Vaults audited:        {}", report.vaults.len());
+    println!("Total confidential:    {}", report.total_confidential);
+    println!("Total revealed:        {}", report.total_revealed);
+    println!("Circulating supply:    {}", report.circulating_supply);
+    println!(
+        "Recorded total supply: {} (revealed only)",
+        report.recorded_total_supply
+    );
+    println!();
+
+    if report.is_consistent() {
+        println!("✅ No mismatches found");
+        return;
+    }
+
+    for mismatch in &report.mismatches {
+        match mismatch {
+            SupplyMismatch::RevealedSupply { recorded, audited } => {
+                println!("❌ Revealed supply mismatch: recorded {recorded}, audited {audited}");
+            },
+            SupplyMismatch::CirculatingSupply { expected, audited } => {
+                println!("❌ Circulating supply mismatch: expected {expected}, audited {audited}");
+            },
+            SupplyMismatch::UndecryptedVaults { vault_ids } => {
+                println!(
+                    "❌ {} vault(s) could not be fully decrypted, circulating supply is a lower bound:",
+                    vault_ids.len()
+                );
+                for vault_id in vault_ids {
+                    println!("   - {vault_id}");
+                }
+            },
+        }
+    }
+}
@@ -11,7 +11,7 @@ use tari_engine_types::{component::new_component_address_from_public_key, indexe
 use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
 use tari_template_lib::{
     args,
-    constants::{XTR, XTR_FAUCET_COMPONENT_ADDRESS, XTR_FAUCET_VAULT_ADDRESS},
+    constants::{XTR, XTR_DIVISIBILITY, XTR_FAUCET_COMPONENT_ADDRESS, XTR_FAUCET_VAULT_ADDRESS},
     models::Amount,
     resource::ResourceType,
 };
@@ -55,6 +55,7 @@ impl Runner {
             XTR,
             ResourceType::Confidential,
             Some("XTR".to_string()),
+            XTR_DIVISIBILITY,
         )?;
         let account = self.sdk.accounts_api().get_account_by_address(account)?;
 
@@ -211,6 +212,7 @@ impl Runner {
                         *vault.resource_address(),
                         vault.resource_type(),
                         None,
+                        0,
                     )?;
                 }
             }
@@ -195,6 +195,7 @@ impl Runner {
                 tariswaps[0].lp_resource_address,
                 ResourceType::NonFungible,
                 Some("LP".to_string()),
+                0,
             )?;
         }
 
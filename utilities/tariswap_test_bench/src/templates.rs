@@ -7,7 +7,12 @@ use crate::cli::CommonArgs;
 
 pub async fn get_templates(cli: &CommonArgs) -> anyhow::Result<(TemplateMetadata, TemplateMetadata)> {
     let mut client = tari_validator_node_client::ValidatorNodeClient::connect(cli.validator_node_url.clone())?;
-    let GetTemplatesResponse { templates } = client.get_active_templates(GetTemplatesRequest { limit: 100 }).await?;
+    let GetTemplatesResponse { templates, .. } = client
+        .get_active_templates(GetTemplatesRequest {
+            limit: 100,
+            ..Default::default()
+        })
+        .await?;
 
     let tariswap = if let Some(template_address) = cli.faucet_template {
         templates